@@ -7,6 +7,7 @@ import android.provider.OpenableColumns
 import android.util.Log
 import android.view.View
 import android.view.ViewGroup
+import org.json.JSONArray
 import org.json.JSONObject
 import com.plugin.blinko.Blinko
 
@@ -99,14 +100,26 @@ class MainActivity : TauriActivity() {
     private fun handleShareIntent() {
         if (hasInjectedShare) return
 
-        if (intent?.action == Intent.ACTION_SEND) {
+        if (intent?.action == Intent.ACTION_SEND || intent?.action == Intent.ACTION_SEND_MULTIPLE) {
             hasInjectedShare = true
             val payload = intentToJson(intent)
-            intent.getParcelableExtra<Uri>(Intent.EXTRA_STREAM)?.let { uri ->
-                val name = getNameFromUri(uri)
-                if (name != null && name != "") {
-                    payload.put("name", name)
-                    Log.i("got name", name)
+            if (intent.action == Intent.ACTION_SEND_MULTIPLE) {
+                // Several files shared at once, e.g. a photo selection
+                val streams = JSONArray()
+                val names = JSONArray()
+                intent.getParcelableArrayListExtra<Uri>(Intent.EXTRA_STREAM)?.forEach { uri ->
+                    streams.put(uri.toString())
+                    names.put(getNameFromUri(uri) ?: "")
+                }
+                payload.put("streams", streams)
+                payload.put("names", names)
+            } else {
+                intent.getParcelableExtra<Uri>(Intent.EXTRA_STREAM)?.let { uri ->
+                    val name = getNameFromUri(uri)
+                    if (name != null && name != "") {
+                        payload.put("name", name)
+                        Log.i("got name", name)
+                    }
                 }
             }
             Log.i("triggering event", payload.toString())
@@ -144,7 +157,7 @@ class MainActivity : TauriActivity() {
         }
 
         val streamUrl = intent.extras?.get("android.intent.extra.STREAM")
-        if (streamUrl != null) {
+        if (streamUrl != null && intent.action == Intent.ACTION_SEND) {
             json.put("stream", streamUrl)
         }
         return json
@@ -0,0 +1,52 @@
+package com.blinko.app
+
+import android.annotation.SuppressLint
+import android.app.PendingIntent
+import android.content.Intent
+import android.net.Uri
+import android.os.Build
+import android.service.quicksettings.Tile
+import android.service.quicksettings.TileService
+
+/**
+ * Quick settings tile that opens Blinko on one of its shortcut actions,
+ * using the same blinko://shortcut/<action> links as the launcher shortcuts
+ */
+abstract class ShortcutTileService(private val action: String) : TileService() {
+
+    override fun onStartListening() {
+        super.onStartListening()
+        qsTile?.let { tile ->
+            tile.state = Tile.STATE_INACTIVE
+            tile.updateTile()
+        }
+    }
+
+    @SuppressLint("StartActivityAndCollapseDeprecated")
+    override fun onClick() {
+        super.onClick()
+        val intent = Intent(Intent.ACTION_VIEW).apply {
+            data = Uri.parse("blinko://shortcut/$action")
+            addFlags(Intent.FLAG_ACTIVITY_NEW_TASK or Intent.FLAG_ACTIVITY_CLEAR_TOP)
+            setClassName(packageName, "$packageName.MainActivity")
+        }
+
+        // Opening an activity from the panel has to collapse it, Android 14 only accepts a PendingIntent
+        if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.UPSIDE_DOWN_CAKE) {
+            val pendingIntent = PendingIntent.getActivity(
+                this,
+                action.hashCode(),
+                intent,
+                PendingIntent.FLAG_UPDATE_CURRENT or PendingIntent.FLAG_IMMUTABLE
+            )
+            startActivityAndCollapse(pendingIntent)
+        } else {
+            @Suppress("DEPRECATION")
+            startActivityAndCollapse(intent)
+        }
+    }
+}
+
+class QuickNoteTileService : ShortcutTileService("quick_note")
+
+class VoiceNoteTileService : ShortcutTileService("voice_recording")
@@ -0,0 +1,150 @@
+use tauri::{AppHandle, Emitter};
+use serde::{Deserialize, Serialize};
+
+use crate::desktop::{
+    delete_secret, generate_id, load_json_or_default, now_millis, read_secret, save_json, store_secret,
+    switch_offline_account,
+};
+
+const ACCOUNTS_FILE: &str = "accounts.json";
+
+/// A server profile, the token lives in the keychain under `account:<id>`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Account {
+    pub id: String,
+    pub name: String,
+    #[serde(rename = "serverUrl")]
+    pub server_url: String,
+    pub username: Option<String>,
+    #[serde(rename = "createdAt")]
+    pub created_at: u64,
+    #[serde(rename = "lastUsedAt")]
+    pub last_used_at: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct AccountsConfig {
+    #[serde(rename = "activeAccountId")]
+    pub active_account_id: Option<String>,
+    pub accounts: Vec<Account>,
+}
+
+/// Payload of the `account-switched` event, includes the token so the webview can re-authenticate
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AccountSwitchedEvent {
+    pub account: Account,
+    pub token: String,
+}
+
+fn keychain_account(id: &str) -> String {
+    format!("account:{}", id)
+}
+
+/// Load account profiles from file
+pub fn load_accounts_config(app: &AppHandle) -> AccountsConfig {
+    load_json_or_default(app, ACCOUNTS_FILE)
+}
+
+fn save_accounts_config(app: &AppHandle, config: &AccountsConfig) -> Result<(), String> {
+    save_json(app, ACCOUNTS_FILE, config)
+}
+
+/// Token stored for the active account, `None` without one
+pub fn read_active_account_token(app: &AppHandle) -> Option<String> {
+    let id = load_accounts_config(app).active_account_id?;
+    read_secret(Some(&keychain_account(&id))).ok().flatten()
+}
+
+#[tauri::command]
+pub fn list_accounts(app: AppHandle) -> AccountsConfig {
+    load_accounts_config(&app)
+}
+
+#[tauri::command]
+pub fn add_account(
+    app: AppHandle,
+    name: String,
+    server_url: String,
+    token: String,
+    username: Option<String>,
+) -> Result<Account, String> {
+    let server_url = server_url.trim().trim_end_matches('/').to_string();
+    if !server_url.starts_with("http://") && !server_url.starts_with("https://") {
+        return Err(format!("Invalid server URL: {}", server_url));
+    }
+
+    let mut config = load_accounts_config(&app);
+    let account = Account {
+        id: generate_id(),
+        name: if name.trim().is_empty() { server_url.clone() } else { name },
+        server_url,
+        username,
+        created_at: now_millis(),
+        last_used_at: None,
+    };
+
+    store_secret(Some(&keychain_account(&account.id)), &token)?;
+    config.accounts.push(account.clone());
+    save_accounts_config(&app, &config)?;
+
+    info!("👤 Added account {} ({})", account.name, account.server_url);
+    Ok(account)
+}
+
+/// Replace the stored token, e.g. after the user logs in again
+#[tauri::command]
+pub fn update_account_token(app: AppHandle, id: String, token: String) -> Result<(), String> {
+    if !load_accounts_config(&app).accounts.iter().any(|a| a.id == id) {
+        return Err(format!("Account not found: {}", id));
+    }
+    store_secret(Some(&keychain_account(&id)), &token)
+}
+
+#[tauri::command]
+pub fn switch_account(app: AppHandle, id: String) -> Result<Account, String> {
+    let mut config = load_accounts_config(&app);
+    let account = config.accounts.iter_mut()
+        .find(|a| a.id == id)
+        .ok_or_else(|| format!("Account not found: {}", id))?;
+    let token = read_secret(Some(&keychain_account(&id)))?
+        .ok_or("No token stored for this account, please sign in again")?;
+
+    account.last_used_at = Some(now_millis());
+    let account = account.clone();
+    config.active_account_id = Some(id.clone());
+    save_accounts_config(&app, &config)?;
+
+    switch_offline_account(&app, &account.id, &account.server_url, token.clone());
+
+    info!("👤 Switched to account {} ({})", account.name, account.server_url);
+    app.emit("account-switched", AccountSwitchedEvent { account: account.clone(), token })
+        .map_err(|e| format!("Failed to emit account-switched event: {}", e))?;
+    Ok(account)
+}
+
+#[tauri::command]
+pub fn remove_account(app: AppHandle, id: String) -> Result<(), String> {
+    let mut config = load_accounts_config(&app);
+    if config.active_account_id.as_deref() == Some(id.as_str()) {
+        return Err("Cannot remove the active account, switch to another one first".to_string());
+    }
+    config.accounts.retain(|a| a.id != id);
+    save_accounts_config(&app, &config)?;
+    delete_secret(Some(&keychain_account(&id)))
+}
+
+/// Reconnect the offline store to the account that was active when the app quit
+pub fn restore_active_account(app: &AppHandle) {
+    let config = load_accounts_config(app);
+    let Some(account) = config.active_account_id.as_ref()
+        .and_then(|id| config.accounts.iter().find(|a| &a.id == id))
+    else {
+        return;
+    };
+
+    match read_secret(Some(&keychain_account(&account.id))) {
+        Ok(Some(token)) => switch_offline_account(app, &account.id, &account.server_url, token),
+        Ok(None) => warn!("⚠️ No token stored for active account {}", account.name),
+        Err(e) => error!("❌ {}", e),
+    }
+}
@@ -0,0 +1,216 @@
+use tauri::{AppHandle, Manager};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+
+use chrono::{Days, Local, NaiveDate};
+
+use crate::desktop::{is_system_idle, load_json_or_default, save_json};
+
+const ACTIVITY_CONFIG_FILE: &str = "activity.json";
+const ACTIVITY_LOG_FILE: &str = "activity_log.json";
+const ACTIVITY_TICK: Duration = Duration::from_secs(15);
+// Flush the in-memory log every few ticks rather than on every one
+const TICKS_PER_SAVE: u32 = 4;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ActivityConfig {
+    /// Off by default, nothing is recorded until the user opts in
+    pub enabled: bool,
+    /// Also count time per note id reported by the editor
+    #[serde(rename = "trackNotes")]
+    pub track_notes: bool,
+}
+
+impl Default for ActivityConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            track_notes: true,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct DayActivity {
+    #[serde(rename = "focusedSeconds")]
+    pub focused_seconds: u64,
+    /// Seconds per note id
+    #[serde(default)]
+    pub notes: BTreeMap<String, u64>,
+}
+
+/// Focused time keyed by local date (YYYY-MM-DD)
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+struct ActivityLog {
+    days: BTreeMap<String, DayActivity>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum ActivityRange {
+    Today,
+    Week,
+    Month,
+    Year,
+    All,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ActivityDay {
+    pub date: String,
+    #[serde(flatten)]
+    pub activity: DayActivity,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NoteActivity {
+    #[serde(rename = "noteId")]
+    pub note_id: String,
+    pub seconds: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ActivityReport {
+    /// Days in the range that have any activity, oldest first
+    pub days: Vec<ActivityDay>,
+    #[serde(rename = "totalSeconds")]
+    pub total_seconds: u64,
+    /// Notes by time spent, most first
+    #[serde(rename = "topNotes")]
+    pub top_notes: Vec<NoteActivity>,
+}
+
+static ACTIVITY_CONFIG: LazyLock<Mutex<Option<ActivityConfig>>> = LazyLock::new(|| Mutex::new(None));
+static ACTIVITY_LOG: LazyLock<Mutex<Option<ActivityLog>>> = LazyLock::new(|| Mutex::new(None));
+static ACTIVE_NOTE: LazyLock<Mutex<Option<String>>> = LazyLock::new(|| Mutex::new(None));
+static TRACKER_STARTED: AtomicBool = AtomicBool::new(false);
+
+pub fn load_activity_config(app: &AppHandle) -> ActivityConfig {
+    ACTIVITY_CONFIG.lock().unwrap()
+        .get_or_insert_with(|| load_json_or_default(app, ACTIVITY_CONFIG_FILE))
+        .clone()
+}
+
+fn with_activity_log<T>(app: &AppHandle, f: impl FnOnce(&mut ActivityLog) -> T) -> T {
+    let mut guard = ACTIVITY_LOG.lock().unwrap();
+    f(guard.get_or_insert_with(|| load_json_or_default(app, ACTIVITY_LOG_FILE)))
+}
+
+fn save_activity_log(app: &AppHandle) {
+    let guard = ACTIVITY_LOG.lock().unwrap();
+    if let Some(ref log) = *guard {
+        if let Err(e) = save_json(app, ACTIVITY_LOG_FILE, log) {
+            error!("Failed to save activity log: {}", e);
+        }
+    }
+}
+
+fn is_main_window_focused(app: &AppHandle) -> bool {
+    app.get_webview_window("main")
+        .is_some_and(|window| window.is_focused().unwrap_or(false) && window.is_visible().unwrap_or(false))
+}
+
+fn record_tick(app: &AppHandle, config: &ActivityConfig) {
+    let today = Local::now().format("%Y-%m-%d").to_string();
+    let note = ACTIVE_NOTE.lock().unwrap().clone().filter(|_| config.track_notes);
+    with_activity_log(app, |log| {
+        let day = log.days.entry(today).or_default();
+        day.focused_seconds += ACTIVITY_TICK.as_secs();
+        if let Some(note) = note {
+            *day.notes.entry(note).or_default() += ACTIVITY_TICK.as_secs();
+        }
+    });
+}
+
+/// Count time with the main window focused, skipping idle stretches (runs for the app lifetime)
+pub fn start_activity_tracker(app: &AppHandle) {
+    if TRACKER_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let app_handle = app.clone();
+    std::thread::spawn(move || {
+        let mut unsaved_ticks = 0;
+        loop {
+            std::thread::sleep(ACTIVITY_TICK);
+            let config = load_activity_config(&app_handle);
+            if config.enabled && !is_system_idle() && is_main_window_focused(&app_handle) {
+                record_tick(&app_handle, &config);
+                unsaved_ticks += 1;
+            }
+            if unsaved_ticks >= TICKS_PER_SAVE {
+                save_activity_log(&app_handle);
+                unsaved_ticks = 0;
+            }
+        }
+    });
+}
+
+fn range_start(range: ActivityRange) -> Option<NaiveDate> {
+    let today = Local::now().date_naive();
+    let days_back = match range {
+        ActivityRange::Today => 0,
+        ActivityRange::Week => 6,
+        ActivityRange::Month => 29,
+        ActivityRange::Year => 364,
+        ActivityRange::All => return None,
+    };
+    today.checked_sub_days(Days::new(days_back))
+}
+
+#[tauri::command]
+pub fn get_activity_config(app: AppHandle) -> ActivityConfig {
+    load_activity_config(&app)
+}
+
+#[tauri::command]
+pub fn save_activity_config(app: AppHandle, config: ActivityConfig) -> Result<(), String> {
+    save_json(&app, ACTIVITY_CONFIG_FILE, &config)?;
+    *ACTIVITY_CONFIG.lock().unwrap() = Some(config);
+    Ok(())
+}
+
+/// The editor reports the note being edited, `None` when it closes
+#[tauri::command]
+pub fn set_active_note(note_id: Option<String>) {
+    *ACTIVE_NOTE.lock().unwrap() = note_id.filter(|id| !id.is_empty());
+}
+
+/// Writing statistics for the last day, week (7 days), month (30 days), year or all time
+#[tauri::command]
+pub fn get_activity_report(app: AppHandle, range: ActivityRange) -> ActivityReport {
+    let start = range_start(range).map(|date| date.format("%Y-%m-%d").to_string());
+    let days: Vec<ActivityDay> = with_activity_log(&app, |log| {
+        log.days.iter()
+            .filter(|(date, _)| start.as_ref().is_none_or(|start| *date >= start))
+            .map(|(date, activity)| ActivityDay { date: date.clone(), activity: activity.clone() })
+            .collect()
+    });
+
+    let mut per_note: HashMap<&str, u64> = HashMap::new();
+    for day in &days {
+        for (note_id, seconds) in &day.activity.notes {
+            *per_note.entry(note_id.as_str()).or_default() += seconds;
+        }
+    }
+    let mut top_notes: Vec<NoteActivity> = per_note.into_iter()
+        .map(|(note_id, seconds)| NoteActivity { note_id: note_id.to_string(), seconds })
+        .collect();
+    top_notes.sort_by(|a, b| b.seconds.cmp(&a.seconds).then_with(|| a.note_id.cmp(&b.note_id)));
+
+    ActivityReport {
+        total_seconds: days.iter().map(|day| day.activity.focused_seconds).sum(),
+        days,
+        top_notes,
+    }
+}
+
+/// Forget all recorded activity
+#[tauri::command]
+pub fn clear_activity(app: AppHandle) -> Result<(), String> {
+    *ACTIVITY_LOG.lock().unwrap() = Some(ActivityLog::default());
+    save_json(&app, ACTIVITY_LOG_FILE, &ActivityLog::default())
+}
@@ -0,0 +1,463 @@
+use tauri::{AppHandle, Emitter};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Condvar, LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use reqwest::blocking::{Client, RequestBuilder, Response};
+use reqwest::header::{HeaderValue, AUTHORIZATION, RETRY_AFTER};
+use reqwest::Method;
+
+use crate::desktop::{
+    generate_id, get_configured_server_url, get_connectivity_status, get_server_token_for_url, http_client_builder,
+    load_json_or_default, now_millis, read_active_account_token, save_json, set_offline_online, update_server_token,
+};
+
+const API_QUEUE_FILE: &str = "api_queue.json";
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(20);
+const DEFAULT_MAX_ATTEMPTS: u32 = 4;
+const BASE_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+// Requests to the Blinko server are spaced so bursts (bulk sync, many chunks) don't trip its rate limit
+const MIN_REQUEST_SPACING: Duration = Duration::from_millis(100);
+const TOKEN_REFRESH_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Failure of a request to the Blinko server
+#[derive(Debug, Clone, PartialEq)]
+pub enum ApiError {
+    /// No server has been signed in to yet
+    NotConfigured,
+    /// The server could not be reached
+    Offline(String),
+    /// Stored in the offline request queue under this id, sent when the server is reachable again
+    Queued(String),
+    /// The token was rejected and no new one arrived
+    Unauthorized,
+    /// Still rate limited after all retries, `retry_after` in seconds
+    RateLimited { retry_after: Option<u64> },
+    Http { status: u16, message: String },
+    /// The response body was not what the caller expected
+    Decode(String),
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiError::NotConfigured => write!(f, "Server credentials are not configured"),
+            // Kept as "Network error" so callers matching on the message keep working
+            ApiError::Offline(e) => write!(f, "Network error: {}", e),
+            ApiError::Queued(id) => write!(f, "Server is offline, request {} was queued", id),
+            ApiError::Unauthorized => write!(f, "Server rejected the token, please sign in again"),
+            ApiError::RateLimited { retry_after: Some(seconds) } => write!(f, "Rate limited by the server, retry in {}s", seconds),
+            ApiError::RateLimited { retry_after: None } => write!(f, "Rate limited by the server"),
+            ApiError::Http { status, message } if message.is_empty() => write!(f, "Server returned {}", status),
+            ApiError::Http { status, message } => write!(f, "Server returned {}: {}", status, message),
+            ApiError::Decode(e) => write!(f, "Invalid server response: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+impl From<ApiError> for String {
+    fn from(error: ApiError) -> Self {
+        error.to_string()
+    }
+}
+
+impl ApiError {
+    pub fn is_offline(&self) -> bool {
+        matches!(self, ApiError::Offline(_) | ApiError::Queued(_))
+    }
+}
+
+/// How often a request is attempted before giving up
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    /// Whether timeouts and 5xx answers may be retried, the server may already have applied the request
+    pub idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self { max_attempts: DEFAULT_MAX_ATTEMPTS, idempotent: true }
+    }
+}
+
+/// A request persisted while the server was unreachable
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QueuedApiRequest {
+    pub id: String,
+    pub method: String,
+    pub path: String,
+    pub body: Option<Value>,
+    #[serde(rename = "createdAt")]
+    pub created_at: u64,
+}
+
+/// JSON request to the configured Blinko server
+#[derive(Debug, Clone)]
+pub struct ApiRequest {
+    method: Method,
+    path: String,
+    body: Option<Value>,
+    timeout: Duration,
+    retry: RetryPolicy,
+    queue_when_offline: bool,
+}
+
+impl ApiRequest {
+    pub fn new(method: Method, path: &str) -> Self {
+        Self {
+            method,
+            path: path.to_string(),
+            body: None,
+            timeout: DEFAULT_TIMEOUT,
+            retry: RetryPolicy::default(),
+            queue_when_offline: false,
+        }
+    }
+
+    pub fn get(path: &str) -> Self {
+        Self::new(Method::GET, path)
+    }
+
+    pub fn post(path: &str, body: Value) -> Self {
+        Self::new(Method::POST, path).json(body)
+    }
+
+    pub fn json(mut self, body: Value) -> Self {
+        self.body = Some(body);
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Mark requests that must not be repeated once the server may have seen them, e.g. creating a note
+    pub fn idempotent(mut self, idempotent: bool) -> Self {
+        self.retry.idempotent = idempotent;
+        self
+    }
+
+    pub fn max_attempts(mut self, attempts: u32) -> Self {
+        self.retry.max_attempts = attempts.max(1);
+        self
+    }
+
+    /// Persist the request and send it later instead of failing while the server is unreachable
+    pub fn queue_when_offline(mut self) -> Self {
+        self.queue_when_offline = true;
+        self
+    }
+
+    /// Send and decode the JSON answer, an empty body decodes as `null`
+    pub fn send(&self, app: &AppHandle) -> Result<Value, ApiError> {
+        let server_url = get_configured_server_url().ok_or(ApiError::NotConfigured)?;
+        if self.queue_when_offline && !get_connectivity_status().online {
+            return Err(enqueue_request(app, self));
+        }
+
+        let url = format!("{}{}", server_url.trim_end_matches('/'), self.path);
+        let result = send_with_retry(app, self.timeout, self.retry, |client| {
+            let request = client.request(self.method.clone(), &url);
+            match self.body {
+                Some(ref body) => request.json(body),
+                None => request,
+            }
+        });
+
+        match result {
+            Ok(response) => {
+                let text = response.text().map_err(|e| ApiError::Decode(e.to_string()))?;
+                if text.trim().is_empty() {
+                    return Ok(Value::Null);
+                }
+                serde_json::from_str(&text).map_err(|e| ApiError::Decode(e.to_string()))
+            }
+            Err(ApiError::Offline(e)) if self.queue_when_offline => {
+                warn!("⚠️ {} {} failed ({}), queued for later", self.method, self.path, e);
+                Err(enqueue_request(app, self))
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+struct TokenExpiredEvent {
+    #[serde(rename = "serverUrl")]
+    server_url: String,
+}
+
+static NEXT_REQUEST_AT: LazyLock<Mutex<Instant>> = LazyLock::new(|| Mutex::new(Instant::now()));
+// Bumped whenever a new token arrives, threads waiting for a refresh watch it change
+static TOKEN_GENERATION: LazyLock<(Mutex<u64>, Condvar)> = LazyLock::new(|| (Mutex::new(0), Condvar::new()));
+static REFRESH_REQUESTED: AtomicBool = AtomicBool::new(false);
+static QUEUE_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+static QUEUE_REPLAYING: AtomicBool = AtomicBool::new(false);
+
+/// Exponential backoff with a little jitter so clients don't retry in lockstep
+pub fn retry_delay(attempt: u32) -> Duration {
+    let delay = BASE_BACKOFF.saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1))).min(MAX_BACKOFF);
+    delay + Duration::from_millis(now_millis() % 250)
+}
+
+/// Statuses worth another attempt, other client errors won't change on retry
+pub fn is_retryable_status(status: u16) -> bool {
+    matches!(status, 408 | 429 | 500..=599)
+}
+
+/// Wait for the next free request slot, shared by every subsystem talking to the server
+fn wait_for_request_slot() {
+    let wait = {
+        let mut next = NEXT_REQUEST_AT.lock().unwrap();
+        let now = Instant::now();
+        let slot = (*next).max(now);
+        *next = slot + MIN_REQUEST_SPACING;
+        slot - now
+    };
+    if !wait.is_zero() {
+        std::thread::sleep(wait);
+    }
+}
+
+/// Hold back all server requests after the server asked us to slow down
+fn defer_requests(delay: Duration) {
+    let mut next = NEXT_REQUEST_AT.lock().unwrap();
+    *next = (*next).max(Instant::now() + delay);
+}
+
+fn retry_after(response: &Response) -> Option<u64> {
+    response.headers().get(RETRY_AFTER)?.to_str().ok()?.trim().parse().ok()
+}
+
+fn error_message(response: Response) -> String {
+    let text = response.text().unwrap_or_default();
+    let message = serde_json::from_str::<Value>(&text).ok()
+        .and_then(|body| body.get("message").and_then(|m| m.as_str()).map(str::to_string))
+        .unwrap_or(text);
+    message.chars().take(200).collect()
+}
+
+/// Get a replacement token after a 401: first from the keychain in case the frontend already
+/// stored one, otherwise by asking the frontend to sign in again via `api-token-expired`
+fn refresh_server_token(app: &AppHandle, rejected: &str) -> bool {
+    let Some(server_url) = get_configured_server_url() else { return false };
+    if get_server_token_for_url(&server_url).is_some_and(|token| token != rejected) {
+        // Another request refreshed it in the meantime
+        return true;
+    }
+    if let Some(token) = read_active_account_token(app).filter(|token| token != rejected) {
+        update_server_token(&token);
+        info!("🔑 Picked up a new server token from the keychain");
+        return true;
+    }
+
+    let (lock, condvar) = &*TOKEN_GENERATION;
+    let generation = lock.lock().unwrap();
+    let started_at = *generation;
+    if !REFRESH_REQUESTED.swap(true, Ordering::SeqCst) {
+        warn!("🔑 Server token expired, asking the frontend for a new one");
+        if let Err(e) = app.emit("api-token-expired", TokenExpiredEvent { server_url }) {
+            error!("Failed to emit api-token-expired event: {}", e);
+        }
+    }
+    let (_generation, timeout) = condvar
+        .wait_timeout_while(generation, TOKEN_REFRESH_TIMEOUT, |generation| *generation == started_at)
+        .unwrap();
+    REFRESH_REQUESTED.store(false, Ordering::SeqCst);
+    !timeout.timed_out()
+}
+
+/// Send with the shared rate limit, retry/backoff and token refresh. `build` is called for every
+/// attempt since request bodies are consumed; the server token is added for server URLs.
+pub fn send_with_retry(
+    app: &AppHandle,
+    timeout: Duration,
+    policy: RetryPolicy,
+    build: impl Fn(&Client) -> RequestBuilder,
+) -> Result<Response, ApiError> {
+    let client = http_client_builder()
+        .timeout(timeout)
+        .build()
+        .map_err(|e| ApiError::Offline(format!("Failed to create HTTP client: {}", e)))?;
+
+    let mut attempt = 0;
+    let mut refreshed = false;
+    loop {
+        attempt += 1;
+        let mut request = build(&client).build().map_err(|e| ApiError::Offline(e.to_string()))?;
+        let token = get_server_token_for_url(request.url().as_str());
+        if token.is_some() {
+            wait_for_request_slot();
+        }
+        if let Some(ref token) = token {
+            if !request.headers().contains_key(AUTHORIZATION) {
+                if let Ok(value) = HeaderValue::from_str(&format!("Bearer {}", token)) {
+                    request.headers_mut().insert(AUTHORIZATION, value);
+                }
+            }
+        }
+        let path = request.url().path().to_string();
+        let can_retry = attempt < policy.max_attempts;
+
+        let error = match client.execute(request) {
+            Ok(response) if response.status().is_success() => return Ok(response),
+            Ok(response) => {
+                let status = response.status().as_u16();
+                match status {
+                    401 if token.is_some() => {
+                        // A refresh doesn't count as an attempt, but only happens once per request
+                        if !refreshed && refresh_server_token(app, token.as_deref().unwrap_or_default()) {
+                            refreshed = true;
+                            attempt -= 1;
+                            continue;
+                        }
+                        return Err(ApiError::Unauthorized);
+                    }
+                    429 => {
+                        let seconds = retry_after(&response);
+                        if token.is_some() {
+                            defer_requests(seconds.map(Duration::from_secs).unwrap_or_else(|| retry_delay(attempt)));
+                        }
+                        if !can_retry {
+                            return Err(ApiError::RateLimited { retry_after: seconds });
+                        }
+                        warn!("⏳ Rate limited on {}, attempt {}/{}", path, attempt, policy.max_attempts);
+                        std::thread::sleep(seconds.map(Duration::from_secs).unwrap_or_else(|| retry_delay(attempt)).min(MAX_BACKOFF));
+                        continue;
+                    }
+                    _ => {
+                        let error = ApiError::Http { status, message: error_message(response) };
+                        if !(policy.idempotent && is_retryable_status(status)) {
+                            return Err(error);
+                        }
+                        error
+                    }
+                }
+            }
+            Err(e) => {
+                let error = ApiError::Offline(e.to_string());
+                // A refused connection never reached the server, a timeout might have
+                if !(e.is_connect() || policy.idempotent) {
+                    return Err(error);
+                }
+                error
+            }
+        };
+
+        if !can_retry {
+            if error.is_offline() && token.is_some() {
+                set_offline_online(app, false);
+            }
+            return Err(error);
+        }
+        let delay = retry_delay(attempt);
+        warn!("⚠️ {} failed ({}), retry {}/{} in {:?}", path, error, attempt, policy.max_attempts - 1, delay);
+        std::thread::sleep(delay);
+    }
+}
+
+/// Store the request for later, the returned error tells the caller what happened
+fn enqueue_request(app: &AppHandle, request: &ApiRequest) -> ApiError {
+    let _guard = QUEUE_LOCK.lock().unwrap();
+    let mut queue: Vec<QueuedApiRequest> = load_json_or_default(app, API_QUEUE_FILE);
+    let id = generate_id();
+    queue.push(QueuedApiRequest {
+        id: id.clone(),
+        method: request.method.to_string(),
+        path: request.path.clone(),
+        body: request.body.clone(),
+        created_at: now_millis(),
+    });
+    match save_json(app, API_QUEUE_FILE, &queue) {
+        Ok(()) => ApiError::Queued(id),
+        Err(e) => ApiError::Offline(format!("server unreachable and the request could not be queued: {}", e)),
+    }
+}
+
+/// Send queued requests in order, stopping while the server stays unreachable
+fn replay_api_queue(app: &AppHandle) {
+    loop {
+        let next = {
+            let _guard = QUEUE_LOCK.lock().unwrap();
+            load_json_or_default::<Vec<QueuedApiRequest>>(app, API_QUEUE_FILE).into_iter().next()
+        };
+        let Some(queued) = next else { return };
+
+        let Ok(method) = Method::from_bytes(queued.method.as_bytes()) else {
+            error!("❌ Dropping queued request with invalid method {}", queued.method);
+            remove_queued_request(app, &queued.id);
+            continue;
+        };
+        let mut request = ApiRequest::new(method, &queued.path);
+        request.body = queued.body.clone();
+
+        match request.send(app) {
+            Ok(_) => {
+                info!("📤 Sent queued {} {}", queued.method, queued.path);
+                remove_queued_request(app, &queued.id);
+                let _ = app.emit("api-queue-sent", json!({ "id": queued.id, "path": queued.path }));
+            }
+            Err(e) if e.is_offline() => return,
+            Err(e) => {
+                error!("❌ Queued {} {} failed, dropping it: {}", queued.method, queued.path, e);
+                remove_queued_request(app, &queued.id);
+                let _ = app.emit("api-queue-failed", json!({ "id": queued.id, "path": queued.path, "error": e.to_string() }));
+            }
+        }
+    }
+}
+
+fn remove_queued_request(app: &AppHandle, id: &str) {
+    let _guard = QUEUE_LOCK.lock().unwrap();
+    let mut queue: Vec<QueuedApiRequest> = load_json_or_default(app, API_QUEUE_FILE);
+    queue.retain(|request| request.id != id);
+    if let Err(e) = save_json(app, API_QUEUE_FILE, &queue) {
+        error!("Failed to save API request queue: {}", e);
+    }
+}
+
+/// Replay the offline request queue on a background thread
+pub fn spawn_api_queue_replay(app: &AppHandle) {
+    if QUEUE_REPLAYING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    let app_handle = app.clone();
+    std::thread::spawn(move || {
+        replay_api_queue(&app_handle);
+        QUEUE_REPLAYING.store(false, Ordering::SeqCst);
+    });
+}
+
+/// Hand over a new token after `api-token-expired`, requests waiting on it continue
+#[tauri::command]
+pub fn provide_refreshed_token(token: String) -> Result<(), String> {
+    if get_configured_server_url().is_none() {
+        return Err(ApiError::NotConfigured.into());
+    }
+    update_server_token(&token);
+
+    let (lock, condvar) = &*TOKEN_GENERATION;
+    *lock.lock().unwrap() += 1;
+    condvar.notify_all();
+    info!("🔑 Server token refreshed");
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_api_queue(app: AppHandle) -> Vec<QueuedApiRequest> {
+    load_json_or_default(&app, API_QUEUE_FILE)
+}
+
+#[tauri::command]
+pub fn clear_api_queue(app: AppHandle) -> Result<(), String> {
+    let _guard = QUEUE_LOCK.lock().unwrap();
+    save_json(&app, API_QUEUE_FILE, &Vec::<QueuedApiRequest>::new())
+}
@@ -0,0 +1,154 @@
+use tauri::AppHandle;
+
+#[cfg(target_os = "macos")]
+use std::sync::OnceLock;
+
+#[cfg(target_os = "macos")]
+use objc2::rc::Retained;
+#[cfg(target_os = "macos")]
+use objc2::runtime::{AnyObject, NSObject};
+#[cfg(target_os = "macos")]
+use objc2::{class, define_class, msg_send, sel, ClassType};
+#[cfg(target_os = "macos")]
+use objc2_foundation::NSString;
+#[cfg(target_os = "macos")]
+use serde_json::json;
+
+#[cfg(target_os = "macos")]
+use crate::desktop::{open_quicknote_with_text, queue_note_change, toggle_dictation, toggle_quicknote_window};
+
+/// Four character codes of the Blinko suite in Blinko.sdef
+#[cfg(target_os = "macos")]
+const SUITE_CODE: u32 = u32::from_be_bytes(*b"Blnk");
+#[cfg(target_os = "macos")]
+const CREATE_NOTE_EVENT: u32 = u32::from_be_bytes(*b"Note");
+#[cfg(target_os = "macos")]
+const QUICK_NOTE_EVENT: u32 = u32::from_be_bytes(*b"Quik");
+#[cfg(target_os = "macos")]
+const DICTATE_EVENT: u32 = u32::from_be_bytes(*b"Dict");
+
+#[cfg(target_os = "macos")]
+const KEY_DIRECT_OBJECT: u32 = u32::from_be_bytes(*b"----");
+#[cfg(target_os = "macos")]
+const KEY_ERROR_NUMBER: u32 = u32::from_be_bytes(*b"errn");
+#[cfg(target_os = "macos")]
+const KEY_ERROR_STRING: u32 = u32::from_be_bytes(*b"errs");
+/// errAEEventFailed, shown by Script Editor together with the message
+#[cfg(target_os = "macos")]
+const ERR_EVENT_FAILED: i32 = -10000;
+
+#[cfg(target_os = "macos")]
+static SCRIPTING_APP: OnceLock<AppHandle> = OnceLock::new();
+
+#[cfg(target_os = "macos")]
+define_class!(
+    // SAFETY: NSObject has no subclassing requirements and the handler has no ivars
+    #[unsafe(super(NSObject))]
+    #[name = "BlinkoAppleEventHandler"]
+    struct AppleEventHandler;
+
+    impl AppleEventHandler {
+        #[unsafe(method(handleEvent:withReplyEvent:))]
+        fn handle_event(&self, event: &AnyObject, reply: &AnyObject) {
+            handle_apple_event(event, reply);
+        }
+    }
+);
+
+/// Run a Blinko suite command, returning the value for the script if it has one
+#[cfg(target_os = "macos")]
+fn run_script_command(app: &AppHandle, event_id: u32, text: Option<String>) -> Result<Option<String>, String> {
+    let text = text.filter(|text| !text.is_empty());
+    match event_id {
+        CREATE_NOTE_EVENT => {
+            let content = text.ok_or("Note text is required")?;
+            info!("🍎 AppleScript create note ({} chars)", content.len());
+            queue_note_change(app.clone(), "create".to_string(), json!({ "content": content, "type": 0 }))
+        }
+        QUICK_NOTE_EVENT => {
+            info!("🍎 AppleScript open quick note");
+            match text {
+                Some(text) => open_quicknote_with_text(app, &text),
+                None => toggle_quicknote_window(app.clone()),
+            }
+            .map(|_| None)
+        }
+        DICTATE_EVENT => {
+            info!("🍎 AppleScript dictate");
+            toggle_dictation(app).map(|_| None)
+        }
+        other => Err(format!("Unknown Blinko command {:08x}", other)),
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn handle_apple_event(event: &AnyObject, reply: &AnyObject) {
+    let Some(app) = SCRIPTING_APP.get() else { return };
+
+    // SAFETY: NSAppleEventManager passes NSAppleEventDescriptor instances for both arguments
+    let (event_id, text) = unsafe {
+        let event_id: u32 = msg_send![event, eventID];
+        let parameter: Option<Retained<AnyObject>> = msg_send![event, paramDescriptorForKeyword: KEY_DIRECT_OBJECT];
+        let text = match parameter {
+            Some(parameter) => {
+                let value: Option<Retained<NSString>> = msg_send![&*parameter, stringValue];
+                value.map(|value| value.to_string())
+            }
+            None => None,
+        };
+        (event_id, text)
+    };
+
+    let result = run_script_command(app, event_id, text);
+    if let Err(ref e) = result {
+        error!("❌ AppleScript command failed: {}", e);
+    }
+
+    // SAFETY: the reply is an NSAppleEventDescriptor and the descriptors are retained until the call returns
+    unsafe {
+        match result {
+            Ok(Some(value)) => {
+                let descriptor: Retained<AnyObject> =
+                    msg_send![class!(NSAppleEventDescriptor), descriptorWithString: &*NSString::from_str(&value)];
+                let _: () = msg_send![reply, setParamDescriptor: &*descriptor, forKeyword: KEY_DIRECT_OBJECT];
+            }
+            Ok(None) => {}
+            Err(e) => {
+                let number: Retained<AnyObject> = msg_send![class!(NSAppleEventDescriptor), descriptorWithInt32: ERR_EVENT_FAILED];
+                let message: Retained<AnyObject> =
+                    msg_send![class!(NSAppleEventDescriptor), descriptorWithString: &*NSString::from_str(&e)];
+                let _: () = msg_send![reply, setParamDescriptor: &*number, forKeyword: KEY_ERROR_NUMBER];
+                let _: () = msg_send![reply, setParamDescriptor: &*message, forKeyword: KEY_ERROR_STRING];
+            }
+        }
+    }
+}
+
+/// Handle the commands of Blinko.sdef, so scripts and Shortcuts' "Run AppleScript" can drive Blinko
+pub fn register_apple_event_handlers(app: &AppHandle) {
+    #[cfg(target_os = "macos")]
+    {
+        let _ = SCRIPTING_APP.set(app.clone());
+
+        // SAFETY: called on the main thread after launch, so these replace the handlers Cocoa
+        // scripting installed for the sdef; the manager does not retain the handler, it is leaked
+        unsafe {
+            let handler: Retained<AppleEventHandler> = msg_send![AppleEventHandler::class(), new];
+            let manager: Retained<AnyObject> = msg_send![class!(NSAppleEventManager), sharedAppleEventManager];
+            for event_id in [CREATE_NOTE_EVENT, QUICK_NOTE_EVENT, DICTATE_EVENT] {
+                let _: () = msg_send![
+                    &*manager,
+                    setEventHandler: &*handler,
+                    andSelector: sel!(handleEvent:withReplyEvent:),
+                    forEventClass: SUITE_CODE,
+                    andEventID: event_id
+                ];
+            }
+            std::mem::forget(handler);
+        }
+        info!("🍎 AppleScript commands registered");
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    let _ = app;
+}
@@ -0,0 +1,324 @@
+use tauri::{AppHandle, Emitter, Url};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::time::Duration;
+
+use scraper::{ElementRef, Html, Node, Selector};
+
+use crate::desktop::{escape_html, http_client_builder, import_bytes, queue_note_change, ImportedFile};
+
+const FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+const MAX_ARTICLE_IMAGES: usize = 40;
+const MAX_IMAGE_BYTES: usize = 10 * 1024 * 1024;
+/// Paragraphs shorter than this are usually captions, bylines or buttons
+const MIN_PARAGRAPH_CHARS: usize = 25;
+const USER_AGENT: &str = "Mozilla/5.0 (compatible; Blinko clipper)";
+
+// Elements that never belong to the article body
+const SKIPPED_TAGS: [&str; 14] = [
+    "script", "style", "noscript", "nav", "footer", "aside", "form", "iframe", "button", "input", "select", "svg", "template", "dialog",
+];
+const VOID_TAGS: [&str; 5] = ["img", "br", "hr", "source", "wbr"];
+// Attributes kept on the cleaned copy, everything else (classes, inline styles, handlers) is dropped
+const KEPT_ATTRIBUTES: [&str; 4] = ["href", "src", "alt", "title"];
+const POSITIVE_HINTS: [&str; 9] = ["article", "body", "content", "entry", "main", "post", "text", "blog", "story"];
+const NEGATIVE_HINTS: [&str; 16] = [
+    "comment", "footer", "nav", "sidebar", "sponsor", "advert", "share", "social", "related", "promo", "header", "menu",
+    "widget", "cookie", "newsletter", "subscribe",
+];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ArticleImage {
+    /// Address the image was downloaded from, as referenced in the markdown
+    pub url: String,
+    pub file: ImportedFile,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ClippedArticle {
+    pub url: String,
+    pub title: String,
+    pub byline: Option<String>,
+    pub excerpt: Option<String>,
+    pub markdown: String,
+    /// Local copies of the article images in the attachments area
+    pub images: Vec<ArticleImage>,
+    /// Offline id of the created note until it syncs
+    #[serde(rename = "localId")]
+    pub local_id: Option<String>,
+}
+
+/// Readable part of a page before it becomes a note
+struct ExtractedArticle {
+    title: String,
+    byline: Option<String>,
+    excerpt: Option<String>,
+    html: String,
+    image_urls: Vec<String>,
+}
+
+fn selector(css: &str) -> Selector {
+    Selector::parse(css).expect("static selector is valid")
+}
+
+fn meta_content(document: &Html, css: &str) -> Option<String> {
+    document.select(&selector(css))
+        .filter_map(|meta| meta.value().attr("content"))
+        .map(|content| content.trim().to_string())
+        .find(|content| !content.is_empty())
+}
+
+fn element_text(element: ElementRef) -> String {
+    element.text().collect::<Vec<_>>().join(" ").split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Class and id hints in the style of readability, positive for content containers
+fn class_weight(element: ElementRef) -> f64 {
+    let hints = format!("{} {}", element.value().attr("class").unwrap_or_default(), element.value().id().unwrap_or_default()).to_lowercase();
+    let mut weight = 0.0;
+    if POSITIVE_HINTS.iter().any(|hint| hints.contains(hint)) {
+        weight += 25.0;
+    }
+    if NEGATIVE_HINTS.iter().any(|hint| hints.contains(hint)) {
+        weight -= 25.0;
+    }
+    weight
+}
+
+fn is_boilerplate(element: ElementRef) -> bool {
+    let tag = element.value().name();
+    SKIPPED_TAGS.contains(&tag)
+        || element.value().attr("aria-hidden") == Some("true")
+        || element.value().attr("role").is_some_and(|role| matches!(role, "navigation" | "complementary" | "banner" | "contentinfo"))
+        || (class_weight(element) < 0.0 && !matches!(tag, "p" | "img" | "pre" | "code" | "figure"))
+}
+
+/// Share of the text that sits inside links, high for menus and link lists
+fn link_density(element: ElementRef) -> f64 {
+    let text_length = element_text(element).len();
+    if text_length == 0 {
+        return 1.0;
+    }
+    let link_length: usize = element.select(&selector("a")).map(|link| element_text(link).len()).sum();
+    link_length as f64 / text_length as f64
+}
+
+/// Pick the container whose paragraphs score highest, as readability does
+fn find_article_root(document: &Html) -> Option<ElementRef<'_>> {
+    let articles: Vec<ElementRef> = document.select(&selector("article")).collect();
+    if articles.len() == 1 && element_text(articles[0]).len() > 500 {
+        return Some(articles[0]);
+    }
+
+    let mut scores = HashMap::new();
+    for paragraph in document.select(&selector("p, pre, td, blockquote")) {
+        let text = element_text(paragraph);
+        if text.len() < MIN_PARAGRAPH_CHARS {
+            continue;
+        }
+        let score = 1.0 + text.matches(',').count() as f64 + (text.len() as f64 / 100.0).min(3.0);
+        let parent = paragraph.parent().and_then(ElementRef::wrap);
+        let grandparent = parent.and_then(|parent| parent.parent()).and_then(ElementRef::wrap);
+        for (ancestor, share) in [(parent, 1.0), (grandparent, 0.5)] {
+            if let Some(ancestor) = ancestor {
+                *scores.entry(ancestor.id()).or_insert_with(|| class_weight(ancestor)) += score * share;
+            }
+        }
+    }
+
+    scores.into_iter()
+        .filter_map(|(id, score)| {
+            let element = document.tree.get(id).and_then(ElementRef::wrap)?;
+            Some((element, score * (1.0 - link_density(element))))
+        })
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(element, _)| element)
+        .or_else(|| document.select(&selector("main, body")).next())
+}
+
+/// Serialize the article without boilerplate, attributes limited and URLs made absolute
+fn write_clean_html(element: ElementRef, base: &Url, out: &mut String, images: &mut Vec<String>) {
+    for child in element.children() {
+        match child.value() {
+            Node::Text(text) => out.push_str(&escape_html(text)),
+            Node::Element(_) => {
+                let Some(child) = ElementRef::wrap(child) else { continue };
+                if is_boilerplate(child) {
+                    continue;
+                }
+                let tag = child.value().name();
+                out.push('<');
+                out.push_str(tag);
+                for name in KEPT_ATTRIBUTES {
+                    // Lazy loaded images keep the real address in data-src
+                    let value = if name == "src" { child.value().attr("data-src").or(child.value().attr("src")) } else { child.value().attr(name) };
+                    let Some(value) = value else { continue };
+                    let value = if matches!(name, "href" | "src") {
+                        base.join(value).map(|url| url.to_string()).unwrap_or_else(|_| value.to_string())
+                    } else {
+                        value.to_string()
+                    };
+                    if tag == "img" && name == "src" && !images.contains(&value) {
+                        images.push(value.clone());
+                    }
+                    out.push_str(&format!(" {}=\"{}\"", name, escape_html(&value)));
+                }
+                out.push('>');
+                if !VOID_TAGS.contains(&tag) {
+                    write_clean_html(child, base, out, images);
+                    out.push_str(&format!("</{}>", tag));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn extract_article(html: &str, base: &Url) -> Result<ExtractedArticle, String> {
+    let document = Html::parse_document(html);
+    let root = find_article_root(&document).ok_or("The page has no readable content")?;
+
+    let mut clean = String::new();
+    let mut image_urls = Vec::new();
+    write_clean_html(root, base, &mut clean, &mut image_urls);
+    if clean.trim().is_empty() {
+        return Err("The page has no readable content".to_string());
+    }
+
+    let title = meta_content(&document, "meta[property='og:title']")
+        .or_else(|| document.select(&selector("title")).next().map(element_text))
+        .or_else(|| document.select(&selector("h1")).next().map(element_text))
+        .filter(|title| !title.is_empty())
+        .unwrap_or_else(|| base.host_str().unwrap_or("Article").to_string());
+
+    Ok(ExtractedArticle {
+        title,
+        byline: meta_content(&document, "meta[name='author']"),
+        excerpt: meta_content(&document, "meta[name='description']")
+            .or_else(|| meta_content(&document, "meta[property='og:description']")),
+        html: clean,
+        image_urls,
+    })
+}
+
+fn fetch_page(url: &Url) -> Result<String, String> {
+    let client = http_client_builder()
+        .timeout(FETCH_TIMEOUT)
+        .user_agent(USER_AGENT)
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let response = client.get(url.clone()).send()
+        .map_err(|e| format!("Failed to fetch {}: {}", url, e))?;
+    if !response.status().is_success() {
+        return Err(format!("Failed to fetch {}: HTTP {}", url, response.status()));
+    }
+    let content_type = response.headers().get("content-type").and_then(|v| v.to_str().ok()).unwrap_or_default().to_string();
+    if !content_type.is_empty() && !content_type.contains("html") {
+        return Err(format!("{} is not a web page ({})", url, content_type));
+    }
+    response.text().map_err(|e| format!("Failed to read {}: {}", url, e))
+}
+
+fn download_image(app: &AppHandle, url: &str, index: usize) -> Result<ImportedFile, String> {
+    let client = http_client_builder()
+        .timeout(FETCH_TIMEOUT)
+        .user_agent(USER_AGENT)
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let response = client.get(url).send().map_err(|e| format!("Failed to download {}: {}", url, e))?;
+    if !response.status().is_success() {
+        return Err(format!("Failed to download {}: HTTP {}", url, response.status()));
+    }
+    let content_type = response.headers().get("content-type").and_then(|v| v.to_str().ok()).unwrap_or_default();
+    let extension = match content_type.split(';').next().unwrap_or_default().trim() {
+        "image/png" => "png",
+        "image/gif" => "gif",
+        "image/webp" => "webp",
+        "image/avif" => "avif",
+        "image/svg+xml" => "svg",
+        _ => "jpg",
+    };
+    let bytes = response.bytes().map_err(|e| format!("Failed to download {}: {}", url, e))?;
+    if bytes.len() > MAX_IMAGE_BYTES {
+        return Err(format!("Skipping {} ({} bytes)", url, bytes.len()));
+    }
+
+    let stem = Url::parse(url).ok()
+        .and_then(|url| url.path_segments()?.next_back().map(String::from))
+        .and_then(|name| name.rsplit_once('.').map(|(stem, _)| stem.to_string()).or(Some(name)))
+        .filter(|stem| !stem.is_empty() && stem.len() <= 80)
+        .unwrap_or_else(|| format!("image-{}", index + 1));
+    import_bytes(app, &format!("{}.{}", stem, extension), &bytes, url)
+}
+
+fn article_note_content(url: &Url, article: &ExtractedArticle, markdown: &str) -> String {
+    let mut content = format!("# {}\n\n", article.title);
+    let source = match article.byline {
+        Some(ref byline) => format!("> Source: <{}> · {}", url, byline),
+        None => format!("> Source: <{}>", url),
+    };
+    content.push_str(&source);
+    content.push_str(&format!(" · clipped {}\n\n", chrono::Local::now().format("%Y-%m-%d")));
+    content.push_str(markdown.trim());
+    content.push('\n');
+    content
+}
+
+/// Fetch (or take the given HTML of) a page, keep the readable article as markdown,
+/// download its images and create a note with the archived copy
+pub fn clip_article(app: &AppHandle, url: &str, html: Option<String>) -> Result<ClippedArticle, String> {
+    let url = Url::parse(url.trim()).map_err(|e| format!("Invalid URL '{}': {}", url, e))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(format!("Only web pages can be clipped, not {}", url.scheme()));
+    }
+    let html = match html.filter(|html| !html.trim().is_empty()) {
+        Some(html) => html,
+        None => fetch_page(&url)?,
+    };
+    let article = extract_article(&html, &url)?;
+    let markdown = html2md::parse_html(&article.html);
+
+    let mut images = Vec::new();
+    for (index, image_url) in article.image_urls.iter().take(MAX_ARTICLE_IMAGES).enumerate() {
+        match download_image(app, image_url, index) {
+            Ok(file) => images.push(ArticleImage { url: image_url.clone(), file }),
+            Err(e) => warn!("⚠️ {}", e),
+        }
+    }
+
+    let content = article_note_content(&url, &article, &markdown);
+    let local_id = queue_note_change(app.clone(), "create".to_string(), json!({ "content": content, "type": 1 }))?;
+    info!("📰 Clipped \"{}\" with {} image(s)", article.title, images.len());
+
+    let clipped = ClippedArticle {
+        url: url.to_string(),
+        title: article.title,
+        byline: article.byline,
+        excerpt: article.excerpt,
+        markdown,
+        images,
+        local_id,
+    };
+    let _ = app.emit("article-clipped", &clipped);
+    Ok(clipped)
+}
+
+/// Clip in the background for callers that can't wait, failures are emitted as `article-clip-failed`
+pub fn spawn_clip_article(app: &AppHandle, url: String, html: Option<String>) {
+    let app_handle = app.clone();
+    std::thread::spawn(move || {
+        if let Err(e) = clip_article(&app_handle, &url, html) {
+            error!("❌ Clipping {} failed: {}", url, e);
+            let _ = app_handle.emit("article-clip-failed", json!({ "url": url, "error": e }));
+        }
+    });
+}
+
+/// Save a web page as a read-later note with its text and images
+#[tauri::command]
+pub async fn clip_url(app: AppHandle, url: String) -> Result<ClippedArticle, String> {
+    tauri::async_runtime::spawn_blocking(move || clip_article(&app, &url, None))
+        .await
+        .map_err(|e| format!("Clipping failed: {}", e))?
+}
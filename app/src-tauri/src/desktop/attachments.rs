@@ -0,0 +1,190 @@
+use tauri::{AppHandle, DragDropEvent, Emitter, Manager, WindowEvent};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use crate::desktop::{get_app_data_subdir, now_millis, prepare_image_for_upload, ImageProcessingOverrides};
+
+pub const ATTACHMENTS_DIR: &str = "attachments";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ImportedFile {
+    /// Original file name
+    pub name: String,
+    /// Path of the copy inside the attachments area
+    pub path: String,
+    #[serde(rename = "sourcePath")]
+    pub source_path: String,
+    pub size: u64,
+    #[serde(rename = "mimeType")]
+    pub mime_type: String,
+    /// Hex encoded SHA-256 of the content
+    pub hash: String,
+    #[serde(rename = "importedAt")]
+    pub imported_at: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FilesImportedEvent {
+    pub window: String,
+    pub files: Vec<ImportedFile>,
+    pub errors: Vec<String>,
+}
+
+/// Get the directory where imported attachments are stored
+pub fn get_attachments_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    get_app_data_subdir(app, ATTACHMENTS_DIR)
+}
+
+/// Stream a file through SHA-256 and return the hex digest
+pub fn hash_file(path: &Path) -> Result<String, String> {
+    let mut file = File::open(path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 64 * 1024];
+
+    loop {
+        let read = file.read(&mut buffer)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+
+    Ok(hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// Copy a file into the attachments area and collect its metadata.
+/// Identical content is stored only once.
+pub fn import_file(app: &AppHandle, source: &Path) -> Result<ImportedFile, String> {
+    import_file_with_options(app, source, None)
+}
+
+/// Like `import_file`, with per-import overrides for image processing
+pub fn import_file_with_options(
+    app: &AppHandle,
+    source: &Path,
+    image_options: Option<&ImageProcessingOverrides>,
+) -> Result<ImportedFile, String> {
+    let metadata = fs::metadata(source)
+        .map_err(|e| format!("Failed to read {}: {}", source.display(), e))?;
+    if !metadata.is_file() {
+        return Err(format!("Not a file: {}", source.display()));
+    }
+
+    let name = source.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or_else(|| format!("Invalid file name: {}", source.display()))?;
+    // Images may be replaced by a scrubbed, downscaled copy
+    let content = prepare_image_for_upload(app, source, image_options);
+    let size = fs::metadata(&content).map(|m| m.len()).unwrap_or(metadata.len());
+    let hash = hash_file(&content)?;
+    let mime_type = mime_guess::from_path(source)
+        .first_or_octet_stream()
+        .to_string();
+
+    let target = get_attachments_dir(app)?.join(format!("{}_{}", &hash[..16], name));
+    if !target.exists() {
+        fs::copy(&content, &target)
+            .map_err(|e| format!("Failed to copy {}: {}", source.display(), e))?;
+    }
+    if content != source {
+        let _ = fs::remove_file(&content);
+    }
+
+    Ok(ImportedFile {
+        name,
+        path: target.to_string_lossy().to_string(),
+        source_path: source.to_string_lossy().to_string(),
+        size,
+        mime_type,
+        hash,
+        imported_at: now_millis(),
+    })
+}
+
+/// Store in-memory content (e.g. decoded from an archive) in the attachments area.
+/// `source` records where it came from.
+pub fn import_bytes(app: &AppHandle, name: &str, bytes: &[u8], source: &str) -> Result<ImportedFile, String> {
+    let hash: String = Sha256::digest(bytes).iter().map(|b| format!("{:02x}", b)).collect();
+    let mime_type = mime_guess::from_path(name)
+        .first_or_octet_stream()
+        .to_string();
+
+    let target = get_attachments_dir(app)?.join(format!("{}_{}", &hash[..16], name));
+    if !target.exists() {
+        fs::write(&target, bytes)
+            .map_err(|e| format!("Failed to write {}: {}", target.display(), e))?;
+    }
+
+    Ok(ImportedFile {
+        name: name.to_string(),
+        path: target.to_string_lossy().to_string(),
+        source_path: source.to_string(),
+        size: bytes.len() as u64,
+        mime_type,
+        hash,
+        imported_at: now_millis(),
+    })
+}
+
+/// Import a batch of dropped or shared files and notify the window they belong to
+pub fn import_files_into_window(app: AppHandle, window_label: String, paths: Vec<PathBuf>) {
+    std::thread::spawn(move || {
+        let mut files = Vec::new();
+        let mut errors = Vec::new();
+
+        for path in paths.iter() {
+            match import_file(&app, path) {
+                Ok(file) => {
+                    info!("📎 Imported {} ({} bytes, {})", file.name, file.size, file.mime_type);
+                    files.push(file);
+                }
+                Err(e) => {
+                    error!("❌ {}", e);
+                    errors.push(e);
+                }
+            }
+        }
+
+        let event = FilesImportedEvent {
+            window: window_label.clone(),
+            files,
+            errors,
+        };
+
+        if let Some(window) = app.get_webview_window(&window_label) {
+            if let Err(e) = window.emit("files-imported", &event) {
+                error!("Failed to emit files-imported event: {}", e);
+            }
+        }
+    });
+}
+
+/// Handle native file drops on a window by importing them as attachments
+pub fn setup_file_drop_import(app: &AppHandle, window_label: &str) {
+    if let Some(window) = app.get_webview_window(window_label) {
+        let app_handle = app.clone();
+        let label = window_label.to_string();
+
+        window.on_window_event(move |event| {
+            if let WindowEvent::DragDrop(DragDropEvent::Drop { paths, .. }) = event {
+                if !paths.is_empty() {
+                    info!("📥 {} file(s) dropped on {} window", paths.len(), label);
+                    import_files_into_window(app_handle.clone(), label.clone(), paths.clone());
+                }
+            }
+        });
+    }
+}
+
+/// Import a picked file as an attachment, optionally overriding image processing for this file
+#[tauri::command]
+pub async fn import_attachment(app: AppHandle, path: String, image_options: Option<ImageProcessingOverrides>) -> Result<ImportedFile, String> {
+    tauri::async_runtime::spawn_blocking(move || import_file_with_options(&app, Path::new(&path), image_options.as_ref()))
+        .await
+        .map_err(|e| format!("Import failed: {}", e))?
+}
@@ -0,0 +1,170 @@
+use tauri::AppHandle;
+use serde::{Deserialize, Serialize};
+
+use auto_launch::{AutoLaunch, AutoLaunchBuilder};
+
+use crate::desktop::{load_json_or_default, portable_data_dir, save_json};
+
+const AUTOSTART_CONFIG_FILE: &str = "autostart.json";
+pub const AUTOSTART_FLAG: &str = "--autostart";
+/// Start in the tray without showing the main window
+pub const HIDDEN_FLAG: &str = "--hidden";
+const PORTABLE_FLAG: &str = "--portable";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AutostartConfig {
+    /// Keep the main window hidden when launched at login
+    pub hidden: bool,
+    /// Additional command line arguments for the login item
+    #[serde(rename = "extraArgs", default)]
+    pub extra_args: Vec<String>,
+}
+
+impl Default for AutostartConfig {
+    fn default() -> Self {
+        // Autostart has always started in the tray
+        Self { hidden: true, extra_args: Vec::new() }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AutostartStatus {
+    pub enabled: bool,
+    pub hidden: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AutostartDetails {
+    pub enabled: bool,
+    /// Name of the login item, registry value or launch agent
+    #[serde(rename = "appName")]
+    pub app_name: String,
+    /// Executable the login item starts
+    pub path: String,
+    pub args: Vec<String>,
+}
+
+/// Whether this launch should skip showing the main window
+pub fn is_hidden_launch(args: &[String]) -> bool {
+    args.iter().any(|arg| arg == HIDDEN_FLAG)
+}
+
+/// The file the OS should launch. An AppImage runs from a temporary mount,
+/// so the login item has to point at the AppImage itself.
+fn autostart_executable() -> Result<String, String> {
+    #[cfg(target_os = "linux")]
+    if let Some(appimage) = std::env::var_os("APPIMAGE") {
+        return Ok(appimage.to_string_lossy().to_string());
+    }
+
+    let exe = std::env::current_exe()
+        .map_err(|e| format!("Failed to resolve executable path: {}", e))?;
+    // Resolve symlinks such as package manager shims
+    let exe = exe.canonicalize().unwrap_or(exe);
+    Ok(exe.to_string_lossy().to_string())
+}
+
+fn autostart_args(config: &AutostartConfig) -> Vec<String> {
+    let mut args = vec![AUTOSTART_FLAG.to_string()];
+    if config.hidden {
+        args.push(HIDDEN_FLAG.to_string());
+    }
+    // A portable copy must keep using its own data folder when started at login
+    if portable_data_dir().is_some() {
+        args.push(PORTABLE_FLAG.to_string());
+    }
+    for arg in &config.extra_args {
+        if !arg.trim().is_empty() && !args.contains(arg) {
+            args.push(arg.clone());
+        }
+    }
+    args
+}
+
+/// Login item for this executable, keyed by the app name like entries of earlier versions
+fn create_auto_launch(app: &AppHandle, config: &AutostartConfig) -> Result<AutoLaunch, String> {
+    let exe = autostart_executable()?;
+    let args = autostart_args(config);
+
+    let mut builder = AutoLaunchBuilder::new();
+    builder
+        .set_app_name(&app.package_info().name)
+        .set_app_path(&exe)
+        .set_args(args.as_slice());
+    #[cfg(target_os = "macos")]
+    builder.set_use_launch_agent(true);
+
+    builder.build().map_err(|e| format!("Failed to create autostart entry: {}", e))
+}
+
+/// Rewrite an enabled login item, e.g. one registered by an older version without
+/// the configured arguments and `--portable`, or before the app was moved
+pub fn refresh_autostart(app: &AppHandle) {
+    let config: AutostartConfig = load_json_or_default(app, AUTOSTART_CONFIG_FILE);
+    let auto_launch = match create_auto_launch(app, &config) {
+        Ok(auto_launch) => auto_launch,
+        Err(e) => {
+            warn!("Failed to check autostart entry: {}", e);
+            return;
+        }
+    };
+    if auto_launch.is_enabled().unwrap_or(false) {
+        if let Err(e) = auto_launch.enable() {
+            warn!("Failed to refresh autostart entry: {}", e);
+        }
+    }
+}
+
+#[tauri::command]
+pub fn get_autostart_status(app: AppHandle) -> Result<AutostartStatus, String> {
+    let config: AutostartConfig = load_json_or_default(&app, AUTOSTART_CONFIG_FILE);
+    let enabled = create_auto_launch(&app, &config)?
+        .is_enabled()
+        .map_err(|e| format!("Failed to read autostart state: {}", e))?;
+    Ok(AutostartStatus { enabled, hidden: config.hidden })
+}
+
+/// What exactly is registered with the OS
+#[tauri::command]
+pub fn get_autostart_details(app: AppHandle) -> Result<AutostartDetails, String> {
+    let config: AutostartConfig = load_json_or_default(&app, AUTOSTART_CONFIG_FILE);
+    let auto_launch = create_auto_launch(&app, &config)?;
+    Ok(AutostartDetails {
+        enabled: auto_launch.is_enabled().map_err(|e| format!("Failed to read autostart state: {}", e))?,
+        app_name: auto_launch.get_app_name().to_string(),
+        path: auto_launch.get_app_path().to_string(),
+        args: autostart_args(&config),
+    })
+}
+
+/// Register Blinko to start at login, in the tray unless `hidden` is false.
+/// `args` replaces the extra command line arguments passed at login.
+#[tauri::command]
+pub fn enable_autostart(app: AppHandle, hidden: Option<bool>, args: Option<Vec<String>>) -> Result<(), String> {
+    let mut config: AutostartConfig = load_json_or_default(&app, AUTOSTART_CONFIG_FILE);
+    if let Some(hidden) = hidden {
+        config.hidden = hidden;
+    }
+    if let Some(args) = args {
+        config.extra_args = args;
+    }
+
+    // The entry is keyed by app name, so this also replaces one registered with an older path
+    create_auto_launch(&app, &config)?
+        .enable()
+        .map_err(|e| format!("Failed to enable autostart: {}", e))?;
+    save_json(&app, AUTOSTART_CONFIG_FILE, &config)?;
+    info!("🚀 Autostart enabled{}", if config.hidden { " (hidden)" } else { "" });
+    Ok(())
+}
+
+#[tauri::command]
+pub fn disable_autostart(app: AppHandle) -> Result<(), String> {
+    let config: AutostartConfig = load_json_or_default(&app, AUTOSTART_CONFIG_FILE);
+    let auto_launch = create_auto_launch(&app, &config)?;
+    if auto_launch.is_enabled().unwrap_or(false) {
+        auto_launch.disable().map_err(|e| format!("Failed to disable autostart: {}", e))?;
+    }
+    info!("🚀 Autostart disabled");
+    Ok(())
+}
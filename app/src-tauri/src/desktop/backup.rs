@@ -0,0 +1,461 @@
+use tauri::{AppHandle, Emitter};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, UNIX_EPOCH};
+
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::desktop::{
+    close_offline_db, ensure_disk_space, get_app_data_dir, get_app_data_subdir, load_json_or_default, now_millis, save_json,
+    with_offline_db,
+};
+
+const BACKUP_CONFIG_FILE: &str = "backup_config.json";
+const BACKUP_MANIFEST_FILE: &str = "backup_manifest.json";
+const BACKUPS_DIR: &str = "backups";
+const BACKUP_FORMAT_VERSION: u32 = 1;
+const BACKUP_CHECK_INTERVAL: Duration = Duration::from_secs(10 * 60);
+/// Directories copied recursively, everything else in them can be rebuilt or re-downloaded
+const BACKUP_DIRS: &[&str] = &["attachments"];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BackupConfig {
+    #[serde(rename = "autoBackupEnabled")]
+    pub auto_backup_enabled: bool,
+    #[serde(rename = "intervalHours")]
+    pub interval_hours: u32,
+    /// Destination for automatic backups, defaults to `<app data>/backups`
+    pub folder: Option<String>,
+    /// Older automatic backups beyond this count are deleted
+    #[serde(rename = "keepCount")]
+    pub keep_count: u32,
+    #[serde(rename = "lastBackupAt")]
+    pub last_backup_at: Option<u64>,
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            auto_backup_enabled: false,
+            interval_hours: 24,
+            folder: None,
+            keep_count: 7,
+            last_backup_at: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BackupFileEntry {
+    pub path: String,
+    pub size: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BackupManifest {
+    pub format: u32,
+    #[serde(rename = "appVersion")]
+    pub app_version: String,
+    #[serde(rename = "createdAt")]
+    pub created_at: u64,
+    pub files: Vec<BackupFileEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BackupResult {
+    pub path: String,
+    pub size: u64,
+    #[serde(rename = "fileCount")]
+    pub file_count: usize,
+    pub automatic: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RestorePreviewEntry {
+    pub path: String,
+    pub size: u64,
+    /// A local file with this path exists and will be replaced
+    pub overwrites: bool,
+    #[serde(rename = "localModifiedAt")]
+    pub local_modified_at: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RestorePreview {
+    #[serde(rename = "createdAt")]
+    pub created_at: u64,
+    #[serde(rename = "appVersion")]
+    pub app_version: String,
+    pub files: Vec<RestorePreviewEntry>,
+    #[serde(rename = "overwriteCount")]
+    pub overwrite_count: usize,
+    #[serde(rename = "newCount")]
+    pub new_count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RestoreResult {
+    #[serde(rename = "restoredCount")]
+    pub restored_count: usize,
+    /// Copy of the previous data taken right before restoring
+    #[serde(rename = "safetyBackupPath")]
+    pub safety_backup_path: String,
+    /// Settings are cached in memory, so the app has to restart to pick them up
+    #[serde(rename = "restartRequired")]
+    pub restart_required: bool,
+}
+
+static BACKUP_SCHEDULER_STARTED: AtomicBool = AtomicBool::new(false);
+static BACKUP_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Load backup config from file
+pub fn load_backup_config(app: &AppHandle) -> BackupConfig {
+    load_json_or_default(app, BACKUP_CONFIG_FILE)
+}
+
+fn backup_folder(app: &AppHandle, config: &BackupConfig) -> Result<PathBuf, String> {
+    match config.folder.as_ref().filter(|f| !f.trim().is_empty()) {
+        Some(folder) => {
+            let dir = PathBuf::from(folder);
+            fs::create_dir_all(&dir)
+                .map_err(|e| format!("Failed to create backup folder {}: {}", dir.display(), e))?;
+            Ok(dir)
+        }
+        None => get_app_data_subdir(app, BACKUPS_DIR),
+    }
+}
+
+/// Top-level settings and databases plus the attachment folders, relative to the app data dir
+fn collect_backup_files(data_dir: &Path) -> Result<Vec<String>, String> {
+    let mut files = Vec::new();
+    let entries = fs::read_dir(data_dir)
+        .map_err(|e| format!("Failed to read {}: {}", data_dir.display(), e))?;
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if entry.path().is_file() && is_backup_root_file(&name) {
+            files.push(name);
+        }
+    }
+
+    for dir_name in BACKUP_DIRS {
+        let mut dirs = vec![data_dir.join(dir_name)];
+        while let Some(dir) = dirs.pop() {
+            let Ok(entries) = fs::read_dir(&dir) else { continue };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    dirs.push(path);
+                } else if let Ok(relative) = path.strip_prefix(data_dir) {
+                    files.push(relative.to_string_lossy().replace('\\', "/"));
+                }
+            }
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+fn is_backup_root_file(name: &str) -> bool {
+    // The schedule itself is machine specific and stays out of backups
+    name != BACKUP_CONFIG_FILE && (name.ends_with(".json") || name.ends_with(".db"))
+}
+
+/// Only paths this module writes are accepted back, so a crafted archive can't escape the data dir
+fn is_restorable_path(path: &str) -> bool {
+    let relative = Path::new(path);
+    if relative.is_absolute() || relative.components().any(|c| !matches!(c, Component::Normal(_))) {
+        return false;
+    }
+    match path.split_once('/') {
+        None => is_backup_root_file(path),
+        Some((dir, rest)) => BACKUP_DIRS.contains(&dir) && !rest.is_empty(),
+    }
+}
+
+/// Write a zip of notes cache, attachments and settings to `target`
+pub fn write_backup(app: &AppHandle, target: &Path, automatic: bool) -> Result<BackupResult, String> {
+    if BACKUP_RUNNING.swap(true, Ordering::SeqCst) {
+        return Err("A backup is already running".to_string());
+    }
+    let result = (|| {
+        let data_dir = get_app_data_dir(app)?;
+        // Fold the write-ahead log into the database file so the copy is complete
+        if let Err(e) = with_offline_db(app, |conn| conn.execute_batch("PRAGMA wal_checkpoint(TRUNCATE);")) {
+            error!("Failed to checkpoint offline database: {}", e);
+        }
+
+        let files = collect_backup_files(&data_dir)?;
+        // Attachments are mostly compressed already, so the archive is about as large as its files
+        let required: u64 = files.iter()
+            .filter_map(|relative| fs::metadata(data_dir.join(relative)).ok())
+            .map(|metadata| metadata.len())
+            .sum();
+        ensure_disk_space(target, required)?;
+        let partial = target.with_extension("zip.part");
+        let output = File::create(&partial)
+            .map_err(|e| format!("Failed to create {}: {}", partial.display(), e))?;
+        let mut zip = ZipWriter::new(output);
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+        let mut manifest = BackupManifest {
+            format: BACKUP_FORMAT_VERSION,
+            app_version: app.package_info().version.to_string(),
+            created_at: now_millis(),
+            files: Vec::new(),
+        };
+
+        for relative in files.iter() {
+            let path = data_dir.join(relative);
+            let mut file = match File::open(&path) {
+                Ok(file) => file,
+                Err(e) => {
+                    warn!("Skipping {} in backup: {}", relative, e);
+                    continue;
+                }
+            };
+            zip.start_file(relative.as_str(), options)
+                .map_err(|e| format!("Failed to add {} to backup: {}", relative, e))?;
+            let size = io::copy(&mut file, &mut zip)
+                .map_err(|e| format!("Failed to add {} to backup: {}", relative, e))?;
+            manifest.files.push(BackupFileEntry { path: relative.clone(), size });
+        }
+
+        let manifest_json = serde_json::to_string_pretty(&manifest)
+            .map_err(|e| format!("Failed to serialize backup manifest: {}", e))?;
+        zip.start_file(BACKUP_MANIFEST_FILE, options)
+            .map_err(|e| format!("Failed to write backup manifest: {}", e))?;
+        io::Write::write_all(&mut zip, manifest_json.as_bytes())
+            .map_err(|e| format!("Failed to write backup manifest: {}", e))?;
+        zip.finish().map_err(|e| format!("Failed to finish backup: {}", e))?;
+
+        fs::rename(&partial, target)
+            .map_err(|e| format!("Failed to finalize backup: {}", e))?;
+        let size = fs::metadata(target).map(|m| m.len()).unwrap_or(0);
+        info!("🗄️ Backup of {} files written to {}", manifest.files.len(), target.display());
+
+        Ok(BackupResult {
+            path: target.to_string_lossy().to_string(),
+            size,
+            file_count: manifest.files.len(),
+            automatic,
+        })
+    })();
+    BACKUP_RUNNING.store(false, Ordering::SeqCst);
+
+    match result {
+        Ok(ref backup) => {
+            let _ = app.emit("backup-completed", backup);
+        }
+        Err(ref e) => {
+            error!("❌ Backup failed: {}", e);
+            let _ = app.emit("backup-failed", e);
+        }
+    }
+    result
+}
+
+fn open_backup(path: &Path) -> Result<(ZipArchive<File>, BackupManifest), String> {
+    let file = File::open(path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mut archive = ZipArchive::new(file)
+        .map_err(|e| format!("Not a valid backup archive: {}", e))?;
+
+    let mut manifest_json = String::new();
+    archive.by_name(BACKUP_MANIFEST_FILE)
+        .map_err(|_| "Backup manifest is missing, this is not a Blinko backup".to_string())?
+        .read_to_string(&mut manifest_json)
+        .map_err(|e| format!("Failed to read backup manifest: {}", e))?;
+    let manifest: BackupManifest = serde_json::from_str(&manifest_json)
+        .map_err(|e| format!("Invalid backup manifest: {}", e))?;
+
+    if manifest.format > BACKUP_FORMAT_VERSION {
+        return Err(format!("Backup format {} is newer than this app supports, please update Blinko", manifest.format));
+    }
+    for entry in manifest.files.iter() {
+        if !is_restorable_path(&entry.path) {
+            return Err(format!("Backup contains an unexpected path: {}", entry.path));
+        }
+        if archive.by_name(&entry.path).is_err() {
+            return Err(format!("Backup is incomplete, {} is missing", entry.path));
+        }
+    }
+    Ok((archive, manifest))
+}
+
+/// Validate a backup and describe which local files it would replace
+pub fn preview_backup_file(app: &AppHandle, path: &Path) -> Result<RestorePreview, String> {
+    let (_, manifest) = open_backup(path)?;
+    let data_dir = get_app_data_dir(app)?;
+
+    let files: Vec<RestorePreviewEntry> = manifest.files.iter().map(|entry| {
+        let local = fs::metadata(data_dir.join(&entry.path)).ok();
+        RestorePreviewEntry {
+            path: entry.path.clone(),
+            size: entry.size,
+            overwrites: local.is_some(),
+            local_modified_at: local
+                .and_then(|m| m.modified().ok())
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map(|d| d.as_millis() as u64),
+        }
+    }).collect();
+    let overwrite_count = files.iter().filter(|f| f.overwrites).count();
+
+    Ok(RestorePreview {
+        created_at: manifest.created_at,
+        app_version: manifest.app_version,
+        new_count: files.len() - overwrite_count,
+        overwrite_count,
+        files,
+    })
+}
+
+/// Replace local data with the backup contents, keeping a safety copy of the current state first.
+/// Nothing is touched if that copy can't be written, e.g. while another backup is running.
+pub fn restore_backup_file(app: &AppHandle, path: &Path) -> Result<RestoreResult, String> {
+    let (mut archive, manifest) = open_backup(path)?;
+    let data_dir = get_app_data_dir(app)?;
+
+    let safety_target = get_app_data_subdir(app, BACKUPS_DIR)?.join(format!("pre-restore-{}.zip", now_millis()));
+    let safety_backup_path = write_backup(app, &safety_target, true)
+        .map_err(|e| format!("Restore aborted, failed to back up the current data first: {}", e))?
+        .path;
+
+    close_offline_db();
+    let mut restored_count = 0;
+    for entry in manifest.files.iter() {
+        let target = data_dir.join(&entry.path);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let mut source = archive.by_name(&entry.path)
+            .map_err(|e| format!("Failed to read {} from backup: {}", entry.path, e))?;
+        let mut output = File::create(&target)
+            .map_err(|e| format!("Failed to write {}: {}", target.display(), e))?;
+        io::copy(&mut source, &mut output)
+            .map_err(|e| format!("Failed to write {}: {}", target.display(), e))?;
+        restored_count += 1;
+    }
+
+    info!("🗄️ Restored {} files from {}", restored_count, path.display());
+    Ok(RestoreResult {
+        restored_count,
+        safety_backup_path,
+        restart_required: true,
+    })
+}
+
+fn prune_automatic_backups(folder: &Path, keep: u32) {
+    let Ok(entries) = fs::read_dir(folder) else { return };
+    let mut backups: Vec<PathBuf> = entries.flatten()
+        .map(|e| e.path())
+        .filter(|p| p.file_name().is_some_and(|n| n.to_string_lossy().starts_with("blinko-auto-")))
+        .collect();
+    // Timestamped names sort chronologically
+    backups.sort();
+    let excess = backups.len().saturating_sub(keep.max(1) as usize);
+    for old in backups.into_iter().take(excess) {
+        if let Err(e) = fs::remove_file(&old) {
+            error!("Failed to delete old backup {}: {}", old.display(), e);
+        }
+    }
+}
+
+fn run_automatic_backup_if_due(app: &AppHandle) {
+    let mut config = load_backup_config(app);
+    if !config.auto_backup_enabled {
+        return;
+    }
+    let interval = config.interval_hours.max(1) as u64 * 60 * 60 * 1000;
+    if config.last_backup_at.is_some_and(|last| now_millis() < last + interval) {
+        return;
+    }
+
+    let folder = match backup_folder(app, &config) {
+        Ok(folder) => folder,
+        Err(e) => {
+            error!("❌ {}", e);
+            return;
+        }
+    };
+    let name = format!("blinko-auto-{}.zip", chrono::Local::now().format("%Y%m%d-%H%M%S"));
+    if write_backup(app, &folder.join(name), true).is_ok() {
+        config.last_backup_at = Some(now_millis());
+        if let Err(e) = save_json(app, BACKUP_CONFIG_FILE, &config) {
+            error!("Failed to save backup config: {}", e);
+        }
+        prune_automatic_backups(&folder, config.keep_count);
+    }
+}
+
+/// Start the background loop for scheduled backups
+pub fn start_backup_scheduler(app: &AppHandle) {
+    if BACKUP_SCHEDULER_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let app_handle = app.clone();
+    std::thread::spawn(move || loop {
+        run_automatic_backup_if_due(&app_handle);
+        std::thread::sleep(BACKUP_CHECK_INTERVAL);
+    });
+}
+
+#[tauri::command]
+pub fn get_backup_config(app: AppHandle) -> BackupConfig {
+    load_backup_config(&app)
+}
+
+#[tauri::command]
+pub async fn save_backup_config(app: AppHandle, config: BackupConfig) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        backup_folder(&app, &config)?;
+        save_json(&app, BACKUP_CONFIG_FILE, &config)
+    })
+    .await
+    .map_err(|e| format!("Failed to save backup settings: {}", e))?
+}
+
+#[tauri::command]
+pub async fn create_backup(app: AppHandle, path: String) -> Result<BackupResult, String> {
+    tauri::async_runtime::spawn_blocking(move || write_backup(&app, Path::new(&path), false))
+        .await
+        .map_err(|e| format!("Backup failed: {}", e))?
+}
+
+#[tauri::command]
+pub async fn preview_backup(app: AppHandle, path: String) -> Result<RestorePreview, String> {
+    tauri::async_runtime::spawn_blocking(move || preview_backup_file(&app, Path::new(&path)))
+        .await
+        .map_err(|e| format!("Failed to read backup: {}", e))?
+}
+
+#[tauri::command]
+pub async fn restore_backup(app: AppHandle, path: String) -> Result<RestoreResult, String> {
+    tauri::async_runtime::spawn_blocking(move || restore_backup_file(&app, Path::new(&path)))
+        .await
+        .map_err(|e| format!("Restore failed: {}", e))?
+}
+
+/// Automatic backups in the configured folder, newest first
+#[tauri::command]
+pub fn list_backups(app: AppHandle) -> Result<Vec<BackupFileEntry>, String> {
+    let folder = backup_folder(&app, &load_backup_config(&app))?;
+    let entries = fs::read_dir(&folder)
+        .map_err(|e| format!("Failed to read {}: {}", folder.display(), e))?;
+    let mut backups: Vec<BackupFileEntry> = entries.flatten()
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "zip"))
+        .map(|e| BackupFileEntry {
+            path: e.path().to_string_lossy().to_string(),
+            size: e.metadata().map(|m| m.len()).unwrap_or(0),
+        })
+        .collect();
+    backups.sort_by(|a, b| b.path.cmp(&a.path));
+    Ok(backups)
+}
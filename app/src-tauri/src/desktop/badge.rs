@@ -0,0 +1,99 @@
+use tauri::{AppHandle, Manager};
+
+/// Set the unread badge on the dock (macOS), taskbar overlay (Windows) or launcher (Linux)
+#[tauri::command]
+pub fn set_badge_count(app: AppHandle, count: u32) -> Result<(), String> {
+    let window = app.get_webview_window("main")
+        .ok_or("Main window not found")?;
+
+    #[cfg(target_os = "windows")]
+    {
+        let overlay = if count == 0 {
+            None
+        } else {
+            Some(render_badge_icon(count))
+        };
+        window.set_overlay_icon(overlay)
+            .map_err(|e| format!("Failed to set taskbar overlay icon: {}", e))?;
+    }
+
+    // macOS dock badge and Linux Unity launcher count (needs a matching .desktop file)
+    #[cfg(not(target_os = "windows"))]
+    {
+        let badge = if count == 0 { None } else { Some(count as i64) };
+        window.set_badge_count(badge)
+            .map_err(|e| format!("Failed to set badge count: {}", e))?;
+    }
+
+    info!("Set badge count to {}", count);
+    Ok(())
+}
+
+/// 3x5 bitmap glyphs for digits and '+', one row per byte (3 low bits used)
+#[cfg(target_os = "windows")]
+fn glyph(c: char) -> [u8; 5] {
+    match c {
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b010, 0b010, 0b010],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        _ => [0b000, 0b010, 0b111, 0b010, 0b000], // '+'
+    }
+}
+
+/// Draw a 16x16 red badge with the count in white, "9+" for larger values
+#[cfg(target_os = "windows")]
+fn render_badge_icon(count: u32) -> tauri::image::Image<'static> {
+    const SIZE: usize = 16;
+    const SCALE: usize = 2;
+    let mut rgba = vec![0u8; SIZE * SIZE * 4];
+
+    let mut set_pixel = |x: usize, y: usize, color: [u8; 4]| {
+        if x < SIZE && y < SIZE {
+            let i = (y * SIZE + x) * 4;
+            rgba[i..i + 4].copy_from_slice(&color);
+        }
+    };
+
+    // Filled circle background
+    let center = (SIZE as f32 - 1.0) / 2.0;
+    for y in 0..SIZE {
+        for x in 0..SIZE {
+            let dx = x as f32 - center;
+            let dy = y as f32 - center;
+            if dx * dx + dy * dy <= (SIZE as f32 / 2.0).powi(2) {
+                set_pixel(x, y, [220, 38, 38, 255]);
+            }
+        }
+    }
+
+    let text = if count > 9 { "9+".to_string() } else { count.to_string() };
+    let char_width = 3 * SCALE;
+    let spacing = SCALE;
+    let text_width = text.len() * char_width + (text.len() - 1) * spacing;
+    let start_x = (SIZE - text_width) / 2;
+    let start_y = (SIZE - 5 * SCALE) / 2;
+
+    for (index, c) in text.chars().enumerate() {
+        let offset_x = start_x + index * (char_width + spacing);
+        for (row, bits) in glyph(c).iter().enumerate() {
+            for col in 0..3 {
+                if bits & (0b100 >> col) != 0 {
+                    for sy in 0..SCALE {
+                        for sx in 0..SCALE {
+                            set_pixel(offset_x + col * SCALE + sx, start_y + row * SCALE + sy, [255, 255, 255, 255]);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    tauri::image::Image::new_owned(rgba, SIZE as u32, SIZE as u32)
+}
@@ -0,0 +1,261 @@
+use tauri::AppHandle;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime};
+
+use crate::desktop::{get_app_data_subdir, load_json_or_default, save_json, ATTACHMENTS_DIR, LOGS_DIR, THUMBNAILS_DIR};
+
+const STORAGE_SETTINGS_FILE: &str = "storage_settings.json";
+/// Downloaded LLM and embedding models live below this
+const MODELS_DIR: &str = "models";
+/// Name marker of recorded audio notes among the attachments
+const AUDIO_NOTE_MARKER: &str = "_audio-note-";
+const CACHE_CHECK_INTERVAL: Duration = Duration::from_secs(6 * 3600);
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+static CACHE_CLEANUP_STARTED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum CacheKind {
+    Thumbnails,
+    /// Downloaded and imported attachments, audio notes not included
+    Attachments,
+    Models,
+    /// Recorded audio notes kept for their transcriptions
+    AudioNotes,
+    Logs,
+}
+
+impl CacheKind {
+    const ALL: [CacheKind; 5] = [
+        CacheKind::Thumbnails,
+        CacheKind::Attachments,
+        CacheKind::Models,
+        CacheKind::AudioNotes,
+        CacheKind::Logs,
+    ];
+
+    fn dir(self) -> &'static str {
+        match self {
+            CacheKind::Thumbnails => THUMBNAILS_DIR,
+            CacheKind::Attachments | CacheKind::AudioNotes => ATTACHMENTS_DIR,
+            CacheKind::Models => MODELS_DIR,
+            CacheKind::Logs => LOGS_DIR,
+        }
+    }
+
+    fn includes(self, path: &Path) -> bool {
+        let is_audio_note = path.file_name()
+            .is_some_and(|name| name.to_string_lossy().contains(AUDIO_NOTE_MARKER));
+        match self {
+            CacheKind::Attachments => !is_audio_note,
+            CacheKind::AudioNotes => is_audio_note,
+            _ => true,
+        }
+    }
+}
+
+/// Size caps per cache in megabytes, 0 for no cap.
+/// Over its cap, a cache loses its least recently used files first.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StorageSettings {
+    #[serde(rename = "thumbnailsMaxMb")]
+    pub thumbnails_max_mb: u64,
+    #[serde(rename = "attachmentsMaxMb")]
+    pub attachments_max_mb: u64,
+    #[serde(rename = "modelsMaxMb")]
+    pub models_max_mb: u64,
+    #[serde(rename = "audioNotesMaxMb")]
+    pub audio_notes_max_mb: u64,
+    #[serde(rename = "logsMaxMb")]
+    pub logs_max_mb: u64,
+}
+
+impl Default for StorageSettings {
+    fn default() -> Self {
+        Self {
+            thumbnails_max_mb: 500,
+            // Attachments and recordings may exist nowhere else, so they are only capped on request
+            attachments_max_mb: 0,
+            models_max_mb: 0,
+            audio_notes_max_mb: 0,
+            logs_max_mb: 100,
+        }
+    }
+}
+
+impl StorageSettings {
+    fn max_bytes(&self, kind: CacheKind) -> Option<u64> {
+        let mb = match kind {
+            CacheKind::Thumbnails => self.thumbnails_max_mb,
+            CacheKind::Attachments => self.attachments_max_mb,
+            CacheKind::Models => self.models_max_mb,
+            CacheKind::AudioNotes => self.audio_notes_max_mb,
+            CacheKind::Logs => self.logs_max_mb,
+        };
+        (mb > 0).then_some(mb * BYTES_PER_MB)
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct CacheStats {
+    pub kind: CacheKind,
+    pub bytes: u64,
+    pub files: usize,
+    #[serde(rename = "maxBytes")]
+    pub max_bytes: Option<u64>,
+}
+
+struct CachedFile {
+    path: PathBuf,
+    size: u64,
+    last_used: SystemTime,
+}
+
+pub fn load_storage_settings(app: &AppHandle) -> StorageSettings {
+    load_json_or_default(app, STORAGE_SETTINGS_FILE)
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<CachedFile>) {
+    let Ok(entries) = fs::read_dir(dir) else { return };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(metadata) = fs::symlink_metadata(&path) else { continue };
+        if metadata.is_dir() {
+            collect_files(&path, files);
+            continue;
+        }
+        // Access times are often not kept, so a later write counts as use too
+        let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+        let last_used = metadata.accessed().map_or(modified, |accessed| accessed.max(modified));
+        files.push(CachedFile { path, size: metadata.len(), last_used });
+    }
+}
+
+/// Files of a cache, least recently used first
+fn cache_files(app: &AppHandle, kind: CacheKind) -> Result<Vec<CachedFile>, String> {
+    let mut files = Vec::new();
+    collect_files(&get_app_data_subdir(app, kind.dir())?, &mut files);
+    files.retain(|file| kind.includes(&file.path));
+    files.sort_by_key(|file| file.last_used);
+    // Today's log file is still being written to
+    if kind == CacheKind::Logs {
+        files.pop();
+    }
+    Ok(files)
+}
+
+/// Delete the given files, returning the bytes freed
+fn remove_files(files: &[CachedFile]) -> u64 {
+    files.iter()
+        .filter(|file| match fs::remove_file(&file.path) {
+            Ok(_) => true,
+            Err(e) => {
+                warn!("Failed to remove {}: {}", file.path.display(), e);
+                false
+            }
+        })
+        .map(|file| file.size)
+        .sum()
+}
+
+fn cache_stats(app: &AppHandle, kind: CacheKind, settings: &StorageSettings) -> Result<CacheStats, String> {
+    let files = cache_files(app, kind)?;
+    Ok(CacheStats {
+        kind,
+        bytes: files.iter().map(|file| file.size).sum(),
+        files: files.len(),
+        max_bytes: settings.max_bytes(kind),
+    })
+}
+
+fn clear_cache_kind(app: &AppHandle, kind: CacheKind) -> Result<u64, String> {
+    // Loaded models keep their files open on Windows
+    #[cfg(feature = "local-llm")]
+    if kind == CacheKind::Models {
+        crate::llm::unload_llm_model();
+    }
+
+    let freed = remove_files(&cache_files(app, kind)?);
+    info!("🧹 Cleared {:?} cache, {} bytes freed", kind, freed);
+    Ok(freed)
+}
+
+/// Evict least recently used files from every cache that is over its cap
+pub fn enforce_cache_limits(app: &AppHandle) {
+    let settings = load_storage_settings(app);
+    for kind in CacheKind::ALL {
+        let Some(max_bytes) = settings.max_bytes(kind) else { continue };
+        let files = match cache_files(app, kind) {
+            Ok(files) => files,
+            Err(e) => {
+                error!("Failed to read {:?} cache: {}", kind, e);
+                continue;
+            }
+        };
+
+        let mut total: u64 = files.iter().map(|file| file.size).sum();
+        let evict = files.iter().take_while(|file| {
+            let over = total > max_bytes;
+            total = total.saturating_sub(file.size);
+            over
+        }).count();
+        if evict > 0 {
+            let freed = remove_files(&files[..evict]);
+            info!("🧹 Evicted {} files ({} bytes) from the {:?} cache", evict, freed, kind);
+        }
+    }
+}
+
+/// Keep caches under their caps, checking at startup and every few hours
+pub fn start_cache_cleanup(app: &AppHandle) {
+    if CACHE_CLEANUP_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let app_handle = app.clone();
+    std::thread::spawn(move || loop {
+        enforce_cache_limits(&app_handle);
+        std::thread::sleep(CACHE_CHECK_INTERVAL);
+    });
+}
+
+#[tauri::command]
+pub async fn get_cache_stats(app: AppHandle) -> Result<Vec<CacheStats>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let settings = load_storage_settings(&app);
+        CacheKind::ALL.iter()
+            .map(|&kind| cache_stats(&app, kind, &settings))
+            .collect()
+    })
+    .await
+    .map_err(|e| format!("Failed to read cache sizes: {}", e))?
+}
+
+/// Delete everything in one cache, returning the bytes freed
+#[tauri::command]
+pub async fn clear_cache(app: AppHandle, kind: CacheKind) -> Result<u64, String> {
+    tauri::async_runtime::spawn_blocking(move || clear_cache_kind(&app, kind))
+        .await
+        .map_err(|e| format!("Failed to clear cache: {}", e))?
+}
+
+#[tauri::command]
+pub fn get_storage_settings(app: AppHandle) -> StorageSettings {
+    load_storage_settings(&app)
+}
+
+/// Save cache caps and apply them right away
+#[tauri::command]
+pub async fn save_storage_settings(app: AppHandle, settings: StorageSettings) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        save_json(&app, STORAGE_SETTINGS_FILE, &settings)?;
+        enforce_cache_limits(&app);
+        Ok(())
+    })
+    .await
+    .map_err(|e| format!("Failed to save storage settings: {}", e))?
+}
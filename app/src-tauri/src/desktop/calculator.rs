@@ -0,0 +1,432 @@
+use tauri::AppHandle;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+
+use crate::desktop::{http_client_builder, load_json_or_default, now_millis, save_json};
+
+const CURRENCY_RATES_FILE: &str = "currency_rates.json";
+const CURRENCY_RATES_URL: &str = "https://open.er-api.com/v6/latest/USD";
+/// Cached rates are refreshed in the background once they are older than this
+const CURRENCY_RATES_MAX_AGE_MS: u64 = 12 * 60 * 60 * 1000;
+const CURRENCY_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExpressionResult {
+    pub value: f64,
+    /// Value with its unit, ready to show or copy
+    pub formatted: String,
+    pub unit: Option<String>,
+    /// Unix millis of the exchange rates used, for currency conversions
+    #[serde(rename = "ratesUpdatedAt")]
+    pub rates_updated_at: Option<u64>,
+}
+
+/// Exchange rates per US dollar
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+struct CurrencyRates {
+    #[serde(rename = "updatedAt")]
+    updated_at: u64,
+    rates: HashMap<String, f64>,
+}
+
+static CURRENCY_RATES: LazyLock<Mutex<Option<CurrencyRates>>> = LazyLock::new(|| Mutex::new(None));
+static RATES_REFRESHING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Dimension {
+    Length,
+    Mass,
+    Volume,
+    Time,
+    Data,
+    Temperature,
+}
+
+/// Unit name, dimension and factor to the dimension's base unit
+const UNITS: &[(&str, Dimension, f64)] = &[
+    ("mm", Dimension::Length, 0.001),
+    ("cm", Dimension::Length, 0.01),
+    ("m", Dimension::Length, 1.0),
+    ("km", Dimension::Length, 1000.0),
+    ("in", Dimension::Length, 0.0254),
+    ("inch", Dimension::Length, 0.0254),
+    ("inches", Dimension::Length, 0.0254),
+    ("ft", Dimension::Length, 0.3048),
+    ("feet", Dimension::Length, 0.3048),
+    ("yd", Dimension::Length, 0.9144),
+    ("mi", Dimension::Length, 1609.344),
+    ("miles", Dimension::Length, 1609.344),
+    ("mg", Dimension::Mass, 0.001),
+    ("g", Dimension::Mass, 1.0),
+    ("kg", Dimension::Mass, 1000.0),
+    ("t", Dimension::Mass, 1_000_000.0),
+    ("oz", Dimension::Mass, 28.349523125),
+    ("lb", Dimension::Mass, 453.59237),
+    ("lbs", Dimension::Mass, 453.59237),
+    ("ml", Dimension::Volume, 0.001),
+    ("l", Dimension::Volume, 1.0),
+    ("tsp", Dimension::Volume, 0.00492892159375),
+    ("tbsp", Dimension::Volume, 0.01478676478125),
+    ("floz", Dimension::Volume, 0.0295735295625),
+    ("cup", Dimension::Volume, 0.2365882365),
+    ("cups", Dimension::Volume, 0.2365882365),
+    ("pt", Dimension::Volume, 0.473176473),
+    ("gal", Dimension::Volume, 3.785411784),
+    ("ms", Dimension::Time, 0.001),
+    ("s", Dimension::Time, 1.0),
+    ("sec", Dimension::Time, 1.0),
+    ("min", Dimension::Time, 60.0),
+    ("h", Dimension::Time, 3600.0),
+    ("hr", Dimension::Time, 3600.0),
+    ("hours", Dimension::Time, 3600.0),
+    ("day", Dimension::Time, 86400.0),
+    ("days", Dimension::Time, 86400.0),
+    ("week", Dimension::Time, 604800.0),
+    ("weeks", Dimension::Time, 604800.0),
+    ("b", Dimension::Data, 1.0),
+    ("kb", Dimension::Data, 1e3),
+    ("mb", Dimension::Data, 1e6),
+    ("gb", Dimension::Data, 1e9),
+    ("tb", Dimension::Data, 1e12),
+    ("kib", Dimension::Data, 1024.0),
+    ("mib", Dimension::Data, 1_048_576.0),
+    ("gib", Dimension::Data, 1_073_741_824.0),
+    ("c", Dimension::Temperature, 0.0),
+    ("°c", Dimension::Temperature, 0.0),
+    ("f", Dimension::Temperature, 0.0),
+    ("°f", Dimension::Temperature, 0.0),
+    ("k", Dimension::Temperature, 0.0),
+];
+
+fn find_unit(name: &str) -> Option<(&'static str, Dimension, f64)> {
+    let name = name.to_lowercase();
+    UNITS.iter().find(|(unit, _, _)| *unit == name).copied()
+}
+
+fn to_kelvin(value: f64, unit: &str) -> f64 {
+    match unit.trim_start_matches('°') {
+        "c" => value + 273.15,
+        "f" => (value - 32.0) * 5.0 / 9.0 + 273.15,
+        _ => value,
+    }
+}
+
+fn from_kelvin(value: f64, unit: &str) -> f64 {
+    match unit.trim_start_matches('°') {
+        "c" => value - 273.15,
+        "f" => (value - 273.15) * 9.0 / 5.0 + 32.0,
+        _ => value,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Ident(String),
+    Op(char),
+}
+
+fn tokenize(text: &str) -> Option<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let chars: Vec<char> = text.chars().collect();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_digit() || c == '.' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.' || chars[i] == '_') {
+                i += 1;
+            }
+            let number: String = chars[start..i].iter().filter(|c| **c != '_').collect();
+            tokens.push(Token::Number(number.parse().ok()?));
+        } else if c.is_alphabetic() {
+            let start = i;
+            while i < chars.len() && chars[i].is_alphanumeric() {
+                i += 1;
+            }
+            tokens.push(Token::Ident(chars[start..i].iter().collect::<String>().to_lowercase()));
+        } else if "+-*/%^()×÷".contains(c) {
+            tokens.push(Token::Op(match c {
+                '×' => '*',
+                '÷' => '/',
+                other => other,
+            }));
+            i += 1;
+        } else {
+            return None;
+        }
+    }
+    Some(tokens)
+}
+
+/// Recursive descent over +, -, *, /, %, ^, parentheses, constants and a few functions
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn eat(&mut self, op: char) -> bool {
+        if self.peek() == Some(&Token::Op(op)) {
+            self.position += 1;
+            return true;
+        }
+        false
+    }
+
+    fn expression(&mut self) -> Option<f64> {
+        let mut value = self.term()?;
+        loop {
+            if self.eat('+') {
+                value += self.term()?;
+            } else if self.eat('-') {
+                value -= self.term()?;
+            } else {
+                return Some(value);
+            }
+        }
+    }
+
+    fn term(&mut self) -> Option<f64> {
+        let mut value = self.power()?;
+        loop {
+            if self.eat('*') {
+                value *= self.power()?;
+            } else if self.eat('/') {
+                value /= self.power()?;
+            } else if self.eat('%') {
+                value %= self.power()?;
+            } else {
+                return Some(value);
+            }
+        }
+    }
+
+    fn power(&mut self) -> Option<f64> {
+        let base = self.unary()?;
+        if self.eat('^') {
+            // Right associative: 2^3^2 = 2^9
+            return Some(base.powf(self.power()?));
+        }
+        Some(base)
+    }
+
+    fn unary(&mut self) -> Option<f64> {
+        if self.eat('-') {
+            return Some(-self.unary()?);
+        }
+        if self.eat('+') {
+            return self.unary();
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Option<f64> {
+        match self.next()? {
+            Token::Number(value) => Some(value),
+            Token::Op('(') => {
+                let value = self.expression()?;
+                self.eat(')').then_some(value)
+            }
+            Token::Ident(name) => match name.as_str() {
+                "pi" => Some(std::f64::consts::PI),
+                "e" => Some(std::f64::consts::E),
+                function => {
+                    if !self.eat('(') {
+                        return None;
+                    }
+                    let argument = self.expression()?;
+                    if !self.eat(')') {
+                        return None;
+                    }
+                    match function {
+                        "sqrt" => Some(argument.sqrt()),
+                        "abs" => Some(argument.abs()),
+                        "round" => Some(argument.round()),
+                        "floor" => Some(argument.floor()),
+                        "ceil" => Some(argument.ceil()),
+                        "ln" => Some(argument.ln()),
+                        "log" => Some(argument.log10()),
+                        "sin" => Some(argument.sin()),
+                        "cos" => Some(argument.cos()),
+                        "tan" => Some(argument.tan()),
+                        _ => None,
+                    }
+                }
+            },
+            Token::Op(_) => None,
+        }
+    }
+}
+
+fn evaluate_arithmetic(text: &str) -> Option<f64> {
+    let tokens = tokenize(text)?;
+    if tokens.is_empty() {
+        return None;
+    }
+    let mut parser = Parser { tokens, position: 0 };
+    let value = parser.expression()?;
+    (parser.position == parser.tokens.len() && value.is_finite()).then_some(value)
+}
+
+fn format_number(value: f64) -> String {
+    if value.abs() >= 1e15 || (value != 0.0 && value.abs() < 1e-6) {
+        return format!("{:e}", value);
+    }
+    let formatted = format!("{:.10}", value);
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+    if trimmed == "-0" { "0".to_string() } else { trimmed.to_string() }
+}
+
+fn load_currency_rates(app: &AppHandle) -> Option<CurrencyRates> {
+    let mut cached = CURRENCY_RATES.lock().unwrap();
+    if cached.is_none() {
+        let rates: CurrencyRates = load_json_or_default(app, CURRENCY_RATES_FILE);
+        if !rates.rates.is_empty() {
+            *cached = Some(rates);
+        }
+    }
+    cached.clone()
+}
+
+fn fetch_currency_rates() -> Result<CurrencyRates, String> {
+    let response: serde_json::Value = http_client_builder()
+        .timeout(CURRENCY_TIMEOUT)
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?
+        .get(CURRENCY_RATES_URL)
+        .send()
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| format!("Exchange rate request failed: {}", e))?
+        .json()
+        .map_err(|e| format!("Invalid exchange rate response: {}", e))?;
+
+    let rates: HashMap<String, f64> = serde_json::from_value(response["rates"].clone())
+        .map_err(|e| format!("Invalid exchange rates: {}", e))?;
+    Ok(CurrencyRates { updated_at: now_millis(), rates })
+}
+
+/// Refresh stale rates without making the caller wait; conversions use the cache meanwhile
+fn refresh_currency_rates_if_stale(app: &AppHandle, rates: Option<&CurrencyRates>) {
+    if rates.is_some_and(|r| now_millis().saturating_sub(r.updated_at) < CURRENCY_RATES_MAX_AGE_MS) {
+        return;
+    }
+    if RATES_REFRESHING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let app_handle = app.clone();
+    std::thread::spawn(move || {
+        match fetch_currency_rates() {
+            Ok(rates) => {
+                info!("💱 Updated {} exchange rates", rates.rates.len());
+                if let Err(e) = save_json(&app_handle, CURRENCY_RATES_FILE, &rates) {
+                    error!("Failed to save exchange rates: {}", e);
+                }
+                *CURRENCY_RATES.lock().unwrap() = Some(rates);
+            }
+            Err(e) => warn!("⚠️ {}", e),
+        }
+        RATES_REFRESHING.store(false, Ordering::SeqCst);
+    });
+}
+
+fn is_currency_code(code: &str) -> bool {
+    code.len() == 3 && code.chars().all(|c| c.is_ascii_alphabetic())
+}
+
+fn convert(app: &AppHandle, value: f64, from: &str, to: &str) -> Option<ExpressionResult> {
+    if let (Some((from_name, from_dim, from_factor)), Some((to_name, to_dim, to_factor))) = (find_unit(from), find_unit(to)) {
+        if from_dim != to_dim {
+            return None;
+        }
+        let converted = if from_dim == Dimension::Temperature {
+            from_kelvin(to_kelvin(value, from_name), to_name)
+        } else {
+            value * from_factor / to_factor
+        };
+        return Some(ExpressionResult {
+            value: converted,
+            formatted: format!("{} {}", format_number(converted), to),
+            unit: Some(to.to_string()),
+            rates_updated_at: None,
+        });
+    }
+
+    if !is_currency_code(from) || !is_currency_code(to) {
+        return None;
+    }
+    let (from, to) = (from.to_uppercase(), to.to_uppercase());
+    let rates = load_currency_rates(app);
+    refresh_currency_rates_if_stale(app, rates.as_ref());
+    let rates = rates?;
+    let converted = value / rates.rates.get(&from)? * rates.rates.get(&to)?;
+
+    Some(ExpressionResult {
+        value: converted,
+        formatted: format!("{:.2} {}", converted, to),
+        unit: Some(to),
+        rates_updated_at: Some(rates.updated_at),
+    })
+}
+
+/// Split "12in to cm" into the amount expression, source unit and target unit
+fn split_conversion(text: &str) -> Option<(&str, &str, &str)> {
+    let (left, target) = [" to ", " in ", " as ", " -> "]
+        .iter()
+        .find_map(|separator| text.rsplit_once(separator))?;
+    let target = target.trim();
+    let left = left.trim_end();
+
+    let unit_start = left.char_indices()
+        .rev()
+        .take_while(|(_, c)| c.is_alphabetic() || *c == '°')
+        .last()
+        .map(|(index, _)| index)?;
+    let (amount, unit) = left.split_at(unit_start);
+    (!amount.trim().is_empty() && !target.is_empty()).then_some((amount.trim(), unit, target))
+}
+
+/// Answer arithmetic like `2^10 / 3` and conversions like `12in to cm`, `30 c to f` or `100 usd in eur`.
+/// Returns None when the text isn't something the calculator understands.
+#[tauri::command]
+pub fn evaluate_expression(app: AppHandle, text: String) -> Option<ExpressionResult> {
+    let text = text.trim().trim_start_matches('=').trim();
+    if text.is_empty() {
+        return None;
+    }
+
+    if let Some((amount, from, to)) = split_conversion(text) {
+        if let Some(result) = evaluate_arithmetic(amount).and_then(|value| convert(&app, value, from, to)) {
+            return Some(result);
+        }
+    }
+
+    // A lone number is not worth answering
+    let value = evaluate_arithmetic(text)?;
+    if text.parse::<f64>().is_ok() {
+        return None;
+    }
+    Some(ExpressionResult {
+        value,
+        formatted: format_number(value),
+        unit: None,
+        rates_updated_at: None,
+    })
+}
@@ -0,0 +1,640 @@
+use tauri::{AppHandle, Emitter, Url};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+use chrono::{Local, NaiveDate, NaiveDateTime, TimeZone};
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use reqwest::blocking::Client;
+use reqwest::{Method, StatusCode};
+
+use crate::desktop::{
+    delete_secret, escape_ics_text, http_client_builder, ics_utc_stamp, is_background_sync_allowed, load_json_or_default,
+    now_millis, push_ics_line, read_secret, save_json, store_secret,
+};
+
+const CALDAV_CONFIG_FILE: &str = "caldav.json";
+const CALDAV_INDEX_FILE: &str = "caldav_index.json";
+const CALDAV_TODOS_FILE: &str = "caldav_todos.json";
+const CALDAV_KEYCHAIN_ACCOUNT: &str = "caldav";
+const CALDAV_TIMEOUT: Duration = Duration::from_secs(30);
+const SYNC_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+const DEFAULT_SYNC_INTERVAL_MINUTES: u64 = 15;
+/// UIDs of tasks created from Blinko notes
+const UID_PREFIX: &str = "blinko-note-";
+
+const CALENDAR_QUERY: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<c:calendar-query xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+  <d:prop><d:getetag/><c:calendar-data/></d:prop>
+  <c:filter><c:comp-filter name="VCALENDAR"><c:comp-filter name="VTODO"/></c:comp-filter></c:filter>
+</c:calendar-query>"#;
+
+const RESOURCE_TYPE_QUERY: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<d:propfind xmlns:d="DAV:"><d:prop><d:resourcetype/></d:prop></d:propfind>"#;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CalDavConfig {
+    pub enabled: bool,
+    /// Task list collection, e.g. `https://cloud.example.com/remote.php/dav/calendars/me/tasks/`
+    #[serde(rename = "calendarUrl")]
+    pub calendar_url: String,
+    /// Password or app token is kept in the keychain
+    pub username: String,
+    #[serde(rename = "syncIntervalMinutes")]
+    pub sync_interval_minutes: u64,
+    #[serde(rename = "lastSyncAt")]
+    pub last_sync_at: Option<u64>,
+}
+
+impl Default for CalDavConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            calendar_url: String::new(),
+            username: String::new(),
+            sync_interval_minutes: DEFAULT_SYNC_INTERVAL_MINUTES,
+            last_sync_at: None,
+        }
+    }
+}
+
+/// The synced part of a todo, on either side
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct TodoState {
+    pub title: String,
+    pub completed: bool,
+    /// Due time as unix milliseconds
+    pub due: Option<u64>,
+}
+
+/// Todo-style note as pushed by the frontend
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TodoNote {
+    pub id: i64,
+    #[serde(flatten)]
+    pub state: TodoState,
+}
+
+/// Payload of `caldav-todo-updated` (apply to the note) and `caldav-todo-created` (create a note, then link it)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CalDavTodoEvent {
+    pub uid: String,
+    #[serde(rename = "noteId")]
+    pub note_id: Option<i64>,
+    #[serde(flatten)]
+    pub state: TodoState,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CalDavSyncReport {
+    pub pushed: usize,
+    pub pulled: usize,
+    pub created: usize,
+    pub removed: usize,
+    pub failed: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct IndexEntry {
+    href: String,
+    etag: String,
+    /// `None` until the frontend linked a task created on the server to a note
+    #[serde(rename = "noteId")]
+    note_id: Option<i64>,
+    /// State both sides agreed on after the last sync
+    state: TodoState,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+struct CalDavIndex {
+    /// Keyed by task UID
+    todos: HashMap<String, IndexEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+struct TodoStore {
+    todos: Vec<TodoNote>,
+}
+
+struct RemoteTodo {
+    href: String,
+    etag: String,
+    uid: String,
+    state: TodoState,
+    /// Full calendar object, kept so fields we don't sync survive our updates
+    data: String,
+}
+
+static SYNC_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+static SYNC_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Load CalDAV account config from file
+pub fn load_caldav_config(app: &AppHandle) -> CalDavConfig {
+    load_json_or_default(app, CALDAV_CONFIG_FILE)
+}
+
+fn load_index(app: &AppHandle) -> CalDavIndex {
+    load_json_or_default(app, CALDAV_INDEX_FILE)
+}
+
+fn save_index(app: &AppHandle, index: &CalDavIndex) {
+    if let Err(e) = save_json(app, CALDAV_INDEX_FILE, index) {
+        error!("Failed to save CalDAV index: {}", e);
+    }
+}
+
+fn caldav_client() -> Result<Client, String> {
+    http_client_builder()
+        .timeout(CALDAV_TIMEOUT)
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))
+}
+
+fn dav_method(name: &str) -> Method {
+    Method::from_bytes(name.as_bytes()).expect("valid WebDAV method")
+}
+
+fn resolve_href(calendar_url: &str, href: &str) -> Result<String, String> {
+    Url::parse(calendar_url)
+        .and_then(|base| base.join(href))
+        .map(|url| url.to_string())
+        .map_err(|e| format!("Invalid CalDAV URL: {}", e))
+}
+
+fn unescape_ics_text(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            out.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => {}
+        }
+    }
+    out
+}
+
+fn unfold_ics(data: &str) -> Vec<String> {
+    data.replace("\r\n", "\n")
+        .replace("\n ", "")
+        .replace("\n\t", "")
+        .lines()
+        .map(String::from)
+        .collect()
+}
+
+/// Split `NAME;PARAMS:VALUE` into the upper-cased name, the parameters and the value
+fn split_property(line: &str) -> Option<(String, &str, &str)> {
+    let (head, value) = line.split_once(':')?;
+    let (name, params) = head.split_once(';').unwrap_or((head, ""));
+    Some((name.to_ascii_uppercase(), params, value))
+}
+
+/// Floating and TZID times are read as local time, close enough for due dates
+fn parse_ics_time(params: &str, value: &str) -> Option<u64> {
+    let value = value.trim();
+    if params.contains("VALUE=DATE") && !params.contains("VALUE=DATE-TIME") {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d").ok()?;
+        return Local.from_local_datetime(&date.and_hms_opt(0, 0, 0)?).earliest()
+            .map(|time| time.timestamp_millis() as u64);
+    }
+    if let Some(utc) = value.strip_suffix('Z') {
+        return NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()
+            .map(|time| time.and_utc().timestamp_millis() as u64);
+    }
+    let time = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+    Local.from_local_datetime(&time).earliest().map(|time| time.timestamp_millis() as u64)
+}
+
+/// UID and synced fields of the first VTODO in a calendar object
+fn parse_vtodo(data: &str) -> Option<(String, TodoState)> {
+    let mut uid = None;
+    let mut state = TodoState::default();
+    let mut depth = 0;
+    let mut in_todo = false;
+
+    for line in unfold_ics(data) {
+        let Some((name, params, value)) = split_property(&line) else { continue };
+        match name.as_str() {
+            "BEGIN" if value.eq_ignore_ascii_case("VTODO") && !in_todo => in_todo = true,
+            "BEGIN" if in_todo => depth += 1,
+            "END" if in_todo && depth > 0 => depth -= 1,
+            "END" if in_todo => break,
+            // Nested components like VALARM have properties of their own
+            _ if !in_todo || depth > 0 => {}
+            "UID" => uid = Some(value.to_string()),
+            "SUMMARY" => state.title = unescape_ics_text(value),
+            "STATUS" => state.completed |= value.eq_ignore_ascii_case("COMPLETED"),
+            "COMPLETED" => state.completed = true,
+            "DUE" => state.due = parse_ics_time(params, value),
+            _ => {}
+        }
+    }
+
+    Some((uid?, state))
+}
+
+fn push_todo_properties(ics: &mut String, state: &TodoState) {
+    let now = ics_utc_stamp(now_millis());
+    push_ics_line(ics, &format!("SUMMARY:{}", escape_ics_text(&state.title)));
+    if let Some(due) = state.due {
+        push_ics_line(ics, &format!("DUE:{}", ics_utc_stamp(due)));
+    }
+    if state.completed {
+        push_ics_line(ics, "STATUS:COMPLETED");
+        push_ics_line(ics, &format!("COMPLETED:{}", now));
+        push_ics_line(ics, "PERCENT-COMPLETE:100");
+    } else {
+        push_ics_line(ics, "STATUS:NEEDS-ACTION");
+    }
+    push_ics_line(ics, &format!("DTSTAMP:{}", now));
+    push_ics_line(ics, &format!("LAST-MODIFIED:{}", now));
+}
+
+fn new_vtodo(uid: &str, state: &TodoState) -> String {
+    let mut ics = String::new();
+    push_ics_line(&mut ics, "BEGIN:VCALENDAR");
+    push_ics_line(&mut ics, "VERSION:2.0");
+    push_ics_line(&mut ics, "PRODID:-//Blinko//Blinko Desktop//EN");
+    push_ics_line(&mut ics, "BEGIN:VTODO");
+    push_ics_line(&mut ics, &format!("UID:{}", uid));
+    push_todo_properties(&mut ics, state);
+    push_ics_line(&mut ics, "END:VTODO");
+    push_ics_line(&mut ics, "END:VCALENDAR");
+    ics
+}
+
+/// Rewrite the synced properties of an existing task, everything else is kept as is
+fn update_vtodo(data: &str, state: &TodoState) -> String {
+    const SYNCED: [&str; 7] = ["SUMMARY", "STATUS", "COMPLETED", "PERCENT-COMPLETE", "DUE", "DTSTAMP", "LAST-MODIFIED"];
+    let mut ics = String::new();
+    let mut depth = 0;
+    let mut in_todo = false;
+
+    for line in unfold_ics(data) {
+        let name = split_property(&line).map(|(name, _, _)| name).unwrap_or_default();
+        let value = line.split_once(':').map(|(_, value)| value).unwrap_or_default();
+        match name.as_str() {
+            "BEGIN" if value.eq_ignore_ascii_case("VTODO") && !in_todo => in_todo = true,
+            "BEGIN" if in_todo => depth += 1,
+            "END" if in_todo && depth > 0 => depth -= 1,
+            "END" if in_todo => {
+                push_todo_properties(&mut ics, state);
+                in_todo = false;
+            }
+            _ if in_todo && depth == 0 && SYNCED.contains(&name.as_str()) => continue,
+            _ => {}
+        }
+        push_ics_line(&mut ics, &line);
+    }
+    ics
+}
+
+/// `href`, `getetag` and `calendar-data` of every response in a multistatus body
+fn parse_multistatus(body: &str) -> Result<Vec<(String, String, String)>, String> {
+    let mut reader = Reader::from_str(body);
+    let mut results = Vec::new();
+    let (mut href, mut etag, mut data) = (String::new(), String::new(), String::new());
+    let mut current = String::new();
+    let mut text = String::new();
+
+    loop {
+        match reader.read_event().map_err(|e| format!("Invalid CalDAV response: {}", e))? {
+            Event::Start(e) => {
+                current = String::from_utf8_lossy(e.local_name().as_ref()).to_string();
+                text.clear();
+            }
+            Event::Text(e) => {
+                text.push_str(&e.unescape().map_err(|e| format!("Invalid CalDAV response: {}", e))?);
+            }
+            Event::CData(e) => text.push_str(&String::from_utf8_lossy(&e.into_inner())),
+            Event::End(e) => {
+                match e.local_name().as_ref() {
+                    b"href" if current == "href" => href = text.trim().to_string(),
+                    b"getetag" => etag = text.trim().to_string(),
+                    b"calendar-data" => data = std::mem::take(&mut text),
+                    b"response" => {
+                        if !data.is_empty() {
+                            results.push((std::mem::take(&mut href), std::mem::take(&mut etag), std::mem::take(&mut data)));
+                        }
+                        href.clear();
+                        etag.clear();
+                    }
+                    _ => {}
+                }
+                current.clear();
+                text.clear();
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(results)
+}
+
+fn fetch_remote_todos(client: &Client, config: &CalDavConfig, password: &str) -> Result<Vec<RemoteTodo>, String> {
+    let response = client.request(dav_method("REPORT"), &config.calendar_url)
+        .basic_auth(&config.username, Some(password))
+        .header("Depth", "1")
+        .header("Content-Type", "application/xml; charset=utf-8")
+        .body(CALENDAR_QUERY)
+        .send()
+        .map_err(|e| format!("CalDAV request failed: {}", e))?;
+    if response.status() != StatusCode::MULTI_STATUS {
+        return Err(format!("CalDAV server answered {}", response.status()));
+    }
+    let body = response.text().map_err(|e| format!("Failed to read CalDAV response: {}", e))?;
+
+    Ok(parse_multistatus(&body)?
+        .into_iter()
+        .filter_map(|(href, etag, data)| {
+            let (uid, state) = parse_vtodo(&data)?;
+            Some(RemoteTodo { href, etag, uid, state, data })
+        })
+        .collect())
+}
+
+/// Upload a task, `etag` is `None` for new ones; returns the new ETag (empty if the server didn't send one)
+fn put_todo(client: &Client, config: &CalDavConfig, password: &str, href: &str, data: String, etag: Option<&str>) -> Result<String, String> {
+    let mut request = client.put(resolve_href(&config.calendar_url, href)?)
+        .basic_auth(&config.username, Some(password))
+        .header("Content-Type", "text/calendar; charset=utf-8")
+        .body(data);
+    // Never overwrite a task that changed on the server since we fetched it
+    request = match etag {
+        Some(etag) if !etag.is_empty() => request.header("If-Match", etag),
+        Some(_) => request,
+        None => request.header("If-None-Match", "*"),
+    };
+
+    let response = request.send().map_err(|e| format!("CalDAV upload failed: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("CalDAV server rejected {}: {}", href, response.status()));
+    }
+    Ok(response.headers()
+        .get("ETag")
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_string())
+}
+
+/// Three-way merge per field: whichever side changed since the last sync wins, local edits first
+fn merge_todo(base: &TodoState, local: &TodoState, remote: &TodoState) -> TodoState {
+    TodoState {
+        title: if local.title != base.title { local.title.clone() } else { remote.title.clone() },
+        completed: if local.completed != base.completed { local.completed } else { remote.completed },
+        due: if local.due != base.due { local.due } else { remote.due },
+    }
+}
+
+fn emit_todo_event(app: &AppHandle, event: &str, uid: &str, note_id: Option<i64>, state: &TodoState) {
+    let payload = CalDavTodoEvent { uid: uid.to_string(), note_id, state: state.clone() };
+    if let Err(e) = app.emit(event, payload) {
+        error!("Failed to emit {} event: {}", event, e);
+    }
+}
+
+/// Sync the cached todo notes with the task list, changes for notes go to the frontend as events
+pub fn run_caldav_sync(app: &AppHandle) -> Result<CalDavSyncReport, String> {
+    let _guard = SYNC_LOCK.lock().unwrap();
+    let mut config = load_caldav_config(app);
+    if !config.enabled || config.calendar_url.is_empty() {
+        return Err("CalDAV sync is not set up".to_string());
+    }
+    let password = read_secret(Some(CALDAV_KEYCHAIN_ACCOUNT))?
+        .ok_or("CalDAV password is missing, please set up the account again")?;
+
+    let client = caldav_client()?;
+    let remote_todos = fetch_remote_todos(&client, &config, &password)?;
+    let local_todos: HashMap<i64, TodoState> = load_json_or_default::<TodoStore>(app, CALDAV_TODOS_FILE)
+        .todos
+        .into_iter()
+        .map(|todo| (todo.id, todo.state))
+        .collect();
+    let mut index = load_index(app);
+    let mut report = CalDavSyncReport::default();
+
+    for remote in remote_todos.iter() {
+        let Some(entry) = index.todos.get_mut(&remote.uid) else {
+            // Created on the server, the frontend links it once the note exists
+            emit_todo_event(app, "caldav-todo-created", &remote.uid, None, &remote.state);
+            index.todos.insert(remote.uid.clone(), IndexEntry {
+                href: remote.href.clone(),
+                etag: remote.etag.clone(),
+                note_id: None,
+                state: remote.state.clone(),
+            });
+            report.created += 1;
+            continue;
+        };
+
+        let local = entry.note_id.and_then(|id| local_todos.get(&id));
+        let merged = match local {
+            Some(local) => merge_todo(&entry.state, local, &remote.state),
+            None => remote.state.clone(),
+        };
+
+        entry.href = remote.href.clone();
+        entry.etag = remote.etag.clone();
+        if merged != remote.state {
+            match put_todo(&client, &config, &password, &remote.href, update_vtodo(&remote.data, &merged), Some(&remote.etag)) {
+                Ok(etag) => {
+                    entry.etag = etag;
+                    report.pushed += 1;
+                }
+                Err(e) => {
+                    // Retried next time, the base state stays as it was
+                    warn!("⚠️ {}", e);
+                    report.failed += 1;
+                    continue;
+                }
+            }
+        }
+        if local.is_some_and(|local| *local != merged) {
+            emit_todo_event(app, "caldav-todo-updated", &remote.uid, entry.note_id, &merged);
+            report.pulled += 1;
+        }
+        entry.state = merged;
+    }
+
+    // Deleted on the server
+    let remote_uids: HashSet<&str> = remote_todos.iter().map(|remote| remote.uid.as_str()).collect();
+    index.todos.retain(|uid, entry| {
+        if remote_uids.contains(uid.as_str()) {
+            return true;
+        }
+        if entry.note_id.is_some() {
+            if let Err(e) = app.emit("caldav-todo-removed", CalDavTodoEvent { uid: uid.clone(), note_id: entry.note_id, state: entry.state.clone() }) {
+                error!("Failed to emit caldav-todo-removed event: {}", e);
+            }
+            report.removed += 1;
+        }
+        false
+    });
+
+    // New todo notes
+    let linked: HashSet<i64> = index.todos.values().filter_map(|entry| entry.note_id).collect();
+    for (id, state) in local_todos.iter().filter(|(id, _)| !linked.contains(id)) {
+        let uid = format!("{}{}", UID_PREFIX, id);
+        let href = format!("{}.ics", uid);
+        match put_todo(&client, &config, &password, &href, new_vtodo(&uid, state), None) {
+            Ok(etag) => {
+                index.todos.insert(uid, IndexEntry { href, etag, note_id: Some(*id), state: state.clone() });
+                report.pushed += 1;
+            }
+            Err(e) => {
+                warn!("⚠️ {}", e);
+                report.failed += 1;
+            }
+        }
+    }
+
+    save_index(app, &index);
+    config.last_sync_at = Some(now_millis());
+    save_json(app, CALDAV_CONFIG_FILE, &config)?;
+
+    info!(
+        "✅ CalDAV sync: {} pushed, {} pulled, {} created, {} removed, {} failed",
+        report.pushed, report.pulled, report.created, report.removed, report.failed
+    );
+    if let Err(e) = app.emit("caldav-sync-finished", &report) {
+        error!("Failed to emit caldav-sync-finished event: {}", e);
+    }
+    Ok(report)
+}
+
+/// Start the periodic background sync (runs for the app lifetime)
+pub fn start_caldav_sync(app: &AppHandle) {
+    if SYNC_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let app_handle = app.clone();
+    std::thread::spawn(move || loop {
+        std::thread::sleep(SYNC_CHECK_INTERVAL);
+
+        let config = load_caldav_config(&app_handle);
+        let interval = config.sync_interval_minutes.max(1) * 60 * 1000;
+        let due = config.last_sync_at.map(|last| now_millis().saturating_sub(last) >= interval).unwrap_or(true);
+        if config.enabled && due && is_background_sync_allowed() {
+            if let Err(e) = run_caldav_sync(&app_handle) {
+                error!("❌ CalDAV sync failed: {}", e);
+            }
+        }
+    });
+}
+
+/// Check that the URL is a calendar collection the credentials can read
+fn verify_calendar(config: &CalDavConfig, password: &str) -> Result<(), String> {
+    let response = caldav_client()?
+        .request(dav_method("PROPFIND"), &config.calendar_url)
+        .basic_auth(&config.username, Some(password))
+        .header("Depth", "0")
+        .header("Content-Type", "application/xml; charset=utf-8")
+        .body(RESOURCE_TYPE_QUERY)
+        .send()
+        .map_err(|e| format!("Failed to reach CalDAV server: {}", e))?;
+
+    match response.status() {
+        StatusCode::MULTI_STATUS => {}
+        StatusCode::UNAUTHORIZED => return Err("CalDAV login failed, check username and password".to_string()),
+        status => return Err(format!("CalDAV server answered {}", status)),
+    }
+
+    let body = response.text().map_err(|e| format!("Failed to read CalDAV response: {}", e))?;
+    let mut reader = Reader::from_str(&body);
+    loop {
+        match reader.read_event().map_err(|e| format!("Invalid CalDAV response: {}", e))? {
+            Event::Start(e) | Event::Empty(e) if e.local_name().as_ref() == b"calendar" => return Ok(()),
+            Event::Eof => return Err("The URL is not a CalDAV calendar".to_string()),
+            _ => {}
+        }
+    }
+}
+
+#[tauri::command]
+pub fn get_caldav_config(app: AppHandle) -> CalDavConfig {
+    load_caldav_config(&app)
+}
+
+/// Verify and save the task list account, the password goes to the keychain
+#[tauri::command]
+pub async fn save_caldav_account(
+    app: AppHandle,
+    calendar_url: String,
+    username: String,
+    password: String,
+    sync_interval_minutes: Option<u64>,
+) -> Result<CalDavConfig, String> {
+    let mut calendar_url = calendar_url.trim().to_string();
+    if !calendar_url.starts_with("http://") && !calendar_url.starts_with("https://") {
+        return Err(format!("Invalid CalDAV URL: {}", calendar_url));
+    }
+    // Task hrefs are resolved relative to the collection
+    if !calendar_url.ends_with('/') {
+        calendar_url.push('/');
+    }
+
+    let previous = load_caldav_config(&app);
+    let config = CalDavConfig {
+        enabled: true,
+        // A different task list starts from scratch
+        last_sync_at: previous.last_sync_at.filter(|_| previous.calendar_url == calendar_url),
+        calendar_url,
+        username,
+        sync_interval_minutes: sync_interval_minutes.unwrap_or(previous.sync_interval_minutes),
+    };
+
+    tauri::async_runtime::spawn_blocking(move || {
+        verify_calendar(&config, &password)?;
+        store_secret(Some(CALDAV_KEYCHAIN_ACCOUNT), &password)?;
+        if previous.calendar_url != config.calendar_url {
+            save_index(&app, &CalDavIndex::default());
+        }
+        save_json(&app, CALDAV_CONFIG_FILE, &config)?;
+        info!("📋 CalDAV account saved for {}", config.calendar_url);
+        Ok(config)
+    })
+    .await
+    .map_err(|e| format!("CalDAV setup failed: {}", e))?
+}
+
+#[tauri::command]
+pub fn remove_caldav_account(app: AppHandle) -> Result<(), String> {
+    delete_secret(Some(CALDAV_KEYCHAIN_ACCOUNT))?;
+    save_json(&app, CALDAV_CONFIG_FILE, &CalDavConfig::default())?;
+    save_index(&app, &CalDavIndex::default());
+    info!("📋 CalDAV account removed");
+    Ok(())
+}
+
+/// Replace the todo notes the next sync works with
+#[tauri::command]
+pub fn set_caldav_todos(app: AppHandle, todos: Vec<TodoNote>) -> Result<(), String> {
+    save_json(&app, CALDAV_TODOS_FILE, &TodoStore { todos })
+}
+
+/// Attach a task created on the server to the note the frontend made for it
+#[tauri::command]
+pub fn link_caldav_todo(app: AppHandle, uid: String, note_id: i64) -> Result<(), String> {
+    let _guard = SYNC_LOCK.lock().unwrap();
+    let mut index = load_index(&app);
+    let entry = index.todos.get_mut(&uid).ok_or_else(|| format!("Unknown CalDAV task: {}", uid))?;
+    entry.note_id = Some(note_id);
+    save_index(&app, &index);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn sync_caldav_now(app: AppHandle) -> Result<CalDavSyncReport, String> {
+    tauri::async_runtime::spawn_blocking(move || run_caldav_sync(&app))
+        .await
+        .map_err(|e| format!("CalDAV sync task failed: {}", e))?
+}
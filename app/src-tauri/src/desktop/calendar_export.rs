@@ -0,0 +1,246 @@
+use tauri::AppHandle;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{LazyLock, Mutex};
+use chrono::{Datelike, Local, TimeZone, Utc};
+
+use crate::desktop::{get_app_data_subdir, list_reminders, load_json_or_default, now_millis, save_json, Reminder, RepeatRule};
+
+const CALENDAR_CONFIG_FILE: &str = "calendar_export.json";
+const CALENDAR_NOTES_FILE: &str = "calendar_notes.json";
+const CALENDAR_DIR: &str = "calendar";
+const CALENDAR_FILE_NAME: &str = "blinko.ics";
+/// RFC 5545 lines are folded at 75 octets
+const ICS_LINE_LIMIT: usize = 75;
+const EVENT_DURATION: &str = "PT15M";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CalendarExportConfig {
+    pub enabled: bool,
+    /// Where the feed is written, defaults to `calendar/blinko.ics` in app data
+    pub path: Option<String>,
+    /// Also serve the feed as `/calendar.ics` from the local API
+    #[serde(rename = "serveFeed", default)]
+    pub serve_feed: bool,
+}
+
+/// Note with a date, pushed by the frontend since notes live on the server
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CalendarNote {
+    pub id: i64,
+    pub title: String,
+    #[serde(default)]
+    pub content: String,
+    /// Scheduled time as unix milliseconds
+    pub date: u64,
+    #[serde(rename = "allDay", default)]
+    pub all_day: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+struct CalendarNoteStore {
+    notes: Vec<CalendarNote>,
+}
+
+static CALENDAR_CONFIG: LazyLock<Mutex<Option<CalendarExportConfig>>> = LazyLock::new(|| Mutex::new(None));
+static CALENDAR_NOTES: LazyLock<Mutex<Option<CalendarNoteStore>>> = LazyLock::new(|| Mutex::new(None));
+
+fn read_config(app: &AppHandle) -> CalendarExportConfig {
+    CALENDAR_CONFIG.lock().unwrap()
+        .get_or_insert_with(|| load_json_or_default(app, CALENDAR_CONFIG_FILE))
+        .clone()
+}
+
+fn read_notes(app: &AppHandle) -> Vec<CalendarNote> {
+    CALENDAR_NOTES.lock().unwrap()
+        .get_or_insert_with(|| load_json_or_default(app, CALENDAR_NOTES_FILE))
+        .notes
+        .clone()
+}
+
+fn feed_path(app: &AppHandle, config: &CalendarExportConfig) -> Result<PathBuf, String> {
+    match config.path.as_deref().filter(|p| !p.trim().is_empty()) {
+        Some(path) => Ok(PathBuf::from(path)),
+        None => get_app_data_subdir(app, CALENDAR_DIR).map(|dir| dir.join(CALENDAR_FILE_NAME)),
+    }
+}
+
+/// Escape TEXT values (commas, semicolons, backslashes, newlines)
+pub fn escape_ics_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Append a content line, folding it without splitting UTF-8 characters
+pub fn push_ics_line(ics: &mut String, line: &str) {
+    let mut width = 0;
+    for ch in line.chars() {
+        if width + ch.len_utf8() > ICS_LINE_LIMIT {
+            ics.push_str("\r\n ");
+            width = 1;
+        }
+        ics.push(ch);
+        width += ch.len_utf8();
+    }
+    ics.push_str("\r\n");
+}
+
+/// Unix milliseconds as an ICS UTC date-time, e.g. `20261015T120000Z`
+pub fn ics_utc_stamp(millis: u64) -> String {
+    Utc.timestamp_millis_opt(millis as i64)
+        .single()
+        .map(|time| time.format("%Y%m%dT%H%M%SZ").to_string())
+        .unwrap_or_default()
+}
+
+fn repeat_rule(reminder: &Reminder, start: u64) -> Option<String> {
+    let rule = match reminder.repeat {
+        RepeatRule::None => return None,
+        RepeatRule::Hourly => "FREQ=HOURLY",
+        RepeatRule::Daily => "FREQ=DAILY",
+        RepeatRule::Weekdays => "FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR",
+        RepeatRule::Weekly => "FREQ=WEEKLY",
+        RepeatRule::Monthly => {
+            // Plain FREQ=MONTHLY skips months without the day, the reminder uses their last day
+            let day = Utc.timestamp_millis_opt(start as i64).single().map_or(1, |time| time.day());
+            if day > 28 {
+                return Some(format!("FREQ=MONTHLY;BYMONTHDAY={},-1;BYSETPOS=1", day));
+            }
+            "FREQ=MONTHLY"
+        }
+    };
+    Some(rule.to_string())
+}
+
+fn push_reminder(ics: &mut String, reminder: &Reminder, stamp: &str) {
+    push_ics_line(ics, "BEGIN:VEVENT");
+    push_ics_line(ics, &format!("UID:reminder-{}@blinko", reminder.id));
+    push_ics_line(ics, &format!("DTSTAMP:{}", stamp));
+    // Monthly repeats are counted from the anchor, the next fire time may be a clamped day
+    let start = match reminder.repeat {
+        RepeatRule::Monthly => reminder.anchor_at.unwrap_or(reminder.fire_at),
+        _ => reminder.fire_at,
+    };
+    push_ics_line(ics, &format!("DTSTART:{}", ics_utc_stamp(start)));
+    push_ics_line(ics, &format!("DURATION:{}", EVENT_DURATION));
+    push_ics_line(ics, &format!("SUMMARY:{}", escape_ics_text(&reminder.title)));
+    if !reminder.body.is_empty() {
+        push_ics_line(ics, &format!("DESCRIPTION:{}", escape_ics_text(&reminder.body)));
+    }
+    if let Some(rule) = repeat_rule(reminder, start) {
+        push_ics_line(ics, &format!("RRULE:{}", rule));
+    }
+    if let Some(note_id) = reminder.note_id {
+        push_ics_line(ics, &format!("URL:blinko://note/{}", note_id));
+    }
+    push_ics_line(ics, "BEGIN:VALARM");
+    push_ics_line(ics, "ACTION:DISPLAY");
+    push_ics_line(ics, &format!("DESCRIPTION:{}", escape_ics_text(&reminder.title)));
+    push_ics_line(ics, "TRIGGER:PT0S");
+    push_ics_line(ics, "END:VALARM");
+    push_ics_line(ics, "END:VEVENT");
+}
+
+fn push_note(ics: &mut String, note: &CalendarNote, stamp: &str) {
+    push_ics_line(ics, "BEGIN:VEVENT");
+    push_ics_line(ics, &format!("UID:note-{}@blinko", note.id));
+    push_ics_line(ics, &format!("DTSTAMP:{}", stamp));
+    if note.all_day {
+        // All-day events use the local date, not the UTC one
+        let date = Local.timestamp_millis_opt(note.date as i64).single().unwrap_or_default();
+        push_ics_line(ics, &format!("DTSTART;VALUE=DATE:{}", date.format("%Y%m%d")));
+    } else {
+        push_ics_line(ics, &format!("DTSTART:{}", ics_utc_stamp(note.date)));
+        push_ics_line(ics, &format!("DURATION:{}", EVENT_DURATION));
+    }
+    push_ics_line(ics, &format!("SUMMARY:{}", escape_ics_text(&note.title)));
+    if !note.content.is_empty() {
+        push_ics_line(ics, &format!("DESCRIPTION:{}", escape_ics_text(&note.content)));
+    }
+    push_ics_line(ics, &format!("URL:blinko://note/{}", note.id));
+    push_ics_line(ics, "END:VEVENT");
+}
+
+/// ICS calendar with the enabled reminders and the dated notes
+pub fn build_calendar_feed(app: &AppHandle) -> String {
+    let stamp = ics_utc_stamp(now_millis());
+    let mut ics = String::new();
+    push_ics_line(&mut ics, "BEGIN:VCALENDAR");
+    push_ics_line(&mut ics, "VERSION:2.0");
+    push_ics_line(&mut ics, "PRODID:-//Blinko//Blinko Desktop//EN");
+    push_ics_line(&mut ics, "CALSCALE:GREGORIAN");
+    push_ics_line(&mut ics, "X-WR-CALNAME:Blinko");
+
+    for reminder in list_reminders(app.clone()).iter().filter(|r| r.enabled) {
+        push_reminder(&mut ics, reminder, &stamp);
+    }
+    for note in read_notes(app).iter() {
+        push_note(&mut ics, note, &stamp);
+    }
+
+    push_ics_line(&mut ics, "END:VCALENDAR");
+    ics
+}
+
+/// Whether the local API should answer `/calendar.ics`
+pub fn is_calendar_feed_served(app: &AppHandle) -> bool {
+    let config = read_config(app);
+    config.enabled && config.serve_feed
+}
+
+fn write_calendar_feed(app: &AppHandle, config: &CalendarExportConfig) -> Result<PathBuf, String> {
+    let path = feed_path(app, config)?;
+    // Written next to the target and renamed, subscribed clients never read half a file
+    let tmp_path = path.with_extension("ics.tmp");
+    fs::write(&tmp_path, build_calendar_feed(app))
+        .map_err(|e| format!("Failed to write calendar feed: {}", e))?;
+    fs::rename(&tmp_path, &path)
+        .map_err(|e| format!("Failed to replace calendar feed: {}", e))?;
+    Ok(path)
+}
+
+/// Rewrite the feed file after reminders or dated notes changed
+pub fn refresh_calendar_export(app: &AppHandle) {
+    let config = read_config(app);
+    if !config.enabled {
+        return;
+    }
+    if let Err(e) = write_calendar_feed(app, &config) {
+        error!("❌ {}", e);
+    }
+}
+
+#[tauri::command]
+pub fn get_calendar_export_config(app: AppHandle) -> CalendarExportConfig {
+    read_config(&app)
+}
+
+#[tauri::command]
+pub fn save_calendar_export_config(app: AppHandle, config: CalendarExportConfig) -> Result<(), String> {
+    save_json(&app, CALENDAR_CONFIG_FILE, &config)?;
+    *CALENDAR_CONFIG.lock().unwrap() = Some(config);
+    refresh_calendar_export(&app);
+    Ok(())
+}
+
+/// Replace the dated notes included in the feed
+#[tauri::command]
+pub fn set_calendar_notes(app: AppHandle, notes: Vec<CalendarNote>) -> Result<(), String> {
+    let store = CalendarNoteStore { notes };
+    save_json(&app, CALENDAR_NOTES_FILE, &store)?;
+    *CALENDAR_NOTES.lock().unwrap() = Some(store);
+    refresh_calendar_export(&app);
+    Ok(())
+}
+
+/// Write the feed now and return its path, for "subscribe to this file" in calendar apps
+#[tauri::command]
+pub fn export_calendar(app: AppHandle) -> Result<String, String> {
+    let path = write_calendar_feed(&app, &read_config(&app))?;
+    info!("📅 Calendar exported to {}", path.display());
+    Ok(path.to_string_lossy().to_string())
+}
@@ -0,0 +1,43 @@
+use tauri::{AppHandle, Emitter};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex};
+
+// Not persisted, a restart always comes back with capture on
+static CAPTURE_PAUSED: AtomicBool = AtomicBool::new(false);
+static CAPTURE_MENU_ITEM: LazyLock<Mutex<Option<tauri::menu::CheckMenuItem<tauri::Wry>>>> = LazyLock::new(|| Mutex::new(None));
+
+/// Whether global shortcuts, input listeners, clipboard capture and hot corners are switched off
+pub fn is_capture_paused() -> bool {
+    CAPTURE_PAUSED.load(Ordering::SeqCst)
+}
+
+/// Remember the tray item so it follows the pause state
+pub fn register_capture_menu_item(item: tauri::menu::CheckMenuItem<tauri::Wry>) {
+    *CAPTURE_MENU_ITEM.lock().unwrap() = Some(item);
+}
+
+/// Flip the pause from the tray
+pub fn toggle_capture_paused(app: &AppHandle) {
+    set_capture_paused(app.clone(), !is_capture_paused());
+}
+
+/// Switch off every global listener at once, e.g. while gaming or screen sharing.
+/// Shortcuts and hooks stay registered and ignore input meanwhile, so resuming
+/// doesn't have to register anything again.
+#[tauri::command]
+pub fn set_capture_paused(app: AppHandle, paused: bool) {
+    if CAPTURE_PAUSED.swap(paused, Ordering::SeqCst) == paused {
+        return;
+    }
+    info!("{} Global capture {}", if paused { "⏸️" } else { "▶️" }, if paused { "paused" } else { "resumed" });
+
+    if let Some(ref item) = *CAPTURE_MENU_ITEM.lock().unwrap() {
+        let _ = item.set_checked(paused);
+    }
+    let _ = app.emit("capture-paused-changed", paused);
+}
+
+#[tauri::command]
+pub fn get_capture_paused() -> bool {
+    is_capture_paused()
+}
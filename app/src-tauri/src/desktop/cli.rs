@@ -0,0 +1,108 @@
+use tauri::AppHandle;
+use std::path::PathBuf;
+
+use crate::desktop::{import_to_quicknote, open_quicknote_with_text, share_to_quicknote, toggle_quicknote_window};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CliCommand {
+    /// --new-note "text": open the quick note window prefilled with text
+    NewNote(String),
+    /// --quicknote: toggle the quick note window
+    QuickNote,
+    /// --dictate: start/stop a voice dictation
+    Dictate,
+    /// --share-text "text" / --share file...: content sent from the OS share menus
+    Share { text: Option<String>, files: Vec<PathBuf> },
+    /// --import file...: files from Explorer's "Add to Blinko", text files become the note text
+    Import(Vec<PathBuf>),
+}
+
+/// Parse quick capture commands from command line arguments (without the executable path)
+pub fn parse_cli_args(args: &[String]) -> Vec<CliCommand> {
+    let mut commands = Vec::new();
+    let mut iter = args.iter().peekable();
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--new-note" | "-n" => match iter.next() {
+                Some(text) => commands.push(CliCommand::NewNote(text.clone())),
+                None => commands.push(CliCommand::NewNote(String::new())),
+            },
+            "--quicknote" | "-q" => commands.push(CliCommand::QuickNote),
+            "--dictate" | "-d" => commands.push(CliCommand::Dictate),
+            "--share-text" => {
+                if let Some(text) = iter.next() {
+                    commands.push(CliCommand::Share { text: Some(text.clone()), files: Vec::new() });
+                }
+            }
+            "--share" => {
+                // "Send to" and Services pass the selected files as the remaining arguments
+                let mut files = Vec::new();
+                while let Some(path) = iter.next_if(|arg| !arg.starts_with("--")) {
+                    files.push(PathBuf::from(path));
+                }
+                if !files.is_empty() {
+                    commands.push(CliCommand::Share { text: None, files });
+                }
+            }
+            "--import" => {
+                let mut files = Vec::new();
+                while let Some(path) = iter.next_if(|arg| !arg.starts_with("--")) {
+                    files.push(PathBuf::from(path));
+                }
+                if !files.is_empty() {
+                    commands.push(CliCommand::Import(files));
+                }
+            }
+            other => {
+                if let Some(text) = other.strip_prefix("--new-note=") {
+                    commands.push(CliCommand::NewNote(text.to_string()));
+                }
+            }
+        }
+    }
+
+    commands
+}
+
+/// Start or stop a dictation with the Windows voice processor
+pub fn toggle_dictation(app: &AppHandle) -> Result<(), String> {
+    #[cfg(all(target_os = "windows", any(feature = "whisper-cuda", feature = "whisper-cpu")))]
+    {
+        match crate::voice::current_voice_processor(app) {
+            Some(processor) => {
+                if processor.toggle_recording() {
+                    info!("🎤 Dictation started");
+                } else {
+                    info!("⏹️ Dictation stopped");
+                }
+                Ok(())
+            }
+            None => Err("Voice recognition not initialized".to_string()),
+        }
+    }
+
+    #[cfg(not(all(target_os = "windows", any(feature = "whisper-cuda", feature = "whisper-cpu"))))]
+    {
+        let _ = app;
+        Err("Voice dictation is not available in this build".to_string())
+    }
+}
+
+/// Execute parsed command line commands against the running app
+pub fn run_cli_commands(app: &AppHandle, commands: &[CliCommand]) {
+    for command in commands {
+        info!("⌨️ Running command line action: {:?}", command);
+        let result = match command {
+            CliCommand::NewNote(text) => open_quicknote_with_text(app, text),
+            CliCommand::QuickNote => toggle_quicknote_window(app.clone()),
+            CliCommand::Dictate => toggle_dictation(app),
+            CliCommand::Share { text, files } => share_to_quicknote(app, text.as_deref(), files),
+            CliCommand::Import(files) => import_to_quicknote(app, files),
+        };
+
+        if let Err(e) = result {
+            error!("❌ Command line action failed: {}", e);
+        }
+    }
+}
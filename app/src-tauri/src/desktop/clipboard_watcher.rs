@@ -0,0 +1,346 @@
+use tauri::{AppHandle, Emitter};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::io::Cursor;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use arboard::Clipboard;
+
+use crate::desktop::{get_app_data_subdir, is_capture_paused, is_clipboard_guarded, load_json_or_default, load_protected_json_or_default, now_millis, save_json, save_protected_file, save_protected_json};
+
+const CLIPBOARD_CONFIG_FILE: &str = "clipboard_config.json";
+pub const CLIPBOARD_HISTORY_FILE: &str = "clipboard_history.json";
+pub const CLIPBOARD_IMAGES_DIR: &str = "clipboard";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ClipboardWatcherConfig {
+    /// Whether clipboard monitoring is enabled (opt-in)
+    pub enabled: bool,
+
+    /// Maximum number of unpinned items kept in history
+    #[serde(rename = "maxItems")]
+    pub max_items: usize,
+
+    /// Whether copied images are captured as well
+    #[serde(rename = "captureImages")]
+    pub capture_images: bool,
+
+    /// Clipboard polling interval in milliseconds
+    #[serde(rename = "pollIntervalMs")]
+    pub poll_interval_ms: u64,
+}
+
+impl Default for ClipboardWatcherConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_items: 100,
+            capture_images: true,
+            poll_interval_ms: 800,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ClipboardItem {
+    pub id: String,
+    /// "text" or "image"
+    pub kind: String,
+    pub text: Option<String>,
+    #[serde(rename = "imagePath")]
+    pub image_path: Option<String>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    #[serde(rename = "createdAt")]
+    pub created_at: u64,
+    pub pinned: bool,
+    hash: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+struct ClipboardHistory {
+    items: Vec<ClipboardItem>,
+}
+
+static CLIPBOARD_HISTORY: LazyLock<Mutex<Option<ClipboardHistory>>> = LazyLock::new(|| Mutex::new(None));
+static WATCHER_RUNNING: AtomicBool = AtomicBool::new(false);
+static WATCHER_GENERATION: AtomicU64 = AtomicU64::new(0);
+static SUPPRESS_UNTIL: LazyLock<Mutex<Option<Instant>>> = LazyLock::new(|| Mutex::new(None));
+
+/// Load clipboard watcher config from file
+pub fn load_clipboard_watcher_config(app: &AppHandle) -> ClipboardWatcherConfig {
+    load_json_or_default(app, CLIPBOARD_CONFIG_FILE)
+}
+
+/// Ignore clipboard changes for a while, used when Blinko itself writes to the clipboard
+pub fn suppress_clipboard_capture(duration: Duration) {
+    *SUPPRESS_UNTIL.lock().unwrap() = Some(Instant::now() + duration);
+}
+
+fn is_capture_suppressed() -> bool {
+    if is_capture_paused() || is_clipboard_guarded() {
+        return true;
+    }
+    match *SUPPRESS_UNTIL.lock().unwrap() {
+        Some(until) => Instant::now() < until,
+        None => false,
+    }
+}
+
+fn with_history<T>(app: &AppHandle, f: impl FnOnce(&mut ClipboardHistory) -> T) -> T {
+    let mut guard = CLIPBOARD_HISTORY.lock().unwrap();
+    let history = guard.get_or_insert_with(|| load_protected_json_or_default(app, CLIPBOARD_HISTORY_FILE));
+    f(history)
+}
+
+fn persist_history(app: &AppHandle) {
+    let guard = CLIPBOARD_HISTORY.lock().unwrap();
+    if let Some(ref history) = *guard {
+        if let Err(e) = save_protected_json(app, CLIPBOARD_HISTORY_FILE, history) {
+            error!("Failed to save clipboard history: {}", e);
+        }
+    }
+}
+
+/// Drop the in-memory history so it is read again, e.g. after unlocking encryption
+pub fn reset_clipboard_history_cache() {
+    *CLIPBOARD_HISTORY.lock().unwrap() = None;
+}
+
+fn hash_bytes(bytes: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Drop the oldest unpinned items beyond the configured cap
+fn enforce_history_cap(history: &mut ClipboardHistory, max_items: usize) {
+    let unpinned = history.items.iter().filter(|item| !item.pinned).count();
+    if unpinned <= max_items {
+        return;
+    }
+
+    let mut to_remove = unpinned - max_items;
+    // Items are stored newest first, so walk from the back
+    let mut index = history.items.len();
+    while to_remove > 0 && index > 0 {
+        index -= 1;
+        if !history.items[index].pinned {
+            let removed = history.items.remove(index);
+            if let Some(path) = removed.image_path {
+                let _ = std::fs::remove_file(path);
+            }
+            to_remove -= 1;
+        }
+    }
+}
+
+fn emit_history_item(app: &AppHandle, item: &ClipboardItem) {
+    if let Err(e) = app.emit("clipboard-item-added", item) {
+        error!("Failed to emit clipboard-item-added event: {}", e);
+    }
+}
+
+/// Move an identical existing entry to the top instead of duplicating it.
+/// Returns false when nothing in the history has this hash.
+fn promote_history_item(app: &AppHandle, hash: u64) -> bool {
+    let promoted = with_history(app, |history| {
+        let pos = history.items.iter().position(|existing| existing.hash == hash)?;
+        let mut existing = history.items.remove(pos);
+        existing.created_at = now_millis();
+        history.items.insert(0, existing.clone());
+        Some(existing)
+    });
+    let Some(existing) = promoted else {
+        return false;
+    };
+
+    persist_history(app);
+    emit_history_item(app, &existing);
+    true
+}
+
+fn add_history_item(app: &AppHandle, item: ClipboardItem, max_items: usize) {
+    if promote_history_item(app, item.hash) {
+        // The existing entry keeps its own image, so this one would be orphaned
+        if let Some(path) = item.image_path {
+            let _ = std::fs::remove_file(path);
+        }
+        return;
+    }
+
+    with_history(app, |history| {
+        history.items.insert(0, item.clone());
+        enforce_history_cap(history, max_items);
+    });
+    persist_history(app);
+    emit_history_item(app, &item);
+}
+
+fn capture_image(app: &AppHandle, image: arboard::ImageData, hash: u64) -> Result<ClipboardItem, String> {
+    let dir = get_app_data_subdir(app, CLIPBOARD_IMAGES_DIR)?;
+    let created_at = now_millis();
+    let id = format!("{}-{:x}", created_at, hash);
+    let path = dir.join(format!("{}.png", id));
+
+    let width = image.width as u32;
+    let height = image.height as u32;
+    let buffer = image::RgbaImage::from_raw(width, height, image.bytes.into_owned())
+        .ok_or("Invalid clipboard image data")?;
+    let mut png = Vec::new();
+    buffer.write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)
+        .map_err(|e| format!("Failed to encode clipboard image: {}", e))?;
+    save_protected_file(&path, &png)?;
+
+    Ok(ClipboardItem {
+        id,
+        kind: "image".to_string(),
+        text: None,
+        image_path: Some(path.to_string_lossy().to_string()),
+        width: Some(width),
+        height: Some(height),
+        created_at,
+        pinned: false,
+        hash,
+    })
+}
+
+fn clipboard_watch_loop(app: AppHandle, config: ClipboardWatcherConfig, generation: u64) {
+    let mut clipboard = match Clipboard::new() {
+        Ok(clipboard) => clipboard,
+        Err(e) => {
+            error!("❌ Failed to access clipboard for monitoring: {}", e);
+            WATCHER_RUNNING.store(false, Ordering::SeqCst);
+            return;
+        }
+    };
+
+    // Skip whatever was already on the clipboard when the watcher started
+    let mut last_hash: Option<u64> = clipboard.get_text().ok()
+        .map(|text| hash_bytes(text.as_bytes()))
+        .or_else(|| clipboard.get_image().ok().map(|image| hash_bytes(&image.bytes)));
+    debug!("📋 Clipboard watcher started");
+
+    while WATCHER_GENERATION.load(Ordering::SeqCst) == generation {
+        if let Ok(text) = clipboard.get_text() {
+            let hash = hash_bytes(text.as_bytes());
+            if last_hash != Some(hash) {
+                last_hash = Some(hash);
+                if !text.trim().is_empty() && !is_capture_suppressed() {
+                    let created_at = now_millis();
+                    let item = ClipboardItem {
+                        id: format!("{}-{:x}", created_at, hash),
+                        kind: "text".to_string(),
+                        text: Some(text),
+                        image_path: None,
+                        width: None,
+                        height: None,
+                        created_at,
+                        pinned: false,
+                        hash,
+                    };
+                    add_history_item(&app, item, config.max_items);
+                }
+            }
+        } else if config.capture_images {
+            if let Ok(image) = clipboard.get_image() {
+                let hash = hash_bytes(&image.bytes);
+                if last_hash != Some(hash) {
+                    last_hash = Some(hash);
+                    // Re-copying a known image only moves it up, without writing another PNG
+                    if !is_capture_suppressed() && !promote_history_item(&app, hash) {
+                        match capture_image(&app, image, hash) {
+                            Ok(item) => add_history_item(&app, item, config.max_items),
+                            Err(e) => error!("Failed to capture clipboard image: {}", e),
+                        }
+                    }
+                }
+            }
+        }
+
+        std::thread::sleep(Duration::from_millis(config.poll_interval_ms.max(200)));
+    }
+
+    // Only clear the flag if no newer watcher took over
+    if WATCHER_GENERATION.load(Ordering::SeqCst) == generation {
+        WATCHER_RUNNING.store(false, Ordering::SeqCst);
+    }
+    debug!("📋 Clipboard watcher stopped");
+}
+
+/// Start the clipboard watcher if it is enabled and not already running
+pub fn start_clipboard_watcher(app: &AppHandle) {
+    let config = load_clipboard_watcher_config(app);
+    if !config.enabled {
+        return;
+    }
+
+    if WATCHER_RUNNING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let generation = WATCHER_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    let app_handle = app.clone();
+    std::thread::spawn(move || {
+        clipboard_watch_loop(app_handle, config, generation);
+    });
+}
+
+/// Stop the clipboard watcher thread
+pub fn stop_clipboard_watcher() {
+    WATCHER_GENERATION.fetch_add(1, Ordering::SeqCst);
+    WATCHER_RUNNING.store(false, Ordering::SeqCst);
+}
+
+#[tauri::command]
+pub fn get_clipboard_watcher_config(app: AppHandle) -> ClipboardWatcherConfig {
+    load_clipboard_watcher_config(&app)
+}
+
+#[tauri::command]
+pub async fn save_clipboard_watcher_config(app: AppHandle, config: ClipboardWatcherConfig) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        if config.max_items == 0 {
+            return Err("History size must be at least 1".to_string());
+        }
+
+        save_json(&app, CLIPBOARD_CONFIG_FILE, &config)?;
+
+        // Restart the watcher so it picks up the new settings
+        stop_clipboard_watcher();
+        start_clipboard_watcher(&app);
+
+        with_history(&app, |history| enforce_history_cap(history, config.max_items));
+        persist_history(&app);
+
+        info!("Saved clipboard watcher config, enabled: {}", config.enabled);
+        Ok(())
+    })
+    .await
+    .map_err(|e| format!("Failed to save clipboard settings: {}", e))?
+}
+
+#[tauri::command]
+pub fn get_clipboard_history(app: AppHandle) -> Vec<ClipboardItem> {
+    with_history(&app, |history| history.items.clone())
+}
+
+#[tauri::command]
+pub fn pin_clipboard_item(app: AppHandle, id: String, pinned: bool) -> Result<(), String> {
+    let max_items = load_clipboard_watcher_config(&app).max_items;
+
+    with_history(&app, |history| {
+        let item = history.items.iter_mut()
+            .find(|item| item.id == id)
+            .ok_or_else(|| format!("Clipboard item not found: {}", id))?;
+        item.pinned = pinned;
+        enforce_history_cap(history, max_items);
+        Ok::<(), String>(())
+    })?;
+
+    persist_history(&app);
+    Ok(())
+}
@@ -0,0 +1,188 @@
+use tauri::{AppHandle, Emitter, Manager, WebviewUrl, WebviewWindowBuilder};
+use serde::{Deserialize, Serialize};
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender};
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+
+use mouse_position::mouse_position::Mouse;
+
+const COLOR_PICKER_LABEL: &str = "colorpicker";
+const COLOR_PICK_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PickedColor {
+    pub hex: String,
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub x: i32,
+    pub y: i32,
+}
+
+// Sender for the pick currently waiting on the overlay; `None` means cancelled
+static PENDING_PICK: LazyLock<Mutex<Option<SyncSender<Option<(i32, i32)>>>>> = LazyLock::new(|| Mutex::new(None));
+
+/// Sample the screen pixel at a physical screen position
+fn sample_pixel(x: i32, y: i32) -> Result<PickedColor, String> {
+    let monitor = xcap::Monitor::from_point(x, y)
+        .map_err(|e| format!("Failed to find monitor at ({}, {}): {}", x, y, e))?;
+
+    let image = monitor.capture_image()
+        .map_err(|e| format!("Failed to capture screen: {}", e))?;
+
+    let monitor_x = monitor.x().unwrap_or(0);
+    let monitor_y = monitor.y().unwrap_or(0);
+    let monitor_width = monitor.width().unwrap_or(image.width()).max(1);
+
+    // Captured images are in physical pixels, monitor geometry may be logical
+    let ratio = image.width() as f64 / monitor_width as f64;
+    let px = (((x - monitor_x) as f64) * ratio).round() as u32;
+    let py = (((y - monitor_y) as f64) * ratio).round() as u32;
+
+    if px >= image.width() || py >= image.height() {
+        return Err(format!("Position ({}, {}) is outside the captured screen", x, y));
+    }
+
+    let pixel = image.get_pixel(px, py);
+    let (r, g, b) = (pixel[0], pixel[1], pixel[2]);
+
+    Ok(PickedColor {
+        hex: format!("#{:02X}{:02X}{:02X}", r, g, b),
+        r,
+        g,
+        b,
+        x,
+        y,
+    })
+}
+
+/// Open a transparent crosshair overlay covering the monitor under the cursor
+fn show_color_picker_overlay(app: &AppHandle) -> Result<(), String> {
+    if let Some(window) = app.get_webview_window(COLOR_PICKER_LABEL) {
+        let _ = window.close();
+    }
+
+    let cursor = app.cursor_position()
+        .map_err(|e| format!("Failed to get cursor position: {}", e))?;
+    let monitor = app.monitor_from_point(cursor.x, cursor.y)
+        .map_err(|e| format!("Failed to get monitor: {}", e))?
+        .or(app.primary_monitor().map_err(|e| format!("Failed to get primary monitor: {}", e))?)
+        .ok_or("No monitor found")?;
+
+    let scale = monitor.scale_factor();
+    let position = monitor.position().to_logical::<f64>(scale);
+    let size = monitor.size().to_logical::<f64>(scale);
+
+    let window = WebviewWindowBuilder::new(app, COLOR_PICKER_LABEL, WebviewUrl::App("/colorpicker".into()))
+        .title("Color Picker")
+        .position(position.x, position.y)
+        .inner_size(size.width, size.height)
+        .transparent(true)
+        .decorations(false)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .resizable(false)
+        .shadow(false)
+        .focused(true)
+        .build()
+        .map_err(|e| format!("Failed to create color picker window: {}", e))?;
+
+    let _ = window.set_cursor_icon(tauri::CursorIcon::Crosshair);
+    Ok(())
+}
+
+fn close_color_picker_overlay(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window(COLOR_PICKER_LABEL) {
+        let _ = window.hide();
+        let _ = window.close();
+    }
+}
+
+/// Run a blocking color pick: show the overlay and wait for a click or cancel
+fn pick_color_blocking(app: &AppHandle) -> Result<PickedColor, String> {
+    let (tx, rx): (SyncSender<Option<(i32, i32)>>, Receiver<Option<(i32, i32)>>) = sync_channel(1);
+    {
+        let mut pending = PENDING_PICK.lock().unwrap();
+        if let Some(previous) = pending.take() {
+            let _ = previous.send(None);
+        }
+        *pending = Some(tx);
+    }
+
+    show_color_picker_overlay(app)?;
+
+    let result = rx.recv_timeout(COLOR_PICK_TIMEOUT);
+    close_color_picker_overlay(app);
+
+    match result {
+        Ok(Some((x, y))) => {
+            // Let the overlay disappear before grabbing the screen
+            std::thread::sleep(Duration::from_millis(80));
+            sample_pixel(x, y)
+        }
+        Ok(None) => Err("Color pick cancelled".to_string()),
+        Err(_) => {
+            PENDING_PICK.lock().unwrap().take();
+            Err("Color pick timed out".to_string())
+        }
+    }
+}
+
+/// Handle the color picker hotkey: pick a color and hand it to the quicktool window
+pub fn handle_color_picker_shortcut(app: &AppHandle) {
+    let app_handle = app.clone();
+    std::thread::spawn(move || {
+        match pick_color_blocking(&app_handle) {
+            Ok(color) => {
+                info!("🎨 Picked color {} at ({}, {})", color.hex, color.x, color.y);
+                match app_handle.get_webview_window("quicktool") {
+                    Some(window) => {
+                        let _ = window.show();
+                        let _ = window.set_focus();
+                    }
+                    None => {
+                        if let Err(e) = crate::desktop::toggle_quicktool_window(app_handle.clone()) {
+                            error!("Failed to show quicktool window: {}", e);
+                        }
+                    }
+                }
+                if let Err(e) = app_handle.emit("color-picked", &color) {
+                    error!("Failed to emit color-picked event: {}", e);
+                }
+            }
+            Err(e) => info!("🎨 {}", e),
+        }
+    });
+}
+
+/// Open the crosshair overlay and return the color the user clicks on
+#[tauri::command]
+pub async fn pick_color(app: AppHandle) -> Result<PickedColor, String> {
+    tauri::async_runtime::spawn_blocking(move || pick_color_blocking(&app))
+        .await
+        .map_err(|e| format!("Color pick task failed: {}", e))?
+}
+
+/// Called by the overlay when the user clicks; samples at the current cursor position
+#[tauri::command]
+pub fn confirm_color_pick() -> Result<(), String> {
+    let (x, y) = match Mouse::get_mouse_position() {
+        Mouse::Position { x, y } => (x, y),
+        Mouse::Error => return Err("Failed to get mouse position".to_string()),
+    };
+
+    let sender = PENDING_PICK.lock().unwrap()
+        .take()
+        .ok_or("No color pick in progress")?;
+    sender.send(Some((x, y)))
+        .map_err(|_| "Color pick is no longer waiting".to_string())
+}
+
+/// Called by the overlay on Escape
+#[tauri::command]
+pub fn cancel_color_pick() -> Result<(), String> {
+    if let Some(sender) = PENDING_PICK.lock().unwrap().take() {
+        let _ = sender.send(None);
+    }
+    Ok(())
+}
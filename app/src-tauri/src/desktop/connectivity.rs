@@ -0,0 +1,179 @@
+use tauri::{AppHandle, Emitter};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
+use crate::desktop::{get_configured_server_url, http_client_builder, now_millis, set_offline_online, set_tray_health};
+
+const ONLINE_PROBE_INTERVAL: Duration = Duration::from_secs(30);
+// Probe more often while offline so we notice recovery quickly
+const OFFLINE_PROBE_INTERVAL: Duration = Duration::from_secs(10);
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ConnectivityStatus {
+    pub online: bool,
+    #[serde(rename = "serverUrl")]
+    pub server_url: Option<String>,
+    /// Round trip of the last successful probe
+    #[serde(rename = "latencyMs")]
+    pub latency_ms: Option<u64>,
+    /// Server version reported by the last successful probe
+    pub version: Option<String>,
+    #[serde(rename = "checkedAt")]
+    pub checked_at: Option<u64>,
+    pub error: Option<String>,
+}
+
+impl Default for ConnectivityStatus {
+    fn default() -> Self {
+        Self {
+            online: true,
+            server_url: None,
+            latency_ms: None,
+            version: None,
+            checked_at: None,
+            error: None,
+        }
+    }
+}
+
+/// Result of `get_server_health`, also emitted as `server-health` after every probe
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ServerHealth {
+    pub reachable: bool,
+    #[serde(rename = "latencyMs")]
+    pub latency_ms: Option<u64>,
+    pub version: Option<String>,
+    #[serde(rename = "serverUrl")]
+    pub server_url: Option<String>,
+    #[serde(rename = "checkedAt")]
+    pub checked_at: Option<u64>,
+    pub error: Option<String>,
+}
+
+impl From<ConnectivityStatus> for ServerHealth {
+    fn from(status: ConnectivityStatus) -> Self {
+        Self {
+            reachable: status.online,
+            latency_ms: status.latency_ms,
+            version: status.version,
+            server_url: status.server_url,
+            checked_at: status.checked_at,
+            error: status.error,
+        }
+    }
+}
+
+static CONNECTIVITY_STATUS: LazyLock<Mutex<ConnectivityStatus>> = LazyLock::new(|| Mutex::new(ConnectivityStatus::default()));
+static MONITOR_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Any HTTP answer counts as reachable, only transport errors mean offline.
+/// Returns the round trip and the version if the endpoint reported one.
+fn probe_server(server_url: &str) -> Result<(u64, Option<String>), String> {
+    let url = format!("{}/api/v1/public/version", server_url.trim_end_matches('/'));
+    let started = Instant::now();
+
+    let response = http_client_builder()
+        .timeout(PROBE_TIMEOUT)
+        .build()
+        .and_then(|client| client.get(&url).send())
+        .map_err(|e| e.to_string())?;
+    let latency = started.elapsed().as_millis() as u64;
+
+    let version = response.status().is_success()
+        .then(|| response.json::<Value>().ok())
+        .flatten()
+        .and_then(|body| match body {
+            Value::String(version) => Some(version),
+            body => body.get("version").and_then(|v| v.as_str()).map(str::to_string),
+        });
+    Ok((latency, version))
+}
+
+/// Green or red dot with the latency for the tray tooltip
+fn health_line(status: &ConnectivityStatus) -> String {
+    match (status.online, status.latency_ms) {
+        (true, Some(latency)) => format!("🟢 Server reachable ({} ms)", latency),
+        (true, None) => "🟢 Server reachable".to_string(),
+        (false, _) => "🔴 Server unreachable".to_string(),
+    }
+}
+
+/// Probe the configured server now and publish state changes
+pub fn check_connectivity(app: &AppHandle) -> ConnectivityStatus {
+    let server_url = get_configured_server_url();
+    let Some(ref url) = server_url else {
+        // Nothing to probe until the frontend has signed in
+        return CONNECTIVITY_STATUS.lock().unwrap().clone();
+    };
+
+    let result = probe_server(url);
+    let status = ConnectivityStatus {
+        online: result.is_ok(),
+        server_url: server_url.clone(),
+        latency_ms: result.as_ref().ok().map(|(latency, _)| *latency),
+        version: result.as_ref().ok().and_then(|(_, version)| version.clone()),
+        checked_at: Some(now_millis()),
+        error: result.err(),
+    };
+
+    let was_online = {
+        let mut current = CONNECTIVITY_STATUS.lock().unwrap();
+        let was_online = current.online;
+        *current = status.clone();
+        was_online
+    };
+
+    if was_online != status.online {
+        let event = if status.online { "network-online" } else { "network-offline" };
+        info!("🌐 {} ({})", event, status.error.as_deref().unwrap_or("reachable"));
+        if let Err(e) = app.emit(event, &status) {
+            error!("Failed to emit {} event: {}", event, e);
+        }
+    }
+
+    set_tray_health(app, Some(health_line(&status)));
+    if let Err(e) = app.emit("server-health", ServerHealth::from(status.clone())) {
+        error!("Failed to emit server-health event: {}", e);
+    }
+
+    // Let the offline queue replay once the server is reachable again
+    set_offline_online(app, status.online);
+    status
+}
+
+/// Start the background probe loop
+pub fn start_connectivity_monitor(app: &AppHandle) {
+    if MONITOR_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let app_handle = app.clone();
+    std::thread::spawn(move || loop {
+        let status = check_connectivity(&app_handle);
+        std::thread::sleep(if status.online { ONLINE_PROBE_INTERVAL } else { OFFLINE_PROBE_INTERVAL });
+    });
+}
+
+#[tauri::command]
+pub fn get_connectivity_status() -> ConnectivityStatus {
+    CONNECTIVITY_STATUS.lock().unwrap().clone()
+}
+
+/// Reachability, latency and version from the last probe
+#[tauri::command]
+pub fn get_server_health() -> ServerHealth {
+    CONNECTIVITY_STATUS.lock().unwrap().clone().into()
+}
+
+/// Probe immediately, e.g. when the user presses "retry"
+#[tauri::command]
+pub async fn check_connectivity_now(app: AppHandle) -> Result<ConnectivityStatus, String> {
+    tauri::async_runtime::spawn_blocking(move || check_connectivity(&app))
+        .await
+        .map_err(|e| format!("Connectivity check failed: {}", e))
+}
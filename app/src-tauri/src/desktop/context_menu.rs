@@ -0,0 +1,97 @@
+use tauri::AppHandle;
+use std::path::{Path, PathBuf};
+
+use crate::desktop::{share_to_quicknote, NOTE_EXTENSIONS};
+
+/// Larger text files are attached instead of pasted into the quick note
+const MAX_NOTE_TEXT_BYTES: u64 = 512 * 1024;
+
+/// Per-user verb for all file types, so registration needs no elevation
+#[cfg(target_os = "windows")]
+const CONTEXT_MENU_KEY: &str = r"HKCU\Software\Classes\*\shell\Blinko";
+
+fn is_note_text_file(path: &Path) -> bool {
+    let is_text = path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| NOTE_EXTENSIONS.contains(&ext.to_lowercase().as_str()));
+    is_text && std::fs::metadata(path).is_ok_and(|metadata| metadata.len() <= MAX_NOTE_TEXT_BYTES)
+}
+
+/// Open the quick note with imported files: text files become the note text, everything else is attached
+pub fn import_to_quicknote(app: &AppHandle, files: &[PathBuf]) -> Result<(), String> {
+    let (text_files, attachments): (Vec<PathBuf>, Vec<PathBuf>) = files.iter().cloned().partition(|path| is_note_text_file(path));
+
+    let mut texts = Vec::new();
+    for path in &text_files {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        texts.push(text.trim().to_string());
+    }
+    let text = texts.join("\n\n");
+
+    info!("📥 Importing {} text file(s) and {} attachment(s)", text_files.len(), attachments.len());
+    share_to_quicknote(app, Some(text.as_str()).filter(|text| !text.is_empty()), &attachments)
+}
+
+#[cfg(target_os = "windows")]
+fn reg_command(args: &[&str]) -> Result<(), String> {
+    let output = crate::desktop::background_command("reg")
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run reg: {}", e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!("reg failed: {}", String::from_utf8_lossy(&output.stderr).trim()))
+    }
+}
+
+/// Add "Add to Blinko" to Explorer's context menu for files
+#[tauri::command]
+pub fn register_context_menu() -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    {
+        let exe = std::env::current_exe().map_err(|e| format!("Failed to locate Blinko executable: {}", e))?;
+        let exe = exe.to_string_lossy();
+        let command = format!("\"{}\" --import \"%1\"", exe);
+        let command_key = format!(r"{}\command", CONTEXT_MENU_KEY);
+
+        reg_command(&["add", CONTEXT_MENU_KEY, "/ve", "/d", "Add to Blinko", "/f"])?;
+        reg_command(&["add", CONTEXT_MENU_KEY, "/v", "Icon", "/d", &exe, "/f"])?;
+        // Several selected files start one process each, the single instance layer forwards them
+        reg_command(&["add", CONTEXT_MENU_KEY, "/v", "MultiSelectModel", "/d", "Player", "/f"])?;
+        reg_command(&["add", &command_key, "/ve", "/d", &command, "/f"])?;
+        info!("📥 Explorer context menu entry registered");
+        Ok(())
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        Err("Explorer context menu is only available on Windows".to_string())
+    }
+}
+
+#[tauri::command]
+pub fn unregister_context_menu() -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    {
+        if is_context_menu_registered() {
+            reg_command(&["delete", CONTEXT_MENU_KEY, "/f"])?;
+        }
+        info!("📥 Explorer context menu entry removed");
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn is_context_menu_registered() -> bool {
+    #[cfg(target_os = "windows")]
+    {
+        reg_command(&["query", &format!(r"{}\command", CONTEXT_MENU_KEY), "/ve"]).is_ok()
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        false
+    }
+}
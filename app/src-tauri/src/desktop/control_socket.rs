@@ -0,0 +1,312 @@
+use tauri::{AppHandle, Url};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tungstenite::{Message, WebSocket};
+
+use crate::desktop::{
+    generate_api_token, is_usable_token, list_shortcut_route_triggers, list_templates, load_json_or_default, open_quicknote_with_text,
+    run_named_action, save_json, tokens_match, TEMPLATE_COMMAND_PREFIX,
+};
+
+const CONTROL_SOCKET_CONFIG_FILE: &str = "control_socket.json";
+const DEFAULT_CONTROL_SOCKET_PORT: u16 = 43220;
+// Accept and read loops wake up this often to notice a restart
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+const BIND_ATTEMPTS: u32 = 4;
+
+/// Built-in commands of the shortcut routing table offered to remote controls
+const BUILTIN_ACTIONS: [(&str, &str); 9] = [
+    ("quicknote", "Toggle quick note"),
+    ("quickai", "Toggle quick AI"),
+    ("quicktool", "Toggle quick tool"),
+    ("palette", "Toggle command palette"),
+    ("dictation", "Start/stop dictation"),
+    ("text-selection", "Capture selected text"),
+    ("screenshot", "Capture screenshot"),
+    ("colorpicker", "Pick a color"),
+    ("capture-page", "Capture current page"),
+];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ControlSocketConfig {
+    pub enabled: bool,
+    pub port: u16,
+    /// Clients pass it as `?token=` in the URL or as `Authorization: Bearer <token>`
+    pub token: String,
+}
+
+impl Default for ControlSocketConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: DEFAULT_CONTROL_SOCKET_PORT,
+            token: String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+enum ControlRequestType {
+    #[default]
+    Run,
+    ListActions,
+}
+
+/// `{"id": 1, "action": "quicknote"}`, or a bare action name as the whole message
+#[derive(Debug, Deserialize, Clone)]
+struct ControlRequest {
+    /// Echoed back in the reply so clients can match it
+    id: Option<Value>,
+    #[serde(rename = "type", default)]
+    kind: ControlRequestType,
+    action: Option<String>,
+    /// Prefills the quick note for the `quicknote` action
+    text: Option<String>,
+}
+
+/// An action name a remote control can send, as listed by `listActions`
+#[derive(Debug, Serialize, Clone)]
+pub struct ControlAction {
+    pub name: String,
+    pub label: String,
+}
+
+// Bumped on every restart so the old listener and its connections exit
+static CONTROL_SOCKET_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Load control socket config from file, creating a token on first use
+pub fn load_control_socket_config(app: &AppHandle) -> Result<ControlSocketConfig, String> {
+    let mut config: ControlSocketConfig = load_json_or_default(app, CONTROL_SOCKET_CONFIG_FILE);
+    if !is_usable_token(&config.token) {
+        config.token = generate_api_token()?;
+        if let Err(e) = save_json(app, CONTROL_SOCKET_CONFIG_FILE, &config) {
+            error!("Failed to save control socket config: {}", e);
+        }
+    }
+    Ok(config)
+}
+
+/// Built-in commands, templates and routed triggers, all runnable by name
+pub fn list_control_actions(app: &AppHandle) -> Vec<ControlAction> {
+    let mut actions: Vec<ControlAction> = BUILTIN_ACTIONS
+        .iter()
+        .map(|(name, label)| ControlAction { name: name.to_string(), label: label.to_string() })
+        .collect();
+    actions.extend(list_templates(app.clone()).into_iter().map(|template| ControlAction {
+        name: format!("{}{}", TEMPLATE_COMMAND_PREFIX, template.id),
+        label: format!("Insert template: {}", template.name),
+    }));
+    actions.extend(list_shortcut_route_triggers(app).into_iter().map(|trigger| ControlAction {
+        label: trigger.clone(),
+        name: trigger,
+    }));
+    actions
+}
+
+fn is_authorized(request: &Request, token: &str) -> bool {
+    let query_token = Url::parse(&format!("ws://127.0.0.1{}", request.uri()))
+        .ok()
+        .and_then(|url| url.query_pairs().find(|(key, _)| key == "token").map(|(_, value)| value.to_string()));
+    query_token.is_some_and(|query_token| tokens_match(&query_token, token))
+        || request.headers().get("Authorization")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|given| tokens_match(given, token))
+}
+
+fn handle_control_message(app: &AppHandle, text: &str) -> Value {
+    let request = match serde_json::from_str::<Value>(text) {
+        Ok(value) if value.is_object() => serde_json::from_value::<ControlRequest>(value),
+        _ => Ok(ControlRequest { id: None, kind: ControlRequestType::Run, action: Some(text.trim().to_string()), text: None }),
+    };
+    let request = match request {
+        Ok(request) => request,
+        Err(e) => return json!({ "ok": false, "error": format!("Invalid request: {}", e) }),
+    };
+
+    let result = match request.kind {
+        ControlRequestType::ListActions => {
+            return json!({ "id": request.id, "ok": true, "actions": list_control_actions(app) });
+        }
+        ControlRequestType::Run => match request.action.as_deref() {
+            Some("quicknote") if request.text.as_ref().is_some_and(|t| !t.is_empty()) => {
+                open_quicknote_with_text(app, request.text.as_deref().unwrap_or_default())
+            }
+            Some(action) if !action.is_empty() => {
+                info!("🎛️ Control socket action: {}", action);
+                run_named_action(app, action)
+            }
+            _ => Err("action is required".to_string()),
+        },
+    };
+
+    match result {
+        Ok(()) => json!({ "id": request.id, "ok": true }),
+        Err(e) => json!({ "id": request.id, "ok": false, "error": e }),
+    }
+}
+
+fn serve_connection(app: &AppHandle, mut socket: WebSocket<TcpStream>, generation: u64) {
+    while CONTROL_SOCKET_GENERATION.load(Ordering::SeqCst) == generation {
+        let reply = match socket.read() {
+            Ok(Message::Text(text)) => handle_control_message(app, &text),
+            Ok(Message::Close(_)) => break,
+            Ok(_) => continue,
+            Err(tungstenite::Error::Io(ref e))
+                if matches!(e.kind(), std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut) => continue,
+            Err(tungstenite::Error::ConnectionClosed) | Err(tungstenite::Error::AlreadyClosed) => break,
+            Err(e) => {
+                warn!("⚠️ Control socket connection failed: {}", e);
+                break;
+            }
+        };
+        if let Err(e) = socket.send(Message::Text(reply.to_string())) {
+            warn!("⚠️ Failed to answer control socket request: {}", e);
+            break;
+        }
+    }
+    let _ = socket.close(None);
+}
+
+fn accept_connection(app: AppHandle, stream: TcpStream, token: String, generation: u64) {
+    // The listener is non-blocking, the handshake is not
+    if let Err(e) = stream.set_nonblocking(false) {
+        error!("Failed to configure control socket connection: {}", e);
+        return;
+    }
+    let socket = tungstenite::accept_hdr(stream, |request: &Request, response: Response| {
+        if is_authorized(request, &token) {
+            Ok(response)
+        } else {
+            let mut rejection = ErrorResponse::new(Some("Invalid or missing token".to_string()));
+            *rejection.status_mut() = tungstenite::http::StatusCode::UNAUTHORIZED;
+            Err(rejection)
+        }
+    });
+    let socket = match socket {
+        Ok(socket) => socket,
+        Err(e) => {
+            warn!("⚠️ Control socket handshake rejected: {}", e);
+            return;
+        }
+    };
+    if let Err(e) = socket.get_ref().set_read_timeout(Some(POLL_INTERVAL)) {
+        error!("Failed to configure control socket connection: {}", e);
+        return;
+    }
+
+    info!("🎛️ Control socket client connected");
+    serve_connection(&app, socket, generation);
+    info!("🎛️ Control socket client disconnected");
+}
+
+/// The previous listener gives up the port on its next poll, so a restart retries briefly
+fn bind_listener(port: u16) -> std::io::Result<TcpListener> {
+    let mut attempts = 0;
+    loop {
+        match TcpListener::bind(("127.0.0.1", port)) {
+            Err(e) if e.kind() == std::io::ErrorKind::AddrInUse && attempts < BIND_ATTEMPTS => {
+                attempts += 1;
+                std::thread::sleep(POLL_INTERVAL);
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Stop the WebSocket server and drop its clients
+pub fn stop_control_socket() {
+    CONTROL_SOCKET_GENERATION.fetch_add(1, Ordering::SeqCst);
+}
+
+/// Start the WebSocket server on localhost if enabled in config
+pub fn start_control_socket(app: &AppHandle) {
+    let generation = CONTROL_SOCKET_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+
+    let config = match load_control_socket_config(app) {
+        Ok(config) => config,
+        Err(e) => {
+            error!("❌ Not starting control socket without a token: {}", e);
+            return;
+        }
+    };
+    if !config.enabled {
+        return;
+    }
+
+    let listener = match bind_listener(config.port) {
+        Ok(listener) => listener,
+        Err(e) => {
+            error!("❌ Failed to start control socket on port {}: {}", config.port, e);
+            return;
+        }
+    };
+    if let Err(e) = listener.set_nonblocking(true) {
+        error!("❌ Failed to configure control socket: {}", e);
+        return;
+    }
+    info!("🎛️ Control socket listening on ws://127.0.0.1:{}", config.port);
+
+    let app_handle = app.clone();
+    std::thread::spawn(move || {
+        while CONTROL_SOCKET_GENERATION.load(Ordering::SeqCst) == generation {
+            match listener.accept() {
+                Ok((stream, _)) => {
+                    let app_handle = app_handle.clone();
+                    let token = config.token.clone();
+                    std::thread::spawn(move || accept_connection(app_handle, stream, token, generation));
+                }
+                Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => std::thread::sleep(POLL_INTERVAL),
+                Err(e) => {
+                    error!("Control socket accept failed: {}", e);
+                    std::thread::sleep(POLL_INTERVAL);
+                }
+            }
+        }
+        info!("🎛️ Control socket stopped");
+    });
+}
+
+#[tauri::command]
+pub fn get_control_socket_config(app: AppHandle) -> Result<ControlSocketConfig, String> {
+    load_control_socket_config(&app)
+}
+
+#[tauri::command]
+pub fn set_control_socket_enabled(app: AppHandle, enabled: bool, port: Option<u16>) -> Result<ControlSocketConfig, String> {
+    let mut config = load_control_socket_config(&app)?;
+    config.enabled = enabled;
+    if let Some(port) = port {
+        config.port = port;
+    }
+    save_json(&app, CONTROL_SOCKET_CONFIG_FILE, &config)?;
+
+    if enabled {
+        start_control_socket(&app);
+    } else {
+        stop_control_socket();
+    }
+    Ok(config)
+}
+
+/// Replace the token and drop connected clients
+#[tauri::command]
+pub fn rotate_control_socket_token(app: AppHandle) -> Result<String, String> {
+    let mut config = load_control_socket_config(&app)?;
+    config.token = generate_api_token()?;
+    save_json(&app, CONTROL_SOCKET_CONFIG_FILE, &config)?;
+    start_control_socket(&app);
+    Ok(config.token)
+}
+
+/// Action names accepted by the control socket, for the settings page and plugin setup
+#[tauri::command]
+pub fn get_control_actions(app: AppHandle) -> Vec<ControlAction> {
+    list_control_actions(&app)
+}
@@ -0,0 +1,137 @@
+use tauri::AppHandle;
+use serde::{Deserialize, Serialize};
+use std::backtrace::Backtrace;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::desktop::{generate_id, get_app_data_subdir, now_millis, recent_log_lines};
+
+const CRASH_REPORTS_DIR: &str = "crash_reports";
+const CRASH_LOG_LINES: usize = 200;
+const MAX_CRASH_REPORTS: usize = 10;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CrashReport {
+    pub id: String,
+    pub timestamp: u64,
+    #[serde(rename = "appVersion")]
+    pub app_version: String,
+    pub os: String,
+    pub arch: String,
+    pub thread: String,
+    pub message: String,
+    /// Source location of the panic, when known
+    pub location: Option<String>,
+    pub backtrace: String,
+    #[serde(rename = "logLines")]
+    pub log_lines: Vec<String>,
+    /// Set once the user has seen the report on a later launch
+    #[serde(default)]
+    pub acknowledged: bool,
+    /// Where the report is stored, filled in when loaded
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub path: String,
+}
+
+fn panic_message(info: &std::panic::PanicHookInfo) -> String {
+    if let Some(message) = info.payload().downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = info.payload().downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Unknown panic".to_string()
+    }
+}
+
+fn write_crash_report(dir: &Path, app_version: &str, info: &std::panic::PanicHookInfo) -> Result<PathBuf, String> {
+    let report = CrashReport {
+        id: generate_id(),
+        timestamp: now_millis(),
+        app_version: app_version.to_string(),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        thread: std::thread::current().name().unwrap_or("unnamed").to_string(),
+        message: panic_message(info),
+        location: info.location().map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column())),
+        backtrace: Backtrace::force_capture().to_string(),
+        log_lines: recent_log_lines(CRASH_LOG_LINES),
+        acknowledged: false,
+        path: String::new(),
+    };
+
+    let path = dir.join(format!("crash-{}.json", report.id));
+    let content = serde_json::to_string_pretty(&report)
+        .map_err(|e| format!("Failed to serialize crash report: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(path)
+}
+
+/// Write a crash report for every panic, then hand over to the default hook.
+/// Only Rust panics are captured; native faults in linked libraries are not.
+pub fn install_crash_handler(app: &AppHandle) -> Result<(), String> {
+    let dir = get_app_data_subdir(app, CRASH_REPORTS_DIR)?;
+    let app_version = app.package_info().version.to_string();
+    prune_crash_reports(&dir);
+
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        match write_crash_report(&dir, &app_version, info) {
+            Ok(path) => error!("💥 Panic: {} (report saved to {})", panic_message(info), path.display()),
+            Err(e) => eprintln!("Failed to save crash report: {}", e),
+        }
+        previous(info);
+    }));
+
+    Ok(())
+}
+
+fn load_crash_reports(dir: &Path) -> Vec<CrashReport> {
+    let mut reports: Vec<CrashReport> = fs::read_dir(dir)
+        .map(|entries| entries.flatten()
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|p| {
+                let mut report: CrashReport = serde_json::from_str(&fs::read_to_string(&p).ok()?).ok()?;
+                report.path = p.to_string_lossy().to_string();
+                Some(report)
+            })
+            .collect())
+        .unwrap_or_default();
+    reports.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    reports
+}
+
+/// Keep only the newest reports
+fn prune_crash_reports(dir: &Path) {
+    for report in load_crash_reports(dir).into_iter().skip(MAX_CRASH_REPORTS) {
+        let _ = fs::remove_file(&report.path);
+    }
+}
+
+/// The newest crash report the user hasn't dismissed yet, for offering to open or submit it
+#[tauri::command]
+pub fn get_last_crash_report(app: AppHandle) -> Result<Option<CrashReport>, String> {
+    let dir = get_app_data_subdir(&app, CRASH_REPORTS_DIR)?;
+    Ok(load_crash_reports(&dir).into_iter().next().filter(|r| !r.acknowledged))
+}
+
+#[tauri::command]
+pub fn list_crash_reports(app: AppHandle) -> Result<Vec<CrashReport>, String> {
+    let dir = get_app_data_subdir(&app, CRASH_REPORTS_DIR)?;
+    Ok(load_crash_reports(&dir))
+}
+
+#[tauri::command]
+pub fn dismiss_crash_report(app: AppHandle, id: String) -> Result<(), String> {
+    let dir = get_app_data_subdir(&app, CRASH_REPORTS_DIR)?;
+    let mut report = load_crash_reports(&dir)
+        .into_iter()
+        .find(|r| r.id == id)
+        .ok_or_else(|| format!("Crash report not found: {}", id))?;
+
+    report.acknowledged = true;
+    let path = std::mem::take(&mut report.path);
+    let content = serde_json::to_string_pretty(&report)
+        .map_err(|e| format!("Failed to serialize crash report: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path, e))
+}
@@ -0,0 +1,54 @@
+use keyring::Entry;
+
+/// Service name the tokens are filed under in Keychain / Credential Manager / Secret Service
+const KEYCHAIN_SERVICE: &str = "com.blinko.app";
+const DEFAULT_ACCOUNT: &str = "default";
+
+fn keychain_entry(account: Option<&str>) -> Result<Entry, String> {
+    let account = account.filter(|a| !a.trim().is_empty()).unwrap_or(DEFAULT_ACCOUNT);
+    Entry::new(KEYCHAIN_SERVICE, account)
+        .map_err(|e| format!("Failed to open keychain entry: {}", e))
+}
+
+/// Save a token in the OS keychain, `account` is usually the server URL
+pub fn store_secret(account: Option<&str>, secret: &str) -> Result<(), String> {
+    keychain_entry(account)?
+        .set_password(secret)
+        .map_err(|e| format!("Failed to store token in keychain: {}", e))
+}
+
+/// Read a token from the OS keychain, `None` if nothing is stored
+pub fn read_secret(account: Option<&str>) -> Result<Option<String>, String> {
+    match keychain_entry(account)?.get_password() {
+        Ok(secret) => Ok(Some(secret)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(format!("Failed to read token from keychain: {}", e)),
+    }
+}
+
+/// Remove a token from the OS keychain, missing entries are not an error
+pub fn delete_secret(account: Option<&str>) -> Result<(), String> {
+    match keychain_entry(account)?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(format!("Failed to delete token from keychain: {}", e)),
+    }
+}
+
+#[tauri::command]
+pub fn store_token(account: Option<String>, token: String) -> Result<(), String> {
+    store_secret(account.as_deref(), &token)?;
+    info!("🔐 Token stored in system keychain");
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_token(account: Option<String>) -> Result<Option<String>, String> {
+    read_secret(account.as_deref())
+}
+
+#[tauri::command]
+pub fn delete_token(account: Option<String>) -> Result<(), String> {
+    delete_secret(account.as_deref())?;
+    info!("🔐 Token removed from system keychain");
+    Ok(())
+}
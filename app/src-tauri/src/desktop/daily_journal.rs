@@ -0,0 +1,140 @@
+use tauri::{AppHandle, Emitter};
+use serde::{Deserialize, Serialize};
+use std::sync::{LazyLock, Mutex};
+use chrono::{Local, NaiveTime};
+
+use crate::desktop::{ensure_main_window, is_system_idle, list_templates, load_json_or_default, render_template_content, save_json};
+
+const DAILY_JOURNAL_FILE: &str = "daily_journal.json";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum DailyNoteTrigger {
+    /// At `time` each day
+    Time,
+    /// The first time the user is active on a new day, e.g. after unlocking
+    FirstActivity,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DailyJournalConfig {
+    pub enabled: bool,
+    pub trigger: DailyNoteTrigger,
+    /// Local time as HH:MM, used with the `time` trigger
+    pub time: String,
+    /// Template the daily note starts from
+    #[serde(rename = "templateId")]
+    pub template_id: Option<String>,
+    /// Local date (YYYY-MM-DD) the daily note was last opened for
+    #[serde(rename = "lastOpenedDate", default)]
+    pub last_opened_date: Option<String>,
+}
+
+impl Default for DailyJournalConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            trigger: DailyNoteTrigger::FirstActivity,
+            time: "08:00".to_string(),
+            template_id: None,
+            last_opened_date: None,
+        }
+    }
+}
+
+/// Payload of `open-note`; the frontend opens today's note if it exists and creates it from `content` otherwise
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenNoteEvent {
+    pub kind: String,
+    /// YYYY-MM-DD, identifies the daily note
+    pub date: String,
+    pub content: String,
+}
+
+static DAILY_JOURNAL_CONFIG: LazyLock<Mutex<Option<DailyJournalConfig>>> = LazyLock::new(|| Mutex::new(None));
+
+fn read_config(app: &AppHandle) -> DailyJournalConfig {
+    DAILY_JOURNAL_CONFIG.lock().unwrap()
+        .get_or_insert_with(|| load_json_or_default(app, DAILY_JOURNAL_FILE))
+        .clone()
+}
+
+fn open_daily_note_for_today(app: &AppHandle, config: &DailyJournalConfig) -> Result<(), String> {
+    let date = Local::now().format("%Y-%m-%d").to_string();
+    let content = config.template_id.as_ref()
+        .and_then(|id| list_templates(app.clone()).into_iter().find(|t| &t.id == id))
+        .map(|template| render_template_content(&template.content))
+        .unwrap_or_default();
+
+    let window = ensure_main_window(app)?;
+    let _ = window.show();
+    let _ = window.set_focus();
+    window.emit("open-note", OpenNoteEvent { kind: "daily".to_string(), date: date.clone(), content })
+        .map_err(|e| format!("Failed to emit open-note event: {}", e))?;
+
+    let mut config = config.clone();
+    config.last_opened_date = Some(date.clone());
+    save_json(app, DAILY_JOURNAL_FILE, &config)?;
+    *DAILY_JOURNAL_CONFIG.lock().unwrap() = Some(config);
+
+    info!("📔 Opened daily note for {}", date);
+    Ok(())
+}
+
+/// Runs on every reminder scheduler tick, so journaling shares its timing with reminders
+pub fn check_daily_journal(app: &AppHandle) {
+    let config = read_config(app);
+    if !config.enabled {
+        return;
+    }
+
+    let now = Local::now();
+    let today = now.format("%Y-%m-%d").to_string();
+    if config.last_opened_date.as_deref() == Some(today.as_str()) {
+        return;
+    }
+
+    let due = match config.trigger {
+        DailyNoteTrigger::Time => match NaiveTime::parse_from_str(&config.time, "%H:%M") {
+            Ok(time) => now.time() >= time,
+            Err(e) => {
+                warn!("⚠️ Invalid daily note time '{}': {}", config.time, e);
+                false
+            }
+        },
+        DailyNoteTrigger::FirstActivity => !is_system_idle(),
+    };
+    if !due {
+        return;
+    }
+
+    if let Err(e) = open_daily_note_for_today(app, &config) {
+        error!("❌ Failed to open daily note: {}", e);
+    }
+}
+
+#[tauri::command]
+pub fn get_daily_journal_config(app: AppHandle) -> DailyJournalConfig {
+    read_config(&app)
+}
+
+#[tauri::command]
+pub fn save_daily_journal_config(app: AppHandle, mut config: DailyJournalConfig) -> Result<(), String> {
+    NaiveTime::parse_from_str(&config.time, "%H:%M")
+        .map_err(|e| format!("Invalid time '{}': {}", config.time, e))?;
+
+    // Keep the date the note was last opened, the frontend doesn't track it
+    config.last_opened_date = read_config(&app).last_opened_date;
+    save_json(&app, DAILY_JOURNAL_FILE, &config)?;
+    *DAILY_JOURNAL_CONFIG.lock().unwrap() = Some(config);
+    Ok(())
+}
+
+/// Open today's daily note now, e.g. from the tray or palette
+#[tauri::command]
+pub async fn open_daily_note(app: AppHandle) -> Result<(), String> {
+    let config = read_config(&app);
+    tauri::async_runtime::spawn_blocking(move || open_daily_note_for_today(&app, &config))
+        .await
+        .map_err(|e| format!("Failed to open daily note: {}", e))?
+}
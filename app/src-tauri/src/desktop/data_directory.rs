@@ -0,0 +1,136 @@
+use tauri::AppHandle;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::desktop::{
+    close_offline_db, default_app_data_dir, get_app_data_dir, load_data_location, portable_data_dir, DataLocation,
+    DATA_LOCATION_FILE,
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DataDirectoryInfo {
+    /// Directory in use by this run
+    pub current: String,
+    /// OS app data directory
+    pub default: String,
+    /// Custom directory that applies from the next launch
+    pub custom: Option<String>,
+    pub portable: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DataDirectoryChange {
+    #[serde(rename = "dataDir")]
+    pub data_dir: String,
+    #[serde(rename = "copiedFiles")]
+    pub copied_files: usize,
+    /// The old files are left in place and can be removed once the new location works
+    #[serde(rename = "previousDir")]
+    pub previous_dir: String,
+    /// Every module caches its paths, so the new location is only used after a restart
+    #[serde(rename = "restartRequired")]
+    pub restart_required: bool,
+}
+
+fn copy_dir_recursive(source: &Path, target: &Path, skip_root_file: &str) -> Result<usize, String> {
+    fs::create_dir_all(target).map_err(|e| format!("Failed to create {}: {}", target.display(), e))?;
+    let mut copied = 0;
+
+    let entries = fs::read_dir(source).map_err(|e| format!("Failed to read {}: {}", source.display(), e))?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name();
+        if name.to_string_lossy() == skip_root_file {
+            continue;
+        }
+        let destination = target.join(&name);
+        if path.is_dir() {
+            copied += copy_dir_recursive(&path, &destination, "")?;
+        } else {
+            fs::copy(&path, &destination)
+                .map_err(|e| format!("Failed to copy {}: {}", path.display(), e))?;
+            copied += 1;
+        }
+    }
+
+    Ok(copied)
+}
+
+fn validate_target(target: &Path, current: &Path, migrate: bool) -> Result<(), String> {
+    if !target.is_absolute() {
+        return Err("Data directory must be an absolute path".to_string());
+    }
+    if target.starts_with(current) || current.starts_with(target) {
+        return Err("Data directory can't be inside the current one or contain it".to_string());
+    }
+
+    fs::create_dir_all(target).map_err(|e| format!("Failed to create {}: {}", target.display(), e))?;
+    let probe = target.join(".blinko-write-test");
+    fs::write(&probe, b"ok").map_err(|e| format!("Data directory is not writable: {}", e))?;
+    let _ = fs::remove_file(&probe);
+
+    let is_empty = fs::read_dir(target).map(|mut entries| entries.next().is_none()).unwrap_or(true);
+    if migrate && !is_empty {
+        return Err(format!("{} is not empty, choose an empty folder or use its existing data", target.display()));
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_data_directory_info(app: AppHandle) -> Result<DataDirectoryInfo, String> {
+    Ok(DataDirectoryInfo {
+        current: get_app_data_dir(&app)?.to_string_lossy().to_string(),
+        default: default_app_data_dir(&app)?.to_string_lossy().to_string(),
+        custom: load_data_location(&app).path,
+        portable: portable_data_dir().is_some(),
+    })
+}
+
+/// Move data to `path`, or back to the OS location when `path` is empty.
+/// With `migrate` off, an existing Blinko data folder at `path` is used as is.
+#[tauri::command]
+pub async fn set_data_directory(app: AppHandle, path: Option<String>, migrate: Option<bool>) -> Result<DataDirectoryChange, String> {
+    if portable_data_dir().is_some() {
+        return Err("The data directory can't be changed in portable mode".to_string());
+    }
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let current = get_app_data_dir(&app)?;
+        let default = default_app_data_dir(&app)?;
+        let custom = path.filter(|p| !p.trim().is_empty()).map(PathBuf::from);
+        let target = custom.clone().unwrap_or_else(|| default.clone());
+        if target == current {
+            return Err("Data is already stored in this directory".to_string());
+        }
+
+        let migrate = migrate.unwrap_or(true);
+        // The OS location still holds the settings pointer and older copies, so it may be overwritten
+        validate_target(&target, &current, migrate && target != default)?;
+
+        let copied_files = if migrate {
+            // Flush pending writes before copying the database
+            close_offline_db();
+            copy_dir_recursive(&current, &target, DATA_LOCATION_FILE)?
+        } else {
+            0
+        };
+
+        let location = DataLocation { path: custom.map(|p| p.to_string_lossy().to_string()) };
+        let content = serde_json::to_string_pretty(&location)
+            .map_err(|e| format!("Failed to serialize {}: {}", DATA_LOCATION_FILE, e))?;
+        fs::create_dir_all(&default).map_err(|e| format!("Failed to create {}: {}", default.display(), e))?;
+        fs::write(default.join(DATA_LOCATION_FILE), content)
+            .map_err(|e| format!("Failed to write {}: {}", DATA_LOCATION_FILE, e))?;
+
+        info!("📁 Data directory set to {} ({} files copied)", target.display(), copied_files);
+        Ok(DataDirectoryChange {
+            data_dir: target.to_string_lossy().to_string(),
+            copied_files,
+            previous_dir: current.to_string_lossy().to_string(),
+            restart_required: true,
+        })
+    })
+    .await
+    .map_err(|e| format!("Changing data directory failed: {}", e))?
+}
@@ -0,0 +1,94 @@
+use tauri::AppHandle;
+
+#[cfg(target_os = "linux")]
+use std::sync::{LazyLock, Mutex};
+
+#[cfg(target_os = "linux")]
+use crate::desktop::{import_files_into_window, open_quicknote_with_text, toggle_dictation, toggle_quicknote_window};
+
+#[cfg(target_os = "linux")]
+const DBUS_SERVICE_NAME: &str = "org.blinko.App";
+#[cfg(target_os = "linux")]
+const DBUS_OBJECT_PATH: &str = "/org/blinko/App";
+
+// The service stays registered for as long as the connection lives
+#[cfg(target_os = "linux")]
+static DBUS_CONNECTION: LazyLock<Mutex<Option<zbus::blocking::Connection>>> = LazyLock::new(|| Mutex::new(None));
+
+/// Session bus object for desktop shortcuts and scripts, e.g.
+/// `gdbus call --session --dest org.blinko.App --object-path /org/blinko/App --method org.blinko.App.QuickNote ""`
+#[cfg(target_os = "linux")]
+struct BlinkoDbusService {
+    app: AppHandle,
+}
+
+#[cfg(target_os = "linux")]
+#[zbus::interface(name = "org.blinko.App")]
+impl BlinkoDbusService {
+    /// Toggle the quick note window, or open it prefilled when `text` is not empty
+    fn quick_note(&self, text: &str) -> zbus::fdo::Result<()> {
+        info!("🐧 D-Bus QuickNote");
+        let result = if text.is_empty() {
+            toggle_quicknote_window(self.app.clone())
+        } else {
+            open_quicknote_with_text(&self.app, text)
+        };
+        result.map_err(zbus::fdo::Error::Failed)
+    }
+
+    /// Start or stop a dictation
+    fn dictate(&self) -> zbus::fdo::Result<()> {
+        info!("🐧 D-Bus Dictate");
+        toggle_dictation(&self.app).map_err(zbus::fdo::Error::Failed)
+    }
+
+    /// Open the quick note window with the clipboard text, or with the copied image attached
+    fn capture_clipboard(&self) -> zbus::fdo::Result<()> {
+        info!("🐧 D-Bus CaptureClipboard");
+        capture_clipboard_to_quicknote(&self.app).map_err(zbus::fdo::Error::Failed)
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn capture_clipboard_to_quicknote(app: &AppHandle) -> Result<(), String> {
+    let mut clipboard = arboard::Clipboard::new().map_err(|e| format!("Failed to access clipboard: {}", e))?;
+
+    if let Ok(text) = clipboard.get_text() {
+        if !text.trim().is_empty() {
+            return open_quicknote_with_text(app, &text);
+        }
+    }
+
+    let image = clipboard.get_image().map_err(|_| "Clipboard is empty".to_string())?;
+    let path = std::env::temp_dir().join(format!("clipboard-{}.png", crate::desktop::now_millis()));
+    image::RgbaImage::from_raw(image.width as u32, image.height as u32, image.bytes.into_owned())
+        .ok_or("Invalid clipboard image data")?
+        .save(&path)
+        .map_err(|e| format!("Failed to save clipboard image: {}", e))?;
+
+    open_quicknote_with_text(app, "")?;
+    import_files_into_window(app.clone(), "quicknote".to_string(), vec![path]);
+    Ok(())
+}
+
+/// Claim `org.blinko.App` on the session bus, so GNOME and KDE shortcuts work without global hotkeys
+pub fn start_dbus_service(app: &AppHandle) {
+    #[cfg(target_os = "linux")]
+    {
+        let service = BlinkoDbusService { app: app.clone() };
+        let connection = zbus::blocking::ConnectionBuilder::session()
+            .and_then(|builder| builder.name(DBUS_SERVICE_NAME))
+            .and_then(|builder| builder.serve_at(DBUS_OBJECT_PATH, service))
+            .and_then(|builder| builder.build());
+        match connection {
+            Ok(connection) => {
+                *DBUS_CONNECTION.lock().unwrap() = Some(connection);
+                info!("🐧 D-Bus service {} registered", DBUS_SERVICE_NAME);
+            }
+            Err(e) => error!("❌ Failed to register D-Bus service {}: {}", DBUS_SERVICE_NAME, e),
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    let _ = app;
+}
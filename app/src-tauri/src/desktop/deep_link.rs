@@ -0,0 +1,166 @@
+use tauri::{AppHandle, Emitter, Url};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex};
+
+use crate::desktop::{ensure_main_window, navigate_main_to_ai_with_prompt, open_quicknote_with_text, spawn_clip_article};
+
+pub const DEEP_LINK_SCHEME: &str = "blinko";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "action")]
+pub enum DeepLinkAction {
+    /// blinko://note/<id>
+    #[serde(rename = "open-note")]
+    OpenNote {
+        #[serde(rename = "noteId")]
+        note_id: String,
+    },
+    /// blinko://new?text=...
+    #[serde(rename = "create-note")]
+    CreateNote { text: String },
+    /// blinko://ai?prompt=...
+    #[serde(rename = "ai-chat")]
+    AiChat { prompt: String },
+    /// blinko://clip?url=...
+    #[serde(rename = "clip-url")]
+    ClipUrl { url: String },
+}
+
+// Links received before the frontend was ready to listen
+static PENDING_DEEP_LINKS: LazyLock<Mutex<Vec<DeepLinkAction>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+static FRONTEND_READY: AtomicBool = AtomicBool::new(false);
+
+fn query_param(url: &Url, key: &str) -> Option<String> {
+    url.query_pairs()
+        .find(|(k, _)| k == key)
+        .map(|(_, v)| v.to_string())
+}
+
+/// Parse a blinko:// URL into a routed action
+pub fn parse_deep_link(url: &str) -> Result<DeepLinkAction, String> {
+    let url = Url::parse(url).map_err(|e| format!("Invalid deep link '{}': {}", url, e))?;
+
+    if url.scheme() != DEEP_LINK_SCHEME {
+        return Err(format!("Unsupported URL scheme: {}", url.scheme()));
+    }
+
+    let route = url.host_str().unwrap_or_default();
+    let segment = url.path().trim_matches('/').to_string();
+
+    match route {
+        "note" | "open" => {
+            let note_id = if segment.is_empty() { query_param(&url, "id") } else { Some(segment) }
+                .ok_or("Missing note id in deep link")?;
+            Ok(DeepLinkAction::OpenNote { note_id })
+        }
+        "new" | "create" => {
+            let text = query_param(&url, "text").unwrap_or_default();
+            Ok(DeepLinkAction::CreateNote { text })
+        }
+        "ai" | "chat" => {
+            let prompt = query_param(&url, "prompt").unwrap_or_default();
+            Ok(DeepLinkAction::AiChat { prompt })
+        }
+        "clip" => {
+            let page = query_param(&url, "url").ok_or("Missing url in clip deep link")?;
+            Ok(DeepLinkAction::ClipUrl { url: page })
+        }
+        other => Err(format!("Unknown deep link route: {}", other)),
+    }
+}
+
+/// Route a deep link to the main window, creating it if necessary
+pub fn handle_deep_link(app: &AppHandle, url: &str) {
+    debug!("🔗 Handling deep link: {}", url);
+
+    let action = match parse_deep_link(url) {
+        Ok(action) => action,
+        Err(e) => {
+            error!("❌ {}", e);
+            return;
+        }
+    };
+
+    if let DeepLinkAction::AiChat { ref prompt } = action {
+        // Reuse the existing quick AI -> main window flow
+        if let Err(e) = ensure_main_window(app)
+            .and_then(|_| navigate_main_to_ai_with_prompt(app.clone(), prompt.clone()))
+        {
+            error!("Failed to open AI chat from deep link: {}", e);
+        }
+        return;
+    }
+
+    // Clipping runs in the background, the note shows up once it is saved
+    if let DeepLinkAction::ClipUrl { ref url } = action {
+        spawn_clip_article(app, url.clone(), None);
+        return;
+    }
+
+    // The quicknote window keeps the text until its editor is up, then the user decides to save
+    if let DeepLinkAction::CreateNote { ref text } = action {
+        if let Err(e) = open_quicknote_with_text(app, text) {
+            error!("Failed to open quick note from deep link: {}", e);
+        }
+        return;
+    }
+
+    let window = match ensure_main_window(app) {
+        Ok(window) => window,
+        Err(e) => {
+            error!("❌ {}", e);
+            return;
+        }
+    };
+
+    let _ = window.show();
+    let _ = window.unminimize();
+    let _ = window.set_focus();
+
+    let event = match action {
+        DeepLinkAction::OpenNote { .. } => "deep-link-open-note",
+        DeepLinkAction::CreateNote { .. } | DeepLinkAction::AiChat { .. } | DeepLinkAction::ClipUrl { .. } => unreachable!(),
+    };
+
+    if !FRONTEND_READY.load(Ordering::SeqCst) {
+        PENDING_DEEP_LINKS.lock().unwrap().push(action);
+        return;
+    }
+
+    if let Err(e) = window.emit(event, &action) {
+        error!("Failed to emit {} event: {}", event, e);
+    }
+}
+
+/// Register the URL scheme and route incoming links
+pub fn setup_deep_links(app: &AppHandle) {
+    use tauri_plugin_deep_link::DeepLinkExt;
+
+    // Installed bundles register the scheme themselves, dev builds on Linux/Windows need it at runtime
+    #[cfg(any(target_os = "linux", all(debug_assertions, target_os = "windows")))]
+    if let Err(e) = app.deep_link().register_all() {
+        error!("Failed to register deep link schemes: {}", e);
+    }
+
+    // Link that launched the app
+    if let Ok(Some(urls)) = app.deep_link().get_current() {
+        for url in urls {
+            handle_deep_link(app, url.as_str());
+        }
+    }
+
+    let app_handle = app.clone();
+    app.deep_link().on_open_url(move |event| {
+        for url in event.urls() {
+            handle_deep_link(&app_handle, url.as_str());
+        }
+    });
+}
+
+/// Called once the frontend listens for deep link events, returns links that arrived earlier
+#[tauri::command]
+pub fn take_pending_deep_links() -> Vec<DeepLinkAction> {
+    FRONTEND_READY.store(true, Ordering::SeqCst);
+    std::mem::take(&mut *PENDING_DEEP_LINKS.lock().unwrap())
+}
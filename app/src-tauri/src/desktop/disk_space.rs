@@ -0,0 +1,57 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Left free on top of what an operation needs, so the OS and other apps keep working
+const RESERVED_FREE_SPACE: u64 = 64 * 1024 * 1024;
+
+/// The volume of `path` can't hold what is about to be written
+#[derive(Debug, Clone, PartialEq)]
+pub struct InsufficientDiskSpace {
+    pub path: PathBuf,
+    /// Bytes needed, reserve included
+    pub required: u64,
+    pub available: u64,
+}
+
+impl fmt::Display for InsufficientDiskSpace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Not enough disk space on {}: {} bytes required, {} bytes available",
+            self.path.display(),
+            self.required,
+            self.available
+        )
+    }
+}
+
+impl std::error::Error for InsufficientDiskSpace {}
+
+impl From<InsufficientDiskSpace> for String {
+    fn from(error: InsufficientDiskSpace) -> Self {
+        error.to_string()
+    }
+}
+
+/// Fail early when the volume `path` is on has less than `bytes` free.
+/// `path` may not exist yet, its closest existing parent is checked instead.
+pub fn ensure_disk_space(path: &Path, bytes: u64) -> Result<(), InsufficientDiskSpace> {
+    let Some(existing) = path.ancestors().find(|p| p.exists()) else {
+        return Ok(());
+    };
+    // Not knowing is no reason to refuse, the write itself will still report a full disk
+    let available = match fs4::available_space(existing) {
+        Ok(available) => available,
+        Err(e) => {
+            warn!("Failed to read free disk space of {}: {}", existing.display(), e);
+            return Ok(());
+        }
+    };
+
+    let required = bytes.saturating_add(RESERVED_FREE_SPACE);
+    if available < required {
+        warn!("💾 Not enough disk space on {}: {} of {} bytes", existing.display(), available, required);
+        return Err(InsufficientDiskSpace { path: existing.to_path_buf(), required, available });
+    }
+    Ok(())
+}
@@ -0,0 +1,397 @@
+use tauri::{AppHandle, Emitter, Manager};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use reqwest::header::{AUTHORIZATION, RANGE};
+use reqwest::StatusCode;
+
+use crate::desktop::{generate_id, get_server_token_for_url, http_client_builder, load_json_or_default, now_millis, save_json, set_tray_status};
+
+const DOWNLOADS_FILE: &str = "downloads.json";
+const MAX_CONCURRENT_DOWNLOADS: usize = 3;
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(300);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum DownloadStatus {
+    Queued,
+    Downloading,
+    Paused,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DownloadItem {
+    pub id: String,
+    pub url: String,
+    #[serde(rename = "fileName")]
+    pub file_name: String,
+    /// Final location, data is written to `<path>.part` until complete
+    pub path: String,
+    pub status: DownloadStatus,
+    pub downloaded: u64,
+    pub total: Option<u64>,
+    pub error: Option<String>,
+    #[serde(rename = "createdAt")]
+    pub created_at: u64,
+}
+
+static DOWNLOADS: LazyLock<Mutex<Option<Vec<DownloadItem>>>> = LazyLock::new(|| Mutex::new(None));
+static TRAY_INDICATOR_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Run a closure against the download list, loading it from disk on first use
+fn with_downloads<T>(app: &AppHandle, f: impl FnOnce(&mut Vec<DownloadItem>) -> T) -> T {
+    let mut guard = DOWNLOADS.lock().unwrap();
+    if guard.is_none() {
+        *guard = Some(load_json_or_default(app, DOWNLOADS_FILE));
+    }
+    f(guard.as_mut().unwrap())
+}
+
+fn persist_downloads(app: &AppHandle) {
+    let items = with_downloads(app, |items| items.clone());
+    if let Err(e) = save_json(app, DOWNLOADS_FILE, &items) {
+        error!("Failed to save download queue: {}", e);
+    }
+}
+
+fn update_download(app: &AppHandle, id: &str, f: impl FnOnce(&mut DownloadItem)) -> Option<DownloadItem> {
+    with_downloads(app, |items| {
+        items.iter_mut().find(|item| item.id == id).map(|item| {
+            f(item);
+            item.clone()
+        })
+    })
+}
+
+fn download_status(app: &AppHandle, id: &str) -> Option<DownloadStatus> {
+    with_downloads(app, |items| items.iter().find(|item| item.id == id).map(|item| item.status.clone()))
+}
+
+fn part_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".part");
+    PathBuf::from(name)
+}
+
+/// Avoid overwriting existing files by appending " (n)" to the name
+fn unique_target(dir: &Path, file_name: &str) -> PathBuf {
+    let candidate = dir.join(file_name);
+    if !candidate.exists() && !part_path(&candidate).exists() {
+        return candidate;
+    }
+
+    let path = Path::new(file_name);
+    let stem = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let extension = path.extension().map(|e| format!(".{}", e.to_string_lossy())).unwrap_or_default();
+    (1..)
+        .map(|n| dir.join(format!("{} ({}){}", stem, n, extension)))
+        .find(|p| !p.exists() && !part_path(p).exists())
+        .unwrap()
+}
+
+fn file_name_from_url(url: &str) -> String {
+    url.split(['?', '#']).next()
+        .and_then(|path| path.rsplit('/').next())
+        .filter(|name| !name.is_empty())
+        .unwrap_or("download")
+        .to_string()
+}
+
+/// Show overall progress of active downloads in the tray
+fn update_tray_indicator(app: &AppHandle) {
+    let (active, downloaded, total) = with_downloads(app, |items| {
+        items.iter()
+            .filter(|item| matches!(item.status, DownloadStatus::Downloading | DownloadStatus::Queued))
+            .fold((0usize, 0u64, 0u64), |(count, done, total), item| {
+                (count + 1, done + item.downloaded, total + item.total.unwrap_or(item.downloaded))
+            })
+    });
+
+    if active == 0 {
+        if TRAY_INDICATOR_ACTIVE.swap(false, Ordering::SeqCst) {
+            set_tray_status(app, None, None);
+        }
+        return;
+    }
+
+    TRAY_INDICATOR_ACTIVE.store(true, Ordering::SeqCst);
+    let percent = if total > 0 { downloaded * 100 / total } else { 0 };
+    set_tray_status(
+        app,
+        Some(&format!("⬇ {}%", percent)),
+        Some(&format!("Blinko - Downloading {} file(s), {}%", active, percent)),
+    );
+}
+
+fn emit_download(app: &AppHandle, event: &str, item: &DownloadItem) {
+    if let Err(e) = app.emit(event, item) {
+        error!("Failed to emit {} event: {}", event, e);
+    }
+}
+
+/// Download with HTTP range requests so interrupted transfers continue where they stopped
+fn run_download(app: &AppHandle, item: &DownloadItem) -> Result<(), String> {
+    let target = PathBuf::from(&item.path);
+    let partial = part_path(&target);
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create download folder: {}", e))?;
+    }
+
+    let existing = fs::metadata(&partial).map(|m| m.len()).unwrap_or(0);
+    let client = http_client_builder()
+        .connect_timeout(CONNECT_TIMEOUT)
+        .timeout(None)
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+    let mut request = client.get(&item.url);
+    if existing > 0 {
+        request = request.header(RANGE, format!("bytes={}-", existing));
+    }
+    if let Some(token) = get_server_token_for_url(&item.url) {
+        request = request.header(AUTHORIZATION, format!("Bearer {}", token));
+    }
+
+    let mut response = request.send().map_err(|e| format!("Network error: {}", e))?;
+    let status = response.status();
+
+    if status == StatusCode::RANGE_NOT_SATISFIABLE && existing > 0 {
+        // The partial file already holds everything
+        fs::rename(&partial, &target).map_err(|e| format!("Failed to finalize download: {}", e))?;
+        return Ok(());
+    }
+    if !status.is_success() {
+        return Err(format!("Server returned {}", status));
+    }
+
+    // A 200 means the server ignored the range, start over
+    let resumed = status == StatusCode::PARTIAL_CONTENT;
+    let mut downloaded = if resumed { existing } else { 0 };
+    let total = response.content_length().map(|len| len + downloaded);
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(resumed)
+        .truncate(!resumed)
+        .open(&partial)
+        .map_err(|e| format!("Failed to open {}: {}", partial.display(), e))?;
+
+    update_download(app, &item.id, |d| {
+        d.downloaded = downloaded;
+        d.total = total;
+    });
+
+    let mut buffer = vec![0u8; 64 * 1024];
+    let mut last_emit = Instant::now();
+
+    loop {
+        // Pause and cancel are signalled by changing the item status
+        if download_status(app, &item.id) != Some(DownloadStatus::Downloading) {
+            return Ok(());
+        }
+
+        let read = response.read(&mut buffer).map_err(|e| format!("Network error: {}", e))?;
+        if read == 0 {
+            break;
+        }
+        file.write_all(&buffer[..read]).map_err(|e| format!("Failed to write download: {}", e))?;
+        downloaded += read as u64;
+
+        if last_emit.elapsed() >= PROGRESS_INTERVAL {
+            last_emit = Instant::now();
+            if let Some(updated) = update_download(app, &item.id, |d| d.downloaded = downloaded) {
+                emit_download(app, "download-progress", &updated);
+            }
+            update_tray_indicator(app);
+        }
+    }
+
+    file.flush().map_err(|e| format!("Failed to write download: {}", e))?;
+    drop(file);
+    fs::rename(&partial, &target).map_err(|e| format!("Failed to finalize download: {}", e))?;
+    update_download(app, &item.id, |d| d.downloaded = downloaded);
+    Ok(())
+}
+
+/// Start queued downloads up to the concurrency limit
+fn pump_downloads(app: &AppHandle) {
+    let to_start: Vec<DownloadItem> = with_downloads(app, |items| {
+        let running = items.iter().filter(|i| i.status == DownloadStatus::Downloading).count();
+        let mut started = Vec::new();
+        for item in items.iter_mut().filter(|i| i.status == DownloadStatus::Queued) {
+            if running + started.len() >= MAX_CONCURRENT_DOWNLOADS {
+                break;
+            }
+            item.status = DownloadStatus::Downloading;
+            item.error = None;
+            started.push(item.clone());
+        }
+        started
+    });
+
+    for item in to_start {
+        let app_handle = app.clone();
+        std::thread::spawn(move || {
+            info!("⬇️ Downloading {}", item.url);
+            let result = run_download(&app_handle, &item);
+
+            let finished = update_download(&app_handle, &item.id, |d| {
+                match result {
+                    Ok(()) if d.status == DownloadStatus::Downloading => {
+                        d.status = DownloadStatus::Completed;
+                        d.total = Some(d.downloaded);
+                    }
+                    Ok(()) => {}
+                    Err(ref e) => {
+                        d.status = DownloadStatus::Failed;
+                        d.error = Some(e.clone());
+                    }
+                }
+            });
+
+            if let Some(finished) = finished {
+                match finished.status {
+                    DownloadStatus::Completed => {
+                        info!("✅ Downloaded {}", finished.path);
+                        emit_download(&app_handle, "download-completed", &finished);
+                    }
+                    DownloadStatus::Failed => {
+                        error!("❌ Download failed: {}", finished.error.as_deref().unwrap_or_default());
+                        emit_download(&app_handle, "download-failed", &finished);
+                    }
+                    DownloadStatus::Cancelled => {
+                        let _ = fs::remove_file(part_path(Path::new(&finished.path)));
+                    }
+                    _ => {}
+                }
+            }
+
+            persist_downloads(&app_handle);
+            update_tray_indicator(&app_handle);
+            pump_downloads(&app_handle);
+        });
+    }
+}
+
+/// Requeue downloads that were running when the app quit
+pub fn resume_pending_downloads(app: &AppHandle) {
+    let resumed = with_downloads(app, |items| {
+        items.iter_mut()
+            .filter(|item| item.status == DownloadStatus::Downloading)
+            .map(|item| item.status = DownloadStatus::Queued)
+            .count()
+    });
+    if resumed > 0 {
+        info!("⬇️ Resuming {} interrupted download(s)", resumed);
+    }
+    pump_downloads(app);
+}
+
+#[tauri::command]
+pub fn enqueue_download(
+    app: AppHandle,
+    url: String,
+    file_name: Option<String>,
+    directory: Option<String>,
+) -> Result<DownloadItem, String> {
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        return Err(format!("Unsupported download URL: {}", url));
+    }
+
+    let dir = match directory {
+        Some(dir) => PathBuf::from(dir),
+        None => app.path().download_dir()
+            .map_err(|e| format!("Failed to get downloads directory: {}", e))?,
+    };
+    let file_name = file_name
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or_else(|| file_name_from_url(&url))
+        .replace(['/', '\\'], "_");
+
+    let item = DownloadItem {
+        id: generate_id(),
+        path: unique_target(&dir, &file_name).to_string_lossy().to_string(),
+        url,
+        file_name,
+        status: DownloadStatus::Queued,
+        downloaded: 0,
+        total: None,
+        error: None,
+        created_at: now_millis(),
+    };
+
+    with_downloads(&app, |items| items.push(item.clone()));
+    persist_downloads(&app);
+    pump_downloads(&app);
+    update_tray_indicator(&app);
+    Ok(item)
+}
+
+#[tauri::command]
+pub fn list_downloads(app: AppHandle) -> Vec<DownloadItem> {
+    with_downloads(&app, |items| items.clone())
+}
+
+#[tauri::command]
+pub fn pause_download(app: AppHandle, id: String) -> Result<(), String> {
+    update_download(&app, &id, |d| {
+        if matches!(d.status, DownloadStatus::Queued | DownloadStatus::Downloading) {
+            d.status = DownloadStatus::Paused;
+        }
+    })
+    .ok_or_else(|| format!("Download not found: {}", id))?;
+    persist_downloads(&app);
+    update_tray_indicator(&app);
+    Ok(())
+}
+
+/// Resume a paused or failed download from where it stopped
+#[tauri::command]
+pub fn resume_download(app: AppHandle, id: String) -> Result<(), String> {
+    update_download(&app, &id, |d| {
+        if matches!(d.status, DownloadStatus::Paused | DownloadStatus::Failed) {
+            d.status = DownloadStatus::Queued;
+        }
+    })
+    .ok_or_else(|| format!("Download not found: {}", id))?;
+    persist_downloads(&app);
+    pump_downloads(&app);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn cancel_download(app: AppHandle, id: String) -> Result<(), String> {
+    let item = update_download(&app, &id, |d| {
+        if d.status != DownloadStatus::Completed {
+            d.status = DownloadStatus::Cancelled;
+        }
+    })
+    .ok_or_else(|| format!("Download not found: {}", id))?;
+
+    // Running downloads clean up their own partial file when they notice the cancel
+    if item.status == DownloadStatus::Cancelled {
+        let _ = fs::remove_file(part_path(Path::new(&item.path)));
+    }
+    persist_downloads(&app);
+    update_tray_indicator(&app);
+    Ok(())
+}
+
+/// Remove completed, failed and cancelled entries from the list
+#[tauri::command]
+pub fn clear_finished_downloads(app: AppHandle) {
+    with_downloads(&app, |items| {
+        items.retain(|item| matches!(item.status, DownloadStatus::Queued | DownloadStatus::Downloading | DownloadStatus::Paused));
+    });
+    persist_downloads(&app);
+}
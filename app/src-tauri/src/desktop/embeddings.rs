@@ -0,0 +1,211 @@
+use tauri::AppHandle;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+
+use fastembed::{EmbeddingModel, InitOptions, TextEmbedding};
+
+use crate::desktop::{get_app_data_subdir, load_json_or_default, save_json, SearchNote};
+
+const EMBEDDINGS_MODEL_DIR: &str = "models/embeddings";
+const VECTOR_INDEX_FILE: &str = "vector_index.json";
+const MODEL_NAME: &str = "all-MiniLM-L6-v2";
+const EMBED_BATCH_SIZE: usize = 32;
+// MiniLM only looks at the first 256 tokens, longer text just costs time
+const MAX_EMBED_CHARS: usize = 2000;
+const MAX_CONTEXT_CHARS: usize = 6000;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct VectorEntry {
+    /// Hash of the embedded text, to skip unchanged notes
+    hash: String,
+    text: String,
+    vector: Vec<f32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+struct VectorIndex {
+    model: String,
+    entries: HashMap<i64, VectorEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SemanticHit {
+    pub id: i64,
+    /// Cosine similarity in [-1, 1]
+    pub score: f32,
+    pub text: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QuickAiContext {
+    /// Prompt with the retrieved notes prepended
+    pub prompt: String,
+    pub sources: Vec<SemanticHit>,
+}
+
+static EMBEDDING_MODEL: LazyLock<Mutex<Option<TextEmbedding>>> = LazyLock::new(|| Mutex::new(None));
+static VECTOR_INDEX: LazyLock<Mutex<Option<VectorIndex>>> = LazyLock::new(|| Mutex::new(None));
+
+/// Run a closure with the embedding model, downloading it to app data on first use
+fn with_embedding_model<T>(
+    app: &AppHandle,
+    f: impl FnOnce(&TextEmbedding) -> Result<T, String>,
+) -> Result<T, String> {
+    let mut guard = EMBEDDING_MODEL.lock().unwrap();
+    if guard.is_none() {
+        let cache_dir = get_app_data_subdir(app, EMBEDDINGS_MODEL_DIR)?;
+        info!("🧠 Loading embedding model {} from {}", MODEL_NAME, cache_dir.display());
+        let model = TextEmbedding::try_new(
+            InitOptions::new(EmbeddingModel::AllMiniLML6V2)
+                .with_cache_dir(cache_dir)
+                .with_show_download_progress(false),
+        )
+        .map_err(|e| format!("Failed to load embedding model: {}", e))?;
+        *guard = Some(model);
+    }
+    f(guard.as_ref().unwrap())
+}
+
+fn with_vector_index<T>(app: &AppHandle, f: impl FnOnce(&mut VectorIndex) -> T) -> T {
+    let mut guard = VECTOR_INDEX.lock().unwrap();
+    if guard.is_none() {
+        let mut index: VectorIndex = load_json_or_default(app, VECTOR_INDEX_FILE);
+        if index.model != MODEL_NAME {
+            // Vectors from another model are not comparable
+            index = VectorIndex { model: MODEL_NAME.to_string(), entries: HashMap::new() };
+        }
+        *guard = Some(index);
+    }
+    f(guard.as_mut().unwrap())
+}
+
+fn save_vector_index(app: &AppHandle) -> Result<(), String> {
+    with_vector_index(app, |index| save_json(app, VECTOR_INDEX_FILE, index))
+}
+
+fn normalize(mut vector: Vec<f32>) -> Vec<f32> {
+    let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|v| *v /= norm);
+    }
+    vector
+}
+
+fn embed_texts(app: &AppHandle, texts: Vec<String>) -> Result<Vec<Vec<f32>>, String> {
+    with_embedding_model(app, |model| {
+        model.embed(texts, Some(EMBED_BATCH_SIZE))
+            .map_err(|e| format!("Failed to compute embeddings: {}", e))
+    })
+    .map(|vectors| vectors.into_iter().map(normalize).collect())
+}
+
+/// Compute vectors for new or changed notes, returns how many were embedded
+pub fn embed_search_notes(app: &AppHandle, notes: &[SearchNote]) -> Result<usize, String> {
+    let pending: Vec<(i64, String, String)> = with_vector_index(app, |index| {
+        notes.iter()
+            .filter(|note| !note.content.trim().is_empty())
+            .map(|note| {
+                let text: String = note.content.chars().take(MAX_EMBED_CHARS).collect();
+                let hash = Sha256::digest(text.as_bytes()).iter().map(|b| format!("{:02x}", b)).collect::<String>();
+                (note.id, text, hash)
+            })
+            .filter(|(id, _, hash)| index.entries.get(id).map(|e| &e.hash != hash).unwrap_or(true))
+            .collect()
+    });
+
+    if pending.is_empty() {
+        return Ok(0);
+    }
+
+    let vectors = embed_texts(app, pending.iter().map(|(_, text, _)| text.clone()).collect())?;
+    let count = vectors.len();
+
+    with_vector_index(app, |index| {
+        for ((id, text, hash), vector) in pending.into_iter().zip(vectors) {
+            index.entries.insert(id, VectorEntry { hash, text, vector });
+        }
+    });
+    save_vector_index(app)?;
+
+    info!("🧠 Embedded {} note(s)", count);
+    Ok(count)
+}
+
+/// Brute-force cosine similarity, fast enough for personal note collections
+pub fn semantic_search_notes(app: &AppHandle, query: &str, k: usize) -> Result<Vec<SemanticHit>, String> {
+    if query.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let query_vector = embed_texts(app, vec![query.to_string()])?
+        .pop()
+        .ok_or("Embedding model returned no vector")?;
+
+    Ok(with_vector_index(app, |index| {
+        let mut hits: Vec<SemanticHit> = index.entries.iter()
+            .map(|(id, entry)| SemanticHit {
+                id: *id,
+                score: entry.vector.iter().zip(&query_vector).map(|(a, b)| a * b).sum(),
+                text: entry.text.clone(),
+            })
+            .collect();
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+        hits.truncate(k.max(1));
+        hits
+    }))
+}
+
+#[tauri::command]
+pub async fn embed_notes(app: AppHandle, notes: Vec<SearchNote>) -> Result<usize, String> {
+    tauri::async_runtime::spawn_blocking(move || embed_search_notes(&app, &notes))
+        .await
+        .map_err(|e| format!("Embedding task failed: {}", e))?
+}
+
+#[tauri::command]
+pub fn remove_note_embeddings(app: AppHandle, ids: Vec<i64>) -> Result<(), String> {
+    with_vector_index(&app, |index| {
+        for id in ids {
+            index.entries.remove(&id);
+        }
+    });
+    save_vector_index(&app)
+}
+
+#[tauri::command]
+pub async fn semantic_search(app: AppHandle, query: String, k: Option<usize>) -> Result<Vec<SemanticHit>, String> {
+    tauri::async_runtime::spawn_blocking(move || semantic_search_notes(&app, &query, k.unwrap_or(5)))
+        .await
+        .map_err(|e| format!("Semantic search task failed: {}", e))?
+}
+
+/// Retrieve the most relevant notes and build a retrieval-augmented prompt for the quickai window
+#[tauri::command]
+pub async fn build_quickai_context(app: AppHandle, prompt: String, k: Option<usize>) -> Result<QuickAiContext, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let sources = semantic_search_notes(&app, &prompt, k.unwrap_or(5))?;
+        if sources.is_empty() {
+            return Ok(QuickAiContext { prompt, sources });
+        }
+
+        let mut context = String::new();
+        for hit in &sources {
+            if context.len() + hit.text.len() > MAX_CONTEXT_CHARS {
+                break;
+            }
+            context.push_str(&format!("[note {}]\n{}\n\n", hit.id, hit.text.trim()));
+        }
+
+        Ok(QuickAiContext {
+            prompt: format!(
+                "Use the following notes as context when they are relevant.\n\n{}Question: {}",
+                context, prompt
+            ),
+            sources,
+        })
+    })
+    .await
+    .map_err(|e| format!("Context task failed: {}", e))?
+}
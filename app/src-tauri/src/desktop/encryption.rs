@@ -0,0 +1,515 @@
+use tauri::{AppHandle, Emitter};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+
+use argon2::Argon2;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use rusqlite::{params, Connection};
+
+use crate::desktop::{
+    close_offline_db, delete_secret, get_app_data_dir, load_json_or_default, read_secret, reset_clipboard_history_cache,
+    save_json, store_secret, wipe_search_index, write_file_atomic, CLIPBOARD_IMAGES_DIR,
+};
+
+const ENCRYPTION_CONFIG_FILE: &str = "encryption.json";
+/// Written once every store has been re-encrypted into a staged copy, the commit point of a key change
+const ENCRYPTION_JOURNAL_FILE: &str = "encryption_journal.json";
+/// Suffix of re-encrypted copies waiting to replace their originals
+const STAGED_SUFFIX: &str = ".rekey";
+const ENCRYPTION_KEYCHAIN_ACCOUNT: &str = "encryption-key";
+/// Prefix of encrypted files on disk
+const FILE_MAGIC: &[u8] = b"BLKENC1";
+/// Prefix of encrypted text stored in database columns
+const TEXT_PREFIX: &str = "enc:v1:";
+const VERIFIER_PLAINTEXT: &[u8] = b"blinko-encryption-check";
+const NONCE_LEN: usize = 24;
+/// JSON files in the app data dir that hold personal content
+const PROTECTED_FILES: &[&str] = &["clipboard_history.json", "quicknote_draft.json"];
+const LOCKED_ERROR: &str = "Encrypted data is locked, enter the passphrase first";
+
+type EncryptionKey = [u8; 32];
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct EncryptionConfig {
+    pub enabled: bool,
+    /// Base64 Argon2id salt
+    pub salt: String,
+    /// A known value sealed with the key, used to check passphrases
+    pub verifier: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EncryptionStatus {
+    pub enabled: bool,
+    pub unlocked: bool,
+    /// The key is kept in the OS keychain and unlocked on startup
+    pub remembered: bool,
+}
+
+/// A key change whose staged copies are ready to be moved in place
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct EncryptionJournal {
+    config: EncryptionConfig,
+    /// Paths relative to the app data dir whose staged copy replaces them
+    files: Vec<String>,
+}
+
+static ENCRYPTION_KEY: LazyLock<Mutex<Option<EncryptionKey>>> = LazyLock::new(|| Mutex::new(None));
+static ENCRYPTION_ENABLED: LazyLock<Mutex<bool>> = LazyLock::new(|| Mutex::new(false));
+
+fn load_encryption_config(app: &AppHandle) -> EncryptionConfig {
+    load_json_or_default(app, ENCRYPTION_CONFIG_FILE)
+}
+
+fn random_bytes<const N: usize>() -> Result<[u8; N], String> {
+    let mut bytes = [0u8; N];
+    getrandom::getrandom(&mut bytes).map_err(|e| format!("Failed to generate random bytes: {}", e))?;
+    Ok(bytes)
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<EncryptionKey, String> {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| format!("Failed to derive encryption key: {}", e))?;
+    Ok(key)
+}
+
+/// nonce || XChaCha20-Poly1305 ciphertext
+fn seal(key: &EncryptionKey, plaintext: &[u8]) -> Result<Vec<u8>, String> {
+    let nonce = random_bytes::<NONCE_LEN>()?;
+    let ciphertext = XChaCha20Poly1305::new(Key::from_slice(key))
+        .encrypt(XNonce::from_slice(&nonce), plaintext)
+        .map_err(|_| "Encryption failed".to_string())?;
+    Ok([nonce.as_slice(), &ciphertext].concat())
+}
+
+fn unseal(key: &EncryptionKey, sealed: &[u8]) -> Result<Vec<u8>, String> {
+    if sealed.len() < NONCE_LEN {
+        return Err("Encrypted data is truncated".to_string());
+    }
+    let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+    XChaCha20Poly1305::new(Key::from_slice(key))
+        .decrypt(XNonce::from_slice(nonce), ciphertext)
+        .map_err(|_| "Decryption failed, wrong passphrase or corrupted data".to_string())
+}
+
+fn verify_passphrase(config: &EncryptionConfig, passphrase: &str) -> Result<EncryptionKey, String> {
+    let salt = STANDARD.decode(&config.salt).map_err(|e| format!("Invalid encryption salt: {}", e))?;
+    let key = derive_key(passphrase, &salt)?;
+    let verifier = STANDARD.decode(&config.verifier).map_err(|e| format!("Invalid encryption verifier: {}", e))?;
+    match unseal(&key, &verifier) {
+        Ok(plain) if plain == VERIFIER_PLAINTEXT => Ok(key),
+        _ => Err("Wrong passphrase".to_string()),
+    }
+}
+
+fn current_key() -> Option<EncryptionKey> {
+    *ENCRYPTION_KEY.lock().unwrap()
+}
+
+fn is_enabled() -> bool {
+    *ENCRYPTION_ENABLED.lock().unwrap()
+}
+
+fn encrypt_text_with(key: Option<&EncryptionKey>, text: &str) -> Result<String, String> {
+    match key {
+        Some(key) => Ok(format!("{}{}", TEXT_PREFIX, STANDARD.encode(seal(key, text.as_bytes())?))),
+        None => Ok(text.to_string()),
+    }
+}
+
+fn decrypt_text_with(key: Option<&EncryptionKey>, text: String) -> Result<String, String> {
+    let Some(encoded) = text.strip_prefix(TEXT_PREFIX) else { return Ok(text) };
+    let key = key.ok_or(LOCKED_ERROR)?;
+    let sealed = STANDARD.decode(encoded).map_err(|e| format!("Invalid encrypted text: {}", e))?;
+    String::from_utf8(unseal(key, &sealed)?).map_err(|e| format!("Invalid encrypted text: {}", e))
+}
+
+fn encrypt_file_bytes_with(key: Option<&EncryptionKey>, bytes: &[u8]) -> Result<Vec<u8>, String> {
+    match key {
+        Some(key) => Ok([FILE_MAGIC, &seal(key, bytes)?].concat()),
+        None => Ok(bytes.to_vec()),
+    }
+}
+
+fn decrypt_file_bytes_with(key: Option<&EncryptionKey>, bytes: Vec<u8>) -> Result<Vec<u8>, String> {
+    let Some(sealed) = bytes.strip_prefix(FILE_MAGIC) else { return Ok(bytes) };
+    let key = key.ok_or(LOCKED_ERROR)?;
+    unseal(key, sealed)
+}
+
+/// Encrypt a value for a database column when encryption is on.
+/// Fails while locked instead of writing plaintext.
+pub fn protect_text(text: &str) -> Result<String, String> {
+    if is_enabled() && current_key().is_none() {
+        return Err(LOCKED_ERROR.to_string());
+    }
+    encrypt_text_with(current_key().as_ref(), text)
+}
+
+/// Whether local data is encrypted, for stores that can't be protected and stay off meanwhile
+pub fn is_local_encryption_enabled() -> bool {
+    is_enabled()
+}
+
+/// Decrypt a column value written by `protect_text`; plaintext passes through
+pub fn unprotect_text(text: String) -> Result<String, String> {
+    decrypt_text_with(current_key().as_ref(), text)
+}
+
+/// Like `load_json_or_default`, decrypting the file if needed.
+/// Returns the default while locked.
+pub fn load_protected_json_or_default<T: DeserializeOwned + Default>(app: &AppHandle, file_name: &str) -> T {
+    let Ok(path) = get_app_data_dir(app).map(|dir| dir.join(file_name)) else { return T::default() };
+    let Ok(bytes) = fs::read(&path) else { return T::default() };
+
+    match decrypt_file_bytes_with(current_key().as_ref(), bytes) {
+        Ok(plain) => serde_json::from_slice(&plain).unwrap_or_else(|e| {
+            error!("Failed to parse {}: {}", file_name, e);
+            T::default()
+        }),
+        Err(e) => {
+            error!("Failed to read {}: {}", file_name, e);
+            T::default()
+        }
+    }
+}
+
+/// Like `save_json`, encrypting the file when encryption is on
+pub fn save_protected_json<T: Serialize>(app: &AppHandle, file_name: &str, value: &T) -> Result<(), String> {
+    if !is_enabled() {
+        return save_json(app, file_name, value);
+    }
+    let json = serde_json::to_vec(value).map_err(|e| format!("Failed to serialize {}: {}", file_name, e))?;
+    save_protected_file(&get_app_data_dir(app)?.join(file_name), &json)
+}
+
+/// Write a file with personal content, encrypted when encryption is on.
+/// Fails while locked instead of writing plaintext.
+pub fn save_protected_file(path: &Path, bytes: &[u8]) -> Result<(), String> {
+    let bytes = if is_enabled() {
+        let key = current_key().ok_or(LOCKED_ERROR)?;
+        encrypt_file_bytes_with(Some(&key), bytes)?
+    } else {
+        bytes.to_vec()
+    };
+    write_file_atomic(path, &bytes).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+fn reencrypt_database(path: &Path, old: Option<&EncryptionKey>, new: Option<&EncryptionKey>) -> Result<(), String> {
+    let mut conn = Connection::open(path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let tx = conn.transaction().map_err(|e| format!("Database error: {}", e))?;
+
+    let convert = |value: String| encrypt_text_with(new, &decrypt_text_with(old, value)?);
+    {
+        let notes: Vec<(i64, String, String)> = {
+            let mut stmt = tx.prepare("SELECT id, content, data FROM notes").map_err(|e| format!("Database error: {}", e))?;
+            let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+                .and_then(|rows| rows.collect())
+                .map_err(|e| format!("Database error: {}", e))?;
+            rows
+        };
+        for (id, content, data) in notes {
+            tx.execute("UPDATE notes SET content = ?2, data = ?3 WHERE id = ?1", params![id, convert(content)?, convert(data)?])
+                .map_err(|e| format!("Database error: {}", e))?;
+        }
+
+        let ops: Vec<(i64, String, Option<String>)> = {
+            let mut stmt = tx.prepare("SELECT id, payload, base_content FROM pending_ops").map_err(|e| format!("Database error: {}", e))?;
+            let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+                .and_then(|rows| rows.collect())
+                .map_err(|e| format!("Database error: {}", e))?;
+            rows
+        };
+        for (id, payload, base_content) in ops {
+            let base_content = base_content.map(&convert).transpose()?;
+            tx.execute("UPDATE pending_ops SET payload = ?2, base_content = ?3 WHERE id = ?1", params![id, convert(payload)?, base_content])
+                .map_err(|e| format!("Database error: {}", e))?;
+        }
+    }
+    tx.commit().map_err(|e| format!("Database error: {}", e))?;
+    // Don't leave plaintext behind in free pages or the WAL
+    conn.execute_batch("PRAGMA wal_checkpoint(TRUNCATE); VACUUM;")
+        .map_err(|e| format!("Database error: {}", e))
+}
+
+fn staged_path(path: &Path) -> PathBuf {
+    let mut staged = path.as_os_str().to_owned();
+    staged.push(STAGED_SUFFIX);
+    PathBuf::from(staged)
+}
+
+/// Offline caches (one per account), protected JSON files and clipboard images, relative to the data dir
+fn protected_stores(data_dir: &Path) -> Vec<String> {
+    let mut stores: Vec<String> = fs::read_dir(data_dir)
+        .map(|entries| entries.flatten()
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .filter(|name| name.starts_with("offline") && name.ends_with(".db"))
+            .collect())
+        .unwrap_or_default();
+    stores.extend(PROTECTED_FILES.iter()
+        .filter(|name| data_dir.join(name).exists())
+        .map(|name| name.to_string()));
+    if let Ok(entries) = fs::read_dir(data_dir.join(CLIPBOARD_IMAGES_DIR)) {
+        stores.extend(entries.flatten()
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .filter(|name| name.ends_with(".png"))
+            .map(|name| format!("{}/{}", CLIPBOARD_IMAGES_DIR, name)));
+    }
+    stores
+}
+
+/// Write a re-encrypted copy of one store next to it, leaving the original untouched
+fn stage_store(path: &Path, old: Option<&EncryptionKey>, new: Option<&EncryptionKey>) -> Result<(), String> {
+    let staged = staged_path(path);
+    let _ = fs::remove_file(&staged);
+
+    if path.extension().is_some_and(|ext| ext == "db") {
+        Connection::open(path)
+            .and_then(|conn| conn.execute("VACUUM INTO ?1", params![staged.to_string_lossy()]))
+            .map_err(|e| format!("Failed to copy {}: {}", path.display(), e))?;
+        return reencrypt_database(&staged, old, new);
+    }
+
+    let bytes = fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let plain = decrypt_file_bytes_with(old, bytes)?;
+    write_file_atomic(&staged, &encrypt_file_bytes_with(new, &plain)?)
+        .map_err(|e| format!("Failed to write {}: {}", staged.display(), e))
+}
+
+fn remove_staged(data_dir: &Path, stores: &[String]) {
+    for store in stores {
+        let _ = fs::remove_file(staged_path(&data_dir.join(store)));
+    }
+}
+
+/// Move staged copies in place and save the new config, then drop the journal.
+/// Safe to repeat, so an interrupted key change finishes on the next start.
+fn apply_journal(data_dir: &Path, journal: &EncryptionJournal) -> Result<(), String> {
+    let config = serde_json::to_vec_pretty(&journal.config)
+        .map_err(|e| format!("Failed to serialize {}: {}", ENCRYPTION_CONFIG_FILE, e))?;
+    write_file_atomic(&data_dir.join(ENCRYPTION_CONFIG_FILE), &config)
+        .map_err(|e| format!("Failed to write {}: {}", ENCRYPTION_CONFIG_FILE, e))?;
+
+    for store in &journal.files {
+        let path = data_dir.join(store);
+        let staged = staged_path(&path);
+        if !staged.exists() {
+            continue;
+        }
+        fs::rename(&staged, &path).map_err(|e| format!("Failed to replace {}: {}", path.display(), e))?;
+        // The old database's WAL would be replayed into the new file
+        if store.ends_with(".db") {
+            let _ = fs::remove_file(data_dir.join(format!("{}-wal", store)));
+            let _ = fs::remove_file(data_dir.join(format!("{}-shm", store)));
+        }
+    }
+
+    fs::remove_file(data_dir.join(ENCRYPTION_JOURNAL_FILE))
+        .map_err(|e| format!("Failed to remove {}: {}", ENCRYPTION_JOURNAL_FILE, e))
+}
+
+/// Move every protected store from the old key to the new one (`None` = plaintext) and save `config`.
+/// Everything is re-encrypted into staged copies first, so a failure before the journal is
+/// written leaves the data and config as they were.
+fn rekey_all(app: &AppHandle, old: Option<&EncryptionKey>, new: Option<&EncryptionKey>, config: EncryptionConfig) -> Result<(), String> {
+    let data_dir = get_app_data_dir(app)?;
+    close_offline_db();
+
+    let stores = protected_stores(&data_dir);
+    for store in &stores {
+        if let Err(e) = stage_store(&data_dir.join(store), old, new) {
+            remove_staged(&data_dir, &stores);
+            return Err(e);
+        }
+    }
+
+    let journal = EncryptionJournal { config, files: stores };
+    let journal_json = serde_json::to_vec_pretty(&journal)
+        .map_err(|e| format!("Failed to serialize {}: {}", ENCRYPTION_JOURNAL_FILE, e))?;
+    if let Err(e) = write_file_atomic(&data_dir.join(ENCRYPTION_JOURNAL_FILE), &journal_json) {
+        remove_staged(&data_dir, &journal.files);
+        return Err(format!("Failed to write {}: {}", ENCRYPTION_JOURNAL_FILE, e));
+    }
+
+    apply_journal(&data_dir, &journal)
+        .map_err(|e| format!("{}, the change will be finished on next start", e))
+}
+
+/// Finish a key change interrupted after its journal was written, or drop the staged copies of one that wasn't
+fn recover_encryption_journal(app: &AppHandle) {
+    let Ok(data_dir) = get_app_data_dir(app) else { return };
+    let journal_path = data_dir.join(ENCRYPTION_JOURNAL_FILE);
+
+    match fs::read(&journal_path).ok().and_then(|bytes| serde_json::from_slice::<EncryptionJournal>(&bytes).ok()) {
+        Some(journal) => match apply_journal(&data_dir, &journal) {
+            Ok(_) => info!("🔒 Finished an interrupted encryption key change"),
+            Err(e) => error!("❌ Failed to finish encryption key change: {}", e),
+        },
+        None => remove_staged(&data_dir, &protected_stores(&data_dir)),
+    }
+}
+
+fn set_active_key(app: &AppHandle, key: Option<EncryptionKey>, enabled: bool) {
+    *ENCRYPTION_KEY.lock().unwrap() = key;
+    *ENCRYPTION_ENABLED.lock().unwrap() = enabled;
+    // Cached history may have been loaded while locked, read it again
+    reset_clipboard_history_cache();
+    let _ = app.emit("encryption-status-changed", encryption_status(app));
+}
+
+fn remember_key(key: &EncryptionKey, remember: bool) -> Result<(), String> {
+    if remember {
+        store_secret(Some(ENCRYPTION_KEYCHAIN_ACCOUNT), &STANDARD.encode(key))
+    } else {
+        delete_secret(Some(ENCRYPTION_KEYCHAIN_ACCOUNT))
+    }
+}
+
+fn encryption_status(app: &AppHandle) -> EncryptionStatus {
+    EncryptionStatus {
+        enabled: load_encryption_config(app).enabled,
+        unlocked: current_key().is_some(),
+        remembered: read_secret(Some(ENCRYPTION_KEYCHAIN_ACCOUNT)).ok().flatten().is_some(),
+    }
+}
+
+/// Load the encryption state on startup and unlock with a key remembered in the keychain
+pub fn restore_encryption_key(app: &AppHandle) {
+    recover_encryption_journal(app);
+    let config = load_encryption_config(app);
+    *ENCRYPTION_ENABLED.lock().unwrap() = config.enabled;
+    if !config.enabled {
+        return;
+    }
+
+    let remembered = read_secret(Some(ENCRYPTION_KEYCHAIN_ACCOUNT)).ok().flatten()
+        .and_then(|encoded| STANDARD.decode(encoded).ok())
+        .and_then(|bytes| EncryptionKey::try_from(bytes.as_slice()).ok());
+    let verifier = STANDARD.decode(&config.verifier).unwrap_or_default();
+
+    match remembered {
+        Some(key) if unseal(&key, &verifier).is_ok_and(|plain| plain == VERIFIER_PLAINTEXT) => {
+            *ENCRYPTION_KEY.lock().unwrap() = Some(key);
+            info!("🔒 Local data unlocked with remembered key");
+        }
+        _ => info!("🔒 Local data is encrypted, waiting for passphrase"),
+    }
+}
+
+#[tauri::command]
+pub fn get_encryption_status(app: AppHandle) -> EncryptionStatus {
+    encryption_status(&app)
+}
+
+/// Turn on encryption and encrypt existing cached data with a key derived from `passphrase`
+#[tauri::command]
+pub async fn set_encryption_passphrase(app: AppHandle, passphrase: String, remember: Option<bool>) -> Result<EncryptionStatus, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        if load_encryption_config(&app).enabled {
+            return Err("Encryption is already enabled, use change_encryption_passphrase".to_string());
+        }
+        if passphrase.chars().count() < 8 {
+            return Err("Passphrase must be at least 8 characters".to_string());
+        }
+
+        let salt = random_bytes::<16>()?;
+        let key = derive_key(&passphrase, &salt)?;
+        rekey_all(&app, None, Some(&key), EncryptionConfig {
+            enabled: true,
+            salt: STANDARD.encode(salt),
+            verifier: STANDARD.encode(seal(&key, VERIFIER_PLAINTEXT)?),
+        })?;
+        // The index holds note text in plaintext and stays off while encryption is on
+        if let Err(e) = wipe_search_index(&app) {
+            error!("Failed to remove search index: {}", e);
+        }
+        remember_key(&key, remember.unwrap_or(false))?;
+        set_active_key(&app, Some(key), true);
+        info!("🔒 Local data encryption enabled");
+        Ok(encryption_status(&app))
+    })
+    .await
+    .map_err(|e| format!("Failed to enable encryption: {}", e))?
+}
+
+/// Re-encrypt everything under a new passphrase
+#[tauri::command]
+pub async fn change_encryption_passphrase(app: AppHandle, old_passphrase: String, new_passphrase: String) -> Result<EncryptionStatus, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let config = load_encryption_config(&app);
+        if !config.enabled {
+            return Err("Encryption is not enabled".to_string());
+        }
+        if new_passphrase.chars().count() < 8 {
+            return Err("Passphrase must be at least 8 characters".to_string());
+        }
+        let old_key = verify_passphrase(&config, &old_passphrase)?;
+
+        let salt = random_bytes::<16>()?;
+        let new_key = derive_key(&new_passphrase, &salt)?;
+        rekey_all(&app, Some(&old_key), Some(&new_key), EncryptionConfig {
+            enabled: true,
+            salt: STANDARD.encode(salt),
+            verifier: STANDARD.encode(seal(&new_key, VERIFIER_PLAINTEXT)?),
+        })?;
+        let remembered = read_secret(Some(ENCRYPTION_KEYCHAIN_ACCOUNT)).ok().flatten().is_some();
+        remember_key(&new_key, remembered)?;
+        set_active_key(&app, Some(new_key), true);
+        info!("🔒 Encryption passphrase changed");
+        Ok(encryption_status(&app))
+    })
+    .await
+    .map_err(|e| format!("Failed to change passphrase: {}", e))?
+}
+
+/// Decrypt everything back to plaintext and turn encryption off
+#[tauri::command]
+pub async fn disable_encryption(app: AppHandle, passphrase: String) -> Result<EncryptionStatus, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let config = load_encryption_config(&app);
+        if !config.enabled {
+            return Ok(encryption_status(&app));
+        }
+        let key = verify_passphrase(&config, &passphrase)?;
+        rekey_all(&app, Some(&key), None, EncryptionConfig::default())?;
+        remember_key(&key, false)?;
+        set_active_key(&app, None, false);
+        info!("🔓 Local data encryption disabled");
+        Ok(encryption_status(&app))
+    })
+    .await
+    .map_err(|e| format!("Failed to disable encryption: {}", e))?
+}
+
+#[tauri::command]
+pub async fn unlock_encryption(app: AppHandle, passphrase: String, remember: Option<bool>) -> Result<EncryptionStatus, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let config = load_encryption_config(&app);
+        if !config.enabled {
+            return Err("Encryption is not enabled".to_string());
+        }
+        let key = verify_passphrase(&config, &passphrase)?;
+        if let Some(remember) = remember {
+            remember_key(&key, remember)?;
+        }
+        set_active_key(&app, Some(key), true);
+        Ok(encryption_status(&app))
+    })
+    .await
+    .map_err(|e| format!("Failed to unlock: {}", e))?
+}
+
+/// Forget the key until the passphrase is entered again
+#[tauri::command]
+pub fn lock_encryption(app: AppHandle) -> EncryptionStatus {
+    let enabled = load_encryption_config(&app).enabled;
+    set_active_key(&app, None, enabled);
+    encryption_status(&app)
+}
@@ -0,0 +1,373 @@
+use tauri::{AppHandle, Emitter};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+
+use crate::desktop::{generate_id, http_client_builder, is_background_sync_allowed, load_json_or_default, now_millis, queue_note_change, save_json};
+
+const FEEDS_FILE: &str = "feeds.json";
+const FEED_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+const FEED_TIMEOUT: Duration = Duration::from_secs(30);
+// Remembered item ids per feed, enough to cover what a feed keeps published
+const MAX_SEEN_IDS: usize = 500;
+const MAX_UNREAD_ITEMS: usize = 200;
+/// A newly added feed only brings in its latest items instead of its whole history
+const INITIAL_ITEMS: usize = 3;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum FeedDelivery {
+    /// One note per item
+    #[default]
+    Notes,
+    /// One note listing all new items of a poll
+    Digest,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FeedItem {
+    pub id: String,
+    pub title: String,
+    pub link: Option<String>,
+    /// Summary or content as markdown
+    pub summary: Option<String>,
+    #[serde(rename = "publishedAt")]
+    pub published_at: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FeedSubscription {
+    pub id: String,
+    pub url: String,
+    pub title: String,
+    /// Added to every note from this feed, without the leading #
+    pub tag: Option<String>,
+    #[serde(default)]
+    pub delivery: FeedDelivery,
+    /// Hold new items as unread in Blinko instead of turning them into notes right away
+    #[serde(rename = "keepUnread")]
+    pub keep_unread: bool,
+    pub enabled: bool,
+    #[serde(rename = "lastCheckedAt")]
+    pub last_checked_at: Option<u64>,
+    #[serde(rename = "lastError")]
+    pub last_error: Option<String>,
+    #[serde(rename = "seenIds", default)]
+    pub seen_ids: Vec<String>,
+    #[serde(default)]
+    pub unread: Vec<FeedItem>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FeedsConfig {
+    pub enabled: bool,
+    #[serde(rename = "intervalMinutes")]
+    pub interval_minutes: u64,
+    pub feeds: Vec<FeedSubscription>,
+}
+
+impl Default for FeedsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_minutes: 60,
+            feeds: Vec::new(),
+        }
+    }
+}
+
+static FEEDS_CONFIG: LazyLock<Mutex<Option<FeedsConfig>>> = LazyLock::new(|| Mutex::new(None));
+static FEED_POLLER_STARTED: AtomicBool = AtomicBool::new(false);
+// Keeps a manual refresh and the poller from ingesting the same items twice
+static FEED_POLL_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+fn with_feeds_config<T>(app: &AppHandle, f: impl FnOnce(&mut FeedsConfig) -> T) -> T {
+    let mut guard = FEEDS_CONFIG.lock().unwrap();
+    f(guard.get_or_insert_with(|| load_json_or_default(app, FEEDS_FILE)))
+}
+
+fn persist_feeds_config(app: &AppHandle) -> Result<(), String> {
+    let config = with_feeds_config(app, |config| config.clone());
+    save_json(app, FEEDS_FILE, &config)
+}
+
+fn fetch_feed(url: &str) -> Result<feed_rs::model::Feed, String> {
+    let response = http_client_builder()
+        .timeout(FEED_TIMEOUT)
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?
+        .get(url)
+        .send()
+        .map_err(|e| format!("Failed to fetch feed: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Failed to fetch feed: HTTP {}", response.status()));
+    }
+    let bytes = response.bytes().map_err(|e| format!("Failed to read feed: {}", e))?;
+    feed_rs::parser::parse(bytes.as_ref()).map_err(|e| format!("Not a valid RSS or Atom feed: {}", e))
+}
+
+fn to_feed_item(entry: &feed_rs::model::Entry) -> FeedItem {
+    let html = entry.summary.as_ref().map(|summary| summary.content.clone())
+        .or_else(|| entry.content.as_ref().and_then(|content| content.body.clone()));
+    FeedItem {
+        id: entry.id.clone(),
+        title: entry.title.as_ref().map(|title| title.content.trim().to_string()).unwrap_or_else(|| "Untitled".to_string()),
+        link: entry.links.first().map(|link| link.href.clone()),
+        summary: html.map(|html| html2md::parse_html(&html).trim().to_string()).filter(|summary| !summary.is_empty()),
+        published_at: entry.published.or(entry.updated).map(|date| date.timestamp_millis().max(0) as u64),
+    }
+}
+
+fn tag_line(feed: &FeedSubscription) -> String {
+    feed.tag.as_ref()
+        .map(|tag| tag.trim().trim_start_matches('#').replace(' ', "-"))
+        .filter(|tag| !tag.is_empty())
+        .map(|tag| format!("\n\n#{}", tag))
+        .unwrap_or_default()
+}
+
+fn item_heading(item: &FeedItem) -> String {
+    match item.link {
+        Some(ref link) => format!("[{}]({})", item.title, link),
+        None => item.title.clone(),
+    }
+}
+
+fn create_feed_note(app: &AppHandle, content: String) -> Result<(), String> {
+    queue_note_change(app.clone(), "create".to_string(), json!({ "content": content, "type": 0 })).map(|_| ())
+}
+
+/// Turn items into notes the way the feed is set up to deliver them
+fn deliver_items(app: &AppHandle, feed: &FeedSubscription, items: &[FeedItem]) -> Result<(), String> {
+    if items.is_empty() {
+        return Ok(());
+    }
+    match feed.delivery {
+        FeedDelivery::Notes => {
+            for item in items {
+                let mut content = format!("## {}", item_heading(item));
+                if let Some(ref summary) = item.summary {
+                    content.push_str(&format!("\n\n{}", summary));
+                }
+                content.push_str(&tag_line(feed));
+                create_feed_note(app, content)?;
+            }
+        }
+        FeedDelivery::Digest => {
+            let mut content = format!("## {} · {}\n", feed.title, chrono::Local::now().format("%Y-%m-%d"));
+            for item in items {
+                content.push_str(&format!("\n- {}", item_heading(item)));
+            }
+            content.push_str(&tag_line(feed));
+            create_feed_note(app, content)?;
+        }
+    }
+    Ok(())
+}
+
+/// Fetch one feed and ingest the items not seen before, returns how many were new
+fn poll_feed(app: &AppHandle, feed: &mut FeedSubscription) -> Result<usize, String> {
+    let parsed = fetch_feed(&feed.url)?;
+    if let Some(title) = parsed.title.as_ref().map(|title| title.content.trim()).filter(|title| !title.is_empty()) {
+        if feed.title.is_empty() || feed.title == feed.url {
+            feed.title = title.to_string();
+        }
+    }
+
+    let first_poll = feed.last_checked_at.is_none();
+    let mut new_items: Vec<FeedItem> = parsed.entries.iter()
+        .filter(|entry| !feed.seen_ids.contains(&entry.id))
+        .map(to_feed_item)
+        .collect();
+    // Feeds list newest first, deliver oldest first so notes keep the feed's order
+    new_items.sort_by_key(|item| item.published_at.unwrap_or(0));
+    feed.seen_ids.extend(new_items.iter().map(|item| item.id.clone()));
+    let overflow = feed.seen_ids.len().saturating_sub(MAX_SEEN_IDS);
+    feed.seen_ids.drain(..overflow);
+
+    if first_poll && new_items.len() > INITIAL_ITEMS {
+        new_items.drain(..new_items.len() - INITIAL_ITEMS);
+    }
+    let count = new_items.len();
+
+    if feed.keep_unread {
+        feed.unread.extend(new_items);
+        let overflow = feed.unread.len().saturating_sub(MAX_UNREAD_ITEMS);
+        feed.unread.drain(..overflow);
+    } else {
+        deliver_items(app, feed, &new_items)?;
+    }
+    Ok(count)
+}
+
+/// Poll feeds that are due, or all enabled feeds when `force` is set
+fn poll_feeds(app: &AppHandle, force: bool) -> usize {
+    let _guard = FEED_POLL_LOCK.lock().unwrap();
+    let (interval, feeds) = with_feeds_config(app, |config| (config.interval_minutes.max(5) * 60 * 1000, config.feeds.clone()));
+
+    let mut total = 0;
+    for mut feed in feeds {
+        let due = feed.last_checked_at.is_none_or(|last| now_millis().saturating_sub(last) >= interval);
+        if !feed.enabled || !(force || due) {
+            continue;
+        }
+        match poll_feed(app, &mut feed) {
+            Ok(count) => {
+                if count > 0 {
+                    info!("📡 {} new item(s) from {}", count, feed.title);
+                }
+                feed.last_error = None;
+                total += count;
+            }
+            Err(e) => {
+                warn!("⚠️ Feed {} failed: {}", feed.url, e);
+                feed.last_error = Some(e);
+            }
+        }
+        feed.last_checked_at = Some(now_millis());
+
+        // Edits made while fetching win, only the poll bookkeeping is written back
+        with_feeds_config(app, |config| {
+            if let Some(stored) = config.feeds.iter_mut().find(|stored| stored.id == feed.id) {
+                stored.title = feed.title.clone();
+                stored.last_checked_at = feed.last_checked_at;
+                stored.last_error = feed.last_error.clone();
+                stored.seen_ids = feed.seen_ids.clone();
+                stored.unread = feed.unread.clone();
+            }
+        });
+    }
+
+    if let Err(e) = persist_feeds_config(app) {
+        error!("Failed to save feeds: {}", e);
+    }
+    if total > 0 {
+        let _ = app.emit("feeds-updated", total);
+    }
+    total
+}
+
+/// Poll registered feeds on their schedule (runs for the app lifetime)
+pub fn start_feed_poller(app: &AppHandle) {
+    if FEED_POLLER_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let app_handle = app.clone();
+    std::thread::spawn(move || loop {
+        std::thread::sleep(FEED_CHECK_INTERVAL);
+        let enabled = with_feeds_config(&app_handle, |config| config.enabled && !config.feeds.is_empty());
+        if enabled && is_background_sync_allowed() {
+            poll_feeds(&app_handle, false);
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_feeds_config(app: AppHandle) -> FeedsConfig {
+    with_feeds_config(&app, |config| config.clone())
+}
+
+/// Turn polling on or off and set how often feeds are checked
+#[tauri::command]
+pub fn save_feeds_settings(app: AppHandle, enabled: bool, interval_minutes: u64) -> Result<(), String> {
+    with_feeds_config(&app, |config| {
+        config.enabled = enabled;
+        config.interval_minutes = interval_minutes.max(5);
+    });
+    persist_feeds_config(&app)
+}
+
+/// Subscribe to a feed after checking that it parses
+#[tauri::command]
+pub async fn add_feed(
+    app: AppHandle,
+    url: String,
+    tag: Option<String>,
+    delivery: Option<FeedDelivery>,
+    keep_unread: Option<bool>,
+) -> Result<FeedSubscription, String> {
+    let url = url.trim().to_string();
+    if with_feeds_config(&app, |config| config.feeds.iter().any(|feed| feed.url == url)) {
+        return Err("This feed is already registered".to_string());
+    }
+    let check_url = url.clone();
+    let parsed = tauri::async_runtime::spawn_blocking(move || fetch_feed(&check_url))
+        .await
+        .map_err(|e| format!("Failed to check feed: {}", e))??;
+
+    let feed = FeedSubscription {
+        id: generate_id(),
+        title: parsed.title.map(|title| title.content.trim().to_string()).filter(|title| !title.is_empty()).unwrap_or_else(|| url.clone()),
+        url,
+        tag: tag.filter(|tag| !tag.trim().is_empty()),
+        delivery: delivery.unwrap_or_default(),
+        keep_unread: keep_unread.unwrap_or(false),
+        enabled: true,
+        last_checked_at: None,
+        last_error: None,
+        seen_ids: Vec::new(),
+        unread: Vec::new(),
+    };
+    with_feeds_config(&app, |config| config.feeds.push(feed.clone()));
+    persist_feeds_config(&app)?;
+    info!("📡 Subscribed to {}", feed.title);
+    Ok(feed)
+}
+
+/// Change a feed's title, tag, delivery, unread mode or enabled state
+#[tauri::command]
+pub fn update_feed(app: AppHandle, feed: FeedSubscription) -> Result<(), String> {
+    with_feeds_config(&app, |config| {
+        let stored = config.feeds.iter_mut().find(|stored| stored.id == feed.id).ok_or("Feed not found")?;
+        stored.title = feed.title;
+        stored.tag = feed.tag;
+        stored.delivery = feed.delivery;
+        stored.keep_unread = feed.keep_unread;
+        stored.enabled = feed.enabled;
+        Ok::<_, String>(())
+    })?;
+    persist_feeds_config(&app)
+}
+
+#[tauri::command]
+pub fn remove_feed(app: AppHandle, id: String) -> Result<(), String> {
+    with_feeds_config(&app, |config| config.feeds.retain(|feed| feed.id != id));
+    persist_feeds_config(&app)
+}
+
+/// Check every enabled feed now, returns the number of new items
+#[tauri::command]
+pub async fn refresh_feeds(app: AppHandle) -> Result<usize, String> {
+    tauri::async_runtime::spawn_blocking(move || poll_feeds(&app, true))
+        .await
+        .map_err(|e| format!("Failed to refresh feeds: {}", e))
+}
+
+/// Turn unread items into notes, all of them when `item_ids` is omitted
+#[tauri::command]
+pub fn save_feed_items(app: AppHandle, feed_id: String, item_ids: Option<Vec<String>>) -> Result<usize, String> {
+    let (feed, items) = with_feeds_config(&app, |config| {
+        let feed = config.feeds.iter_mut().find(|feed| feed.id == feed_id).ok_or("Feed not found")?;
+        let (items, kept): (Vec<FeedItem>, Vec<FeedItem>) = std::mem::take(&mut feed.unread).into_iter()
+            .partition(|item| item_ids.as_ref().is_none_or(|ids| ids.contains(&item.id)));
+        feed.unread = kept;
+        Ok::<_, String>((feed.clone(), items))
+    })?;
+    deliver_items(&app, &feed, &items)?;
+    persist_feeds_config(&app)?;
+    Ok(items.len())
+}
+
+/// Drop unread items without making notes, all of them when `item_ids` is omitted
+#[tauri::command]
+pub fn mark_feed_read(app: AppHandle, feed_id: String, item_ids: Option<Vec<String>>) -> Result<(), String> {
+    with_feeds_config(&app, |config| {
+        let feed = config.feeds.iter_mut().find(|feed| feed.id == feed_id).ok_or("Feed not found")?;
+        feed.unread.retain(|item| item_ids.as_ref().is_some_and(|ids| !ids.contains(&item.id)));
+        Ok::<_, String>(())
+    })?;
+    persist_feeds_config(&app)
+}
@@ -0,0 +1,180 @@
+use tauri::{AppHandle, Emitter};
+use serde::{Deserialize, Serialize};
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+
+use crate::desktop::{publish_mqtt_event, send_notification, set_tray_status};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum FocusTimerStatus {
+    Idle,
+    Running,
+    Paused,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FocusTimerState {
+    pub status: FocusTimerStatus,
+    #[serde(rename = "totalSeconds")]
+    pub total_seconds: u64,
+    #[serde(rename = "remainingSeconds")]
+    pub remaining_seconds: u64,
+}
+
+struct FocusTimer {
+    state: FocusTimerState,
+    // Bumped on every start/stop so stale tick threads exit
+    generation: u64,
+}
+
+static FOCUS_TIMER: LazyLock<Mutex<FocusTimer>> = LazyLock::new(|| Mutex::new(FocusTimer {
+    state: FocusTimerState {
+        status: FocusTimerStatus::Idle,
+        total_seconds: 0,
+        remaining_seconds: 0,
+    },
+    generation: 0,
+}));
+
+fn format_countdown(seconds: u64) -> String {
+    format!("{:02}:{:02}", seconds / 60, seconds % 60)
+}
+
+fn update_tray_countdown(app: &AppHandle, state: &FocusTimerState) {
+    match state.status {
+        FocusTimerStatus::Idle => set_tray_status(app, None, None),
+        FocusTimerStatus::Running => {
+            let countdown = format_countdown(state.remaining_seconds);
+            set_tray_status(app, Some(&countdown), Some(&format!("Blinko - Focus {}", countdown)));
+        }
+        FocusTimerStatus::Paused => {
+            let countdown = format_countdown(state.remaining_seconds);
+            set_tray_status(app, Some(&format!("⏸ {}", countdown)), Some(&format!("Blinko - Focus paused {}", countdown)));
+        }
+    }
+}
+
+/// Share state changes (not ticks) with home automation
+fn publish_focus_state(action: &str, state: &FocusTimerState) {
+    publish_mqtt_event("focus_timer", serde_json::json!({ "action": action, "state": state }));
+}
+
+fn spawn_tick_thread(app: AppHandle, generation: u64) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(Duration::from_secs(1));
+
+        let (state, completed) = {
+            let mut timer = FOCUS_TIMER.lock().unwrap();
+            if timer.generation != generation {
+                return;
+            }
+            if timer.state.status != FocusTimerStatus::Running {
+                continue;
+            }
+
+            timer.state.remaining_seconds = timer.state.remaining_seconds.saturating_sub(1);
+            let completed = timer.state.remaining_seconds == 0;
+            let state = timer.state.clone();
+            if completed {
+                timer.state.status = FocusTimerStatus::Idle;
+                timer.generation += 1;
+            }
+            (state, completed)
+        };
+
+        if completed {
+            info!("🍅 Focus session completed");
+            set_tray_status(&app, None, None);
+            send_notification(&app, "Focus session complete", &format!("{} minutes of focus done. Time for a break!", state.total_seconds / 60));
+            let _ = app.emit("focus-timer-completed", &state);
+            publish_focus_state("completed", &state);
+            return;
+        }
+
+        update_tray_countdown(&app, &state);
+        let _ = app.emit("focus-timer-tick", &state);
+    });
+}
+
+#[tauri::command]
+pub fn start_focus_timer(app: AppHandle, minutes: u32) -> Result<FocusTimerState, String> {
+    if minutes == 0 || minutes > 24 * 60 {
+        return Err("Focus duration must be between 1 minute and 24 hours".to_string());
+    }
+
+    let (state, generation) = {
+        let mut timer = FOCUS_TIMER.lock().unwrap();
+        timer.generation += 1;
+        timer.state = FocusTimerState {
+            status: FocusTimerStatus::Running,
+            total_seconds: minutes as u64 * 60,
+            remaining_seconds: minutes as u64 * 60,
+        };
+        (timer.state.clone(), timer.generation)
+    };
+
+    spawn_tick_thread(app.clone(), generation);
+    update_tray_countdown(&app, &state);
+    let _ = app.emit("focus-timer-started", &state);
+    publish_focus_state("started", &state);
+
+    info!("🍅 Focus timer started for {} minutes", minutes);
+    Ok(state)
+}
+
+#[tauri::command]
+pub fn pause_focus_timer(app: AppHandle) -> Result<FocusTimerState, String> {
+    let state = {
+        let mut timer = FOCUS_TIMER.lock().unwrap();
+        if timer.state.status != FocusTimerStatus::Running {
+            return Err("Focus timer is not running".to_string());
+        }
+        timer.state.status = FocusTimerStatus::Paused;
+        timer.state.clone()
+    };
+
+    update_tray_countdown(&app, &state);
+    let _ = app.emit("focus-timer-tick", &state);
+    publish_focus_state("paused", &state);
+    Ok(state)
+}
+
+#[tauri::command]
+pub fn resume_focus_timer(app: AppHandle) -> Result<FocusTimerState, String> {
+    let state = {
+        let mut timer = FOCUS_TIMER.lock().unwrap();
+        if timer.state.status != FocusTimerStatus::Paused {
+            return Err("Focus timer is not paused".to_string());
+        }
+        timer.state.status = FocusTimerStatus::Running;
+        timer.state.clone()
+    };
+
+    update_tray_countdown(&app, &state);
+    let _ = app.emit("focus-timer-tick", &state);
+    publish_focus_state("resumed", &state);
+    Ok(state)
+}
+
+#[tauri::command]
+pub fn stop_focus_timer(app: AppHandle) -> FocusTimerState {
+    let state = {
+        let mut timer = FOCUS_TIMER.lock().unwrap();
+        timer.generation += 1;
+        timer.state.status = FocusTimerStatus::Idle;
+        timer.state.remaining_seconds = 0;
+        timer.state.clone()
+    };
+
+    set_tray_status(&app, None, None);
+    let _ = app.emit("focus-timer-stopped", &state);
+    publish_focus_state("stopped", &state);
+    info!("🍅 Focus timer stopped");
+    state
+}
+
+#[tauri::command]
+pub fn get_focus_timer_status() -> FocusTimerState {
+    FOCUS_TIMER.lock().unwrap().state.clone()
+}
@@ -0,0 +1,311 @@
+use tauri::{AppHandle, Emitter};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::channel;
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::desktop::{generate_id, import_file, load_json_or_default, queue_note_change, save_json, ImportedFile};
+
+const FOLDER_WATCH_FILE: &str = "folder_watch.json";
+/// Files read as note text rather than attached
+pub const NOTE_EXTENSIONS: &[&str] = &["md", "markdown", "txt"];
+const ATTACHMENT_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "webp", "bmp", "svg", "pdf"];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WatchedFolder {
+    pub id: String,
+    pub path: String,
+    /// Tag added to notes imported from this folder
+    pub tag: Option<String>,
+    /// Remove the source file once it has been imported
+    #[serde(rename = "deleteAfterImport")]
+    pub delete_after_import: bool,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct FolderWatchConfig {
+    pub paused: bool,
+    pub folders: Vec<WatchedFolder>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FolderImportEvent {
+    /// "note" or "attachment"
+    pub kind: String,
+    #[serde(rename = "folderId")]
+    pub folder_id: String,
+    pub tag: Option<String>,
+    /// Note content for text/markdown files
+    pub content: Option<String>,
+    /// Attachment metadata for images and documents
+    pub file: Option<ImportedFile>,
+    #[serde(rename = "sourcePath")]
+    pub source_path: String,
+    /// Id of the queued note, attachments still have to be uploaded with it
+    #[serde(rename = "localId")]
+    pub local_id: Option<String>,
+}
+
+static ACTIVE_WATCHER: LazyLock<Mutex<Option<RecommendedWatcher>>> = LazyLock::new(|| Mutex::new(None));
+static PAUSE_MENU_ITEM: LazyLock<Mutex<Option<tauri::menu::CheckMenuItem<tauri::Wry>>>> = LazyLock::new(|| Mutex::new(None));
+
+/// Load folder watch config from file
+pub fn load_folder_watch_config(app: &AppHandle) -> FolderWatchConfig {
+    load_json_or_default(app, FOLDER_WATCH_FILE)
+}
+
+fn save_folder_watch_config(app: &AppHandle, config: &FolderWatchConfig) -> Result<(), String> {
+    save_json(app, FOLDER_WATCH_FILE, config)?;
+    restart_folder_watcher(app);
+    Ok(())
+}
+
+/// Remember the tray check item so its state follows the pause toggle
+pub fn register_folder_watch_menu_item(item: tauri::menu::CheckMenuItem<tauri::Wry>) {
+    *PAUSE_MENU_ITEM.lock().unwrap() = Some(item);
+}
+
+fn extension_of(path: &Path) -> String {
+    path.extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+}
+
+/// Skip hidden files and partial downloads
+fn is_ignored_file(path: &Path) -> bool {
+    let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    name.starts_with('.') || name.starts_with('~')
+        || [".tmp", ".part", ".crdownload", ".download"].iter().any(|suffix| name.ends_with(suffix))
+}
+
+/// Wait until the file size stops changing so we don't import half-written files
+fn wait_for_stable_file(path: &Path) -> bool {
+    let mut last_size = None;
+    for _ in 0..20 {
+        let size = match fs::metadata(path) {
+            Ok(metadata) => metadata.len(),
+            Err(_) => return false,
+        };
+        if last_size == Some(size) {
+            return true;
+        }
+        last_size = Some(size);
+        std::thread::sleep(Duration::from_millis(500));
+    }
+    false
+}
+
+fn find_folder_for_path<'a>(config: &'a FolderWatchConfig, path: &Path) -> Option<&'a WatchedFolder> {
+    config.folders.iter()
+        .filter(|folder| folder.enabled)
+        .find(|folder| path.starts_with(&folder.path))
+}
+
+fn import_watched_file(app: &AppHandle, path: &Path) {
+    let config = load_folder_watch_config(app);
+    if config.paused || is_ignored_file(path) || !path.is_file() {
+        return;
+    }
+
+    let folder = match find_folder_for_path(&config, path) {
+        Some(folder) => folder.clone(),
+        None => return,
+    };
+
+    let extension = extension_of(path);
+    let is_note = NOTE_EXTENSIONS.contains(&extension.as_str());
+    let is_attachment = ATTACHMENT_EXTENSIONS.contains(&extension.as_str());
+    if !is_note && !is_attachment {
+        return;
+    }
+
+    if !wait_for_stable_file(path) {
+        warn!("⚠️ Skipping {}, file did not settle", path.display());
+        return;
+    }
+
+    let mut event = FolderImportEvent {
+        kind: if is_note { "note" } else { "attachment" }.to_string(),
+        folder_id: folder.id.clone(),
+        tag: folder.tag.clone(),
+        content: None,
+        file: None,
+        source_path: path.to_string_lossy().to_string(),
+        local_id: None,
+    };
+
+    let result = if is_note {
+        fs::read_to_string(path)
+            .map(|content| event.content = Some(content))
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+    } else {
+        import_file(app, path).map(|file| event.file = Some(file))
+    };
+
+    if let Err(e) = result {
+        error!("❌ Folder import failed: {}", e);
+        return;
+    }
+
+    let mut content = match (&event.content, &event.file) {
+        (Some(text), _) => text.trim().to_string(),
+        (None, Some(file)) => format!("📎 {}", file.name),
+        (None, None) => return,
+    };
+    if let Some(tag) = folder.tag.as_ref().map(|tag| tag.trim().trim_start_matches('#').replace(' ', "-")).filter(|tag| !tag.is_empty()) {
+        content.push_str(&format!("\n\n#{}", tag));
+    }
+
+    // The source may only go once the note is safely queued
+    match queue_note_change(app.clone(), "create".to_string(), json!({ "content": content, "type": 0 })) {
+        Ok(local_id) => event.local_id = local_id,
+        Err(e) => {
+            error!("❌ Failed to create note for {}: {}", path.display(), e);
+            return;
+        }
+    }
+
+    info!("📂 Imported {} from watched folder {}", path.display(), folder.path);
+    if let Err(e) = app.emit("folder-file-imported", &event) {
+        error!("Failed to emit folder-file-imported event: {}", e);
+    }
+
+    if folder.delete_after_import {
+        if let Err(e) = fs::remove_file(path) {
+            error!("Failed to delete imported file {}: {}", path.display(), e);
+        }
+    }
+}
+
+/// (Re)start watching all enabled folders
+pub fn restart_folder_watcher(app: &AppHandle) {
+    let config = load_folder_watch_config(app);
+    let mut active = ACTIVE_WATCHER.lock().unwrap();
+
+    // Dropping the previous watcher stops it
+    *active = None;
+
+    let folders: Vec<&WatchedFolder> = config.folders.iter().filter(|f| f.enabled).collect();
+    if folders.is_empty() {
+        return;
+    }
+
+    let (tx, rx) = channel::<notify::Result<notify::Event>>();
+    let mut watcher = match notify::recommended_watcher(tx) {
+        Ok(watcher) => watcher,
+        Err(e) => {
+            error!("❌ Failed to create folder watcher: {}", e);
+            return;
+        }
+    };
+
+    for folder in folders {
+        if let Err(e) = watcher.watch(Path::new(&folder.path), RecursiveMode::NonRecursive) {
+            error!("❌ Failed to watch folder {}: {}", folder.path, e);
+        } else {
+            info!("📂 Watching folder: {}", folder.path);
+        }
+    }
+
+    *active = Some(watcher);
+
+    // The channel closes when the watcher is dropped, which ends this thread
+    let app_handle = app.clone();
+    std::thread::spawn(move || {
+        while let Ok(result) = rx.recv() {
+            let event = match result {
+                Ok(event) => event,
+                Err(e) => {
+                    error!("Folder watch error: {}", e);
+                    continue;
+                }
+            };
+
+            if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(notify::event::ModifyKind::Name(_))) {
+                for path in event.paths {
+                    import_watched_file(&app_handle, &path);
+                }
+            }
+        }
+    });
+}
+
+/// Flip the pause flag from the tray
+pub fn toggle_folder_watch_paused(app: &AppHandle) {
+    let paused = !load_folder_watch_config(app).paused;
+    if let Err(e) = set_folder_watch_paused(app.clone(), paused) {
+        error!("Failed to toggle folder import: {}", e);
+    }
+}
+
+#[tauri::command]
+pub fn get_folder_watch_config(app: AppHandle) -> FolderWatchConfig {
+    load_folder_watch_config(&app)
+}
+
+#[tauri::command]
+pub fn add_watched_folder(
+    app: AppHandle,
+    path: String,
+    tag: Option<String>,
+    delete_after_import: bool,
+) -> Result<WatchedFolder, String> {
+    let dir = PathBuf::from(&path);
+    if !dir.is_dir() {
+        return Err(format!("Folder not found: {}", path));
+    }
+
+    let mut config = load_folder_watch_config(&app);
+    if config.folders.iter().any(|f| Path::new(&f.path) == dir) {
+        return Err(format!("Folder is already watched: {}", path));
+    }
+
+    let folder = WatchedFolder {
+        id: generate_id(),
+        path,
+        tag: tag.filter(|t| !t.trim().is_empty()),
+        delete_after_import,
+        enabled: true,
+    };
+    config.folders.push(folder.clone());
+    save_folder_watch_config(&app, &config)?;
+    Ok(folder)
+}
+
+#[tauri::command]
+pub fn update_watched_folder(app: AppHandle, folder: WatchedFolder) -> Result<(), String> {
+    let mut config = load_folder_watch_config(&app);
+    let existing = config.folders.iter_mut()
+        .find(|f| f.id == folder.id)
+        .ok_or_else(|| format!("Watched folder not found: {}", folder.id))?;
+    *existing = folder;
+    save_folder_watch_config(&app, &config)
+}
+
+#[tauri::command]
+pub fn remove_watched_folder(app: AppHandle, id: String) -> Result<(), String> {
+    let mut config = load_folder_watch_config(&app);
+    config.folders.retain(|f| f.id != id);
+    save_folder_watch_config(&app, &config)
+}
+
+#[tauri::command]
+pub fn set_folder_watch_paused(app: AppHandle, paused: bool) -> Result<(), String> {
+    let mut config = load_folder_watch_config(&app);
+    config.paused = paused;
+    save_json(&app, FOLDER_WATCH_FILE, &config)?;
+
+    if let Some(ref item) = *PAUSE_MENU_ITEM.lock().unwrap() {
+        let _ = item.set_checked(paused);
+    }
+
+    let _ = app.emit("folder-watch-paused", paused);
+    info!("📂 Folder import {}", if paused { "paused" } else { "resumed" });
+    Ok(())
+}
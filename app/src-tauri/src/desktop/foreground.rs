@@ -0,0 +1,156 @@
+use serde::{Deserialize, Serialize};
+use std::sync::{LazyLock, Mutex};
+
+/// Application that currently has keyboard focus
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ForegroundApp {
+    /// Display name, e.g. "Visual Studio Code"
+    pub name: String,
+    /// Executable name without extension, e.g. "code"
+    pub process: String,
+}
+
+impl ForegroundApp {
+    /// Case-insensitive match against either the display or the executable name
+    pub fn matches_any(&self, apps: &[String]) -> bool {
+        apps.iter().any(|app| app.eq_ignore_ascii_case(&self.name) || app.eq_ignore_ascii_case(&self.process))
+    }
+}
+
+pub fn foreground_app() -> Option<ForegroundApp> {
+    let window = active_win_pos_rs::get_active_window().ok()?;
+    let process = window.process_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    Some(ForegroundApp {
+        name: window.app_name,
+        process,
+    })
+}
+
+/// Where a capture came from, stored with quick notes and dictations
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ForegroundAppInfo {
+    pub name: String,
+    pub process: String,
+    pub title: String,
+    /// Address of the active tab when the app is a browser that can be asked for it
+    pub url: Option<String>,
+}
+
+/// Executable names of browsers, compared case-insensitively
+const BROWSER_PROCESSES: [&str; 10] = [
+    "chrome", "google chrome", "msedge", "microsoft edge", "brave", "brave browser", "firefox", "safari", "arc", "vivaldi",
+];
+
+// Source recorded just before the quicknote window took focus
+static CAPTURE_SOURCE: LazyLock<Mutex<Option<ForegroundAppInfo>>> = LazyLock::new(|| Mutex::new(None));
+
+pub fn is_browser(app: &ForegroundApp) -> bool {
+    BROWSER_PROCESSES.iter().any(|browser| browser.eq_ignore_ascii_case(&app.process) || browser.eq_ignore_ascii_case(&app.name))
+}
+
+/// Safari and Chromium browsers answer AppleScript, the first call asks the user for automation access
+#[cfg(target_os = "macos")]
+fn browser_url(app: &ForegroundApp) -> Option<String> {
+    let script = if app.name == "Safari" {
+        "tell application \"Safari\" to return URL of front document".to_string()
+    } else if app.process.eq_ignore_ascii_case("firefox") {
+        return None;
+    } else {
+        format!("tell application \"{}\" to return URL of active tab of front window", app.name)
+    };
+    let output = crate::desktop::background_command("osascript").args(["-e", &script]).output().ok()?;
+    let url = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !url.is_empty()).then_some(url)
+}
+
+/// The address bar is the first edit control in the browser window's UI Automation tree
+#[cfg(target_os = "windows")]
+fn browser_url(_app: &ForegroundApp) -> Option<String> {
+    use windows::core::{Interface, VARIANT};
+    use windows::Win32::System::Com::{CoCreateInstance, CoInitializeEx, CLSCTX_INPROC_SERVER, COINIT_MULTITHREADED};
+    use windows::Win32::UI::Accessibility::{
+        CUIAutomation, IUIAutomation, IUIAutomationValuePattern, TreeScope_Descendants, UIA_ControlTypePropertyId,
+        UIA_EditControlTypeId, UIA_ValuePatternId,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::GetForegroundWindow;
+
+    // SAFETY: plain COM calls, the interfaces are released when dropped
+    let url = unsafe {
+        let _ = CoInitializeEx(None, COINIT_MULTITHREADED);
+        let automation: IUIAutomation = CoCreateInstance(&CUIAutomation, None, CLSCTX_INPROC_SERVER).ok()?;
+        let window = automation.ElementFromHandle(GetForegroundWindow()).ok()?;
+        let condition = automation.CreatePropertyCondition(UIA_ControlTypePropertyId, &VARIANT::from(UIA_EditControlTypeId.0)).ok()?;
+        let address_bar = window.FindFirst(TreeScope_Descendants, &condition).ok()?;
+        let pattern = address_bar.GetCurrentPattern(UIA_ValuePatternId).ok()?.cast::<IUIAutomationValuePattern>().ok()?;
+        pattern.CurrentValue().ok()?.to_string()
+    };
+
+    let url = url.trim();
+    if url.is_empty() || url.contains(' ') {
+        return None;
+    }
+    // Chromium hides the scheme of https pages in the address bar
+    Some(if url.contains("://") { url.to_string() } else { format!("https://{}", url) })
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn browser_url(_app: &ForegroundApp) -> Option<String> {
+    None
+}
+
+/// Process, window title and (for browsers where possible) URL of the focused app.
+/// `None` when Blinko itself has focus.
+pub fn get_foreground_app_info() -> Option<ForegroundAppInfo> {
+    let window = active_win_pos_rs::get_active_window().ok()?;
+    if window.process_id == std::process::id() as u64 {
+        return None;
+    }
+    let app = ForegroundApp {
+        name: window.app_name,
+        process: window.process_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default(),
+    };
+    let url = if is_browser(&app) { browser_url(&app) } else { None };
+
+    Some(ForegroundAppInfo {
+        name: app.name,
+        process: app.process,
+        title: window.title,
+        url,
+    })
+}
+
+/// Tab title from a browser window title, without the " - Google Chrome" style suffix
+pub fn page_title_from_window(app: &ForegroundAppInfo) -> String {
+    let title = app.title.trim();
+    for separator in [" - ", " — "] {
+        if let Some((page, suffix)) = title.rsplit_once(separator) {
+            // Edge writes its name with a zero width space
+            let suffix = suffix.replace('\u{200b}', "").to_lowercase();
+            if suffix.ends_with(&app.name.to_lowercase()) || BROWSER_PROCESSES.contains(&suffix.as_str()) {
+                // Edge puts the profile name in between, e.g. "Page - Personal - Microsoft Edge"
+                return page_title_from_window(&ForegroundAppInfo { title: page.to_string(), ..app.clone() });
+            }
+        }
+    }
+    title.to_string()
+}
+
+/// Record the app the user is in before a capture window steals focus
+pub fn remember_capture_source() -> Option<ForegroundAppInfo> {
+    let source = get_foreground_app_info();
+    *CAPTURE_SOURCE.lock().unwrap() = source.clone();
+    source
+}
+
+/// App the current quick note is being captured from, for the note's metadata
+#[tauri::command]
+pub fn get_capture_source() -> Option<ForegroundAppInfo> {
+    CAPTURE_SOURCE.lock().unwrap().clone()
+}
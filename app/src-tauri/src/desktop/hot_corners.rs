@@ -0,0 +1,128 @@
+use tauri::AppHandle;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::desktop::{is_capture_paused, load_json_or_default, run_routed_trigger, save_json};
+
+const HOT_CORNERS_FILE: &str = "hot_corners.json";
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+const DISABLED_POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// Distance from both edges, in physical pixels, that still counts as the corner
+const CORNER_SIZE: f64 = 4.0;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum HotCorner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl HotCorner {
+    /// Routing table trigger, e.g. `corner:top-left`
+    fn trigger(self) -> &'static str {
+        match self {
+            HotCorner::TopLeft => "corner:top-left",
+            HotCorner::TopRight => "corner:top-right",
+            HotCorner::BottomLeft => "corner:bottom-left",
+            HotCorner::BottomRight => "corner:bottom-right",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HotCornerConfig {
+    pub enabled: bool,
+    /// How long the cursor has to rest in a corner
+    #[serde(rename = "dwellMs")]
+    pub dwell_ms: u64,
+    /// Built-in command per corner; a route registered for the corner's trigger takes precedence
+    pub corners: HashMap<HotCorner, String>,
+}
+
+impl Default for HotCornerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            dwell_ms: 500,
+            corners: HashMap::new(),
+        }
+    }
+}
+
+static HOT_CORNER_CONFIG: LazyLock<Mutex<HotCornerConfig>> = LazyLock::new(|| Mutex::new(HotCornerConfig::default()));
+static HOT_CORNER_MONITOR_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Corner of the monitor under the cursor, if the cursor is in one
+fn cursor_corner(app: &AppHandle) -> Option<HotCorner> {
+    let cursor = app.cursor_position().ok()?;
+    let monitor = app.monitor_from_point(cursor.x, cursor.y).ok()??;
+    let position = monitor.position();
+    let size = monitor.size();
+
+    let left = cursor.x - f64::from(position.x) <= CORNER_SIZE;
+    let right = f64::from(position.x) + f64::from(size.width) - cursor.x <= CORNER_SIZE + 1.0;
+    let top = cursor.y - f64::from(position.y) <= CORNER_SIZE;
+    let bottom = f64::from(position.y) + f64::from(size.height) - cursor.y <= CORNER_SIZE + 1.0;
+
+    match (top, bottom, left, right) {
+        (true, _, true, _) => Some(HotCorner::TopLeft),
+        (true, _, _, true) => Some(HotCorner::TopRight),
+        (_, true, true, _) => Some(HotCorner::BottomLeft),
+        (_, true, _, true) => Some(HotCorner::BottomRight),
+        _ => None,
+    }
+}
+
+/// Poll the cursor and fire a corner once per visit after the dwell time
+pub fn start_hot_corner_monitor(app: &AppHandle) {
+    *HOT_CORNER_CONFIG.lock().unwrap() = load_json_or_default(app, HOT_CORNERS_FILE);
+    if HOT_CORNER_MONITOR_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let app_handle = app.clone();
+    std::thread::spawn(move || {
+        // Corner the cursor is resting in, when it got there, and whether it already fired
+        let mut visit: Option<(HotCorner, Instant, bool)> = None;
+        loop {
+            let config = HOT_CORNER_CONFIG.lock().unwrap().clone();
+            if !config.enabled || config.corners.is_empty() || is_capture_paused() {
+                visit = None;
+                std::thread::sleep(DISABLED_POLL_INTERVAL);
+                continue;
+            }
+
+            match (cursor_corner(&app_handle), visit.as_mut()) {
+                (Some(corner), Some((current, entered, fired))) if corner == *current => {
+                    if !*fired && entered.elapsed() >= Duration::from_millis(config.dwell_ms) {
+                        *fired = true;
+                        if let Some(command) = config.corners.get(&corner) {
+                            info!("📐 Hot corner {} activated", corner.trigger());
+                            run_routed_trigger(&app_handle, corner.trigger(), command);
+                        }
+                    }
+                }
+                (Some(corner), _) => visit = Some((corner, Instant::now(), false)),
+                (None, _) => visit = None,
+            }
+            std::thread::sleep(POLL_INTERVAL);
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_hot_corner_config() -> HotCornerConfig {
+    HOT_CORNER_CONFIG.lock().unwrap().clone()
+}
+
+#[tauri::command]
+pub fn save_hot_corner_config(app: AppHandle, config: HotCornerConfig) -> Result<(), String> {
+    save_json(&app, HOT_CORNERS_FILE, &config)?;
+    *HOT_CORNER_CONFIG.lock().unwrap() = config;
+    Ok(())
+}
@@ -1,14 +1,14 @@
-use tauri::AppHandle;
+use tauri::{AppHandle, Emitter, Manager, State};
 use std::collections::HashMap;
 use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
 use serde::{Deserialize, Serialize};
 
+use crate::desktop::{add_input_listener, ensure_input_hook, load_json_or_default, save_json};
+
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 use tauri_plugin_global_shortcut::Shortcut;
 
-// Global state for managing shortcuts
-static REGISTERED_SHORTCUTS: LazyLock<Mutex<HashMap<String, String>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
-
 #[derive(Debug, Serialize, Deserialize)]
 pub struct HotkeyConfig {
     pub quick_note: String,
@@ -63,7 +63,7 @@ fn get_default_window_size() -> (f64, f64) {
 }
 
 #[tauri::command]
-pub fn register_hotkey(app: AppHandle, shortcut: String, command: String) -> Result<(), String> {
+pub fn register_hotkey(app: AppHandle, registry: State<'_, ShortcutRegistry>, shortcut: String, command: String) -> Result<(), String> {
     #[cfg(not(any(target_os = "android", target_os = "ios")))]
     {
         use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut};
@@ -79,11 +79,11 @@ pub fn register_hotkey(app: AppHandle, shortcut: String, command: String) -> Res
         app.global_shortcut().register(parsed_shortcut)
             .map_err(|e| format!("Failed to register shortcut: {}", e))?;
         
-        // Store command for the shortcut handler (normalize to lowercase)
-        let mut shortcuts = REGISTERED_SHORTCUTS.lock().unwrap();
-        shortcuts.insert(shortcut.to_lowercase(), command.clone());
+        // Store command for the shortcut handler, replacing a route for the same shortcut
+        registry.set_command(&shortcut, command.clone());
+        registry.remove_route(&shortcut);
         
-        println!("Successfully registered shortcut: {} for command: {}", shortcut, command);
+        info!("Successfully registered shortcut: {} for command: {}", shortcut, command);
         Ok(())
     }
     #[cfg(any(target_os = "android", target_os = "ios"))]
@@ -93,7 +93,7 @@ pub fn register_hotkey(app: AppHandle, shortcut: String, command: String) -> Res
 }
 
 #[tauri::command]
-pub fn unregister_hotkey(app: AppHandle, shortcut: String) -> Result<(), String> {
+pub fn unregister_hotkey(app: AppHandle, registry: State<'_, ShortcutRegistry>, shortcut: String) -> Result<(), String> {
     #[cfg(not(any(target_os = "android", target_os = "ios")))]
     {
         use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut};
@@ -106,11 +106,10 @@ pub fn unregister_hotkey(app: AppHandle, shortcut: String) -> Result<(), String>
         app.global_shortcut().unregister(parsed_shortcut)
             .map_err(|e| format!("Failed to unregister shortcut: {}", e))?;
         
-        // Remove from local storage (normalize to lowercase)
-        let mut shortcuts = REGISTERED_SHORTCUTS.lock().unwrap();
-        shortcuts.remove(&shortcut.to_lowercase());
+        registry.remove_command(&shortcut);
+        registry.remove_route(&shortcut);
         
-        println!("Successfully unregistered shortcut: {}", shortcut);
+        info!("Successfully unregistered shortcut: {}", shortcut);
         Ok(())
     }
     #[cfg(any(target_os = "android", target_os = "ios"))]
@@ -120,13 +119,12 @@ pub fn unregister_hotkey(app: AppHandle, shortcut: String) -> Result<(), String>
 }
 
 #[tauri::command]
-pub fn get_registered_shortcuts() -> HashMap<String, String> {
-    REGISTERED_SHORTCUTS.lock().unwrap().clone()
+pub fn get_registered_shortcuts(registry: State<'_, ShortcutRegistry>) -> HashMap<String, String> {
+    registry.commands()
 }
 
-pub fn register_shortcut_command(shortcut: String, command: String) {
-    let mut shortcuts = REGISTERED_SHORTCUTS.lock().unwrap();
-    shortcuts.insert(shortcut.to_lowercase(), command);
+pub fn register_shortcut_command(app: &AppHandle, shortcut: String, command: String) {
+    app.state::<ShortcutRegistry>().set_command(&shortcut, command);
 }
 
 #[allow(dead_code)]
@@ -140,27 +138,450 @@ pub fn setup_default_shortcuts(app_handle: &AppHandle) -> Result<(), String> {
         // Register default quick note shortcut
         if let Ok(parsed_shortcut) = default_config.quick_note.parse::<Shortcut>() {
             if let Err(e) = app_handle.global_shortcut().register(parsed_shortcut) {
-                eprintln!("Failed to register default quicknote hotkey: {}", e);
+                error!("Failed to register default quicknote hotkey: {}", e);
             } else {
-                // Store the registered shortcut (normalize to lowercase)
-                let mut shortcuts = REGISTERED_SHORTCUTS.lock().unwrap();
-                shortcuts.insert(default_config.quick_note.to_lowercase(), "quicknote".to_string());
-                println!("Registered default shortcut: {}", default_config.quick_note);
+                register_shortcut_command(app_handle, default_config.quick_note.clone(), "quicknote".to_string());
+                info!("Registered default shortcut: {}", default_config.quick_note);
             }
         }
         
         // Register default quick AI shortcut
         if let Ok(parsed_shortcut) = default_config.quick_ai.parse::<Shortcut>() {
             if let Err(e) = app_handle.global_shortcut().register(parsed_shortcut) {
-                eprintln!("Failed to register default quickai hotkey: {}", e);
+                error!("Failed to register default quickai hotkey: {}", e);
             } else {
-                // Store the registered shortcut (normalize to lowercase)
-                let mut shortcuts = REGISTERED_SHORTCUTS.lock().unwrap();
-                shortcuts.insert(default_config.quick_ai.to_lowercase(), "quickai".to_string());
-                println!("Registered default AI shortcut: {}", default_config.quick_ai);
+                register_shortcut_command(app_handle, default_config.quick_ai.clone(), "quickai".to_string());
+                info!("Registered default AI shortcut: {}", default_config.quick_ai);
             }
         }
     }
     
     Ok(())
-}
\ No newline at end of file
+}
+
+/// What a routed shortcut does with its target window
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ShortcutAction {
+    Toggle,
+    Show,
+    Hide,
+    /// Only send `shortcut-triggered` to the window, without changing its visibility
+    Emit,
+}
+
+/// Routing table entry registered by the frontend, so new windows need no Rust changes
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ShortcutRoute {
+    /// Window label, e.g. "quicknote" or a window created by the frontend
+    pub window: String,
+    pub action: ShortcutAction,
+    /// Sent to the window with the `shortcut-triggered` event
+    pub payload: Option<serde_json::Value>,
+}
+
+/// What a pressed shortcut runs
+#[derive(Clone)]
+pub enum ShortcutBinding {
+    /// Routing table entry with the trigger it was registered under
+    Route(String, ShortcutRoute),
+    Command(String),
+}
+
+/// Built-in commands and routes of the registered shortcuts, managed by the app.
+/// Keys are lowercase so lookups don't depend on how the frontend spelled a shortcut.
+#[derive(Default)]
+pub struct ShortcutRegistry {
+    commands: Mutex<HashMap<String, String>>,
+    // Keyed by trigger, normally a shortcut string
+    routes: Mutex<HashMap<String, ShortcutRoute>>,
+    // Parsed once at registration, so a key press is a single map lookup
+    bindings: Mutex<HashMap<Shortcut, ShortcutBinding>>,
+}
+
+impl ShortcutRegistry {
+    /// Look up a pressed shortcut; routes take precedence over built-in commands
+    pub fn resolve(&self, shortcut: &Shortcut) -> Option<ShortcutBinding> {
+        self.bindings.lock().unwrap().get(shortcut).cloned()
+    }
+
+    pub fn commands(&self) -> HashMap<String, String> {
+        self.commands.lock().unwrap().clone()
+    }
+
+    pub fn set_command(&self, shortcut: &str, command: String) {
+        if let Ok(parsed) = shortcut.parse::<Shortcut>() {
+            let mut bindings = self.bindings.lock().unwrap();
+            if !matches!(bindings.get(&parsed), Some(ShortcutBinding::Route(..))) {
+                bindings.insert(parsed, ShortcutBinding::Command(command.clone()));
+            }
+        }
+        self.commands.lock().unwrap().insert(shortcut.to_lowercase(), command);
+    }
+
+    pub fn remove_command(&self, shortcut: &str) {
+        if let Ok(parsed) = shortcut.parse::<Shortcut>() {
+            let mut bindings = self.bindings.lock().unwrap();
+            if matches!(bindings.get(&parsed), Some(ShortcutBinding::Command(_))) {
+                bindings.remove(&parsed);
+            }
+        }
+        self.commands.lock().unwrap().remove(&shortcut.to_lowercase());
+    }
+
+    pub fn routes(&self) -> HashMap<String, ShortcutRoute> {
+        self.routes.lock().unwrap().clone()
+    }
+
+    pub fn route(&self, trigger: &str) -> Option<ShortcutRoute> {
+        self.routes.lock().unwrap().get(&trigger.to_lowercase()).cloned()
+    }
+
+    pub fn set_route(&self, trigger: &str, route: ShortcutRoute) {
+        let trigger = trigger.to_lowercase();
+        // Triggers like `gesture:shake` are not key presses and have no binding
+        if let Ok(parsed) = trigger.parse::<Shortcut>() {
+            self.bindings.lock().unwrap().insert(parsed, ShortcutBinding::Route(trigger.clone(), route.clone()));
+        }
+        self.routes.lock().unwrap().insert(trigger, route);
+    }
+
+    pub fn remove_route(&self, trigger: &str) {
+        let trigger = trigger.to_lowercase();
+        let removed = self.routes.lock().unwrap().remove(&trigger);
+        let Ok(parsed) = trigger.parse::<Shortcut>() else { return };
+        if removed.is_none() {
+            return;
+        }
+
+        // Fall back to a built-in command still registered for the same keys
+        let command = self.commands.lock().unwrap().iter()
+            .find(|(shortcut, _)| shortcut.parse::<Shortcut>().is_ok_and(|other| other == parsed))
+            .map(|(_, command)| command.clone());
+        let mut bindings = self.bindings.lock().unwrap();
+        match command {
+            Some(command) => bindings.insert(parsed, ShortcutBinding::Command(command)),
+            None => bindings.remove(&parsed),
+        };
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+struct ShortcutTriggered {
+    trigger: String,
+    action: ShortcutAction,
+    payload: Option<serde_json::Value>,
+}
+
+/// Built-in quick windows are created on demand, so they toggle through their own commands
+fn toggle_route_window(app: &AppHandle, label: &str) -> Result<(), String> {
+    match label {
+        "quicknote" => crate::desktop::toggle_quicknote_window(app.clone()),
+        "quickai" => crate::desktop::toggle_quickai_window(app.clone()),
+        "quicktool" => crate::desktop::toggle_quicktool_window(app.clone()),
+        "palette" => crate::desktop::toggle_palette_window(app.clone()),
+        _ => {
+            let window = app.get_webview_window(label)
+                .ok_or_else(|| format!("{} window not found", label))?;
+            if window.is_visible().unwrap_or(false) {
+                let _ = window.hide();
+            } else {
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Run a routing table entry for the given trigger
+pub fn dispatch_shortcut_route(app: &AppHandle, trigger: &str, route: &ShortcutRoute) -> Result<(), String> {
+    match route.action {
+        ShortcutAction::Toggle => toggle_route_window(app, &route.window)?,
+        ShortcutAction::Show => {
+            if app.get_webview_window(&route.window).is_none() {
+                toggle_route_window(app, &route.window)?;
+            }
+            let window = app.get_webview_window(&route.window)
+                .ok_or_else(|| format!("{} window not found", route.window))?;
+            let _ = window.show();
+            let _ = window.set_focus();
+        }
+        ShortcutAction::Hide => {
+            if let Some(window) = app.get_webview_window(&route.window) {
+                let _ = window.hide();
+            }
+        }
+        ShortcutAction::Emit => {}
+    }
+
+    let window = app.get_webview_window(&route.window)
+        .ok_or_else(|| format!("{} window not found", route.window))?;
+    window.emit("shortcut-triggered", ShortcutTriggered {
+        trigger: trigger.to_string(),
+        action: route.action,
+        payload: route.payload.clone(),
+    })
+    .map_err(|e| format!("Failed to emit shortcut-triggered event: {}", e))?;
+
+    debug!("🎯 Routed {} to {} ({:?})", trigger, route.window, route.action);
+    Ok(())
+}
+
+/// Run one of the built-in commands registered through `register_hotkey`
+pub fn run_shortcut_command(app: &AppHandle, command: &str) -> bool {
+    match command {
+        "quicknote" | "quickai" | "quicktool" | "palette" => {
+            if let Err(e) = toggle_route_window(app, command) {
+                error!("Failed to toggle {} window: {}", command, e);
+            }
+        }
+        "text-selection" => crate::desktop::handle_text_selection(app),
+        "screenshot" => crate::desktop::handle_screenshot_shortcut(app),
+        "colorpicker" => crate::desktop::handle_color_picker_shortcut(app),
+        "capture-page" => crate::desktop::handle_capture_page_shortcut(app),
+        "dictation" => {
+            if let Err(e) = crate::desktop::toggle_dictation(app) {
+                error!("Failed to toggle dictation: {}", e);
+            }
+        }
+        _ => {
+            if let Some(id) = command.strip_prefix(crate::desktop::TEMPLATE_COMMAND_PREFIX) {
+                crate::desktop::capture_with_template(app, id);
+            } else if let Some(script) = command.strip_prefix(crate::desktop::SCRIPT_COMMAND_PREFIX) {
+                crate::desktop::run_hotkey_script(app, script);
+            } else {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+/// Run an action by its routing table name: a routed trigger first, then a built-in command
+pub fn run_named_action(app: &AppHandle, name: &str) -> Result<(), String> {
+    if let Some(route) = get_shortcut_route(app, name) {
+        return dispatch_shortcut_route(app, name, &route);
+    }
+    if run_shortcut_command(app, name) {
+        Ok(())
+    } else {
+        Err(format!("Unknown action: {}", name))
+    }
+}
+
+/// Triggers with a routing table entry, so remote controls can offer them as actions
+pub fn list_shortcut_route_triggers(app: &AppHandle) -> Vec<String> {
+    let mut triggers: Vec<String> = app.state::<ShortcutRegistry>().routes().into_keys().collect();
+    triggers.sort();
+    triggers
+}
+
+pub fn get_shortcut_route(app: &AppHandle, trigger: &str) -> Option<ShortcutRoute> {
+    app.state::<ShortcutRegistry>().route(trigger)
+}
+
+#[tauri::command]
+pub fn register_shortcut_route(app: AppHandle, registry: State<'_, ShortcutRegistry>, shortcut: String, route: ShortcutRoute) -> Result<(), String> {
+    use tauri_plugin_global_shortcut::GlobalShortcutExt;
+
+    let parsed_shortcut = shortcut.parse::<Shortcut>()
+        .map_err(|e| format!("Invalid shortcut format: {}", e))?;
+
+    // Re-registering an existing shortcut replaces its target
+    let _ = app.global_shortcut().unregister(parsed_shortcut);
+    app.global_shortcut().register(parsed_shortcut)
+        .map_err(|e| format!("Failed to register shortcut: {}", e))?;
+
+    // A shortcut has either a built-in command or a route, never both
+    registry.remove_command(&shortcut);
+    info!("Successfully registered shortcut: {} for window: {} ({:?})", shortcut, route.window, route.action);
+    registry.set_route(&shortcut, route);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn unregister_shortcut_route(app: AppHandle, registry: State<'_, ShortcutRegistry>, shortcut: String) -> Result<(), String> {
+    use tauri_plugin_global_shortcut::GlobalShortcutExt;
+
+    let parsed_shortcut = shortcut.parse::<Shortcut>()
+        .map_err(|e| format!("Invalid shortcut format: {}", e))?;
+    app.global_shortcut().unregister(parsed_shortcut)
+        .map_err(|e| format!("Failed to unregister shortcut: {}", e))?;
+
+    registry.remove_route(&shortcut);
+    info!("Successfully unregistered shortcut route: {}", shortcut);
+    Ok(())
+}
+
+/// Route a trigger that is not a key press, e.g. `gesture:shake` or `corner:top-left`; None removes it
+#[tauri::command]
+pub fn set_trigger_route(registry: State<'_, ShortcutRegistry>, trigger: String, route: Option<ShortcutRoute>) -> Result<(), String> {
+    if !trigger.contains(':') {
+        return Err(format!("Use register_shortcut_route for keyboard shortcuts: {}", trigger));
+    }
+    match route {
+        Some(route) => registry.set_route(&trigger, route),
+        None => registry.remove_route(&trigger),
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_shortcut_routes(registry: State<'_, ShortcutRegistry>) -> HashMap<String, ShortcutRoute> {
+    registry.routes()
+}
+
+const MOUSE_GESTURES_FILE: &str = "mouse_gestures.json";
+/// Direction changes have to happen within this window to count as one shake
+const SHAKE_WINDOW: Duration = Duration::from_millis(800);
+const SHAKE_COOLDOWN: Duration = Duration::from_millis(1500);
+/// Routing table trigger for the shake gesture, see `set_trigger_route`
+pub const SHAKE_GESTURE_TRIGGER: &str = "gesture:shake";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MouseGestureConfig {
+    pub enabled: bool,
+    /// Built-in command run on a cursor shake, unless a route is registered for `gesture:shake`
+    #[serde(rename = "shakeCommand")]
+    pub shake_command: String,
+    /// 1 (firm, wide shakes) to 10 (light, short shakes)
+    pub sensitivity: u8,
+}
+
+impl Default for MouseGestureConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            shake_command: "quicknote".to_string(),
+            sensitivity: 5,
+        }
+    }
+}
+
+impl MouseGestureConfig {
+    /// Horizontal travel needed before a direction change counts
+    fn shake_distance(&self) -> f64 {
+        160.0 - f64::from(self.sensitivity.clamp(1, 10)) * 12.0
+    }
+
+    fn shake_reversals(&self) -> usize {
+        if self.sensitivity >= 7 { 3 } else { 4 }
+    }
+}
+
+#[derive(Default)]
+struct ShakeTracker {
+    last_x: Option<f64>,
+    /// +1 or -1 while moving, 0 before the first movement
+    direction: i8,
+    travelled: f64,
+    reversals: Vec<Instant>,
+    last_trigger: Option<Instant>,
+}
+
+impl ShakeTracker {
+    /// Feed a cursor position, returns true when a shake was completed
+    fn track(&mut self, x: f64, config: &MouseGestureConfig) -> bool {
+        let Some(last_x) = self.last_x.replace(x) else {
+            return false;
+        };
+        let delta = x - last_x;
+        if delta == 0.0 {
+            return false;
+        }
+
+        let direction = if delta > 0.0 { 1 } else { -1 };
+        if direction == self.direction {
+            self.travelled += delta.abs();
+            return false;
+        }
+
+        let now = Instant::now();
+        if self.direction != 0 && self.travelled >= config.shake_distance() {
+            self.reversals.push(now);
+        }
+        self.direction = direction;
+        self.travelled = delta.abs();
+        self.reversals.retain(|at| now.duration_since(*at) <= SHAKE_WINDOW);
+
+        let cooling_down = self.last_trigger.is_some_and(|at| now.duration_since(at) < SHAKE_COOLDOWN);
+        if self.reversals.len() >= config.shake_reversals() && !cooling_down {
+            self.reversals.clear();
+            self.last_trigger = Some(now);
+            return true;
+        }
+        false
+    }
+}
+
+static MOUSE_GESTURE_CONFIG: LazyLock<Mutex<MouseGestureConfig>> = LazyLock::new(|| Mutex::new(MouseGestureConfig::default()));
+static SHAKE_TRACKER: LazyLock<Mutex<ShakeTracker>> = LazyLock::new(|| Mutex::new(ShakeTracker::default()));
+static GESTURE_APP_HANDLE: LazyLock<Mutex<Option<AppHandle>>> = LazyLock::new(|| Mutex::new(None));
+
+/// Run the route registered for a non-keyboard trigger, or the built-in command when there is none
+pub fn run_routed_trigger(app: &AppHandle, trigger: &str, command: &str) {
+    if let Some(route) = get_shortcut_route(app, trigger) {
+        if let Err(e) = dispatch_shortcut_route(app, trigger, &route) {
+            error!("❌ Failed to route {}: {}", trigger, e);
+        }
+    } else if !run_shortcut_command(app, command) {
+        warn!("⚠️ Unknown command '{}' for {}", command, trigger);
+    }
+}
+
+fn handle_gesture_event(event: rdev::Event) {
+    let rdev::EventType::MouseMove { x, .. } = event.event_type else {
+        return;
+    };
+    let config = MOUSE_GESTURE_CONFIG.lock().unwrap().clone();
+    if !config.enabled {
+        return;
+    }
+    if !SHAKE_TRACKER.lock().unwrap().track(x, &config) {
+        return;
+    }
+
+    if let Some(app) = GESTURE_APP_HANDLE.lock().unwrap().clone() {
+        // Leave the rdev hook quickly, window work happens elsewhere
+        info!("🖱️ Mouse gesture detected: {}", SHAKE_GESTURE_TRIGGER);
+        std::thread::spawn(move || run_routed_trigger(&app, SHAKE_GESTURE_TRIGGER, &config.shake_command));
+    }
+}
+
+fn ensure_gesture_listener(app: &AppHandle) {
+    *GESTURE_APP_HANDLE.lock().unwrap() = Some(app.clone());
+    add_input_listener("mouse-gestures", handle_gesture_event);
+    ensure_input_hook();
+}
+
+/// Load the gesture settings and start listening if gestures are on
+pub fn start_mouse_gestures(app: &AppHandle) {
+    let config: MouseGestureConfig = load_json_or_default(app, MOUSE_GESTURES_FILE);
+    let enabled = config.enabled;
+    *MOUSE_GESTURE_CONFIG.lock().unwrap() = config;
+    if enabled {
+        ensure_gesture_listener(app);
+    }
+}
+
+#[tauri::command]
+pub fn get_mouse_gesture_config() -> MouseGestureConfig {
+    MOUSE_GESTURE_CONFIG.lock().unwrap().clone()
+}
+
+#[tauri::command]
+pub fn save_mouse_gesture_config(app: AppHandle, config: MouseGestureConfig) -> Result<(), String> {
+    if !(1..=10).contains(&config.sensitivity) {
+        return Err(format!("Sensitivity must be between 1 and 10, got {}", config.sensitivity));
+    }
+    save_json(&app, MOUSE_GESTURES_FILE, &config)?;
+
+    let enabled = config.enabled;
+    *MOUSE_GESTURE_CONFIG.lock().unwrap() = config;
+    *SHAKE_TRACKER.lock().unwrap() = ShakeTracker::default();
+    if enabled {
+        ensure_gesture_listener(&app);
+    }
+    Ok(())
+}
@@ -0,0 +1,153 @@
+use tauri::{AppHandle, Emitter};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+
+use user_idle::UserIdle;
+
+use crate::desktop::{load_json_or_default, read_sync_status, save_json, spawn_offline_replay};
+
+const IDLE_CONFIG_FILE: &str = "idle_config.json";
+const IDLE_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IdleConfig {
+    /// Seconds without keyboard or mouse input before the system counts as idle
+    #[serde(rename = "thresholdSeconds")]
+    pub threshold_seconds: u64,
+    /// Release the Whisper and local LLM models while idle. Whisper is loaded again as soon
+    /// as the user is back, the LLM on its next use.
+    #[serde(rename = "unloadModelsWhenIdle")]
+    pub unload_models_when_idle: bool,
+    /// Replay pending offline changes once the user stops working
+    #[serde(rename = "syncWhenIdle")]
+    pub sync_when_idle: bool,
+}
+
+impl Default for IdleConfig {
+    fn default() -> Self {
+        Self {
+            threshold_seconds: 300,
+            // Reloading costs several seconds of disk and GPU work, so only on request
+            unload_models_when_idle: false,
+            sync_when_idle: true,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IdleEvent {
+    #[serde(rename = "idleSeconds")]
+    pub idle_seconds: u64,
+}
+
+static IDLE_CONFIG: LazyLock<Mutex<IdleConfig>> = LazyLock::new(|| Mutex::new(IdleConfig::default()));
+static IDLE_MONITOR_STARTED: AtomicBool = AtomicBool::new(false);
+static SYSTEM_IDLE: AtomicBool = AtomicBool::new(false);
+// Set when the Whisper model was unloaded for idleness, so it is loaded again on return
+static WHISPER_UNLOADED_WHILE_IDLE: AtomicBool = AtomicBool::new(false);
+
+/// Seconds since the last keyboard or mouse input anywhere on the system
+pub fn system_idle_seconds() -> Result<u64, String> {
+    UserIdle::get_time()
+        .map(|idle| idle.as_seconds())
+        .map_err(|e| format!("Failed to read idle time: {:?}", e))
+}
+
+/// Whether the user is currently away, for deferring heavy work
+pub fn is_system_idle() -> bool {
+    SYSTEM_IDLE.load(Ordering::SeqCst)
+}
+
+/// Drop models that are cheap to reload compared to the memory they hold
+fn unload_idle_models(app: &AppHandle) {
+    #[cfg(all(target_os = "windows", any(feature = "whisper-cuda", feature = "whisper-cpu")))]
+    {
+        let processor = crate::voice::current_voice_processor(app);
+        if let Some(processor) = processor {
+            if processor.transcriber.unload() {
+                WHISPER_UNLOADED_WHILE_IDLE.store(true, Ordering::SeqCst);
+                info!("💤 Whisper model unloaded while idle");
+            }
+        }
+    }
+
+    #[cfg(not(all(target_os = "windows", any(feature = "whisper-cuda", feature = "whisper-cpu"))))]
+    let _ = app;
+
+    #[cfg(feature = "local-llm")]
+    crate::llm::unload_llm_model();
+}
+
+/// Load the Whisper model again in the background, so the next dictation doesn't wait for it
+fn reload_idle_models(app: &AppHandle) {
+    if !WHISPER_UNLOADED_WHILE_IDLE.swap(false, Ordering::SeqCst) {
+        return;
+    }
+
+    #[cfg(all(target_os = "windows", any(feature = "whisper-cuda", feature = "whisper-cpu")))]
+    if let Some(processor) = crate::voice::current_voice_processor(app) {
+        std::thread::spawn(move || match processor.transcriber.preload() {
+            Ok(_) => info!("🔄 Whisper model reloaded after idle"),
+            Err(e) => error!("❌ Failed to reload Whisper model: {}", e),
+        });
+    }
+
+    #[cfg(not(all(target_os = "windows", any(feature = "whisper-cuda", feature = "whisper-cpu"))))]
+    let _ = app;
+}
+
+fn on_system_idle(app: &AppHandle, config: &IdleConfig, idle_seconds: u64) {
+    info!("💤 System idle for {}s", idle_seconds);
+    if config.unload_models_when_idle {
+        unload_idle_models(app);
+    }
+    if config.sync_when_idle && read_sync_status(app).is_ok_and(|s| s.pending_count > 0) {
+        spawn_offline_replay(app);
+    }
+    let _ = app.emit("system-idle", IdleEvent { idle_seconds });
+}
+
+/// Poll the idle time and emit `system-idle` / `system-active` on transitions
+pub fn start_idle_monitor(app: &AppHandle) {
+    *IDLE_CONFIG.lock().unwrap() = load_json_or_default(app, IDLE_CONFIG_FILE);
+    if IDLE_MONITOR_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let app_handle = app.clone();
+    std::thread::spawn(move || loop {
+        std::thread::sleep(IDLE_POLL_INTERVAL);
+        let Ok(idle_seconds) = system_idle_seconds() else { continue };
+        let config = IDLE_CONFIG.lock().unwrap().clone();
+        let idle = idle_seconds >= config.threshold_seconds;
+
+        if idle != SYSTEM_IDLE.swap(idle, Ordering::SeqCst) {
+            if idle {
+                on_system_idle(&app_handle, &config, idle_seconds);
+            } else {
+                info!("👋 System active again");
+                reload_idle_models(&app_handle);
+                let _ = app_handle.emit("system-active", IdleEvent { idle_seconds });
+            }
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_idle_seconds() -> Result<u64, String> {
+    system_idle_seconds()
+}
+
+#[tauri::command]
+pub fn get_idle_config() -> IdleConfig {
+    IDLE_CONFIG.lock().unwrap().clone()
+}
+
+#[tauri::command]
+pub fn save_idle_config(app: AppHandle, config: IdleConfig) -> Result<(), String> {
+    save_json(&app, IDLE_CONFIG_FILE, &config)?;
+    *IDLE_CONFIG.lock().unwrap() = config;
+    Ok(())
+}
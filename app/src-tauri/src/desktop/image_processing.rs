@@ -0,0 +1,340 @@
+use tauri::AppHandle;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageFormat, ImageReader};
+
+use crate::desktop::{generate_id, get_app_data_subdir, load_json_or_default, save_json};
+
+const IMAGE_PROCESSING_CONFIG_FILE: &str = "image_processing.json";
+const PROCESSED_IMAGES_DIR: &str = "processed_images";
+/// Processed copies only need to live until they are imported or uploaded
+const PROCESSED_MAX_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+const MIN_JPEG_QUALITY: u8 = 60;
+const MAX_COMPRESSION_ATTEMPTS: u32 = 8;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ImageProcessingConfig {
+    pub enabled: bool,
+    /// Remove location data from EXIF before the image leaves the machine
+    #[serde(rename = "stripGps")]
+    pub strip_gps: bool,
+    /// Longest edge in pixels, larger images are downscaled
+    #[serde(rename = "maxDimension")]
+    pub max_dimension: Option<u32>,
+    /// Images above this size are recompressed, then downscaled until they fit
+    #[serde(rename = "maxBytes")]
+    pub max_bytes: Option<u64>,
+    #[serde(rename = "jpegQuality")]
+    pub jpeg_quality: u8,
+}
+
+impl Default for ImageProcessingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            strip_gps: true,
+            max_dimension: Some(2560),
+            max_bytes: Some(2 * 1024 * 1024),
+            jpeg_quality: 85,
+        }
+    }
+}
+
+/// Per-import overrides, unset fields fall back to the saved config
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ImageProcessingOverrides {
+    pub enabled: Option<bool>,
+    #[serde(rename = "stripGps")]
+    pub strip_gps: Option<bool>,
+    #[serde(rename = "maxDimension")]
+    pub max_dimension: Option<u32>,
+    #[serde(rename = "maxBytes")]
+    pub max_bytes: Option<u64>,
+    #[serde(rename = "jpegQuality")]
+    pub jpeg_quality: Option<u8>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProcessedImage {
+    /// Processed copy, or the original path when nothing had to change
+    pub path: String,
+    #[serde(rename = "originalSize")]
+    pub original_size: u64,
+    pub size: u64,
+    pub resized: bool,
+    #[serde(rename = "gpsRemoved")]
+    pub gps_removed: bool,
+}
+
+/// Load image processing config from file
+pub fn load_image_processing_config(app: &AppHandle) -> ImageProcessingConfig {
+    load_json_or_default(app, IMAGE_PROCESSING_CONFIG_FILE)
+}
+
+fn effective_config(app: &AppHandle, overrides: Option<&ImageProcessingOverrides>) -> ImageProcessingConfig {
+    let mut config = load_image_processing_config(app);
+    if let Some(o) = overrides {
+        config.enabled = o.enabled.unwrap_or(config.enabled);
+        config.strip_gps = o.strip_gps.unwrap_or(config.strip_gps);
+        config.max_dimension = o.max_dimension.or(config.max_dimension);
+        config.max_bytes = o.max_bytes.or(config.max_bytes);
+        config.jpeg_quality = o.jpeg_quality.unwrap_or(config.jpeg_quality);
+    }
+    config
+}
+
+struct ExifInfo {
+    has_gps: bool,
+    orientation: u32,
+}
+
+fn read_exif_info(bytes: &[u8]) -> ExifInfo {
+    match exif::Reader::new().read_from_container(&mut Cursor::new(bytes)) {
+        Ok(data) => ExifInfo {
+            has_gps: data.fields().any(|f| f.tag.context() == exif::Context::Gps),
+            orientation: data.get_field(exif::Tag::Orientation, exif::In::PRIMARY)
+                .and_then(|f| f.value.get_uint(0))
+                .unwrap_or(1),
+        },
+        Err(_) => ExifInfo { has_gps: false, orientation: 1 },
+    }
+}
+
+/// Drop APP1 (EXIF and XMP) segments without touching the image data
+fn strip_jpeg_metadata(bytes: &[u8]) -> Option<Vec<u8>> {
+    if !bytes.starts_with(&[0xFF, 0xD8]) {
+        return None;
+    }
+    let mut output = vec![0xFF, 0xD8];
+    let mut i = 2;
+    while i + 4 <= bytes.len() {
+        if bytes[i] != 0xFF {
+            return None;
+        }
+        let marker = bytes[i + 1];
+        // Start of scan, the rest is entropy coded data
+        if marker == 0xDA {
+            output.extend_from_slice(&bytes[i..]);
+            return Some(output);
+        }
+        let len = u16::from_be_bytes([bytes[i + 2], bytes[i + 3]]) as usize;
+        let end = (i + 2 + len).min(bytes.len());
+        if marker != 0xE1 {
+            output.extend_from_slice(&bytes[i..end]);
+        }
+        i = end;
+    }
+    None
+}
+
+/// Drop `eXIf` and XMP chunks from a PNG
+fn strip_png_metadata(bytes: &[u8]) -> Option<Vec<u8>> {
+    const SIGNATURE: &[u8] = &[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+    if !bytes.starts_with(SIGNATURE) {
+        return None;
+    }
+    let mut output = SIGNATURE.to_vec();
+    let mut i = SIGNATURE.len();
+    while i + 12 <= bytes.len() {
+        let len = u32::from_be_bytes([bytes[i], bytes[i + 1], bytes[i + 2], bytes[i + 3]]) as usize;
+        let end = (i + 12 + len).min(bytes.len());
+        let kind = &bytes[i + 4..i + 8];
+        let data = &bytes[i + 8..(i + 8 + len).min(bytes.len())];
+        let is_metadata = kind == b"eXIf" || (kind == b"iTXt" && data.starts_with(b"XML:com.adobe.xmp"));
+        if !is_metadata {
+            output.extend_from_slice(&bytes[i..end]);
+        }
+        i = end;
+    }
+    Some(output)
+}
+
+fn apply_orientation(image: DynamicImage, orientation: u32) -> DynamicImage {
+    match orientation {
+        2 => image.fliph(),
+        3 => image.rotate180(),
+        4 => image.flipv(),
+        5 => image.rotate90().fliph(),
+        6 => image.rotate90(),
+        7 => image.rotate270().fliph(),
+        8 => image.rotate270(),
+        _ => image,
+    }
+}
+
+fn encode(image: &DynamicImage, format: ImageFormat, quality: u8) -> Result<Vec<u8>, String> {
+    let mut output = Vec::new();
+    if format == ImageFormat::Jpeg {
+        // JPEG has no alpha channel
+        let rgb = DynamicImage::ImageRgb8(image.to_rgb8());
+        JpegEncoder::new_with_quality(&mut output, quality)
+            .encode_image(&rgb)
+            .map_err(|e| format!("Failed to encode image: {}", e))?;
+    } else {
+        image.write_to(&mut Cursor::new(&mut output), format)
+            .map_err(|e| format!("Failed to encode image: {}", e))?;
+    }
+    Ok(output)
+}
+
+/// Decode, fix orientation, downscale and re-encode until the size limit is met.
+/// Re-encoding drops all metadata.
+fn reencode_image(bytes: &[u8], format: ImageFormat, orientation: u32, config: &ImageProcessingConfig) -> Result<(Vec<u8>, bool), String> {
+    let decoded = image::load_from_memory_with_format(bytes, format)
+        .map_err(|e| format!("Failed to decode image: {}", e))?;
+    let mut image = apply_orientation(decoded, orientation);
+    let mut resized = false;
+
+    if let Some(max) = config.max_dimension {
+        if image.width().max(image.height()) > max {
+            image = image.resize(max, max, FilterType::Lanczos3);
+            resized = true;
+        }
+    }
+
+    let mut quality = config.jpeg_quality.clamp(1, 100);
+    let mut output = encode(&image, format, quality)?;
+    for _ in 0..MAX_COMPRESSION_ATTEMPTS {
+        let Some(limit) = config.max_bytes else { break };
+        if output.len() as u64 <= limit {
+            break;
+        }
+        if format == ImageFormat::Jpeg && quality > MIN_JPEG_QUALITY {
+            quality = quality.saturating_sub(10).max(MIN_JPEG_QUALITY);
+        } else {
+            let (width, height) = (image.width() * 4 / 5, image.height() * 4 / 5);
+            if width < 320 || height < 320 {
+                break;
+            }
+            image = image.resize(width, height, FilterType::Lanczos3);
+            resized = true;
+        }
+        output = encode(&image, format, quality)?;
+    }
+    Ok((output, resized))
+}
+
+fn cleanup_processed_images(dir: &Path) {
+    let Ok(entries) = fs::read_dir(dir) else { return };
+    for entry in entries.flatten() {
+        let expired = entry.metadata()
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+            .is_some_and(|age| age > PROCESSED_MAX_AGE);
+        if expired {
+            let _ = fs::remove_file(entry.path());
+        }
+    }
+}
+
+/// Strip GPS data and shrink an image before it is imported or uploaded.
+/// Anything that isn't a JPEG or PNG is returned untouched.
+pub fn process_image_file(app: &AppHandle, source: &Path, overrides: Option<&ImageProcessingOverrides>) -> Result<ProcessedImage, String> {
+    let original_size = fs::metadata(source)
+        .map_err(|e| format!("Failed to read {}: {}", source.display(), e))?
+        .len();
+    let unchanged = ProcessedImage {
+        path: source.to_string_lossy().to_string(),
+        original_size,
+        size: original_size,
+        resized: false,
+        gps_removed: false,
+    };
+
+    let format = match source.extension().map(|e| e.to_string_lossy().to_lowercase()).as_deref() {
+        Some("jpg") | Some("jpeg") => ImageFormat::Jpeg,
+        Some("png") => ImageFormat::Png,
+        _ => return Ok(unchanged),
+    };
+    let config = effective_config(app, overrides);
+    if !config.enabled {
+        return Ok(unchanged);
+    }
+
+    let bytes = fs::read(source)
+        .map_err(|e| format!("Failed to read {}: {}", source.display(), e))?;
+    let exif = read_exif_info(&bytes);
+    let (width, height) = ImageReader::with_format(Cursor::new(&bytes), format)
+        .into_dimensions()
+        .map_err(|e| format!("Failed to read image size: {}", e))?;
+
+    let too_large = config.max_dimension.is_some_and(|max| width.max(height) > max)
+        || config.max_bytes.is_some_and(|max| original_size > max);
+    let strip_gps = config.strip_gps && exif.has_gps;
+    if !too_large && !strip_gps {
+        return Ok(unchanged);
+    }
+
+    // Dropping EXIF loses the orientation tag, so rotated photos are re-encoded upright instead
+    let (output, resized) = if too_large || exif.orientation != 1 {
+        reencode_image(&bytes, format, exif.orientation, &config)?
+    } else {
+        let stripped = match format {
+            ImageFormat::Jpeg => strip_jpeg_metadata(&bytes),
+            _ => strip_png_metadata(&bytes),
+        };
+        match stripped {
+            Some(stripped) => (stripped, false),
+            None => reencode_image(&bytes, format, exif.orientation, &config)?,
+        }
+    };
+
+    let dir = get_app_data_subdir(app, PROCESSED_IMAGES_DIR)?;
+    cleanup_processed_images(&dir);
+    let name = source.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_else(|| "image".to_string());
+    let target: PathBuf = dir.join(format!("{}_{}", generate_id(), name));
+    fs::write(&target, &output)
+        .map_err(|e| format!("Failed to write {}: {}", target.display(), e))?;
+
+    info!(
+        "🖼️ Processed {} ({} → {} bytes{}{})",
+        name,
+        original_size,
+        output.len(),
+        if resized { ", resized" } else { "" },
+        if strip_gps { ", GPS removed" } else { "" }
+    );
+    Ok(ProcessedImage {
+        path: target.to_string_lossy().to_string(),
+        original_size,
+        size: output.len() as u64,
+        resized,
+        gps_removed: strip_gps,
+    })
+}
+
+/// Path to import or upload, falling back to the original if processing fails
+pub fn prepare_image_for_upload(app: &AppHandle, source: &Path, overrides: Option<&ImageProcessingOverrides>) -> PathBuf {
+    match process_image_file(app, source, overrides) {
+        Ok(processed) => PathBuf::from(processed.path),
+        Err(e) => {
+            warn!("Image processing skipped for {}: {}", source.display(), e);
+            source.to_path_buf()
+        }
+    }
+}
+
+#[tauri::command]
+pub fn get_image_processing_config(app: AppHandle) -> ImageProcessingConfig {
+    load_image_processing_config(&app)
+}
+
+#[tauri::command]
+pub fn save_image_processing_config(app: AppHandle, config: ImageProcessingConfig) -> Result<(), String> {
+    save_json(&app, IMAGE_PROCESSING_CONFIG_FILE, &config)
+}
+
+/// Process an image on demand, e.g. to preview the result before attaching it
+#[tauri::command]
+pub async fn process_image(app: AppHandle, path: String, overrides: Option<ImageProcessingOverrides>) -> Result<ProcessedImage, String> {
+    tauri::async_runtime::spawn_blocking(move || process_image_file(&app, Path::new(&path), overrides.as_ref()))
+        .await
+        .map_err(|e| format!("Image processing failed: {}", e))?
+}
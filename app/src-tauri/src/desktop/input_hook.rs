@@ -0,0 +1,47 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex};
+
+type InputListener = Box<dyn Fn(rdev::Event) + Send>;
+
+// rdev keeps a single global callback per process, so every feature that needs
+// raw keyboard or mouse events subscribes here instead of calling `rdev::listen`
+static INPUT_LISTENERS: LazyLock<Mutex<Vec<(&'static str, InputListener)>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+static INPUT_HOOK_STARTED: AtomicBool = AtomicBool::new(false);
+
+fn dispatch_input_event(event: rdev::Event) {
+    // Releases still go through while capture is paused so no listener thinks a key is stuck down
+    if crate::desktop::is_capture_paused()
+        && !matches!(event.event_type, rdev::EventType::KeyRelease(_) | rdev::EventType::ButtonRelease(_))
+    {
+        return;
+    }
+    for (_, listener) in INPUT_LISTENERS.lock().unwrap().iter() {
+        listener(event.clone());
+    }
+}
+
+/// Subscribe to global input events; a listener with the same name is replaced
+pub fn add_input_listener(name: &'static str, listener: impl Fn(rdev::Event) + Send + 'static) {
+    let mut listeners = INPUT_LISTENERS.lock().unwrap();
+    listeners.retain(|(existing, _)| *existing != name);
+    listeners.push((name, Box::new(listener)));
+}
+
+pub fn remove_input_listener(name: &str) {
+    INPUT_LISTENERS.lock().unwrap().retain(|(existing, _)| *existing != name);
+}
+
+/// Start the global hook once. It can't be stopped, listeners check their own enabled state.
+pub fn ensure_input_hook() {
+    if INPUT_HOOK_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    std::thread::spawn(|| {
+        info!("⌨️ Starting global input hook");
+        if let Err(e) = rdev::listen(dispatch_input_event) {
+            error!("❌ Global input hook failed: {:?}", e);
+            INPUT_HOOK_STARTED.store(false, Ordering::SeqCst);
+        }
+    });
+}
@@ -0,0 +1,310 @@
+use tauri::{AppHandle, Emitter, Url};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::io::Read;
+use std::sync::{Arc, LazyLock, Mutex};
+
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::desktop::{build_calendar_feed, is_calendar_feed_served, load_json_or_default, now_millis, queue_note_change, save_json, spawn_clip_article};
+
+const LOCAL_API_CONFIG_FILE: &str = "local_api.json";
+const DEFAULT_LOCAL_API_PORT: u16 = 43219;
+const MAX_BODY_BYTES: u64 = 5 * 1024 * 1024;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LocalApiConfig {
+    pub enabled: bool,
+    pub port: u16,
+    /// Clients send it as `Authorization: Bearer <token>` or `X-Blinko-Token`
+    pub token: String,
+}
+
+impl Default for LocalApiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: DEFAULT_LOCAL_API_PORT,
+            token: String::new(),
+        }
+    }
+}
+
+/// Body of `POST /capture`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CaptureRequest {
+    pub content: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// Body of `POST /clip`, sent by the web clipper extension
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ClipRequest {
+    pub url: String,
+    pub title: Option<String>,
+    /// Selected text, falls back to the page text
+    pub selection: Option<String>,
+    pub text: Option<String>,
+    pub html: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LocalApiEvent<T> {
+    #[serde(rename = "receivedAt")]
+    pub received_at: u64,
+    #[serde(flatten)]
+    pub payload: T,
+}
+
+static LOCAL_API_SERVER: LazyLock<Mutex<Option<Arc<Server>>>> = LazyLock::new(|| Mutex::new(None));
+
+/// Load local API config from file, creating a token on first use
+pub fn load_local_api_config(app: &AppHandle) -> Result<LocalApiConfig, String> {
+    let mut config: LocalApiConfig = load_json_or_default(app, LOCAL_API_CONFIG_FILE);
+    if !is_usable_token(&config.token) {
+        config.token = generate_api_token()?;
+        if let Err(e) = save_json(app, LOCAL_API_CONFIG_FILE, &config) {
+            error!("Failed to save local API config: {}", e);
+        }
+    }
+    Ok(config)
+}
+
+/// Random hex token for the localhost endpoints
+pub fn generate_api_token() -> Result<String, String> {
+    let mut bytes = [0u8; 24];
+    getrandom::getrandom(&mut bytes).map_err(|e| format!("Failed to generate random token: {}", e))?;
+    Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// Empty, or all zeros as written by versions that ignored a failed random source
+pub fn is_usable_token(token: &str) -> bool {
+    !token.is_empty() && token.bytes().any(|b| b != b'0')
+}
+
+/// Compare without an early exit, so response times don't reveal how much of a guess was right
+pub fn tokens_match(given: &str, expected: &str) -> bool {
+    given.len() == expected.len()
+        && given.bytes().zip(expected.bytes()).fold(0u8, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+fn header(name: &str, value: &str) -> Header {
+    Header::from_bytes(name.as_bytes(), value.as_bytes()).unwrap()
+}
+
+/// Browser extensions call from their own origin, so every response carries CORS headers
+fn respond(request: Request, status: u16, body: Value) {
+    let response = Response::from_string(body.to_string())
+        .with_status_code(status)
+        .with_header(header("Content-Type", "application/json"))
+        .with_header(header("Access-Control-Allow-Origin", "*"))
+        .with_header(header("Access-Control-Allow-Headers", "Authorization, Content-Type, X-Blinko-Token"))
+        .with_header(header("Access-Control-Allow-Methods", "GET, POST, OPTIONS"));
+    if let Err(e) = request.respond(response) {
+        error!("Failed to answer local API request: {}", e);
+    }
+}
+
+/// Calendar apps can't send headers, so the feed also accepts `?token=`
+fn respond_calendar_feed(app: &AppHandle, token: &str, request: Request) {
+    let query_token = Url::parse(&format!("http://127.0.0.1{}", request.url()))
+        .ok()
+        .and_then(|url| url.query_pairs().find(|(key, _)| key == "token").map(|(_, value)| value.to_string()));
+    if !query_token.is_some_and(|query_token| tokens_match(&query_token, token)) && !is_authorized(&request, token) {
+        return respond(request, 401, json!({ "error": "Invalid or missing token" }));
+    }
+    if !is_calendar_feed_served(app) {
+        return respond(request, 404, json!({ "error": "Calendar feed is disabled" }));
+    }
+
+    let response = Response::from_string(build_calendar_feed(app))
+        .with_header(header("Content-Type", "text/calendar; charset=utf-8"))
+        .with_header(header("Access-Control-Allow-Origin", "*"));
+    if let Err(e) = request.respond(response) {
+        error!("Failed to answer local API request: {}", e);
+    }
+}
+
+fn is_authorized(request: &Request, token: &str) -> bool {
+    request.headers().iter().any(|h| {
+        let value = h.value.as_str();
+        (h.field.equiv("Authorization") && value.strip_prefix("Bearer ").is_some_and(|given| tokens_match(given, token)))
+            || (h.field.equiv("X-Blinko-Token") && tokens_match(value, token))
+    })
+}
+
+fn read_json_body<T: for<'de> Deserialize<'de>>(request: &mut Request) -> Result<T, String> {
+    let mut body = String::new();
+    request.as_reader()
+        .take(MAX_BODY_BYTES)
+        .read_to_string(&mut body)
+        .map_err(|e| format!("Failed to read request body: {}", e))?;
+    serde_json::from_str(&body).map_err(|e| format!("Invalid JSON body: {}", e))
+}
+
+fn emit_local_api_event<T: Serialize + Clone>(app: &AppHandle, event: &str, payload: T) -> Result<(), String> {
+    app.emit(event, LocalApiEvent { received_at: now_millis(), payload })
+        .map_err(|e| format!("Failed to emit {} event: {}", event, e))
+}
+
+/// Tags go at the end as hashtags, the way Blinko notes carry them
+fn append_tags(content: &mut String, tags: &[String]) {
+    let tags: Vec<String> = tags.iter()
+        .map(|tag| tag.trim().trim_start_matches('#').replace(' ', "-"))
+        .filter(|tag| !tag.is_empty())
+        .map(|tag| format!("#{}", tag))
+        .collect();
+    if !tags.is_empty() {
+        content.push_str(&format!("\n\n{}", tags.join(" ")));
+    }
+}
+
+/// Queue the note in the offline store, so it is kept even before the next sync.
+/// Errors come with the HTTP status to answer with.
+fn queue_local_api_note(app: &AppHandle, content: String) -> Result<Option<String>, (u16, String)> {
+    queue_note_change(app.clone(), "create".to_string(), json!({ "content": content, "type": 0 }))
+        .map_err(|e| (500, e))
+}
+
+fn capture_note(app: &AppHandle, request: &mut Request) -> Result<Value, (u16, String)> {
+    let capture = read_json_body::<CaptureRequest>(request).map_err(|e| (400, e))?;
+    if capture.content.trim().is_empty() {
+        return Err((400, "content must not be empty".to_string()));
+    }
+    info!("🔌 Local API capture ({} chars)", capture.content.len());
+
+    let mut content = capture.content.trim().to_string();
+    append_tags(&mut content, &capture.tags);
+    let local_id = queue_local_api_note(app, content)?;
+    // Lets open windows show the note right away, it is queued either way
+    let _ = emit_local_api_event(app, "local-api-capture", capture);
+    Ok(json!({ "ok": true, "localId": local_id }))
+}
+
+fn clip_note(app: &AppHandle, request: &mut Request) -> Result<Value, (u16, String)> {
+    let clip = read_json_body::<ClipRequest>(request).map_err(|e| (400, e))?;
+    info!("🔌 Local API clip from {}", clip.url);
+
+    // A selection is kept as a quote, whole pages go through article extraction
+    let Some(selection) = clip.selection.as_ref().map(|s| s.trim().to_string()).filter(|s| !s.is_empty()) else {
+        spawn_clip_article(app, clip.url, clip.html);
+        return Ok(json!({ "ok": true }));
+    };
+
+    let quote: Vec<String> = selection.lines().map(|line| format!("> {}", line)).collect();
+    let title = clip.title.as_deref().map(str::trim).filter(|title| !title.is_empty()).unwrap_or(&clip.url);
+    let mut content = format!("{}\n\n[{}]({})", quote.join("\n"), title, clip.url);
+    append_tags(&mut content, &clip.tags);
+    let local_id = queue_local_api_note(app, content)?;
+    let _ = emit_local_api_event(app, "local-api-clip", clip);
+    Ok(json!({ "ok": true, "localId": local_id }))
+}
+
+fn handle_request(app: &AppHandle, token: &str, mut request: Request) {
+    let method = request.method().clone();
+    let path = request.url().split('?').next().unwrap_or_default().to_string();
+
+    if method == Method::Options {
+        return respond(request, 204, Value::Null);
+    }
+    if path == "/status" && method == Method::Get {
+        return respond(request, 200, json!({ "ok": true, "version": env!("CARGO_PKG_VERSION") }));
+    }
+    if path == "/calendar.ics" && method == Method::Get {
+        return respond_calendar_feed(app, token, request);
+    }
+    if !is_authorized(&request, token) {
+        return respond(request, 401, json!({ "error": "Invalid or missing token" }));
+    }
+
+    let result = match (method, path.as_str()) {
+        (Method::Post, "/capture") => capture_note(app, &mut request),
+        (Method::Post, "/clip") => clip_note(app, &mut request),
+        _ => return respond(request, 404, json!({ "error": format!("Unknown endpoint {}", path) })),
+    };
+
+    match result {
+        Ok(body) => respond(request, 202, body),
+        Err((status, e)) => {
+            error!("❌ Local API request to {} failed: {}", path, e);
+            respond(request, status, json!({ "error": e }))
+        }
+    }
+}
+
+/// Stop the embedded server if it is running
+pub fn stop_local_api() {
+    if let Some(server) = LOCAL_API_SERVER.lock().unwrap().take() {
+        server.unblock();
+        info!("🔌 Local API stopped");
+    }
+}
+
+/// Start the embedded server on localhost if enabled in config
+pub fn start_local_api(app: &AppHandle) {
+    stop_local_api();
+
+    let config = match load_local_api_config(app) {
+        Ok(config) => config,
+        Err(e) => {
+            error!("❌ Not starting local API without a token: {}", e);
+            return;
+        }
+    };
+    if !config.enabled {
+        return;
+    }
+
+    let server = match Server::http(("127.0.0.1", config.port)) {
+        Ok(server) => Arc::new(server),
+        Err(e) => {
+            error!("❌ Failed to start local API on port {}: {}", config.port, e);
+            return;
+        }
+    };
+    *LOCAL_API_SERVER.lock().unwrap() = Some(server.clone());
+    info!("🔌 Local API listening on http://127.0.0.1:{}", config.port);
+
+    let app_handle = app.clone();
+    std::thread::spawn(move || {
+        // Ends once `unblock` is called
+        for request in server.incoming_requests() {
+            handle_request(&app_handle, &config.token, request);
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_local_api_config(app: AppHandle) -> Result<LocalApiConfig, String> {
+    load_local_api_config(&app)
+}
+
+#[tauri::command]
+pub fn set_local_api_enabled(app: AppHandle, enabled: bool, port: Option<u16>) -> Result<LocalApiConfig, String> {
+    let mut config = load_local_api_config(&app)?;
+    config.enabled = enabled;
+    if let Some(port) = port {
+        config.port = port;
+    }
+    save_json(&app, LOCAL_API_CONFIG_FILE, &config)?;
+
+    if enabled {
+        start_local_api(&app);
+    } else {
+        stop_local_api();
+    }
+    Ok(config)
+}
+
+/// Replace the token, clients using the old one are rejected immediately
+#[tauri::command]
+pub fn rotate_local_api_token(app: AppHandle) -> Result<String, String> {
+    let mut config = load_local_api_config(&app)?;
+    config.token = generate_api_token()?;
+    save_json(&app, LOCAL_API_CONFIG_FILE, &config)?;
+    start_local_api(&app);
+    Ok(config.token)
+}
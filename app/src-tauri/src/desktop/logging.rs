@@ -0,0 +1,201 @@
+use tauri::AppHandle;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::{fmt, layer::SubscriberExt, reload, util::SubscriberInitExt, EnvFilter, Registry};
+
+use crate::desktop::{get_app_data_subdir, load_json_or_default, save_json};
+
+const LOGGING_CONFIG_FILE: &str = "logging.json";
+pub const LOGS_DIR: &str = "logs";
+const LOG_FILE_PREFIX: &str = "blinko.log";
+const MAX_LOG_FILES: usize = 7;
+const LEVELS: [&str; 5] = ["error", "warn", "info", "debug", "trace"];
+
+static LOG_DIR: OnceLock<PathBuf> = OnceLock::new();
+static FILTER_HANDLE: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+// Dropping the guard stops the background writer, so it lives for the whole process
+static WRITER_GUARD: OnceLock<WorkerGuard> = OnceLock::new();
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LoggingConfig {
+    /// Minimum level written for the app's own modules: error, warn, info, debug or trace
+    pub level: String,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self { level: "info".to_string() }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LogEntry {
+    pub timestamp: String,
+    pub level: String,
+    /// Module that wrote the entry
+    pub target: String,
+    pub message: String,
+}
+
+fn normalize_level(level: &str) -> Result<&'static str, String> {
+    let level = level.trim().to_lowercase();
+    LEVELS.iter()
+        .find(|l| **l == level)
+        .copied()
+        .ok_or_else(|| format!("Unknown log level: {}", level))
+}
+
+/// Dependencies stay at warn so debug logging doesn't drown in HTTP and index chatter
+fn build_filter(level: &str) -> EnvFilter {
+    EnvFilter::new(format!("warn,app_lib={}", level))
+}
+
+/// Install the global subscriber writing to daily rotated files in app data and to stdout
+pub fn init_logging(app: &AppHandle) -> Result<(), String> {
+    let config: LoggingConfig = load_json_or_default(app, LOGGING_CONFIG_FILE);
+    let level = normalize_level(&config.level).unwrap_or("info");
+    let dir = get_app_data_subdir(app, LOGS_DIR)?;
+
+    let appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(LOG_FILE_PREFIX)
+        .max_log_files(MAX_LOG_FILES)
+        .build(&dir)
+        .map_err(|e| format!("Failed to create log file: {}", e))?;
+    let (writer, guard) = tracing_appender::non_blocking(appender);
+
+    let (filter, handle) = reload::Layer::new(build_filter(level));
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt::layer().with_ansi(false).with_writer(writer))
+        .with(fmt::layer().with_writer(std::io::stdout))
+        .try_init()
+        .map_err(|e| format!("Failed to initialize logging: {}", e))?;
+
+    let _ = LOG_DIR.set(dir);
+    let _ = FILTER_HANDLE.set(handle);
+    let _ = WRITER_GUARD.set(guard);
+    tracing::info!("📝 Logging initialized at level {}", level);
+    Ok(())
+}
+
+/// Log files, newest first
+fn log_files() -> Vec<PathBuf> {
+    let Some(dir) = LOG_DIR.get() else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| entries.flatten()
+            .map(|e| e.path())
+            .filter(|p| p.file_name().is_some_and(|n| n.to_string_lossy().starts_with(LOG_FILE_PREFIX)))
+            .collect())
+        .unwrap_or_default();
+    // Rotated files carry the date as suffix, so name order is age order
+    files.sort();
+    files.reverse();
+    files
+}
+
+/// The last `limit` raw log lines, oldest first
+pub fn recent_log_lines(limit: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for file in log_files() {
+        let Ok(content) = fs::read_to_string(&file) else { continue };
+        for line in content.lines().rev() {
+            lines.push(line.to_string());
+            if lines.len() >= limit {
+                lines.reverse();
+                return lines;
+            }
+        }
+    }
+    lines.reverse();
+    lines
+}
+
+/// Parse `<timestamp> <LEVEL> <target>: <message>` as written by the fmt layer
+fn parse_log_line(line: &str) -> Option<LogEntry> {
+    let (timestamp, rest) = line.split_once(' ')?;
+    if !timestamp.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    // Levels are padded to equal width
+    let (level, rest) = rest.trim_start().split_once(' ')?;
+    if !LEVELS.contains(&level.to_lowercase().as_str()) {
+        return None;
+    }
+    let (target, message) = rest.trim_start().split_once(": ")?;
+
+    Some(LogEntry {
+        timestamp: timestamp.to_string(),
+        level: level.to_lowercase(),
+        target: target.to_string(),
+        message: message.to_string(),
+    })
+}
+
+/// Entries at or above `level`, newest first
+#[tauri::command]
+pub fn get_recent_logs(level: Option<String>, limit: Option<usize>) -> Result<Vec<LogEntry>, String> {
+    let max_level = match level {
+        Some(level) => normalize_level(&level)?,
+        None => "trace",
+    };
+    let max_rank = LEVELS.iter().position(|l| *l == max_level).unwrap_or(LEVELS.len() - 1);
+    let limit = limit.unwrap_or(500);
+
+    let mut entries = Vec::new();
+    for file in log_files() {
+        let Ok(content) = fs::read_to_string(&file) else { continue };
+        // Lines that don't parse continue a multi-line message above them
+        let mut continuation: Vec<&str> = Vec::new();
+        for line in content.lines().rev() {
+            let Some(mut entry) = parse_log_line(line) else {
+                continuation.push(line);
+                continue;
+            };
+            if !continuation.is_empty() {
+                continuation.reverse();
+                entry.message = format!("{}\n{}", entry.message, continuation.join("\n"));
+                continuation.clear();
+            }
+            if LEVELS.iter().position(|l| *l == entry.level).is_some_and(|rank| rank <= max_rank) {
+                entries.push(entry);
+                if entries.len() >= limit {
+                    return Ok(entries);
+                }
+            }
+        }
+    }
+
+    Ok(entries)
+}
+
+#[tauri::command]
+pub fn get_log_level(app: AppHandle) -> String {
+    let config: LoggingConfig = load_json_or_default(&app, LOGGING_CONFIG_FILE);
+    config.level
+}
+
+/// Change the level immediately and remember it for the next launch
+#[tauri::command]
+pub fn set_log_level(app: AppHandle, level: String) -> Result<(), String> {
+    let level = normalize_level(&level)?;
+    if let Some(handle) = FILTER_HANDLE.get() {
+        handle.reload(build_filter(level))
+            .map_err(|e| format!("Failed to change log level: {}", e))?;
+    }
+    save_json(&app, LOGGING_CONFIG_FILE, &LoggingConfig { level: level.to_string() })?;
+    tracing::info!("📝 Log level set to {}", level);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_log_directory(app: AppHandle) -> Result<String, String> {
+    Ok(get_app_data_subdir(&app, LOGS_DIR)?.to_string_lossy().to_string())
+}
@@ -0,0 +1,457 @@
+use tauri::{AppHandle, Emitter};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::Duration;
+
+use mail_parser::{MessageParser, MimeHeaders};
+use rustls::pki_types::ServerName;
+
+use crate::desktop::{
+    delete_secret, generate_id, http_client_builder, import_bytes, is_background_sync_allowed, load_json_or_default, now_millis,
+    queue_note_change, read_secret, save_json, store_secret, ImportedFile,
+};
+use crate::importer::sanitize_file_name;
+
+const MAIL_CONFIG_FILE: &str = "mail.json";
+const MAIL_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+const MAIL_TIMEOUT: Duration = Duration::from_secs(30);
+const DEFAULT_MAIL_INTERVAL_MINUTES: u64 = 5;
+// Catch up on a full mailbox over several polls instead of one long session
+const MAX_MESSAGES_PER_POLL: usize = 50;
+const MAX_ATTACHMENT_SIZE: usize = 25 * 1024 * 1024;
+
+type TlsStream = rustls::StreamOwned<rustls::ClientConnection, TcpStream>;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum MailAuth {
+    /// Account or app password
+    #[default]
+    Password,
+    /// XOAUTH2 with an access token refreshed from the stored refresh token
+    OAuth2,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MailAccount {
+    pub id: String,
+    pub label: String,
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+    /// Folder or Gmail label to watch, e.g. `INBOX` or `Blinko`
+    pub mailbox: String,
+    #[serde(default)]
+    pub auth: MailAuth,
+    /// Token endpoint and client id for OAuth, the refresh token itself is kept in the keychain
+    #[serde(rename = "oauthTokenUrl")]
+    pub oauth_token_url: Option<String>,
+    #[serde(rename = "oauthClientId")]
+    pub oauth_client_id: Option<String>,
+    /// Added to every note from this account, without the leading #
+    pub tag: Option<String>,
+    /// Flag ingested emails as read on the server
+    #[serde(rename = "markSeen")]
+    pub mark_seen: bool,
+    #[serde(rename = "saveAttachments")]
+    pub save_attachments: bool,
+    pub enabled: bool,
+    #[serde(rename = "uidValidity")]
+    pub uid_validity: Option<u32>,
+    /// Highest UID already turned into a note, `None` until the first poll
+    #[serde(rename = "lastUid")]
+    pub last_uid: Option<u32>,
+    #[serde(rename = "lastCheckedAt")]
+    pub last_checked_at: Option<u64>,
+    #[serde(rename = "lastError")]
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MailConfig {
+    #[serde(rename = "intervalMinutes")]
+    pub interval_minutes: u64,
+    pub accounts: Vec<MailAccount>,
+}
+
+impl Default for MailConfig {
+    fn default() -> Self {
+        Self {
+            interval_minutes: DEFAULT_MAIL_INTERVAL_MINUTES,
+            accounts: Vec::new(),
+        }
+    }
+}
+
+/// Payload of `mail-note-created`, attachments still have to be uploaded with the note
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MailNote {
+    #[serde(rename = "accountId")]
+    pub account_id: String,
+    pub subject: String,
+    pub from: Option<String>,
+    #[serde(rename = "localId")]
+    pub local_id: Option<String>,
+    pub attachments: Vec<ImportedFile>,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    refresh_token: Option<String>,
+}
+
+struct XOAuth2 {
+    user: String,
+    access_token: String,
+}
+
+impl imap::Authenticator for XOAuth2 {
+    type Response = String;
+
+    fn process(&self, _challenge: &[u8]) -> Self::Response {
+        format!("user={}\x01auth=Bearer {}\x01\x01", self.user, self.access_token)
+    }
+}
+
+static MAIL_CONFIG: LazyLock<Mutex<Option<MailConfig>>> = LazyLock::new(|| Mutex::new(None));
+static MAIL_POLLER_STARTED: AtomicBool = AtomicBool::new(false);
+// Keeps a manual check and the poller from ingesting the same emails twice
+static MAIL_POLL_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+fn with_mail_config<T>(app: &AppHandle, f: impl FnOnce(&mut MailConfig) -> T) -> T {
+    let mut guard = MAIL_CONFIG.lock().unwrap();
+    f(guard.get_or_insert_with(|| load_json_or_default(app, MAIL_CONFIG_FILE)))
+}
+
+fn persist_mail_config(app: &AppHandle) -> Result<(), String> {
+    let config = with_mail_config(app, |config| config.clone());
+    save_json(app, MAIL_CONFIG_FILE, &config)
+}
+
+fn keychain_account(account_id: &str) -> String {
+    format!("imap:{}", account_id)
+}
+
+fn connect_tls(host: &str, port: u16) -> Result<TlsStream, String> {
+    let mut roots = rustls::RootCertStore::empty();
+    roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+    let config = rustls::ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+        .with_safe_default_protocol_versions()
+        .map_err(|e| format!("Failed to set up TLS: {}", e))?
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    let server_name = ServerName::try_from(host.to_string()).map_err(|_| format!("Invalid mail server name: {}", host))?;
+    let connection = rustls::ClientConnection::new(Arc::new(config), server_name)
+        .map_err(|e| format!("Failed to set up TLS: {}", e))?;
+
+    let address = (host, port).to_socket_addrs()
+        .map_err(|e| format!("Failed to resolve {}: {}", host, e))?
+        .next()
+        .ok_or_else(|| format!("Failed to resolve {}", host))?;
+    let tcp = TcpStream::connect_timeout(&address, MAIL_TIMEOUT)
+        .map_err(|e| format!("Failed to connect to {}:{}: {}", host, port, e))?;
+    let _ = tcp.set_read_timeout(Some(MAIL_TIMEOUT));
+    let _ = tcp.set_write_timeout(Some(MAIL_TIMEOUT));
+    Ok(rustls::StreamOwned::new(connection, tcp))
+}
+
+/// Trade the stored refresh token for a fresh access token, keeping a rotated refresh token
+fn refresh_access_token(account: &MailAccount, refresh_token: &str) -> Result<String, String> {
+    let token_url = account.oauth_token_url.as_deref().filter(|url| !url.is_empty()).ok_or("OAuth token URL is missing")?;
+    let client_id = account.oauth_client_id.as_deref().filter(|id| !id.is_empty()).ok_or("OAuth client id is missing")?;
+    let response = http_client_builder()
+        .timeout(MAIL_TIMEOUT)
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?
+        .post(token_url)
+        .form(&[("grant_type", "refresh_token"), ("refresh_token", refresh_token), ("client_id", client_id)])
+        .send()
+        .map_err(|e| format!("OAuth token refresh failed: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("OAuth token refresh failed: HTTP {}", response.status()));
+    }
+    let tokens: TokenResponse = response.json().map_err(|e| format!("Invalid OAuth token response: {}", e))?;
+    if let Some(rotated) = tokens.refresh_token.filter(|rotated| rotated != refresh_token) {
+        store_secret(Some(&keychain_account(&account.id)), &rotated)?;
+    }
+    Ok(tokens.access_token)
+}
+
+fn open_session(account: &MailAccount, secret: &str) -> Result<imap::Session<TlsStream>, String> {
+    let mut client = imap::Client::new(connect_tls(&account.host, account.port)?);
+    client.read_greeting().map_err(|e| format!("Mail server did not answer: {}", e))?;
+    match account.auth {
+        MailAuth::Password => client.login(&account.username, secret)
+            .map_err(|(e, _)| format!("Mail login failed: {}", e)),
+        MailAuth::OAuth2 => {
+            let authenticator = XOAuth2 { user: account.username.clone(), access_token: refresh_access_token(account, secret)? };
+            client.authenticate("XOAUTH2", &authenticator)
+                .map_err(|(e, _)| format!("Mail login failed: {}", e))
+        }
+    }
+}
+
+fn format_sender(message: &mail_parser::Message) -> Option<String> {
+    let sender = message.from()?.first()?;
+    match (sender.name(), sender.address()) {
+        (Some(name), Some(address)) => Some(format!("{} <{}>", name, address)),
+        (name, address) => name.or(address).map(|sender| sender.to_string()),
+    }
+}
+
+/// Create a note from one raw email, `None` if it could not be parsed
+fn email_to_note(app: &AppHandle, account: &MailAccount, raw: &[u8]) -> Result<Option<MailNote>, String> {
+    let Some(message) = MessageParser::default().parse(raw) else {
+        return Ok(None);
+    };
+    let subject = message.subject().map(str::trim).filter(|subject| !subject.is_empty()).unwrap_or("(no subject)").to_string();
+    let from = format_sender(&message);
+    let date = message.date()
+        .and_then(|date| chrono::DateTime::from_timestamp(date.to_timestamp(), 0))
+        .map(|date| date.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string());
+    // HTML mails convert to richer markdown, plain ones come back as their text
+    let body = message.body_html(0).map(|html| html2md::parse_html(&html))
+        .or_else(|| message.body_text(0).map(|text| text.to_string()))
+        .unwrap_or_default();
+
+    let mut attachments = Vec::new();
+    if account.save_attachments {
+        let source = format!("imap://{}/{}", account.host, account.mailbox);
+        for part in message.attachments() {
+            let contents = part.contents();
+            if contents.is_empty() || contents.len() > MAX_ATTACHMENT_SIZE {
+                continue;
+            }
+            let name = sanitize_file_name(part.attachment_name().unwrap_or("attachment"));
+            match import_bytes(app, &name, contents, &source) {
+                Ok(file) => attachments.push(file),
+                Err(e) => warn!("⚠️ Failed to save mail attachment {}: {}", name, e),
+            }
+        }
+    }
+
+    let mut content = format!("## {}\n\n", subject);
+    let meta: Vec<String> = [from.clone(), date].into_iter().flatten().collect();
+    if !meta.is_empty() {
+        content.push_str(&format!("> From: {}\n\n", meta.join(" · ")));
+    }
+    content.push_str(body.trim());
+    if !attachments.is_empty() {
+        let names: Vec<&str> = attachments.iter().map(|file| file.name.as_str()).collect();
+        content.push_str(&format!("\n\n📎 {}", names.join(", ")));
+    }
+    if let Some(tag) = account.tag.as_ref().map(|tag| tag.trim().trim_start_matches('#').replace(' ', "-")).filter(|tag| !tag.is_empty()) {
+        content.push_str(&format!("\n\n#{}", tag));
+    }
+
+    let local_id = queue_note_change(app.clone(), "create".to_string(), json!({ "content": content, "type": 0 }))?;
+    Ok(Some(MailNote { account_id: account.id.clone(), subject, from, local_id, attachments }))
+}
+
+/// Turn emails that arrived since the last poll into notes, returns how many were created
+fn ingest_new_messages(app: &AppHandle, session: &mut imap::Session<TlsStream>, account: &mut MailAccount) -> Result<usize, String> {
+    let mailbox = session.select(&account.mailbox).map_err(|e| format!("Failed to open {}: {}", account.mailbox, e))?;
+
+    // A new account (or a rebuilt mailbox with another UIDVALIDITY) starts with what arrives from now on
+    if account.last_uid.is_none() || account.uid_validity != mailbox.uid_validity {
+        let newest = match mailbox.uid_next {
+            Some(next) => next.saturating_sub(1),
+            None => session.uid_search("ALL").map_err(|e| format!("Mail search failed: {}", e))?.into_iter().max().unwrap_or(0),
+        };
+        account.uid_validity = mailbox.uid_validity;
+        account.last_uid = Some(newest);
+        info!("📧 Watching {} on {} for new emails", account.mailbox, account.host);
+        return Ok(0);
+    }
+
+    let last_uid = account.last_uid.unwrap_or(0);
+    // `n:*` always matches the newest message, even when it is older than n
+    let mut uids: Vec<u32> = session.uid_search(format!("UID {}:*", last_uid.saturating_add(1)))
+        .map_err(|e| format!("Mail search failed: {}", e))?
+        .into_iter()
+        .filter(|uid| *uid > last_uid)
+        .collect();
+    uids.sort_unstable();
+    uids.truncate(MAX_MESSAGES_PER_POLL);
+
+    let mut created = 0;
+    for uid in uids {
+        let fetches = session.uid_fetch(uid.to_string(), "BODY.PEEK[]").map_err(|e| format!("Failed to fetch email: {}", e))?;
+        if let Some(raw) = fetches.iter().find_map(|fetch| fetch.body()) {
+            match email_to_note(app, account, raw)? {
+                Some(note) => {
+                    created += 1;
+                    let _ = app.emit("mail-note-created", &note);
+                }
+                None => warn!("⚠️ Skipping email {} in {}, it could not be parsed", uid, account.mailbox),
+            }
+        }
+        if account.mark_seen {
+            if let Err(e) = session.uid_store(uid.to_string(), "+FLAGS (\\Seen)") {
+                warn!("⚠️ Failed to mark email as read: {}", e);
+            }
+        }
+        account.last_uid = Some(uid);
+    }
+    Ok(created)
+}
+
+fn poll_account(app: &AppHandle, account: &mut MailAccount) -> Result<usize, String> {
+    let secret = read_secret(Some(&keychain_account(&account.id)))?.ok_or("No credentials stored for this mail account")?;
+    let mut session = open_session(account, &secret)?;
+    let result = ingest_new_messages(app, &mut session, account);
+    let _ = session.logout();
+    result
+}
+
+/// Poll accounts that are due, or all enabled accounts when `force` is set
+fn poll_mail_accounts(app: &AppHandle, force: bool) -> usize {
+    let _guard = MAIL_POLL_LOCK.lock().unwrap();
+    let (interval, accounts) = with_mail_config(app, |config| (config.interval_minutes.max(1) * 60 * 1000, config.accounts.clone()));
+
+    let mut total = 0;
+    for mut account in accounts {
+        let due = account.last_checked_at.is_none_or(|last| now_millis().saturating_sub(last) >= interval);
+        if !account.enabled || !(force || due) {
+            continue;
+        }
+        match poll_account(app, &mut account) {
+            Ok(count) => {
+                if count > 0 {
+                    info!("📧 {} email(s) from {} saved as notes", count, account.label);
+                }
+                account.last_error = None;
+                total += count;
+            }
+            Err(e) => {
+                warn!("⚠️ Mail account {} failed: {}", account.label, e);
+                account.last_error = Some(e);
+            }
+        }
+        account.last_checked_at = Some(now_millis());
+
+        // Edits made while polling win, only the poll bookkeeping is written back
+        with_mail_config(app, |config| {
+            if let Some(stored) = config.accounts.iter_mut().find(|stored| stored.id == account.id) {
+                stored.uid_validity = account.uid_validity;
+                stored.last_uid = account.last_uid;
+                stored.last_checked_at = account.last_checked_at;
+                stored.last_error = account.last_error.clone();
+            }
+        });
+    }
+
+    if let Err(e) = persist_mail_config(app) {
+        error!("Failed to save mail accounts: {}", e);
+    }
+    total
+}
+
+/// Poll enabled mail accounts on their schedule (runs for the app lifetime)
+pub fn start_mail_poller(app: &AppHandle) {
+    if MAIL_POLLER_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let app_handle = app.clone();
+    std::thread::spawn(move || loop {
+        std::thread::sleep(MAIL_CHECK_INTERVAL);
+        let any_enabled = with_mail_config(&app_handle, |config| config.accounts.iter().any(|account| account.enabled));
+        if any_enabled && is_background_sync_allowed() {
+            poll_mail_accounts(&app_handle, false);
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_mail_config(app: AppHandle) -> MailConfig {
+    with_mail_config(&app, |config| config.clone())
+}
+
+#[tauri::command]
+pub fn save_mail_settings(app: AppHandle, interval_minutes: u64) -> Result<(), String> {
+    with_mail_config(&app, |config| config.interval_minutes = interval_minutes.max(1));
+    persist_mail_config(&app)
+}
+
+/// Add or update an account after logging in and opening its mailbox.
+/// `secret` is the password or OAuth refresh token and goes to the keychain, omit it to keep the stored one.
+#[tauri::command]
+pub async fn save_mail_account(app: AppHandle, account: MailAccount, secret: Option<String>) -> Result<MailAccount, String> {
+    let mut account = account;
+    account.host = account.host.trim().to_string();
+    account.mailbox = account.mailbox.trim().to_string();
+    if account.host.is_empty() || account.username.trim().is_empty() {
+        return Err("Mail server and username are required".to_string());
+    }
+    if account.mailbox.is_empty() {
+        account.mailbox = "INBOX".to_string();
+    }
+    if account.id.is_empty() {
+        account.id = generate_id();
+    }
+    if account.label.trim().is_empty() {
+        account.label = account.username.clone();
+    }
+
+    let previous = with_mail_config(&app, |config| config.accounts.iter().find(|stored| stored.id == account.id).cloned());
+    // Another server or mailbox starts again from its newest email
+    let same_mailbox = previous.as_ref().is_some_and(|previous| {
+        previous.host == account.host && previous.username == account.username && previous.mailbox == account.mailbox
+    });
+    account.uid_validity = previous.as_ref().filter(|_| same_mailbox).and_then(|previous| previous.uid_validity);
+    account.last_uid = previous.as_ref().filter(|_| same_mailbox).and_then(|previous| previous.last_uid);
+    account.last_checked_at = None;
+    account.last_error = None;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let secret = match secret.filter(|secret| !secret.is_empty()) {
+            Some(secret) => secret,
+            None => read_secret(Some(&keychain_account(&account.id)))?.ok_or("Password or token is required")?,
+        };
+        let mut session = open_session(&account, &secret)?;
+        let checked = session.examine(&account.mailbox).map(|_| ()).map_err(|e| format!("Mailbox {} not found: {}", account.mailbox, e));
+        let _ = session.logout();
+        checked?;
+
+        store_secret(Some(&keychain_account(&account.id)), &secret)?;
+        with_mail_config(&app, |config| match config.accounts.iter_mut().find(|stored| stored.id == account.id) {
+            Some(stored) => *stored = account.clone(),
+            None => config.accounts.push(account.clone()),
+        });
+        persist_mail_config(&app)?;
+        info!("📧 Mail account saved for {} ({})", account.label, account.mailbox);
+        Ok(account)
+    })
+    .await
+    .map_err(|e| format!("Mail account setup failed: {}", e))?
+}
+
+#[tauri::command]
+pub fn set_mail_account_enabled(app: AppHandle, id: String, enabled: bool) -> Result<(), String> {
+    with_mail_config(&app, |config| {
+        let account = config.accounts.iter_mut().find(|account| account.id == id).ok_or("Mail account not found")?;
+        account.enabled = enabled;
+        Ok::<_, String>(())
+    })?;
+    persist_mail_config(&app)
+}
+
+#[tauri::command]
+pub fn remove_mail_account(app: AppHandle, id: String) -> Result<(), String> {
+    delete_secret(Some(&keychain_account(&id)))?;
+    with_mail_config(&app, |config| config.accounts.retain(|account| account.id != id));
+    persist_mail_config(&app)
+}
+
+/// Check every enabled account now, returns the number of notes created
+#[tauri::command]
+pub async fn check_mail_now(app: AppHandle) -> Result<usize, String> {
+    tauri::async_runtime::spawn_blocking(move || poll_mail_accounts(&app, true))
+        .await
+        .map_err(|e| format!("Mail check failed: {}", e))
+}
@@ -0,0 +1,408 @@
+use tauri::{AppHandle, Emitter};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::channel;
+use std::sync::{LazyLock, Mutex};
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::desktop::{commit_note_history, load_json_or_default, now_millis, save_json};
+
+const MARKDOWN_SYNC_CONFIG_FILE: &str = "markdown_sync.json";
+const MARKDOWN_SYNC_INDEX_FILE: &str = "markdown_sync_index.json";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct MarkdownSyncConfig {
+    pub enabled: bool,
+    /// Folder the notes are mirrored to
+    pub folder: String,
+}
+
+/// A note as exchanged between the frontend and the mirror folder
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SyncNote {
+    /// Server note id, `None` for files created locally
+    pub id: Option<i64>,
+    pub content: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(rename = "createdAt", default)]
+    pub created_at: Option<String>,
+    #[serde(rename = "updatedAt", default)]
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct IndexEntry {
+    #[serde(rename = "fileName")]
+    file_name: String,
+    /// Hash of the file as last written or imported by the sync engine
+    #[serde(rename = "fileHash")]
+    file_hash: String,
+    #[serde(rename = "updatedAt")]
+    updated_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+struct SyncIndex {
+    notes: HashMap<i64, IndexEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MarkdownChangeEvent {
+    pub note: SyncNote,
+    pub path: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MarkdownConflictEvent {
+    #[serde(rename = "noteId")]
+    pub note_id: i64,
+    pub path: String,
+    #[serde(rename = "conflictPath")]
+    pub conflict_path: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct MarkdownSyncReport {
+    pub written: usize,
+    pub unchanged: usize,
+    #[serde(rename = "localChanges")]
+    pub local_changes: usize,
+    pub conflicts: usize,
+}
+
+static SYNC_WATCHER: LazyLock<Mutex<Option<RecommendedWatcher>>> = LazyLock::new(|| Mutex::new(None));
+// Serializes folder writes and watcher handling so we never react to our own writes
+static SYNC_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+/// Load markdown sync config from file
+pub fn load_markdown_sync_config(app: &AppHandle) -> MarkdownSyncConfig {
+    load_json_or_default(app, MARKDOWN_SYNC_CONFIG_FILE)
+}
+
+fn load_index(app: &AppHandle) -> SyncIndex {
+    load_json_or_default(app, MARKDOWN_SYNC_INDEX_FILE)
+}
+
+fn save_index(app: &AppHandle, index: &SyncIndex) {
+    if let Err(e) = save_json(app, MARKDOWN_SYNC_INDEX_FILE, index) {
+        error!("Failed to save markdown sync index: {}", e);
+    }
+}
+
+fn hash_text(text: &str) -> String {
+    Sha256::digest(text.as_bytes()).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Build a file name like `42-first-words-of-note.md`
+fn note_file_name(id: i64, content: &str) -> String {
+    let first_line = content.lines()
+        .map(|l| l.trim_start_matches('#').trim())
+        .find(|l| !l.is_empty())
+        .unwrap_or("note");
+
+    let slug: String = first_line.chars()
+        .map(|c| if c.is_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+        .collect::<String>()
+        .split('-')
+        .filter(|part| !part.is_empty())
+        .take(8)
+        .collect::<Vec<_>>()
+        .join("-");
+
+    if slug.is_empty() {
+        format!("{}.md", id)
+    } else {
+        format!("{}-{}.md", id, slug.chars().take(60).collect::<String>())
+    }
+}
+
+/// Render a note with YAML-style front matter
+pub fn render_markdown_note(note: &SyncNote) -> String {
+    let mut out = String::from("---\n");
+    if let Some(id) = note.id {
+        out.push_str(&format!("id: {}\n", id));
+    }
+    if !note.tags.is_empty() {
+        out.push_str(&format!("tags: [{}]\n", note.tags.join(", ")));
+    }
+    if let Some(ref created) = note.created_at {
+        out.push_str(&format!("created: {}\n", created));
+    }
+    if let Some(ref updated) = note.updated_at {
+        out.push_str(&format!("updated: {}\n", updated));
+    }
+    out.push_str("---\n\n");
+    out.push_str(&note.content);
+    if !note.content.ends_with('\n') {
+        out.push('\n');
+    }
+    out
+}
+
+/// Parse front matter and body back into a note
+pub fn parse_markdown_note(text: &str) -> SyncNote {
+    let mut note = SyncNote {
+        id: None,
+        content: text.to_string(),
+        tags: Vec::new(),
+        created_at: None,
+        updated_at: None,
+    };
+
+    let normalized = text.replace("\r\n", "\n");
+    let Some(rest) = normalized.strip_prefix("---\n") else {
+        return note;
+    };
+    let Some(end) = rest.find("\n---") else {
+        return note;
+    };
+
+    for line in rest[..end].lines() {
+        let Some((key, value)) = line.split_once(':') else { continue };
+        let value = value.trim();
+        match key.trim() {
+            "id" => note.id = value.parse().ok(),
+            "tags" => {
+                note.tags = value.trim_matches(|c| c == '[' || c == ']')
+                    .split(',')
+                    .map(|t| t.trim().trim_matches('"').to_string())
+                    .filter(|t| !t.is_empty())
+                    .collect();
+            }
+            "created" => note.created_at = Some(value.to_string()),
+            "updated" => note.updated_at = Some(value.to_string()),
+            _ => {}
+        }
+    }
+
+    let body = &rest[end + 4..];
+    note.content = body.trim_start_matches('\n').trim_end().to_string();
+    note
+}
+
+/// File a synced note is mirrored to, if it has been written yet
+pub fn markdown_note_file(app: &AppHandle, id: i64) -> Option<PathBuf> {
+    let config = load_markdown_sync_config(app);
+    let entry = load_index(app).notes.remove(&id)?;
+    Some(PathBuf::from(config.folder).join(entry.file_name))
+}
+
+fn sync_folder(config: &MarkdownSyncConfig) -> Result<PathBuf, String> {
+    if !config.enabled || config.folder.trim().is_empty() {
+        return Err("Markdown folder sync is not enabled".to_string());
+    }
+    let folder = PathBuf::from(&config.folder);
+    fs::create_dir_all(&folder)
+        .map_err(|e| format!("Failed to create sync folder: {}", e))?;
+    Ok(folder)
+}
+
+/// Write notes from the server into the folder, keeping local edits and creating conflict copies
+pub fn write_notes_to_folder(app: &AppHandle, notes: &[SyncNote]) -> Result<MarkdownSyncReport, String> {
+    let config = load_markdown_sync_config(app);
+    let folder = sync_folder(&config)?;
+    let _guard = SYNC_LOCK.lock().unwrap();
+    let mut index = load_index(app);
+    let mut report = MarkdownSyncReport::default();
+
+    for note in notes {
+        let Some(id) = note.id else { continue };
+        let rendered = render_markdown_note(note);
+        let rendered_hash = hash_text(&rendered);
+
+        let entry = index.notes.get(&id).cloned();
+        let path = match entry {
+            Some(ref entry) => folder.join(&entry.file_name),
+            None => folder.join(note_file_name(id, &note.content)),
+        };
+
+        let current = fs::read_to_string(&path).ok();
+        let current_hash = current.as_deref().map(hash_text);
+
+        if current_hash.as_deref() == Some(rendered_hash.as_str()) {
+            report.unchanged += 1;
+        } else {
+            let locally_edited = match (&entry, &current_hash) {
+                (Some(entry), Some(hash)) => *hash != entry.file_hash,
+                _ => false,
+            };
+            let server_changed = entry.as_ref()
+                .map(|e| e.updated_at != note.updated_at)
+                .unwrap_or(true);
+
+            if locally_edited && !server_changed {
+                // Local edit wins, the watcher already reported it
+                report.local_changes += 1;
+                continue;
+            }
+
+            if locally_edited && server_changed {
+                let stem = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+                let conflict_path = folder.join(format!("{}.conflict-{}.md", stem, now_millis()));
+                if let Some(ref local) = current {
+                    fs::write(&conflict_path, local)
+                        .map_err(|e| format!("Failed to write conflict copy: {}", e))?;
+                }
+                report.conflicts += 1;
+                let _ = app.emit("markdown-sync-conflict", MarkdownConflictEvent {
+                    note_id: id,
+                    path: path.to_string_lossy().to_string(),
+                    conflict_path: conflict_path.to_string_lossy().to_string(),
+                });
+            }
+
+            fs::write(&path, &rendered)
+                .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+            report.written += 1;
+        }
+
+        index.notes.insert(id, IndexEntry {
+            file_name: path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default(),
+            file_hash: rendered_hash,
+            updated_at: note.updated_at.clone(),
+        });
+    }
+
+    save_index(app, &index);
+    if report.written > 0 {
+        commit_note_history(app, &format!("Sync {} note(s) from server", report.written));
+    }
+    Ok(report)
+}
+
+/// React to a file edited or created in the mirror folder
+fn handle_folder_change(app: &AppHandle, path: &Path) {
+    if path.extension().map(|e| e != "md").unwrap_or(true) {
+        return;
+    }
+    let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    if name.starts_with('.') || name.contains(".conflict-") {
+        return;
+    }
+
+    let _guard = SYNC_LOCK.lock().unwrap();
+    let Ok(text) = fs::read_to_string(path) else { return };
+    let file_hash = hash_text(&text);
+    let mut index = load_index(app);
+    let note = parse_markdown_note(&text);
+
+    let event_name = match note.id {
+        Some(id) => {
+            if index.notes.get(&id).map(|e| e.file_hash == file_hash).unwrap_or(false) {
+                // Our own write or no real change
+                return;
+            }
+            if let Some(entry) = index.notes.get_mut(&id) {
+                entry.file_hash = file_hash;
+                entry.file_name = name;
+            }
+            "markdown-note-changed"
+        }
+        None => "markdown-note-created",
+    };
+
+    save_index(app, &index);
+    info!("📝 Markdown sync detected {} for {}", event_name, path.display());
+
+    let event = MarkdownChangeEvent {
+        note,
+        path: path.to_string_lossy().to_string(),
+    };
+    if let Err(e) = app.emit(event_name, &event) {
+        error!("Failed to emit {} event: {}", event_name, e);
+    }
+    commit_note_history(app, &format!("Local edit of {}", event.path.rsplit(['/', '\\']).next().unwrap_or_default()));
+}
+
+/// (Re)start watching the mirror folder
+pub fn restart_markdown_sync_watcher(app: &AppHandle) {
+    let mut active = SYNC_WATCHER.lock().unwrap();
+    *active = None;
+
+    let config = load_markdown_sync_config(app);
+    let folder = match sync_folder(&config) {
+        Ok(folder) => folder,
+        Err(_) => return,
+    };
+
+    let (tx, rx) = channel::<notify::Result<notify::Event>>();
+    let mut watcher = match notify::recommended_watcher(tx) {
+        Ok(watcher) => watcher,
+        Err(e) => {
+            error!("❌ Failed to create markdown sync watcher: {}", e);
+            return;
+        }
+    };
+
+    if let Err(e) = watcher.watch(&folder, RecursiveMode::NonRecursive) {
+        error!("❌ Failed to watch markdown sync folder {}: {}", folder.display(), e);
+        return;
+    }
+    *active = Some(watcher);
+    info!("📝 Markdown sync watching: {}", folder.display());
+
+    let app_handle = app.clone();
+    std::thread::spawn(move || {
+        while let Ok(result) = rx.recv() {
+            if let Ok(event) = result {
+                if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+                    for path in event.paths {
+                        handle_folder_change(&app_handle, &path);
+                    }
+                }
+            }
+        }
+    });
+}
+
+/// Record the server id for a note created from a local file, so later edits map to it
+#[tauri::command]
+pub fn link_markdown_note(app: AppHandle, path: String, note: SyncNote) -> Result<(), String> {
+    let id = note.id.ok_or("Note id is required")?;
+    let _guard = SYNC_LOCK.lock().unwrap();
+
+    let rendered = render_markdown_note(&note);
+    fs::write(&path, &rendered)
+        .map_err(|e| format!("Failed to write {}: {}", path, e))?;
+
+    let mut index = load_index(&app);
+    index.notes.insert(id, IndexEntry {
+        file_name: Path::new(&path).file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default(),
+        file_hash: hash_text(&rendered),
+        updated_at: note.updated_at.clone(),
+    });
+    save_index(&app, &index);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_markdown_sync_config(app: AppHandle) -> MarkdownSyncConfig {
+    load_markdown_sync_config(&app)
+}
+
+#[tauri::command]
+pub async fn save_markdown_sync_config(app: AppHandle, config: MarkdownSyncConfig) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        if config.enabled && config.folder.trim().is_empty() {
+            return Err("Please choose a folder for markdown sync".to_string());
+        }
+        save_json(&app, MARKDOWN_SYNC_CONFIG_FILE, &config)?;
+        restart_markdown_sync_watcher(&app);
+        Ok(())
+    })
+    .await
+    .map_err(|e| format!("Failed to save markdown sync settings: {}", e))?
+}
+
+/// Mirror notes fetched from the server into the folder
+#[tauri::command]
+pub async fn sync_notes_to_folder(app: AppHandle, notes: Vec<SyncNote>) -> Result<MarkdownSyncReport, String> {
+    tauri::async_runtime::spawn_blocking(move || write_notes_to_folder(&app, &notes))
+        .await
+        .map_err(|e| format!("Markdown sync task failed: {}", e))?
+}
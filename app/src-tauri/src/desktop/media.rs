@@ -0,0 +1,255 @@
+use tauri::{AppHandle, Emitter};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::{LazyLock, Mutex};
+
+use crate::desktop::{load_json_or_default, save_json};
+
+const MEDIA_KEYS_FILE: &str = "media_keys.json";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MediaKeysConfig {
+    pub enabled: bool,
+    /// Let the pause key end a running dictation
+    #[serde(rename = "pauseDictation")]
+    pub pause_dictation: bool,
+}
+
+impl Default for MediaKeysConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            pause_dictation: true,
+        }
+    }
+}
+
+/// What the system media overlay (MPRIS, SMTC) should show for Blinko
+#[derive(Debug, Clone, PartialEq)]
+pub enum MediaSessionState {
+    Playing(String),
+    Paused(String),
+    Stopped,
+}
+
+static MEDIA_CONFIG: LazyLock<Mutex<Option<MediaKeysConfig>>> = LazyLock::new(|| Mutex::new(None));
+static MEDIA_SESSION: LazyLock<Mutex<Option<Sender<MediaSessionState>>>> = LazyLock::new(|| Mutex::new(None));
+static MEDIA_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+pub fn load_media_keys_config(app: &AppHandle) -> MediaKeysConfig {
+    MEDIA_CONFIG.lock().unwrap()
+        .get_or_insert_with(|| load_json_or_default(app, MEDIA_KEYS_FILE))
+        .clone()
+}
+
+/// Show speech playback in the system media overlay so play/pause keys reach Blinko
+pub fn update_media_session(state: MediaSessionState) {
+    if let Some(ref session) = *MEDIA_SESSION.lock().unwrap() {
+        let _ = session.send(state);
+    }
+}
+
+#[cfg(all(target_os = "windows", any(feature = "whisper-cuda", feature = "whisper-cpu")))]
+fn is_dictating(app: &AppHandle) -> bool {
+    crate::voice::current_voice_processor(app).is_some_and(|processor| processor.is_recording())
+}
+
+#[cfg(not(all(target_os = "windows", any(feature = "whisper-cuda", feature = "whisper-cpu"))))]
+fn is_dictating(_app: &AppHandle) -> bool {
+    false
+}
+
+#[cfg(all(target_os = "windows", any(feature = "whisper-cuda", feature = "whisper-cpu")))]
+fn media_key_trigger(app: &AppHandle) -> crate::voice::MediaKeyTrigger {
+    let processor = crate::voice::current_voice_processor(app);
+    processor.map(|processor| processor.get_config().media_key_trigger).unwrap_or_default()
+}
+
+/// The voice config names a media key as dictation trigger
+#[cfg(all(target_os = "windows", any(feature = "whisper-cuda", feature = "whisper-cpu")))]
+fn is_dictation_trigger_enabled(app: &AppHandle) -> bool {
+    media_key_trigger(app) != crate::voice::MediaKeyTrigger::Off
+}
+
+#[cfg(not(all(target_os = "windows", any(feature = "whisper-cuda", feature = "whisper-cpu"))))]
+fn is_dictation_trigger_enabled(_app: &AppHandle) -> bool {
+    false
+}
+
+/// Start or stop dictation when the event is the configured trigger key
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+fn toggle_dictation_by_media_key(app: &AppHandle, event: &souvlaki::MediaControlEvent) -> bool {
+    #[cfg(all(target_os = "windows", any(feature = "whisper-cuda", feature = "whisper-cpu")))]
+    {
+        use crate::voice::MediaKeyTrigger;
+        use souvlaki::MediaControlEvent;
+
+        let matches = match media_key_trigger(app) {
+            MediaKeyTrigger::Off => false,
+            MediaKeyTrigger::PlayPause => matches!(event, MediaControlEvent::Toggle | MediaControlEvent::Play | MediaControlEvent::Pause),
+            MediaKeyTrigger::Next => matches!(event, MediaControlEvent::Next),
+            MediaKeyTrigger::Previous => matches!(event, MediaControlEvent::Previous),
+        };
+        let processor = crate::voice::current_voice_processor(app);
+        if let Some(processor) = processor.filter(|_| matches) {
+            if processor.toggle_recording() {
+                info!("🎧 Dictation started by media key");
+            } else {
+                info!("🎧 Dictation stopped by media key");
+            }
+            return true;
+        }
+    }
+
+    #[cfg(not(all(target_os = "windows", any(feature = "whisper-cuda", feature = "whisper-cpu"))))]
+    let _ = (app, event);
+    false
+}
+
+/// Stop the dictation recording, which then gets transcribed as usual
+fn finish_dictation(app: &AppHandle) -> bool {
+    #[cfg(all(target_os = "windows", any(feature = "whisper-cuda", feature = "whisper-cpu")))]
+    {
+        let processor = crate::voice::current_voice_processor(app);
+        if let Some(processor) = processor.filter(|processor| processor.is_recording()) {
+            processor.toggle_recording();
+            info!("⏸️ Dictation stopped by media key");
+            return true;
+        }
+    }
+
+    #[cfg(not(all(target_os = "windows", any(feature = "whisper-cuda", feature = "whisper-cpu"))))]
+    let _ = app;
+    false
+}
+
+/// Play/pause drive read-aloud first, then the dictation trigger key, then a running dictation
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+fn handle_media_event(app: &AppHandle, event: souvlaki::MediaControlEvent) {
+    use crate::desktop::{is_speaking, is_speech_paused, pause_speaking, resume_speaking, stop_speaking};
+    use souvlaki::MediaControlEvent;
+
+    let pause_dictation = load_media_keys_config(app).pause_dictation;
+    let action = match event {
+        MediaControlEvent::Play if is_speech_paused() => resume_speaking(app.clone()).then_some("resume"),
+        MediaControlEvent::Pause if is_speaking() => pause_speaking().then_some("pause"),
+        MediaControlEvent::Toggle if is_speaking() => pause_speaking().then_some("pause"),
+        MediaControlEvent::Toggle if is_speech_paused() => resume_speaking(app.clone()).then_some("resume"),
+        ref event if toggle_dictation_by_media_key(app, event) => Some("dictation"),
+        MediaControlEvent::Pause | MediaControlEvent::Toggle if pause_dictation => finish_dictation(app).then_some("dictation"),
+        MediaControlEvent::Stop => {
+            stop_speaking(app.clone());
+            Some("stop")
+        }
+        _ => None,
+    };
+    if let Some(action) = action {
+        debug!("🎵 Media key handled: {}", action);
+        let _ = app.emit("media-key", action);
+    }
+}
+
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+fn apply_media_state(controls: &mut souvlaki::MediaControls, state: &MediaSessionState) {
+    use souvlaki::{MediaMetadata, MediaPlayback};
+
+    let (playback, title) = match state {
+        MediaSessionState::Playing(title) => (MediaPlayback::Playing { progress: None }, Some(title.as_str())),
+        MediaSessionState::Paused(title) => (MediaPlayback::Paused { progress: None }, Some(title.as_str())),
+        MediaSessionState::Stopped => (MediaPlayback::Stopped, None),
+    };
+    if let Some(title) = title {
+        let _ = controls.set_metadata(MediaMetadata { title: Some(title), artist: Some("Blinko"), ..Default::default() });
+    }
+    if let Err(e) = controls.set_playback(playback) {
+        warn!("⚠️ Failed to update media session: {:?}", e);
+    }
+}
+
+/// Own the platform media session on one thread, fed by `update_media_session`
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+fn run_media_session(app: AppHandle, receiver: std::sync::mpsc::Receiver<MediaSessionState>, hwnd: Option<isize>, generation: u64) {
+    use souvlaki::{MediaControls, PlatformConfig};
+    use std::sync::mpsc::RecvTimeoutError;
+
+    let config = PlatformConfig {
+        display_name: "Blinko",
+        dbus_name: "blinko",
+        hwnd: hwnd.map(|hwnd| hwnd as *mut std::ffi::c_void),
+    };
+    let mut controls = match MediaControls::new(config) {
+        Ok(controls) => controls,
+        Err(e) => {
+            error!("❌ Failed to create media session: {:?}", e);
+            return;
+        }
+    };
+    let event_app = app.clone();
+    if let Err(e) = controls.attach(move |event| handle_media_event(&event_app, event)) {
+        error!("❌ Failed to listen for media keys: {:?}", e);
+        return;
+    }
+    info!("🎵 Media keys ready");
+
+    // Speech reports its state, dictation is polled because it starts from its own hotkey
+    let mut speech = MediaSessionState::Stopped;
+    let mut shown = MediaSessionState::Stopped;
+    while MEDIA_GENERATION.load(Ordering::SeqCst) == generation {
+        match receiver.recv_timeout(std::time::Duration::from_millis(250)) {
+            Ok(state) => speech = state,
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+        // With a trigger key configured Blinko stays the active session so headset presses reach it
+        let wanted = match speech {
+            MediaSessionState::Stopped if is_dictating(&app) => MediaSessionState::Playing("Dictating".to_string()),
+            MediaSessionState::Stopped if is_dictation_trigger_enabled(&app) => MediaSessionState::Paused("Ready to dictate".to_string()),
+            ref state => state.clone(),
+        };
+        if wanted != shown {
+            apply_media_state(&mut controls, &wanted);
+            shown = wanted;
+        }
+    }
+    let _ = controls.detach();
+}
+
+/// (Re)create the media session from the saved config
+pub fn restart_media_keys(app: &AppHandle) {
+    let generation = MEDIA_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    MEDIA_SESSION.lock().unwrap().take();
+    if !load_media_keys_config(app).enabled {
+        return;
+    }
+
+    #[cfg(any(target_os = "windows", target_os = "linux"))]
+    {
+        // SMTC is tied to a window, MPRIS only needs the session bus
+        #[cfg(target_os = "windows")]
+        let hwnd = tauri::Manager::get_webview_window(app, "main").and_then(|window| window.hwnd().ok()).map(|hwnd| hwnd.0 as isize);
+        #[cfg(not(target_os = "windows"))]
+        let hwnd = None;
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+        *MEDIA_SESSION.lock().unwrap() = Some(sender);
+        let app_handle = app.clone();
+        std::thread::spawn(move || run_media_session(app_handle, receiver, hwnd, generation));
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "linux")))]
+    debug!("Media keys are not supported on this platform (generation {})", generation);
+}
+
+#[tauri::command]
+pub fn get_media_keys_config(app: AppHandle) -> MediaKeysConfig {
+    load_media_keys_config(&app)
+}
+
+#[tauri::command]
+pub fn save_media_keys_config(app: AppHandle, config: MediaKeysConfig) -> Result<(), String> {
+    save_json(&app, MEDIA_KEYS_FILE, &config)?;
+    *MEDIA_CONFIG.lock().unwrap() = Some(config);
+    restart_media_keys(&app);
+    Ok(())
+}
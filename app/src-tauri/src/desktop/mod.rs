@@ -4,10 +4,176 @@ pub mod tray;
 pub mod setup;
 pub mod window_state;
 pub mod text_selection;
+pub mod storage;
+pub mod clipboard_watcher;
+pub mod ocr;
+pub mod process;
+pub mod screen_recorder;
+pub mod color_picker;
+pub mod notifications;
+pub mod reminders;
+pub mod focus_timer;
+pub mod badge;
+pub mod deep_link;
+pub mod single_instance;
+pub mod cli;
+pub mod attachments;
+pub mod folder_watch;
+pub mod markdown_sync;
+pub mod search;
+#[cfg(feature = "semantic-search")]
+pub mod embeddings;
+pub mod offline_store;
+pub mod connectivity;
+pub mod downloads;
+pub mod uploads;
+pub mod credentials;
+pub mod accounts;
+pub mod network;
+pub mod local_api;
+pub mod webhooks;
+pub mod mqtt;
+pub mod backup;
+pub mod encryption;
+pub mod image_processing;
+pub mod thumbnails;
+pub mod logging;
+pub mod crash_report;
+pub mod updates;
+pub mod data_directory;
+pub mod autostart;
+pub mod idle;
+pub mod theme;
+pub mod window_effects;
+pub mod titlebar;
+pub mod print;
+pub mod palette;
+pub mod hot_corners;
+pub mod quicknote_draft;
+pub mod templates;
+pub mod daily_journal;
+pub mod input_hook;
+pub mod text_injection;
+pub mod foreground;
+pub mod snippets;
+pub mod calculator;
+pub mod plugins;
+pub mod scripts;
+pub mod calendar_export;
+pub mod caldav;
+pub mod s3_storage;
+pub mod note_history;
+pub mod note_versions;
+pub mod note_merge;
+pub mod sync_scheduler;
+pub mod api;
+pub mod site_export;
+pub mod note_export;
+pub mod tts;
+pub mod media;
+pub mod activity;
+pub mod capture_pause;
+pub mod privacy_guard;
+pub mod page_capture;
+pub mod article;
+pub mod feeds;
+pub mod mail_ingest;
+pub mod share_target;
+pub mod control_socket;
+pub mod dbus_service;
+pub mod apple_events;
+pub mod context_menu;
+pub mod os_search;
+pub mod resource_usage;
+pub mod cache;
+pub mod disk_space;
 
 pub use hotkey::*;
 pub use window::*;
 pub use tray::*;
 pub use setup::*;
 pub use window_state::*;
-pub use text_selection::*;
\ No newline at end of file
+pub use text_selection::*;
+pub use storage::*;
+pub use clipboard_watcher::*;
+pub use ocr::*;
+pub use process::*;
+pub use screen_recorder::*;
+pub use color_picker::*;
+pub use notifications::*;
+pub use reminders::*;
+pub use focus_timer::*;
+pub use badge::*;
+pub use deep_link::*;
+pub use single_instance::*;
+pub use cli::*;
+pub use attachments::*;
+pub use folder_watch::*;
+pub use markdown_sync::*;
+pub use search::*;
+#[cfg(feature = "semantic-search")]
+pub use embeddings::*;
+pub use offline_store::*;
+pub use connectivity::*;
+pub use downloads::*;
+pub use uploads::*;
+pub use credentials::*;
+pub use accounts::*;
+pub use network::*;
+pub use local_api::*;
+pub use webhooks::*;
+pub use mqtt::*;
+pub use backup::*;
+pub use encryption::*;
+pub use image_processing::*;
+pub use thumbnails::*;
+pub use logging::*;
+pub use crash_report::*;
+pub use updates::*;
+pub use data_directory::*;
+pub use autostart::*;
+pub use idle::*;
+pub use theme::*;
+pub use window_effects::*;
+pub use titlebar::*;
+pub use print::*;
+pub use palette::*;
+pub use hot_corners::*;
+pub use quicknote_draft::*;
+pub use templates::*;
+pub use daily_journal::*;
+pub use input_hook::*;
+pub use text_injection::*;
+pub use foreground::*;
+pub use snippets::*;
+pub use calculator::*;
+pub use plugins::*;
+pub use scripts::*;
+pub use calendar_export::*;
+pub use caldav::*;
+pub use s3_storage::*;
+pub use note_history::*;
+pub use note_versions::*;
+pub use note_merge::*;
+pub use sync_scheduler::*;
+pub use api::*;
+pub use site_export::*;
+pub use note_export::*;
+pub use tts::*;
+pub use media::*;
+pub use activity::*;
+pub use capture_pause::*;
+pub use privacy_guard::*;
+pub use page_capture::*;
+pub use article::*;
+pub use feeds::*;
+pub use mail_ingest::*;
+pub use share_target::*;
+pub use control_socket::*;
+pub use dbus_service::*;
+pub use apple_events::*;
+pub use context_menu::*;
+pub use os_search::*;
+pub use resource_usage::*;
+pub use cache::*;
+pub use disk_space::*;
\ No newline at end of file
@@ -0,0 +1,207 @@
+use tauri::AppHandle;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+
+use rumqttc::{Client, Event, MqttOptions, Packet, QoS};
+
+use crate::desktop::{
+    load_json_or_default, now_millis, open_quicknote_with_text, read_secret, save_json, store_secret,
+    toggle_quickai_window, toggle_quicknote_window,
+};
+
+const MQTT_CONFIG_FILE: &str = "mqtt.json";
+const MQTT_KEYCHAIN_ACCOUNT: &str = "mqtt";
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MqttConfig {
+    pub enabled: bool,
+    pub host: String,
+    pub port: u16,
+    /// Password is kept in the keychain
+    pub username: Option<String>,
+    #[serde(rename = "clientId")]
+    pub client_id: String,
+    /// Events are published to `<topicPrefix>/<event>`
+    #[serde(rename = "topicPrefix")]
+    pub topic_prefix: String,
+    /// Incoming `quicknote`, `quickai` or `{"action": "quicknote", "text": "..."}` messages
+    #[serde(rename = "commandTopic")]
+    pub command_topic: Option<String>,
+}
+
+impl Default for MqttConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: "localhost".to_string(),
+            port: 1883,
+            username: None,
+            client_id: "blinko-desktop".to_string(),
+            topic_prefix: "blinko".to_string(),
+            command_topic: Some("blinko/command".to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MqttStatus {
+    pub enabled: bool,
+    pub connected: bool,
+    pub error: Option<String>,
+}
+
+struct MqttConnection {
+    client: Client,
+    topic_prefix: String,
+}
+
+static MQTT_CONNECTION: LazyLock<Mutex<Option<MqttConnection>>> = LazyLock::new(|| Mutex::new(None));
+static MQTT_STATUS: LazyLock<Mutex<MqttStatus>> = LazyLock::new(|| Mutex::new(MqttStatus {
+    enabled: false,
+    connected: false,
+    error: None,
+}));
+// Bumped on every restart so the old event loop thread exits
+static MQTT_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Load MQTT config from file
+pub fn load_mqtt_config(app: &AppHandle) -> MqttConfig {
+    load_json_or_default(app, MQTT_CONFIG_FILE)
+}
+
+/// Publish an event to `<topicPrefix>/<event>` if the client is connected
+pub fn publish_mqtt_event(event: &str, payload: Value) {
+    let guard = MQTT_CONNECTION.lock().unwrap();
+    let Some(ref connection) = *guard else { return };
+
+    let topic = format!("{}/{}", connection.topic_prefix.trim_end_matches('/'), event);
+    let body = json!({ "timestamp": now_millis(), "data": payload }).to_string();
+    // State topics are retained so Home Assistant picks up the last value after a restart
+    let retain = event == "focus_timer";
+    if let Err(e) = connection.client.try_publish(topic, QoS::AtLeastOnce, retain, body) {
+        error!("Failed to publish MQTT event {}: {}", event, e);
+    }
+}
+
+fn handle_mqtt_command(app: &AppHandle, payload: &[u8]) {
+    let text = String::from_utf8_lossy(payload).trim().to_string();
+    let (action, note_text) = match serde_json::from_str::<Value>(&text) {
+        Ok(value) if value.is_object() => (
+            value.get("action").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+            value.get("text").and_then(|v| v.as_str()).map(String::from),
+        ),
+        _ => (text.clone(), None),
+    };
+
+    info!("📡 MQTT command: {}", action);
+    let result = match action.as_str() {
+        "quicknote" => match note_text {
+            Some(ref text) if !text.is_empty() => open_quicknote_with_text(app, text),
+            _ => toggle_quicknote_window(app.clone()),
+        },
+        "quickai" => toggle_quickai_window(app.clone()),
+        other => Err(format!("Unknown MQTT command: {}", other)),
+    };
+    if let Err(e) = result {
+        error!("❌ {}", e);
+    }
+}
+
+fn set_mqtt_status(enabled: bool, connected: bool, error: Option<String>) {
+    *MQTT_STATUS.lock().unwrap() = MqttStatus { enabled, connected, error };
+}
+
+/// (Re)connect to the broker using the saved config
+pub fn restart_mqtt_client(app: &AppHandle) {
+    let generation = MQTT_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    if let Some(old) = MQTT_CONNECTION.lock().unwrap().take() {
+        let _ = old.client.try_disconnect();
+    }
+
+    let config = load_mqtt_config(app);
+    if !config.enabled || config.host.trim().is_empty() {
+        set_mqtt_status(false, false, None);
+        return;
+    }
+
+    let mut options = MqttOptions::new(config.client_id.clone(), config.host.clone(), config.port);
+    options.set_keep_alive(Duration::from_secs(30));
+    if let Some(ref username) = config.username {
+        let password = read_secret(Some(MQTT_KEYCHAIN_ACCOUNT)).ok().flatten().unwrap_or_default();
+        options.set_credentials(username.clone(), password);
+    }
+
+    let (client, mut connection) = Client::new(options, 32);
+    if let Some(ref topic) = config.command_topic.as_ref().filter(|t| !t.is_empty()) {
+        if let Err(e) = client.subscribe(topic.as_str(), QoS::AtLeastOnce) {
+            error!("Failed to subscribe to MQTT command topic: {}", e);
+        }
+    }
+
+    *MQTT_CONNECTION.lock().unwrap() = Some(MqttConnection {
+        client,
+        topic_prefix: config.topic_prefix.clone(),
+    });
+    set_mqtt_status(true, false, None);
+    info!("📡 Connecting to MQTT broker {}:{}", config.host, config.port);
+
+    let app_handle = app.clone();
+    std::thread::spawn(move || {
+        // The iterator reconnects on its own after errors
+        for notification in connection.iter() {
+            if MQTT_GENERATION.load(Ordering::SeqCst) != generation {
+                break;
+            }
+            match notification {
+                Ok(Event::Incoming(Packet::ConnAck(_))) => {
+                    info!("📡 MQTT connected");
+                    set_mqtt_status(true, true, None);
+                }
+                Ok(Event::Incoming(Packet::Publish(message))) => {
+                    handle_mqtt_command(&app_handle, &message.payload);
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    error!("MQTT connection error: {}", e);
+                    set_mqtt_status(true, false, Some(e.to_string()));
+                    std::thread::sleep(RECONNECT_DELAY);
+                }
+            }
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_mqtt_config(app: AppHandle) -> MqttConfig {
+    load_mqtt_config(&app)
+}
+
+/// Save broker settings, `password` goes to the keychain when provided
+#[tauri::command]
+pub async fn save_mqtt_config(app: AppHandle, config: MqttConfig, password: Option<String>) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        if let Some(ref password) = password {
+            store_secret(Some(MQTT_KEYCHAIN_ACCOUNT), password)?;
+        }
+        save_json(&app, MQTT_CONFIG_FILE, &config)?;
+        restart_mqtt_client(&app);
+        Ok(())
+    })
+    .await
+    .map_err(|e| format!("Failed to save MQTT settings: {}", e))?
+}
+
+#[tauri::command]
+pub fn get_mqtt_status() -> MqttStatus {
+    MQTT_STATUS.lock().unwrap().clone()
+}
+
+/// Publish from the webview, e.g. when a quick note has been saved
+#[tauri::command]
+pub fn mqtt_publish(event: String, payload: Value) {
+    publish_mqtt_event(&event, payload);
+}
@@ -0,0 +1,251 @@
+use tauri::webview::PageLoadEvent;
+use tauri::{AppHandle, Url, WebviewUrl, WebviewWindowBuilder};
+use tauri_plugin_dialog::DialogExt;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::channel;
+use std::time::Duration;
+
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::desktop::{
+    escape_html, generate_id, get_cached_notes, get_configured_server_url, note_date, note_id, note_tags, note_title,
+    print_window_to_pdf, render_markdown_html, render_markdown_xhtml, PrintToPdfOptions,
+};
+
+const EXPORT_WINDOW_LABEL: &str = "note-export";
+const PAGE_LOAD_TIMEOUT: Duration = Duration::from_secs(30);
+
+const EXPORT_STYLESHEET: &str = r#"body { font: 11pt/1.6 Georgia, "Times New Roman", serif; color: #111; }
+h1, h2, h3 { font-family: -apple-system, "Segoe UI", sans-serif; line-height: 1.3; }
+.meta { color: #666; font-size: 0.85em; }
+img { max-width: 100%; }
+pre { white-space: pre-wrap; padding: 0.6em; background: #f4f4f4; }
+table { border-collapse: collapse; }
+td, th { border: 1px solid #ccc; padding: 0.2em 0.5em; }
+article + article { page-break-before: always; break-before: page; }
+"#;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Pdf,
+    Epub,
+}
+
+impl ExportFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Pdf => "pdf",
+            ExportFormat::Epub => "epub",
+        }
+    }
+}
+
+fn cached_note(app: &AppHandle, id: i64) -> Result<Value, String> {
+    get_cached_notes(app.clone(), Some(true))?
+        .into_iter()
+        .find(|note| note_id(note) == Some(id))
+        .ok_or_else(|| format!("Note {} is not in the local cache", id))
+}
+
+/// Notes with the tag or one of its sub tags (`work` also matches `work/meetings`), oldest first
+fn cached_notes_with_tag(app: &AppHandle, tag: &str) -> Result<Vec<Value>, String> {
+    let tag = tag.trim().trim_start_matches('#').trim_end_matches('/');
+    let mut notes: Vec<Value> = get_cached_notes(app.clone(), Some(false))?
+        .into_iter()
+        .filter(|note| note_tags(note).iter().any(|t| t == tag || t.starts_with(&format!("{}/", tag))))
+        .collect();
+    if notes.is_empty() {
+        return Err(format!("No cached notes tagged #{}", tag));
+    }
+    notes.sort_by_key(|note| note.get("createdAt").and_then(|v| v.as_str()).unwrap_or_default().to_string());
+    Ok(notes)
+}
+
+/// Point attachment links at the server, the exported file has no relative base
+fn absolute_url(url: &str) -> Option<String> {
+    if !url.starts_with('/') {
+        return None;
+    }
+    get_configured_server_url().map(|server| format!("{}{}", server.trim_end_matches('/'), url))
+}
+
+fn choose_export_path(app: &AppHandle, path: Option<String>, title: &str, format: ExportFormat) -> Result<Option<PathBuf>, String> {
+    let mut path = match path {
+        Some(path) => PathBuf::from(path),
+        None => {
+            let file_name: String = title.chars()
+                .map(|c| if c.is_alphanumeric() || c == ' ' || c == '-' { c } else { '_' })
+                .take(60)
+                .collect();
+            let Some(picked) = app.dialog()
+                .file()
+                .add_filter(format.extension().to_uppercase(), &[format.extension()])
+                .set_file_name(format!("{}.{}", file_name.trim(), format.extension()))
+                .blocking_save_file()
+            else {
+                return Ok(None);
+            };
+            picked.into_path().map_err(|e| format!("Invalid save location: {}", e))?
+        }
+    };
+    if path.extension().is_none() {
+        path.set_extension(format.extension());
+    }
+    Ok(Some(path))
+}
+
+fn document_html(title: &str, notes: &[Value]) -> String {
+    let mut body = String::new();
+    for note in notes {
+        let content = note.get("content").and_then(|v| v.as_str()).unwrap_or_default();
+        body.push_str(&format!(
+            "<article>\n{}<p class=\"meta\">{}</p>\n</article>\n",
+            render_markdown_html(content, absolute_url),
+            escape_html(&note_date(note)),
+        ));
+    }
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n{}</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+        escape_html(title), EXPORT_STYLESHEET, body,
+    )
+}
+
+/// Load the document in a hidden webview and print it, the same path `print_to_pdf` uses
+fn write_pdf(app: &AppHandle, path: &Path, title: &str, notes: &[Value]) -> Result<(), String> {
+    let page = std::env::temp_dir().join(format!("blinko-export-{}.html", generate_id()));
+    fs::write(&page, document_html(title, notes)).map_err(|e| format!("Failed to write export page: {}", e))?;
+    let url = Url::from_file_path(&page).map_err(|_| format!("Invalid export page path: {}", page.display()))?;
+
+    let (loaded, load_result) = channel();
+    let result = WebviewWindowBuilder::new(app, EXPORT_WINDOW_LABEL, WebviewUrl::External(url))
+        .title(title)
+        .visible(false)
+        .on_page_load(move |_, payload| {
+            if payload.event() == PageLoadEvent::Finished {
+                let _ = loaded.send(());
+            }
+        })
+        .build()
+        .map_err(|e| format!("Failed to open export window: {}", e))
+        .and_then(|window| {
+            let printed = load_result.recv_timeout(PAGE_LOAD_TIMEOUT)
+                .map_err(|_| "Timed out loading the export page".to_string())
+                .and_then(|_| print_window_to_pdf(&window, path, &PrintToPdfOptions::default()));
+            let _ = window.destroy();
+            printed
+        });
+
+    let _ = fs::remove_file(&page);
+    result
+}
+
+fn xhtml_page(title: &str, body: &str) -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<!DOCTYPE html>\n\
+         <html xmlns=\"http://www.w3.org/1999/xhtml\" xmlns:epub=\"http://www.idpf.org/2007/ops\">\n\
+         <head>\n<title>{}</title>\n<link rel=\"stylesheet\" type=\"text/css\" href=\"style.css\"/>\n</head>\n<body>\n{}</body>\n</html>\n",
+        escape_html(title), body,
+    )
+}
+
+/// EPUB 3 with one chapter per note. Images stay links to the server.
+fn write_epub(path: &Path, title: &str, notes: &[Value]) -> Result<(), String> {
+    let file = File::create(path).map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+    let mut zip = ZipWriter::new(file);
+    let deflated = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let add = |zip: &mut ZipWriter<File>, name: &str, content: &str, options: SimpleFileOptions| -> Result<(), String> {
+        zip.start_file(name, options).map_err(|e| format!("Failed to write EPUB: {}", e))?;
+        zip.write_all(content.as_bytes()).map_err(|e| format!("Failed to write EPUB: {}", e))
+    };
+
+    // Readers identify the format by an uncompressed `mimetype` as the very first entry
+    add(&mut zip, "mimetype", "application/epub+zip", SimpleFileOptions::default().compression_method(CompressionMethod::Stored))?;
+    add(&mut zip, "META-INF/container.xml", concat!(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n",
+        "<container version=\"1.0\" xmlns=\"urn:oasis:names:tc:opendocument:xmlns:container\">\n",
+        "<rootfiles><rootfile full-path=\"OEBPS/content.opf\" media-type=\"application/oebps-package+xml\"/></rootfiles>\n",
+        "</container>\n",
+    ), deflated)?;
+    add(&mut zip, "OEBPS/style.css", EXPORT_STYLESHEET, deflated)?;
+
+    let mut manifest = String::new();
+    let mut spine = String::new();
+    let mut toc = String::new();
+    for (index, note) in notes.iter().enumerate() {
+        let chapter = format!("chapter-{}.xhtml", index + 1);
+        let chapter_title = note_title(note);
+        let content = note.get("content").and_then(|v| v.as_str()).unwrap_or_default();
+        let body = format!(
+            "<section epub:type=\"chapter\">\n{}<p class=\"meta\">{}</p>\n</section>\n",
+            render_markdown_xhtml(content, absolute_url),
+            escape_html(&note_date(note)),
+        );
+        add(&mut zip, &format!("OEBPS/{}", chapter), &xhtml_page(&chapter_title, &body), deflated)?;
+
+        manifest.push_str(&format!("<item id=\"c{}\" href=\"{}\" media-type=\"application/xhtml+xml\"/>\n", index + 1, chapter));
+        spine.push_str(&format!("<itemref idref=\"c{}\"/>\n", index + 1));
+        toc.push_str(&format!("<li><a href=\"{}\">{}</a></li>\n", chapter, escape_html(&chapter_title)));
+    }
+
+    let nav = xhtml_page(title, &format!("<nav epub:type=\"toc\" id=\"toc\">\n<h1>{}</h1>\n<ol>\n{}</ol>\n</nav>\n", escape_html(title), toc));
+    add(&mut zip, "OEBPS/nav.xhtml", &nav, deflated)?;
+
+    let opf = format!(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n\
+         <package xmlns=\"http://www.idpf.org/2007/opf\" version=\"3.0\" unique-identifier=\"book-id\">\n\
+         <metadata xmlns:dc=\"http://purl.org/dc/elements/1.1/\">\n\
+         <dc:identifier id=\"book-id\">urn:blinko:{}</dc:identifier>\n<dc:title>{}</dc:title>\n<dc:language>en</dc:language>\n\
+         <meta property=\"dcterms:modified\">{}</meta>\n</metadata>\n\
+         <manifest>\n<item id=\"nav\" href=\"nav.xhtml\" media-type=\"application/xhtml+xml\" properties=\"nav\"/>\n\
+         <item id=\"css\" href=\"style.css\" media-type=\"text/css\"/>\n{}</manifest>\n\
+         <spine>\n{}</spine>\n</package>\n",
+        generate_id(), escape_html(title), chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ"), manifest, spine,
+    );
+    add(&mut zip, "OEBPS/content.opf", &opf, deflated)?;
+
+    zip.finish().map_err(|e| format!("Failed to write EPUB: {}", e))?;
+    Ok(())
+}
+
+fn export_notes_to(app: &AppHandle, notes: &[Value], title: &str, format: ExportFormat, path: Option<String>) -> Result<Option<String>, String> {
+    let Some(path) = choose_export_path(app, path, title, format)? else {
+        return Ok(None);
+    };
+    match format {
+        ExportFormat::Pdf => write_pdf(app, &path, title, notes)?,
+        ExportFormat::Epub => write_epub(&path, title, notes)?,
+    }
+    info!("📚 Exported {} note(s) as {} to {}", notes.len(), format.extension(), path.display());
+    Ok(Some(path.to_string_lossy().to_string()))
+}
+
+/// Export one cached note as PDF or EPUB.
+/// Returns the written file, or None if the save dialog was cancelled.
+#[tauri::command]
+pub async fn export_note(app: AppHandle, id: i64, format: ExportFormat, path: Option<String>) -> Result<Option<String>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let note = cached_note(&app, id)?;
+        let title = note_title(&note);
+        export_notes_to(&app, &[note], &title, format, path)
+    })
+    .await
+    .map_err(|e| format!("Note export failed: {}", e))?
+}
+
+/// Export every note with a tag (and its sub tags) as one document, one note per page or chapter
+#[tauri::command]
+pub async fn export_tag(app: AppHandle, tag: String, format: ExportFormat, path: Option<String>) -> Result<Option<String>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let notes = cached_notes_with_tag(&app, &tag)?;
+        let title = format!("#{}", tag.trim().trim_start_matches('#'));
+        export_notes_to(&app, &notes, &title, format, path)
+    })
+    .await
+    .map_err(|e| format!("Tag export failed: {}", e))?
+}
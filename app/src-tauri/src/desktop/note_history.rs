@@ -0,0 +1,172 @@
+use tauri::AppHandle;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+
+use git2::{Commit, IndexAddOption, IndexMatchedPath, Oid, Repository, Signature, Sort};
+
+use crate::desktop::{load_json_or_default, load_markdown_sync_config, markdown_note_file, parse_markdown_note, save_json, SyncNote};
+
+const NOTE_HISTORY_CONFIG_FILE: &str = "note_history.json";
+const MAX_HISTORY_ENTRIES: usize = 200;
+const COMMITTER_NAME: &str = "Blinko";
+const COMMITTER_EMAIL: &str = "blinko@localhost";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct NoteHistoryConfig {
+    /// Commit the markdown mirror folder after every sync
+    pub enabled: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NoteVersion {
+    pub commit: String,
+    pub message: String,
+    /// Commit time as unix milliseconds
+    pub timestamp: u64,
+}
+
+// libgit2 repositories must not be written from two threads at once
+static HISTORY_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+fn load_note_history_config(app: &AppHandle) -> NoteHistoryConfig {
+    load_json_or_default(app, NOTE_HISTORY_CONFIG_FILE)
+}
+
+fn open_repository(folder: &Path) -> Result<Repository, String> {
+    Repository::open(folder)
+        .or_else(|_| Repository::init(folder))
+        .map_err(|e| format!("Failed to open note history repository: {}", e))
+}
+
+fn history_repository(app: &AppHandle) -> Result<Repository, String> {
+    let sync_config = load_markdown_sync_config(app);
+    if !sync_config.enabled || sync_config.folder.trim().is_empty() {
+        return Err("Note history needs markdown folder sync".to_string());
+    }
+    open_repository(Path::new(&sync_config.folder))
+}
+
+/// Stage all notes and commit them, `None` when nothing changed since the last commit
+fn commit_all(repo: &Repository, message: &str) -> Result<Option<Oid>, git2::Error> {
+    let mut index = repo.index()?;
+    // Conflict copies are scratch files, not note versions
+    let mut skip_conflicts = |path: &Path, _: &[u8]| -> i32 {
+        if path.to_string_lossy().contains(".conflict-") { 1 } else { 0 }
+    };
+    index.add_all(["*.md"], IndexAddOption::DEFAULT, Some(&mut skip_conflicts as &mut IndexMatchedPath))?;
+    index.update_all(["*.md"], None)?;
+    index.write()?;
+
+    let tree_id = index.write_tree()?;
+    let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+    if parent.as_ref().map(|commit| commit.tree_id()) == Some(tree_id) {
+        return Ok(None);
+    }
+
+    let tree = repo.find_tree(tree_id)?;
+    let signature = Signature::now(COMMITTER_NAME, COMMITTER_EMAIL)?;
+    let parents: Vec<&Commit> = parent.iter().collect();
+    repo.commit(Some("HEAD"), &signature, &signature, message, &tree, &parents).map(Some)
+}
+
+/// Commit the mirror folder if auto-versioning is on, called after every sync
+pub fn commit_note_history(app: &AppHandle, message: &str) {
+    if !load_note_history_config(app).enabled {
+        return;
+    }
+
+    let _guard = HISTORY_LOCK.lock().unwrap();
+    let result = history_repository(app)
+        .and_then(|repo| commit_all(&repo, message).map_err(|e| format!("Failed to commit note history: {}", e)));
+    match result {
+        Ok(Some(oid)) => debug!("📚 Note history commit {}: {}", oid, message),
+        Ok(None) => {}
+        Err(e) => error!("❌ {}", e),
+    }
+}
+
+fn blob_at(commit: &Commit, path: &Path) -> Option<Oid> {
+    commit.tree().ok()?.get_path(path).ok().map(|entry| entry.id())
+}
+
+/// Path of the note inside the repository, the mirror folder is flat so it is just the file name
+fn note_repo_path(app: &AppHandle, note_id: i64) -> Result<PathBuf, String> {
+    markdown_note_file(app, note_id)
+        .and_then(|file| file.file_name().map(PathBuf::from))
+        .ok_or_else(|| format!("Note {} is not in the markdown folder", note_id))
+}
+
+#[tauri::command]
+pub fn get_note_history_config(app: AppHandle) -> NoteHistoryConfig {
+    load_note_history_config(&app)
+}
+
+#[tauri::command]
+pub fn save_note_history_config(app: AppHandle, config: NoteHistoryConfig) -> Result<(), String> {
+    if config.enabled {
+        history_repository(&app)?;
+    }
+    save_json(&app, NOTE_HISTORY_CONFIG_FILE, &config)?;
+    commit_note_history(&app, "Enable note history");
+    Ok(())
+}
+
+/// Commits that changed the note, newest first
+#[tauri::command]
+pub fn get_note_history(app: AppHandle, note_id: i64) -> Result<Vec<NoteVersion>, String> {
+    let _guard = HISTORY_LOCK.lock().unwrap();
+    let repo = history_repository(&app)?;
+    let path = note_repo_path(&app, note_id)?;
+
+    let mut walk = repo.revwalk().map_err(|e| format!("Failed to read note history: {}", e))?;
+    if walk.push_head().is_err() {
+        // Nothing committed yet
+        return Ok(Vec::new());
+    }
+    let _ = walk.set_sorting(Sort::TIME);
+
+    let mut versions = Vec::new();
+    for oid in walk.flatten() {
+        let Ok(commit) = repo.find_commit(oid) else { continue };
+        let blob = blob_at(&commit, &path);
+        let parent_blob = commit.parent(0).ok().and_then(|parent| blob_at(&parent, &path));
+        if blob.is_none() || blob == parent_blob {
+            continue;
+        }
+
+        versions.push(NoteVersion {
+            commit: oid.to_string(),
+            message: commit.summary().unwrap_or_default().to_string(),
+            timestamp: (commit.time().seconds() * 1000) as u64,
+        });
+        if versions.len() >= MAX_HISTORY_ENTRIES {
+            break;
+        }
+    }
+    Ok(versions)
+}
+
+/// Write an old version back to the note file, the folder watcher then syncs it like any local edit
+#[tauri::command]
+pub fn restore_note_version(app: AppHandle, note_id: i64, commit: String) -> Result<SyncNote, String> {
+    let guard = HISTORY_LOCK.lock().unwrap();
+    let repo = history_repository(&app)?;
+    let path = note_repo_path(&app, note_id)?;
+
+    let oid = Oid::from_str(&commit).map_err(|e| format!("Invalid commit id: {}", e))?;
+    let old_commit = repo.find_commit(oid).map_err(|e| format!("Commit not found: {}", e))?;
+    let blob_id = blob_at(&old_commit, &path).ok_or_else(|| format!("Note {} does not exist in {}", note_id, commit))?;
+    let blob = repo.find_blob(blob_id).map_err(|e| format!("Failed to read note version: {}", e))?;
+    let content = String::from_utf8_lossy(blob.content()).to_string();
+
+    let workdir = repo.workdir().ok_or("Note history repository has no working directory")?;
+    fs::write(workdir.join(&path), &content)
+        .map_err(|e| format!("Failed to restore note {}: {}", note_id, e))?;
+    drop(guard);
+
+    info!("📚 Restored note {} to {}", note_id, &commit[..commit.len().min(8)]);
+    commit_note_history(&app, &format!("Restore note {} to {}", note_id, &commit[..commit.len().min(8)]));
+    Ok(parse_markdown_note(&content))
+}
@@ -0,0 +1,87 @@
+use similar::{capture_diff_slices, Algorithm, DiffOp};
+
+const LOCAL_MARKER: &str = "<<<<<<< local\n";
+const SEPARATOR_MARKER: &str = "=======\n";
+const SERVER_MARKER: &str = ">>>>>>> server\n";
+
+#[derive(Debug, Clone)]
+pub struct MergeResult {
+    pub content: String,
+    /// Regions changed differently on both sides, written with conflict markers
+    pub conflicts: usize,
+}
+
+/// For every base line, the line it was kept as on the other side
+fn line_map(base: &[&str], other: &[&str]) -> Vec<Option<usize>> {
+    let mut map = vec![None; base.len()];
+    for op in capture_diff_slices(Algorithm::Myers, base, other) {
+        if let DiffOp::Equal { old_index, new_index, len } = op {
+            for offset in 0..len {
+                map[old_index + offset] = Some(new_index + offset);
+            }
+        }
+    }
+    map
+}
+
+fn push_lines(out: &mut String, lines: &[&str]) {
+    for line in lines {
+        out.push_str(line);
+    }
+    // Markers must start on their own line even if the last line had no newline
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+}
+
+/// Line based three-way merge (diff3) of two edits of the same base text.
+/// Regions only one side changed take that side; regions both changed get conflict markers.
+pub fn merge_markdown(base: &str, local: &str, server: &str) -> MergeResult {
+    let base: Vec<&str> = base.split_inclusive('\n').collect();
+    let local: Vec<&str> = local.split_inclusive('\n').collect();
+    let server: Vec<&str> = server.split_inclusive('\n').collect();
+    let local_map = line_map(&base, &local);
+    let server_map = line_map(&base, &server);
+
+    let mut content = String::new();
+    let mut conflicts = 0;
+    let (mut i, mut a, mut b) = (0, 0, 0);
+
+    loop {
+        // Lines unchanged on both sides
+        while i < base.len() && local_map[i] == Some(a) && server_map[i] == Some(b) {
+            content.push_str(base[i]);
+            i += 1;
+            a += 1;
+            b += 1;
+        }
+
+        // Next base line both sides kept ends the changed region
+        let (next_i, next_a, next_b) = (i..base.len())
+            .find_map(|k| Some((k, local_map[k]?, server_map[k]?)))
+            .unwrap_or((base.len(), local.len(), server.len()));
+        if (next_i, next_a, next_b) == (i, a, b) {
+            break;
+        }
+
+        let (base_chunk, local_chunk, server_chunk) = (&base[i..next_i], &local[a..next_a], &server[b..next_b]);
+        if local_chunk == server_chunk || server_chunk == base_chunk {
+            content.extend(local_chunk.iter().copied());
+        } else if local_chunk == base_chunk {
+            content.extend(server_chunk.iter().copied());
+        } else {
+            if !content.is_empty() && !content.ends_with('\n') {
+                content.push('\n');
+            }
+            content.push_str(LOCAL_MARKER);
+            push_lines(&mut content, local_chunk);
+            content.push_str(SEPARATOR_MARKER);
+            push_lines(&mut content, server_chunk);
+            content.push_str(SERVER_MARKER);
+            conflicts += 1;
+        }
+        (i, a, b) = (next_i, next_a, next_b);
+    }
+
+    MergeResult { content, conflicts }
+}
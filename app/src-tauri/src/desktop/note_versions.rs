@@ -0,0 +1,130 @@
+use tauri::AppHandle;
+use serde::{Deserialize, Serialize};
+
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use similar::{ChangeTag, TextDiff};
+
+use crate::desktop::{now_millis, protect_column, unprotect_column, with_offline_db};
+
+/// Older snapshots of a note are pruned beyond this
+const MAX_VERSIONS_PER_NOTE: i64 = 50;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NoteSnapshot {
+    pub id: i64,
+    #[serde(rename = "noteId")]
+    pub note_id: i64,
+    pub content: String,
+    /// "local" for edits made on this device, "server" for server copies replaced by them
+    pub source: String,
+    #[serde(rename = "createdAt")]
+    pub created_at: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DiffLine {
+    /// "equal", "insert" or "delete"
+    pub tag: String,
+    /// Zero-based line in the older version, `None` for inserted lines
+    #[serde(rename = "oldLine")]
+    pub old_line: Option<usize>,
+    #[serde(rename = "newLine")]
+    pub new_line: Option<usize>,
+    pub text: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NoteVersionDiff {
+    pub from: i64,
+    pub to: i64,
+    pub insertions: usize,
+    pub deletions: usize,
+    pub lines: Vec<DiffLine>,
+}
+
+/// Store a version of a note unless it equals the latest one, keeping the newest snapshots only
+pub fn snapshot_note_version(conn: &Connection, note_id: i64, content: &str, source: &str) -> rusqlite::Result<()> {
+    let latest: Option<String> = conn.query_row(
+        "SELECT content FROM note_versions WHERE note_id = ?1 ORDER BY id DESC LIMIT 1",
+        params![note_id],
+        |row| unprotect_column(0, row.get(0)?),
+    ).optional()?;
+    if latest.as_deref() == Some(content) {
+        return Ok(());
+    }
+
+    conn.execute(
+        "INSERT INTO note_versions (note_id, content, source, created_at) VALUES (?1, ?2, ?3, ?4)",
+        params![note_id, protect_column(content)?, source, now_millis() as i64],
+    )?;
+    conn.execute(
+        "DELETE FROM note_versions WHERE note_id = ?1 AND id NOT IN
+             (SELECT id FROM note_versions WHERE note_id = ?1 ORDER BY id DESC LIMIT ?2)",
+        params![note_id, MAX_VERSIONS_PER_NOTE],
+    )?;
+    Ok(())
+}
+
+fn snapshot_from_row(row: &Row) -> rusqlite::Result<NoteSnapshot> {
+    Ok(NoteSnapshot {
+        id: row.get(0)?,
+        note_id: row.get(1)?,
+        content: unprotect_column(2, row.get(2)?)?,
+        source: row.get(3)?,
+        created_at: row.get::<_, i64>(4)? as u64,
+    })
+}
+
+fn read_snapshot(conn: &Connection, note_id: i64, version: i64) -> rusqlite::Result<Option<NoteSnapshot>> {
+    conn.query_row(
+        "SELECT id, note_id, content, source, created_at FROM note_versions WHERE id = ?1 AND note_id = ?2",
+        params![version, note_id],
+        snapshot_from_row,
+    ).optional()
+}
+
+/// Line diff between two texts
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    TextDiff::from_lines(old, new)
+        .iter_all_changes()
+        .map(|change| DiffLine {
+            tag: match change.tag() {
+                ChangeTag::Equal => "equal",
+                ChangeTag::Insert => "insert",
+                ChangeTag::Delete => "delete",
+            }.to_string(),
+            old_line: change.old_index(),
+            new_line: change.new_index(),
+            text: change.value().trim_end_matches(['\r', '\n']).to_string(),
+        })
+        .collect()
+}
+
+/// Snapshots of a note, newest first
+#[tauri::command]
+pub fn list_note_versions(app: AppHandle, id: i64) -> Result<Vec<NoteSnapshot>, String> {
+    with_offline_db(&app, |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, note_id, content, source, created_at FROM note_versions WHERE note_id = ?1 ORDER BY id DESC",
+        )?;
+        let rows = stmt.query_map(params![id], snapshot_from_row)?;
+        rows.collect()
+    })
+}
+
+/// Changes from snapshot `a` to snapshot `b` of a note
+#[tauri::command]
+pub fn diff_note_versions(app: AppHandle, id: i64, a: i64, b: i64) -> Result<NoteVersionDiff, String> {
+    let (from, to) = with_offline_db(&app, |conn| Ok((read_snapshot(conn, id, a)?, read_snapshot(conn, id, b)?)))?;
+    let from = from.ok_or_else(|| format!("Version {} of note {} not found", a, id))?;
+    let to = to.ok_or_else(|| format!("Version {} of note {} not found", b, id))?;
+
+    let lines = diff_lines(&from.content, &to.content);
+    Ok(NoteVersionDiff {
+        from: a,
+        to: b,
+        insertions: lines.iter().filter(|line| line.tag == "insert").count(),
+        deletions: lines.iter().filter(|line| line.tag == "delete").count(),
+        lines,
+    })
+}
@@ -0,0 +1,182 @@
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_notification::NotificationExt;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+
+use crate::desktop::now_millis;
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+use crate::desktop::background_command;
+
+const DND_POLL_INTERVAL: Duration = Duration::from_secs(30);
+/// Flushing more than this at once collapses the rest into a summary
+const MAX_FLUSHED_NOTIFICATIONS: usize = 3;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DndStatus {
+    pub active: bool,
+    /// False where the OS state can't be read, notifications are then never held back
+    pub supported: bool,
+    /// Notifications waiting for DND to end
+    pub queued: usize,
+}
+
+#[derive(Debug, Clone)]
+struct QueuedNotification {
+    title: String,
+    body: String,
+    queued_at: u64,
+}
+
+static DND_ACTIVE: AtomicBool = AtomicBool::new(false);
+// Held by Blinko itself, e.g. while focus mode is on
+static NOTIFICATIONS_HELD: AtomicBool = AtomicBool::new(false);
+static DND_MONITOR_STARTED: AtomicBool = AtomicBool::new(false);
+static NOTIFICATION_QUEUE: LazyLock<Mutex<Vec<QueuedNotification>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+#[cfg(target_os = "windows")]
+#[link(name = "shell32")]
+extern "system" {
+    fn SHQueryUserNotificationState(state: *mut i32) -> i32;
+}
+
+/// Focus assist, full screen apps and presentation mode all suppress toasts on Windows
+#[cfg(target_os = "windows")]
+fn detect_dnd() -> Option<bool> {
+    const QUNS_ACCEPTS_NOTIFICATIONS: i32 = 5;
+
+    let mut state = 0;
+    // SAFETY: the call only writes the state enum into the provided integer
+    let result = unsafe { SHQueryUserNotificationState(&mut state) };
+    if result == 0 && state != QUNS_ACCEPTS_NOTIFICATIONS {
+        return Some(true);
+    }
+
+    // "Do not disturb" on Windows 11 turns toasts off globally
+    let output = background_command("reg")
+        .args(["query", r"HKCU\Software\Microsoft\Windows\CurrentVersion\Notifications\Settings", "/v", "NOC_GLOBAL_SETTING_TOASTS_ENABLED"])
+        .output()
+        .ok()?;
+    let text = String::from_utf8_lossy(&output.stdout);
+    Some(text.lines().any(|line| line.contains("NOC_GLOBAL_SETTING_TOASTS_ENABLED") && line.trim_end().ends_with("0x0")))
+}
+
+/// Focus modes record their assertions in the DoNotDisturb database, older releases use a defaults key
+#[cfg(target_os = "macos")]
+fn detect_dnd() -> Option<bool> {
+    if let Some(home) = std::env::var_os("HOME") {
+        let assertions = std::path::Path::new(&home).join("Library/DoNotDisturb/DB/Assertions.json");
+        if let Ok(content) = std::fs::read_to_string(&assertions) {
+            let json: serde_json::Value = serde_json::from_str(&content).ok()?;
+            let active = json["data"].as_array().is_some_and(|data| data.iter().any(|entry| {
+                entry["storeAssertionRecords"].as_array().is_some_and(|records| !records.is_empty())
+            }));
+            return Some(active);
+        }
+    }
+
+    let output = background_command("defaults")
+        .args(["-currentHost", "read", "com.apple.notificationcenterui", "doNotDisturb"])
+        .output()
+        .ok()?;
+    Some(String::from_utf8_lossy(&output.stdout).trim() == "1")
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn detect_dnd() -> Option<bool> {
+    None
+}
+
+fn show_notification(app: &AppHandle, title: &str, body: &str) {
+    if let Err(e) = app.notification()
+        .builder()
+        .title(title)
+        .body(body)
+        .show()
+    {
+        error!("Failed to show notification '{}': {}", title, e);
+    }
+}
+
+/// Show a native notification, works while the app only lives in the tray.
+/// Held back while the OS is in do-not-disturb and shown once it ends.
+pub fn send_notification(app: &AppHandle, title: &str, body: &str) {
+    if DND_ACTIVE.load(Ordering::SeqCst) || NOTIFICATIONS_HELD.load(Ordering::SeqCst) {
+        info!("🔕 Do not disturb is on, queueing notification '{}'", title);
+        NOTIFICATION_QUEUE.lock().unwrap().push(QueuedNotification {
+            title: title.to_string(),
+            body: body.to_string(),
+            queued_at: now_millis(),
+        });
+        return;
+    }
+    show_notification(app, title, body);
+}
+
+/// Show a notification even during do-not-disturb
+pub fn send_urgent_notification(app: &AppHandle, title: &str, body: &str) {
+    show_notification(app, title, body);
+}
+
+fn flush_notification_queue(app: &AppHandle) {
+    let mut queued = std::mem::take(&mut *NOTIFICATION_QUEUE.lock().unwrap());
+    if queued.is_empty() {
+        return;
+    }
+    queued.sort_by_key(|n| n.queued_at);
+    info!("🔔 Do not disturb ended, showing {} queued notification(s)", queued.len());
+
+    if queued.len() > MAX_FLUSHED_NOTIFICATIONS {
+        let skipped = queued.len() - (MAX_FLUSHED_NOTIFICATIONS - 1);
+        for notification in queued.iter().skip(skipped) {
+            show_notification(app, &notification.title, &notification.body);
+        }
+        show_notification(app, "Blinko", &format!("{} more notifications arrived while Do Not Disturb was on", skipped));
+    } else {
+        for notification in queued.iter() {
+            show_notification(app, &notification.title, &notification.body);
+        }
+    }
+}
+
+/// Queue notifications like OS do-not-disturb does, and show what queued up once released
+pub fn hold_notifications(app: &AppHandle, held: bool) {
+    if NOTIFICATIONS_HELD.swap(held, Ordering::SeqCst) && !held && !DND_ACTIVE.load(Ordering::SeqCst) {
+        flush_notification_queue(app);
+    }
+}
+
+fn current_dnd_status() -> DndStatus {
+    DndStatus {
+        active: DND_ACTIVE.load(Ordering::SeqCst),
+        supported: cfg!(any(target_os = "windows", target_os = "macos")),
+        queued: NOTIFICATION_QUEUE.lock().unwrap().len(),
+    }
+}
+
+/// Poll the OS do-not-disturb state and flush queued notifications when it ends
+pub fn start_dnd_monitor(app: &AppHandle) {
+    if !cfg!(any(target_os = "windows", target_os = "macos")) || DND_MONITOR_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let app_handle = app.clone();
+    std::thread::spawn(move || loop {
+        if let Some(active) = detect_dnd() {
+            if active != DND_ACTIVE.swap(active, Ordering::SeqCst) {
+                info!("{} Do not disturb {}", if active { "🔕" } else { "🔔" }, if active { "on" } else { "off" });
+                if !active && !NOTIFICATIONS_HELD.load(Ordering::SeqCst) {
+                    flush_notification_queue(&app_handle);
+                }
+                let _ = app_handle.emit("dnd-status-changed", current_dnd_status());
+            }
+        }
+        std::thread::sleep(DND_POLL_INTERVAL);
+    });
+}
+
+#[tauri::command]
+pub fn get_dnd_status() -> DndStatus {
+    current_dnd_status()
+}
@@ -0,0 +1,169 @@
+use tauri::{AppHandle, Emitter};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::process::Command;
+
+use mouse_position::mouse_position::Mouse;
+
+use crate::desktop::{background_command, get_app_data_subdir, load_json_or_default, now_millis, open_quicknote_with_text, save_json};
+
+const OCR_CONFIG_FILE: &str = "ocr_config.json";
+const SCREENSHOTS_DIR: &str = "screenshots";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OcrConfig {
+    /// Tesseract language codes, e.g. "eng" or "eng+chi_sim"
+    pub language: String,
+
+    /// Custom path to the tesseract executable (uses PATH when empty)
+    #[serde(rename = "tesseractPath")]
+    pub tesseract_path: String,
+
+    /// Run OCR on screenshots taken via hotkey and insert the text into a quick note
+    #[serde(rename = "ocrOnScreenshot")]
+    pub ocr_on_screenshot: bool,
+}
+
+impl Default for OcrConfig {
+    fn default() -> Self {
+        Self {
+            language: "eng".to_string(),
+            tesseract_path: String::new(),
+            ocr_on_screenshot: false,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ScreenshotCapturedEvent {
+    pub path: String,
+    pub text: Option<String>,
+}
+
+/// Load OCR config from file
+pub fn load_ocr_config(app: &AppHandle) -> OcrConfig {
+    load_json_or_default(app, OCR_CONFIG_FILE)
+}
+
+fn tesseract_command(config: &OcrConfig) -> Command {
+    let program = if config.tesseract_path.trim().is_empty() {
+        "tesseract"
+    } else {
+        config.tesseract_path.trim()
+    };
+
+    background_command(program)
+}
+
+/// Run tesseract on an image file and return the recognized text
+pub fn recognize_text(config: &OcrConfig, image_path: &Path) -> Result<String, String> {
+    if !image_path.exists() {
+        return Err(format!("Image file not found: {}", image_path.display()));
+    }
+
+    let output = tesseract_command(config)
+        .arg(image_path)
+        .arg("stdout")
+        .arg("-l")
+        .arg(&config.language)
+        .output()
+        .map_err(|e| format!("Failed to run tesseract (is it installed?): {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Tesseract failed: {}", stderr.trim()));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Capture the monitor under the mouse cursor and save it as PNG
+fn capture_screen_under_cursor(app: &AppHandle) -> Result<String, String> {
+    let (x, y) = match Mouse::get_mouse_position() {
+        Mouse::Position { x, y } => (x, y),
+        Mouse::Error => (0, 0),
+    };
+
+    let monitor = match xcap::Monitor::from_point(x, y) {
+        Ok(monitor) => monitor,
+        Err(_) => xcap::Monitor::all()
+            .map_err(|e| format!("Failed to list monitors: {}", e))?
+            .into_iter()
+            .next()
+            .ok_or("No monitor found")?,
+    };
+
+    let image = monitor.capture_image()
+        .map_err(|e| format!("Failed to capture screen: {}", e))?;
+
+    let path = get_app_data_subdir(app, SCREENSHOTS_DIR)?
+        .join(format!("screenshot-{}.png", now_millis()));
+    image.save(&path)
+        .map_err(|e| format!("Failed to save screenshot: {}", e))?;
+
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Handle the screenshot hotkey: capture the screen and optionally OCR it into a quick note
+pub fn handle_screenshot_shortcut(app: &AppHandle) {
+    let app_handle = app.clone();
+
+    // Capture and OCR can take a while, keep the shortcut handler responsive
+    std::thread::spawn(move || {
+        let path = match capture_screen_under_cursor(&app_handle) {
+            Ok(path) => path,
+            Err(e) => {
+                error!("❌ Screenshot failed: {}", e);
+                return;
+            }
+        };
+        info!("📸 Screenshot saved to: {}", path);
+
+        let config = load_ocr_config(&app_handle);
+        let text = if config.ocr_on_screenshot {
+            match recognize_text(&config, Path::new(&path)) {
+                Ok(text) => Some(text),
+                Err(e) => {
+                    error!("❌ OCR failed: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        if let Some(ref text) = text {
+            if !text.is_empty() {
+                if let Err(e) = open_quicknote_with_text(&app_handle, text) {
+                    error!("Failed to insert recognized text into quick note: {}", e);
+                }
+            }
+        }
+
+        let event = ScreenshotCapturedEvent { path, text };
+        if let Err(e) = app_handle.emit("screenshot-captured", &event) {
+            error!("Failed to emit screenshot-captured event: {}", e);
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_ocr_config(app: AppHandle) -> OcrConfig {
+    load_ocr_config(&app)
+}
+
+#[tauri::command]
+pub fn save_ocr_config(app: AppHandle, config: OcrConfig) -> Result<(), String> {
+    if config.language.trim().is_empty() {
+        return Err("OCR language must not be empty".to_string());
+    }
+    save_json(&app, OCR_CONFIG_FILE, &config)
+}
+
+#[tauri::command]
+pub async fn recognize_text_in_image(app: AppHandle, path: String) -> Result<String, String> {
+    let config = load_ocr_config(&app);
+    tauri::async_runtime::spawn_blocking(move || recognize_text(&config, Path::new(&path)))
+        .await
+        .map_err(|e| format!("OCR task failed: {}", e))?
+}
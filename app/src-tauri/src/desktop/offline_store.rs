@@ -0,0 +1,681 @@
+use tauri::{AppHandle, Emitter};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::desktop::{
+    generate_id, get_app_data_dir, is_background_sync_allowed, merge_markdown, now_millis, protect_text, read_secret,
+    schedule_os_search_refresh, snapshot_note_version, spawn_api_queue_replay, unprotect_text, ApiRequest,
+};
+
+const OFFLINE_DB_FILE: &str = "offline.db";
+const REPLAY_RETRY_INTERVAL: Duration = Duration::from_secs(60);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(20);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SyncStatus {
+    pub online: bool,
+    pub syncing: bool,
+    #[serde(rename = "pendingCount")]
+    pub pending_count: u32,
+    #[serde(rename = "conflictCount")]
+    pub conflict_count: u32,
+    #[serde(rename = "failedCount")]
+    pub failed_count: u32,
+    #[serde(rename = "lastSyncedAt")]
+    pub last_synced_at: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PendingOperation {
+    pub id: i64,
+    /// "create", "update" or "delete"
+    pub kind: String,
+    #[serde(rename = "noteId")]
+    pub note_id: Option<i64>,
+    /// Temporary id for notes created offline
+    #[serde(rename = "localId")]
+    pub local_id: Option<String>,
+    pub payload: Value,
+    /// Server `updatedAt` the edit was based on, used for conflict detection
+    #[serde(rename = "baseUpdatedAt")]
+    pub base_updated_at: Option<String>,
+    /// Server content the edit was based on, the common ancestor for merging
+    #[serde(rename = "baseContent")]
+    pub base_content: Option<String>,
+    /// "pending", "conflict" or "failed"
+    pub status: String,
+    pub error: Option<String>,
+    pub attempts: u32,
+    #[serde(rename = "createdAt")]
+    pub created_at: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OfflineConflictEvent {
+    #[serde(rename = "operationId")]
+    pub operation_id: i64,
+    #[serde(rename = "noteId")]
+    pub note_id: i64,
+    pub local: Value,
+    pub server: Value,
+    pub base: Option<String>,
+    /// Three-way merge with conflict markers around the regions both sides changed
+    pub merged: Option<String>,
+    pub conflicts: usize,
+}
+
+/// How `resolve_conflict` settles a conflicting offline edit
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum ConflictResolution {
+    /// Overwrite the server copy with the local edit
+    Local,
+    /// Drop the local edit
+    Server,
+    /// Save this content, usually the merge after the user removed the markers
+    Content { content: String },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OfflineNoteSyncedEvent {
+    #[serde(rename = "localId")]
+    pub local_id: Option<String>,
+    pub note: Value,
+}
+
+#[derive(Debug, Clone)]
+struct ServerCredentials {
+    url: String,
+    token: String,
+}
+
+static OFFLINE_DB: LazyLock<Mutex<Option<Connection>>> = LazyLock::new(|| Mutex::new(None));
+// Each account gets its own database so cached notes never leak between servers
+static OFFLINE_DB_NAME: LazyLock<Mutex<String>> = LazyLock::new(|| Mutex::new(OFFLINE_DB_FILE.to_string()));
+// Kept in memory only, the frontend hands them over after login
+static SERVER_CREDENTIALS: LazyLock<Mutex<Option<ServerCredentials>>> = LazyLock::new(|| Mutex::new(None));
+static ONLINE: AtomicBool = AtomicBool::new(true);
+static SYNCING: AtomicBool = AtomicBool::new(false);
+static RETRY_LOOP_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Disk used by the offline databases of all accounts, including their WAL files
+pub fn offline_db_size(app: &AppHandle) -> u64 {
+    let Ok(entries) = get_app_data_dir(app).and_then(|dir| std::fs::read_dir(dir).map_err(|e| e.to_string())) else {
+        return 0;
+    };
+    entries.flatten()
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            name.starts_with("offline") && name.contains(".db")
+        })
+        .filter_map(|entry| entry.metadata().ok())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+/// Server the frontend is signed in to, if it has configured one
+pub fn get_configured_server_url() -> Option<String> {
+    SERVER_CREDENTIALS.lock().unwrap().as_ref().map(|c| c.url.clone())
+}
+
+/// Swap in a refreshed token for the configured server
+pub fn update_server_token(token: &str) {
+    if let Some(credentials) = SERVER_CREDENTIALS.lock().unwrap().as_mut() {
+        credentials.token = token.to_string();
+    }
+}
+
+/// Token for requests to the configured server, `None` for other hosts
+pub fn get_server_token_for_url(url: &str) -> Option<String> {
+    SERVER_CREDENTIALS.lock().unwrap().as_ref()
+        .filter(|c| url.starts_with(c.url.trim_end_matches('/')))
+        .map(|c| c.token.clone())
+}
+
+fn open_offline_db(app: &AppHandle) -> Result<Connection, String> {
+    let path = get_app_data_dir(app)?.join(OFFLINE_DB_NAME.lock().unwrap().as_str());
+    let conn = Connection::open(&path)
+        .map_err(|e| format!("Failed to open offline database: {}", e))?;
+
+    conn.execute_batch(
+        "PRAGMA journal_mode = WAL;
+         CREATE TABLE IF NOT EXISTS notes (
+             id INTEGER PRIMARY KEY,
+             content TEXT NOT NULL,
+             updated_at TEXT,
+             is_archived INTEGER NOT NULL DEFAULT 0,
+             data TEXT NOT NULL
+         );
+         CREATE TABLE IF NOT EXISTS pending_ops (
+             id INTEGER PRIMARY KEY AUTOINCREMENT,
+             kind TEXT NOT NULL,
+             note_id INTEGER,
+             local_id TEXT,
+             payload TEXT NOT NULL,
+             base_updated_at TEXT,
+             status TEXT NOT NULL DEFAULT 'pending',
+             error TEXT,
+             attempts INTEGER NOT NULL DEFAULT 0,
+             created_at INTEGER NOT NULL
+         );
+         CREATE TABLE IF NOT EXISTS meta (
+             key TEXT PRIMARY KEY,
+             value TEXT NOT NULL
+         );
+         CREATE TABLE IF NOT EXISTS note_versions (
+             id INTEGER PRIMARY KEY AUTOINCREMENT,
+             note_id INTEGER NOT NULL,
+             content TEXT NOT NULL,
+             source TEXT NOT NULL,
+             created_at INTEGER NOT NULL
+         );
+         CREATE INDEX IF NOT EXISTS note_versions_note ON note_versions (note_id, id);",
+    )
+    .map_err(|e| format!("Failed to initialize offline database: {}", e))?;
+    // Added after the first release, fails harmlessly once the column exists
+    let _ = conn.execute("ALTER TABLE pending_ops ADD COLUMN base_content TEXT", []);
+
+    info!("💾 Offline store opened at {}", path.display());
+    Ok(conn)
+}
+
+/// Run a closure against the lazily opened offline database
+pub fn with_offline_db<T>(
+    app: &AppHandle,
+    f: impl FnOnce(&Connection) -> rusqlite::Result<T>,
+) -> Result<T, String> {
+    let mut guard = OFFLINE_DB.lock().unwrap();
+    if guard.is_none() {
+        *guard = Some(open_offline_db(app)?);
+    }
+    f(guard.as_ref().unwrap()).map_err(|e| format!("Offline database error: {}", e))
+}
+
+/// Close the database so its file can be replaced, it is reopened on next use
+pub fn close_offline_db() {
+    *OFFLINE_DB.lock().unwrap() = None;
+}
+
+fn note_id_of(note: &Value) -> Option<i64> {
+    note.get("id").and_then(|v| v.as_i64())
+}
+
+fn updated_at_of(note: &Value) -> Option<String> {
+    note.get("updatedAt").and_then(|v| v.as_str()).map(String::from)
+}
+
+/// Note text goes through the at-rest encryption layer when it is enabled
+pub fn protect_column(text: &str) -> rusqlite::Result<String> {
+    protect_text(text).map_err(|e| rusqlite::Error::ToSqlConversionFailure(std::io::Error::other(e).into()))
+}
+
+pub fn unprotect_column(index: usize, text: String) -> rusqlite::Result<String> {
+    unprotect_text(text).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(index, rusqlite::types::Type::Text, std::io::Error::other(e).into())
+    })
+}
+
+fn upsert_cached_note(conn: &Connection, note: &Value) -> rusqlite::Result<()> {
+    let Some(id) = note_id_of(note) else { return Ok(()) };
+    conn.execute(
+        "INSERT INTO notes (id, content, updated_at, is_archived, data) VALUES (?1, ?2, ?3, ?4, ?5)
+         ON CONFLICT(id) DO UPDATE SET content = ?2, updated_at = ?3, is_archived = ?4, data = ?5",
+        params![
+            id,
+            protect_column(note.get("content").and_then(|v| v.as_str()).unwrap_or_default())?,
+            updated_at_of(note),
+            note.get("isArchived").and_then(|v| v.as_bool()).unwrap_or(false),
+            protect_column(&note.to_string())?,
+        ],
+    )?;
+    Ok(())
+}
+
+fn read_operations(conn: &Connection, status: Option<&str>) -> rusqlite::Result<Vec<PendingOperation>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, kind, note_id, local_id, payload, base_updated_at, status, error, attempts, created_at, base_content
+         FROM pending_ops WHERE (?1 IS NULL OR status = ?1) ORDER BY id",
+    )?;
+    let rows = stmt.query_map(params![status], |row| {
+        let payload = unprotect_column(4, row.get(4)?)?;
+        Ok(PendingOperation {
+            id: row.get(0)?,
+            kind: row.get(1)?,
+            note_id: row.get(2)?,
+            local_id: row.get(3)?,
+            payload: serde_json::from_str(&payload).unwrap_or(Value::Null),
+            base_updated_at: row.get(5)?,
+            status: row.get(6)?,
+            error: row.get(7)?,
+            attempts: row.get(8)?,
+            created_at: row.get::<_, i64>(9)? as u64,
+            base_content: row.get::<_, Option<String>>(10)?.map(|text| unprotect_column(10, text)).transpose()?,
+        })
+    })?;
+    rows.collect()
+}
+
+/// Current queue and connectivity state
+pub fn read_sync_status(app: &AppHandle) -> Result<SyncStatus, String> {
+    let (pending, conflicts, failed, last_synced) = with_offline_db(app, |conn| {
+        let count = |status: &str| -> rusqlite::Result<u32> {
+            conn.query_row("SELECT COUNT(*) FROM pending_ops WHERE status = ?1", params![status], |row| row.get(0))
+        };
+        let last_synced: Option<String> = conn.query_row(
+            "SELECT value FROM meta WHERE key = 'last_synced_at'", [], |row| row.get(0),
+        ).optional()?;
+        Ok((count("pending")?, count("conflict")?, count("failed")?, last_synced))
+    })?;
+
+    Ok(SyncStatus {
+        online: ONLINE.load(Ordering::SeqCst),
+        syncing: SYNCING.load(Ordering::SeqCst),
+        pending_count: pending,
+        conflict_count: conflicts,
+        failed_count: failed,
+        last_synced_at: last_synced.and_then(|v| v.parse().ok()),
+    })
+}
+
+fn emit_sync_status(app: &AppHandle) {
+    if let Ok(status) = read_sync_status(app) {
+        let _ = app.emit("offline-sync-status", status);
+    }
+}
+
+/// Update the online flag, replaying the queue when the server becomes reachable again
+pub fn set_offline_online(app: &AppHandle, online: bool) {
+    let was_online = ONLINE.swap(online, Ordering::SeqCst);
+    if was_online != online {
+        info!("💾 Offline store is now {}", if online { "online" } else { "offline" });
+        emit_sync_status(app);
+        if online {
+            spawn_offline_replay(app);
+            spawn_api_queue_replay(app);
+        }
+    }
+}
+
+/// POST to the server through the shared API client; `create` is not retried once it may have been applied
+fn server_request(app: &AppHandle, path: &str, body: &Value, idempotent: bool) -> Result<Value, String> {
+    ApiRequest::post(path, body.clone())
+        .timeout(REQUEST_TIMEOUT)
+        .idempotent(idempotent)
+        .send(app)
+        .map_err(String::from)
+}
+
+fn is_network_error(error: &str) -> bool {
+    error.starts_with("Network error")
+}
+
+/// Apply one queued operation to the server
+fn replay_operation(app: &AppHandle, op: &PendingOperation) -> Result<(), String> {
+    match op.kind.as_str() {
+        "create" => {
+            let mut body = op.payload.clone();
+            if let Some(obj) = body.as_object_mut() {
+                obj.remove("id");
+            }
+            let note = server_request(app, "/api/v1/note/upsert", &body, false)?;
+            with_offline_db(app, |conn| {
+                upsert_cached_note(conn, &note)?;
+                conn.execute("DELETE FROM pending_ops WHERE id = ?1", params![op.id])?;
+                Ok(())
+            })?;
+            let _ = app.emit("offline-note-synced", OfflineNoteSyncedEvent {
+                local_id: op.local_id.clone(),
+                note,
+            });
+        }
+        "update" => {
+            let note_id = op.note_id.ok_or("Queued update has no note id")?;
+            let server_note = server_request(app, "/api/v1/note/detail", &json!({ "id": note_id }), true)?;
+
+            let mut payload = op.payload.clone();
+            if op.base_updated_at.is_some() && updated_at_of(&server_note) != op.base_updated_at {
+                let local_content = op.payload.get("content").and_then(|v| v.as_str());
+                let server_content = server_note.get("content").and_then(|v| v.as_str());
+                let merge = match (op.base_content.as_deref(), local_content, server_content) {
+                    (Some(base), Some(local), Some(server)) => Some(merge_markdown(base, local, server)),
+                    _ => None,
+                };
+
+                match merge {
+                    Some(merge) if merge.conflicts == 0 => {
+                        info!("🔀 Merged offline edit of note {} with server changes", note_id);
+                        if let Some(obj) = payload.as_object_mut() {
+                            obj.insert("content".to_string(), Value::String(merge.content));
+                        }
+                    }
+                    merge => {
+                        with_offline_db(app, |conn| {
+                            conn.execute("UPDATE pending_ops SET status = 'conflict' WHERE id = ?1", params![op.id])?;
+                            Ok(())
+                        })?;
+                        warn!("⚠️ Conflict while replaying edit of note {}", note_id);
+                        let _ = app.emit("sync-conflict", OfflineConflictEvent {
+                            operation_id: op.id,
+                            note_id,
+                            local: op.payload.clone(),
+                            server: server_note,
+                            base: op.base_content.clone(),
+                            conflicts: merge.as_ref().map(|m| m.conflicts).unwrap_or(1),
+                            merged: merge.map(|m| m.content),
+                        });
+                        return Ok(());
+                    }
+                }
+            }
+
+            // The server copy is about to be overwritten, keep it recoverable
+            if let Some(content) = server_note.get("content").and_then(|v| v.as_str()) {
+                with_offline_db(app, |conn| snapshot_note_version(conn, note_id, content, "server"))?;
+            }
+            let note = server_request(app, "/api/v1/note/upsert", &payload, true)?;
+            with_offline_db(app, |conn| {
+                upsert_cached_note(conn, &note)?;
+                conn.execute("DELETE FROM pending_ops WHERE id = ?1", params![op.id])?;
+                Ok(())
+            })?;
+            let _ = app.emit("offline-note-synced", OfflineNoteSyncedEvent { local_id: None, note });
+        }
+        "delete" => {
+            let note_id = op.note_id.ok_or("Queued delete has no note id")?;
+            server_request(app, "/api/v1/note/batch-trash", &json!({ "ids": [note_id] }), true)?;
+            with_offline_db(app, |conn| {
+                conn.execute("DELETE FROM pending_ops WHERE id = ?1", params![op.id])?;
+                Ok(())
+            })?;
+        }
+        other => return Err(format!("Unknown queued operation: {}", other)),
+    }
+    Ok(())
+}
+
+/// Replay every pending operation in order, stopping at the first network failure
+pub fn replay_offline_queue_now(app: &AppHandle) -> Result<SyncStatus, String> {
+    if SERVER_CREDENTIALS.lock().unwrap().is_none() {
+        return Err("Server credentials are not configured".to_string());
+    }
+
+    if SYNCING.swap(true, Ordering::SeqCst) {
+        return read_sync_status(app);
+    }
+    emit_sync_status(app);
+
+    let result = (|| {
+        let ops = with_offline_db(app, |conn| read_operations(conn, Some("pending")))?;
+        for op in ops {
+            if let Err(e) = replay_operation(app, &op) {
+                if is_network_error(&e) {
+                    set_offline_online(app, false);
+                    return Ok(());
+                }
+                error!("❌ Failed to replay queued {} #{}: {}", op.kind, op.id, e);
+                with_offline_db(app, |conn| {
+                    conn.execute(
+                        "UPDATE pending_ops SET status = 'failed', error = ?2, attempts = attempts + 1 WHERE id = ?1",
+                        params![op.id, e],
+                    )?;
+                    Ok(())
+                })?;
+            }
+        }
+        ONLINE.store(true, Ordering::SeqCst);
+        with_offline_db(app, |conn| {
+            conn.execute(
+                "INSERT OR REPLACE INTO meta (key, value) VALUES ('last_synced_at', ?1)",
+                params![now_millis().to_string()],
+            )?;
+            Ok(())
+        })
+    })();
+
+    SYNCING.store(false, Ordering::SeqCst);
+    emit_sync_status(app);
+    result.and_then(|_| read_sync_status(app))
+}
+
+/// Replay the queue on a background thread
+pub fn spawn_offline_replay(app: &AppHandle) {
+    let app_handle = app.clone();
+    std::thread::spawn(move || {
+        if let Err(e) = replay_offline_queue_now(&app_handle) {
+            warn!("Offline replay skipped: {}", e);
+        }
+    });
+}
+
+/// Retry the queue periodically while operations are waiting
+fn start_offline_retry_loop(app: &AppHandle) {
+    if RETRY_LOOP_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let app_handle = app.clone();
+    std::thread::spawn(move || loop {
+        std::thread::sleep(REPLAY_RETRY_INTERVAL);
+        let has_pending = read_sync_status(&app_handle)
+            .map(|s| s.pending_count > 0)
+            .unwrap_or(false);
+        if has_pending && is_background_sync_allowed() && SERVER_CREDENTIALS.lock().unwrap().is_some() {
+            if let Err(e) = replay_offline_queue_now(&app_handle) {
+                error!("Offline replay failed: {}", e);
+            }
+        }
+    });
+}
+
+/// Point the offline store at another account's database and server
+pub fn switch_offline_account(app: &AppHandle, account_id: &str, server_url: &str, token: String) {
+    *OFFLINE_DB.lock().unwrap() = None;
+    *OFFLINE_DB_NAME.lock().unwrap() = format!("offline_{}.db", account_id);
+    *SERVER_CREDENTIALS.lock().unwrap() = Some(ServerCredentials { url: server_url.to_string(), token });
+    start_offline_retry_loop(app);
+    emit_sync_status(app);
+    spawn_offline_replay(app);
+}
+
+/// Provide the server address and token used to replay queued changes.
+/// Without a token the one stored in the keychain for this server is used.
+#[tauri::command]
+pub fn configure_offline_sync(app: AppHandle, server_url: String, token: Option<String>) -> Result<(), String> {
+    if server_url.trim().is_empty() {
+        return Err("Server URL is required".to_string());
+    }
+    let token = match token {
+        Some(token) => token,
+        None => read_secret(Some(&server_url))?
+            .ok_or("No token stored for this server")?,
+    };
+    *SERVER_CREDENTIALS.lock().unwrap() = Some(ServerCredentials { url: server_url, token });
+    start_offline_retry_loop(&app);
+    spawn_offline_replay(&app);
+    Ok(())
+}
+
+/// Store notes fetched from the server
+#[tauri::command]
+pub fn cache_notes(app: AppHandle, notes: Vec<Value>) -> Result<usize, String> {
+    with_offline_db(&app, |conn| {
+        let tx = conn.unchecked_transaction()?;
+        for note in &notes {
+            upsert_cached_note(&tx, note)?;
+        }
+        tx.commit()?;
+        Ok(notes.len())
+    })
+    .inspect(|_| schedule_os_search_refresh(&app))
+}
+
+/// Read cached notes, newest first
+#[tauri::command]
+pub fn get_cached_notes(app: AppHandle, include_archived: Option<bool>) -> Result<Vec<Value>, String> {
+    with_offline_db(&app, |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT data FROM notes WHERE (?1 OR is_archived = 0) ORDER BY updated_at DESC",
+        )?;
+        let rows = stmt.query_map(params![include_archived.unwrap_or(false)], |row| unprotect_column(0, row.get(0)?))?;
+        let rows: Vec<String> = rows.collect::<rusqlite::Result<_>>()?;
+        Ok(rows.iter()
+            .filter_map(|data| serde_json::from_str(data).ok())
+            .collect())
+    })
+}
+
+/// Content of the given cached notes, for search snippets
+pub fn cached_note_contents(app: &AppHandle, ids: &[i64]) -> Result<HashMap<i64, String>, String> {
+    with_offline_db(app, |conn| {
+        let mut stmt = conn.prepare("SELECT content FROM notes WHERE id = ?1")?;
+        let mut contents = HashMap::with_capacity(ids.len());
+        for &id in ids {
+            let content = stmt.query_row(params![id], |row| unprotect_column(0, row.get(0)?)).optional()?;
+            if let Some(content) = content {
+                contents.insert(id, content);
+            }
+        }
+        Ok(contents)
+    })
+}
+
+/// Queue a create/update/delete made while offline and apply it to the local cache.
+/// Returns the local id for created notes so the UI can track them until they sync.
+#[tauri::command]
+pub fn queue_note_change(app: AppHandle, kind: String, note: Value) -> Result<Option<String>, String> {
+    if !["create", "update", "delete"].contains(&kind.as_str()) {
+        return Err(format!("Unknown change kind: {}", kind));
+    }
+
+    let note_id = note_id_of(&note);
+    if kind != "create" && note_id.is_none() {
+        return Err("Note id is required".to_string());
+    }
+    let local_id = if kind == "create" { Some(generate_id()) } else { None };
+
+    with_offline_db(&app, |conn| {
+        // `data` keeps the last server copy, local edits only touch `content`
+        let (base_updated_at, base_data): (Option<String>, Option<String>) = match note_id {
+            Some(id) => conn.query_row(
+                "SELECT updated_at, data FROM notes WHERE id = ?1",
+                params![id],
+                |row| Ok((row.get(0)?, Some(unprotect_column(1, row.get(1)?)?))),
+            )
+                .optional()?
+                .unwrap_or((None, None)),
+            None => (None, None),
+        };
+        let base_content = base_data
+            .and_then(|data| serde_json::from_str::<Value>(&data).ok())
+            .and_then(|data| data.get("content").and_then(|v| v.as_str()).map(String::from))
+            .map(|content| protect_column(&content))
+            .transpose()?;
+
+        conn.execute(
+            "INSERT INTO pending_ops (kind, note_id, local_id, payload, base_updated_at, created_at, base_content)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![kind, note_id, local_id, protect_column(&note.to_string())?, base_updated_at, now_millis() as i64, base_content],
+        )?;
+
+        match kind.as_str() {
+            "update" => {
+                if let Some(id) = note_id {
+                    if let Some(content) = note.get("content").and_then(|v| v.as_str()) {
+                        snapshot_note_version(conn, id, content, "local")?;
+                    }
+                    let content = note.get("content").and_then(|v| v.as_str()).map(protect_column).transpose()?;
+                    conn.execute("UPDATE notes SET content = COALESCE(?2, content) WHERE id = ?1",
+                        params![id, content])?;
+                }
+            }
+            "delete" => {
+                conn.execute("DELETE FROM notes WHERE id = ?1", params![note_id])?;
+            }
+            _ => {}
+        }
+        Ok(())
+    })?;
+
+    emit_sync_status(&app);
+    if ONLINE.load(Ordering::SeqCst) && SERVER_CREDENTIALS.lock().unwrap().is_some() {
+        spawn_offline_replay(&app);
+    }
+    Ok(local_id)
+}
+
+#[tauri::command]
+pub fn get_sync_status(app: AppHandle) -> Result<SyncStatus, String> {
+    read_sync_status(&app)
+}
+
+#[tauri::command]
+pub fn get_pending_operations(app: AppHandle) -> Result<Vec<PendingOperation>, String> {
+    with_offline_db(&app, |conn| read_operations(conn, None))
+}
+
+#[tauri::command]
+pub async fn replay_offline_queue(app: AppHandle) -> Result<SyncStatus, String> {
+    tauri::async_runtime::spawn_blocking(move || replay_offline_queue_now(&app))
+        .await
+        .map_err(|e| format!("Replay task failed: {}", e))?
+}
+
+/// Resolve a conflict by keeping the local edit (overwrite server) or dropping it
+#[tauri::command]
+pub fn resolve_offline_conflict(app: AppHandle, operation_id: i64, keep_local: bool) -> Result<(), String> {
+    let resolution = if keep_local { ConflictResolution::Local } else { ConflictResolution::Server };
+    resolve_conflict(app, operation_id, resolution)
+}
+
+/// Settle a `sync-conflict`: keep the local edit, keep the server copy or save resolved content
+#[tauri::command]
+pub fn resolve_conflict(app: AppHandle, id: i64, resolution: ConflictResolution) -> Result<(), String> {
+    let replay = with_offline_db(&app, |conn| {
+        match resolution {
+            ConflictResolution::Server => {
+                conn.execute("DELETE FROM pending_ops WHERE id = ?1", params![id])?;
+                return Ok(false);
+            }
+            ConflictResolution::Content { ref content } => {
+                let payload: Option<String> = conn.query_row(
+                    "SELECT payload FROM pending_ops WHERE id = ?1", params![id], |row| unprotect_column(0, row.get(0)?),
+                ).optional()?;
+                let mut payload: Value = payload.and_then(|p| serde_json::from_str(&p).ok()).unwrap_or_else(|| json!({}));
+                if let Some(obj) = payload.as_object_mut() {
+                    obj.insert("content".to_string(), Value::String(content.clone()));
+                }
+                conn.execute("UPDATE pending_ops SET payload = ?2 WHERE id = ?1", params![id, protect_column(&payload.to_string())?])?;
+            }
+            ConflictResolution::Local => {}
+        }
+        // Clearing the base version makes the next replay overwrite the server copy
+        conn.execute(
+            "UPDATE pending_ops SET status = 'pending', base_updated_at = NULL, error = NULL WHERE id = ?1",
+            params![id],
+        )?;
+        Ok(true)
+    })?;
+
+    emit_sync_status(&app);
+    if replay {
+        spawn_offline_replay(&app);
+    }
+    Ok(())
+}
+
+/// Put failed operations back into the queue
+#[tauri::command]
+pub fn retry_failed_operations(app: AppHandle) -> Result<(), String> {
+    with_offline_db(&app, |conn| {
+        conn.execute("UPDATE pending_ops SET status = 'pending', error = NULL WHERE status = 'failed'", [])?;
+        Ok(())
+    })?;
+    spawn_offline_replay(&app);
+    Ok(())
+}
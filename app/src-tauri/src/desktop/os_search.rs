@@ -0,0 +1,306 @@
+use tauri::AppHandle;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use crate::desktop::{get_cached_notes, get_encryption_status, load_json_or_default, note_id, note_tags, note_title, save_json};
+
+const OS_SEARCH_CONFIG_FILE: &str = "os_search.json";
+/// Only the most recently updated notes get a stub
+const MAX_INDEXED_NOTES: usize = 500;
+const SNIPPET_CHARS: usize = 200;
+// Notes are cached in batches while syncing, index once things settle
+const REFRESH_DELAY: Duration = Duration::from_secs(10);
+
+#[cfg(target_os = "macos")]
+const SPOTLIGHT_DOMAIN: &str = "notes";
+#[cfg(target_os = "windows")]
+const START_MENU_FOLDER: &str = "Blinko Notes";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct OsSearchConfig {
+    /// Publish titles and snippets of cached notes to Spotlight or Windows Search (opt-in)
+    pub enabled: bool,
+}
+
+/// Title and snippet of a note as shown by the OS search, opening it deep links to the note
+#[derive(Debug, Serialize, Clone)]
+struct NoteStub {
+    id: i64,
+    title: String,
+    snippet: String,
+    tags: Vec<String>,
+}
+
+// Bumped on every scheduled refresh so only the last one runs
+static OS_SEARCH_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+pub fn load_os_search_config(app: &AppHandle) -> OsSearchConfig {
+    load_json_or_default(app, OS_SEARCH_CONFIG_FILE)
+}
+
+fn note_snippet(note: &Value) -> String {
+    let content = note.get("content").and_then(|v| v.as_str()).unwrap_or_default();
+    // The first non-empty line is already the title
+    let body = content.lines()
+        .skip_while(|line| line.trim_start_matches('#').trim().is_empty())
+        .skip(1)
+        .flat_map(|line| line.split_whitespace())
+        .collect::<Vec<_>>()
+        .join(" ");
+    body.chars().take(SNIPPET_CHARS).collect()
+}
+
+fn collect_note_stubs(app: &AppHandle) -> Result<Vec<NoteStub>, String> {
+    let notes = get_cached_notes(app.clone(), Some(false))?;
+    Ok(notes.iter()
+        .filter_map(|note| Some(NoteStub {
+            id: note_id(note)?,
+            title: note_title(note),
+            snippet: note_snippet(note),
+            tags: note_tags(note),
+        }))
+        .take(MAX_INDEXED_NOTES)
+        .collect())
+}
+
+#[cfg(target_os = "macos")]
+#[link(name = "CoreSpotlight", kind = "framework")]
+extern "C" {}
+
+/// Replace the Blinko items in the Core Spotlight index
+#[cfg(target_os = "macos")]
+fn write_platform_index(_app: &AppHandle, stubs: &[NoteStub]) -> Result<(), String> {
+    use block2::Block;
+    use objc2::rc::{Allocated, Retained};
+    use objc2::runtime::AnyObject;
+    use objc2::{class, msg_send};
+    use objc2_foundation::{NSArray, NSError, NSString};
+
+    // SAFETY: plain Core Spotlight calls, completion handlers are optional and every object is retained
+    unsafe {
+        let index: Retained<AnyObject> = msg_send![class!(CSSearchableIndex), defaultSearchableIndex];
+        let domain = NSString::from_str(SPOTLIGHT_DOMAIN);
+        let domains = NSArray::from_retained_slice(&[domain.clone()]);
+        let no_handler: Option<&Block<dyn Fn(*mut NSError)>> = None;
+        let _: () = msg_send![&*index, deleteSearchableItemsWithDomainIdentifiers: &*domains, completionHandler: no_handler];
+
+        let items: Vec<Retained<AnyObject>> = stubs.iter().map(|stub| {
+            let attributes: Allocated<AnyObject> = msg_send![class!(CSSearchableItemAttributeSet), alloc];
+            let attributes: Retained<AnyObject> =
+                msg_send![attributes, initWithItemContentType: &*NSString::from_str("public.text")];
+            let _: () = msg_send![&*attributes, setTitle: &*NSString::from_str(&stub.title)];
+            let _: () = msg_send![&*attributes, setContentDescription: &*NSString::from_str(&stub.snippet)];
+            let keywords: Vec<Retained<NSString>> = stub.tags.iter().map(|tag| NSString::from_str(tag)).collect();
+            let _: () = msg_send![&*attributes, setKeywords: &*NSArray::from_retained_slice(&keywords)];
+
+            let item: Allocated<AnyObject> = msg_send![class!(CSSearchableItem), alloc];
+            msg_send![
+                item,
+                initWithUniqueIdentifier: &*NSString::from_str(&stub.id.to_string()),
+                domainIdentifier: &*domain,
+                attributeSet: &*attributes
+            ]
+        }).collect();
+        if !items.is_empty() {
+            let _: () = msg_send![&*index, indexSearchableItems: &*NSArray::from_retained_slice(&items), completionHandler: no_handler];
+        }
+    }
+    Ok(())
+}
+
+/// Spotlight hands a picked result back as a user activity carrying the note id
+#[cfg(target_os = "macos")]
+fn register_spotlight_activity_handler(app: &AppHandle) {
+    use objc2::rc::Retained;
+    use objc2::runtime::{AnyObject, Bool, Sel};
+    use objc2::{class, msg_send, sel};
+    use objc2_foundation::NSString;
+    use std::sync::OnceLock;
+
+    static SPOTLIGHT_APP: OnceLock<AppHandle> = OnceLock::new();
+
+    unsafe extern "C-unwind" fn continue_user_activity(
+        _this: &AnyObject,
+        _cmd: Sel,
+        _application: &AnyObject,
+        activity: &AnyObject,
+        _restoration_handler: *mut AnyObject,
+    ) -> Bool {
+        let activity_type: Retained<NSString> = msg_send![activity, activityType];
+        if activity_type.to_string() != "com.apple.corespotlightitem" {
+            return Bool::NO;
+        }
+        let user_info: Option<Retained<AnyObject>> = msg_send![activity, userInfo];
+        let key = NSString::from_str("kCSSearchableItemActivityIdentifier");
+        let note_id: Option<Retained<NSString>> = match user_info {
+            Some(ref info) => msg_send![&**info, objectForKey: &*key],
+            None => None,
+        };
+        match (SPOTLIGHT_APP.get(), note_id) {
+            (Some(app), Some(note_id)) => {
+                info!("🔍 Opening note {} from Spotlight", note_id);
+                crate::desktop::handle_deep_link(app, &format!("{}://note/{}", crate::desktop::DEEP_LINK_SCHEME, note_id));
+                Bool::YES
+            }
+            _ => Bool::NO,
+        }
+    }
+
+    if SPOTLIGHT_APP.set(app.clone()).is_err() {
+        return;
+    }
+    // SAFETY: the app delegate is tao's, which does not implement the method, so adding it
+    // changes nothing else; the type encoding matches the function signature
+    unsafe {
+        let application: Retained<AnyObject> = msg_send![class!(NSApplication), sharedApplication];
+        let delegate: Option<Retained<AnyObject>> = msg_send![&*application, delegate];
+        let Some(delegate) = delegate else {
+            warn!("⚠️ No app delegate, Spotlight results cannot open notes");
+            return;
+        };
+        let implementation: unsafe extern "C-unwind" fn(&AnyObject, Sel, &AnyObject, &AnyObject, *mut AnyObject) -> Bool =
+            continue_user_activity;
+        objc2::ffi::class_addMethod(
+            delegate.class() as *const _ as *mut _,
+            sel!(application:continueUserActivity:restorationHandler:),
+            Some(std::mem::transmute::<_, objc2::runtime::Imp>(implementation)),
+            c"B@:@@@?".as_ptr(),
+        );
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn start_menu_notes_dir(app: &AppHandle) -> Result<std::path::PathBuf, String> {
+    use tauri::Manager;
+
+    let roaming = app.path().data_dir().map_err(|e| format!("Failed to get AppData folder: {}", e))?;
+    Ok(roaming.join("Microsoft").join("Windows").join("Start Menu").join("Programs").join(START_MENU_FOLDER))
+}
+
+/// Windows Search indexes Start menu shortcuts, one per note with the snippet as its comment
+#[cfg(target_os = "windows")]
+fn write_platform_index(app: &AppHandle, stubs: &[NoteStub]) -> Result<(), String> {
+    use serde_json::json;
+    use std::collections::HashSet;
+
+    let dir = start_menu_notes_dir(app)?;
+    if dir.exists() {
+        std::fs::remove_dir_all(&dir).map_err(|e| format!("Failed to clear {}: {}", dir.display(), e))?;
+    }
+    if stubs.is_empty() {
+        return Ok(());
+    }
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+
+    let mut used_names = HashSet::new();
+    let shortcuts: Vec<Value> = stubs.iter().map(|stub| {
+        let mut name = crate::importer::sanitize_file_name(&stub.title);
+        if !used_names.insert(name.to_lowercase()) {
+            name = format!("{} ({})", name, stub.id);
+        }
+        json!({
+            "path": dir.join(format!("{}.lnk", name)),
+            "arguments": format!("{}://note/{}", crate::desktop::DEEP_LINK_SCHEME, stub.id),
+            "description": stub.snippet,
+        })
+    }).collect();
+
+    let manifest = std::env::temp_dir().join("blinko-search-stubs.json");
+    std::fs::write(&manifest, Value::from(shortcuts).to_string())
+        .map_err(|e| format!("Failed to write search stubs: {}", e))?;
+    let exe = std::env::current_exe().map_err(|e| format!("Failed to locate Blinko executable: {}", e))?;
+
+    // One PowerShell run for all shortcuts, paths and text go through the manifest so they need no quoting
+    let status = crate::desktop::background_command("powershell")
+        .args([
+            "-NoProfile",
+            "-Command",
+            "$shell = New-Object -ComObject WScript.Shell; \
+             foreach ($item in (Get-Content -Raw -Encoding UTF8 $env:BLINKO_MANIFEST | ConvertFrom-Json)) { \
+                 $s = $shell.CreateShortcut($item.path); $s.TargetPath = $env:BLINKO_EXE; $s.Arguments = $item.arguments; \
+                 $s.Description = $item.description; $s.IconLocation = $env:BLINKO_EXE; $s.Save() }",
+        ])
+        .env("BLINKO_MANIFEST", &manifest)
+        .env("BLINKO_EXE", &exe)
+        .status();
+    let _ = std::fs::remove_file(&manifest);
+
+    match status {
+        Ok(status) if status.success() => Ok(()),
+        Ok(_) => Err("Failed to create search shortcuts".to_string()),
+        Err(e) => Err(format!("Failed to create search shortcuts: {}", e)),
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn write_platform_index(_app: &AppHandle, _stubs: &[NoteStub]) -> Result<(), String> {
+    Err("OS search indexing is not supported on this platform".to_string())
+}
+
+/// Rebuild the OS search stubs from the cached notes, or remove them when indexing is off
+pub fn rebuild_os_search_index(app: &AppHandle) -> Result<usize, String> {
+    if !load_os_search_config(app).enabled {
+        write_platform_index(app, &[])?;
+        return Ok(0);
+    }
+    // Snippets would put encrypted note text in plaintext OS indexes
+    if get_encryption_status(app.clone()).enabled {
+        write_platform_index(app, &[])?;
+        return Err("OS search indexing is unavailable while local encryption is on".to_string());
+    }
+
+    let stubs = collect_note_stubs(app)?;
+    write_platform_index(app, &stubs)?;
+    info!("🔍 Published {} note(s) to the OS search index", stubs.len());
+    Ok(stubs.len())
+}
+
+/// Rebuild the index shortly after notes were cached, if indexing is enabled
+pub fn schedule_os_search_refresh(app: &AppHandle) {
+    if !load_os_search_config(app).enabled {
+        return;
+    }
+    let generation = OS_SEARCH_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    let app_handle = app.clone();
+    std::thread::spawn(move || {
+        std::thread::sleep(REFRESH_DELAY);
+        if OS_SEARCH_GENERATION.load(Ordering::SeqCst) != generation {
+            return;
+        }
+        if let Err(e) = rebuild_os_search_index(&app_handle) {
+            warn!("⚠️ Failed to update OS search index: {}", e);
+        }
+    });
+}
+
+/// Let Spotlight results open their note
+pub fn setup_os_search(app: &AppHandle) {
+    #[cfg(target_os = "macos")]
+    register_spotlight_activity_handler(app);
+
+    #[cfg(not(target_os = "macos"))]
+    let _ = app;
+}
+
+#[tauri::command]
+pub fn get_os_search_config(app: AppHandle) -> OsSearchConfig {
+    load_os_search_config(&app)
+}
+
+/// Turn indexing on or off, the stubs are written or removed right away
+#[tauri::command]
+pub async fn set_os_search_enabled(app: AppHandle, enabled: bool) -> Result<usize, String> {
+    save_json(&app, OS_SEARCH_CONFIG_FILE, &OsSearchConfig { enabled })?;
+    tauri::async_runtime::spawn_blocking(move || rebuild_os_search_index(&app))
+        .await
+        .map_err(|e| format!("Failed to update OS search index: {}", e))?
+}
+
+#[tauri::command]
+pub async fn refresh_os_search_index(app: AppHandle) -> Result<usize, String> {
+    tauri::async_runtime::spawn_blocking(move || rebuild_os_search_index(&app))
+        .await
+        .map_err(|e| format!("Failed to update OS search index: {}", e))?
+}
@@ -0,0 +1,51 @@
+use tauri::{AppHandle, Emitter};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::desktop::{get_foreground_app_info, page_title_from_window, queue_note_change, send_notification};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BrowserPage {
+    pub url: String,
+    pub title: String,
+    /// Browser the page is open in, e.g. "Google Chrome"
+    pub browser: String,
+}
+
+/// URL and title of the active tab, when a browser has focus
+pub fn active_browser_page() -> Option<BrowserPage> {
+    let app = get_foreground_app_info()?;
+    let url = app.url.clone()?;
+    Some(BrowserPage {
+        title: page_title_from_window(&app),
+        url,
+        browser: app.name,
+    })
+}
+
+fn bookmark_content(page: &BrowserPage) -> String {
+    let title = if page.title.is_empty() { page.url.as_str() } else { page.title.as_str() };
+    format!("[{}]({})", title.replace('[', "\\[").replace(']', "\\]"), page.url)
+}
+
+/// Save the active browser tab as a bookmark note, queued like any offline edit
+#[tauri::command]
+pub fn capture_current_page(app: AppHandle) -> Result<BrowserPage, String> {
+    let page = active_browser_page().ok_or("No browser tab with a readable address has focus")?;
+    queue_note_change(app.clone(), "create".to_string(), json!({ "content": bookmark_content(&page), "type": 0 }))?;
+    info!("🔖 Captured {}", page.url);
+    let _ = app.emit("page-captured", &page);
+    Ok(page)
+}
+
+/// Global shortcut entry point, reports the result as a notification since Blinko has no focus
+pub fn handle_capture_page_shortcut(app: &AppHandle) {
+    let app_handle = app.clone();
+    std::thread::spawn(move || match capture_current_page(app_handle.clone()) {
+        Ok(page) => send_notification(&app_handle, "Page saved", &page.title),
+        Err(e) => {
+            error!("❌ Page capture failed: {}", e);
+            send_notification(&app_handle, "Page not saved", &e);
+        }
+    });
+}
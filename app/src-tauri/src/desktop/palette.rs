@@ -0,0 +1,129 @@
+use tauri::{AppHandle, Emitter, Manager};
+use serde::{Deserialize, Serialize};
+use std::sync::{LazyLock, Mutex};
+
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+
+const DEFAULT_PALETTE_LIMIT: usize = 20;
+/// Keyword and subtitle hits count less than a hit in the title
+const SECONDARY_MATCH_PENALTY: i64 = 2;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PaletteItemKind {
+    Note,
+    Command,
+    Action,
+}
+
+/// Entry the frontend pushes into the palette
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PaletteItem {
+    pub id: String,
+    pub kind: PaletteItemKind,
+    pub title: String,
+    pub subtitle: Option<String>,
+    /// Extra terms that should find the item, e.g. "new" for "Create note"
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    /// Keyboard shortcut shown next to the item
+    pub shortcut: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PaletteMatch {
+    pub item: PaletteItem,
+    pub score: i64,
+    /// Character positions in the title to highlight
+    #[serde(rename = "matchedIndices")]
+    pub matched_indices: Vec<usize>,
+}
+
+static PALETTE_ITEMS: LazyLock<Mutex<Vec<PaletteItem>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+fn match_item(matcher: &SkimMatcherV2, item: &PaletteItem, query: &str) -> Option<PaletteMatch> {
+    let title = matcher.fuzzy_indices(&item.title, query);
+    let secondary = item.keywords.iter()
+        .chain(item.subtitle.iter())
+        .filter_map(|text| matcher.fuzzy_match(text, query))
+        .max()
+        .map(|score| score / SECONDARY_MATCH_PENALTY);
+
+    let (score, matched_indices) = match (title, secondary) {
+        (Some((score, indices)), Some(other)) if other > score => (other, indices),
+        (Some((score, indices)), _) => (score, indices),
+        (None, Some(score)) => (score, Vec::new()),
+        (None, None) => return None,
+    };
+    Some(PaletteMatch { item: item.clone(), score, matched_indices })
+}
+
+/// Replace the palette entries of one kind, or all of them when no kind is given
+#[tauri::command]
+pub fn update_palette_items(kind: Option<PaletteItemKind>, items: Vec<PaletteItem>) -> Result<(), String> {
+    let mut palette = PALETTE_ITEMS.lock().unwrap();
+    match kind {
+        Some(kind) => {
+            if let Some(item) = items.iter().find(|item| item.kind != kind) {
+                return Err(format!("Palette item {} is not of kind {:?}", item.id, kind));
+            }
+            palette.retain(|item| item.kind != kind);
+            palette.extend(items);
+        }
+        None => *palette = items,
+    }
+    debug!("🎛️ Palette now holds {} items", palette.len());
+    Ok(())
+}
+
+/// Fuzzy match the query against the palette; an empty query lists items in the order they were pushed
+#[tauri::command]
+pub fn search_palette(query: String, limit: Option<usize>) -> Vec<PaletteMatch> {
+    let limit = limit.unwrap_or(DEFAULT_PALETTE_LIMIT);
+    let items = PALETTE_ITEMS.lock().unwrap();
+    let query = query.trim();
+
+    if query.is_empty() {
+        return items.iter()
+            .take(limit)
+            .map(|item| PaletteMatch { item: item.clone(), score: 0, matched_indices: Vec::new() })
+            .collect();
+    }
+
+    let matcher = SkimMatcherV2::default().ignore_case();
+    let mut matches: Vec<PaletteMatch> = items.iter()
+        .filter_map(|item| match_item(&matcher, item, query))
+        .collect();
+    // Stable sort keeps the pushed order (e.g. note recency) for equal scores
+    matches.sort_by(|a, b| b.score.cmp(&a.score));
+    matches.truncate(limit);
+    matches
+}
+
+/// Hide the palette and let the main window run the chosen item
+#[tauri::command]
+pub fn select_palette_item(app: AppHandle, id: String) -> Result<(), String> {
+    let item = PALETTE_ITEMS.lock().unwrap()
+        .iter()
+        .find(|item| item.id == id)
+        .cloned()
+        .ok_or_else(|| format!("Palette item {} not found", id))?;
+
+    if let Some(palette) = app.get_webview_window("palette") {
+        let _ = palette.hide();
+    }
+
+    let main_window = app.get_webview_window("main")
+        .ok_or("Main window not found")?;
+    // Commands run in the background, notes and actions need the main window
+    if item.kind != PaletteItemKind::Command {
+        let _ = main_window.show();
+        let _ = main_window.set_focus();
+    }
+    main_window.emit("palette-item-selected", &item)
+        .map_err(|e| format!("Failed to emit palette selection: {}", e))?;
+
+    info!("🎛️ Palette item selected: {} ({:?})", item.title, item.kind);
+    Ok(())
+}
@@ -0,0 +1,306 @@
+use tauri::AppHandle;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{LazyLock, Mutex};
+
+use wasmtime::{Caller, Config, Engine, Linker, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+use crate::desktop::{get_app_data_subdir, load_json_or_default, save_json, send_notification};
+
+// WASM plugins that transform captured text.
+//
+// A plugin is a directory in `plugins/` holding `manifest.json` and the module file it names.
+// Modules export `memory`, `alloc(len: i32) -> i32` and one function per hook taking
+// `(ptr: i32, len: i32)` of UTF-8 input and returning the output as `(ptr << 32) | len` in an i64.
+// Imports live in the `blinko` namespace: `log(ptr, len)` is always available,
+// `notify(title_ptr, title_len, body_ptr, body_len)` needs the `notifications` permission.
+
+const PLUGINS_DIR: &str = "plugins";
+const PLUGINS_FILE: &str = "plugins.json";
+const MANIFEST_FILE: &str = "manifest.json";
+/// Instructions a hook may run before it is stopped
+const PLUGIN_FUEL: u64 = 500_000_000;
+const PLUGIN_MEMORY_LIMIT: usize = 64 * 1024 * 1024;
+const MAX_PLUGIN_OUTPUT: usize = 1024 * 1024;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginHook {
+    OnCapture,
+    OnTranscription,
+}
+
+impl PluginHook {
+    fn export_name(self) -> &'static str {
+        match self {
+            PluginHook::OnCapture => "on_capture",
+            PluginHook::OnTranscription => "on_transcription",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginPermission {
+    Notifications,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PluginManifest {
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    #[serde(default)]
+    pub description: String,
+    /// Module file relative to the plugin directory
+    #[serde(default = "default_module")]
+    pub module: String,
+    pub hooks: Vec<PluginHook>,
+    #[serde(default)]
+    pub permissions: Vec<PluginPermission>,
+}
+
+fn default_module() -> String {
+    "plugin.wasm".to_string()
+}
+
+/// What the user approved for a plugin
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+struct PluginGrant {
+    enabled: bool,
+    /// Permissions shown to the user when the plugin was enabled
+    permissions: Vec<PluginPermission>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PluginInfo {
+    pub manifest: PluginManifest,
+    pub enabled: bool,
+    #[serde(rename = "grantedPermissions")]
+    pub granted_permissions: Vec<PluginPermission>,
+    /// Set when the module failed to load, or asks for permissions that were never granted
+    pub error: Option<String>,
+}
+
+struct LoadedPlugin {
+    manifest: PluginManifest,
+    module: Option<Module>,
+    error: Option<String>,
+}
+
+struct PluginState {
+    limits: StoreLimits,
+    plugin_id: String,
+    app: AppHandle,
+}
+
+static PLUGIN_ENGINE: LazyLock<Engine> = LazyLock::new(|| {
+    let mut config = Config::new();
+    config.consume_fuel(true);
+    Engine::new(&config).expect("Failed to create WASM engine")
+});
+static LOADED_PLUGINS: LazyLock<Mutex<Option<Vec<LoadedPlugin>>>> = LazyLock::new(|| Mutex::new(None));
+
+fn plugins_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    get_app_data_subdir(app, PLUGINS_DIR)
+}
+
+fn load_plugin(dir: &std::path::Path) -> Option<LoadedPlugin> {
+    let manifest_path = dir.join(MANIFEST_FILE);
+    let content = fs::read_to_string(&manifest_path).ok()?;
+    let manifest: PluginManifest = match serde_json::from_str(&content) {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            error!("❌ Invalid plugin manifest {}: {}", manifest_path.display(), e);
+            return None;
+        }
+    };
+
+    let (module, error) = match Module::from_file(&PLUGIN_ENGINE, dir.join(&manifest.module)) {
+        Ok(module) => (Some(module), None),
+        Err(e) => (None, Some(format!("Failed to load module: {:#}", e))),
+    };
+    Some(LoadedPlugin { manifest, module, error })
+}
+
+fn scan_plugins(app: &AppHandle) -> Vec<LoadedPlugin> {
+    let Ok(dir) = plugins_dir(app) else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+
+    let mut plugins: Vec<LoadedPlugin> = entries
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| load_plugin(&entry.path()))
+        .collect();
+    plugins.sort_by(|a, b| a.manifest.id.cmp(&b.manifest.id));
+    info!("🧩 Found {} plugin(s)", plugins.len());
+    plugins
+}
+
+fn with_plugins<T>(app: &AppHandle, f: impl FnOnce(&[LoadedPlugin]) -> T) -> T {
+    let mut guard = LOADED_PLUGINS.lock().unwrap();
+    let plugins = guard.get_or_insert_with(|| scan_plugins(app));
+    f(plugins)
+}
+
+fn read_guest_string(caller: &mut Caller<'_, PluginState>, ptr: i32, len: i32) -> Option<String> {
+    let memory = caller.get_export("memory")?.into_memory()?;
+    let data = memory.data(&*caller);
+    let bytes = data.get(ptr as usize..(ptr as usize).checked_add(len as usize)?)?;
+    Some(String::from_utf8_lossy(bytes).to_string())
+}
+
+/// Only granted permissions get their imports linked, so a module asking for more fails to instantiate
+fn create_linker(permissions: &[PluginPermission]) -> Result<Linker<PluginState>, String> {
+    let mut linker = Linker::new(&PLUGIN_ENGINE);
+
+    linker.func_wrap("blinko", "log", |mut caller: Caller<'_, PluginState>, ptr: i32, len: i32| {
+        if let Some(message) = read_guest_string(&mut caller, ptr, len) {
+            info!("🧩 [{}] {}", caller.data().plugin_id, message);
+        }
+    })
+    .map_err(|e| format!("Failed to link log: {}", e))?;
+
+    if permissions.contains(&PluginPermission::Notifications) {
+        linker.func_wrap(
+            "blinko",
+            "notify",
+            |mut caller: Caller<'_, PluginState>, title_ptr: i32, title_len: i32, body_ptr: i32, body_len: i32| {
+                let title = read_guest_string(&mut caller, title_ptr, title_len).unwrap_or_default();
+                let body = read_guest_string(&mut caller, body_ptr, body_len).unwrap_or_default();
+                send_notification(&caller.data().app, &title, &body);
+            },
+        )
+        .map_err(|e| format!("Failed to link notify: {}", e))?;
+    }
+    Ok(linker)
+}
+
+/// Each call gets a fresh instance, so plugins keep no state between captures
+fn call_plugin_hook(app: &AppHandle, plugin: &LoadedPlugin, module: &Module, permissions: &[PluginPermission], hook: PluginHook, input: &str) -> Result<String, String> {
+    let state = PluginState {
+        limits: StoreLimitsBuilder::new().memory_size(PLUGIN_MEMORY_LIMIT).build(),
+        plugin_id: plugin.manifest.id.clone(),
+        app: app.clone(),
+    };
+    let mut store = Store::new(&PLUGIN_ENGINE, state);
+    store.limiter(|state| &mut state.limits);
+    store.set_fuel(PLUGIN_FUEL).map_err(|e| format!("Failed to set fuel: {}", e))?;
+
+    let instance = create_linker(permissions)?
+        .instantiate(&mut store, module)
+        .map_err(|e| format!("Failed to instantiate: {:#}", e))?;
+    let memory = instance.get_memory(&mut store, "memory")
+        .ok_or("Module does not export memory")?;
+    let alloc = instance.get_typed_func::<i32, i32>(&mut store, "alloc")
+        .map_err(|e| format!("Missing alloc export: {}", e))?;
+    let hook_fn = instance.get_typed_func::<(i32, i32), i64>(&mut store, hook.export_name())
+        .map_err(|e| format!("Missing {} export: {}", hook.export_name(), e))?;
+
+    let bytes = input.as_bytes();
+    let len = i32::try_from(bytes.len()).map_err(|_| "Input too large".to_string())?;
+    let ptr = alloc.call(&mut store, len).map_err(|e| format!("alloc failed: {:#}", e))?;
+    memory.write(&mut store, ptr as usize, bytes)
+        .map_err(|e| format!("Failed to write input: {}", e))?;
+
+    let packed = hook_fn.call(&mut store, (ptr, len))
+        .map_err(|e| format!("{} failed: {:#}", hook.export_name(), e))? as u64;
+    let (out_ptr, out_len) = ((packed >> 32) as usize, (packed & 0xffff_ffff) as usize);
+    if out_len > MAX_PLUGIN_OUTPUT {
+        return Err(format!("Output of {} bytes is too large", out_len));
+    }
+    let mut output = vec![0; out_len];
+    memory.read(&store, out_ptr, &mut output)
+        .map_err(|e| format!("Failed to read output: {}", e))?;
+    String::from_utf8(output).map_err(|_| "Output is not valid UTF-8".to_string())
+}
+
+/// Pipe text through every enabled plugin implementing the hook, in plugin id order.
+/// A failing plugin is skipped and its input passed on unchanged.
+pub fn run_plugin_hook(app: &AppHandle, hook: PluginHook, text: &str) -> String {
+    let grants: HashMap<String, PluginGrant> = load_json_or_default(app, PLUGINS_FILE);
+    if !grants.values().any(|grant| grant.enabled) {
+        return text.to_string();
+    }
+
+    with_plugins(app, |plugins| {
+        let mut text = text.to_string();
+        for plugin in plugins.iter().filter(|p| p.manifest.hooks.contains(&hook)) {
+            let Some(grant) = grants.get(&plugin.manifest.id).filter(|g| g.enabled) else { continue };
+            let Some(ref module) = plugin.module else { continue };
+            if plugin.manifest.permissions.iter().any(|p| !grant.permissions.contains(p)) {
+                continue;
+            }
+
+            match call_plugin_hook(app, plugin, module, &grant.permissions, hook, &text) {
+                Ok(output) => text = output,
+                Err(e) => error!("❌ Plugin {} failed in {}: {}", plugin.manifest.id, hook.export_name(), e),
+            }
+        }
+        text
+    })
+}
+
+#[tauri::command]
+pub fn list_plugins(app: AppHandle) -> Vec<PluginInfo> {
+    let grants: HashMap<String, PluginGrant> = load_json_or_default(&app, PLUGINS_FILE);
+    with_plugins(&app, |plugins| {
+        plugins.iter().map(|plugin| {
+            let grant = grants.get(&plugin.manifest.id).cloned().unwrap_or_default();
+            let ungranted = plugin.manifest.permissions.iter().any(|p| !grant.permissions.contains(p));
+            let error = plugin.error.clone().or_else(|| {
+                (grant.enabled && ungranted).then(|| "The plugin asks for new permissions, enable it again to grant them".to_string())
+            });
+            PluginInfo {
+                manifest: plugin.manifest.clone(),
+                enabled: grant.enabled,
+                granted_permissions: grant.permissions,
+                error,
+            }
+        }).collect()
+    })
+}
+
+/// Enabling grants the permissions the manifest currently asks for, the frontend shows them first
+#[tauri::command]
+pub fn enable_plugin(app: AppHandle, id: String, enabled: bool) -> Result<(), String> {
+    let manifest = with_plugins(&app, |plugins| {
+        plugins.iter().find(|p| p.manifest.id == id).map(|p| p.manifest.clone())
+    })
+    .ok_or_else(|| format!("Plugin {} not found", id))?;
+
+    let mut grants: HashMap<String, PluginGrant> = load_json_or_default(&app, PLUGINS_FILE);
+    let grant = grants.entry(id.clone()).or_default();
+    grant.enabled = enabled;
+    grant.permissions = if enabled { manifest.permissions.clone() } else { Vec::new() };
+    save_json(&app, PLUGINS_FILE, &grants)?;
+
+    info!("🧩 Plugin {} {}", id, if enabled { "enabled" } else { "disabled" });
+    Ok(())
+}
+
+/// Rescan the plugins directory after plugins were added or updated
+#[tauri::command]
+pub fn reload_plugins(app: AppHandle) -> Vec<PluginInfo> {
+    *LOADED_PLUGINS.lock().unwrap() = None;
+    list_plugins(app)
+}
+
+#[tauri::command]
+pub fn get_plugins_directory(app: AppHandle) -> Result<String, String> {
+    plugins_dir(&app).map(|dir| dir.to_string_lossy().to_string())
+}
+
+/// Run the `on_capture` plugins over a note before it is saved
+#[tauri::command]
+pub async fn apply_capture_plugins(app: AppHandle, text: String) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || run_plugin_hook(&app, PluginHook::OnCapture, &text))
+        .await
+        .map_err(|e| format!("Capture plugins failed: {}", e))
+}
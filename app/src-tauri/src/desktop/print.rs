@@ -0,0 +1,199 @@
+use tauri::{AppHandle, Manager, WebviewWindow};
+use tauri_plugin_dialog::DialogExt;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Sender};
+use std::time::Duration;
+
+const PRINT_TIMEOUT: Duration = Duration::from_secs(120);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PrintToPdfOptions {
+    /// Target file; a save dialog is shown when missing
+    pub path: Option<String>,
+    /// Suggested name in the save dialog
+    #[serde(rename = "fileName")]
+    pub file_name: Option<String>,
+    #[serde(default)]
+    pub landscape: bool,
+    /// Keep background colors and images
+    #[serde(rename = "printBackground", default = "default_print_background")]
+    pub print_background: bool,
+}
+
+fn default_print_background() -> bool {
+    true
+}
+
+impl Default for PrintToPdfOptions {
+    fn default() -> Self {
+        Self {
+            path: None,
+            file_name: None,
+            landscape: false,
+            print_background: true,
+        }
+    }
+}
+
+type PrintResult = Result<(), String>;
+
+/// WebView2 paginates and writes the PDF itself
+#[cfg(target_os = "windows")]
+fn start_pdf_print(window: &WebviewWindow, path: PathBuf, options: &PrintToPdfOptions, done: Sender<PrintResult>) -> Result<(), String> {
+    use webview2_com::Microsoft::Web::WebView2::Win32::{
+        ICoreWebView2Environment6, ICoreWebView2_7, COREWEBVIEW2_PRINT_ORIENTATION_LANDSCAPE,
+        COREWEBVIEW2_PRINT_ORIENTATION_PORTRAIT,
+    };
+    use webview2_com::PrintToPdfCompletedHandler;
+    use windows::core::{Interface, HSTRING};
+
+    let landscape = options.landscape;
+    let print_background = options.print_background;
+    window.with_webview(move |webview| {
+        // SAFETY: WebView2 COM calls on the UI thread that owns the controller
+        let result = unsafe {
+            (|| -> windows::core::Result<()> {
+                let core: ICoreWebView2_7 = webview.controller().CoreWebView2()?.cast()?;
+                let environment: ICoreWebView2Environment6 = webview.environment().cast()?;
+                let settings = environment.CreatePrintSettings()?;
+                settings.SetOrientation(if landscape {
+                    COREWEBVIEW2_PRINT_ORIENTATION_LANDSCAPE
+                } else {
+                    COREWEBVIEW2_PRINT_ORIENTATION_PORTRAIT
+                })?;
+                settings.SetShouldPrintBackgrounds(print_background.into())?;
+
+                let finished = done.clone();
+                let handler = PrintToPdfCompletedHandler::create(Box::new(move |result, success| {
+                    let _ = finished.send(match result {
+                        Ok(()) if bool::from(success) => Ok(()),
+                        Ok(()) => Err("WebView2 could not print to PDF".to_string()),
+                        Err(e) => Err(e.to_string()),
+                    });
+                    Ok(())
+                }));
+                core.PrintToPdf(&HSTRING::from(path.as_os_str()), &settings, &handler)
+            })()
+        };
+        if let Err(e) = result {
+            let _ = done.send(Err(e.to_string()));
+        }
+    })
+    .map_err(|e| format!("Failed to access webview: {}", e))
+}
+
+/// WKWebView renders the whole page into a single PDF page
+#[cfg(target_os = "macos")]
+fn start_pdf_print(window: &WebviewWindow, path: PathBuf, _options: &PrintToPdfOptions, done: Sender<PrintResult>) -> Result<(), String> {
+    use block2::RcBlock;
+    use objc2_foundation::{NSData, NSError};
+    use objc2_web_kit::WKWebView;
+
+    window.with_webview(move |webview| {
+        let finished = done.clone();
+        let path = path.clone();
+        let completion = RcBlock::new(move |data: *mut NSData, error: *mut NSError| {
+            // SAFETY: WebKit passes either valid data or a valid error
+            let result = unsafe {
+                match (data.as_ref(), error.as_ref()) {
+                    (Some(data), _) => std::fs::write(&path, data.to_vec())
+                        .map_err(|e| format!("Failed to write {}: {}", path.display(), e)),
+                    (None, Some(error)) => Err(error.localizedDescription().to_string()),
+                    (None, None) => Err("WebKit returned no PDF data".to_string()),
+                }
+            };
+            let _ = finished.send(result);
+        });
+        // SAFETY: the platform webview pointer is a WKWebView that outlives this call
+        unsafe {
+            let wk_webview: &WKWebView = &*(webview.inner() as *const WKWebView);
+            wk_webview.createPDFWithConfiguration_completionHandler(None, &completion);
+        }
+    })
+    .map_err(|e| format!("Failed to access webview: {}", e))
+}
+
+/// WebKitGTK prints through GTK's "Print to File" backend without showing a dialog
+#[cfg(target_os = "linux")]
+fn start_pdf_print(window: &WebviewWindow, path: PathBuf, options: &PrintToPdfOptions, done: Sender<PrintResult>) -> Result<(), String> {
+    use gtk::{PageOrientation, PrintSettings};
+    use webkit2gtk::{PrintOperation, PrintOperationExt};
+
+    let landscape = options.landscape;
+    window.with_webview(move |webview| {
+        let operation = PrintOperation::new(&webview.inner());
+        let settings = PrintSettings::new();
+        settings.set_printer("Print to File");
+        settings.set(gtk::PRINT_SETTINGS_OUTPUT_FILE_FORMAT, Some("pdf"));
+        settings.set(gtk::PRINT_SETTINGS_OUTPUT_URI, Some(&format!("file://{}", path.display())));
+        if landscape {
+            settings.set_orientation(PageOrientation::Landscape);
+        }
+        operation.set_print_settings(&settings);
+
+        // "failed" is followed by "finished", the receiver only takes the first result
+        let failed = done.clone();
+        operation.connect_failed(move |_, error| {
+            let _ = failed.send(Err(error.to_string()));
+        });
+        let finished = done.clone();
+        operation.connect_finished(move |_| {
+            let _ = finished.send(Ok(()));
+        });
+        operation.print();
+    })
+    .map_err(|e| format!("Failed to access webview: {}", e))
+}
+
+/// Write what `window` shows to `path` as PDF, blocking until the webview is done
+pub fn print_window_to_pdf(window: &WebviewWindow, path: &Path, options: &PrintToPdfOptions) -> Result<(), String> {
+    let (done, result) = channel();
+    start_pdf_print(window, path.to_path_buf(), options, done)?;
+    result.recv_timeout(PRINT_TIMEOUT)
+        .map_err(|_| "Timed out waiting for the PDF".to_string())?
+}
+
+fn choose_pdf_path(app: &AppHandle, options: &PrintToPdfOptions) -> Result<Option<PathBuf>, String> {
+    if let Some(ref path) = options.path {
+        return Ok(Some(PathBuf::from(path)));
+    }
+
+    let file_name = options.file_name.clone().unwrap_or_else(|| "note.pdf".to_string());
+    let Some(picked) = app.dialog()
+        .file()
+        .add_filter("PDF", &["pdf"])
+        .set_file_name(&file_name)
+        .blocking_save_file()
+    else {
+        return Ok(None);
+    };
+    picked.into_path()
+        .map(Some)
+        .map_err(|e| format!("Invalid save location: {}", e))
+}
+
+/// Export what a window currently shows to PDF.
+/// Returns the written file, or None if the save dialog was cancelled.
+#[tauri::command]
+pub async fn print_to_pdf(app: AppHandle, label: String, options: Option<PrintToPdfOptions>) -> Result<Option<String>, String> {
+    let options = options.unwrap_or_default();
+    let window = app.get_webview_window(&label)
+        .ok_or_else(|| format!("{} window not found", label))?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let Some(mut path) = choose_pdf_path(&app, &options)? else {
+            return Ok(None);
+        };
+        if path.extension().is_none() {
+            path.set_extension("pdf");
+        }
+
+        print_window_to_pdf(&window, &path, &options)?;
+
+        info!("🖨️ Exported {} window to {}", label, path.display());
+        Ok(Some(path.to_string_lossy().to_string()))
+    })
+    .await
+    .map_err(|e| format!("Print to PDF failed: {}", e))?
+}
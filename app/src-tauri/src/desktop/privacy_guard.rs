@@ -0,0 +1,169 @@
+use tauri::{AppHandle, Emitter};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+
+use crate::desktop::{load_json_or_default, save_json};
+
+const PRIVACY_GUARD_FILE: &str = "privacy_guard.json";
+const PRIVACY_POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PrivacyGuardConfig {
+    pub enabled: bool,
+    #[serde(rename = "pauseClipboard")]
+    pub pause_clipboard: bool,
+    /// Keep dictated text out of the focused app, it still reaches Blinko itself
+    #[serde(rename = "pauseDictation")]
+    pub pause_dictation: bool,
+    /// Window title fragments (case-insensitive) shown by meeting and recording apps while sharing
+    pub indicators: Vec<String>,
+}
+
+impl Default for PrivacyGuardConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            pause_clipboard: true,
+            pause_dictation: true,
+            indicators: [
+                "is sharing your screen",
+                "is sharing a window",
+                "is sharing this tab",
+                "sharing indicator",
+                "zoom share toolbar",
+                "zoom share statusbar",
+                "sharing control bar",
+                "screen sharing toolbar",
+                "you are screen sharing",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct PrivacyGuardStatus {
+    pub active: bool,
+    /// What gave the sharing away, e.g. the matching window title
+    pub reason: Option<String>,
+}
+
+static PRIVACY_CONFIG: LazyLock<Mutex<Option<PrivacyGuardConfig>>> = LazyLock::new(|| Mutex::new(None));
+static PRIVACY_STATUS: LazyLock<Mutex<PrivacyGuardStatus>> = LazyLock::new(|| Mutex::new(PrivacyGuardStatus::default()));
+static CLIPBOARD_GUARDED: AtomicBool = AtomicBool::new(false);
+static INJECTION_GUARDED: AtomicBool = AtomicBool::new(false);
+static PRIVACY_MONITOR_STARTED: AtomicBool = AtomicBool::new(false);
+
+pub fn load_privacy_guard_config(app: &AppHandle) -> PrivacyGuardConfig {
+    PRIVACY_CONFIG.lock().unwrap()
+        .get_or_insert_with(|| load_json_or_default(app, PRIVACY_GUARD_FILE))
+        .clone()
+}
+
+/// Clipboard history capture is held back while the screen is shared
+pub fn is_clipboard_guarded() -> bool {
+    CLIPBOARD_GUARDED.load(Ordering::SeqCst)
+}
+
+/// Dictation must not type into other apps while the screen is shared
+pub fn is_injection_guarded() -> bool {
+    INJECTION_GUARDED.load(Ordering::SeqCst)
+}
+
+fn matching_window_title(indicators: &[String]) -> Option<String> {
+    let indicators: Vec<String> = indicators.iter()
+        .map(|indicator| indicator.trim().to_lowercase())
+        .filter(|indicator| !indicator.is_empty())
+        .collect();
+    if indicators.is_empty() {
+        return None;
+    }
+    let windows = xcap::Window::all().ok()?;
+    windows.iter()
+        .filter_map(|window| window.title().ok())
+        .find(|title| {
+            let title = title.to_lowercase();
+            indicators.iter().any(|indicator| title.contains(indicator.as_str()))
+        })
+}
+
+/// Screencasts through the desktop portal show up as a running PipeWire video source
+#[cfg(target_os = "linux")]
+fn active_portal_screencast() -> Option<String> {
+    let output = crate::desktop::background_command("pw-dump").output().ok()?;
+    let nodes: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout).ok()?;
+    nodes.iter().find_map(|node| {
+        let info = node.get("info")?;
+        let props = info.get("props")?;
+        let name = props.get("node.name").and_then(|v| v.as_str()).unwrap_or_default().to_lowercase();
+        let is_screencast = props.get("media.class").and_then(|v| v.as_str()) == Some("Video/Source")
+            && ["screencast", "xdph", "screen-share"].iter().any(|marker| name.contains(marker));
+        (is_screencast && info.get("state").and_then(|v| v.as_str()) == Some("running"))
+            .then(|| format!("PipeWire screencast {}", name))
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+fn active_portal_screencast() -> Option<String> {
+    None
+}
+
+fn detect_screen_sharing(config: &PrivacyGuardConfig) -> Option<String> {
+    active_portal_screencast()
+        .or_else(|| matching_window_title(&config.indicators).map(|title| format!("Window \"{}\"", title)))
+}
+
+fn apply_privacy_guard(app: &AppHandle, config: &PrivacyGuardConfig, reason: Option<String>) {
+    let active = reason.is_some();
+    CLIPBOARD_GUARDED.store(active && config.pause_clipboard, Ordering::SeqCst);
+    INJECTION_GUARDED.store(active && config.pause_dictation, Ordering::SeqCst);
+
+    let status = PrivacyGuardStatus { active, reason };
+    let changed = std::mem::replace(&mut *PRIVACY_STATUS.lock().unwrap(), status.clone()).active != active;
+    if !changed {
+        return;
+    }
+    if active {
+        info!("🛡️ Screen sharing detected ({}), pausing capture", status.reason.as_deref().unwrap_or_default());
+        let _ = app.emit("privacy-guard-activated", &status);
+    } else {
+        info!("🛡️ Screen sharing ended, capture resumed");
+        let _ = app.emit("privacy-guard-deactivated", &status);
+    }
+}
+
+/// Poll for screen sharing and hold back clipboard capture and dictation typing meanwhile
+pub fn start_privacy_guard(app: &AppHandle) {
+    if PRIVACY_MONITOR_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let app_handle = app.clone();
+    std::thread::spawn(move || loop {
+        let config = load_privacy_guard_config(&app_handle);
+        let reason = if config.enabled { detect_screen_sharing(&config) } else { None };
+        apply_privacy_guard(&app_handle, &config, reason);
+        std::thread::sleep(PRIVACY_POLL_INTERVAL);
+    });
+}
+
+#[tauri::command]
+pub fn get_privacy_guard_config(app: AppHandle) -> PrivacyGuardConfig {
+    load_privacy_guard_config(&app)
+}
+
+#[tauri::command]
+pub fn save_privacy_guard_config(app: AppHandle, config: PrivacyGuardConfig) -> Result<(), String> {
+    save_json(&app, PRIVACY_GUARD_FILE, &config)?;
+    *PRIVACY_CONFIG.lock().unwrap() = Some(config);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_privacy_guard_status() -> PrivacyGuardStatus {
+    PRIVACY_STATUS.lock().unwrap().clone()
+}
@@ -0,0 +1,15 @@
+use std::process::Command;
+
+/// Create a command for a helper executable that must not flash a console window
+pub fn background_command(program: &str) -> Command {
+    #[allow(unused_mut)]
+    let mut command = Command::new(program);
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        command.creation_flags(0x08000000); // CREATE_NO_WINDOW
+    }
+
+    command
+}
@@ -0,0 +1,84 @@
+use tauri::AppHandle;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+
+use crate::desktop::{get_app_data_dir, load_protected_json_or_default, now_millis, save_protected_json};
+
+const QUICKNOTE_DRAFT_FILE: &str = "quicknote_draft.json";
+/// Typing bursts are written once the user pauses for this long
+const DRAFT_SAVE_DEBOUNCE: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QuicknoteDraft {
+    pub text: String,
+    /// Unix millis
+    #[serde(rename = "updatedAt")]
+    pub updated_at: u64,
+}
+
+// Latest text that has not been written yet
+static PENDING_DRAFT: LazyLock<Mutex<Option<QuicknoteDraft>>> = LazyLock::new(|| Mutex::new(None));
+// Bumped on every save so only the last one in a burst writes
+static DRAFT_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+fn write_draft(app: &AppHandle, draft: &QuicknoteDraft) -> Result<(), String> {
+    // Drafts are personal content, so they follow the local encryption setting
+    save_protected_json(app, QUICKNOTE_DRAFT_FILE, draft)
+}
+
+/// Write the pending draft right away, used on exit
+pub fn flush_quicknote_draft(app: &AppHandle) {
+    let Some(draft) = PENDING_DRAFT.lock().unwrap().take() else {
+        return;
+    };
+    if let Err(e) = write_draft(app, &draft) {
+        error!("❌ {}", e);
+    }
+}
+
+/// Called by the quicknote window as the user types; writes are debounced
+#[tauri::command]
+pub fn save_quicknote_draft(app: AppHandle, text: String) -> Result<(), String> {
+    if text.trim().is_empty() {
+        return clear_quicknote_draft(app);
+    }
+
+    *PENDING_DRAFT.lock().unwrap() = Some(QuicknoteDraft { text, updated_at: now_millis() });
+    let generation = DRAFT_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+
+    std::thread::spawn(move || {
+        std::thread::sleep(DRAFT_SAVE_DEBOUNCE);
+        if DRAFT_GENERATION.load(Ordering::SeqCst) == generation {
+            flush_quicknote_draft(&app);
+        }
+    });
+    Ok(())
+}
+
+/// Text left in the quicknote window when it was dismissed or the app went down
+#[tauri::command]
+pub fn load_quicknote_draft(app: AppHandle) -> Result<Option<QuicknoteDraft>, String> {
+    if let Some(draft) = PENDING_DRAFT.lock().unwrap().clone() {
+        return Ok(Some(draft));
+    }
+
+    Ok(load_protected_json_or_default(&app, QUICKNOTE_DRAFT_FILE))
+}
+
+/// Called once the note was submitted or explicitly discarded
+#[tauri::command]
+pub fn clear_quicknote_draft(app: AppHandle) -> Result<(), String> {
+    // Cancels any write still waiting for its debounce
+    DRAFT_GENERATION.fetch_add(1, Ordering::SeqCst);
+    *PENDING_DRAFT.lock().unwrap() = None;
+
+    let path = get_app_data_dir(&app)?.join(QUICKNOTE_DRAFT_FILE);
+    if path.exists() {
+        fs::remove_file(&path)
+            .map_err(|e| format!("Failed to remove quicknote draft: {}", e))?;
+    }
+    Ok(())
+}
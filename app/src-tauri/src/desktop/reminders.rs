@@ -0,0 +1,282 @@
+use tauri::{AppHandle, Emitter};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+use chrono::{DateTime, Datelike, Local, Months, NaiveDateTime, TimeZone, Weekday};
+
+use crate::desktop::{check_daily_journal, check_script_schedules, dispatch_webhook_event, generate_id, load_json_or_default, now_millis, refresh_calendar_export, save_json, send_notification, send_urgent_notification};
+
+const REMINDERS_FILE: &str = "reminders.json";
+const REMINDER_CHECK_INTERVAL: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum RepeatRule {
+    None,
+    Hourly,
+    Daily,
+    Weekdays,
+    Weekly,
+    Monthly,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Reminder {
+    pub id: String,
+
+    /// Blinko note this reminder points to
+    #[serde(rename = "noteId")]
+    pub note_id: Option<i64>,
+
+    pub title: String,
+    pub body: String,
+
+    /// Next fire time as unix milliseconds
+    #[serde(rename = "fireAt")]
+    pub fire_at: u64,
+
+    /// Time the reminder was scheduled for, monthly repeats keep its day of month
+    #[serde(rename = "anchorAt", default)]
+    pub anchor_at: Option<u64>,
+
+    pub repeat: RepeatRule,
+    pub enabled: bool,
+
+    /// Shown even while the OS is in do-not-disturb
+    #[serde(default)]
+    pub urgent: bool,
+
+    #[serde(rename = "lastFiredAt")]
+    pub last_fired_at: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReminderInput {
+    #[serde(rename = "noteId")]
+    pub note_id: Option<i64>,
+    pub title: String,
+    #[serde(default)]
+    pub body: String,
+    #[serde(rename = "fireAt")]
+    pub fire_at: u64,
+    #[serde(default = "default_repeat")]
+    pub repeat: RepeatRule,
+    #[serde(default)]
+    pub urgent: bool,
+}
+
+fn default_repeat() -> RepeatRule {
+    RepeatRule::None
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+struct ReminderStore {
+    reminders: Vec<Reminder>,
+}
+
+static REMINDER_STORE: LazyLock<Mutex<Option<ReminderStore>>> = LazyLock::new(|| Mutex::new(None));
+static SCHEDULER_STARTED: AtomicBool = AtomicBool::new(false);
+
+fn with_reminders<T>(app: &AppHandle, f: impl FnOnce(&mut Vec<Reminder>) -> T) -> T {
+    let mut guard = REMINDER_STORE.lock().unwrap();
+    let store = guard.get_or_insert_with(|| load_json_or_default(app, REMINDERS_FILE));
+    let result = f(&mut store.reminders);
+
+    if let Err(e) = save_json(app, REMINDERS_FILE, store) {
+        error!("Failed to save reminders: {}", e);
+    }
+    // The feed reads the reminders again
+    drop(guard);
+    refresh_calendar_export(app);
+    result
+}
+
+fn read_reminders(app: &AppHandle) -> Vec<Reminder> {
+    let mut guard = REMINDER_STORE.lock().unwrap();
+    let store = guard.get_or_insert_with(|| load_json_or_default(app, REMINDERS_FILE));
+    store.reminders.clone()
+}
+
+fn to_local(millis: u64) -> Option<DateTime<Local>> {
+    Local.timestamp_millis_opt(millis as i64).single()
+}
+
+/// A local wall clock time, moved past the gap if a DST change skips it
+fn from_local_time(time: NaiveDateTime) -> Option<DateTime<Local>> {
+    time.and_local_timezone(Local).earliest()
+        .or_else(|| (time + chrono::Duration::hours(1)).and_local_timezone(Local).earliest())
+}
+
+/// First monthly occurrence after `now`, counted from the anchor so a reminder on the 31st
+/// fires on the last day of shorter months without sliding to the 28th for good
+fn next_monthly_fire_time(anchor_at: u64, now: u64) -> Option<u64> {
+    let anchor = to_local(anchor_at)?.naive_local();
+    let mut months = 1;
+    loop {
+        // Clamped to the last day of the month
+        let next = from_local_time(anchor.checked_add_months(Months::new(months))?)?;
+        if next.timestamp_millis() as u64 > now {
+            return Some(next.timestamp_millis() as u64);
+        }
+        months += 1;
+    }
+}
+
+/// Compute the next occurrence strictly after `now` for a repeating reminder
+fn next_fire_time(fire_at: u64, anchor_at: u64, repeat: RepeatRule, now: u64) -> Option<u64> {
+    let mut next = to_local(fire_at)?;
+
+    while next.timestamp_millis() as u64 <= now {
+        next = match repeat {
+            RepeatRule::None => return None,
+            RepeatRule::Hourly => next + chrono::Duration::hours(1),
+            RepeatRule::Daily => next + chrono::Duration::days(1),
+            RepeatRule::Weekly => next + chrono::Duration::weeks(1),
+            RepeatRule::Monthly => return next_monthly_fire_time(anchor_at, now),
+            RepeatRule::Weekdays => {
+                let mut candidate = next + chrono::Duration::days(1);
+                while matches!(candidate.weekday(), Weekday::Sat | Weekday::Sun) {
+                    candidate += chrono::Duration::days(1);
+                }
+                candidate
+            }
+        };
+    }
+
+    Some(next.timestamp_millis() as u64)
+}
+
+fn fire_reminder(app: &AppHandle, reminder: &Reminder) {
+    info!("⏰ Reminder fired: {}", reminder.title);
+
+    if reminder.urgent {
+        send_urgent_notification(app, &reminder.title, &reminder.body);
+    } else {
+        send_notification(app, &reminder.title, &reminder.body);
+    }
+
+    if let Err(e) = app.emit("reminder-fired", reminder) {
+        error!("Failed to emit reminder-fired event: {}", e);
+    }
+
+    dispatch_webhook_event(app, "reminder.fired", serde_json::to_value(reminder).unwrap_or_default());
+}
+
+/// Fire all due reminders and reschedule repeating ones
+fn check_due_reminders(app: &AppHandle) {
+    let now = now_millis();
+    if !read_reminders(app).iter().any(|r| r.enabled && r.fire_at <= now) {
+        return;
+    }
+
+    let due: Vec<Reminder> = with_reminders(app, |reminders| {
+        let mut due = Vec::new();
+        for reminder in reminders.iter_mut().filter(|r| r.enabled && r.fire_at <= now) {
+            due.push(reminder.clone());
+            reminder.last_fired_at = Some(now);
+            // Older reminders have no anchor, their current time is the best one left
+            let anchor_at = *reminder.anchor_at.get_or_insert(reminder.fire_at);
+            match next_fire_time(reminder.fire_at, anchor_at, reminder.repeat, now) {
+                Some(next) => reminder.fire_at = next,
+                None => reminder.enabled = false,
+            }
+        }
+        due
+    });
+
+    for reminder in due.iter() {
+        fire_reminder(app, reminder);
+    }
+}
+
+/// Start the background reminder scheduler (runs for the app lifetime)
+pub fn start_reminder_scheduler(app: &AppHandle) {
+    if SCHEDULER_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let app_handle = app.clone();
+    std::thread::spawn(move || {
+        info!("⏰ Reminder scheduler started");
+        loop {
+            check_due_reminders(&app_handle);
+            check_daily_journal(&app_handle);
+            check_script_schedules(&app_handle);
+            std::thread::sleep(REMINDER_CHECK_INTERVAL);
+        }
+    });
+}
+
+fn validate_reminder_input(input: &ReminderInput) -> Result<(), String> {
+    if input.title.trim().is_empty() {
+        return Err("Reminder title must not be empty".to_string());
+    }
+    if input.fire_at == 0 {
+        return Err("Reminder time must be set".to_string());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn list_reminders(app: AppHandle) -> Vec<Reminder> {
+    let mut reminders = read_reminders(&app);
+    reminders.sort_by_key(|r| r.fire_at);
+    reminders
+}
+
+#[tauri::command]
+pub fn create_reminder(app: AppHandle, input: ReminderInput) -> Result<Reminder, String> {
+    validate_reminder_input(&input)?;
+
+    let reminder = Reminder {
+        id: generate_id(),
+        note_id: input.note_id,
+        title: input.title,
+        body: input.body,
+        fire_at: input.fire_at,
+        anchor_at: Some(input.fire_at),
+        repeat: input.repeat,
+        enabled: true,
+        urgent: input.urgent,
+        last_fired_at: None,
+    };
+
+    with_reminders(&app, |reminders| reminders.push(reminder.clone()));
+    info!("⏰ Created reminder '{}' for {}", reminder.title, reminder.fire_at);
+    Ok(reminder)
+}
+
+#[tauri::command]
+pub fn update_reminder(app: AppHandle, id: String, input: ReminderInput, enabled: bool) -> Result<Reminder, String> {
+    validate_reminder_input(&input)?;
+
+    with_reminders(&app, |reminders| {
+        let reminder = reminders.iter_mut()
+            .find(|r| r.id == id)
+            .ok_or_else(|| format!("Reminder not found: {}", id))?;
+
+        reminder.note_id = input.note_id;
+        reminder.title = input.title;
+        reminder.body = input.body;
+        reminder.fire_at = input.fire_at;
+        reminder.anchor_at = Some(input.fire_at);
+        reminder.repeat = input.repeat;
+        reminder.urgent = input.urgent;
+        reminder.enabled = enabled;
+        Ok(reminder.clone())
+    })
+}
+
+#[tauri::command]
+pub fn delete_reminder(app: AppHandle, id: String) -> Result<(), String> {
+    with_reminders(&app, |reminders| {
+        let before = reminders.len();
+        reminders.retain(|r| r.id != id);
+        if reminders.len() == before {
+            Err(format!("Reminder not found: {}", id))
+        } else {
+            Ok(())
+        }
+    })
+}
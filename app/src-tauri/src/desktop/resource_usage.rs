@@ -0,0 +1,146 @@
+use tauri::AppHandle;
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+use crate::desktop::{get_app_data_dir, offline_db_size, CLIPBOARD_HISTORY_FILE, CLIPBOARD_IMAGES_DIR, THUMBNAILS_DIR};
+
+/// Where memory and disk go, in bytes. None where this platform or build can't tell.
+#[derive(Debug, Serialize, Clone)]
+pub struct ResourceUsage {
+    /// Resident memory of the app process, webview processes not included
+    #[serde(rename = "appRss")]
+    pub app_rss: Option<u64>,
+    #[serde(rename = "whisperModel")]
+    pub whisper_model: Option<u64>,
+    #[serde(rename = "gpuVram")]
+    pub gpu_vram: Option<u64>,
+    #[serde(rename = "thumbnailCache")]
+    pub thumbnail_cache: u64,
+    /// History file and the copied images it refers to
+    #[serde(rename = "clipboardHistory")]
+    pub clipboard_history: u64,
+    #[serde(rename = "offlineDatabase")]
+    pub offline_database: u64,
+}
+
+/// Size of a file, or of everything below a directory
+fn path_size(path: &Path) -> u64 {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+    fs::read_dir(path)
+        .map(|entries| entries.flatten().map(|entry| path_size(&entry.path())).sum())
+        .unwrap_or(0)
+}
+
+fn whisper_model_memory(app: &AppHandle) -> Option<u64> {
+    #[cfg(all(target_os = "windows", any(feature = "whisper-cuda", feature = "whisper-cpu")))]
+    {
+        let processor = crate::voice::current_voice_processor(app);
+        Some(processor.map(|processor| processor.transcriber.memory_bytes()).unwrap_or(0))
+    }
+
+    #[cfg(not(all(target_os = "windows", any(feature = "whisper-cuda", feature = "whisper-cpu"))))]
+    {
+        let _ = app;
+        None
+    }
+}
+
+/// Resident memory of this process, read from the OS without extra crates
+fn process_resident_memory() -> Option<u64> {
+    #[cfg(target_os = "linux")]
+    {
+        let status = fs::read_to_string("/proc/self/status").ok()?;
+        let kib: u64 = status.lines()
+            .find_map(|line| line.strip_prefix("VmRSS:"))?
+            .trim()
+            .trim_end_matches("kB")
+            .trim()
+            .parse()
+            .ok()?;
+        Some(kib * 1024)
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        use windows::Win32::System::ProcessStatus::{GetProcessMemoryInfo, PROCESS_MEMORY_COUNTERS};
+        use windows::Win32::System::Threading::GetCurrentProcess;
+
+        let mut counters = PROCESS_MEMORY_COUNTERS::default();
+        let size = std::mem::size_of::<PROCESS_MEMORY_COUNTERS>() as u32;
+        unsafe { GetProcessMemoryInfo(GetCurrentProcess(), &mut counters, size) }.ok()?;
+        Some(counters.WorkingSetSize as u64)
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let output = crate::desktop::background_command("ps")
+            .args(["-o", "rss=", "-p", &std::process::id().to_string()])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())?;
+        let kib: u64 = String::from_utf8_lossy(&output.stdout).trim().parse().ok()?;
+        Some(kib * 1024)
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+    {
+        None
+    }
+}
+
+/// Video memory used by this process, as reported by the NVIDIA driver
+fn gpu_memory_in_use() -> Option<u64> {
+    #[cfg(any(target_os = "windows", target_os = "linux"))]
+    {
+        let output = crate::desktop::background_command("nvidia-smi")
+            .args(["--query-compute-apps=pid,used_memory", "--format=csv,noheader,nounits"])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())?;
+
+        let pid = std::process::id();
+        let used_mib: u64 = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| {
+                let (line_pid, used) = line.split_once(',')?;
+                if line_pid.trim().parse::<u32>().ok()? != pid {
+                    return None;
+                }
+                used.trim().parse::<u64>().ok()
+            })
+            .sum();
+        Some(used_mib * 1024 * 1024)
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "linux")))]
+    {
+        None
+    }
+}
+
+fn read_resource_usage(app: &AppHandle) -> Result<ResourceUsage, String> {
+    let data_dir = get_app_data_dir(app)?;
+    Ok(ResourceUsage {
+        app_rss: process_resident_memory(),
+        whisper_model: whisper_model_memory(app),
+        gpu_vram: gpu_memory_in_use(),
+        thumbnail_cache: path_size(&data_dir.join(THUMBNAILS_DIR)),
+        clipboard_history: path_size(&data_dir.join(CLIPBOARD_HISTORY_FILE)) + path_size(&data_dir.join(CLIPBOARD_IMAGES_DIR)),
+        offline_database: offline_db_size(app),
+    })
+}
+
+/// Memory and disk usage for the settings screen
+#[tauri::command]
+pub async fn get_resource_usage(app: AppHandle) -> Result<ResourceUsage, String> {
+    // Walking the caches and asking the GPU driver both take a moment
+    tauri::async_runtime::spawn_blocking(move || read_resource_usage(&app))
+        .await
+        .map_err(|e| format!("Failed to read resource usage: {}", e))?
+}
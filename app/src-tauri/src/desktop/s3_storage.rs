@@ -0,0 +1,299 @@
+use tauri::{AppHandle, Url};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::path::Path;
+use std::time::Duration;
+use chrono::{Datelike, Utc};
+
+use hmac::{Hmac, Mac};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use reqwest::blocking::{Body, Client};
+use reqwest::Method;
+use sha2::{Digest, Sha256};
+
+use crate::desktop::{generate_id, http_client_builder, load_json_or_default, read_secret, save_json, store_secret};
+
+const S3_CONFIG_FILE: &str = "s3_storage.json";
+const S3_KEYCHAIN_ACCOUNT: &str = "s3";
+const S3_TIMEOUT: Duration = Duration::from_secs(300);
+/// Bodies are sent unhashed, large parts don't have to be read twice
+const UNSIGNED_PAYLOAD: &str = "UNSIGNED-PAYLOAD";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct S3Config {
+    pub enabled: bool,
+    /// e.g. `https://minio.example.com:9000`
+    pub endpoint: String,
+    pub region: String,
+    pub bucket: String,
+    /// Secret key is kept in the keychain
+    #[serde(rename = "accessKeyId")]
+    pub access_key_id: String,
+    /// `endpoint/bucket/key` instead of `bucket.endpoint/key`, MinIO needs this by default
+    #[serde(rename = "pathStyle")]
+    pub path_style: bool,
+    /// Prepended to every object key
+    pub prefix: String,
+}
+
+impl Default for S3Config {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoint: String::new(),
+            region: "us-east-1".to_string(),
+            bucket: String::new(),
+            access_key_id: String::new(),
+            path_style: true,
+            prefix: "blinko/".to_string(),
+        }
+    }
+}
+
+/// Signs requests with the configured access key (AWS Signature Version 4)
+pub struct S3Client {
+    config: S3Config,
+    secret_key: String,
+    http: Client,
+}
+
+/// Load S3 storage config from file
+pub fn load_s3_config(app: &AppHandle) -> S3Config {
+    load_json_or_default(app, S3_CONFIG_FILE)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn hmac_sha256(key: &[u8], data: &str) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Percent-encode everything but unreserved characters (and `/` in paths), as SigV4 expects
+fn uri_encode(value: &str, keep_slash: bool) -> String {
+    value.bytes().map(|b| match b {
+        b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+        b'/' if keep_slash => "/".to_string(),
+        _ => format!("%{:02X}", b),
+    }).collect()
+}
+
+/// First text of an element in an S3 XML response
+fn xml_element_text(body: &str, name: &str) -> Option<String> {
+    let mut reader = Reader::from_str(body);
+    let mut inside = false;
+    loop {
+        match reader.read_event().ok()? {
+            Event::Start(e) if e.local_name().as_ref() == name.as_bytes() => inside = true,
+            Event::Text(e) if inside => return e.unescape().ok().map(|text| text.to_string()),
+            Event::End(_) => inside = false,
+            Event::Eof => return None,
+            _ => {}
+        }
+    }
+}
+
+/// Object key like `blinko/2026/10/<id>-photo.jpg`
+pub fn s3_object_key(config: &S3Config, file_name: &str) -> String {
+    let now = Utc::now();
+    format!("{}{}/{:02}/{}-{}", config.prefix, now.year(), now.month(), generate_id(), file_name)
+}
+
+impl S3Client {
+    pub fn new(app: &AppHandle) -> Result<Self, String> {
+        let config = load_s3_config(app);
+        if !config.enabled || config.endpoint.is_empty() || config.bucket.is_empty() {
+            return Err("S3 storage is not configured".to_string());
+        }
+        let secret_key = read_secret(Some(S3_KEYCHAIN_ACCOUNT))?
+            .ok_or("S3 secret key is missing, please enter it again")?;
+        let http = http_client_builder()
+            .timeout(S3_TIMEOUT)
+            .build()
+            .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+        Ok(Self { config, secret_key, http })
+    }
+
+    /// Host and path of an object (or the bucket for an empty key)
+    fn location(&self, key: &str) -> Result<(String, String), String> {
+        let endpoint = Url::parse(&self.config.endpoint)
+            .map_err(|e| format!("Invalid S3 endpoint: {}", e))?;
+        let host = endpoint.host_str().ok_or("S3 endpoint has no host")?;
+        let host = match endpoint.port() {
+            Some(port) => format!("{}:{}", host, port),
+            None => host.to_string(),
+        };
+        let key = uri_encode(key, true);
+        Ok(if self.config.path_style && key.is_empty() {
+            (host, format!("/{}", self.config.bucket))
+        } else if self.config.path_style {
+            (host, format!("/{}/{}", self.config.bucket, key))
+        } else {
+            (format!("{}.{}", self.config.bucket, host), format!("/{}", key))
+        })
+    }
+
+    fn scheme(&self) -> &'static str {
+        if self.config.endpoint.starts_with("http://") { "http" } else { "https" }
+    }
+
+    /// Public URL of an object, only reachable if the bucket policy allows it
+    pub fn object_url(&self, key: &str) -> String {
+        let scheme = self.scheme();
+        match self.location(key) {
+            Ok((host, path)) => format!("{}://{}{}", scheme, host, path),
+            Err(_) => String::new(),
+        }
+    }
+
+    fn signed_request(&self, method: Method, key: &str, query: &[(&str, &str)]) -> Result<reqwest::blocking::RequestBuilder, String> {
+        let (host, path) = self.location(key)?;
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+
+        let mut pairs: Vec<(String, String)> = query.iter()
+            .map(|(k, v)| (uri_encode(k, false), uri_encode(v, false)))
+            .collect();
+        pairs.sort();
+        let canonical_query = pairs.iter().map(|(k, v)| format!("{}={}", k, v)).collect::<Vec<_>>().join("&");
+
+        let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+        let canonical_request = format!(
+            "{}\n{}\n{}\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\n{}\n{}",
+            method, path, canonical_query, host, UNSIGNED_PAYLOAD, amz_date, signed_headers, UNSIGNED_PAYLOAD
+        );
+        let scope = format!("{}/{}/s3/aws4_request", date, self.config.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date, scope, hex(&Sha256::digest(canonical_request.as_bytes()))
+        );
+
+        let mut signing_key = hmac_sha256(format!("AWS4{}", self.secret_key).as_bytes(), &date);
+        for part in [self.config.region.as_str(), "s3", "aws4_request"] {
+            signing_key = hmac_sha256(&signing_key, part);
+        }
+        let signature = hex(&hmac_sha256(&signing_key, &string_to_sign));
+
+        let scheme = self.scheme();
+        let mut url = format!("{}://{}{}", scheme, host, path);
+        if !canonical_query.is_empty() {
+            url = format!("{}?{}", url, canonical_query);
+        }
+        Ok(self.http.request(method, url)
+            .header("x-amz-date", amz_date)
+            .header("x-amz-content-sha256", UNSIGNED_PAYLOAD)
+            .header("Authorization", format!(
+                "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+                self.config.access_key_id, scope, signed_headers, signature
+            )))
+    }
+
+    fn send(&self, request: reqwest::blocking::RequestBuilder, action: &str) -> Result<reqwest::blocking::Response, String> {
+        let response = request.send().map_err(|e| format!("S3 {} failed: {}", action, e))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().unwrap_or_default();
+            let message = xml_element_text(&body, "Message").unwrap_or_default();
+            return Err(format!("S3 {} failed with {}: {}", action, status, message));
+        }
+        Ok(response)
+    }
+
+    /// Check that the bucket exists and the credentials can reach it
+    pub fn check_bucket(&self) -> Result<(), String> {
+        self.send(self.signed_request(Method::HEAD, "", &[])?, "bucket check").map(|_| ())
+    }
+
+    /// Start a multipart upload, returns its upload id
+    pub fn create_multipart_upload(&self, key: &str) -> Result<String, String> {
+        let response = self.send(self.signed_request(Method::POST, key, &[("uploads", "")])?, "upload start")?;
+        let body = response.text().map_err(|e| format!("Failed to read S3 response: {}", e))?;
+        xml_element_text(&body, "UploadId").ok_or_else(|| "S3 did not return an upload id".to_string())
+    }
+
+    /// Upload one part (numbered from 1), returns its ETag
+    pub fn upload_part(&self, key: &str, upload_id: &str, part_number: u32, data: Vec<u8>) -> Result<String, String> {
+        let part = part_number.to_string();
+        let request = self.signed_request(Method::PUT, key, &[("partNumber", part.as_str()), ("uploadId", upload_id)])?
+            .body(data);
+        let response = self.send(request, "part upload")?;
+        response.headers()
+            .get("ETag")
+            .and_then(|value| value.to_str().ok())
+            .map(String::from)
+            .ok_or_else(|| format!("S3 did not return an ETag for part {}", part_number))
+    }
+
+    pub fn complete_multipart_upload(&self, key: &str, upload_id: &str, etags: &[String]) -> Result<(), String> {
+        let parts: String = etags.iter().enumerate()
+            .map(|(index, etag)| format!("<Part><PartNumber>{}</PartNumber><ETag>{}</ETag></Part>", index + 1, etag))
+            .collect();
+        let request = self.signed_request(Method::POST, key, &[("uploadId", upload_id)])?
+            .header("Content-Type", "application/xml")
+            .body(format!("<CompleteMultipartUpload>{}</CompleteMultipartUpload>", parts));
+        let response = self.send(request, "upload completion")?;
+        // Errors can also arrive with a 200 status once the body is read
+        let body = response.text().unwrap_or_default();
+        match xml_element_text(&body, "Code") {
+            Some(code) => Err(format!("S3 upload completion failed: {}", code)),
+            None => Ok(()),
+        }
+    }
+
+    /// Drop the parts of an upload that will not be finished
+    pub fn abort_multipart_upload(&self, key: &str, upload_id: &str) -> Result<(), String> {
+        self.send(self.signed_request(Method::DELETE, key, &[("uploadId", upload_id)])?, "upload abort").map(|_| ())
+    }
+}
+
+/// PUT a whole file to a URL presigned by the Blinko server, streamed from disk
+pub fn put_presigned_file(url: &str, path: &Path, content_type: &str) -> Result<(), String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let size = file.metadata().map(|m| m.len()).unwrap_or(0);
+    let response = http_client_builder()
+        .timeout(S3_TIMEOUT)
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?
+        .put(url)
+        .header("Content-Type", content_type)
+        .body(Body::sized(file, size))
+        .send()
+        .map_err(|e| format!("Presigned upload failed: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Presigned upload failed with {}", response.status()));
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_s3_config(app: AppHandle) -> S3Config {
+    load_s3_config(&app)
+}
+
+/// Save storage settings, `secret_key` goes to the keychain when provided
+#[tauri::command]
+pub async fn save_s3_config(app: AppHandle, config: S3Config, secret_key: Option<String>) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        if config.enabled && (config.endpoint.trim().is_empty() || config.bucket.trim().is_empty()) {
+            return Err("S3 endpoint and bucket are required".to_string());
+        }
+        if let Some(ref secret_key) = secret_key {
+            store_secret(Some(S3_KEYCHAIN_ACCOUNT), secret_key)?;
+        }
+        save_json(&app, S3_CONFIG_FILE, &config)
+    })
+    .await
+    .map_err(|e| format!("Failed to save S3 settings: {}", e))?
+}
+
+#[tauri::command]
+pub async fn test_s3_connection(app: AppHandle) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || S3Client::new(&app)?.check_bucket())
+        .await
+        .map_err(|e| format!("S3 connection test failed: {}", e))?
+}
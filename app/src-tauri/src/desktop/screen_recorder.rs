@@ -0,0 +1,362 @@
+use tauri::{AppHandle, Emitter};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::Write;
+use std::process::{Child, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::desktop::{background_command, ensure_disk_space, get_app_data_subdir, now_millis};
+
+const RECORDINGS_DIR: &str = "recordings";
+/// Free space needed to start, enough for several minutes of video
+const MIN_RECORDING_SPACE: u64 = 1024 * 1024 * 1024;
+/// ffmpeg fails within this time on missing devices, codecs or permissions
+const STARTUP_CHECK: Duration = Duration::from_millis(1500);
+const STOP_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ScreenRecordingOptions {
+    /// Container format: "mp4" or "webm"
+    #[serde(default = "default_format")]
+    pub format: String,
+
+    /// Whether to record the microphone alongside the screen
+    #[serde(rename = "includeMicrophone", default)]
+    pub include_microphone: bool,
+
+    /// Microphone device name (platform default when empty)
+    #[serde(rename = "microphoneDevice", default)]
+    pub microphone_device: Option<String>,
+
+    /// Capture frame rate
+    #[serde(default = "default_fps")]
+    pub fps: u32,
+
+    /// Custom path to the ffmpeg executable (uses PATH when empty)
+    #[serde(rename = "ffmpegPath", default)]
+    pub ffmpeg_path: Option<String>,
+}
+
+fn default_format() -> String {
+    "mp4".to_string()
+}
+
+fn default_fps() -> u32 {
+    30
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ScreenRecordingProgress {
+    pub path: String,
+    #[serde(rename = "elapsedSeconds")]
+    pub elapsed_seconds: f64,
+    #[serde(rename = "fileSize")]
+    pub file_size: u64,
+}
+
+struct ActiveRecording {
+    child: Child,
+    path: String,
+    /// ffmpeg's error output, so failures can be explained
+    log_path: String,
+    started_at: Instant,
+}
+
+static ACTIVE_RECORDING: LazyLock<Mutex<Option<ActiveRecording>>> = LazyLock::new(|| Mutex::new(None));
+// Set while ffmpeg is launched and checked, without holding the recording lock for that long
+static RECORDING_STARTING: AtomicBool = AtomicBool::new(false);
+
+/// Device indexes and names listed by `ffmpeg -f avfoundation -list_devices true`
+#[cfg(target_os = "macos")]
+#[derive(Debug, Default)]
+struct AvFoundationDevices {
+    video: Vec<(String, String)>,
+    audio: Vec<(String, String)>,
+}
+
+/// Parse lines like `[AVFoundation indev @ 0x7f8] [1] Capture screen 0`
+#[cfg(target_os = "macos")]
+fn parse_avfoundation_devices(output: &str) -> AvFoundationDevices {
+    let mut devices = AvFoundationDevices::default();
+    let mut in_audio = false;
+    for line in output.lines() {
+        if line.contains("AVFoundation video devices") {
+            in_audio = false;
+            continue;
+        }
+        if line.contains("AVFoundation audio devices") {
+            in_audio = true;
+            continue;
+        }
+        let Some((_, rest)) = line.split_once("] [") else { continue };
+        let Some((index, name)) = rest.split_once("] ") else { continue };
+        if index.is_empty() || !index.chars().all(|c| c.is_ascii_digit()) {
+            continue;
+        }
+        let device = (index.to_string(), name.trim().to_string());
+        if in_audio {
+            devices.audio.push(device);
+        } else {
+            devices.video.push(device);
+        }
+    }
+    devices
+}
+
+/// avfoundation input as "video:audio" indexes. Cameras are listed before screens and
+/// their number varies, so the screen is looked up by name.
+#[cfg(target_os = "macos")]
+fn avfoundation_input(program: &str, options: &ScreenRecordingOptions) -> Result<String, String> {
+    // Listing exits with an error since no input is given, the devices are in stderr
+    let output = background_command(program)
+        .args(["-hide_banner", "-f", "avfoundation", "-list_devices", "true", "-i", ""])
+        .output()
+        .map_err(|e| format!("Failed to start ffmpeg (is it installed?): {}", e))?;
+    let devices = parse_avfoundation_devices(&String::from_utf8_lossy(&output.stderr));
+
+    let screen = devices.video.iter()
+        .find(|(_, name)| name.starts_with("Capture screen"))
+        .map(|(index, _)| index.clone())
+        .ok_or("No screen capture device found, check the screen recording permission")?;
+    if !options.include_microphone {
+        return Ok(format!("{}:none", screen));
+    }
+
+    // The device may be given by index or by name
+    let wanted = options.microphone_device.as_deref().map(str::trim).filter(|device| !device.is_empty());
+    let microphone = match wanted {
+        Some(wanted) => devices.audio.iter()
+            .find(|(index, name)| index == wanted || name == wanted)
+            .map(|(index, _)| index.clone())
+            .ok_or_else(|| format!("Microphone not found: {}", wanted))?,
+        None => devices.audio.first()
+            .map(|(index, _)| index.clone())
+            .ok_or("No microphone found")?,
+    };
+    Ok(format!("{}:{}", screen, microphone))
+}
+
+/// Platform specific ffmpeg input arguments for screen (and optional microphone) capture
+fn capture_input_args(program: &str, options: &ScreenRecordingOptions) -> Result<Vec<String>, String> {
+    let fps = options.fps.clamp(1, 60).to_string();
+    let mut args: Vec<String> = Vec::new();
+
+    #[cfg(target_os = "windows")]
+    {
+        args.extend(["-f", "gdigrab", "-framerate", fps.as_str(), "-i", "desktop"].map(String::from));
+        if options.include_microphone {
+            let device = options.microphone_device.clone()
+                .unwrap_or_else(|| "default".to_string());
+            args.extend(["-f".to_string(), "dshow".to_string(), "-i".to_string(), format!("audio={}", device)]);
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let input = avfoundation_input(program, options)?;
+        args.extend(["-f".to_string(), "avfoundation".to_string(), "-framerate".to_string(), fps, "-i".to_string(), input]);
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let display = std::env::var("DISPLAY").unwrap_or_else(|_| ":0".to_string());
+        args.extend(["-f".to_string(), "x11grab".to_string(), "-framerate".to_string(), fps, "-i".to_string(), display]);
+        if options.include_microphone {
+            let device = options.microphone_device.clone()
+                .unwrap_or_else(|| "default".to_string());
+            args.extend(["-f".to_string(), "pulse".to_string(), "-i".to_string(), device]);
+        }
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    let _ = program;
+    Ok(args)
+}
+
+fn codec_args(format: &str, include_microphone: bool) -> Vec<String> {
+    let mut args: Vec<&str> = match format {
+        "webm" => vec!["-c:v", "libvpx-vp9", "-deadline", "realtime", "-b:v", "2M"],
+        _ => vec!["-c:v", "libx264", "-preset", "veryfast", "-pix_fmt", "yuv420p"],
+    };
+
+    if include_microphone {
+        args.extend(match format {
+            "webm" => ["-c:a", "libopus"],
+            _ => ["-c:a", "aac"],
+        });
+    }
+
+    args.into_iter().map(String::from).collect()
+}
+
+fn spawn_progress_thread(app: AppHandle) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(Duration::from_secs(1));
+
+        let progress = {
+            let guard = ACTIVE_RECORDING.lock().unwrap();
+            match *guard {
+                Some(ref recording) => ScreenRecordingProgress {
+                    path: recording.path.clone(),
+                    elapsed_seconds: recording.started_at.elapsed().as_secs_f64(),
+                    file_size: fs::metadata(&recording.path).map(|m| m.len()).unwrap_or(0),
+                },
+                None => break,
+            }
+        };
+
+        if let Err(e) = app.emit("screen-recording-progress", &progress) {
+            error!("Failed to emit screen recording progress: {}", e);
+        }
+    });
+}
+
+/// Last line ffmpeg logged, usually the reason it failed
+fn last_log_line(log_path: &str) -> String {
+    fs::read_to_string(log_path)
+        .ok()
+        .and_then(|log| log.lines().rev().map(str::trim).find(|line| !line.is_empty()).map(String::from))
+        .unwrap_or_else(|| "no error output".to_string())
+}
+
+fn begin_screen_recording(app: &AppHandle, options: ScreenRecordingOptions) -> Result<String, String> {
+    if ACTIVE_RECORDING.lock().unwrap().is_some() {
+        return Err("A screen recording is already in progress".to_string());
+    }
+
+    let format = match options.format.as_str() {
+        "mp4" | "webm" => options.format.clone(),
+        other => return Err(format!("Unsupported recording format: {}", other)),
+    };
+
+    let dir = get_app_data_subdir(app, RECORDINGS_DIR)?;
+    ensure_disk_space(&dir, MIN_RECORDING_SPACE)?;
+    let path = dir
+        .join(format!("recording-{}.{}", now_millis(), format))
+        .to_string_lossy()
+        .to_string();
+    let log_path = format!("{}.log", path);
+
+    let program = options.ffmpeg_path.clone()
+        .filter(|p| !p.trim().is_empty())
+        .unwrap_or_else(|| "ffmpeg".to_string());
+
+    let input_args = capture_input_args(&program, &options)?;
+    let log = File::create(&log_path)
+        .map_err(|e| format!("Failed to create ffmpeg log: {}", e))?;
+    let mut child = background_command(&program)
+        .args(["-hide_banner", "-loglevel", "error", "-y"])
+        .args(input_args)
+        .args(codec_args(&format, options.include_microphone))
+        .arg(&path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::from(log))
+        .spawn()
+        .map_err(|e| {
+            let _ = fs::remove_file(&log_path);
+            format!("Failed to start ffmpeg (is it installed?): {}", e)
+        })?;
+
+    // A bad device or codec makes ffmpeg quit right away instead of recording
+    let deadline = Instant::now() + STARTUP_CHECK;
+    while Instant::now() < deadline {
+        match child.try_wait() {
+            Ok(Some(status)) => {
+                let reason = last_log_line(&log_path);
+                let _ = fs::remove_file(&log_path);
+                let _ = fs::remove_file(&path);
+                error!("❌ ffmpeg exited right away ({}): {}", status, reason);
+                return Err(format!("ffmpeg exited right away: {}", reason));
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(100)),
+            Err(e) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("Failed to check on ffmpeg: {}", e));
+            }
+        }
+    }
+
+    *ACTIVE_RECORDING.lock().unwrap() = Some(ActiveRecording {
+        child,
+        path: path.clone(),
+        log_path,
+        started_at: Instant::now(),
+    });
+
+    spawn_progress_thread(app.clone());
+    let _ = app.emit("screen-recording-started", &path);
+
+    info!("🎬 Screen recording started: {}", path);
+    Ok(path)
+}
+
+fn finish_screen_recording(app: &AppHandle) -> Result<ScreenRecordingProgress, String> {
+    let mut recording = ACTIVE_RECORDING.lock().unwrap()
+        .take()
+        .ok_or("No screen recording in progress")?;
+
+    // Ask ffmpeg to finish gracefully so the container gets finalized
+    if let Some(ref mut stdin) = recording.child.stdin {
+        let _ = stdin.write_all(b"q");
+        let _ = stdin.flush();
+    }
+
+    let deadline = Instant::now() + STOP_TIMEOUT;
+    loop {
+        match recording.child.try_wait() {
+            Ok(Some(_)) => break,
+            Ok(None) if Instant::now() < deadline => std::thread::sleep(Duration::from_millis(100)),
+            _ => {
+                warn!("ffmpeg did not exit in time, killing it");
+                let _ = recording.child.kill();
+                let _ = recording.child.wait();
+                break;
+            }
+        }
+    }
+
+    let result = ScreenRecordingProgress {
+        path: recording.path.clone(),
+        elapsed_seconds: recording.started_at.elapsed().as_secs_f64(),
+        file_size: fs::metadata(&recording.path).map(|m| m.len()).unwrap_or(0),
+    };
+    let reason = last_log_line(&recording.log_path);
+    let _ = fs::remove_file(&recording.log_path);
+
+    if result.file_size == 0 {
+        return Err(format!("Recording failed, no output was written: {}", reason));
+    }
+
+    let _ = app.emit("screen-recording-finished", &result);
+    info!("⏹️ Screen recording saved: {} ({} bytes)", result.path, result.file_size);
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn start_screen_recording(app: AppHandle, options: ScreenRecordingOptions) -> Result<String, String> {
+    if RECORDING_STARTING.swap(true, Ordering::SeqCst) {
+        return Err("A screen recording is already starting".to_string());
+    }
+    let result = tauri::async_runtime::spawn_blocking(move || begin_screen_recording(&app, options))
+        .await
+        .map_err(|e| format!("Failed to start screen recording: {}", e));
+    RECORDING_STARTING.store(false, Ordering::SeqCst);
+    result?
+}
+
+/// Lets ffmpeg finalize the file, which can take a few seconds
+#[tauri::command]
+pub async fn stop_screen_recording(app: AppHandle) -> Result<ScreenRecordingProgress, String> {
+    tauri::async_runtime::spawn_blocking(move || finish_screen_recording(&app))
+        .await
+        .map_err(|e| format!("Failed to stop screen recording: {}", e))?
+}
+
+#[tauri::command]
+pub fn is_screen_recording() -> bool {
+    ACTIVE_RECORDING.lock().unwrap().is_some()
+}
@@ -0,0 +1,285 @@
+use tauri::{AppHandle, Emitter};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use rhai::{Array, Dynamic, Engine, EvalAltResult, Scope, AST};
+
+use crate::desktop::{background_command, get_app_data_subdir, http_client_builder, load_json_or_default, register_shortcut_command, save_json, send_notification};
+
+const SCRIPTS_DIR: &str = "scripts";
+const SCRIPTS_FILE: &str = "scripts.json";
+const SCRIPT_EXTENSION: &str = "rhai";
+/// Stops runaway loops; plenty for small automations
+const MAX_SCRIPT_OPERATIONS: u64 = 50_000_000;
+const SCRIPT_HTTP_TIMEOUT: Duration = Duration::from_secs(15);
+/// Prefix of the shortcut commands that run a script's `on_hotkey`
+pub const SCRIPT_COMMAND_PREFIX: &str = "script:";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ScriptHotkey {
+    pub shortcut: String,
+    /// Script file name in the scripts directory, e.g. "daily.rhai"
+    pub script: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ScriptSchedule {
+    pub script: String,
+    #[serde(rename = "everyMinutes")]
+    pub every_minutes: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ScriptsConfig {
+    pub enabled: bool,
+    #[serde(default)]
+    pub hotkeys: Vec<ScriptHotkey>,
+    #[serde(default)]
+    pub schedules: Vec<ScriptSchedule>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ScriptInfo {
+    pub name: String,
+    /// Hook functions the script defines
+    pub hooks: Vec<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct ScriptNoteEvent {
+    script: String,
+    content: String,
+}
+
+const SCRIPT_HOOKS: [&str; 3] = ["on_hotkey", "on_transcription", "on_schedule"];
+
+static SCRIPTS_CONFIG: LazyLock<Mutex<Option<ScriptsConfig>>> = LazyLock::new(|| Mutex::new(None));
+// Last run per scheduled script, schedules start counting at app launch
+static SCHEDULE_LAST_RUN: LazyLock<Mutex<HashMap<String, Instant>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+fn scripts_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    get_app_data_subdir(app, SCRIPTS_DIR)
+}
+
+fn read_config(app: &AppHandle) -> ScriptsConfig {
+    SCRIPTS_CONFIG.lock().unwrap()
+        .get_or_insert_with(|| load_json_or_default(app, SCRIPTS_FILE))
+        .clone()
+}
+
+/// Engine with the automation API:
+/// `create_note(content)`, `notify(title, body)`, `run_command(program, args)` and `http_get(url)`
+fn create_engine(app: &AppHandle, script: &str) -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_SCRIPT_OPERATIONS);
+
+    let name = script.to_string();
+    engine.on_print(move |text| info!("📜 [{}] {}", name, text));
+    let name = script.to_string();
+    engine.on_debug(move |text, _, _| debug!("📜 [{}] {}", name, text));
+
+    let (handle, name) = (app.clone(), script.to_string());
+    engine.register_fn("create_note", move |content: &str| -> Result<(), Box<EvalAltResult>> {
+        // The frontend owns note creation, same as local API captures
+        handle.emit("script-create-note", ScriptNoteEvent { script: name.clone(), content: content.to_string() })
+            .map_err(|e| format!("Failed to create note: {}", e).into())
+    });
+
+    let handle = app.clone();
+    engine.register_fn("notify", move |title: &str, body: &str| send_notification(&handle, title, body));
+
+    engine.register_fn("run_command", |program: &str, args: Array| -> Result<String, Box<EvalAltResult>> {
+        let args: Vec<String> = args.into_iter().map(|arg| arg.to_string()).collect();
+        let output = background_command(program)
+            .args(&args)
+            .output()
+            .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+        if !output.status.success() {
+            return Err(format!("{} exited with {}: {}", program, output.status, String::from_utf8_lossy(&output.stderr).trim()).into());
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    });
+
+    engine.register_fn("http_get", |url: &str| -> Result<String, Box<EvalAltResult>> {
+        http_client_builder()
+            .timeout(SCRIPT_HTTP_TIMEOUT)
+            .build()
+            .map_err(|e| format!("Failed to create HTTP client: {}", e))?
+            .get(url)
+            .send()
+            .and_then(|response| response.error_for_status())
+            .and_then(|response| response.text())
+            .map_err(|e| format!("Request to {} failed: {}", url, e).into())
+    });
+
+    engine
+}
+
+fn compile_script(app: &AppHandle, engine: &Engine, script: &str) -> Result<AST, String> {
+    // Only plain file names, scripts can't point outside the scripts directory
+    if std::path::Path::new(script).file_name().and_then(|n| n.to_str()) != Some(script) {
+        return Err(format!("Invalid script name: {}", script));
+    }
+    let path = scripts_dir(app)?.join(script);
+    if path.extension().and_then(|e| e.to_str()) != Some(SCRIPT_EXTENSION) || !path.is_file() {
+        return Err(format!("Script {} not found", script));
+    }
+    engine.compile_file(path).map_err(|e| format!("{}: {}", script, e))
+}
+
+fn defines_hook(ast: &AST, hook: &str) -> bool {
+    ast.iter_functions().any(|f| f.name == hook)
+}
+
+/// Run one hook function of a script; top-level statements run first as setup
+fn run_script_hook(app: &AppHandle, script: &str, hook: &str, args: Vec<Dynamic>) -> Result<Option<Dynamic>, String> {
+    let engine = create_engine(app, script);
+    let ast = compile_script(app, &engine, script)?;
+    if !defines_hook(&ast, hook) {
+        return Ok(None);
+    }
+
+    let mut scope = Scope::new();
+    engine.call_fn::<Dynamic>(&mut scope, &ast, hook, args)
+        .map(Some)
+        .map_err(|e| format!("{} in {}: {}", hook, script, e))
+}
+
+fn spawn_script_hook(app: &AppHandle, script: String, hook: &'static str, args: Vec<Dynamic>) {
+    let app_handle = app.clone();
+    std::thread::spawn(move || {
+        if let Err(e) = run_script_hook(&app_handle, &script, hook, args) {
+            error!("❌ Script failed: {}", e);
+        }
+    });
+}
+
+fn script_names(app: &AppHandle) -> Vec<String> {
+    let Ok(entries) = scripts_dir(app).and_then(|dir| fs::read_dir(dir).map_err(|e| e.to_string())) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|e| e.to_str()) == Some(SCRIPT_EXTENSION))
+        .filter_map(|path| path.file_name().map(|name| name.to_string_lossy().to_string()))
+        .collect();
+    names.sort();
+    names
+}
+
+/// Shortcut handler for `script:<name>` commands
+pub fn run_hotkey_script(app: &AppHandle, script: &str) {
+    if !read_config(app).enabled {
+        return;
+    }
+    info!("📜 Running {} from hotkey", script);
+    spawn_script_hook(app, script.to_string(), "on_hotkey", Vec::new());
+}
+
+/// Give every script with `on_transcription(text)` the finished dictation
+pub fn run_transcription_scripts(app: &AppHandle, text: &str) {
+    if !read_config(app).enabled {
+        return;
+    }
+    for script in script_names(app) {
+        spawn_script_hook(app, script, "on_transcription", vec![Dynamic::from(text.to_string())]);
+    }
+}
+
+/// Runs on every reminder scheduler tick and starts the scripts whose interval has passed
+pub fn check_script_schedules(app: &AppHandle) {
+    let config = read_config(app);
+    if !config.enabled {
+        return;
+    }
+
+    let mut last_runs = SCHEDULE_LAST_RUN.lock().unwrap();
+    for schedule in config.schedules.iter().filter(|s| s.every_minutes > 0) {
+        let interval = Duration::from_secs(schedule.every_minutes * 60);
+        let last_run = last_runs.entry(schedule.script.clone()).or_insert_with(Instant::now);
+        if last_run.elapsed() >= interval {
+            *last_run = Instant::now();
+            spawn_script_hook(app, schedule.script.clone(), "on_schedule", Vec::new());
+        }
+    }
+}
+
+fn register_script_hotkeys(app: &AppHandle, config: &ScriptsConfig) {
+    use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut};
+
+    for hotkey in config.hotkeys.iter() {
+        let Ok(parsed_shortcut) = hotkey.shortcut.parse::<Shortcut>() else {
+            error!("Invalid shortcut '{}' for script {}", hotkey.shortcut, hotkey.script);
+            continue;
+        };
+        let _ = app.global_shortcut().unregister(parsed_shortcut);
+        if !config.enabled {
+            continue;
+        }
+        if let Err(e) = app.global_shortcut().register(parsed_shortcut) {
+            error!("Failed to register shortcut '{}' for script {}: {}", hotkey.shortcut, hotkey.script, e);
+            continue;
+        }
+        register_shortcut_command(app, hotkey.shortcut.clone(), format!("{}{}", SCRIPT_COMMAND_PREFIX, hotkey.script));
+    }
+}
+
+/// Register the hotkeys bound to scripts
+pub fn start_script_hooks(app: &AppHandle) {
+    register_script_hotkeys(app, &read_config(app));
+}
+
+#[tauri::command]
+pub fn get_scripts_config(app: AppHandle) -> ScriptsConfig {
+    read_config(&app)
+}
+
+#[tauri::command]
+pub fn save_scripts_config(app: AppHandle, config: ScriptsConfig) -> Result<(), String> {
+    // Shortcuts dropped from the config have to be released too
+    let previous = ScriptsConfig { enabled: false, ..read_config(&app) };
+    register_script_hotkeys(&app, &previous);
+
+    save_json(&app, SCRIPTS_FILE, &config)?;
+    register_script_hotkeys(&app, &config);
+    *SCRIPTS_CONFIG.lock().unwrap() = Some(config);
+    SCHEDULE_LAST_RUN.lock().unwrap().clear();
+    Ok(())
+}
+
+/// Scripts in the scripts directory with the hooks they define, or their compile error
+#[tauri::command]
+pub fn list_scripts(app: AppHandle) -> Vec<ScriptInfo> {
+    let engine = Engine::new();
+    script_names(&app).into_iter().map(|name| match compile_script(&app, &engine, &name) {
+        Ok(ast) => ScriptInfo {
+            hooks: SCRIPT_HOOKS.iter().filter(|hook| defines_hook(&ast, hook)).map(|hook| hook.to_string()).collect(),
+            name,
+            error: None,
+        },
+        Err(e) => ScriptInfo { name, hooks: Vec::new(), error: Some(e) },
+    }).collect()
+}
+
+/// Run a script's `on_hotkey` by hand, e.g. to try it out; returns what the hook returned
+#[tauri::command]
+pub async fn run_script(app: AppHandle, script: String) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        run_script_hook(&app, &script, "on_hotkey", Vec::new())?
+            .map(|result| result.to_string())
+            .ok_or_else(|| format!("{} has no on_hotkey function", script))
+    })
+    .await
+    .map_err(|e| format!("Failed to run script: {}", e))?
+}
+
+#[tauri::command]
+pub fn get_scripts_directory(app: AppHandle) -> Result<String, String> {
+    scripts_dir(&app).map(|dir| dir.to_string_lossy().to_string())
+}
@@ -0,0 +1,313 @@
+use tauri::AppHandle;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::ops::Bound;
+use std::sync::{LazyLock, Mutex};
+
+use tantivy::collector::TopDocs;
+use tantivy::directory::MmapDirectory;
+use tantivy::query::{AllQuery, BooleanQuery, Occur, Query, QueryParser, RangeQuery, TermQuery};
+use tantivy::schema::{Field, IndexRecordOption, Schema, Value, FAST, INDEXED, STORED, STRING, TEXT};
+use tantivy::{Index, IndexReader, IndexWriter, ReloadPolicy, SnippetGenerator, TantivyDocument, Term};
+
+use crate::desktop::{cached_note_contents, get_app_data_dir, get_app_data_subdir, is_local_encryption_enabled};
+
+const SEARCH_INDEX_DIR: &str = "search_index_v2";
+/// Earlier index that stored full note text, removed on open
+const LEGACY_SEARCH_INDEX_DIR: &str = "search_index";
+const WRITER_MEMORY_BYTES: usize = 50_000_000;
+const DEFAULT_RESULT_LIMIT: usize = 30;
+const PLAIN_SNIPPET_CHARS: usize = 160;
+
+/// Note fields the frontend pushes into the local index
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SearchNote {
+    pub id: i64,
+    pub content: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(rename = "isArchived", default)]
+    pub is_archived: bool,
+    /// Unix millis
+    #[serde(rename = "updatedAt", default)]
+    pub updated_at: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SearchFilters {
+    /// Every listed tag must be present
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub archived: Option<bool>,
+    /// Unix millis, inclusive
+    #[serde(rename = "updatedFrom")]
+    pub updated_from: Option<i64>,
+    #[serde(rename = "updatedTo")]
+    pub updated_to: Option<i64>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SearchResult {
+    pub id: i64,
+    pub score: f32,
+    /// HTML snippet with matches wrapped in <b> tags
+    pub highlight: String,
+    pub tags: Vec<String>,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: i64,
+}
+
+struct SearchFields {
+    id: Field,
+    content: Field,
+    tags: Field,
+    archived: Field,
+    updated_at: Field,
+}
+
+struct SearchIndex {
+    index: Index,
+    reader: IndexReader,
+    writer: IndexWriter,
+    fields: SearchFields,
+}
+
+static SEARCH_INDEX: LazyLock<Mutex<Option<SearchIndex>>> = LazyLock::new(|| Mutex::new(None));
+
+fn build_schema() -> (Schema, SearchFields) {
+    let mut builder = Schema::builder();
+    let fields = SearchFields {
+        id: builder.add_i64_field("id", INDEXED | STORED | FAST),
+        // Not stored, snippets are made from the offline cache which encryption protects
+        content: builder.add_text_field("content", TEXT),
+        tags: builder.add_text_field("tags", STRING | STORED),
+        archived: builder.add_bool_field("archived", INDEXED | STORED),
+        updated_at: builder.add_i64_field("updated_at", INDEXED | STORED | FAST),
+    };
+    (builder.build(), fields)
+}
+
+fn open_search_index(app: &AppHandle) -> Result<SearchIndex, String> {
+    let legacy = get_app_data_dir(app)?.join(LEGACY_SEARCH_INDEX_DIR);
+    if legacy.exists() {
+        if let Err(e) = fs::remove_dir_all(&legacy) {
+            warn!("Failed to remove old search index: {}", e);
+        }
+    }
+
+    let dir = get_app_data_subdir(app, SEARCH_INDEX_DIR)?;
+    let (schema, fields) = build_schema();
+
+    let directory = MmapDirectory::open(&dir)
+        .map_err(|e| format!("Failed to open search index directory: {}", e))?;
+    let index = Index::open_or_create(directory, schema)
+        .map_err(|e| format!("Failed to open search index: {}", e))?;
+    let writer = index.writer(WRITER_MEMORY_BYTES)
+        .map_err(|e| format!("Failed to create search index writer: {}", e))?;
+    let reader = index.reader_builder()
+        .reload_policy(ReloadPolicy::Manual)
+        .try_into()
+        .map_err(|e| format!("Failed to create search index reader: {}", e))?;
+
+    debug!("🔍 Search index opened at {}", dir.display());
+    Ok(SearchIndex { index, reader, writer, fields })
+}
+
+/// Run a closure against the lazily opened index
+fn with_search_index<T>(
+    app: &AppHandle,
+    f: impl FnOnce(&mut SearchIndex) -> Result<T, String>,
+) -> Result<T, String> {
+    // Indexed terms are note text in plaintext
+    if is_local_encryption_enabled() {
+        return Err("Local search is unavailable while local encryption is on".to_string());
+    }
+    let mut guard = SEARCH_INDEX.lock().unwrap();
+    if guard.is_none() {
+        *guard = Some(open_search_index(app)?);
+    }
+    f(guard.as_mut().unwrap())
+}
+
+fn commit(search: &mut SearchIndex) -> Result<(), String> {
+    search.writer.commit()
+        .map_err(|e| format!("Failed to commit search index: {}", e))?;
+    search.reader.reload()
+        .map_err(|e| format!("Failed to reload search index: {}", e))
+}
+
+/// Add or replace notes in the index
+pub fn upsert_search_notes(app: &AppHandle, notes: &[SearchNote]) -> Result<usize, String> {
+    with_search_index(app, |search| {
+        let fields = &search.fields;
+        for note in notes {
+            search.writer.delete_term(Term::from_field_i64(fields.id, note.id));
+
+            let mut doc = TantivyDocument::default();
+            doc.add_i64(fields.id, note.id);
+            doc.add_text(fields.content, &note.content);
+            for tag in &note.tags {
+                doc.add_text(fields.tags, tag);
+            }
+            doc.add_bool(fields.archived, note.is_archived);
+            doc.add_i64(fields.updated_at, note.updated_at);
+
+            search.writer.add_document(doc)
+                .map_err(|e| format!("Failed to index note {}: {}", note.id, e))?;
+        }
+        commit(search)?;
+        Ok(notes.len())
+    })
+}
+
+/// Remove notes from the index
+pub fn remove_search_notes(app: &AppHandle, ids: &[i64]) -> Result<(), String> {
+    with_search_index(app, |search| {
+        for id in ids {
+            search.writer.delete_term(Term::from_field_i64(search.fields.id, *id));
+        }
+        commit(search)
+    })
+}
+
+/// Close the index and delete its files
+pub fn wipe_search_index(app: &AppHandle) -> Result<(), String> {
+    *SEARCH_INDEX.lock().unwrap() = None;
+    let dir = get_app_data_dir(app)?.join(SEARCH_INDEX_DIR);
+    if dir.exists() {
+        fs::remove_dir_all(&dir).map_err(|e| format!("Failed to remove search index: {}", e))?;
+    }
+    Ok(())
+}
+
+fn plain_snippet(content: &str) -> String {
+    let mut snippet: String = content.chars().take(PLAIN_SNIPPET_CHARS).collect();
+    if content.chars().count() > PLAIN_SNIPPET_CHARS {
+        snippet.push('…');
+    }
+    snippet
+}
+
+fn run_search(app: &AppHandle, search: &SearchIndex, query: &str, filters: &SearchFilters) -> Result<Vec<SearchResult>, String> {
+    let fields = &search.fields;
+    let searcher = search.reader.searcher();
+
+    let text_query: Option<Box<dyn Query>> = if query.trim().is_empty() {
+        None
+    } else {
+        let mut parser = QueryParser::for_index(&search.index, vec![fields.content, fields.tags]);
+        parser.set_conjunction_by_default();
+        // Lenient parsing so half-typed queries like `foo AND` still return results
+        let (parsed, _errors) = parser.parse_query_lenient(query);
+        Some(parsed)
+    };
+
+    let mut clauses: Vec<(Occur, Box<dyn Query>)> = Vec::new();
+    match text_query {
+        Some(ref q) => clauses.push((Occur::Must, q.box_clone())),
+        None => clauses.push((Occur::Must, Box::new(AllQuery))),
+    }
+    for tag in &filters.tags {
+        clauses.push((Occur::Must, Box::new(TermQuery::new(
+            Term::from_field_text(fields.tags, tag),
+            IndexRecordOption::Basic,
+        ))));
+    }
+    if let Some(archived) = filters.archived {
+        clauses.push((Occur::Must, Box::new(TermQuery::new(
+            Term::from_field_bool(fields.archived, archived),
+            IndexRecordOption::Basic,
+        ))));
+    }
+    if filters.updated_from.is_some() || filters.updated_to.is_some() {
+        let lower = filters.updated_from.map(Bound::Included).unwrap_or(Bound::Unbounded);
+        let upper = filters.updated_to.map(Bound::Included).unwrap_or(Bound::Unbounded);
+        clauses.push((Occur::Must, Box::new(RangeQuery::new_i64_bounds("updated_at".to_string(), lower, upper))));
+    }
+
+    let combined = BooleanQuery::new(clauses);
+    let limit = filters.limit.unwrap_or(DEFAULT_RESULT_LIMIT).max(1);
+    let top_docs = searcher.search(&combined, &TopDocs::with_limit(limit))
+        .map_err(|e| format!("Search failed: {}", e))?;
+
+    let snippet_generator = match text_query {
+        Some(ref q) => Some(SnippetGenerator::create(&searcher, q.as_ref(), fields.content)
+            .map_err(|e| format!("Failed to create snippet generator: {}", e))?),
+        None => None,
+    };
+
+    let mut docs = Vec::with_capacity(top_docs.len());
+    for (score, address) in top_docs {
+        let doc: TantivyDocument = searcher.doc(address)
+            .map_err(|e| format!("Failed to load search hit: {}", e))?;
+        docs.push((score, doc));
+    }
+    let ids: Vec<i64> = docs.iter()
+        .filter_map(|(_, doc)| doc.get_first(fields.id).and_then(|v| v.as_i64()))
+        .collect();
+    // Notes missing from the cache get an empty snippet
+    let contents: HashMap<i64, String> = cached_note_contents(app, &ids).unwrap_or_default();
+
+    let mut results = Vec::with_capacity(docs.len());
+    for (score, doc) in docs {
+        let id = doc.get_first(fields.id).and_then(|v| v.as_i64()).unwrap_or_default();
+        let content = contents.get(&id).map(String::as_str).unwrap_or_default();
+        let highlight = match snippet_generator {
+            Some(ref generator) => {
+                let snippet = generator.snippet(content);
+                if snippet.fragment().is_empty() {
+                    plain_snippet(content)
+                } else {
+                    snippet.to_html()
+                }
+            }
+            None => plain_snippet(content),
+        };
+
+        results.push(SearchResult {
+            id,
+            score,
+            highlight,
+            tags: doc.get_all(fields.tags).filter_map(|v| v.as_str().map(String::from)).collect(),
+            updated_at: doc.get_first(fields.updated_at).and_then(|v| v.as_i64()).unwrap_or_default(),
+        });
+    }
+
+    Ok(results)
+}
+
+/// Ranked local search over indexed notes
+#[tauri::command]
+pub async fn search_notes(app: AppHandle, query: String, filters: Option<SearchFilters>) -> Result<Vec<SearchResult>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let filters = filters.unwrap_or_default();
+        with_search_index(&app, |search| run_search(&app, search, &query, &filters))
+    })
+    .await
+    .map_err(|e| format!("Search task failed: {}", e))?
+}
+
+/// Add or update notes in the local search index
+#[tauri::command]
+pub async fn index_notes(app: AppHandle, notes: Vec<SearchNote>) -> Result<usize, String> {
+    tauri::async_runtime::spawn_blocking(move || upsert_search_notes(&app, &notes))
+        .await
+        .map_err(|e| format!("Indexing task failed: {}", e))?
+}
+
+#[tauri::command]
+pub fn remove_notes_from_index(app: AppHandle, ids: Vec<i64>) -> Result<(), String> {
+    remove_search_notes(&app, &ids)
+}
+
+/// Drop every document, used before a full reindex or on logout
+#[tauri::command]
+pub fn clear_search_index(app: AppHandle) -> Result<(), String> {
+    with_search_index(&app, |search| {
+        search.writer.delete_all_documents()
+            .map_err(|e| format!("Failed to clear search index: {}", e))?;
+        commit(search)
+    })
+}
@@ -5,31 +5,57 @@ use tauri::{AppHandle, Manager};
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 use tauri_plugin_global_shortcut::{ShortcutState, ShortcutEvent};
 
-use crate::desktop::{HotkeyConfig, setup_system_tray, toggle_quicknote_window, toggle_quickai_window, toggle_quicktool_window, restore_main_window_state, setup_window_state_monitoring};
+use crate::desktop::{HotkeyConfig, setup_system_tray, restore_main_window_state, setup_window_state_monitoring, start_clipboard_watcher, start_reminder_scheduler, setup_deep_links, parse_cli_args, run_cli_commands, setup_file_drop_import, restart_folder_watcher, restart_markdown_sync_watcher, start_connectivity_monitor, restore_active_account, load_network_config, resume_pending_downloads, resume_pending_uploads, start_local_api, restart_mqtt_client, start_backup_scheduler, start_cache_cleanup, restore_encryption_key, init_logging, install_crash_handler, is_hidden_launch, refresh_autostart, AUTOSTART_FLAG, start_idle_monitor, start_dnd_monitor, start_theme_monitor, restore_window_effects, apply_titlebar_config, restore_window_zoom, setup_palette_window, start_mouse_gestures, start_hot_corner_monitor, restore_session, register_template_shortcuts, start_snippet_expander, start_script_hooks, start_caldav_sync, start_sync_scheduler, restart_media_keys, start_activity_tracker, start_privacy_guard, start_feed_poller, start_mail_poller, start_control_socket, start_dbus_service, register_apple_event_handlers, setup_os_search, ShortcutBinding, ShortcutRegistry};
 #[cfg(all(target_os = "windows", any(feature = "whisper-cuda", feature = "whisper-cpu")))]
-use crate::voice::{load_voice_config, VoiceProcessor, VOICE_STATE};
+use crate::voice::{load_voice_config, start_voice_processor, warm_up_voice_model};
 
 pub fn setup_app(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
     let app_handle = app.handle();
+
+    // Start logging first so everything below ends up in the log files
+    if let Err(e) = init_logging(&app_handle) {
+        eprintln!("Failed to initialize logging: {}", e);
+    }
+
+    // Save a crash report with recent log lines if anything panics from here on
+    if let Err(e) = install_crash_handler(&app_handle) {
+        error!("Failed to install crash handler: {}", e);
+    }
+
     let main_window = app.get_webview_window("main").unwrap();
 
     // Check if launched via autostart
     let args: Vec<String> = std::env::args().collect();
-    let is_autostart = args.iter().any(|arg| arg == "--autostart");
+    let is_autostart = args.iter().any(|arg| arg == AUTOSTART_FLAG);
+    let cli_commands = parse_cli_args(args.get(1..).unwrap_or_default());
 
-    if is_autostart {
-        println!("Application launched via autostart, hiding window to tray");
-        // Hide window immediately on autostart
+    if is_hidden_launch(&args) || !cli_commands.is_empty() {
+        info!("Application launched hidden or via quick capture command, hiding window to tray");
+        // Hide window immediately on hidden autostart and quick capture launches
         let _ = main_window.hide();
     } else {
-        println!("Application launched normally");
+        info!("Application launched {}", if is_autostart { "via autostart" } else { "normally" });
         // Restore window state before applying decorations only for normal launches
         restore_main_window_state(&app_handle);
+
+        // Reopen the quick windows that were visible at quit, if the user opted in
+        restore_session(&app_handle);
     }
 
+    // Keep the login item pointing at this executable with the current arguments
+    let autostart_app = app_handle.clone();
+    std::thread::spawn(move || refresh_autostart(&autostart_app));
+
     // Setup window state monitoring
     setup_window_state_monitoring(&app_handle);
 
+    // Zoom factors the user set per window
+    restore_window_zoom(&app_handle);
+
+    // Import files dropped onto the main and quicknote windows as attachments
+    setup_file_drop_import(&app_handle, "main");
+    setup_file_drop_import(&app_handle, "quicknote");
+
     // Set window close event handler to hide to tray instead of exit
     let window = main_window.clone();
     main_window.on_window_event(move |event| {
@@ -38,7 +64,7 @@ pub fn setup_app(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>>
             api.prevent_close();
             // Hide window to tray
             let _ = window.hide();
-            println!("Window hidden to tray");
+            info!("Window hidden to tray");
         }
     });
 
@@ -50,15 +76,127 @@ pub fn setup_app(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>>
         // Setup system tray
         if default_config.system_tray_enabled {
             if let Err(e) = setup_system_tray(&app_handle) {
-                eprintln!("Failed to setup system tray: {}", e);
+                error!("Failed to setup system tray: {}", e);
             } else {
-                println!("System tray setup successfully");
+                info!("System tray setup successfully");
             }
         }
         
         // Note: Shortcuts will be registered when frontend loads user configuration
         // This prevents conflicts between default and user-configured shortcuts
-        println!("Waiting for frontend to register shortcuts based on user configuration...");
+        info!("Waiting for frontend to register shortcuts based on user configuration...");
+
+        // Unlock encrypted local data before anything reads it
+        restore_encryption_key(&app_handle);
+
+        // Start clipboard history capture if the user opted in
+        start_clipboard_watcher(&app_handle);
+
+        // Reminders must fire even when the main window is hidden in the tray
+        start_reminder_scheduler(&app_handle);
+
+        // Route blinko:// links to the main window
+        setup_deep_links(&app_handle);
+
+        // Spotlight results open their note through the deep link router
+        setup_os_search(&app_handle);
+
+        // Auto-import from user registered folders
+        restart_folder_watcher(&app_handle);
+
+        // Two-way mirror of notes as markdown files
+        restart_markdown_sync_watcher(&app_handle);
+
+        // Proxy and TLS settings must be in place before any Rust side request
+        load_network_config(&app_handle);
+
+        // Reconnect the offline store to the last used server profile
+        restore_active_account(&app_handle);
+
+        // Probe the Blinko server so the UI and offline queue know when it is reachable
+        start_connectivity_monitor(&app_handle);
+
+        // Periodic two-way sync of todo notes with a CalDAV task list
+        start_caldav_sync(&app_handle);
+
+        // Interval sync that holds off on metered networks and low battery
+        start_sync_scheduler(&app_handle);
+
+        // Continue attachment transfers interrupted by the last quit
+        resume_pending_downloads(&app_handle);
+        resume_pending_uploads(&app_handle);
+
+        // Localhost endpoint for the web clipper and scripts, off unless enabled
+        start_local_api(&app_handle);
+
+        // WebSocket remote for Stream Deck and macro pads, off unless enabled
+        start_control_socket(&app_handle);
+
+        // org.blinko.App on the Linux session bus for native desktop shortcuts
+        start_dbus_service(&app_handle);
+
+        // AppleScript commands from Blinko.sdef on macOS
+        register_apple_event_handlers(&app_handle);
+
+        // Home automation bridge, off unless a broker is configured
+        restart_mqtt_client(&app_handle);
+
+        // Scheduled automatic backups, if enabled
+        start_backup_scheduler(&app_handle);
+
+        // Keep thumbnails, logs and other caches under their size caps
+        start_cache_cleanup(&app_handle);
+
+        // Watch for user inactivity to unload models and sync while away
+        start_idle_monitor(&app_handle);
+
+        // Hold back notifications while the OS is in do-not-disturb
+        start_dnd_monitor(&app_handle);
+
+        // Tell the webview when the OS switches between light and dark or changes accent
+        start_theme_monitor(&app_handle);
+
+        // Mica, acrylic or vibrancy backgrounds chosen by the user
+        restore_window_effects(&app_handle);
+
+        // Custom webview titlebar on Linux, if the user chose one
+        apply_titlebar_config(&app_handle);
+
+        // Hide the command palette when it loses focus
+        setup_palette_window(&app_handle);
+
+        // Cursor shake gesture for mouse-only capture, if the user opted in
+        start_mouse_gestures(&app_handle);
+
+        // Screen corners that run a command when the cursor rests in them
+        start_hot_corner_monitor(&app_handle);
+
+        // Global shortcuts that capture a note from a template
+        register_template_shortcuts(&app_handle);
+
+        // Expand typed abbreviations into saved snippets, if the user opted in
+        start_snippet_expander(&app_handle);
+
+        // Hotkeys bound to user automation scripts
+        start_script_hooks(&app_handle);
+
+        // Play/pause keys for read-aloud and dictation via MPRIS or SMTC
+        restart_media_keys(&app_handle);
+
+        // Writing time statistics, recorded only if the user opted in
+        start_activity_tracker(&app_handle);
+
+        // Stop clipboard capture and dictation typing while the screen is shared
+        start_privacy_guard(&app_handle);
+
+        // Turn new items from subscribed RSS/Atom feeds into notes
+        start_feed_poller(&app_handle);
+
+        // Turn new emails in watched IMAP mailboxes into notes
+        start_mail_poller(&app_handle);
+
+        // Quick capture actions passed on the command line (e.g. `blinko --new-note "text"`)
+        run_cli_commands(&app_handle, &cli_commands);
 
         // Initialize voice recognition if enabled (Windows only, non-blocking)
         #[cfg(target_os = "windows")]
@@ -70,234 +208,85 @@ pub fn setup_app(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>>
 
                 // Print build configuration info
                 #[cfg(feature = "whisper-cuda")]
-                println!("🚀 Voice recognition built with CUDA acceleration support");
+                info!("🚀 Voice recognition built with CUDA acceleration support");
                 #[cfg(all(feature = "whisper-cpu", not(feature = "whisper-cuda")))]
-                println!("🖥️ Voice recognition built with CPU-only support");
+                info!("🖥️ Voice recognition built with CPU-only support");
 
                 if voice_config.enabled && std::path::Path::new(&voice_config.model_path).exists() {
-                    println!("🎤 Voice recognition enabled, initializing in background...");
+                    info!("🎤 Voice recognition enabled, initializing in background...");
 
-                    // Clone voice config for the background thread
+                    // Load the model in the background; with preloading on, also warm it up once it's there
                     let voice_config_clone = voice_config.clone();
-
-                    // Use std::thread::spawn instead of tokio::spawn to avoid runtime issues
+                    let voice_app_handle = app_handle.clone();
                     std::thread::spawn(move || {
-                        match VoiceProcessor::new(voice_config_clone.clone()) {
+                        let preload = voice_config_clone.preload_on_startup;
+                        match start_voice_processor(&voice_app_handle, voice_config_clone, true) {
                             Ok(processor) => {
                                 #[cfg(feature = "whisper-cuda")]
-                                println!("✅ Voice recognition initialized successfully with CUDA support");
+                                info!("✅ Voice recognition initialized successfully with CUDA support");
                                 #[cfg(all(feature = "whisper-cpu", not(feature = "whisper-cuda")))]
-                                println!("✅ Voice recognition initialized successfully with CPU support");
-
-                                // Update global state
-                                {
-                                    let mut state = VOICE_STATE.lock();
-                                    state.processor = Some(std::sync::Arc::new(processor));
-                                    state.is_initialized = true;
-                                    *state.config.lock() = voice_config_clone.clone();
-                                }
-
-                                // Start the voice recognition service
-                                if let Some(ref processor) = VOICE_STATE.lock().processor {
-                                    if let Err(e) = processor.start() {
-                                        eprintln!("❌ Failed to start voice recognition: {}", e);
-                                        println!("💡 Voice recognition failed to start, but application will continue normally");
-                                    } else {
-                                        println!("🚀 Voice recognition service started successfully");
-                                    }
+                                info!("✅ Voice recognition initialized successfully with CPU support");
+                                if preload {
+                                    warm_up_voice_model(&voice_app_handle, &processor);
                                 }
                             }
                             Err(e) => {
-                                eprintln!("❌ Failed to initialize voice recognition: {}", e);
+                                error!("❌ {}", e);
                                 #[cfg(feature = "whisper-cuda")]
-                                println!("💡 If you see CUDA errors, try the CPU-only version or install CUDA toolkit");
-                                println!("💡 Please check model path and configuration in voice settings");
-                                println!("💡 Application will continue to run normally without voice recognition");
+                                info!("💡 If you see CUDA errors, try the CPU-only version or install CUDA toolkit");
+                                info!("💡 Please check model path and configuration in voice settings");
+                                info!("💡 Application will continue to run normally without voice recognition");
                             }
                         }
                     });
                 } else if voice_config.enabled && !std::path::Path::new(&voice_config.model_path).exists() {
-                    println!("⚠️ Voice recognition enabled but model file not found: {}", voice_config.model_path);
-                    println!("💡 Please download a model file and update the path in voice settings");
-                    println!("💡 Application will continue to run normally without voice recognition");
+                    warn!("⚠️ Voice recognition enabled but model file not found: {}", voice_config.model_path);
+                    info!("💡 Please download a model file and update the path in voice settings");
+                    info!("💡 Application will continue to run normally without voice recognition");
                 } else {
-                    println!("🔇 Voice recognition disabled in configuration");
+                    info!("🔇 Voice recognition disabled in configuration");
                 }
             }
 
             // If whisper-rs is not available in this build
             #[cfg(not(any(feature = "whisper-cuda", feature = "whisper-cpu")))]
             {
-                println!("🔇 Voice recognition not available in this build (no whisper features enabled)");
+                info!("🔇 Voice recognition not available in this build (no whisper features enabled)");
             }
         }
         #[cfg(not(target_os = "windows"))]
         {
-            println!("🔇 Voice recognition not available on this platform (only supported on Windows)");
+            info!("🔇 Voice recognition not available on this platform (only supported on Windows)");
         }
     }
 
     Ok(())
 }
 
-#[cfg(not(any(target_os = "android", target_os = "ios")))]
-fn shortcuts_match(actual: &str, registered: &str) -> bool {
-    // Normalize both shortcuts for comparison
-    let normalize = |s: &str| -> String {
-        let mut normalized = s.to_lowercase();
-        
-        // Handle CommandOrControl -> control mapping
-        normalized = normalized.replace("commandorcontrol", "control");
-        
-        // Remove "key" prefix from key names (control+KeyG -> control+g)
-        normalized = normalized.replace("key", "");
-        
-        // Ensure consistent casing for modifiers
-        normalized = normalized.replace("shift+", "shift+");
-        normalized = normalized.replace("control+", "control+");
-        normalized = normalized.replace("alt+", "alt+");
-        normalized = normalized.replace("cmd+", "control+");
-        normalized = normalized.replace("command+", "control+");
-        
-        // Sort modifiers to ensure consistent order
-        let parts: Vec<&str> = normalized.split('+').collect();
-        if parts.len() > 1 {
-            let mut modifiers: Vec<&str> = parts[..parts.len()-1].to_vec();
-            let key = parts[parts.len()-1];
-            modifiers.sort();
-            format!("{}+{}", modifiers.join("+"), key)
-        } else {
-            normalized
-        }
-    };
-    
-    let normalized_actual = normalize(actual);
-    let normalized_registered = normalize(registered);
-    
-    println!("Shortcut match comparison: '{}' (from '{}') == '{}' (from '{}') -> {}", 
-             normalized_actual, actual, normalized_registered, registered,
-             normalized_actual == normalized_registered);
-    
-    normalized_actual == normalized_registered
-}
-
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 pub fn create_global_shortcut_handler() -> impl Fn(&AppHandle<tauri::Wry>, &tauri_plugin_global_shortcut::Shortcut, ShortcutEvent) + Send + Sync + 'static {
     move |app, shortcut, event| {
         if event.state == ShortcutState::Pressed {
-            let shortcut_str = shortcut.to_string();
-
-            println!("🔥 Global shortcut triggered: {}", shortcut_str);
-
-            // Check for text selection trigger combinations
-            // Handle different representations of backtick/grave accent
-            if shortcut_str.contains("Control") && (shortcut_str.contains("`") || shortcut_str.contains("Backquote") || shortcut_str.contains("Grave")) {
-                println!("🎹 Text selection trigger pressed: {} (ctrl + `)", shortcut_str);
-                let is_enabled = crate::desktop::is_text_selection_enabled_for("ctrl");
-                println!("🔍 Text selection enabled for ctrl: {}", is_enabled);
-                if is_enabled {
-                    println!("🚀 Triggering text selection via Ctrl + `");
-                    crate::desktop::handle_text_selection(app);
-                    return;
-                } else {
-                    println!("⚠️ Text selection not enabled for ctrl, ignoring shortcut");
-                }
-            } else if shortcut_str.contains("Shift") && (shortcut_str.contains("`") || shortcut_str.contains("Backquote") || shortcut_str.contains("Grave")) {
-                println!("🎹 Text selection trigger pressed: {} (shift + `)", shortcut_str);
-                let is_enabled = crate::desktop::is_text_selection_enabled_for("shift");
-                println!("🔍 Text selection enabled for shift: {}", is_enabled);
-                if is_enabled {
-                    println!("🚀 Triggering text selection via Shift + `");
-                    crate::desktop::handle_text_selection(app);
-                    return;
-                } else {
-                    println!("⚠️ Text selection not enabled for shift, ignoring shortcut");
-                }
-            } else if shortcut_str.contains("Alt") && (shortcut_str.contains("`") || shortcut_str.contains("Backquote") || shortcut_str.contains("Grave")) {
-                println!("🎹 Text selection trigger pressed: {} (alt + `)", shortcut_str);
-                let is_enabled = crate::desktop::is_text_selection_enabled_for("alt");
-                println!("🔍 Text selection enabled for alt: {}", is_enabled);
-                if is_enabled {
-                    println!("🚀 Triggering text selection via Alt + `");
-                    crate::desktop::handle_text_selection(app);
-                    return;
-                } else {
-                    println!("⚠️ Text selection not enabled for alt, ignoring shortcut");
-                }
+            if crate::desktop::is_capture_paused() {
+                debug!("⏸️ Capture paused, ignoring shortcut {}", shortcut);
+                return;
             }
-
-            // Get the command mapped to this shortcut from our registration map
-            let shortcuts_map = crate::desktop::get_registered_shortcuts();
-            println!("📋 Available shortcuts: {:?}", shortcuts_map);
-
-            // Try direct match first (normalize to lowercase)
-            if let Some(command) = shortcuts_map.get(&shortcut_str.to_lowercase()) {
-                println!("🎯 Direct match found: {} -> {}", shortcut_str, command);
-                match command.as_str() {
-                    "quicknote" => {
-                        let _ = toggle_quicknote_window(app.clone());
-                        println!("Triggered quicknote window via shortcut: {}", shortcut_str);
-                        return;
-                    },
-                    "quickai" => {
-                        let _ = toggle_quickai_window(app.clone());
-                        println!("Triggered quickai window via shortcut: {}", shortcut_str);
-                        return;
-                    },
-                    "quicktool" => {
-                        let _ = toggle_quicktool_window(app.clone());
-                        println!("Triggered quicktool window via shortcut: {}", shortcut_str);
-                        return;
-                    },
-                    "text-selection" => {
-                        println!("🚀 Triggering text selection via direct shortcut: {}", shortcut_str);
-                        crate::desktop::handle_text_selection(app);
-                        return;
-                    },
-                    _ => {
-                        println!("Unknown command for shortcut {}: {}", shortcut_str, command);
+            // Resolved from bindings parsed at registration, no string handling on the hot path
+            match app.state::<ShortcutRegistry>().resolve(shortcut) {
+                Some(ShortcutBinding::Route(trigger, route)) => {
+                    if let Err(e) = crate::desktop::dispatch_shortcut_route(app, &trigger, &route) {
+                        error!("❌ Failed to route shortcut {}: {}", shortcut, e);
                     }
                 }
-            } else {
-                println!("❌ No direct match for shortcut: {}", shortcut_str);
-            }
-
-            // If no direct match, try to find by matching against all registered shortcuts
-            for (registered_shortcut, command) in shortcuts_map.iter() {
-                println!("🔍 Checking registered shortcut: '{}' -> '{}'", registered_shortcut, command);
-                if shortcuts_match(&shortcut_str, registered_shortcut) {
-                    println!("✅ Found matching shortcut: {} -> {}", shortcut_str, registered_shortcut);
-                    match command.as_str() {
-                        "quicknote" => {
-                            let _ = toggle_quicknote_window(app.clone());
-                            println!("Triggered quicknote window via matched shortcut: {} -> {}", shortcut_str, registered_shortcut);
-                            return;
-                        },
-                        "quickai" => {
-                            let _ = toggle_quickai_window(app.clone());
-                            println!("Triggered quickai window via matched shortcut: {} -> {}", shortcut_str, registered_shortcut);
-                            return;
-                        },
-                        "quicktool" => {
-                            let _ = toggle_quicktool_window(app.clone());
-                            println!("Triggered quicktool window via matched shortcut: {} -> {}", shortcut_str, registered_shortcut);
-                            return;
-                        },
-                        "text-selection" => {
-                            println!("🚀 Triggering text selection via matched shortcut: {} -> {}", shortcut_str, registered_shortcut);
-                            crate::desktop::handle_text_selection(app);
-                            return;
-                        },
-                        _ => {
-                            println!("⚠️ Unknown command '{}' for shortcut {}", command, registered_shortcut);
-                        }
+                Some(ShortcutBinding::Command(command)) => {
+                    if crate::desktop::run_shortcut_command(app, &command) {
+                        debug!("Triggered {} via shortcut: {}", command, shortcut);
+                    } else {
+                        warn!("⚠️ Unknown command '{}' for shortcut {}", command, shortcut);
                     }
-                } else {
-                    println!("❌ No match for shortcut: {} vs {}", shortcut_str, registered_shortcut);
                 }
+                None => debug!("No command mapped for shortcut: {}", shortcut),
             }
-
-            println!("No command mapped for shortcut: {}", shortcut_str);
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,269 @@
+use tauri::AppHandle;
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+use tauri::Manager;
+use std::path::PathBuf;
+
+use crate::desktop::{import_files_into_window, open_quicknote_with_text};
+
+#[cfg(target_os = "macos")]
+const SERVICE_INFO_PLIST: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+	<key>NSServices</key>
+	<array>
+		<dict>
+			<key>NSMenuItem</key>
+			<dict>
+				<key>default</key>
+				<string>{MENU_TITLE}</string>
+			</dict>
+			<key>NSMessage</key>
+			<string>runWorkflowAsService</string>
+			<key>{SEND_KEY}</key>
+			<array>
+				<string>{SEND_TYPE}</string>
+			</array>
+		</dict>
+	</array>
+</dict>
+</plist>
+"#;
+
+/// Quick Action with a single "Run Shell Script" step that gets the input as arguments
+#[cfg(target_os = "macos")]
+const SERVICE_WORKFLOW: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+	<key>AMApplicationBuild</key>
+	<string>523</string>
+	<key>AMApplicationVersion</key>
+	<string>2.10</string>
+	<key>AMDocumentVersion</key>
+	<string>2</string>
+	<key>actions</key>
+	<array>
+		<dict>
+			<key>action</key>
+			<dict>
+				<key>AMAccepts</key>
+				<dict>
+					<key>Container</key>
+					<string>List</string>
+					<key>Optional</key>
+					<true/>
+					<key>Types</key>
+					<array>
+						<string>com.apple.cocoa.string</string>
+					</array>
+				</dict>
+				<key>AMActionVersion</key>
+				<string>2.0.3</string>
+				<key>AMApplication</key>
+				<array>
+					<string>Automator</string>
+				</array>
+				<key>AMProvides</key>
+				<dict>
+					<key>Container</key>
+					<string>List</string>
+					<key>Types</key>
+					<array>
+						<string>com.apple.cocoa.string</string>
+					</array>
+				</dict>
+				<key>ActionBundlePath</key>
+				<string>/System/Library/Automator/Run Shell Script.action</string>
+				<key>ActionName</key>
+				<string>Run Shell Script</string>
+				<key>ActionParameters</key>
+				<dict>
+					<key>COMMAND_STRING</key>
+					<string>{COMMAND}</string>
+					<key>CheckedForUserDefaultShell</key>
+					<true/>
+					<key>inputMethod</key>
+					<integer>1</integer>
+					<key>shell</key>
+					<string>/bin/bash</string>
+					<key>source</key>
+					<string></string>
+				</dict>
+				<key>BundleIdentifier</key>
+				<string>com.apple.RunShellScript</string>
+				<key>CFBundleVersion</key>
+				<string>2.0.3</string>
+				<key>Class Name</key>
+				<string>RunShellScriptAction</string>
+			</dict>
+		</dict>
+	</array>
+	<key>workflowMetaData</key>
+	<dict>
+		<key>serviceInputTypeIdentifier</key>
+		<string>{INPUT_TYPE}</string>
+		<key>serviceOutputTypeIdentifier</key>
+		<string>com.apple.Automator.nothing</string>
+		<key>serviceProcessesInput</key>
+		<integer>0</integer>
+		<key>workflowTypeIdentifier</key>
+		<string>com.apple.Automator.servicesMenu</string>
+	</dict>
+</dict>
+</plist>
+"#;
+
+/// Services menu entries: (menu title, send key, send type, Automator input type, arguments before the input)
+#[cfg(target_os = "macos")]
+const MACOS_SERVICES: [(&str, &str, &str, &str, &str); 2] = [
+    ("Send to Blinko", "NSSendTypes", "public.utf8-plain-text", "com.apple.Automator.text", "--share-text \"$*\""),
+    ("Send Files to Blinko", "NSSendFileTypes", "public.item", "com.apple.Automator.fileSystemObject", "--share \"$@\""),
+];
+
+/// Open the quick note window with content shared from another app, files become its attachments
+pub fn share_to_quicknote(app: &AppHandle, text: Option<&str>, files: &[PathBuf]) -> Result<(), String> {
+    open_quicknote_with_text(app, text.unwrap_or_default())?;
+    if !files.is_empty() {
+        info!("📤 {} shared file(s) sent to quick note", files.len());
+        import_files_into_window(app.clone(), "quicknote".to_string(), files.to_vec());
+    }
+    Ok(())
+}
+
+fn current_exe() -> Result<PathBuf, String> {
+    std::env::current_exe().map_err(|e| format!("Failed to locate Blinko executable: {}", e))
+}
+
+#[cfg(target_os = "windows")]
+fn send_to_shortcut(app: &AppHandle) -> Result<PathBuf, String> {
+    let roaming = app.path().data_dir().map_err(|e| format!("Failed to get AppData folder: {}", e))?;
+    Ok(roaming.join("Microsoft").join("Windows").join("SendTo").join("Blinko.lnk"))
+}
+
+#[cfg(target_os = "macos")]
+fn services_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let home = app.path().home_dir().map_err(|e| format!("Failed to get home folder: {}", e))?;
+    Ok(home.join("Library").join("Services"))
+}
+
+#[cfg(target_os = "macos")]
+fn write_service_workflow(dir: &std::path::Path, service: (&str, &str, &str, &str, &str), exe: &std::path::Path) -> Result<(), String> {
+    use crate::desktop::escape_html;
+
+    let (title, send_key, send_type, input_type, arguments) = service;
+    let contents = dir.join(format!("{}.workflow", title)).join("Contents");
+    std::fs::create_dir_all(&contents).map_err(|e| format!("Failed to create {}: {}", contents.display(), e))?;
+
+    // Launching the binary again hands the input to the running Blinko through the single instance channel
+    let exe = format!("'{}'", exe.to_string_lossy().replace('\'', "'\\''"));
+    let command = format!("{} {} >/dev/null 2>&1 &", exe, arguments);
+    let info_plist = SERVICE_INFO_PLIST
+        .replace("{MENU_TITLE}", title)
+        .replace("{SEND_KEY}", send_key)
+        .replace("{SEND_TYPE}", send_type);
+    let workflow = SERVICE_WORKFLOW
+        .replace("{COMMAND}", &escape_html(&command))
+        .replace("{INPUT_TYPE}", input_type);
+
+    std::fs::write(contents.join("Info.plist"), info_plist).map_err(|e| format!("Failed to write service: {}", e))?;
+    std::fs::write(contents.join("document.wflow"), workflow).map_err(|e| format!("Failed to write service: {}", e))
+}
+
+#[cfg(target_os = "macos")]
+fn refresh_services_menu() {
+    if let Err(e) = crate::desktop::background_command("/System/Library/CoreServices/pbs").arg("-update").status() {
+        warn!("⚠️ Failed to refresh the Services menu: {}", e);
+    }
+}
+
+/// Add "Send to Blinko" to the OS share menus: Explorer's "Send to" on Windows, the Services menu on macOS
+#[tauri::command]
+pub fn register_share_target(app: AppHandle) -> Result<(), String> {
+    let exe = current_exe()?;
+
+    #[cfg(target_os = "windows")]
+    {
+        let shortcut = send_to_shortcut(&app)?;
+        // Paths go through the environment so they need no PowerShell quoting
+        let status = crate::desktop::background_command("powershell")
+            .args([
+                "-NoProfile",
+                "-Command",
+                "$s = (New-Object -ComObject WScript.Shell).CreateShortcut($env:BLINKO_SHORTCUT); \
+                 $s.TargetPath = $env:BLINKO_EXE; $s.Arguments = '--share'; $s.IconLocation = $env:BLINKO_EXE; $s.Save()",
+            ])
+            .env("BLINKO_SHORTCUT", &shortcut)
+            .env("BLINKO_EXE", &exe)
+            .status()
+            .map_err(|e| format!("Failed to create Send to shortcut: {}", e))?;
+        if !status.success() {
+            return Err("Failed to create Send to shortcut".to_string());
+        }
+        info!("📤 Send to shortcut created at {}", shortcut.display());
+        Ok(())
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let dir = services_dir(&app)?;
+        for service in MACOS_SERVICES {
+            write_service_workflow(&dir, service, &exe)?;
+        }
+        refresh_services_menu();
+        info!("📤 Services menu entries installed in {}", dir.display());
+        Ok(())
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    {
+        let _ = (app, exe);
+        Err("Share menu integration is not supported on this platform".to_string())
+    }
+}
+
+#[tauri::command]
+pub fn unregister_share_target(app: AppHandle) -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    {
+        let shortcut = send_to_shortcut(&app)?;
+        if shortcut.exists() {
+            std::fs::remove_file(&shortcut).map_err(|e| format!("Failed to remove Send to shortcut: {}", e))?;
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let dir = services_dir(&app)?;
+        for (title, ..) in MACOS_SERVICES {
+            let workflow = dir.join(format!("{}.workflow", title));
+            if workflow.exists() {
+                std::fs::remove_dir_all(&workflow).map_err(|e| format!("Failed to remove service: {}", e))?;
+            }
+        }
+        refresh_services_menu();
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let _ = app;
+
+    info!("📤 Share menu integration removed");
+    Ok(())
+}
+
+#[tauri::command]
+pub fn is_share_target_registered(app: AppHandle) -> bool {
+    #[cfg(target_os = "windows")]
+    return send_to_shortcut(&app).is_ok_and(|shortcut| shortcut.exists());
+
+    #[cfg(target_os = "macos")]
+    return services_dir(&app).is_ok_and(|dir| {
+        MACOS_SERVICES.iter().all(|(title, ..)| dir.join(format!("{}.workflow", title)).exists())
+    });
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    {
+        let _ = app;
+        false
+    }
+}
@@ -0,0 +1,55 @@
+use tauri::{AppHandle, Emitter};
+use serde::{Deserialize, Serialize};
+
+use crate::desktop::{ensure_main_window, parse_cli_args, run_cli_commands, DEEP_LINK_SCHEME};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SecondInstanceEvent {
+    pub args: Vec<String>,
+    pub cwd: String,
+}
+
+fn focus_main_window(app: &AppHandle) {
+    match ensure_main_window(app) {
+        Ok(window) => {
+            if let Err(e) = window.show() {
+                error!("Failed to show window: {}", e);
+            }
+            if let Err(e) = window.unminimize() {
+                error!("Failed to unminimize window: {}", e);
+            }
+            if let Err(e) = window.set_focus() {
+                error!("Failed to focus window: {}", e);
+            }
+            info!("Focused existing Blinko window");
+        }
+        Err(e) => error!("❌ {}", e),
+    }
+}
+
+/// Called in the running instance when Blinko is launched again
+pub fn handle_second_instance(app: &AppHandle, args: Vec<String>, cwd: String) {
+    info!("Second instance detected with args: {:?} and cwd: {:?}", args, cwd);
+
+    // Skip the executable path
+    let forwarded: Vec<String> = args.into_iter().skip(1).collect();
+
+    // blinko:// links are routed by the deep link plugin, which also focuses the window
+    let has_deep_link = forwarded.iter()
+        .any(|arg| arg.starts_with(&format!("{}://", DEEP_LINK_SCHEME)));
+
+    // Quick capture commands open their own windows instead of the main one
+    let commands = parse_cli_args(&forwarded);
+    if !commands.is_empty() {
+        run_cli_commands(app, &commands);
+    } else if !has_deep_link {
+        focus_main_window(app);
+    }
+
+    if !forwarded.is_empty() {
+        let event = SecondInstanceEvent { args: forwarded, cwd };
+        if let Err(e) = app.emit("second-instance-args", &event) {
+            error!("Failed to forward second instance args: {}", e);
+        }
+    }
+}
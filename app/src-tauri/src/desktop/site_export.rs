@@ -0,0 +1,309 @@
+use tauri::AppHandle;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use pulldown_cmark::{html, CowStr, Event, Options, Parser, Tag};
+
+use crate::desktop::{get_cached_notes, get_configured_server_url, send_with_retry, RetryPolicy};
+
+const ATTACHMENT_TIMEOUT: Duration = Duration::from_secs(60);
+// Attachment links the server hands out, rewritten to the downloaded copies
+const SERVER_FILE_PREFIX: &str = "/api/file/";
+
+const SITE_STYLESHEET: &str = r#"body { max-width: 46rem; margin: 2rem auto; padding: 0 1rem; font: 16px/1.6 -apple-system, "Segoe UI", sans-serif; color: #222; }
+a { color: #2563eb; }
+header a { text-decoration: none; }
+.meta { color: #777; font-size: 0.85rem; }
+.tag { display: inline-block; margin-right: 0.4rem; padding: 0 0.4rem; border-radius: 4px; background: #eef2ff; }
+ul.notes { list-style: none; padding: 0; }
+ul.notes li { margin: 0.8rem 0; }
+img { max-width: 100%; }
+pre { overflow-x: auto; padding: 0.8rem; background: #f6f8fa; }
+@media (prefers-color-scheme: dark) { body { background: #111; color: #ddd; } pre { background: #1d1d1d; } .tag { background: #1e293b; } }
+"#;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SiteExportOptions {
+    /// Site title, defaults to "Blinko Notes"
+    pub title: Option<String>,
+    #[serde(rename = "includeArchived", default)]
+    pub include_archived: bool,
+    /// Only export notes with one of these tags, empty exports everything
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Download attachments from the server into `assets/`
+    #[serde(rename = "includeAttachments", default)]
+    pub include_attachments: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SiteExportReport {
+    pub path: String,
+    pub notes: usize,
+    pub attachments: usize,
+    /// Attachments that could not be downloaded, their links point at the server
+    #[serde(rename = "failedAttachments")]
+    pub failed_attachments: usize,
+}
+
+pub fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// First non-empty line without heading markers
+pub fn note_title(note: &Value) -> String {
+    let content = note.get("content").and_then(|v| v.as_str()).unwrap_or_default();
+    let title: String = content.lines()
+        .map(|line| line.trim_start_matches('#').trim())
+        .find(|line| !line.is_empty())
+        .unwrap_or("Untitled")
+        .chars()
+        .take(80)
+        .collect();
+    title
+}
+
+/// Tag names of a cached note, the server nests them as `tags[].tag.name`
+pub fn note_tags(note: &Value) -> Vec<String> {
+    note.get("tags").and_then(|v| v.as_array())
+        .map(|tags| tags.iter()
+            .filter_map(|tag| tag.as_str().or_else(|| tag.get("tag")?.get("name")?.as_str()))
+            .map(str::to_string)
+            .collect())
+        .unwrap_or_default()
+}
+
+pub fn note_id(note: &Value) -> Option<i64> {
+    note.get("id").and_then(|v| v.as_i64())
+}
+
+/// Day part of the note's `updatedAt`, falling back to `createdAt`
+pub fn note_date(note: &Value) -> String {
+    note.get("updatedAt").or_else(|| note.get("createdAt"))
+        .and_then(|v| v.as_str())
+        .map(|date| date.chars().take(10).collect())
+        .unwrap_or_default()
+}
+
+fn render_markdown(markdown: &str, rewrite_url: impl Fn(&str) -> Option<String>, raw_html: bool) -> String {
+    let options = Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS | Options::ENABLE_FOOTNOTES;
+    let events = Parser::new_ext(markdown, options).map(|event| match event {
+        Event::Start(Tag::Image { link_type, dest_url, title, id }) => Event::Start(Tag::Image {
+            dest_url: rewrite_url(&dest_url).map(CowStr::from).unwrap_or(dest_url),
+            link_type,
+            title,
+            id,
+        }),
+        Event::Start(Tag::Link { link_type, dest_url, title, id }) => Event::Start(Tag::Link {
+            dest_url: rewrite_url(&dest_url).map(CowStr::from).unwrap_or(dest_url),
+            link_type,
+            title,
+            id,
+        }),
+        Event::Html(html) | Event::InlineHtml(html) if !raw_html => Event::Text(html),
+        event => event,
+    });
+
+    let mut out = String::new();
+    html::push_html(&mut out, events);
+    out
+}
+
+/// Render note markdown to HTML, `rewrite_url` may replace link and image targets
+pub fn render_markdown_html(markdown: &str, rewrite_url: impl Fn(&str) -> Option<String>) -> String {
+    render_markdown(markdown, rewrite_url, true)
+}
+
+/// Like `render_markdown_html` but with embedded HTML escaped, so the output stays valid XHTML
+pub fn render_markdown_xhtml(markdown: &str, rewrite_url: impl Fn(&str) -> Option<String>) -> String {
+    render_markdown(markdown, rewrite_url, false)
+}
+
+fn tag_file_name(tag: &str) -> String {
+    let slug: String = tag.chars()
+        .map(|c| if c.is_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+        .collect();
+    match slug.trim_matches('-') {
+        "" => "tag.html".to_string(),
+        slug => format!("{}.html", slug),
+    }
+}
+
+fn html_page(title: &str, root: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{title}</title>\n<link rel=\"stylesheet\" href=\"{root}assets/style.css\">\n</head>\n<body>\n{body}\n</body>\n</html>\n",
+        title = escape_html(title),
+        root = root,
+        body = body,
+    )
+}
+
+fn note_list(notes: &[&Value], root: &str) -> String {
+    let mut out = String::from("<ul class=\"notes\">\n");
+    for note in notes {
+        let Some(id) = note_id(note) else { continue };
+        out.push_str(&format!(
+            "<li><a href=\"{}notes/{}.html\">{}</a> <span class=\"meta\">{}</span></li>\n",
+            root, id, escape_html(&note_title(note)), escape_html(&note_date(note)),
+        ));
+    }
+    out.push_str("</ul>");
+    out
+}
+
+fn tag_links(tags: &[String], root: &str) -> String {
+    tags.iter()
+        .map(|tag| format!("<a class=\"tag\" href=\"{}tags/{}\">#{}</a>", root, tag_file_name(tag), escape_html(tag)))
+        .collect()
+}
+
+/// Server paths of the attachments a note links to or lists
+fn attachment_paths(note: &Value) -> Vec<String> {
+    let mut paths: Vec<String> = note.get("attachments").and_then(|v| v.as_array())
+        .map(|list| list.iter()
+            .filter_map(|a| a.get("path").and_then(|p| p.as_str()).map(str::to_string))
+            .collect())
+        .unwrap_or_default();
+
+    let content = note.get("content").and_then(|v| v.as_str()).unwrap_or_default();
+    for event in Parser::new(content) {
+        if let Event::Start(Tag::Image { dest_url, .. } | Tag::Link { dest_url, .. }) = event {
+            if dest_url.starts_with(SERVER_FILE_PREFIX) && !paths.iter().any(|p| **p == *dest_url) {
+                paths.push(dest_url.to_string());
+            }
+        }
+    }
+    paths
+}
+
+/// Download attachments into `assets/`, returning server path → local file name
+fn download_attachments(app: &AppHandle, assets: &Path, notes: &[&Value]) -> (HashMap<String, String>, usize) {
+    let mut files = HashMap::new();
+    let mut failed = 0;
+    let Some(server_url) = get_configured_server_url() else {
+        return (files, notes.iter().map(|note| attachment_paths(note).len()).sum());
+    };
+
+    for path in notes.iter().flat_map(|note| attachment_paths(note)) {
+        if files.contains_key(&path) {
+            continue;
+        }
+        let name = path.rsplit('/').next().unwrap_or_default().split('?').next().unwrap_or_default().to_string();
+        let name = format!("{}-{}", files.len(), name.replace(['\\', ':'], "_"));
+        let url = format!("{}{}", server_url.trim_end_matches('/'), path);
+
+        let result = send_with_retry(app, ATTACHMENT_TIMEOUT, RetryPolicy::default(), |client| client.get(&url))
+            .map_err(|e| e.to_string())
+            .and_then(|response| response.bytes().map_err(|e| e.to_string()))
+            .and_then(|bytes| fs::write(assets.join(&name), bytes).map_err(|e| e.to_string()));
+        match result {
+            Ok(()) => {
+                files.insert(path, name);
+            }
+            Err(e) => {
+                warn!("⚠️ Skipping attachment {}: {}", path, e);
+                failed += 1;
+            }
+        }
+    }
+    (files, failed)
+}
+
+fn write_file(path: &Path, content: &str) -> Result<(), String> {
+    fs::write(path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Render cached notes as `index.html`, `notes/<id>.html`, `tags/<tag>.html` and `assets/`
+pub fn write_site(app: &AppHandle, target: &Path, options: &SiteExportOptions) -> Result<SiteExportReport, String> {
+    let cached = get_cached_notes(app.clone(), Some(options.include_archived))?;
+    let notes: Vec<&Value> = cached.iter()
+        .filter(|note| note_id(note).is_some())
+        .filter(|note| options.tags.is_empty() || note_tags(note).iter().any(|tag| options.tags.contains(tag)))
+        .collect();
+    let title = options.title.clone().filter(|t| !t.trim().is_empty()).unwrap_or("Blinko Notes".to_string());
+
+    for dir in ["notes", "tags", "assets"] {
+        fs::create_dir_all(target.join(dir))
+            .map_err(|e| format!("Failed to create {}: {}", target.join(dir).display(), e))?;
+    }
+    write_file(&target.join("assets").join("style.css"), SITE_STYLESHEET)?;
+
+    let (attachments, failed_attachments) = if options.include_attachments {
+        download_attachments(app, &target.join("assets"), &notes)
+    } else {
+        (HashMap::new(), 0)
+    };
+
+    let mut by_tag: BTreeMap<String, Vec<&Value>> = BTreeMap::new();
+    for note in &notes {
+        let id = note_id(note).unwrap_or_default();
+        let tags = note_tags(note);
+        for tag in &tags {
+            by_tag.entry(tag.clone()).or_default().push(*note);
+        }
+
+        let content = note.get("content").and_then(|v| v.as_str()).unwrap_or_default();
+        let local_url = |url: &str| attachments.get(url).map(|name| format!("../assets/{}", name));
+        let mut rendered = render_markdown_html(content, local_url);
+        let files: Vec<String> = attachment_paths(note).into_iter()
+            .filter(|path| !content.contains(path.as_str()))
+            .map(|path| {
+                let name = path.rsplit('/').next().unwrap_or_default().to_string();
+                format!("<li><a href=\"{}\">{}</a></li>", escape_html(&local_url(&path).unwrap_or(path)), escape_html(&name))
+            })
+            .collect();
+        if !files.is_empty() {
+            rendered.push_str(&format!("<ul class=\"attachments\">\n{}\n</ul>\n", files.join("\n")));
+        }
+        let body = format!(
+            "<header><a href=\"../index.html\">← {}</a></header>\n<article>\n{}</article>\n<p class=\"meta\">{} {}</p>",
+            escape_html(&title), rendered, escape_html(&note_date(note)), tag_links(&tags, "../"),
+        );
+        write_file(&target.join("notes").join(format!("{}.html", id)), &html_page(&note_title(note), "../", &body))?;
+    }
+
+    for (tag, tagged) in &by_tag {
+        let body = format!(
+            "<header><a href=\"../index.html\">← {}</a></header>\n<h1>#{}</h1>\n{}",
+            escape_html(&title), escape_html(tag), note_list(tagged, "../"),
+        );
+        write_file(&target.join("tags").join(tag_file_name(tag)), &html_page(&format!("#{} - {}", tag, title), "../", &body))?;
+    }
+
+    let tags: Vec<String> = by_tag.keys().cloned().collect();
+    let body = format!("<h1>{}</h1>\n<p>{}</p>\n{}", escape_html(&title), tag_links(&tags, ""), note_list(&notes, ""));
+    write_file(&target.join("index.html"), &html_page(&title, "", &body))?;
+
+    info!("🌐 Exported {} notes ({} attachments) to {}", notes.len(), attachments.len(), target.display());
+    Ok(SiteExportReport {
+        path: target.to_string_lossy().to_string(),
+        notes: notes.len(),
+        attachments: attachments.len(),
+        failed_attachments,
+    })
+}
+
+/// Export cached notes as a static HTML site into `path`
+#[tauri::command]
+pub async fn export_site(app: AppHandle, path: String, options: Option<SiteExportOptions>) -> Result<SiteExportReport, String> {
+    let options = options.unwrap_or_default();
+    tauri::async_runtime::spawn_blocking(move || write_site(&app, &PathBuf::from(path), &options))
+        .await
+        .map_err(|e| format!("Site export task failed: {}", e))?
+}
@@ -0,0 +1,149 @@
+use tauri::AppHandle;
+use serde::{Deserialize, Serialize};
+use std::sync::{LazyLock, Mutex};
+
+use rdev::{EventType, Key};
+
+use crate::desktop::{
+    add_input_listener, ensure_input_hook, foreground_app, inject_text, is_injecting_text, load_json_or_default,
+    remove_input_listener, render_template_content, save_json, InjectionStrategy,
+};
+
+const SNIPPETS_FILE: &str = "snippets.json";
+/// Only the tail of what was typed is kept, longer abbreviations can't match
+const MAX_TYPED_CHARS: usize = 64;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Snippet {
+    /// Typed text that triggers the expansion, e.g. ";;addr"
+    pub abbreviation: String,
+    /// Replacement text, template placeholders like `{{date}}` are expanded
+    pub expansion: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SnippetConfig {
+    pub enabled: bool,
+    pub snippets: Vec<Snippet>,
+    pub strategy: InjectionStrategy,
+    /// Apps (display or executable name) where abbreviations are left alone
+    #[serde(rename = "disabledApps", default)]
+    pub disabled_apps: Vec<String>,
+}
+
+#[derive(Default)]
+struct TypedBuffer {
+    text: String,
+    /// Ctrl, Alt or Meta held: keys are shortcuts, not text
+    modifiers_held: usize,
+}
+
+static SNIPPET_CONFIG: LazyLock<Mutex<SnippetConfig>> = LazyLock::new(|| Mutex::new(SnippetConfig::default()));
+static TYPED_BUFFER: LazyLock<Mutex<TypedBuffer>> = LazyLock::new(|| Mutex::new(TypedBuffer::default()));
+
+fn is_modifier(key: Key) -> bool {
+    matches!(key, Key::ControlLeft | Key::ControlRight | Key::Alt | Key::AltGr | Key::MetaLeft | Key::MetaRight)
+}
+
+/// Keys that move the cursor, after which the typed text no longer ends at the cursor
+fn breaks_typing(key: Key) -> bool {
+    matches!(
+        key,
+        Key::Return | Key::KpReturn | Key::Tab | Key::Escape | Key::UpArrow | Key::DownArrow | Key::LeftArrow
+            | Key::RightArrow | Key::Home | Key::End | Key::PageUp | Key::PageDown | Key::Delete
+    )
+}
+
+/// Longest abbreviation the typed text ends with
+fn find_expansion(typed: &str, config: &SnippetConfig) -> Option<Snippet> {
+    config.snippets.iter()
+        .filter(|snippet| !snippet.abbreviation.is_empty() && typed.ends_with(&snippet.abbreviation))
+        .max_by_key(|snippet| snippet.abbreviation.len())
+        .cloned()
+}
+
+fn expand_snippet(snippet: Snippet, strategy: InjectionStrategy, disabled_apps: Vec<String>) {
+    if foreground_app().is_some_and(|app| app.matches_any(&disabled_apps)) {
+        debug!("Snippet {} typed in a disabled app, not expanding", snippet.abbreviation);
+        return;
+    }
+
+    let text = render_template_content(&snippet.expansion);
+    match inject_text(&text, snippet.abbreviation.chars().count(), strategy) {
+        Ok(()) => info!("✂️ Expanded snippet {}", snippet.abbreviation),
+        Err(e) => error!("❌ Failed to expand snippet {}: {}", snippet.abbreviation, e),
+    }
+}
+
+fn handle_snippet_event(event: rdev::Event) {
+    if is_injecting_text() {
+        return;
+    }
+
+    let mut buffer = TYPED_BUFFER.lock().unwrap();
+    match event.event_type {
+        EventType::KeyPress(key) if is_modifier(key) => {
+            buffer.modifiers_held += 1;
+            buffer.text.clear();
+        }
+        EventType::KeyRelease(key) if is_modifier(key) => {
+            buffer.modifiers_held = buffer.modifiers_held.saturating_sub(1);
+        }
+        EventType::KeyPress(Key::Backspace) => {
+            buffer.text.pop();
+        }
+        EventType::KeyPress(key) if breaks_typing(key) => buffer.text.clear(),
+        EventType::ButtonPress(_) => buffer.text.clear(),
+        EventType::KeyPress(_) if buffer.modifiers_held == 0 => {
+            let Some(name) = event.name.filter(|name| !name.chars().any(char::is_control)) else {
+                return;
+            };
+            buffer.text.push_str(&name);
+            let excess = buffer.text.chars().count().saturating_sub(MAX_TYPED_CHARS);
+            if excess > 0 {
+                buffer.text = buffer.text.chars().skip(excess).collect();
+            }
+
+            let config = SNIPPET_CONFIG.lock().unwrap().clone();
+            if let Some(snippet) = find_expansion(&buffer.text, &config) {
+                buffer.text.clear();
+                // Never simulate input from inside the hook callback
+                std::thread::spawn(move || expand_snippet(snippet, config.strategy, config.disabled_apps));
+            }
+        }
+        _ => {}
+    }
+}
+
+fn apply_snippet_config(config: SnippetConfig) {
+    let enabled = config.enabled && !config.snippets.is_empty();
+    *SNIPPET_CONFIG.lock().unwrap() = config;
+    *TYPED_BUFFER.lock().unwrap() = TypedBuffer::default();
+
+    if enabled {
+        add_input_listener("snippets", handle_snippet_event);
+        ensure_input_hook();
+    } else {
+        remove_input_listener("snippets");
+    }
+}
+
+/// Load saved snippets and start watching keystrokes if the expander is on
+pub fn start_snippet_expander(app: &AppHandle) {
+    apply_snippet_config(load_json_or_default(app, SNIPPETS_FILE));
+}
+
+#[tauri::command]
+pub fn get_snippet_config() -> SnippetConfig {
+    SNIPPET_CONFIG.lock().unwrap().clone()
+}
+
+#[tauri::command]
+pub fn save_snippet_config(app: AppHandle, config: SnippetConfig) -> Result<(), String> {
+    if let Some(snippet) = config.snippets.iter().find(|s| s.abbreviation.chars().count() > MAX_TYPED_CHARS) {
+        return Err(format!("Abbreviation '{}' is longer than {} characters", snippet.abbreviation, MAX_TYPED_CHARS));
+    }
+    save_json(&app, SNIPPETS_FILE, &config)?;
+    apply_snippet_config(config);
+    Ok(())
+}
@@ -0,0 +1,164 @@
+use tauri::{AppHandle, Manager};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, OnceLock};
+
+const PORTABLE_FLAG: &str = "--portable";
+const PORTABLE_MARKER_FILE: &str = "portable";
+const PORTABLE_DATA_DIR: &str = "data";
+/// Kept in the OS app data directory, points at a custom data directory
+pub const DATA_LOCATION_FILE: &str = "data_location.json";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct DataLocation {
+    /// Custom data directory, the OS location is used when unset
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct PortableInfo {
+    pub portable: bool,
+    #[serde(rename = "dataDir")]
+    pub data_dir: String,
+}
+
+// Decided once at startup so every module agrees on where data lives
+static PORTABLE_DIR: LazyLock<Option<PathBuf>> = LazyLock::new(detect_portable_data_dir);
+static DATA_DIR_OVERRIDE: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+/// Portable mode keeps all data in `data/` beside the executable.
+/// It is enabled by a `portable` marker file next to the binary or the `--portable` flag.
+fn detect_portable_data_dir() -> Option<PathBuf> {
+    let exe_dir = std::env::current_exe().ok()?.parent()?.to_path_buf();
+    let by_flag = std::env::args().any(|arg| arg == PORTABLE_FLAG);
+    if !by_flag && !exe_dir.join(PORTABLE_MARKER_FILE).exists() {
+        return None;
+    }
+    Some(exe_dir.join(PORTABLE_DATA_DIR))
+}
+
+/// The portable data directory when running in portable mode
+pub fn portable_data_dir() -> Option<PathBuf> {
+    PORTABLE_DIR.clone()
+}
+
+/// The OS app data directory, ignoring portable mode and custom locations
+pub fn default_app_data_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path().app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))
+}
+
+/// Read the custom data directory setting; changes only apply after a restart
+pub fn load_data_location(app: &AppHandle) -> DataLocation {
+    default_app_data_dir(app)
+        .ok()
+        .and_then(|dir| fs::read_to_string(dir.join(DATA_LOCATION_FILE)).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Get the app data directory, creating it if needed.
+/// Portable mode wins over a custom data directory, which wins over the OS location.
+pub fn get_app_data_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let custom_dir = DATA_DIR_OVERRIDE.get_or_init(|| {
+        load_data_location(app).path.filter(|p| !p.trim().is_empty()).map(PathBuf::from)
+    });
+    let app_data_dir = match (portable_data_dir(), custom_dir) {
+        (Some(dir), _) => dir,
+        (None, Some(dir)) => dir.clone(),
+        (None, None) => default_app_data_dir(app)?,
+    };
+
+    if !app_data_dir.exists() {
+        fs::create_dir_all(&app_data_dir)
+            .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+    }
+
+    Ok(app_data_dir)
+}
+
+/// Get a subdirectory of the app data directory, creating it if needed
+pub fn get_app_data_subdir(app: &AppHandle, name: &str) -> Result<PathBuf, String> {
+    let dir = get_app_data_dir(app)?.join(name);
+
+    if !dir.exists() {
+        fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create {} directory: {}", name, e))?;
+    }
+
+    Ok(dir)
+}
+
+/// Load a JSON file from the app data directory, falling back to the default value
+pub fn load_json_or_default<T: DeserializeOwned + Default>(app: &AppHandle, file_name: &str) -> T {
+    match get_app_data_dir(app) {
+        Ok(dir) => {
+            let path = dir.join(file_name);
+            if path.exists() {
+                match fs::read_to_string(&path) {
+                    Ok(content) => {
+                        match serde_json::from_str::<T>(&content) {
+                            Ok(value) => return value,
+                            Err(e) => error!("Failed to parse {}: {}", file_name, e),
+                        }
+                    }
+                    Err(e) => error!("Failed to read {}: {}", file_name, e),
+                }
+            }
+        }
+        Err(e) => error!("Failed to get path for {}: {}", file_name, e),
+    }
+
+    T::default()
+}
+
+/// Save a value as pretty JSON into the app data directory
+pub fn save_json<T: Serialize>(app: &AppHandle, file_name: &str, value: &T) -> Result<(), String> {
+    let path = get_app_data_dir(app)?.join(file_name);
+
+    let content = serde_json::to_string_pretty(value)
+        .map_err(|e| format!("Failed to serialize {}: {}", file_name, e))?;
+
+    write_file_atomic(&path, content.as_bytes())
+        .map_err(|e| format!("Failed to write {}: {}", file_name, e))
+}
+
+/// Write through a temporary file renamed over `path`, so a crash never leaves a half-written file
+pub fn write_file_atomic(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(".tmp");
+    let temp = PathBuf::from(temp);
+
+    let mut file = File::create(&temp)?;
+    file.write_all(bytes)?;
+    file.sync_all()?;
+    drop(file);
+    fs::rename(&temp, path)
+}
+
+/// Current unix time in milliseconds
+pub fn now_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Generate a unique, roughly time-ordered identifier
+pub fn generate_id() -> String {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    static COUNTER: AtomicU32 = AtomicU32::new(0);
+
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed) & 0xffff;
+    format!("{:x}{:04x}", now_millis(), count)
+}
+
+#[tauri::command]
+pub fn get_portable_info(app: AppHandle) -> Result<PortableInfo, String> {
+    Ok(PortableInfo {
+        portable: portable_data_dir().is_some(),
+        data_dir: get_app_data_dir(&app)?.to_string_lossy().to_string(),
+    })
+}
@@ -0,0 +1,288 @@
+use tauri::{AppHandle, Emitter};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+
+use crate::desktop::{get_configured_server_url, load_json_or_default, now_millis, replay_offline_queue_now, save_json};
+
+const SYNC_SCHEDULER_FILE: &str = "sync_scheduler.json";
+const SCHEDULER_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SyncSchedulerConfig {
+    pub enabled: bool,
+    #[serde(rename = "intervalMinutes")]
+    pub interval_minutes: u64,
+    #[serde(rename = "pauseOnMetered")]
+    pub pause_on_metered: bool,
+    /// Pause while running on battery below this charge, `None` to ignore the battery
+    #[serde(rename = "pauseOnBatteryBelow")]
+    pub pause_on_battery_below: Option<u8>,
+    /// Paused by hand from the tray or settings
+    pub paused: bool,
+}
+
+impl Default for SyncSchedulerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_minutes: 5,
+            pause_on_metered: true,
+            pause_on_battery_below: Some(20),
+            paused: false,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SyncSchedulerStatus {
+    /// "manual", "metered" or "battery" while background sync is held back
+    #[serde(rename = "pausedReason")]
+    pub paused_reason: Option<String>,
+    #[serde(rename = "onBattery")]
+    pub on_battery: bool,
+    #[serde(rename = "batteryPercent")]
+    pub battery_percent: Option<u8>,
+    pub metered: bool,
+    #[serde(rename = "lastRunAt")]
+    pub last_run_at: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct PowerState {
+    on_battery: bool,
+    percent: Option<u8>,
+}
+
+struct SyncMenuItems {
+    pause: tauri::menu::CheckMenuItem<tauri::Wry>,
+    status: tauri::menu::MenuItem<tauri::Wry>,
+}
+
+static SYNC_CONFIG: LazyLock<Mutex<Option<SyncSchedulerConfig>>> = LazyLock::new(|| Mutex::new(None));
+static SYNC_STATUS: LazyLock<Mutex<SyncSchedulerStatus>> = LazyLock::new(|| Mutex::new(SyncSchedulerStatus::default()));
+static SYNC_MENU_ITEMS: LazyLock<Mutex<Option<SyncMenuItems>>> = LazyLock::new(|| Mutex::new(None));
+static SCHEDULER_STARTED: AtomicBool = AtomicBool::new(false);
+
+#[cfg(not(target_os = "windows"))]
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = crate::desktop::background_command(program).args(args).output().ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[cfg(target_os = "windows")]
+fn power_state() -> PowerState {
+    use windows::Win32::System::Power::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+
+    let mut status = SYSTEM_POWER_STATUS::default();
+    if unsafe { GetSystemPowerStatus(&mut status) }.is_err() {
+        return PowerState::default();
+    }
+    PowerState {
+        // 0 = offline, 1 = online, 255 = unknown
+        on_battery: status.ACLineStatus == 0,
+        percent: (status.BatteryLifePercent <= 100).then_some(status.BatteryLifePercent),
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn power_state() -> PowerState {
+    // "Now drawing from 'Battery Power'" followed by "-InternalBattery-0 (id=...)\t85%; discharging; ..."
+    let Some(output) = command_output("pmset", &["-g", "batt"]) else { return PowerState::default() };
+    PowerState {
+        on_battery: output.contains("'Battery Power'"),
+        percent: output.split_whitespace()
+            .find_map(|word| word.trim_end_matches(';').strip_suffix('%'))
+            .and_then(|percent| percent.parse().ok()),
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn power_state() -> PowerState {
+    let mut state = PowerState::default();
+    let Ok(supplies) = std::fs::read_dir("/sys/class/power_supply") else { return state };
+    for supply in supplies.flatten() {
+        let read = |name: &str| std::fs::read_to_string(supply.path().join(name)).map(|v| v.trim().to_string()).unwrap_or_default();
+        if read("type") != "Battery" || read("scope") == "Device" {
+            // Skip mice and headsets that report their own battery
+            continue;
+        }
+        state.percent = read("capacity").parse().ok();
+        state.on_battery = read("status") == "Discharging";
+    }
+    state
+}
+
+#[cfg(target_os = "windows")]
+fn is_metered_connection() -> bool {
+    use windows::Networking::Connectivity::{NetworkCostType, NetworkInformation};
+
+    NetworkInformation::GetInternetConnectionProfile()
+        .and_then(|profile| profile.GetConnectionCost())
+        .and_then(|cost| Ok(matches!(cost.NetworkCostType()?, NetworkCostType::Fixed | NetworkCostType::Variable) || cost.Roaming()?))
+        .unwrap_or(false)
+}
+
+#[cfg(target_os = "macos")]
+fn is_metered_connection() -> bool {
+    // Low Data Mode is only exposed through Network.framework path monitoring
+    false
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn is_metered_connection() -> bool {
+    // NetworkManager NMMetered: 1 = yes, 3 = guessed yes
+    command_output("busctl", &[
+        "get-property", "org.freedesktop.NetworkManager", "/org/freedesktop/NetworkManager",
+        "org.freedesktop.NetworkManager", "Metered",
+    ])
+    .is_some_and(|value| matches!(value.trim(), "u 1" | "u 3"))
+}
+
+pub fn load_sync_scheduler_config(app: &AppHandle) -> SyncSchedulerConfig {
+    SYNC_CONFIG.lock().unwrap()
+        .get_or_insert_with(|| load_json_or_default(app, SYNC_SCHEDULER_FILE))
+        .clone()
+}
+
+/// Remember the tray items so they follow the pause state
+pub fn register_sync_menu_items(pause: tauri::menu::CheckMenuItem<tauri::Wry>, status: tauri::menu::MenuItem<tauri::Wry>) {
+    *SYNC_MENU_ITEMS.lock().unwrap() = Some(SyncMenuItems { pause, status });
+}
+
+pub fn sync_status_label(status: &SyncSchedulerStatus) -> String {
+    match status.paused_reason.as_deref() {
+        Some("manual") => "Sync: Paused".to_string(),
+        Some("metered") => "Sync: Paused (metered network)".to_string(),
+        Some("battery") => match status.battery_percent {
+            Some(percent) => format!("Sync: Paused (battery {}%)", percent),
+            None => "Sync: Paused (battery)".to_string(),
+        },
+        _ => match status.last_run_at {
+            Some(_) => "Sync: Active".to_string(),
+            None => "Sync: Waiting".to_string(),
+        },
+    }
+}
+
+/// Re-evaluate power and network conditions, updating the tray and emitting `sync-scheduler-status` on changes
+fn refresh_sync_status(app: &AppHandle) -> SyncSchedulerStatus {
+    let config = load_sync_scheduler_config(app);
+    let power = power_state();
+    let metered = config.pause_on_metered && is_metered_connection();
+    let low_battery = power.on_battery
+        && config.pause_on_battery_below.zip(power.percent).is_some_and(|(threshold, percent)| percent < threshold);
+
+    let mut guard = SYNC_STATUS.lock().unwrap();
+    let previous = guard.clone();
+    guard.on_battery = power.on_battery;
+    guard.battery_percent = power.percent;
+    guard.metered = metered;
+    guard.paused_reason = if config.paused {
+        Some("manual".to_string())
+    } else if metered {
+        Some("metered".to_string())
+    } else if low_battery {
+        Some("battery".to_string())
+    } else {
+        None
+    };
+    let status = guard.clone();
+    drop(guard);
+
+    if status.paused_reason != previous.paused_reason || status.last_run_at != previous.last_run_at {
+        if let Some(ref items) = *SYNC_MENU_ITEMS.lock().unwrap() {
+            let _ = items.pause.set_checked(config.paused);
+            let _ = items.status.set_text(sync_status_label(&status));
+        }
+        if status.paused_reason != previous.paused_reason {
+            info!("🔄 Background sync {}", status.paused_reason.as_deref().map(|r| format!("paused ({})", r)).unwrap_or("resumed".to_string()));
+        }
+        if let Err(e) = app.emit("sync-scheduler-status", &status) {
+            error!("Failed to emit sync-scheduler-status event: {}", e);
+        }
+    }
+    status
+}
+
+/// Whether background sync jobs may run now, checked by every periodic sync
+pub fn is_background_sync_allowed() -> bool {
+    SYNC_STATUS.lock().unwrap().paused_reason.is_none()
+}
+
+fn run_scheduled_sync(app: &AppHandle) {
+    SYNC_STATUS.lock().unwrap().last_run_at = Some(now_millis());
+    if get_configured_server_url().is_some() {
+        if let Err(e) = replay_offline_queue_now(app) {
+            warn!("⚠️ Scheduled offline replay failed: {}", e);
+        }
+    }
+    // The frontend pulls server changes and mirrors them to the markdown folder
+    if let Err(e) = app.emit("sync-requested", ()) {
+        error!("Failed to emit sync-requested event: {}", e);
+    }
+}
+
+/// Start the periodic sync scheduler (runs for the app lifetime)
+pub fn start_sync_scheduler(app: &AppHandle) {
+    if SCHEDULER_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let app_handle = app.clone();
+    std::thread::spawn(move || loop {
+        let config = load_sync_scheduler_config(&app_handle);
+        let status = refresh_sync_status(&app_handle);
+        let interval = config.interval_minutes.max(1) * 60 * 1000;
+        let due = status.last_run_at.map(|last| now_millis().saturating_sub(last) >= interval).unwrap_or(true);
+        if config.enabled && due && status.paused_reason.is_none() {
+            run_scheduled_sync(&app_handle);
+            refresh_sync_status(&app_handle);
+        }
+        std::thread::sleep(SCHEDULER_CHECK_INTERVAL);
+    });
+}
+
+fn set_sync_paused(app: &AppHandle, paused: bool) -> Result<SyncSchedulerStatus, String> {
+    let mut config = load_sync_scheduler_config(app);
+    config.paused = paused;
+    save_json(app, SYNC_SCHEDULER_FILE, &config)?;
+    *SYNC_CONFIG.lock().unwrap() = Some(config);
+    Ok(refresh_sync_status(app))
+}
+
+/// Flip the manual pause from the tray
+pub fn toggle_sync_paused(app: &AppHandle) {
+    let paused = !load_sync_scheduler_config(app).paused;
+    if let Err(e) = set_sync_paused(app, paused) {
+        error!("Failed to toggle background sync: {}", e);
+    }
+}
+
+#[tauri::command]
+pub fn get_sync_scheduler_config(app: AppHandle) -> SyncSchedulerConfig {
+    load_sync_scheduler_config(&app)
+}
+
+#[tauri::command]
+pub fn save_sync_scheduler_config(app: AppHandle, config: SyncSchedulerConfig) -> Result<SyncSchedulerStatus, String> {
+    save_json(&app, SYNC_SCHEDULER_FILE, &config)?;
+    *SYNC_CONFIG.lock().unwrap() = Some(config);
+    Ok(refresh_sync_status(&app))
+}
+
+#[tauri::command]
+pub fn get_sync_scheduler_status(app: AppHandle) -> SyncSchedulerStatus {
+    refresh_sync_status(&app)
+}
+
+#[tauri::command]
+pub fn pause_sync(app: AppHandle) -> Result<SyncSchedulerStatus, String> {
+    set_sync_paused(&app, true)
+}
+
+#[tauri::command]
+pub fn resume_sync(app: AppHandle) -> Result<SyncSchedulerStatus, String> {
+    set_sync_paused(&app, false)
+}
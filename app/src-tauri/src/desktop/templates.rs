@@ -0,0 +1,208 @@
+use tauri::AppHandle;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::desktop::{generate_id, http_client_builder, load_json_or_default, open_quicknote_with_text, register_shortcut_command, save_json};
+
+const TEMPLATES_FILE: &str = "templates.json";
+const WEATHER_TIMEOUT: Duration = Duration::from_secs(5);
+/// Prefix of the shortcut commands that capture with a template
+pub const TEMPLATE_COMMAND_PREFIX: &str = "template:";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NoteTemplate {
+    /// Empty for new templates, assigned on save
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    /// Markdown with `{{placeholder}}` or `{{placeholder:argument}}` tags
+    pub content: String,
+    /// Global shortcut that opens quicknote with the rendered template
+    pub shortcut: Option<String>,
+}
+
+fn load_templates(app: &AppHandle) -> Vec<NoteTemplate> {
+    load_json_or_default(app, TEMPLATES_FILE)
+}
+
+/// Current conditions from wttr.in, for the IP location unless a place is given
+fn fetch_weather(location: Option<&str>) -> Result<String, String> {
+    let url = format!("https://wttr.in/{}?format=%c+%t", location.unwrap_or_default());
+    let response = http_client_builder()
+        .timeout(WEATHER_TIMEOUT)
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?
+        .get(&url)
+        .send()
+        .map_err(|e| format!("Weather request failed: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Weather request failed with status {}", response.status()));
+    }
+    response.text()
+        .map(|text| text.trim().to_string())
+        .map_err(|e| format!("Failed to read weather: {}", e))
+}
+
+/// chrono panics on `to_string` with an invalid format, so user formats go through `write!`
+fn format_now(format: &str) -> String {
+    use std::fmt::Write;
+
+    let mut formatted = String::new();
+    if write!(formatted, "{}", chrono::Local::now().format(format)).is_err() {
+        warn!("⚠️ Invalid date format in template: {}", format);
+        formatted.clear();
+    }
+    formatted
+}
+
+fn expand_placeholder(name: &str, argument: Option<&str>) -> Option<String> {
+    let value = match name {
+        "date" => format_now(argument.unwrap_or("%Y-%m-%d")),
+        "time" => format_now(argument.unwrap_or("%H:%M")),
+        "datetime" => format_now(argument.unwrap_or("%Y-%m-%d %H:%M")),
+        "weekday" => format_now("%A"),
+        "clipboard" => arboard::Clipboard::new()
+            .and_then(|mut clipboard| clipboard.get_text())
+            .unwrap_or_default(),
+        "selection" => get_selected_text::get_selected_text().unwrap_or_default(),
+        "weather" => fetch_weather(argument).unwrap_or_else(|e| {
+            warn!("⚠️ {}", e);
+            String::new()
+        }),
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// Replace known placeholders; unknown ones are left as written
+pub fn render_template_content(content: &str) -> String {
+    let mut rendered = String::with_capacity(content.len());
+    let mut rest = content;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(length) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        let tag = &rest[start + 2..start + 2 + length];
+        let (name, argument) = match tag.split_once(':') {
+            Some((name, argument)) => (name.trim(), Some(argument.trim())),
+            None => (tag.trim(), None),
+        };
+
+        rendered.push_str(&rest[..start]);
+        match expand_placeholder(&name.to_lowercase(), argument) {
+            Some(value) => rendered.push_str(&value),
+            None => rendered.push_str(&rest[start..start + 4 + length]),
+        }
+        rest = &rest[start + 4 + length..];
+    }
+    rendered.push_str(rest);
+    rendered
+}
+
+fn register_template_shortcut(app: &AppHandle, template: &NoteTemplate) {
+    use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut};
+
+    let Some(ref shortcut) = template.shortcut else {
+        return;
+    };
+    let parsed_shortcut = match shortcut.parse::<Shortcut>() {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            error!("Invalid shortcut '{}' for template {}: {}", shortcut, template.name, e);
+            return;
+        }
+    };
+    let _ = app.global_shortcut().unregister(parsed_shortcut);
+    if let Err(e) = app.global_shortcut().register(parsed_shortcut) {
+        error!("Failed to register shortcut '{}' for template {}: {}", shortcut, template.name, e);
+        return;
+    }
+    register_shortcut_command(app, shortcut.clone(), format!("{}{}", TEMPLATE_COMMAND_PREFIX, template.id));
+    info!("Registered shortcut {} for template {}", shortcut, template.name);
+}
+
+fn unregister_template_shortcut(app: &AppHandle, template: &NoteTemplate) {
+    use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut};
+
+    if let Some(ref shortcut) = template.shortcut {
+        if let Ok(parsed_shortcut) = shortcut.parse::<Shortcut>() {
+            let _ = app.global_shortcut().unregister(parsed_shortcut);
+        }
+    }
+}
+
+/// Register the shortcuts of all saved templates
+pub fn register_template_shortcuts(app: &AppHandle) {
+    for template in load_templates(app) {
+        register_template_shortcut(app, &template);
+    }
+}
+
+/// Shortcut handler for `template:<id>` commands: render and open it in quicknote
+pub fn capture_with_template(app: &AppHandle, id: &str) {
+    let Some(template) = load_templates(app).into_iter().find(|t| t.id == id) else {
+        warn!("⚠️ Template {} not found", id);
+        return;
+    };
+
+    let app_handle = app.clone();
+    // Selection and weather lookups block, keep them off the shortcut handler
+    std::thread::spawn(move || {
+        let text = render_template_content(&template.content);
+        if let Err(e) = open_quicknote_with_text(&app_handle, &text) {
+            error!("❌ Failed to open template {}: {}", template.name, e);
+        }
+    });
+}
+
+#[tauri::command]
+pub fn list_templates(app: AppHandle) -> Vec<NoteTemplate> {
+    load_templates(&app)
+}
+
+/// Create or update a template, returns it with its id
+#[tauri::command]
+pub fn save_template(app: AppHandle, mut template: NoteTemplate) -> Result<NoteTemplate, String> {
+    if template.name.trim().is_empty() {
+        return Err("Template name is required".to_string());
+    }
+
+    let mut templates = load_templates(&app);
+    if template.id.is_empty() {
+        template.id = generate_id();
+    }
+    if let Some(position) = templates.iter().position(|t| t.id == template.id) {
+        let previous = templates.remove(position);
+        unregister_template_shortcut(&app, &previous);
+        templates.insert(position, template.clone());
+    } else {
+        templates.push(template.clone());
+    }
+
+    save_json(&app, TEMPLATES_FILE, &templates)?;
+    register_template_shortcut(&app, &template);
+    Ok(template)
+}
+
+#[tauri::command]
+pub fn delete_template(app: AppHandle, id: String) -> Result<(), String> {
+    let mut templates = load_templates(&app);
+    let position = templates.iter().position(|t| t.id == id)
+        .ok_or_else(|| format!("Template {} not found", id))?;
+    let removed = templates.remove(position);
+    unregister_template_shortcut(&app, &removed);
+    save_json(&app, TEMPLATES_FILE, &templates)
+}
+
+/// Expand a saved template's placeholders
+#[tauri::command]
+pub async fn render_template(app: AppHandle, id: String) -> Result<String, String> {
+    let template = load_templates(&app).into_iter()
+        .find(|t| t.id == id)
+        .ok_or_else(|| format!("Template {} not found", id))?;
+
+    tauri::async_runtime::spawn_blocking(move || render_template_content(&template.content))
+        .await
+        .map_err(|e| format!("Failed to render template: {}", e))
+}
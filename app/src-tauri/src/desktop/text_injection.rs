@@ -0,0 +1,152 @@
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use enigo::{Direction, Enigo, Key, Keyboard, Settings};
+
+/// Time the target app gets to read the clipboard before it is restored
+const PASTE_SETTLE_DELAY: Duration = Duration::from_millis(150);
+/// Simulated events can reach the input hook shortly after they were sent
+const INJECTED_EVENT_GRACE: Duration = Duration::from_millis(50);
+
+/// How text is put into the focused application
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum InjectionStrategy {
+    /// Simulated key presses, works everywhere but is slow for long text
+    #[default]
+    Type,
+    /// Clipboard and the paste shortcut; the previous clipboard text is restored afterwards
+    Paste,
+}
+
+// Set while keystrokes are being simulated, so input listeners can ignore them
+static INJECTING: AtomicBool = AtomicBool::new(false);
+
+/// Whether the keystrokes currently seen by the input hook are our own
+pub fn is_injecting_text() -> bool {
+    INJECTING.load(Ordering::SeqCst)
+}
+
+fn new_enigo() -> Result<Enigo, String> {
+    Enigo::new(&Settings::default()).map_err(|e| format!("Failed to create input simulator: {}", e))
+}
+
+fn paste_text(enigo: &mut Enigo, text: &str) -> Result<(), String> {
+    let mut clipboard = arboard::Clipboard::new()
+        .map_err(|e| format!("Failed to open clipboard: {}", e))?;
+    let previous = clipboard.get_text().ok();
+    clipboard.set_text(text)
+        .map_err(|e| format!("Failed to set clipboard: {}", e))?;
+
+    let modifier = if cfg!(target_os = "macos") { Key::Meta } else { Key::Control };
+    let result = enigo.key(modifier, Direction::Press)
+        .and_then(|_| enigo.key(Key::Unicode('v'), Direction::Click))
+        .and_then(|_| enigo.key(modifier, Direction::Release))
+        .map_err(|e| format!("Failed to paste: {}", e));
+
+    std::thread::sleep(PASTE_SETTLE_DELAY);
+    if let Some(previous) = previous {
+        let _ = clipboard.set_text(previous);
+    }
+    result
+}
+
+/// UI Automation reports password edits through the IsPassword property
+#[cfg(target_os = "windows")]
+pub fn is_focused_password_field() -> Option<bool> {
+    use windows::Win32::System::Com::{CoCreateInstance, CoInitializeEx, CLSCTX_INPROC_SERVER, COINIT_MULTITHREADED};
+    use windows::Win32::UI::Accessibility::{CUIAutomation, IUIAutomation};
+
+    // SAFETY: plain COM calls, the interfaces are released when dropped
+    unsafe {
+        let _ = CoInitializeEx(None, COINIT_MULTITHREADED);
+        let automation: IUIAutomation = CoCreateInstance(&CUIAutomation, None, CLSCTX_INPROC_SERVER).ok()?;
+        let element = automation.GetFocusedElement().ok()?;
+        element.CurrentIsPassword().ok().map(|is_password| is_password.as_bool())
+    }
+}
+
+/// Secure text fields carry the AXSecureTextField subrole and switch on secure event input
+#[cfg(target_os = "macos")]
+pub fn is_focused_password_field() -> Option<bool> {
+    use objc2_foundation::NSString;
+    use std::ffi::c_void;
+
+    #[link(name = "ApplicationServices", kind = "framework")]
+    extern "C" {
+        fn AXUIElementCreateSystemWide() -> *const c_void;
+        fn AXUIElementCopyAttributeValue(element: *const c_void, attribute: *const c_void, value: *mut *const c_void) -> i32;
+    }
+    #[link(name = "Carbon", kind = "framework")]
+    extern "C" {
+        fn IsSecureEventInputEnabled() -> u8;
+    }
+    #[link(name = "CoreFoundation", kind = "framework")]
+    extern "C" {
+        fn CFRelease(value: *const c_void);
+    }
+
+    // SAFETY: every copied AX object is released, NSString is toll-free bridged with CFString
+    unsafe {
+        if IsSecureEventInputEnabled() != 0 {
+            return Some(true);
+        }
+        let system = AXUIElementCreateSystemWide();
+        if system.is_null() {
+            return None;
+        }
+        let focused_attribute = NSString::from_str("AXFocusedUIElement");
+        let mut focused: *const c_void = std::ptr::null();
+        let result = AXUIElementCopyAttributeValue(system, &*focused_attribute as *const NSString as *const c_void, &mut focused);
+        CFRelease(system);
+        if result != 0 || focused.is_null() {
+            return None;
+        }
+
+        let subrole_attribute = NSString::from_str("AXSubrole");
+        let mut subrole: *const c_void = std::ptr::null();
+        let result = AXUIElementCopyAttributeValue(focused, &*subrole_attribute as *const NSString as *const c_void, &mut subrole);
+        CFRelease(focused);
+        if result != 0 || subrole.is_null() {
+            return Some(false);
+        }
+        let is_secure = (*(subrole as *const NSString)).to_string() == "AXSecureTextField";
+        CFRelease(subrole);
+        Some(is_secure)
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+pub fn is_focused_password_field() -> Option<bool> {
+    None
+}
+
+/// Leave text on the clipboard for the user to paste themselves, without it landing in clipboard history
+pub fn copy_text_for_manual_paste(text: &str) -> Result<(), String> {
+    crate::desktop::suppress_clipboard_capture(Duration::from_secs(2));
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.set_text(text))
+        .map_err(|e| format!("Failed to set clipboard: {}", e))
+}
+
+/// Send text to the focused application, optionally erasing characters before the cursor first
+pub fn inject_text(text: &str, erase_before: usize, strategy: InjectionStrategy) -> Result<(), String> {
+    let mut enigo = new_enigo()?;
+    INJECTING.store(true, Ordering::SeqCst);
+
+    let result = (|| {
+        for _ in 0..erase_before {
+            enigo.key(Key::Backspace, Direction::Click)
+                .map_err(|e| format!("Failed to erase text: {}", e))?;
+        }
+        match strategy {
+            InjectionStrategy::Type => enigo.text(text).map_err(|e| format!("Failed to type text: {}", e)),
+            InjectionStrategy::Paste => paste_text(&mut enigo, text),
+        }
+    })();
+
+    std::thread::sleep(INJECTED_EVENT_GRACE);
+    INJECTING.store(false, Ordering::SeqCst);
+    result
+}
@@ -18,10 +18,10 @@ use macos_accessibility_client::accessibility;
 fn query_accessibility_permissions() -> bool {
     let trusted = accessibility::application_is_trusted_with_prompt();
     if trusted {
-        println!("✅ Application has accessibility permissions");
+        info!("✅ Application has accessibility permissions");
     } else {
-        println!("❌ Application does not have accessibility permissions");
-        println!("ℹ️  Please grant accessibility permissions in System Settings > Privacy & Security > Accessibility");
+        warn!("⚠️ Application does not have accessibility permissions");
+        info!("ℹ️  Please grant accessibility permissions in System Settings > Privacy & Security > Accessibility");
     }
     trusted
 }
@@ -72,7 +72,7 @@ pub fn setup_text_selection_monitoring<R: Runtime>(
 ) -> Result<(), String> {
     use tauri_plugin_global_shortcut::{Shortcut, GlobalShortcutExt};
 
-    println!("🔧 setup_text_selection_monitoring called: enabled={}, modifier={}", enabled, trigger_modifier);
+    debug!("🔧 setup_text_selection_monitoring called: enabled={}, modifier={}", enabled, trigger_modifier);
 
     let mut monitor = TEXT_SELECTION_STATE.lock().unwrap();
 
@@ -90,7 +90,7 @@ pub fn setup_text_selection_monitoring<R: Runtime>(
             _ => "Control+Backquote",
         };
 
-        println!("📝 Registering shortcut: {}", shortcut_str);
+        info!("📝 Registering shortcut: {}", shortcut_str);
 
         let parsed_shortcut: Shortcut = shortcut_str.parse()
             .map_err(|e| format!("Failed to parse shortcut '{}': {}", shortcut_str, e))?;
@@ -99,9 +99,9 @@ pub fn setup_text_selection_monitoring<R: Runtime>(
             .map_err(|e| format!("Failed to register shortcut: {}", e))?;
 
         // Store the shortcut mapping for the global handler (normalize to lowercase)
-        crate::desktop::register_shortcut_command(shortcut_str.to_lowercase(), "text-selection".to_string());
+        app.state::<crate::desktop::ShortcutRegistry>().set_command(shortcut_str, "text-selection".to_string());
 
-        println!("✅ Text selection monitoring enabled with {} + Backquote", trigger_modifier);
+        info!("✅ Text selection monitoring enabled with {} + Backquote", trigger_modifier);
     } else {
         // Disable monitoring and unregister shortcuts
         monitor.enabled = false;
@@ -117,8 +117,9 @@ pub fn setup_text_selection_monitoring<R: Runtime>(
         if let Ok(parsed_shortcut) = shortcut_str.parse::<Shortcut>() {
             let _ = app.global_shortcut().unregister(parsed_shortcut);
         }
+        app.state::<crate::desktop::ShortcutRegistry>().remove_command(shortcut_str);
 
-        println!("❌ Text selection monitoring disabled");
+        info!("⏸️ Text selection monitoring disabled");
     }
 
     Ok(())
@@ -130,21 +131,21 @@ pub fn setup_text_selection_monitoring<R: Runtime>(
 fn get_mouse_position<R: Runtime>(app: &AppHandle<R>) -> (f64, f64) {
     match Mouse::get_mouse_position() {
         Mouse::Position { x, y } => {
-            println!("📍 Raw mouse position: ({}, {})", x, y);
+            debug!("📍 Raw mouse position: ({}, {})", x, y);
             let mut pos_x = x as f64;
             let mut pos_y = y as f64; // Position at cursor level
 
             // Windows-specific adjustments for DPI scaling
             #[cfg(target_os = "windows")]
             {
-                println!("🪟 Windows detected - checking DPI scaling");
+                debug!("🪟 Windows detected - checking DPI scaling");
                 // Get primary monitor to determine scaling factor
                 if let Ok(monitors) = app.available_monitors() {
                     if let Some(primary_monitor) = monitors.into_iter().next() {
                         let monitor_size = primary_monitor.size();
                         let monitor_scale = primary_monitor.scale_factor();
 
-                        println!("📺 Primary monitor: size=({}x{}), scale={}",
+                        debug!("📺 Primary monitor: size=({}x{}), scale={}",
                                 monitor_size.width, monitor_size.height, monitor_scale);
 
                         // If scale factor > 1, mouse position is likely in physical pixels
@@ -152,7 +153,7 @@ fn get_mouse_position<R: Runtime>(app: &AppHandle<R>) -> (f64, f64) {
                         if monitor_scale > 1.0 {
                             pos_x /= monitor_scale;
                             pos_y /= monitor_scale;
-                            println!("🔧 Converted to logical pixels: ({}, {})", pos_x, pos_y);
+                            debug!("🔧 Converted to logical pixels: ({}, {})", pos_x, pos_y);
                         }
 
                         // Ensure window fits within monitor bounds
@@ -164,19 +165,19 @@ fn get_mouse_position<R: Runtime>(app: &AppHandle<R>) -> (f64, f64) {
                         pos_x = pos_x.max(10.0).min(max_x);
                         pos_y = pos_y.max(10.0).min(max_y);
 
-                        println!("📐 Constrained position: ({}, {}) within bounds (0,0)-({}, {})",
+                        debug!("📐 Constrained position: ({}, {}) within bounds (0,0)-({}, {})",
                                 pos_x, pos_y, max_x, max_y);
                     } else {
-                        println!("⚠️ No primary monitor found, using fallback positioning");
+                        warn!("⚠️ No primary monitor found, using fallback positioning");
                         // Fallback: assume 1920x1080 monitor with scale 1.0
                         let max_x = 1920.0 - crate::desktop::QUICKTOOL_WIDTH - 10.0;
                         let max_y = 1080.0 - crate::desktop::QUICKTOOL_HEIGHT - 10.0;
                         pos_x = pos_x.max(10.0).min(max_x);
                         pos_y = pos_y.max(10.0).min(max_y);
-                        println!("📐 Fallback constrained position: ({}, {})", pos_x, pos_y);
+                        debug!("📐 Fallback constrained position: ({}, {})", pos_x, pos_y);
                     }
                 } else {
-                    println!("❌ Failed to get monitors, using basic bounds check");
+                    warn!("⚠️ Failed to get monitors, using basic bounds check");
                     pos_x = pos_x.max(10.0).min(1600.0); // Conservative bounds
                     pos_y = pos_y.max(10.0).min(900.0);
                 }
@@ -189,11 +190,11 @@ fn get_mouse_position<R: Runtime>(app: &AppHandle<R>) -> (f64, f64) {
                 pos_y = pos_y.max(10.0);
             }
 
-            println!("🎯 Final window position: ({}, {})", pos_x, pos_y);
+            debug!("🎯 Final window position: ({}, {})", pos_x, pos_y);
             (pos_x, pos_y)
         }
         Mouse::Error => {
-            println!("❌ Failed to get mouse position, using fallback");
+            warn!("⚠️ Failed to get mouse position, using fallback");
             (400.0, 300.0) // Better fallback position
         }
     }
@@ -202,23 +203,23 @@ fn get_mouse_position<R: Runtime>(app: &AppHandle<R>) -> (f64, f64) {
 // Fallback for mobile platforms
 #[cfg(any(target_os = "android", target_os = "ios"))]
 fn get_mouse_position<R: Runtime>(_app: &AppHandle<R>) -> (f64, f64) {
-    println!("📱 Mobile platform - using fixed position");
+    info!("📱 Mobile platform - using fixed position");
     (400.0, 300.0) // Fixed position for mobile
 }
 
 // Helper function to show and position quicktool window
 fn show_quicktool_window_at_position<R: Runtime>(app: &AppHandle<R>, x: f64, y: f64) -> Result<(), String> {
     if let Some(window) = app.get_webview_window("quicktool") {
-        println!("✅ Found existing quicktool window, repositioning and showing");
+        debug!("✅ Found existing quicktool window, repositioning and showing");
 
         // Check current URL and navigate back to quicktool if needed
         if let Ok(url) = window.url() {
             let url_str = url.to_string();
-            println!("🔗 Current quicktool window URL: {}", url_str);
+            debug!("🔗 Current quicktool window URL: {}", url_str);
 
             // If URL is not /quicktool, send navigation event to frontend
             if !url_str.contains("/quicktool") {
-                println!("🔄 Quicktool window URL changed, sending navigation event to frontend");
+                debug!("🔄 Quicktool window URL changed, sending navigation event to frontend");
 
                 // Use JavaScript to navigate back to quicktool route
                 let js_code = r#"
@@ -232,9 +233,9 @@ fn show_quicktool_window_at_position<R: Runtime>(app: &AppHandle<R>, x: f64, y:
                 "#;
 
                 if let Err(e) = window.eval(js_code) {
-                    eprintln!("❌ Failed to navigate quicktool window back to /quicktool via JS: {}", e);
+                    error!("❌ Failed to navigate quicktool window back to /quicktool via JS: {}", e);
                 } else {
-                    println!("✅ Quicktool window navigated back to /quicktool via JavaScript");
+                    debug!("✅ Quicktool window navigated back to /quicktool via JavaScript");
                 }
 
                 // Add a small delay to ensure navigation completes
@@ -258,38 +259,38 @@ fn show_quicktool_window_at_position<R: Runtime>(app: &AppHandle<R>, x: f64, y:
 
         // Debug: Check if window is actually visible
         match window.is_visible() {
-            Ok(true) => println!("✅ Quicktool window is visible"),
-            Ok(false) => println!("⚠️ Quicktool window is not visible after show()"),
-            Err(e) => println!("❌ Failed to check window visibility: {}", e),
+            Ok(true) => debug!("✅ Quicktool window is visible"),
+            Ok(false) => warn!("⚠️ Quicktool window is not visible after show()"),
+            Err(e) => error!("❌ Failed to check window visibility: {}", e),
         }
 
-        println!("✅ Quicktool window repositioned and shown at ({}, {})", x, y);
+        debug!("✅ Quicktool window repositioned and shown at ({}, {})", x, y);
         Ok(())
     } else {
         // Create new window if it doesn't exist
         crate::desktop::toggle_quicktool_window(app.clone())
             .map_err(|e| format!("Failed to create quicktool window: {}", e))?;
-        println!("✅ New quicktool window created via toggle");
+        debug!("✅ New quicktool window created via toggle");
         Ok(())
     }
 }
 
 // Function to handle text selection when double modifier press is detected
 pub fn handle_text_selection<R: Runtime>(app: &AppHandle<R>) {
-    println!("🎯 Text selection shortcut triggered!");
+    debug!("🎯 Text selection shortcut triggered!");
 
     // Get and validate selected text
     let selected_text = match get_selected_text_directly() {
         Ok(text) if !text.is_empty() && text.trim().len() > 1 => {
-            println!("📋 Got selected text: '{}' (length: {})", text, text.len());
+            debug!("📋 Got selected text ({} characters)", text.chars().count());
             text
         }
         Ok(_) => {
-            println!("⚠️ Selected text is empty or too short");
+            debug!("📋 Selected text is empty or too short");
             return;
         }
         Err(e) => {
-            eprintln!("❌ Failed to get selected text: {}", e);
+            error!("❌ Failed to get selected text: {}", e);
             return;
         }
     };
@@ -311,17 +312,17 @@ pub fn handle_text_selection<R: Runtime>(app: &AppHandle<R>) {
         match window.is_visible() {
             Ok(true) => {
                 // Window is visible, hide it
-                println!("🔄 Quicktool window is visible, hiding it (toggle)");
+                debug!("🔄 Quicktool window is visible, hiding it (toggle)");
                 if let Err(e) = window.hide() {
-                    eprintln!("❌ Failed to hide quicktool window: {}", e);
+                    error!("❌ Failed to hide quicktool window: {}", e);
                 }
                 return;
             }
             Ok(false) => {
                 // Window exists but is hidden, show it at mouse position
-                println!("🔄 Quicktool window exists but hidden, showing at mouse position");
+                debug!("🔄 Quicktool window exists but hidden, showing at mouse position");
                 if let Err(e) = show_quicktool_window_at_position(app, x, y) {
-                    eprintln!("❌ Failed to show quicktool window: {}", e);
+                    error!("❌ Failed to show quicktool window: {}", e);
                     return;
                 }
                 // Send event after showing window
@@ -330,15 +331,15 @@ pub fn handle_text_selection<R: Runtime>(app: &AppHandle<R>) {
             }
             Err(_) => {
                 // Can't determine visibility, recreate the window
-                println!("⚠️ Can't determine window visibility, recreating");
+                warn!("⚠️ Can't determine window visibility, recreating");
             }
         }
     }
 
     // Window doesn't exist or visibility check failed, create and show
-    println!("🔄 Quicktool window doesn't exist, creating and showing");
+    debug!("🔄 Quicktool window doesn't exist, creating and showing");
     if let Err(e) = show_quicktool_window_at_position(app, x, y) {
-        eprintln!("❌ Failed to create/show quicktool window: {}", e);
+        error!("❌ Failed to create/show quicktool window: {}", e);
         return;
     }
 
@@ -351,34 +352,23 @@ fn send_text_selection_event<R: Runtime>(app: &AppHandle<R>, text_event: &TextSe
     // Emit to the quicktool window specifically
     if let Some(quicktool_window) = app.get_webview_window("quicktool") {
         match quicktool_window.emit("text-selection-detected", text_event) {
-            Ok(_) => println!("📡 Successfully emitted text selection event to quicktool window"),
-            Err(e) => eprintln!("❌ Failed to emit text selection event to quicktool window: {}", e),
+            Ok(_) => debug!("📡 Successfully emitted text selection event to quicktool window"),
+            Err(e) => error!("❌ Failed to emit text selection event to quicktool window: {}", e),
         }
     } else {
-        eprintln!("❌ Quicktool window not found for event emission");
+        warn!("⚠️ Quicktool window not found for event emission");
     }
 
     // Also emit globally as fallback
     match app.emit("text-selection-detected", text_event) {
-        Ok(_) => println!("📡 Successfully emitted text selection event globally"),
-        Err(e) => eprintln!("❌ Failed to emit text selection event globally: {}", e),
+        Ok(_) => debug!("📡 Successfully emitted text selection event globally"),
+        Err(e) => error!("❌ Failed to emit text selection event globally: {}", e),
     }
 }
 
-// Function to check if text selection is enabled for a modifier
-pub fn is_text_selection_enabled_for(modifier: &str) -> bool {
-    println!("🔍 Checking if text selection is enabled for modifier: {}", modifier);
-
-    let monitor = TEXT_SELECTION_STATE.lock().unwrap();
-
-    println!("📊 Current monitor state: enabled={}, trigger_modifier={}", monitor.enabled, monitor.trigger_modifier);
-
-    monitor.enabled && monitor.trigger_modifier == modifier
-}
-
 #[tauri::command]
 pub fn copy_to_clipboard(text: String) -> Result<(), String> {
-    println!("📋 copy_to_clipboard called with text: '{}' (length: {})", text, text.len());
+    debug!("📋 copy_to_clipboard called with {} characters", text.chars().count());
 
     // Use arboard for cross-platform clipboard access
     #[cfg(not(any(target_os = "android", target_os = "ios")))]
@@ -386,19 +376,22 @@ pub fn copy_to_clipboard(text: String) -> Result<(), String> {
         let mut clipboard = Clipboard::new()
             .map_err(|e| format!("Failed to access clipboard: {}", e))?;
 
+        // Keep this temporary write out of the clipboard history
+        crate::desktop::suppress_clipboard_capture(std::time::Duration::from_millis(2000));
+
         clipboard.set_text(&text)
             .map_err(|e| format!("Failed to set clipboard text: {}", e))?;
 
-        println!("✅ Clipboard updated with text");
+        debug!("✅ Clipboard updated with text");
 
         // Clear clipboard after a delay
         std::thread::spawn(move || {
             std::thread::sleep(std::time::Duration::from_millis(1000));
             if let Ok(mut cb) = Clipboard::new() {
                 if let Err(e) = cb.clear() {
-                    eprintln!("Failed to clear clipboard: {}", e);
+                    error!("Failed to clear clipboard: {}", e);
                 } else {
-                    println!("🗑️ Clipboard cleared");
+                    debug!("🗑️ Clipboard cleared");
                 }
             }
         });
@@ -407,7 +400,7 @@ pub fn copy_to_clipboard(text: String) -> Result<(), String> {
     #[cfg(any(target_os = "android", target_os = "ios"))]
     {
         // Mobile platforms don't need clipboard clearing
-        println!("📱 Mobile platform - clipboard not modified");
+        info!("📱 Mobile platform - clipboard not modified");
     }
 
     Ok(())
@@ -415,20 +408,20 @@ pub fn copy_to_clipboard(text: String) -> Result<(), String> {
 
 #[tauri::command]
 pub fn test_text_selection() -> Result<String, String> {
-    println!("🧪 test_text_selection command called");
+    info!("🧪 test_text_selection command called");
     Ok("Text selection system is working!".to_string())
 }
 
 #[tauri::command]
 pub fn check_accessibility_permissions() -> Result<bool, String> {
-    println!("🔐 Checking accessibility permissions...");
+    info!("🔐 Checking accessibility permissions...");
     let has_permissions = query_accessibility_permissions();
     Ok(has_permissions)
 }
 
 #[tauri::command]
 pub fn show_quicktool<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
-    println!("🔧 Manually showing quicktool window");
+    debug!("🔧 Manually showing quicktool window");
 
     if let Some(window) = app.get_webview_window("quicktool") {
         // Position at center of screen
@@ -442,13 +435,13 @@ pub fn show_quicktool<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
         window.set_focus()
             .map_err(|e| format!("Failed to focus window: {}", e))?;
 
-        println!("✅ Quicktool window shown at (400, 300)");
+        debug!("✅ Quicktool window shown at (400, 300)");
         Ok(())
     } else {
         // Create new window if it doesn't exist
         crate::desktop::toggle_quicktool_window(app)
             .map_err(|e| format!("Failed to create window: {}", e))?;
-        println!("✅ New quicktool window created");
+        debug!("✅ New quicktool window created");
         Ok(())
     }
 }
@@ -457,30 +450,30 @@ pub fn show_quicktool<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
 // Get selected text directly without using clipboard
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 fn get_selected_text_directly() -> Result<String, String> {
-    println!("📋 Attempting to get selected text directly...");
+    debug!("📋 Attempting to get selected text directly...");
 
     // Check accessibility permissions on macOS
     if !query_accessibility_permissions() {
-        println!("⚠️  Accessibility permissions not granted - text selection may not work properly");
-        println!("ℹ️  On macOS, please grant accessibility permissions in System Settings > Privacy & Security > Accessibility");
+        warn!("⚠️  Accessibility permissions not granted - text selection may not work properly");
+        info!("ℹ️  On macOS, please grant accessibility permissions in System Settings > Privacy & Security > Accessibility");
     }
 
     match get_selected_text() {
         Ok(text) => {
             if !text.trim().is_empty() {
-                println!("✅ Selected text found: '{}' (length: {})", text, text.len());
+                debug!("✅ Selected text found ({} characters)", text.chars().count());
                 Ok(text)
             } else {
-                println!("❌ Selected text is empty");
+                debug!("📋 Selected text is empty");
                 Err("No text selected".to_string())
             }
         }
         Err(e) => {
-            println!("❌ Failed to get selected text: {}", e);
-            println!("ℹ️  This might be because:");
-            println!("   - The application doesn't support accessibility API");
-            println!("   - On macOS: accessibility permissions not granted");
-            println!("   - The fallback clipboard method will be used automatically");
+            warn!("⚠️ Failed to get selected text: {}", e);
+            debug!("ℹ️  This might be because:");
+            debug!("   - The application doesn't support accessibility API");
+            debug!("   - On macOS: accessibility permissions not granted");
+            debug!("   - The fallback clipboard method will be used automatically");
             Err(format!("Failed to get selected text: {}", e))
         }
     }
@@ -0,0 +1,138 @@
+use tauri::{AppHandle, Emitter, Manager, WindowEvent};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+
+use crate::desktop::background_command;
+
+// Accent color changes have no window event, so they are checked on this interval
+const ACCENT_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SystemTheme {
+    /// "light" or "dark"
+    pub theme: String,
+    /// `#RRGGBB`, where the OS exposes one
+    #[serde(rename = "accentColor")]
+    pub accent_color: Option<String>,
+}
+
+static LAST_THEME: LazyLock<Mutex<Option<SystemTheme>>> = LazyLock::new(|| Mutex::new(None));
+static THEME_MONITOR_STARTED: AtomicBool = AtomicBool::new(false);
+
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = background_command(program).args(args).output().ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[cfg(target_os = "windows")]
+fn reg_dword(key: &str, value: &str) -> Option<u32> {
+    let text = command_output("reg", &["query", key, "/v", value])?;
+    let hex = text.lines()
+        .find(|line| line.contains(value))?
+        .split_whitespace()
+        .last()?
+        .trim_start_matches("0x");
+    u32::from_str_radix(hex, 16).ok()
+}
+
+#[cfg(target_os = "windows")]
+fn detect_system_theme() -> SystemTheme {
+    let light = reg_dword(r"HKCU\Software\Microsoft\Windows\CurrentVersion\Themes\Personalize", "AppsUseLightTheme");
+    // Stored as 0xAABBGGRR
+    let accent_color = reg_dword(r"HKCU\Software\Microsoft\Windows\DWM", "AccentColor")
+        .map(|abgr| format!("#{:02X}{:02X}{:02X}", abgr & 0xff, (abgr >> 8) & 0xff, (abgr >> 16) & 0xff));
+
+    SystemTheme {
+        theme: if light == Some(0) { "dark" } else { "light" }.to_string(),
+        accent_color,
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn detect_system_theme() -> SystemTheme {
+    let dark = command_output("defaults", &["read", "-g", "AppleInterfaceStyle"])
+        .is_some_and(|style| style.trim() == "Dark");
+    // The key is missing for the default blue accent
+    let accent = command_output("defaults", &["read", "-g", "AppleAccentColor"])
+        .and_then(|value| value.trim().parse::<i32>().ok());
+    let accent_color = match accent {
+        Some(-1) => "#8C8C8C",
+        Some(0) => "#FF5257",
+        Some(1) => "#F7821B",
+        Some(2) => "#FFC600",
+        Some(3) => "#62BA46",
+        Some(5) => "#A550A7",
+        Some(6) => "#F74F9E",
+        _ => "#007AFF",
+    };
+
+    SystemTheme {
+        theme: if dark { "dark" } else { "light" }.to_string(),
+        accent_color: Some(accent_color.to_string()),
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn detect_system_theme() -> SystemTheme {
+    let scheme = command_output("gsettings", &["get", "org.gnome.desktop.interface", "color-scheme"]).unwrap_or_default();
+    let gtk_theme = command_output("gsettings", &["get", "org.gnome.desktop.interface", "gtk-theme"]).unwrap_or_default();
+    let dark = scheme.contains("dark") || gtk_theme.to_lowercase().contains("dark");
+
+    SystemTheme {
+        theme: if dark { "dark" } else { "light" }.to_string(),
+        accent_color: None,
+    }
+}
+
+/// Emit `system-theme-changed` if the appearance differs from the last one seen
+fn refresh_system_theme(app: &AppHandle) {
+    let theme = detect_system_theme();
+    let mut last = LAST_THEME.lock().unwrap();
+    if last.as_ref() == Some(&theme) {
+        return;
+    }
+    let first = last.is_none();
+    *last = Some(theme.clone());
+    drop(last);
+
+    if !first {
+        info!("🎨 System appearance changed to {} (accent {:?})", theme.theme, theme.accent_color);
+        if let Err(e) = app.emit("system-theme-changed", &theme) {
+            error!("Failed to emit system-theme-changed event: {}", e);
+        }
+    }
+}
+
+/// Follow OS appearance changes for the webview and the custom titlebar
+pub fn start_theme_monitor(app: &AppHandle) {
+    if THEME_MONITOR_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    refresh_system_theme(app);
+
+    // Light/dark switches arrive as window events while the window follows the OS theme
+    if let Some(window) = app.get_webview_window("main") {
+        let app_handle = app.clone();
+        window.on_window_event(move |event| {
+            if let WindowEvent::ThemeChanged(_) = event {
+                // Detection spawns helper processes, keep it off the event loop
+                let app_handle = app_handle.clone();
+                std::thread::spawn(move || refresh_system_theme(&app_handle));
+            }
+        });
+    }
+
+    // A theme forced by set_desktop_theme suppresses those events, and accents never send one
+    let app_handle = app.clone();
+    std::thread::spawn(move || loop {
+        std::thread::sleep(ACCENT_CHECK_INTERVAL);
+        refresh_system_theme(&app_handle);
+    });
+}
+
+#[tauri::command]
+pub fn get_system_theme() -> SystemTheme {
+    detect_system_theme()
+}
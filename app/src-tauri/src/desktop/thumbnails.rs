@@ -0,0 +1,254 @@
+use tauri::AppHandle;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use image::codecs::jpeg::JpegEncoder;
+use image::{DynamicImage, GenericImageView, Rgba, RgbaImage};
+use reqwest::header::AUTHORIZATION;
+
+use crate::desktop::{
+    background_command, generate_id, get_app_data_subdir, get_attachments_dir, get_configured_server_url,
+    get_server_token_for_url, http_client_builder, load_json_or_default, save_json,
+};
+
+const THUMBNAIL_CONFIG_FILE: &str = "thumbnail_config.json";
+pub const THUMBNAILS_DIR: &str = "thumbnails";
+const THUMBNAIL_QUALITY: u8 = 80;
+const MIN_THUMBNAIL_SIZE: u32 = 16;
+const MAX_THUMBNAIL_SIZE: u32 = 1024;
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ThumbnailConfig {
+    /// Custom path to ffmpeg for video frames (uses PATH when empty)
+    #[serde(rename = "ffmpegPath")]
+    pub ffmpeg_path: String,
+    /// Custom path to poppler's pdftoppm for PDF pages (uses PATH when empty)
+    #[serde(rename = "pdftoppmPath")]
+    pub pdftoppm_path: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Thumbnail {
+    /// JPEG file in the thumbnail cache
+    pub path: String,
+    pub width: u32,
+    pub height: u32,
+    /// Served from cache without regenerating
+    pub cached: bool,
+}
+
+/// Load thumbnail config from file
+pub fn load_thumbnail_config(app: &AppHandle) -> ThumbnailConfig {
+    load_json_or_default(app, THUMBNAIL_CONFIG_FILE)
+}
+
+fn tool_program<'a>(configured: &'a str, default: &'a str) -> &'a str {
+    if configured.trim().is_empty() { default } else { configured.trim() }
+}
+
+fn is_remote(id: &str) -> bool {
+    id.starts_with('/') && !Path::new(id).exists() || id.starts_with("http://") || id.starts_with("https://")
+}
+
+/// Key the cache on the id, and on the modification time for arbitrary local paths
+fn cache_key(id: &str, source: Option<&Path>) -> String {
+    let modified = source
+        .and_then(|p| fs::metadata(p).ok())
+        .and_then(|m| m.modified().ok())
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+    let digest = Sha256::digest(format!("{}:{}", id, modified).as_bytes());
+    digest.iter().take(12).map(|b| format!("{:02x}", b)).collect()
+}
+
+/// An attachment id is the content hash of an imported file, a local path, or a server file path
+fn resolve_local_attachment(app: &AppHandle, id: &str) -> Result<Option<PathBuf>, String> {
+    let path = Path::new(id);
+    if path.is_absolute() && path.is_file() {
+        return Ok(Some(path.to_path_buf()));
+    }
+    if id.len() < 16 || !id.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Ok(None);
+    }
+
+    let prefix = format!("{}_", &id[..16]);
+    let dir = get_attachments_dir(app)?;
+    let entries = fs::read_dir(&dir).map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+    Ok(entries.flatten()
+        .find(|e| e.file_name().to_string_lossy().starts_with(&prefix))
+        .map(|e| e.path()))
+}
+
+/// Fetch a server attachment into a temporary file
+fn download_attachment(app: &AppHandle, id: &str) -> Result<PathBuf, String> {
+    let url = if id.starts_with("http") {
+        id.to_string()
+    } else {
+        format!("{}{}", get_configured_server_url().ok_or("Server is not configured")?.trim_end_matches('/'), id)
+    };
+
+    let client = http_client_builder()
+        .timeout(DOWNLOAD_TIMEOUT)
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let mut request = client.get(&url);
+    if let Some(token) = get_server_token_for_url(&url) {
+        request = request.header(AUTHORIZATION, format!("Bearer {}", token));
+    }
+    let mut response = request.send()
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| format!("Failed to download {}: {}", url, e))?;
+
+    let name = url.split('?').next().unwrap_or(&url).rsplit('/').next().unwrap_or("attachment").to_string();
+    let temp = get_app_data_subdir(app, THUMBNAILS_DIR)?.join(format!("download-{}-{}", generate_id(), name));
+    let mut file = File::create(&temp).map_err(|e| format!("Failed to create {}: {}", temp.display(), e))?;
+    io::copy(&mut response, &mut file).map_err(|e| format!("Failed to download {}: {}", url, e))?;
+    Ok(temp)
+}
+
+/// Render the first PDF page, via poppler or Quick Look on macOS
+fn render_pdf_page(config: &ThumbnailConfig, source: &Path, size: u32, work_dir: &Path) -> Result<PathBuf, String> {
+    let prefix = work_dir.join(format!("page-{}", generate_id()));
+    let output = background_command(tool_program(&config.pdftoppm_path, "pdftoppm"))
+        .args(["-png", "-f", "1", "-l", "1", "-singlefile", "-scale-to"])
+        .arg(size.to_string())
+        .arg(source)
+        .arg(&prefix)
+        .output();
+    let page = prefix.with_extension("png");
+    if output.as_ref().is_ok_and(|o| o.status.success()) && page.exists() {
+        return Ok(page);
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let status = background_command("qlmanage")
+            .args(["-t", "-s"])
+            .arg(size.to_string())
+            .arg("-o")
+            .arg(work_dir)
+            .arg(source)
+            .output();
+        let preview = work_dir.join(format!("{}.png", source.file_name().unwrap_or_default().to_string_lossy()));
+        if status.is_ok_and(|o| o.status.success()) && preview.exists() {
+            return Ok(preview);
+        }
+    }
+
+    Err(match output {
+        Ok(o) => format!("pdftoppm failed: {}", String::from_utf8_lossy(&o.stderr).trim()),
+        Err(e) => format!("Failed to run pdftoppm (is poppler installed?): {}", e),
+    })
+}
+
+fn render_video_frame(config: &ThumbnailConfig, source: &Path, size: u32, work_dir: &Path) -> Result<PathBuf, String> {
+    let frame = work_dir.join(format!("frame-{}.png", generate_id()));
+    let output = background_command(tool_program(&config.ffmpeg_path, "ffmpeg"))
+        .arg("-y")
+        .arg("-i")
+        .arg(source)
+        .args(["-frames:v", "1", "-vf"])
+        .arg(format!("scale='min({0},iw)':'min({0},ih)':force_original_aspect_ratio=decrease", size))
+        .arg(&frame)
+        .output()
+        .map_err(|e| format!("Failed to run ffmpeg (is it installed?): {}", e))?;
+    if !output.status.success() || !frame.exists() {
+        return Err(format!("ffmpeg failed: {}", String::from_utf8_lossy(&output.stderr).lines().last().unwrap_or_default()));
+    }
+    Ok(frame)
+}
+
+/// JPEG has no alpha, so transparent areas are put on white
+fn flatten_alpha(image: &DynamicImage) -> DynamicImage {
+    let (width, height) = image.dimensions();
+    let mut background = RgbaImage::from_pixel(width, height, Rgba([255, 255, 255, 255]));
+    image::imageops::overlay(&mut background, &image.to_rgba8(), 0, 0);
+    DynamicImage::ImageRgb8(DynamicImage::ImageRgba8(background).to_rgb8())
+}
+
+fn write_thumbnail(image: DynamicImage, size: u32, target: &Path) -> Result<(u32, u32), String> {
+    let thumbnail = flatten_alpha(&image.thumbnail(size, size));
+    let mut file = File::create(target).map_err(|e| format!("Failed to create {}: {}", target.display(), e))?;
+    JpegEncoder::new_with_quality(&mut file, THUMBNAIL_QUALITY)
+        .encode_image(&thumbnail)
+        .map_err(|e| format!("Failed to encode thumbnail: {}", e))?;
+    Ok(thumbnail.dimensions())
+}
+
+fn render_thumbnail(app: &AppHandle, source: &Path, size: u32, target: &Path) -> Result<(u32, u32), String> {
+    let mime = mime_guess::from_path(source).first_or_octet_stream();
+    let work_dir = get_app_data_subdir(app, THUMBNAILS_DIR)?;
+    let config = load_thumbnail_config(app);
+
+    let intermediate = match (mime.type_().as_str(), mime.subtype().as_str()) {
+        ("image", _) => None,
+        ("application", "pdf") => Some(render_pdf_page(&config, source, size, &work_dir)?),
+        ("video", _) => Some(render_video_frame(&config, source, size, &work_dir)?),
+        _ => return Err(format!("No thumbnail available for {} files", mime)),
+    };
+
+    let image = image::open(intermediate.as_deref().unwrap_or(source))
+        .map_err(|e| format!("Failed to decode {}: {}", source.display(), e));
+    if let Some(ref path) = intermediate {
+        let _ = fs::remove_file(path);
+    }
+    write_thumbnail(image?, size, target)
+}
+
+/// Return a cached thumbnail for an attachment, generating it on first request
+pub fn generate_thumbnail(app: &AppHandle, attachment_id: &str, size: u32) -> Result<Thumbnail, String> {
+    let size = size.clamp(MIN_THUMBNAIL_SIZE, MAX_THUMBNAIL_SIZE);
+    let local = resolve_local_attachment(app, attachment_id)?;
+    let target = get_app_data_subdir(app, THUMBNAILS_DIR)?
+        .join(format!("{}_{}.jpg", cache_key(attachment_id, local.as_deref()), size));
+
+    if let Ok(image) = image::open(&target) {
+        let (width, height) = image.dimensions();
+        // Marks the thumbnail as recently used for cache eviction
+        let _ = File::options().append(true).open(&target).and_then(|file| file.set_modified(SystemTime::now()));
+        return Ok(Thumbnail { path: target.to_string_lossy().to_string(), width, height, cached: true });
+    }
+
+    let (width, height) = match local {
+        Some(ref path) => render_thumbnail(app, path, size, &target)?,
+        None if is_remote(attachment_id) => {
+            let temp = download_attachment(app, attachment_id)?;
+            let result = render_thumbnail(app, &temp, size, &target);
+            let _ = fs::remove_file(&temp);
+            result?
+        }
+        None => return Err(format!("Attachment not found: {}", attachment_id)),
+    };
+
+    Ok(Thumbnail { path: target.to_string_lossy().to_string(), width, height, cached: false })
+}
+
+/// `attachment_id` is an imported file hash, a local path, or a server file path like `/api/file/...`
+#[tauri::command]
+pub async fn get_thumbnail(app: AppHandle, attachment_id: String, size: Option<u32>) -> Result<Thumbnail, String> {
+    tauri::async_runtime::spawn_blocking(move || generate_thumbnail(&app, &attachment_id, size.unwrap_or(256)))
+        .await
+        .map_err(|e| format!("Thumbnail generation failed: {}", e))?
+}
+
+#[tauri::command]
+pub fn clear_thumbnail_cache(app: AppHandle) -> Result<(), String> {
+    let dir = get_app_data_subdir(&app, THUMBNAILS_DIR)?;
+    fs::remove_dir_all(&dir).map_err(|e| format!("Failed to clear thumbnail cache: {}", e))
+}
+
+#[tauri::command]
+pub fn get_thumbnail_config(app: AppHandle) -> ThumbnailConfig {
+    load_thumbnail_config(&app)
+}
+
+#[tauri::command]
+pub fn save_thumbnail_config(app: AppHandle, config: ThumbnailConfig) -> Result<(), String> {
+    save_json(&app, THUMBNAIL_CONFIG_FILE, &config)
+}
@@ -0,0 +1,106 @@
+use tauri::{AppHandle, Manager, WebviewWindow};
+use serde::{Deserialize, Serialize};
+
+use crate::desktop::{load_json_or_default, save_json};
+
+const TITLEBAR_CONFIG_FILE: &str = "titlebar_config.json";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct TitlebarConfig {
+    /// Draw the titlebar in the webview instead of using window manager decorations (Linux only)
+    #[serde(rename = "customTitlebar")]
+    pub custom_titlebar: bool,
+}
+
+fn titlebar_window(app: &AppHandle, label: Option<String>) -> Result<WebviewWindow, String> {
+    let label = label.unwrap_or_else(|| "main".to_string());
+    app.get_webview_window(&label)
+        .ok_or_else(|| format!("{} window not found", label))
+}
+
+/// Drop native decorations on Linux when the webview draws its own titlebar
+pub fn apply_titlebar_config(app: &AppHandle) {
+    if !cfg!(target_os = "linux") {
+        return;
+    }
+    let config: TitlebarConfig = load_json_or_default(app, TITLEBAR_CONFIG_FILE);
+    if let Some(window) = app.get_webview_window("main") {
+        if let Err(e) = window.set_decorations(!config.custom_titlebar) {
+            error!("Failed to set main window decorations: {}", e);
+        }
+    }
+}
+
+#[tauri::command]
+pub fn get_titlebar_config(app: AppHandle) -> TitlebarConfig {
+    load_json_or_default(&app, TITLEBAR_CONFIG_FILE)
+}
+
+#[tauri::command]
+pub fn save_titlebar_config(app: AppHandle, config: TitlebarConfig) -> Result<(), String> {
+    save_json(&app, TITLEBAR_CONFIG_FILE, &config)?;
+    apply_titlebar_config(&app);
+    Ok(())
+}
+
+/// Called on mousedown in the titlebar drag region
+#[tauri::command]
+pub fn start_dragging(app: AppHandle, label: Option<String>) -> Result<(), String> {
+    titlebar_window(&app, label)?
+        .start_dragging()
+        .map_err(|e| format!("Failed to start dragging: {}", e))
+}
+
+/// Without decorations the window edges have to be handled by the webview as well.
+/// `direction` is one of n, s, e, w, ne, nw, se, sw.
+#[tauri::command]
+pub fn start_resize_dragging(app: AppHandle, label: Option<String>, direction: String) -> Result<(), String> {
+    use tauri::window::ResizeDirection;
+
+    let direction = match direction.as_str() {
+        "n" => ResizeDirection::North,
+        "s" => ResizeDirection::South,
+        "e" => ResizeDirection::East,
+        "w" => ResizeDirection::West,
+        "ne" => ResizeDirection::NorthEast,
+        "nw" => ResizeDirection::NorthWest,
+        "se" => ResizeDirection::SouthEast,
+        "sw" => ResizeDirection::SouthWest,
+        other => return Err(format!("Invalid resize direction: {}", other)),
+    };
+    titlebar_window(&app, label)?
+        .start_resize_dragging(direction)
+        .map_err(|e| format!("Failed to start resizing: {}", e))
+}
+
+#[tauri::command]
+pub fn minimize(app: AppHandle, label: Option<String>) -> Result<(), String> {
+    titlebar_window(&app, label)?
+        .minimize()
+        .map_err(|e| format!("Failed to minimize window: {}", e))
+}
+
+/// Returns whether the window is maximized afterwards, for the restore/maximize button icon
+#[tauri::command]
+pub fn toggle_maximize(app: AppHandle, label: Option<String>) -> Result<bool, String> {
+    let window = titlebar_window(&app, label)?;
+    let maximized = window.is_maximized().unwrap_or(false);
+    let result = if maximized { window.unmaximize() } else { window.maximize() };
+    result.map_err(|e| format!("Failed to toggle maximize: {}", e))?;
+    Ok(!maximized)
+}
+
+#[tauri::command]
+pub fn is_window_maximized(app: AppHandle, label: Option<String>) -> Result<bool, String> {
+    titlebar_window(&app, label)?
+        .is_maximized()
+        .map_err(|e| format!("Failed to read window state: {}", e))
+}
+
+/// Same as the native close button: the app keeps running in the tray
+#[tauri::command]
+pub fn close_to_tray(app: AppHandle, label: Option<String>) -> Result<(), String> {
+    titlebar_window(&app, label)?
+        .hide()
+        .map_err(|e| format!("Failed to hide window: {}", e))
+}
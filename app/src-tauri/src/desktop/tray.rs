@@ -1,14 +1,59 @@
 use tauri::AppHandle;
+use std::sync::{LazyLock, Mutex};
 
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 use tauri::{
     image::Image,
-    menu::{MenuBuilder, MenuItem, PredefinedMenuItem},
+    menu::{CheckMenuItem, MenuBuilder, MenuItem, PredefinedMenuItem},
     tray::{MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent},
     Manager, Emitter,
 };
 
-use crate::desktop::{toggle_editor_window, toggle_quicknote_window};
+use crate::desktop::{toggle_editor_window, toggle_quicknote_window, load_folder_watch_config, register_folder_watch_menu_item, toggle_folder_watch_paused, load_sync_scheduler_config, register_sync_menu_items, sync_status_label, toggle_sync_paused, SyncSchedulerStatus, is_capture_paused, register_capture_menu_item, toggle_capture_paused};
+
+pub const TRAY_ID: &str = "blinko-tray";
+pub const DEFAULT_TRAY_TOOLTIP: &str = "Blinko - Quick Note";
+
+// Custom tooltip set through `set_tray_status` and the server health line shown below it
+static TRAY_TOOLTIP: LazyLock<Mutex<Option<String>>> = LazyLock::new(|| Mutex::new(None));
+static TRAY_HEALTH_LINE: LazyLock<Mutex<Option<String>>> = LazyLock::new(|| Mutex::new(None));
+
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+fn tray_tooltip() -> String {
+    let tooltip = TRAY_TOOLTIP.lock().unwrap().clone().unwrap_or(DEFAULT_TRAY_TOOLTIP.to_string());
+    match *TRAY_HEALTH_LINE.lock().unwrap() {
+        Some(ref line) => format!("{}\n{}", tooltip, line),
+        None => tooltip,
+    }
+}
+
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+fn apply_tray_tooltip(app: &AppHandle) {
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        if let Err(e) = tray.set_tooltip(Some(tray_tooltip())) {
+            error!("Failed to set tray tooltip: {}", e);
+        }
+    }
+}
+
+/// Update the tray title (shown next to the icon on macOS) and tooltip, `None` restores defaults
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+pub fn set_tray_status(app: &AppHandle, title: Option<&str>, tooltip: Option<&str>) {
+    *TRAY_TOOLTIP.lock().unwrap() = tooltip.map(str::to_string);
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        if let Err(e) = tray.set_title(title) {
+            error!("Failed to set tray title: {}", e);
+        }
+    }
+    apply_tray_tooltip(app);
+}
+
+/// Show the server health line under the tooltip, kept while other features change the tooltip
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+pub fn set_tray_health(app: &AppHandle, line: Option<String>) {
+    *TRAY_HEALTH_LINE.lock().unwrap() = line;
+    apply_tray_tooltip(app);
+}
 
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 pub fn setup_system_tray(app: &AppHandle) -> Result<TrayIcon, Box<dyn std::error::Error>> {
@@ -20,6 +65,15 @@ pub fn setup_system_tray(app: &AppHandle) -> Result<TrayIcon, Box<dyn std::error
     let separator1 = PredefinedMenuItem::separator(app)?;
     let toggle_item = MenuItem::with_id(app, "toggle", "Show/Hide Window", true, None::<&str>)?;
     let settings_item = MenuItem::with_id(app, "settings", "Settings", true, None::<&str>)?;
+    let folder_watch_paused = load_folder_watch_config(app).paused;
+    let pause_folder_watch_item = CheckMenuItem::with_id(app, "pause_folder_watch", "Pause Folder Import", true, folder_watch_paused, None::<&str>)?;
+    register_folder_watch_menu_item(pause_folder_watch_item.clone());
+    let sync_paused = load_sync_scheduler_config(app).paused;
+    let pause_sync_item = CheckMenuItem::with_id(app, "pause_sync", "Pause Sync", true, sync_paused, None::<&str>)?;
+    let sync_status_item = MenuItem::with_id(app, "sync_status", sync_status_label(&SyncSchedulerStatus::default()), false, None::<&str>)?;
+    register_sync_menu_items(pause_sync_item.clone(), sync_status_item.clone());
+    let pause_capture_item = CheckMenuItem::with_id(app, "pause_capture", "Pause Global Capture", true, is_capture_paused(), None::<&str>)?;
+    register_capture_menu_item(pause_capture_item.clone());
     let separator2 = PredefinedMenuItem::separator(app)?;
     let quit_item = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
     
@@ -29,15 +83,19 @@ pub fn setup_system_tray(app: &AppHandle) -> Result<TrayIcon, Box<dyn std::error
             &separator1,
             &toggle_item,
             &settings_item,
+            &pause_folder_watch_item,
+            &pause_sync_item,
+            &sync_status_item,
+            &pause_capture_item,
             &separator2,
             &quit_item,
         ])
         .build()?;
     
-    let tray_icon = TrayIconBuilder::with_id("blinko-tray")
+    let tray_icon = TrayIconBuilder::with_id(TRAY_ID)
         .icon(image)
         .menu(&tray_menu)
-        .tooltip("Blinko - Quick Note")
+        .tooltip(tray_tooltip())
         .on_tray_icon_event(|tray, event| {
             match event {
                 TrayIconEvent::Click {
@@ -67,6 +125,15 @@ pub fn setup_system_tray(app: &AppHandle) -> Result<TrayIcon, Box<dyn std::error
                         let _ = window.emit("navigate-to-settings", ());
                     }
                 }
+                "pause_folder_watch" => {
+                    toggle_folder_watch_paused(app);
+                }
+                "pause_sync" => {
+                    toggle_sync_paused(app);
+                }
+                "pause_capture" => {
+                    toggle_capture_paused(app);
+                }
                 "quit" => {
                     app.exit(0);
                 }
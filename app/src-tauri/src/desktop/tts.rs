@@ -0,0 +1,348 @@
+use tauri::{AppHandle, Emitter};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+
+use pulldown_cmark::{Event, Parser, TagEnd};
+
+use crate::desktop::{background_command, generate_id, update_media_session, MediaSessionState};
+
+/// Utterances are split at sentence ends and spoken one by one so progress can be reported
+const MAX_CHUNK_CHARS: usize = 400;
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TtsProgressEvent {
+    #[serde(rename = "utteranceId")]
+    pub utterance_id: String,
+    /// Zero-based chunk now being spoken
+    pub index: usize,
+    pub total: usize,
+    /// Character range of the chunk in the spoken plain text
+    #[serde(rename = "charStart")]
+    pub char_start: usize,
+    #[serde(rename = "charEnd")]
+    pub char_end: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TtsFinishedEvent {
+    #[serde(rename = "utteranceId")]
+    pub utterance_id: String,
+    /// Stopped by `stop_speaking` or replaced by a newer utterance
+    pub interrupted: bool,
+    pub error: Option<String>,
+}
+
+static SPEAKING_PROCESS: LazyLock<Mutex<Option<Child>>> = LazyLock::new(|| Mutex::new(None));
+// Bumped by every new utterance and by `stop_speaking`, older speaking threads notice and quit
+static UTTERANCE_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Text of rendered markdown, so markup characters aren't read out
+pub fn markdown_to_plain_text(markdown: &str) -> String {
+    let mut text = String::new();
+    for event in Parser::new(markdown) {
+        match event {
+            Event::Text(t) | Event::Code(t) => text.push_str(&t),
+            Event::SoftBreak | Event::HardBreak => text.push(' '),
+            Event::End(TagEnd::Paragraph | TagEnd::Heading(_) | TagEnd::Item | TagEnd::CodeBlock | TagEnd::TableRow) => {
+                text.push('\n')
+            }
+            _ => {}
+        }
+    }
+    text.trim().to_string()
+}
+
+/// Sentence-aligned chunks as (start, end) char ranges
+fn split_chunks(text: &str) -> Vec<(usize, usize)> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut chunks = Vec::new();
+    let (mut start, mut last_break) = (0, None);
+    for (i, c) in chars.iter().enumerate() {
+        if matches!(c, '.' | '!' | '?' | '\n' | '。' | '！' | '？') {
+            last_break = Some(i + 1);
+        }
+        if i + 1 - start >= MAX_CHUNK_CHARS {
+            let end = last_break.filter(|end| *end > start).unwrap_or(i + 1);
+            chunks.push((start, end));
+            start = end;
+        } else if *c == '\n' {
+            chunks.push((start, i + 1));
+            start = i + 1;
+        }
+    }
+    if start < chars.len() {
+        chunks.push((start, chars.len()));
+    }
+    chunks.retain(|(start, end)| chars[*start..*end].iter().any(|c| c.is_alphanumeric()));
+    chunks
+}
+
+/// SAPI rates run from -10 to 10 where 10 is about three times normal speed
+#[cfg(target_os = "windows")]
+fn speak_command(voice: Option<&str>, rate: f32) -> Command {
+    let sapi_rate = (10.0 * rate.ln() / 3f32.ln()).round().clamp(-10.0, 10.0) as i32;
+    let select_voice = voice
+        .map(|v| format!("$s.SelectVoice('{}');", v.replace('\'', "''")))
+        .unwrap_or_default();
+    let mut command = background_command("powershell");
+    command.args([
+        "-NoProfile",
+        "-Command",
+        &format!(
+            "Add-Type -AssemblyName System.Speech; $s = New-Object System.Speech.Synthesis.SpeechSynthesizer; \
+             $s.Rate = {}; {} [Console]::InputEncoding = [Text.Encoding]::UTF8; $s.Speak([Console]::In.ReadToEnd())",
+            sapi_rate, select_voice,
+        ),
+    ]);
+    command
+}
+
+#[cfg(target_os = "macos")]
+fn speak_command(voice: Option<&str>, rate: f32) -> Command {
+    let mut command = background_command("say");
+    command.args(["-r", &((175.0 * rate).round() as u32).to_string(), "-f", "-"]);
+    if let Some(voice) = voice {
+        command.args(["-v", voice]);
+    }
+    command
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn speak_command(voice: Option<&str>, rate: f32) -> Command {
+    let program = if which_espeak_ng() { "espeak-ng" } else { "espeak" };
+    let mut command = background_command(program);
+    command.args(["-s", &((175.0 * rate).round() as u32).to_string(), "--stdin"]);
+    if let Some(voice) = voice {
+        command.args(["-v", voice]);
+    }
+    command
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn which_espeak_ng() -> bool {
+    background_command("espeak-ng").arg("--version").stdout(Stdio::null()).stderr(Stdio::null()).status().is_ok()
+}
+
+/// Installed voice names as accepted by `speak_text`
+fn installed_voices() -> Vec<String> {
+    #[cfg(target_os = "windows")]
+    let output = background_command("powershell").args([
+        "-NoProfile",
+        "-Command",
+        "Add-Type -AssemblyName System.Speech; (New-Object System.Speech.Synthesis.SpeechSynthesizer).GetInstalledVoices() | ForEach-Object { $_.VoiceInfo.Name }",
+    ]).output();
+    #[cfg(target_os = "macos")]
+    let output = background_command("say").args(["-v", "?"]).output();
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let output = background_command(if which_espeak_ng() { "espeak-ng" } else { "espeak" }).arg("--voices").output();
+
+    let Ok(output) = output else { return Vec::new() };
+    let text = String::from_utf8_lossy(&output.stdout);
+    text.lines()
+        .filter_map(|line| {
+            // `say -v ?` prints "Name   en_US  # sample", espeak a table whose 4th column is the voice name
+            #[cfg(target_os = "macos")]
+            return line.split("  ").next().map(str::trim).filter(|name| !name.is_empty()).map(str::to_string);
+            #[cfg(target_os = "windows")]
+            return Some(line.trim().to_string()).filter(|name| !name.is_empty());
+            #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+            return line.split_whitespace().nth(3).filter(|name| *name != "VoiceName").map(str::to_string);
+        })
+        .collect()
+}
+
+/// Speak one chunk, returning false when the utterance was cancelled meanwhile
+fn speak_chunk(text: &str, voice: Option<&str>, rate: f32, generation: u64) -> Result<bool, String> {
+    let mut child = speak_command(voice, rate)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to start speech synthesis: {}", e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(text.as_bytes());
+    }
+    *SPEAKING_PROCESS.lock().unwrap() = Some(child);
+
+    loop {
+        if UTTERANCE_GENERATION.load(Ordering::SeqCst) != generation {
+            return Ok(false);
+        }
+        let mut guard = SPEAKING_PROCESS.lock().unwrap();
+        let Some(child) = guard.as_mut() else { return Ok(false) };
+        match child.try_wait() {
+            Ok(Some(status)) => {
+                guard.take();
+                return if status.success() { Ok(true) } else { Err(format!("Speech synthesis exited with {}", status)) };
+            }
+            Ok(None) => {}
+            Err(e) => return Err(format!("Speech synthesis failed: {}", e)),
+        }
+        drop(guard);
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn kill_speaking_process() {
+    if let Some(mut child) = SPEAKING_PROCESS.lock().unwrap().take() {
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Utterance {
+    id: String,
+    text: String,
+    chunks: Vec<(usize, usize)>,
+    voice: Option<String>,
+    rate: f32,
+    /// Chunk to speak next, kept up to date so a pause can resume from it
+    index: usize,
+}
+
+impl Utterance {
+    /// Short label for the system media overlay
+    fn title(&self) -> String {
+        let title: String = self.text.lines().next().unwrap_or_default().chars().take(60).collect();
+        if title.is_empty() { "Blinko".to_string() } else { title }
+    }
+}
+
+static CURRENT_UTTERANCE: LazyLock<Mutex<Option<Utterance>>> = LazyLock::new(|| Mutex::new(None));
+static PAUSED_UTTERANCE: LazyLock<Mutex<Option<Utterance>>> = LazyLock::new(|| Mutex::new(None));
+
+fn emit_tts_finished(app: &AppHandle, utterance_id: String, result: Result<bool, String>) {
+    if let Err(ref e) = result {
+        error!("❌ {}", e);
+    }
+    let _ = app.emit("tts-finished", TtsFinishedEvent {
+        utterance_id,
+        interrupted: matches!(result, Ok(false)),
+        error: result.err(),
+    });
+}
+
+/// Speak the utterance from its current chunk on a background thread
+fn run_utterance(app: AppHandle, utterance: Utterance) {
+    let generation = UTTERANCE_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    kill_speaking_process();
+    update_media_session(MediaSessionState::Playing(utterance.title()));
+    *CURRENT_UTTERANCE.lock().unwrap() = Some(utterance.clone());
+
+    std::thread::spawn(move || {
+        let chars: Vec<char> = utterance.text.chars().collect();
+        let id = utterance.id.clone();
+        let _ = app.emit(if utterance.index == 0 { "tts-started" } else { "tts-resumed" }, &id);
+
+        let mut result = Ok(true);
+        for (index, (start, end)) in utterance.chunks.iter().enumerate().skip(utterance.index) {
+            if let Some(current) = CURRENT_UTTERANCE.lock().unwrap().as_mut().filter(|u| u.id == id) {
+                current.index = index;
+            }
+            let _ = app.emit("tts-progress", TtsProgressEvent {
+                utterance_id: id.clone(),
+                index,
+                total: utterance.chunks.len(),
+                char_start: *start,
+                char_end: *end,
+            });
+            let chunk: String = chars[*start..*end].iter().collect();
+            result = speak_chunk(&chunk, utterance.voice.as_deref(), utterance.rate, generation);
+            if !matches!(result, Ok(true)) {
+                break;
+            }
+        }
+
+        let mut current = CURRENT_UTTERANCE.lock().unwrap();
+        // A paused utterance finishes later, when resumed or stopped
+        if PAUSED_UTTERANCE.lock().unwrap().as_ref().is_some_and(|u| u.id == id) {
+            drop(current);
+            let _ = app.emit("tts-paused", &id);
+            return;
+        }
+        if current.as_ref().is_some_and(|u| u.id == id) {
+            current.take();
+            update_media_session(MediaSessionState::Stopped);
+        }
+        drop(current);
+        emit_tts_finished(&app, id, result);
+    });
+}
+
+pub fn is_speaking() -> bool {
+    CURRENT_UTTERANCE.lock().unwrap().is_some()
+}
+
+pub fn is_speech_paused() -> bool {
+    PAUSED_UTTERANCE.lock().unwrap().is_some()
+}
+
+/// Read text aloud with a system voice, replacing anything being spoken.
+/// `rate` is relative to normal speed (1.0). Emits `tts-started`, `tts-progress` per sentence and `tts-finished`.
+#[tauri::command]
+pub fn speak_text(app: AppHandle, text: String, voice: Option<String>, rate: Option<f32>) -> Result<String, String> {
+    let text = markdown_to_plain_text(&text);
+    let chunks = split_chunks(&text);
+    if chunks.is_empty() {
+        return Err("Nothing to read aloud".to_string());
+    }
+
+    if let Some(paused) = PAUSED_UTTERANCE.lock().unwrap().take() {
+        emit_tts_finished(&app, paused.id, Ok(false));
+    }
+    let utterance = Utterance {
+        id: generate_id(),
+        text,
+        chunks,
+        voice: voice.filter(|v| !v.trim().is_empty()),
+        rate: rate.unwrap_or(1.0).clamp(0.3, 3.0),
+        index: 0,
+    };
+    let utterance_id = utterance.id.clone();
+    run_utterance(app, utterance);
+    Ok(utterance_id)
+}
+
+/// Pause at the current sentence, emits `tts-paused`. Returns false when nothing is being spoken.
+#[tauri::command]
+pub fn pause_speaking() -> bool {
+    let mut current = CURRENT_UTTERANCE.lock().unwrap();
+    let Some(utterance) = current.take() else { return false };
+    update_media_session(MediaSessionState::Paused(utterance.title()));
+    *PAUSED_UTTERANCE.lock().unwrap() = Some(utterance);
+    drop(current);
+    UTTERANCE_GENERATION.fetch_add(1, Ordering::SeqCst);
+    kill_speaking_process();
+    true
+}
+
+/// Continue a paused utterance from the sentence it was paused in, emits `tts-resumed`
+#[tauri::command]
+pub fn resume_speaking(app: AppHandle) -> bool {
+    let Some(utterance) = PAUSED_UTTERANCE.lock().unwrap().take() else { return false };
+    run_utterance(app, utterance);
+    true
+}
+
+#[tauri::command]
+pub fn stop_speaking(app: AppHandle) {
+    UTTERANCE_GENERATION.fetch_add(1, Ordering::SeqCst);
+    kill_speaking_process();
+    if let Some(paused) = PAUSED_UTTERANCE.lock().unwrap().take() {
+        emit_tts_finished(&app, paused.id, Ok(false));
+    }
+    update_media_session(MediaSessionState::Stopped);
+}
+
+#[tauri::command]
+pub async fn list_tts_voices() -> Result<Vec<String>, String> {
+    tauri::async_runtime::spawn_blocking(installed_voices)
+        .await
+        .map_err(|e| format!("Failed to list voices: {}", e))
+}
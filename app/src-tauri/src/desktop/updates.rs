@@ -0,0 +1,223 @@
+use tauri::{AppHandle, Emitter, Url};
+use serde::{Deserialize, Serialize};
+use std::sync::{LazyLock, Mutex};
+
+use tauri_plugin_updater::{Update, UpdaterExt};
+
+use crate::desktop::{current_proxy_url, load_json_or_default, save_json};
+
+const UPDATE_CONFIG_FILE: &str = "update_config.json";
+const RELEASES_URL: &str = "https://github.com/blinkospace/blinko/releases";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateChannel {
+    #[default]
+    Stable,
+    Beta,
+    Nightly,
+}
+
+impl UpdateChannel {
+    /// Each channel publishes its own `latest.json` manifest
+    fn endpoint(&self) -> String {
+        match self {
+            UpdateChannel::Stable => format!("{}/latest/download/latest.json", RELEASES_URL),
+            UpdateChannel::Beta => format!("{}/download/beta/latest.json", RELEASES_URL),
+            UpdateChannel::Nightly => format!("{}/download/nightly/latest.json", RELEASES_URL),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct UpdateConfig {
+    #[serde(default)]
+    pub channel: UpdateChannel,
+    /// Install a downloaded update when the app quits instead of asking to restart
+    #[serde(rename = "installOnQuit", default)]
+    pub install_on_quit: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UpdateInfo {
+    pub version: String,
+    #[serde(rename = "currentVersion")]
+    pub current_version: String,
+    pub channel: UpdateChannel,
+    pub date: Option<String>,
+    /// Release notes
+    pub body: Option<String>,
+    pub downloaded: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UpdateProgress {
+    pub version: String,
+    pub downloaded: u64,
+    pub total: Option<u64>,
+}
+
+#[derive(Default)]
+struct UpdateState {
+    /// Result of the last check
+    available: Option<Update>,
+    /// Installer bytes for `available`, once downloaded
+    package: Option<Vec<u8>>,
+    downloading: bool,
+}
+
+static UPDATE_STATE: LazyLock<Mutex<UpdateState>> = LazyLock::new(|| Mutex::new(UpdateState::default()));
+
+fn load_update_config(app: &AppHandle) -> UpdateConfig {
+    load_json_or_default(app, UPDATE_CONFIG_FILE)
+}
+
+fn update_info(update: &Update, channel: UpdateChannel, downloaded: bool) -> UpdateInfo {
+    UpdateInfo {
+        version: update.version.clone(),
+        current_version: update.current_version.clone(),
+        channel,
+        date: update.date.map(|d| d.to_string()),
+        body: update.body.clone(),
+        downloaded,
+    }
+}
+
+#[tauri::command]
+pub fn get_update_config(app: AppHandle) -> UpdateConfig {
+    load_update_config(&app)
+}
+
+/// Switch channels; a pending update from the previous channel is discarded
+#[tauri::command]
+pub fn set_update_channel(app: AppHandle, channel: UpdateChannel) -> Result<(), String> {
+    let mut config = load_update_config(&app);
+    if config.channel != channel {
+        *UPDATE_STATE.lock().unwrap() = UpdateState::default();
+    }
+    config.channel = channel;
+    save_json(&app, UPDATE_CONFIG_FILE, &config)?;
+    info!("🔄 Update channel set to {:?}", channel);
+    Ok(())
+}
+
+/// Check the selected channel now, returning the available update if any
+#[tauri::command]
+pub async fn check_for_updates_now(app: AppHandle) -> Result<Option<UpdateInfo>, String> {
+    let config = load_update_config(&app);
+    let endpoint = Url::parse(&config.channel.endpoint())
+        .map_err(|e| format!("Invalid update endpoint: {}", e))?;
+
+    let mut builder = app.updater_builder()
+        .endpoints(vec![endpoint])
+        .map_err(|e| format!("Failed to configure updater: {}", e))?;
+    if let Some(proxy) = current_proxy_url().and_then(|url| Url::parse(&url).ok()) {
+        builder = builder.proxy(proxy);
+    }
+    // Moving from a pre-release channel back to stable should offer the stable build even if it is older
+    if config.channel == UpdateChannel::Stable {
+        builder = builder.version_comparator(|current, remote| remote.version != current);
+    }
+
+    let updater = builder.build().map_err(|e| format!("Failed to create updater: {}", e))?;
+    let update = updater.check().await.map_err(|e| format!("Update check failed: {}", e))?;
+
+    let mut state = UPDATE_STATE.lock().unwrap();
+    let already_downloaded = state.package.is_some()
+        && state.available.as_ref().map(|u| &u.version) == update.as_ref().map(|u| &u.version);
+    if !already_downloaded {
+        state.package = None;
+    }
+    state.available = update.clone();
+
+    match update {
+        Some(update) => {
+            info!("🔄 Update {} available on {:?} channel", update.version, config.channel);
+            Ok(Some(update_info(&update, config.channel, already_downloaded)))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Download the update found by the last check, emitting `update-download-progress`
+#[tauri::command]
+pub async fn download_update(app: AppHandle) -> Result<UpdateInfo, String> {
+    let update = {
+        let mut state = UPDATE_STATE.lock().unwrap();
+        if state.downloading {
+            return Err("An update is already downloading".to_string());
+        }
+        let update = state.available.clone().ok_or("No update available, check for updates first")?;
+        if state.package.is_some() {
+            return Ok(update_info(&update, load_update_config(&app).channel, true));
+        }
+        state.downloading = true;
+        update
+    };
+
+    let version = update.version.clone();
+    let mut downloaded: u64 = 0;
+    let progress_app = app.clone();
+    let result = update.download(
+        |chunk, total| {
+            downloaded += chunk as u64;
+            let _ = progress_app.emit("update-download-progress", UpdateProgress {
+                version: version.clone(),
+                downloaded,
+                total,
+            });
+        },
+        || {},
+    ).await;
+
+    let mut state = UPDATE_STATE.lock().unwrap();
+    state.downloading = false;
+    let package = result.map_err(|e| {
+        let message = format!("Update download failed: {}", e);
+        let _ = app.emit("update-download-failed", &message);
+        message
+    })?;
+    // The channel may have changed while downloading
+    if state.available.as_ref().map(|u| &u.version) != Some(&update.version) {
+        return Err("Update was discarded while downloading".to_string());
+    }
+    state.package = Some(package);
+    drop(state);
+
+    info!("📦 Update {} downloaded", update.version);
+    let info = update_info(&update, load_update_config(&app).channel, true);
+    let _ = app.emit("update-downloaded", &info);
+    Ok(info)
+}
+
+/// Choose whether a downloaded update is installed when the app quits
+#[tauri::command]
+pub fn install_update_on_quit(app: AppHandle, enabled: Option<bool>) -> Result<(), String> {
+    let mut config = load_update_config(&app);
+    config.install_on_quit = enabled.unwrap_or(true);
+    save_json(&app, UPDATE_CONFIG_FILE, &config)
+}
+
+/// Install the downloaded update right away; the app has to be restarted afterwards
+#[tauri::command]
+pub fn install_update_now() -> Result<(), String> {
+    let mut state = UPDATE_STATE.lock().unwrap();
+    let (Some(update), Some(package)) = (state.available.take(), state.package.take()) else {
+        return Err("No downloaded update to install".to_string());
+    };
+    update.install(package).map_err(|e| format!("Failed to install update: {}", e))
+}
+
+/// Called from the exit event, installs a downloaded update when install on quit is enabled
+pub fn install_pending_update_on_exit(app: &AppHandle) {
+    if !load_update_config(app).install_on_quit {
+        return;
+    }
+    let mut state = UPDATE_STATE.lock().unwrap();
+    if let (Some(update), Some(package)) = (state.available.take(), state.package.take()) {
+        info!("📦 Installing update {} on quit", update.version);
+        if let Err(e) = update.install(package) {
+            error!("❌ Failed to install update on quit: {}", e);
+        }
+    }
+}
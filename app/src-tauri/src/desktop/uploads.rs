@@ -0,0 +1,454 @@
+use tauri::{AppHandle, Emitter};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+
+use reqwest::blocking::multipart::{Form, Part};
+
+use crate::desktop::{
+    generate_id, get_configured_server_url, load_json_or_default, now_millis, load_s3_config, prepare_image_for_upload,
+    put_presigned_file, retry_delay, s3_object_key, save_json, send_with_retry, ImageProcessingOverrides, RetryPolicy,
+    S3Client,
+};
+
+const UPLOADS_FILE: &str = "uploads.json";
+const CHUNK_SIZE: u64 = 5 * 1024 * 1024;
+const CHUNK_RETRIES: u32 = 4;
+const CHUNK_TIMEOUT: Duration = Duration::from_secs(120);
+const CHUNK_UPLOAD_PATH: &str = "/api/file/upload-chunk";
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum UploadStatus {
+    Queued,
+    Uploading,
+    Paused,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+/// Where an upload goes, the frontend picks S3 when the Blinko server reports S3 storage
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum UploadTarget {
+    /// Chunk endpoint of the Blinko server
+    #[default]
+    Server,
+    /// Multipart upload with the S3 credentials from the storage settings, the key is generated when empty
+    S3 { key: Option<String> },
+    /// Single PUT to a URL presigned by the Blinko server
+    Presigned { url: String },
+}
+
+/// Persisted manifest entry, lets an upload continue after a restart
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UploadItem {
+    pub id: String,
+    /// Id sent to the server, renewed when the file changes so old chunks are not mixed in
+    #[serde(rename = "uploadId")]
+    pub upload_id: String,
+    pub path: String,
+    #[serde(rename = "fileName")]
+    pub file_name: String,
+    /// Chunk endpoint, receives `uploadId`, `chunkIndex`, `totalChunks`, `fileName` and `file` as multipart fields
+    pub endpoint: String,
+    pub size: u64,
+    /// Modification time when queued, a changed file restarts from the first chunk
+    #[serde(rename = "modifiedAt")]
+    pub modified_at: u64,
+    #[serde(rename = "chunkSize")]
+    pub chunk_size: u64,
+    #[serde(rename = "totalChunks")]
+    pub total_chunks: u32,
+    /// Chunks the server has acknowledged, uploaded in order
+    #[serde(rename = "uploadedChunks")]
+    pub uploaded_chunks: u32,
+    pub status: UploadStatus,
+    pub error: Option<String>,
+    /// Server response to the last chunk, usually the stored file info
+    pub result: Option<Value>,
+    #[serde(rename = "createdAt")]
+    pub created_at: u64,
+    #[serde(default)]
+    pub target: UploadTarget,
+    /// S3 multipart upload id, started with the first part
+    #[serde(rename = "s3UploadId", default)]
+    pub s3_upload_id: Option<String>,
+    /// ETags of the S3 parts uploaded so far, in part order
+    #[serde(rename = "s3Parts", default)]
+    pub s3_parts: Vec<String>,
+}
+
+static UPLOADS: LazyLock<Mutex<Option<Vec<UploadItem>>>> = LazyLock::new(|| Mutex::new(None));
+static WORKER_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Run a closure against the upload manifest, loading it from disk on first use
+fn with_uploads<T>(app: &AppHandle, f: impl FnOnce(&mut Vec<UploadItem>) -> T) -> T {
+    let mut guard = UPLOADS.lock().unwrap();
+    if guard.is_none() {
+        *guard = Some(load_json_or_default(app, UPLOADS_FILE));
+    }
+    f(guard.as_mut().unwrap())
+}
+
+fn persist_uploads(app: &AppHandle) {
+    let items = with_uploads(app, |items| items.clone());
+    if let Err(e) = save_json(app, UPLOADS_FILE, &items) {
+        error!("Failed to save upload manifest: {}", e);
+    }
+}
+
+fn update_upload(app: &AppHandle, id: &str, f: impl FnOnce(&mut UploadItem)) -> Option<UploadItem> {
+    with_uploads(app, |items| {
+        items.iter_mut().find(|item| item.id == id).map(|item| {
+            f(item);
+            item.clone()
+        })
+    })
+}
+
+fn emit_upload(app: &AppHandle, event: &str, item: &UploadItem) {
+    if let Err(e) = app.emit(event, item) {
+        error!("Failed to emit {} event: {}", event, e);
+    }
+}
+
+fn modified_millis(path: &Path) -> u64 {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn read_chunk(path: &Path, index: u32, chunk_size: u64) -> Result<Vec<u8>, String> {
+    let mut file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    file.seek(SeekFrom::Start(index as u64 * chunk_size))
+        .map_err(|e| format!("Failed to seek {}: {}", path.display(), e))?;
+    let mut buffer = Vec::with_capacity(chunk_size as usize);
+    file.take(chunk_size).read_to_end(&mut buffer)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    Ok(buffer)
+}
+
+fn send_chunk(app: &AppHandle, item: &UploadItem, index: u32, data: Vec<u8>) -> Result<Value, String> {
+    // Chunks are retried by the upload loop so a pause is noticed between attempts
+    let policy = RetryPolicy { max_attempts: 1, idempotent: true };
+    let response = send_with_retry(app, CHUNK_TIMEOUT, policy, |client| {
+        let form = Form::new()
+            .text("uploadId", item.upload_id.clone())
+            .text("chunkIndex", index.to_string())
+            .text("totalChunks", item.total_chunks.to_string())
+            .text("fileName", item.file_name.clone())
+            .part("file", Part::bytes(data.clone()).file_name(item.file_name.clone()));
+        client.post(&item.endpoint).multipart(form)
+    })
+    .map_err(|e| format!("Chunk {}: {}", index, e))?;
+    Ok(response.json::<Value>().unwrap_or(Value::Null))
+}
+
+/// Upload chunk `index` as S3 part `index + 1`, completing the object after the last one
+fn send_s3_part(app: &AppHandle, item: &UploadItem, key: &str, index: u32, data: Vec<u8>) -> Result<Value, String> {
+    let client = S3Client::new(app)?;
+    let upload_id = match update_upload(app, &item.id, |_| {}).and_then(|u| u.s3_upload_id) {
+        Some(upload_id) => upload_id,
+        None => {
+            let upload_id = client.create_multipart_upload(key)?;
+            update_upload(app, &item.id, |u| u.s3_upload_id = Some(upload_id.clone()));
+            persist_uploads(app);
+            upload_id
+        }
+    };
+
+    let etag = client.upload_part(key, &upload_id, index + 1, data)?;
+    let parts = update_upload(app, &item.id, |u| {
+        u.s3_parts.truncate(index as usize);
+        u.s3_parts.push(etag);
+    })
+    .map(|u| u.s3_parts)
+    .unwrap_or_default();
+
+    if index + 1 < item.total_chunks {
+        return Ok(json!({ "part": index + 1 }));
+    }
+    client.complete_multipart_upload(key, &upload_id, &parts)?;
+    Ok(json!({ "storage": "s3", "key": key, "url": client.object_url(key), "size": item.size }))
+}
+
+fn send_item_chunk(app: &AppHandle, item: &UploadItem, path: &Path, index: u32) -> Result<Value, String> {
+    match item.target {
+        UploadTarget::Server => send_chunk(app, item, index, read_chunk(path, index, item.chunk_size)?),
+        UploadTarget::S3 { ref key } => {
+            let key = key.as_deref().ok_or("S3 object key is missing")?;
+            send_s3_part(app, item, key, index, read_chunk(path, index, item.chunk_size)?)
+        }
+        UploadTarget::Presigned { ref url } => {
+            let content_type = mime_guess::from_path(&item.file_name).first_or_octet_stream();
+            put_presigned_file(url, path, content_type.as_ref())?;
+            Ok(json!({ "storage": "s3", "url": url.split('?').next().unwrap_or_default(), "size": item.size }))
+        }
+    }
+}
+
+fn chunk_size_for(target: &UploadTarget, size: u64) -> u64 {
+    match target {
+        // Presigned URLs take the whole file in one request
+        UploadTarget::Presigned { .. } => size.max(1),
+        _ => CHUNK_SIZE,
+    }
+}
+
+/// Upload the remaining chunks of one item, retrying each chunk with backoff
+fn run_upload(app: &AppHandle, id: &str) -> Result<(), String> {
+    let Some(mut item) = update_upload(app, id, |_| {}) else { return Ok(()) };
+    let file_path = PathBuf::from(&item.path);
+    let path = file_path.as_path();
+
+    let size = fs::metadata(path).map(|m| m.len())
+        .map_err(|e| format!("File is no longer available: {}", e))?;
+    if size != item.size || modified_millis(path) != item.modified_at {
+        info!("⬆️ {} changed since it was queued, restarting upload", item.file_name);
+        if let (UploadTarget::S3 { key: Some(ref key) }, Some(ref upload_id)) = (&item.target, &item.s3_upload_id) {
+            if let Err(e) = S3Client::new(app).and_then(|client| client.abort_multipart_upload(key, upload_id)) {
+                warn!("⚠️ Failed to abort old S3 upload of {}: {}", item.file_name, e);
+            }
+        }
+        item = update_upload(app, id, |u| {
+            u.size = size;
+            u.modified_at = modified_millis(path);
+            u.chunk_size = chunk_size_for(&u.target, size);
+            u.total_chunks = size.div_ceil(u.chunk_size).max(1) as u32;
+            u.uploaded_chunks = 0;
+            u.upload_id = generate_id();
+            u.s3_upload_id = None;
+            u.s3_parts.clear();
+        })
+        .ok_or("Upload disappeared")?;
+    }
+
+    for index in item.uploaded_chunks..item.total_chunks {
+        let mut attempt = 0;
+        let response = loop {
+            match update_upload(app, id, |_| {}).map(|u| u.status) {
+                Some(UploadStatus::Uploading) => {}
+                // Paused or cancelled while waiting
+                _ => return Ok(()),
+            }
+
+            match send_item_chunk(app, &item, path, index) {
+                Ok(response) => break response,
+                Err(e) if attempt < CHUNK_RETRIES => {
+                    attempt += 1;
+                    let delay = retry_delay(attempt);
+                    warn!("⚠️ Chunk {} of {} failed ({}), retrying in {:?}", index, item.file_name, e, delay);
+                    std::thread::sleep(delay);
+                }
+                Err(e) => return Err(e),
+            }
+        };
+
+        if let Some(updated) = update_upload(app, id, |u| {
+            u.uploaded_chunks = index + 1;
+            u.result = Some(response);
+        }) {
+            emit_upload(app, "upload-progress", &updated);
+        }
+        // Persist after every chunk so a restart continues from here
+        persist_uploads(app);
+    }
+
+    update_upload(app, id, |u| u.status = UploadStatus::Completed);
+    Ok(())
+}
+
+/// Process queued uploads one at a time on a background thread
+fn start_upload_worker(app: &AppHandle) {
+    if WORKER_RUNNING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let app_handle = app.clone();
+    std::thread::spawn(move || {
+        loop {
+            let next = with_uploads(&app_handle, |items| {
+                items.iter_mut()
+                    .find(|item| item.status == UploadStatus::Queued)
+                    .map(|item| {
+                        item.status = UploadStatus::Uploading;
+                        item.error = None;
+                        item.id.clone()
+                    })
+            });
+            let Some(id) = next else { break };
+
+            let result = run_upload(&app_handle, &id);
+            let finished = update_upload(&app_handle, &id, |item| {
+                if let Err(ref e) = result {
+                    item.status = UploadStatus::Failed;
+                    item.error = Some(e.clone());
+                }
+            });
+
+            if let Some(finished) = finished {
+                match finished.status {
+                    UploadStatus::Completed => {
+                        info!("✅ Uploaded {}", finished.file_name);
+                        emit_upload(&app_handle, "upload-completed", &finished);
+                    }
+                    UploadStatus::Failed => {
+                        error!("❌ Upload of {} failed: {}", finished.file_name, finished.error.as_deref().unwrap_or_default());
+                        emit_upload(&app_handle, "upload-failed", &finished);
+                    }
+                    _ => {}
+                }
+            }
+            persist_uploads(&app_handle);
+        }
+        WORKER_RUNNING.store(false, Ordering::SeqCst);
+    });
+}
+
+/// Continue uploads that were running when the app quit
+pub fn resume_pending_uploads(app: &AppHandle) {
+    let resumed = with_uploads(app, |items| {
+        items.iter_mut()
+            .filter(|item| item.status == UploadStatus::Uploading)
+            .map(|item| item.status = UploadStatus::Queued)
+            .count()
+    });
+    if resumed > 0 {
+        info!("⬆️ Resuming {} interrupted upload(s)", resumed);
+    }
+    start_upload_worker(app);
+}
+
+/// Queue a file for chunked upload, defaults to the chunk endpoint of the configured server.
+/// Images are scrubbed and downscaled first according to the image processing settings.
+#[tauri::command]
+pub fn enqueue_upload(
+    app: AppHandle,
+    path: String,
+    endpoint: Option<String>,
+    image_options: Option<ImageProcessingOverrides>,
+    target: Option<UploadTarget>,
+) -> Result<UploadItem, String> {
+    let original_path = Path::new(&path);
+    let metadata = fs::metadata(original_path)
+        .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    if !metadata.is_file() {
+        return Err(format!("Not a file: {}", path));
+    }
+    let file_name = original_path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let upload_path = prepare_image_for_upload(&app, original_path, image_options.as_ref());
+    let file_path = upload_path.as_path();
+    let metadata = fs::metadata(file_path)
+        .map_err(|e| format!("Failed to read {}: {}", file_path.display(), e))?;
+
+    let endpoint = match endpoint {
+        Some(endpoint) => endpoint,
+        None => format!(
+            "{}{}",
+            get_configured_server_url().ok_or("Server is not configured")?.trim_end_matches('/'),
+            CHUNK_UPLOAD_PATH
+        ),
+    };
+
+    let target = match target.unwrap_or_default() {
+        UploadTarget::S3 { key: None } => UploadTarget::S3 { key: Some(s3_object_key(&load_s3_config(&app), &file_name)) },
+        target => target,
+    };
+
+    let size = metadata.len();
+    let chunk_size = chunk_size_for(&target, size);
+    let item = UploadItem {
+        id: generate_id(),
+        upload_id: generate_id(),
+        file_name,
+        path: file_path.to_string_lossy().to_string(),
+        endpoint,
+        size,
+        modified_at: modified_millis(file_path),
+        chunk_size,
+        total_chunks: size.div_ceil(chunk_size).max(1) as u32,
+        uploaded_chunks: 0,
+        status: UploadStatus::Queued,
+        error: None,
+        result: None,
+        created_at: now_millis(),
+        target,
+        s3_upload_id: None,
+        s3_parts: Vec::new(),
+    };
+
+    with_uploads(&app, |items| items.push(item.clone()));
+    persist_uploads(&app);
+    start_upload_worker(&app);
+    Ok(item)
+}
+
+#[tauri::command]
+pub fn list_uploads(app: AppHandle) -> Vec<UploadItem> {
+    with_uploads(&app, |items| items.clone())
+}
+
+#[tauri::command]
+pub fn pause_upload(app: AppHandle, id: String) -> Result<(), String> {
+    update_upload(&app, &id, |u| {
+        if matches!(u.status, UploadStatus::Queued | UploadStatus::Uploading) {
+            u.status = UploadStatus::Paused;
+        }
+    })
+    .ok_or_else(|| format!("Upload not found: {}", id))?;
+    persist_uploads(&app);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn resume_upload(app: AppHandle, id: String) -> Result<(), String> {
+    update_upload(&app, &id, |u| {
+        if matches!(u.status, UploadStatus::Paused | UploadStatus::Failed) {
+            u.status = UploadStatus::Queued;
+        }
+    })
+    .ok_or_else(|| format!("Upload not found: {}", id))?;
+    persist_uploads(&app);
+    start_upload_worker(&app);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn cancel_upload(app: AppHandle, id: String) -> Result<(), String> {
+    let item = update_upload(&app, &id, |u| {
+        if u.status != UploadStatus::Completed {
+            u.status = UploadStatus::Cancelled;
+        }
+    })
+    .ok_or_else(|| format!("Upload not found: {}", id))?;
+    persist_uploads(&app);
+
+    // Uploaded S3 parts are stored (and billed) until the multipart upload is aborted
+    if let (UploadStatus::Cancelled, UploadTarget::S3 { key: Some(key) }, Some(upload_id)) = (item.status, item.target, item.s3_upload_id) {
+        std::thread::spawn(move || {
+            if let Err(e) = S3Client::new(&app).and_then(|client| client.abort_multipart_upload(&key, &upload_id)) {
+                warn!("⚠️ Failed to abort S3 upload: {}", e);
+            }
+        });
+    }
+    Ok(())
+}
+
+/// Remove completed, failed and cancelled entries from the manifest
+#[tauri::command]
+pub fn clear_finished_uploads(app: AppHandle) {
+    with_uploads(&app, |items| {
+        items.retain(|item| matches!(item.status, UploadStatus::Queued | UploadStatus::Uploading | UploadStatus::Paused));
+    });
+    persist_uploads(&app);
+}
@@ -0,0 +1,257 @@
+use tauri::AppHandle;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::desktop::{generate_id, http_client_builder, is_retryable_status, load_json_or_default, now_millis, retry_delay, save_json};
+
+const WEBHOOKS_FILE: &str = "webhooks.json";
+const WEBHOOK_LOG_FILE: &str = "webhook_log.json";
+const MAX_LOG_ENTRIES: usize = 200;
+const MAX_ATTEMPTS: u32 = 5;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Events that can trigger webhooks
+pub const WEBHOOK_EVENTS: &[&str] = &["note.captured", "transcription.completed", "reminder.fired"];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Webhook {
+    pub id: String,
+    pub url: String,
+    /// Subscribed event names, empty means all events
+    pub events: Vec<String>,
+    /// Used to sign the body as `X-Blinko-Signature: sha256=<hmac>`
+    pub secret: Option<String>,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct WebhooksConfig {
+    pub webhooks: Vec<Webhook>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WebhookDelivery {
+    pub id: String,
+    #[serde(rename = "webhookId")]
+    pub webhook_id: String,
+    pub event: String,
+    pub url: String,
+    /// "delivered" or "failed"
+    pub status: String,
+    pub attempts: u32,
+    #[serde(rename = "responseStatus")]
+    pub response_status: Option<u16>,
+    pub error: Option<String>,
+    pub timestamp: u64,
+}
+
+// Serializes log writes from concurrent delivery threads
+static LOG_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+/// Load webhooks from file
+pub fn load_webhooks_config(app: &AppHandle) -> WebhooksConfig {
+    load_json_or_default(app, WEBHOOKS_FILE)
+}
+
+fn save_webhooks_config(app: &AppHandle, config: &WebhooksConfig) -> Result<(), String> {
+    save_json(app, WEBHOOKS_FILE, config)
+}
+
+fn append_delivery_log(app: &AppHandle, delivery: WebhookDelivery) {
+    let _guard = LOG_LOCK.lock().unwrap();
+    let mut log: Vec<WebhookDelivery> = load_json_or_default(app, WEBHOOK_LOG_FILE);
+    log.insert(0, delivery);
+    log.truncate(MAX_LOG_ENTRIES);
+    if let Err(e) = save_json(app, WEBHOOK_LOG_FILE, &log) {
+        error!("Failed to save webhook delivery log: {}", e);
+    }
+}
+
+fn sign_body(secret: &str, body: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(body.as_bytes());
+    mac.finalize().into_bytes().iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn post_webhook(webhook: &Webhook, event: &str, body: &str) -> (Option<u16>, Result<(), String>) {
+    let client = match http_client_builder().timeout(REQUEST_TIMEOUT).build() {
+        Ok(client) => client,
+        Err(e) => return (None, Err(format!("Failed to create HTTP client: {}", e))),
+    };
+
+    let mut request = client.post(&webhook.url)
+        .header("Content-Type", "application/json")
+        .header("X-Blinko-Event", event)
+        .body(body.to_string());
+    if let Some(ref secret) = webhook.secret.as_ref().filter(|s| !s.is_empty()) {
+        request = request.header("X-Blinko-Signature", format!("sha256={}", sign_body(secret, body)));
+    }
+
+    match request.send() {
+        Ok(response) if response.status().is_success() => (Some(response.status().as_u16()), Ok(())),
+        Ok(response) => (Some(response.status().as_u16()), Err(format!("Endpoint returned {}", response.status()))),
+        Err(e) => (None, Err(format!("Network error: {}", e))),
+    }
+}
+
+/// Deliver with exponential backoff, then record the outcome
+fn deliver_webhook(app: &AppHandle, webhook: &Webhook, event: &str, body: &str) {
+    let mut attempts = 0;
+    let (response_status, result) = loop {
+        attempts += 1;
+        let (status, result) = post_webhook(webhook, event, body);
+        // Client errors other than rate limiting won't succeed on retry
+        let retryable = status.is_none_or(is_retryable_status);
+        if result.is_ok() || !retryable || attempts >= MAX_ATTEMPTS {
+            break (status, result);
+        }
+        std::thread::sleep(retry_delay(attempts));
+    };
+
+    match result {
+        Ok(()) => info!("🪝 Delivered {} to {}", event, webhook.url),
+        Err(ref e) => error!("❌ Webhook {} for {} failed after {} attempt(s): {}", webhook.url, event, attempts, e),
+    }
+
+    append_delivery_log(app, WebhookDelivery {
+        id: generate_id(),
+        webhook_id: webhook.id.clone(),
+        event: event.to_string(),
+        url: webhook.url.clone(),
+        status: if result.is_ok() { "delivered" } else { "failed" }.to_string(),
+        attempts,
+        response_status,
+        error: result.err(),
+        timestamp: now_millis(),
+    });
+}
+
+/// POST an event to every enabled webhook subscribed to it, each on its own thread
+pub fn dispatch_webhook_event(app: &AppHandle, event: &str, data: Value) {
+    let config = load_webhooks_config(app);
+    let targets: Vec<Webhook> = config.webhooks.into_iter()
+        .filter(|w| w.enabled && (w.events.is_empty() || w.events.iter().any(|e| e == event)))
+        .collect();
+    if targets.is_empty() {
+        return;
+    }
+
+    let body = json!({
+        "event": event,
+        "timestamp": now_millis(),
+        "data": data,
+    })
+    .to_string();
+
+    for webhook in targets {
+        let app_handle = app.clone();
+        let event = event.to_string();
+        let body = body.clone();
+        std::thread::spawn(move || deliver_webhook(&app_handle, &webhook, &event, &body));
+    }
+}
+
+fn validate_webhook(url: &str, events: &[String]) -> Result<(), String> {
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        return Err(format!("Invalid webhook URL: {}", url));
+    }
+    if let Some(unknown) = events.iter().find(|e| !WEBHOOK_EVENTS.contains(&e.as_str())) {
+        return Err(format!("Unknown webhook event: {}", unknown));
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn list_webhooks(app: AppHandle) -> WebhooksConfig {
+    load_webhooks_config(&app)
+}
+
+#[tauri::command]
+pub fn add_webhook(app: AppHandle, url: String, events: Vec<String>, secret: Option<String>) -> Result<Webhook, String> {
+    validate_webhook(&url, &events)?;
+    let webhook = Webhook {
+        id: generate_id(),
+        url,
+        events,
+        secret: secret.filter(|s| !s.is_empty()),
+        enabled: true,
+    };
+
+    let mut config = load_webhooks_config(&app);
+    config.webhooks.push(webhook.clone());
+    save_webhooks_config(&app, &config)?;
+    Ok(webhook)
+}
+
+#[tauri::command]
+pub fn update_webhook(app: AppHandle, webhook: Webhook) -> Result<(), String> {
+    validate_webhook(&webhook.url, &webhook.events)?;
+    let mut config = load_webhooks_config(&app);
+    let existing = config.webhooks.iter_mut()
+        .find(|w| w.id == webhook.id)
+        .ok_or_else(|| format!("Webhook not found: {}", webhook.id))?;
+    *existing = webhook;
+    save_webhooks_config(&app, &config)
+}
+
+#[tauri::command]
+pub fn remove_webhook(app: AppHandle, id: String) -> Result<(), String> {
+    let mut config = load_webhooks_config(&app);
+    config.webhooks.retain(|w| w.id != id);
+    save_webhooks_config(&app, &config)
+}
+
+#[tauri::command]
+pub fn get_webhook_delivery_log(app: AppHandle) -> Vec<WebhookDelivery> {
+    load_json_or_default(&app, WEBHOOK_LOG_FILE)
+}
+
+#[tauri::command]
+pub fn clear_webhook_delivery_log(app: AppHandle) -> Result<(), String> {
+    let _guard = LOG_LOCK.lock().unwrap();
+    save_json(&app, WEBHOOK_LOG_FILE, &Vec::<WebhookDelivery>::new())
+}
+
+/// Send a sample payload to one webhook and wait for the result
+#[tauri::command]
+pub async fn test_webhook(app: AppHandle, id: String) -> Result<WebhookDelivery, String> {
+    let webhook = load_webhooks_config(&app).webhooks.into_iter()
+        .find(|w| w.id == id)
+        .ok_or_else(|| format!("Webhook not found: {}", id))?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let body = json!({ "event": "test", "timestamp": now_millis(), "data": {} }).to_string();
+        let (response_status, result) = post_webhook(&webhook, "test", &body);
+        let delivery = WebhookDelivery {
+            id: generate_id(),
+            webhook_id: webhook.id.clone(),
+            event: "test".to_string(),
+            url: webhook.url.clone(),
+            status: if result.is_ok() { "delivered" } else { "failed" }.to_string(),
+            attempts: 1,
+            response_status,
+            error: result.err(),
+            timestamp: now_millis(),
+        };
+        append_delivery_log(&app, delivery.clone());
+        delivery
+    })
+    .await
+    .map_err(|e| format!("Webhook test failed: {}", e))
+}
+
+/// Let the webview report events that happen on its side, e.g. a note saved from the quicknote hotkey
+#[tauri::command]
+pub fn trigger_webhook_event(app: AppHandle, event: String, data: Value) -> Result<(), String> {
+    if !WEBHOOK_EVENTS.contains(&event.as_str()) {
+        return Err(format!("Unknown webhook event: {}", event));
+    }
+    dispatch_webhook_event(&app, &event, data);
+    Ok(())
+}
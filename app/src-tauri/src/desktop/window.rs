@@ -1,9 +1,34 @@
 use tauri::{AppHandle, Manager, Emitter, WebviewWindowBuilder, WebviewUrl, Runtime, WindowEvent};
 
+use crate::desktop::{apply_saved_window_effect, apply_saved_zoom, hold_notifications, remember_capture_source};
+use std::sync::{LazyLock, Mutex};
+
 // QuickTool window dimensions - defined once for consistency
 pub const QUICKTOOL_WIDTH: f64 = 190.0;
 pub const QUICKTOOL_HEIGHT: f64 = 35.0;
 
+// Command palette window dimensions
+pub const PALETTE_WIDTH: f64 = 640.0;
+pub const PALETTE_HEIGHT: f64 = 420.0;
+
+// Width of the centered writing column when focus mode doesn't go fullscreen
+const FOCUS_MODE_WIDTH: f64 = 960.0;
+const QUICK_WINDOW_LABELS: [&str; 4] = ["quicknote", "quickai", "quicktool", "palette"];
+
+/// Main window geometry and hidden windows to put back when focus mode ends
+struct FocusModeRestore {
+    hidden_windows: Vec<String>,
+    fullscreen: bool,
+    maximized: bool,
+    always_on_top: bool,
+    position: Option<tauri::PhysicalPosition<i32>>,
+    size: Option<tauri::PhysicalSize<u32>>,
+}
+
+static FOCUS_MODE: LazyLock<Mutex<Option<FocusModeRestore>>> = LazyLock::new(|| Mutex::new(None));
+// Text for the quicknote window, kept until its page takes it so a window still loading doesn't miss it
+static PENDING_QUICKNOTE_TEXT: LazyLock<Mutex<Vec<String>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
 /// Configuration for quick windows
 struct QuickWindowConfig {
     label: &'static str,
@@ -35,13 +60,16 @@ fn create_quick_window<R: Runtime>(
         .build()
         .map_err(|e| format!("Failed to create {} window: {}", config.label, e))?;
 
+    apply_saved_window_effect(&window);
+    apply_saved_zoom(&window);
+
     // Handle window close event - hide instead of close
     let window_clone = window.clone();
     window.on_window_event(move |event| {
         if let WindowEvent::CloseRequested { api, .. } = event {
             api.prevent_close();
             let _ = window_clone.hide();
-            println!("{} window hidden", config.label);
+            info!("{} window hidden", config.label);
         }
     });
 
@@ -54,13 +82,13 @@ fn toggle_window<R: Runtime>(app: &AppHandle<R>, window_label: &str) -> Result<(
         match window.is_visible() {
             Ok(true) => {
                 let _ = window.hide();
-                println!("{} window hidden", window_label);
+                info!("{} window hidden", window_label);
                 Ok(())
             }
             Ok(false) | Err(_) => {
                 let _ = window.show();
                 let _ = window.set_focus();
-                println!("{} window shown", window_label);
+                info!("{} window shown", window_label);
                 Ok(())
             }
         }
@@ -69,6 +97,23 @@ fn toggle_window<R: Runtime>(app: &AppHandle<R>, window_label: &str) -> Result<(
     }
 }
 
+/// Get the main window, recreating it if it was destroyed
+pub fn ensure_main_window<R: tauri::Runtime>(app: &AppHandle<R>) -> Result<tauri::WebviewWindow<R>, String> {
+    if let Some(window) = app.get_webview_window("main") {
+        return Ok(window);
+    }
+
+    info!("Main window not found, recreating it");
+    WebviewWindowBuilder::new(app, "main", WebviewUrl::App("/".into()))
+        .title("Blinko")
+        .inner_size(1280.0, 800.0)
+        .min_inner_size(600.0, 300.0)
+        .center()
+        .visible(true)
+        .build()
+        .map_err(|e| format!("Failed to create main window: {}", e))
+}
+
 #[tauri::command]
 pub fn toggle_editor_window<R: tauri::Runtime>(app: AppHandle<R>) -> Result<(), String> {
     match app.get_webview_window("main") {
@@ -109,7 +154,7 @@ pub fn resize_quicknote_window<R: tauri::Runtime>(app: AppHandle<R>, height: f64
         window.set_size(size)
             .map_err(|e| format!("Failed to set size: {}", e))?;
         
-        println!("Resized quicknote window to {}x{} (requested: {})", width, constrained_height, height);
+        info!("Resized quicknote window to {}x{} (requested: {})", width, constrained_height, height);
         Ok(())
     } else {
         Err("Quicknote window not found".to_string())
@@ -118,6 +163,12 @@ pub fn resize_quicknote_window<R: tauri::Runtime>(app: AppHandle<R>, height: f64
 
 #[tauri::command]
 pub fn toggle_quicknote_window<R: tauri::Runtime>(app: AppHandle<R>) -> Result<(), String> {
+    // Note where the capture comes from while that app still has focus
+    let opening = !app.get_webview_window("quicknote").is_some_and(|window| window.is_visible().unwrap_or(false));
+    if opening {
+        let _ = app.emit("capture-source", remember_capture_source());
+    }
+
     // Try to toggle existing window first
     if let Ok(()) = toggle_window(&app, "quicknote") {
         return Ok(());
@@ -137,6 +188,33 @@ pub fn toggle_quicknote_window<R: tauri::Runtime>(app: AppHandle<R>) -> Result<(
     create_quick_window(&app, config)
 }
 
+/// Show the quicknote window (without toggling) and prefill it with text.
+/// A loaded page is told to take the text right away, a new one takes it once it has loaded.
+pub fn open_quicknote_with_text<R: tauri::Runtime>(app: &AppHandle<R>, text: &str) -> Result<(), String> {
+    PENDING_QUICKNOTE_TEXT.lock().unwrap().push(text.to_string());
+
+    if app.get_webview_window("quicknote").is_none() {
+        toggle_quicknote_window(app.clone())?;
+    }
+
+    let window = app.get_webview_window("quicknote")
+        .ok_or("Quicknote window not found")?;
+    let _ = window.show();
+    let _ = window.set_focus();
+
+    window.emit("quicknote-text-pending", ())
+        .map_err(|e| format!("Failed to notify quicknote window: {}", e))?;
+
+    info!("Opened quicknote window with {} characters of text", text.len());
+    Ok(())
+}
+
+/// Called by the quicknote page once loaded and on `quicknote-text-pending`
+#[tauri::command]
+pub fn take_pending_quicknote_text() -> Vec<String> {
+    std::mem::take(&mut *PENDING_QUICKNOTE_TEXT.lock().unwrap())
+}
+
 #[tauri::command]
 pub fn resize_quickai_window<R: tauri::Runtime>(app: AppHandle<R>, height: f64) -> Result<(), String> {
     if let Some(window) = app.get_webview_window("quickai") {
@@ -149,7 +227,7 @@ pub fn resize_quickai_window<R: tauri::Runtime>(app: AppHandle<R>, height: f64)
         window.set_size(size)
             .map_err(|e| format!("Failed to set size: {}", e))?;
         
-        println!("Resized quickai window to {}x{} (requested: {})", width, constrained_height, height);
+        info!("Resized quickai window to {}x{} (requested: {})", width, constrained_height, height);
         Ok(())
     } else {
         Err("Quickai window not found".to_string())
@@ -187,12 +265,12 @@ pub fn navigate_main_to_ai_with_prompt<R: tauri::Runtime>(app: AppHandle<R>, pro
 
     // Show main window if it's hidden
     if let Err(e) = main_window.show() {
-        eprintln!("Failed to show main window: {}", e);
+        error!("Failed to show main window: {}", e);
     }
 
     // Focus main window
     if let Err(e) = main_window.set_focus() {
-        eprintln!("Failed to focus main window: {}", e);
+        error!("Failed to focus main window: {}", e);
     }
 
     // Emit event to main window with the AI prompt
@@ -200,7 +278,7 @@ pub fn navigate_main_to_ai_with_prompt<R: tauri::Runtime>(app: AppHandle<R>, pro
         return Err(format!("Failed to emit navigation event: {}", e));
     }
 
-    println!("Triggered main window navigation to AI with prompt");
+    info!("Triggered main window navigation to AI with prompt");
     Ok(())
 }
 
@@ -229,13 +307,151 @@ pub fn toggle_quicktool_window<R: tauri::Runtime>(app: AppHandle<R>) -> Result<(
 pub fn hide_quicktool_window<R: tauri::Runtime>(app: AppHandle<R>) -> Result<(), String> {
     if let Some(window) = app.get_webview_window("quicktool") {
         let _ = window.hide();
-        println!("Quicktool window hidden");
+        info!("Quicktool window hidden");
         Ok(())
     } else {
         Err("Quicktool window not found".to_string())
     }
 }
 
+/// The palette is transient and goes away as soon as it loses focus
+pub fn setup_palette_window<R: Runtime>(app: &AppHandle<R>) {
+    if let Some(window) = app.get_webview_window("palette") {
+        let window_clone = window.clone();
+        window.on_window_event(move |event| {
+            if let WindowEvent::Focused(false) = event {
+                let _ = window_clone.hide();
+            }
+        });
+    }
+}
+
+#[tauri::command]
+pub fn toggle_palette_window<R: tauri::Runtime>(app: AppHandle<R>) -> Result<(), String> {
+    if app.get_webview_window("palette").is_some() {
+        toggle_window(&app, "palette")?;
+    } else {
+        // Create new palette window if it doesn't exist
+        let config = QuickWindowConfig {
+            label: "palette",
+            title: "Command Palette",
+            url: "/palette",
+            width: PALETTE_WIDTH,
+            height: PALETTE_HEIGHT,
+            resizable: false,
+            skip_taskbar: true,
+        };
+        create_quick_window(&app, config)?;
+        setup_palette_window(&app);
+    }
+
+    let window = app.get_webview_window("palette")
+        .ok_or("Palette window not found")?;
+    if window.is_visible().unwrap_or(false) {
+        let _ = window.center();
+        // Lets the palette clear the previous query and focus its input
+        let _ = window.emit("palette-opened", ());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn hide_palette_window<R: tauri::Runtime>(app: AppHandle<R>) -> Result<(), String> {
+    if let Some(window) = app.get_webview_window("palette") {
+        let _ = window.hide();
+        Ok(())
+    } else {
+        Err("Palette window not found".to_string())
+    }
+}
+
+/// Distraction-free writing: hide quick windows, keep the main window on top and
+/// fullscreen (or a centered column when `fullscreen` is false), and hold notifications
+#[tauri::command]
+pub fn enter_focus_mode(app: AppHandle, fullscreen: Option<bool>) -> Result<(), String> {
+    let mut focus_mode = FOCUS_MODE.lock().unwrap();
+    if focus_mode.is_some() {
+        return Ok(());
+    }
+    let window = ensure_main_window(&app)?;
+
+    let mut hidden_windows = Vec::new();
+    for label in QUICK_WINDOW_LABELS {
+        if let Some(quick_window) = app.get_webview_window(label) {
+            if quick_window.is_visible().unwrap_or(false) && quick_window.hide().is_ok() {
+                hidden_windows.push(label.to_string());
+            }
+        }
+    }
+
+    *focus_mode = Some(FocusModeRestore {
+        hidden_windows,
+        fullscreen: window.is_fullscreen().unwrap_or(false),
+        maximized: window.is_maximized().unwrap_or(false),
+        always_on_top: window.is_always_on_top().unwrap_or(false),
+        position: window.outer_position().ok(),
+        size: window.inner_size().ok(),
+    });
+
+    let _ = window.show();
+    let _ = window.unminimize();
+    if fullscreen.unwrap_or(true) {
+        window.set_fullscreen(true).map_err(|e| format!("Failed to enter fullscreen: {}", e))?;
+    } else if let Ok(Some(monitor)) = window.current_monitor() {
+        let scale = monitor.scale_factor();
+        let work_area = monitor.size().to_logical::<f64>(scale);
+        let _ = window.unmaximize();
+        let _ = window.set_size(tauri::LogicalSize::new(FOCUS_MODE_WIDTH.min(work_area.width), work_area.height * 0.9));
+        let _ = window.center();
+    }
+    let _ = window.set_always_on_top(true);
+    let _ = window.set_focus();
+    hold_notifications(&app, true);
+
+    info!("🎯 Focus mode on");
+    let _ = app.emit("focus-mode-changed", true);
+    Ok(())
+}
+
+/// Put the main window, quick windows and notifications back as they were
+#[tauri::command]
+pub fn exit_focus_mode(app: AppHandle) -> Result<(), String> {
+    let Some(restore) = FOCUS_MODE.lock().unwrap().take() else {
+        return Ok(());
+    };
+
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.set_always_on_top(restore.always_on_top);
+        let _ = window.set_fullscreen(restore.fullscreen);
+        if restore.maximized {
+            let _ = window.maximize();
+        } else if !restore.fullscreen {
+            if let Some(size) = restore.size {
+                let _ = window.set_size(size);
+            }
+            if let Some(position) = restore.position {
+                let _ = window.set_position(position);
+            }
+        }
+    }
+
+    for label in &restore.hidden_windows {
+        if let Some(quick_window) = app.get_webview_window(label) {
+            let _ = quick_window.show();
+        }
+    }
+    hold_notifications(&app, false);
+
+    info!("🎯 Focus mode off");
+    let _ = app.emit("focus-mode-changed", false);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn is_focus_mode() -> bool {
+    FOCUS_MODE.lock().unwrap().is_some()
+}
+
 #[tauri::command]
 pub fn set_desktop_theme<R: tauri::Runtime>(app: AppHandle<R>, theme: String) -> Result<(), String> {
     use tauri::{Theme, window::Color};
@@ -257,16 +473,16 @@ pub fn set_desktop_theme<R: tauri::Runtime>(app: AppHandle<R>, theme: String) ->
     if let Some(window) = app.get_webview_window("main") {
         // Set system theme
         if let Err(e) = window.set_theme(Some(tauri_theme)) {
-            eprintln!("Failed to set theme for main window: {}", e);
+            error!("Failed to set theme for main window: {}", e);
         } else {
-            println!("Set main window theme to: {}", theme);
+            info!("Set main window theme to: {}", theme);
         }
 
         // Set window background color
         if let Err(e) = window.set_background_color(Some(background_color)) {
-            eprintln!("Failed to set background color for main window: {}", e);
+            error!("Failed to set background color for main window: {}", e);
         } else {
-            println!("Set main window background color to: {:?}", background_color);
+            info!("Set main window background color to: {:?}", background_color);
         }
     }
 
@@ -300,9 +516,9 @@ pub fn set_desktop_colors<R: tauri::Runtime>(
             if let Some(window) = app.get_webview_window("main") {
                 let color = Color(r, g, b, a);
                 if let Err(e) = window.set_background_color(Some(color)) {
-                    eprintln!("Failed to set custom background color for main window: {}", e);
+                    error!("Failed to set custom background color for main window: {}", e);
                 } else {
-                    println!("Set main window custom background color to: {}", color_str);
+                    info!("Set main window custom background color to: {}", color_str);
                 }
             }
         } else {
@@ -0,0 +1,120 @@
+use tauri::{AppHandle, Manager, Runtime, WebviewWindow};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+
+use crate::desktop::{load_json_or_default, save_json};
+
+const WINDOW_EFFECTS_FILE: &str = "window_effects.json";
+const EFFECT_WINDOWS: [&str; 5] = ["main", "quicknote", "quickai", "quicktool", "palette"];
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum WindowEffect {
+    #[default]
+    None,
+    /// Windows 11
+    Mica,
+    /// Windows 10/11
+    Acrylic,
+    Blur,
+    /// macOS NSVisualEffectView
+    Vibrancy,
+}
+
+// Effects per window label, applied again whenever a quick window is recreated
+static WINDOW_EFFECTS: LazyLock<Mutex<HashMap<String, WindowEffect>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+#[cfg(target_os = "windows")]
+fn apply_native_effect<R: Runtime>(window: &WebviewWindow<R>, effect: WindowEffect) -> Result<bool, String> {
+    use window_vibrancy::{apply_acrylic, apply_blur, apply_mica, clear_acrylic, clear_blur, clear_mica};
+
+    // Only one effect can be active, clear whatever was there before
+    let _ = clear_mica(window);
+    let _ = clear_acrylic(window);
+    let _ = clear_blur(window);
+    let result = match effect {
+        WindowEffect::None => return Ok(true),
+        WindowEffect::Mica => apply_mica(window, None),
+        WindowEffect::Acrylic | WindowEffect::Vibrancy => apply_acrylic(window, Some((18, 18, 18, 125))),
+        WindowEffect::Blur => apply_blur(window, Some((18, 18, 18, 125))),
+    };
+    result.map(|_| true).map_err(|e| format!("Failed to apply {:?} effect: {}", effect, e))
+}
+
+#[cfg(target_os = "macos")]
+fn apply_native_effect<R: Runtime>(window: &WebviewWindow<R>, effect: WindowEffect) -> Result<bool, String> {
+    use window_vibrancy::{apply_vibrancy, clear_vibrancy, NSVisualEffectMaterial, NSVisualEffectState};
+
+    if effect == WindowEffect::None {
+        let _ = clear_vibrancy(window);
+        return Ok(true);
+    }
+    // Every effect maps to the closest native material
+    apply_vibrancy(window, NSVisualEffectMaterial::UnderWindowBackground, Some(NSVisualEffectState::FollowsWindowActiveState), None)
+        .map(|_| true)
+        .map_err(|e| format!("Failed to apply vibrancy: {}", e))
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn apply_native_effect<R: Runtime>(_window: &WebviewWindow<R>, effect: WindowEffect) -> Result<bool, String> {
+    Ok(effect == WindowEffect::None)
+}
+
+/// Apply an effect; returns false when this platform has no such effect
+pub fn apply_window_effect<R: Runtime>(window: &WebviewWindow<R>, effect: WindowEffect) -> Result<bool, String> {
+    let applied = apply_native_effect(window, effect)?;
+    if applied && effect != WindowEffect::None {
+        // The effect sits behind the webview, so its background has to be see-through
+        if let Err(e) = window.set_background_color(Some(tauri::window::Color(0, 0, 0, 0))) {
+            warn!("⚠️ Failed to clear background for {}: {}", window.label(), e);
+        }
+    }
+    Ok(applied)
+}
+
+/// Reapply the saved effect when a window is created
+pub fn apply_saved_window_effect<R: Runtime>(window: &WebviewWindow<R>) {
+    let effect = WINDOW_EFFECTS.lock().unwrap().get(window.label()).copied().unwrap_or_default();
+    if effect == WindowEffect::None {
+        return;
+    }
+    if let Err(e) = apply_window_effect(window, effect) {
+        error!("❌ {}", e);
+    }
+}
+
+/// Load saved effects and apply them to the windows that already exist
+pub fn restore_window_effects(app: &AppHandle) {
+    let effects: HashMap<String, WindowEffect> = load_json_or_default(app, WINDOW_EFFECTS_FILE);
+    *WINDOW_EFFECTS.lock().unwrap() = effects;
+
+    for label in EFFECT_WINDOWS {
+        if let Some(window) = app.get_webview_window(label) {
+            apply_saved_window_effect(&window);
+        }
+    }
+}
+
+/// Returns false if the effect is not available on this platform, which is not an error
+#[tauri::command]
+pub fn set_window_effect(app: AppHandle, label: String, effect: WindowEffect) -> Result<bool, String> {
+    if !EFFECT_WINDOWS.contains(&label.as_str()) {
+        return Err(format!("Window effects are not supported for {}", label));
+    }
+
+    let applied = match app.get_webview_window(&label) {
+        Some(window) => apply_window_effect(&window, effect)?,
+        None => true,
+    };
+
+    let mut effects = WINDOW_EFFECTS.lock().unwrap();
+    effects.insert(label, effect);
+    save_json(&app, WINDOW_EFFECTS_FILE, &*effects)?;
+    Ok(applied)
+}
+
+#[tauri::command]
+pub fn get_window_effects() -> HashMap<String, WindowEffect> {
+    WINDOW_EFFECTS.lock().unwrap().clone()
+}
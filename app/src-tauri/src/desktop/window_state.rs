@@ -1,15 +1,44 @@
-use tauri::{AppHandle, Manager};
+use tauri::{AppHandle, Manager, Runtime, WebviewWindow};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
 use crate::desktop::hotkey::WindowConfig;
+use crate::desktop::get_app_data_dir;
 
 const WINDOW_STATE_FILE: &str = "window_state.json";
+const MIN_ZOOM: f64 = 0.5;
+const MAX_ZOOM: f64 = 3.0;
+const ZOOM_STEP: f64 = 0.1;
+/// Resizes arrive in bursts while dragging, only the size after the last one is written
+const RESIZE_SAVE_DELAY: Duration = Duration::from_millis(500);
+/// Auxiliary windows that are reopened by session restore
+const SESSION_WINDOWS: [&str; 3] = ["quicknote", "quickai", "quicktool"];
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AppWindowState {
     main_window: Option<WindowConfig>,
     quicknote_window: Option<WindowConfig>,
+    /// Webview zoom factor per window label
+    #[serde(default)]
+    zoom: HashMap<String, f64>,
+    /// Reopen the auxiliary windows that were visible at quit
+    #[serde(default)]
+    restore_session: bool,
+    /// Auxiliary windows visible at the last quit
+    #[serde(default)]
+    session: Vec<SessionWindow>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SessionWindow {
+    pub label: String,
+    /// Frontend handle of the unsaved draft shown in the window
+    #[serde(rename = "draftHandle")]
+    pub draft_handle: Option<String>,
 }
 
 impl Default for AppWindowState {
@@ -17,23 +46,27 @@ impl Default for AppWindowState {
         Self {
             main_window: Some(WindowConfig::default()),
             quicknote_window: None,
+            zoom: HashMap::new(),
+            restore_session: false,
+            session: Vec::new(),
         }
     }
 }
 
+// Zoom factors from window_state.json, so windows created later get theirs without reading the file
+static ZOOM_LEVELS: LazyLock<Mutex<HashMap<String, f64>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+// Draft handles the auxiliary windows report while open
+static SESSION_DRAFTS: LazyLock<Mutex<HashMap<String, String>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+// Draft handles from the last session, handed out once to the reopened windows
+static RESTORED_DRAFTS: LazyLock<Mutex<HashMap<String, String>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+// Serializes read-modify-write of window_state.json between background writes and commands
+static WINDOW_STATE_WRITE: Mutex<()> = Mutex::new(());
+// Bumped on every resize so only the last pending write runs
+static RESIZE_GENERATION: AtomicU64 = AtomicU64::new(0);
+
 // Get window state file path
 fn get_window_state_path(app: &AppHandle) -> Result<PathBuf, String> {
-    let app_data_dir = app.path().app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
-    
-    
-    // Ensure directory exists
-    if !app_data_dir.exists() {
-        fs::create_dir_all(&app_data_dir)
-            .map_err(|e| format!("Failed to create app data directory: {}", e))?;
-    }
-    
-    Ok(app_data_dir.join(WINDOW_STATE_FILE))
+    Ok(get_app_data_dir(app)?.join(WINDOW_STATE_FILE))
 }
 
 // Load window state from file
@@ -48,20 +81,20 @@ pub fn load_window_state(app: &AppHandle) -> AppWindowState {
                                 return state;
                             }
                             Err(e) => {
-                                eprintln!("Failed to parse window state: {}", e);
+                                error!("Failed to parse window state: {}", e);
                             }
                         }
                     }
                     Err(e) => {
-                        eprintln!("Failed to read window state file: {}", e);
+                        error!("Failed to read window state file: {}", e);
                     }
                 }
             } else {
-                println!("Window state file does not exist, using defaults");
+                info!("Window state file does not exist, using defaults");
             }
         }
         Err(e) => {
-            eprintln!("Failed to get window state path: {}", e);
+            error!("Failed to get window state path: {}", e);
         }
     }
     
@@ -75,22 +108,30 @@ pub fn save_window_state(app: &AppHandle, state: &AppWindowState) {
             match serde_json::to_string_pretty(state) {
                 Ok(content) => {
                     if let Err(e) = fs::write(&path, content) {
-                        eprintln!("Failed to write window state to file: {}", e);
+                        error!("Failed to write window state to file: {}", e);
                     } else {
-                        println!("Saved window state to: {}", path.display());
+                        info!("Saved window state to: {}", path.display());
                     }
                 }
                 Err(e) => {
-                    eprintln!("Failed to serialize window state: {}", e);
+                    error!("Failed to serialize window state: {}", e);
                 }
             }
         }
         Err(e) => {
-            eprintln!("Failed to get window state path: {}", e);
+            error!("Failed to get window state path: {}", e);
         }
     }
 }
 
+// Load, change and save the window state without racing other writers
+fn update_window_state(app: &AppHandle, update: impl FnOnce(&mut AppWindowState)) {
+    let _guard = WINDOW_STATE_WRITE.lock().unwrap();
+    let mut window_state = load_window_state(app);
+    update(&mut window_state);
+    save_window_state(app, &window_state);
+}
+
 // Apply window state to main window
 pub fn restore_main_window_state(app: &AppHandle) {
     let window_state = load_window_state(app);
@@ -102,40 +143,40 @@ pub fn restore_main_window_state(app: &AppHandle) {
                 // Use PhysicalSize to ensure exact pixel restoration
                 let size = tauri::Size::Physical(tauri::PhysicalSize::new(config.width as u32, config.height as u32));
                 if let Err(e) = window.set_size(size) {
-                    eprintln!("Failed to restore window size: {}", e);
+                    error!("Failed to restore window size: {}", e);
                 } else {
-                    println!("Restored window size: {}x{}", config.width, config.height);
+                    info!("Restored window size: {}x{}", config.width, config.height);
                 }
 
                 // Center the window after setting size
                 if let Err(e) = window.center() {
-                    eprintln!("Failed to center window: {}", e);
+                    error!("Failed to center window: {}", e);
                 } else {
-                    println!("Window centered successfully");
+                    info!("Window centered successfully");
                 }
             }
 
             // Restore maximized state
             if config.maximized {
                 if let Err(e) = window.maximize() {
-                    eprintln!("Failed to maximize window: {}", e);
+                    error!("Failed to maximize window: {}", e);
                 } else {
-                    println!("Window maximized successfully");
+                    info!("Window maximized successfully");
                 }
             }
 
             // Show window after restoring state
             if let Err(e) = window.show() {
-                eprintln!("Failed to show main window: {}", e);
+                error!("Failed to show main window: {}", e);
             } else {
-                println!("Main window shown after state restoration");
+                info!("Main window shown after state restoration");
             }
         } else {
             // No saved state, show window with default settings
             if let Err(e) = window.show() {
-                eprintln!("Failed to show main window: {}", e);
+                error!("Failed to show main window: {}", e);
             } else {
-                println!("Main window shown with default settings");
+                info!("Main window shown with default settings");
             }
         }
     }
@@ -145,44 +186,63 @@ pub fn restore_main_window_state(app: &AppHandle) {
 const MIN_WINDOW_WIDTH: f64 = 600.0;
 const MIN_WINDOW_HEIGHT: f64 = 300.0;
 
-// Save current main window state
-pub fn save_main_window_state(app: &AppHandle) {
-    if let Some(window) = app.get_webview_window("main") {
-        let mut window_state = load_window_state(app);
-
-        // Get current window state - only size and maximized state
-        if let (Ok(size), Ok(is_maximized), Ok(is_minimized)) = (
-            window.inner_size(),
-            window.is_maximized(),
-            window.is_minimized()
-        ) {
-            let width = size.width as f64;
-            let height = size.height as f64;
-
-            // Don't save state if window is minimized or dimensions are too small
-            if is_minimized || width < MIN_WINDOW_WIDTH || height < MIN_WINDOW_HEIGHT {
-                println!("Skipping window state save - minimized: {}, size: {}x{} (min: {}x{})",
-                         is_minimized, width, height, MIN_WINDOW_WIDTH, MIN_WINDOW_HEIGHT);
-                return;
-            }
+// Current size of the main window, None while it is minimized or below the minimum size
+fn current_main_window_config(app: &AppHandle) -> Option<WindowConfig> {
+    let window = app.get_webview_window("main")?;
 
-            let config = WindowConfig {
-                width,
-                height,
-                x: None,  // Don't save position, always center
-                y: None,  // Don't save position, always center
-                maximized: is_maximized,
-            };
+    // Get current window state - only size and maximized state
+    let (Ok(size), Ok(is_maximized), Ok(is_minimized)) = (
+        window.inner_size(),
+        window.is_maximized(),
+        window.is_minimized()
+    ) else {
+        return None;
+    };
+    let width = size.width as f64;
+    let height = size.height as f64;
 
-            window_state.main_window = Some(config.clone());
-            save_window_state(app, &window_state);
+    // Don't save state if window is minimized or dimensions are too small
+    if is_minimized || width < MIN_WINDOW_WIDTH || height < MIN_WINDOW_HEIGHT {
+        info!("Skipping window state save - minimized: {}, size: {}x{} (min: {}x{})",
+                 is_minimized, width, height, MIN_WINDOW_WIDTH, MIN_WINDOW_HEIGHT);
+        return None;
+    }
 
-            println!("Saved main window state: {}x{}, maximized: {}",
-                     config.width, config.height, config.maximized);
-        }
+    Some(WindowConfig {
+        width,
+        height,
+        x: None,  // Don't save position, always center
+        y: None,  // Don't save position, always center
+        maximized: is_maximized,
+    })
+}
+
+fn write_main_window_config(app: &AppHandle, config: WindowConfig) {
+    info!("Saved main window state: {}x{}, maximized: {}",
+             config.width, config.height, config.maximized);
+    update_window_state(app, |window_state| window_state.main_window = Some(config));
+}
+
+// Save current main window state
+pub fn save_main_window_state(app: &AppHandle) {
+    if let Some(config) = current_main_window_config(app) {
+        write_main_window_config(app, config);
     }
 }
 
+// Write the size after a resize from a background thread, once resizing has settled
+fn schedule_main_window_state_save(app: &AppHandle) {
+    let Some(config) = current_main_window_config(app) else { return };
+    let generation = RESIZE_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    let app_handle = app.clone();
+    std::thread::spawn(move || {
+        std::thread::sleep(RESIZE_SAVE_DELAY);
+        if RESIZE_GENERATION.load(Ordering::SeqCst) == generation {
+            write_main_window_config(&app_handle, config);
+        }
+    });
+}
+
 // Setup window state monitoring - ONLY for main window
 pub fn setup_window_state_monitoring(app: &AppHandle) {
     // Only monitor the main window for state saving
@@ -192,19 +252,154 @@ pub fn setup_window_state_monitoring(app: &AppHandle) {
         window.on_window_event(move |event| {
             match event {
                 tauri::WindowEvent::Resized(_) => {
-                    // Save state on resize (but only if not minimized and above minimum size)
-                    save_main_window_state(&app_handle);
+                    // Runs on the UI thread for every resize step, the file is written later
+                    schedule_main_window_state_save(&app_handle);
                 }
                 tauri::WindowEvent::CloseRequested { .. } => {
-                    // Save state before closing (but only if not minimized and above minimum size)
+                    // The app may exit right after, so this one is written immediately
+                    RESIZE_GENERATION.fetch_add(1, Ordering::SeqCst);
                     save_main_window_state(&app_handle);
                 }
                 _ => {}
             }
         });
 
-        println!("Window state monitoring setup ONLY for main window");
+        info!("Window state monitoring setup ONLY for main window");
     } else {
-        eprintln!("Failed to setup window state monitoring: main window not found");
+        error!("Failed to setup window state monitoring: main window not found");
+    }
+}
+
+// Apply the saved zoom factor to a newly created window
+pub fn apply_saved_zoom<R: Runtime>(window: &WebviewWindow<R>) {
+    let zoom = ZOOM_LEVELS.lock().unwrap().get(window.label()).copied();
+    if let Some(zoom) = zoom {
+        if let Err(e) = window.set_zoom(zoom) {
+            error!("Failed to restore zoom for {} window: {}", window.label(), e);
+        }
+    }
+}
+
+// Load saved zoom factors and apply them to existing windows
+pub fn restore_window_zoom(app: &AppHandle) {
+    *ZOOM_LEVELS.lock().unwrap() = load_window_state(app).zoom;
+    for window in app.webview_windows().values() {
+        apply_saved_zoom(window);
+    }
+}
+
+#[tauri::command]
+pub fn get_zoom(label: String) -> f64 {
+    ZOOM_LEVELS.lock().unwrap().get(&label).copied().unwrap_or(1.0)
+}
+
+#[tauri::command]
+pub async fn set_zoom(app: AppHandle, label: String, factor: f64) -> Result<f64, String> {
+    let factor = (factor.clamp(MIN_ZOOM, MAX_ZOOM) * 100.0).round() / 100.0;
+    let window = app.get_webview_window(&label)
+        .ok_or_else(|| format!("{} window not found", label))?;
+    window.set_zoom(factor)
+        .map_err(|e| format!("Failed to set zoom: {}", e))?;
+
+    ZOOM_LEVELS.lock().unwrap().insert(label.clone(), factor);
+    tauri::async_runtime::spawn_blocking(move || {
+        update_window_state(&app, |window_state| {
+            window_state.zoom.insert(label, factor);
+        })
+    })
+    .await
+    .map_err(|e| format!("Failed to save zoom: {}", e))?;
+    Ok(factor)
+}
+
+// For the in-app Ctrl+= / Ctrl+- / Ctrl+0 shortcuts; `direction` is "in", "out" or "reset"
+#[tauri::command]
+pub async fn step_zoom(app: AppHandle, label: String, direction: String) -> Result<f64, String> {
+    let current = get_zoom(label.clone());
+    let factor = match direction.as_str() {
+        "in" => current + ZOOM_STEP,
+        "out" => current - ZOOM_STEP,
+        "reset" => 1.0,
+        other => return Err(format!("Invalid zoom direction: {}", other)),
+    };
+    set_zoom(app, label, factor).await
+}
+
+
+// Remember which auxiliary windows are open at quit, called on exit request
+pub fn save_session(app: &AppHandle) {
+    update_window_state(app, |window_state| {
+        if !window_state.restore_session {
+            return;
+        }
+
+        let drafts = SESSION_DRAFTS.lock().unwrap();
+        window_state.session = SESSION_WINDOWS.iter()
+            .filter(|label| app.get_webview_window(label).is_some_and(|w| w.is_visible().unwrap_or(false)))
+            .map(|label| SessionWindow {
+                label: label.to_string(),
+                draft_handle: drafts.get(*label).cloned(),
+            })
+            .collect();
+
+        info!("Saving session with {} open window(s)", window_state.session.len());
+    });
+}
+
+// Reopen the windows from the last session; their drafts are picked up with take_session_draft
+pub fn restore_session(app: &AppHandle) {
+    let window_state = load_window_state(app);
+    if !window_state.restore_session || window_state.session.is_empty() {
+        return;
+    }
+
+    let mut restored = RESTORED_DRAFTS.lock().unwrap();
+    for window in window_state.session {
+        if !SESSION_WINDOWS.contains(&window.label.as_str()) {
+            continue;
+        }
+        if let Some(handle) = window.draft_handle {
+            restored.insert(window.label.clone(), handle);
+        }
+        // The windows start hidden, so toggling shows them
+        crate::desktop::run_shortcut_command(app, &window.label);
+        info!("Restored {} window from last session", window.label);
     }
-}
\ No newline at end of file
+}
+
+#[tauri::command]
+pub async fn get_restore_session(app: AppHandle) -> bool {
+    load_window_state(&app).restore_session
+}
+
+#[tauri::command]
+pub async fn set_restore_session(app: AppHandle, enabled: bool) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        update_window_state(&app, |window_state| {
+            window_state.restore_session = enabled;
+            if !enabled {
+                window_state.session.clear();
+            }
+        })
+    })
+    .await
+    .map_err(|e| format!("Failed to save session setting: {}", e))
+}
+
+// Called by an auxiliary window whenever its draft changes; None once the draft is saved or discarded
+#[tauri::command]
+pub fn set_session_draft(label: String, draft_handle: Option<String>) {
+    let mut drafts = SESSION_DRAFTS.lock().unwrap();
+    match draft_handle {
+        Some(handle) => drafts.insert(label, handle),
+        None => drafts.remove(&label),
+    };
+}
+
+// Called by an auxiliary window on load to pick up the draft it showed before the restart
+#[tauri::command]
+pub fn take_session_draft(label: String) -> Option<String> {
+    let handle = RESTORED_DRAFTS.lock().unwrap().remove(&label)?;
+    SESSION_DRAFTS.lock().unwrap().insert(label, handle.clone());
+    Some(handle)
+}
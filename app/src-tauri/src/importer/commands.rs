@@ -0,0 +1,35 @@
+use std::path::PathBuf;
+use tauri::AppHandle;
+
+use crate::importer::{request_import_cancel, run_enex_import, run_notion_import, run_obsidian_import, ImportSummary};
+
+/// Import an Evernote `.enex` export. Notes arrive through `import-notes` events,
+/// progress through `import-progress`. With `dry_run` only the summary is produced.
+#[tauri::command]
+pub async fn import_enex(app: AppHandle, path: String, dry_run: Option<bool>) -> Result<ImportSummary, String> {
+    tauri::async_runtime::spawn_blocking(move || run_enex_import(&app, &PathBuf::from(path), dry_run.unwrap_or(false)))
+        .await
+        .map_err(|e| format!("ENEX import failed: {}", e))?
+}
+
+/// Import a Notion workspace export zip (Markdown or HTML)
+#[tauri::command]
+pub async fn import_notion_zip(app: AppHandle, path: String, dry_run: Option<bool>) -> Result<ImportSummary, String> {
+    tauri::async_runtime::spawn_blocking(move || run_notion_import(&app, &PathBuf::from(path), dry_run.unwrap_or(false)))
+        .await
+        .map_err(|e| format!("Notion import failed: {}", e))?
+}
+
+/// Import an Obsidian vault folder, resolving wikilinks and embedded attachments
+#[tauri::command]
+pub async fn import_obsidian_vault(app: AppHandle, path: String, dry_run: Option<bool>) -> Result<ImportSummary, String> {
+    tauri::async_runtime::spawn_blocking(move || run_obsidian_import(&app, &PathBuf::from(path), dry_run.unwrap_or(false)))
+        .await
+        .map_err(|e| format!("Obsidian import failed: {}", e))?
+}
+
+/// Stop the running import after the current note
+#[tauri::command]
+pub fn cancel_import() {
+    request_import_cancel();
+}
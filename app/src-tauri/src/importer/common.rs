@@ -0,0 +1,297 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Emitter};
+
+use crate::desktop::{generate_id, import_bytes, import_file, now_millis, ImportedFile};
+
+/// Notes are handed to the webview in batches so huge archives don't produce one giant event
+const NOTE_BATCH_SIZE: usize = 20;
+
+/// A note converted to Blinko Markdown, ready for the webview to create
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ImportedNote {
+    pub title: String,
+    /// Markdown body; attachments are referenced by file name
+    pub content: String,
+    pub tags: Vec<String>,
+    #[serde(rename = "createdAt")]
+    pub created_at: Option<u64>,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: Option<u64>,
+    pub attachments: Vec<ImportedFile>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ImportProgress {
+    #[serde(rename = "importId")]
+    pub import_id: String,
+    /// "enex", "notion" or "obsidian"
+    pub source: String,
+    #[serde(rename = "processedBytes")]
+    pub processed_bytes: u64,
+    #[serde(rename = "totalBytes")]
+    pub total_bytes: u64,
+    #[serde(rename = "noteCount")]
+    pub note_count: u32,
+    #[serde(rename = "currentTitle")]
+    pub current_title: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ImportNotesBatch {
+    #[serde(rename = "importId")]
+    pub import_id: String,
+    pub notes: Vec<ImportedNote>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ImportSummary {
+    #[serde(rename = "importId")]
+    pub import_id: String,
+    pub source: String,
+    #[serde(rename = "noteCount")]
+    pub note_count: u32,
+    #[serde(rename = "attachmentCount")]
+    pub attachment_count: u32,
+    /// Distinct tags found across all notes
+    pub tags: Vec<String>,
+    /// Files that are neither notes nor referenced attachments, e.g. Notion database CSVs
+    #[serde(rename = "skippedFiles")]
+    pub skipped_files: Vec<String>,
+    /// Links that point to notes or files missing from the export
+    #[serde(rename = "unresolvedLinks")]
+    pub unresolved_links: Vec<String>,
+    /// Per-note problems that did not abort the import
+    pub errors: Vec<String>,
+    /// Nothing was written or sent to the webview, the summary is a preview
+    #[serde(rename = "dryRun")]
+    pub dry_run: bool,
+    pub cancelled: bool,
+}
+
+static IMPORT_CANCELLED: AtomicBool = AtomicBool::new(false);
+
+/// Ask the running import to stop after the current note
+pub fn request_import_cancel() {
+    IMPORT_CANCELLED.store(true, Ordering::SeqCst);
+}
+
+/// Collects converted notes, streams them to the webview and builds the summary
+pub struct ImportSession {
+    app: AppHandle,
+    pending: Vec<ImportedNote>,
+    pub summary: ImportSummary,
+    pub total_bytes: u64,
+}
+
+impl ImportSession {
+    pub fn new(app: &AppHandle, source: &str, total_bytes: u64, dry_run: bool) -> Self {
+        IMPORT_CANCELLED.store(false, Ordering::SeqCst);
+        Self {
+            app: app.clone(),
+            pending: Vec::new(),
+            summary: ImportSummary {
+                import_id: generate_id(),
+                source: source.to_string(),
+                dry_run,
+                ..Default::default()
+            },
+            total_bytes,
+        }
+    }
+
+    pub fn app(&self) -> &AppHandle {
+        &self.app
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        IMPORT_CANCELLED.load(Ordering::SeqCst)
+    }
+
+    pub fn is_dry_run(&self) -> bool {
+        self.summary.dry_run
+    }
+
+    pub fn add_skipped(&mut self, path: String) {
+        self.summary.skipped_files.push(path);
+    }
+
+    pub fn add_unresolved_link(&mut self, link: String) {
+        if !self.summary.unresolved_links.contains(&link) {
+            self.summary.unresolved_links.push(link);
+        }
+    }
+
+    /// Store decoded attachment content; a dry run only describes it
+    pub fn store_attachment_bytes(&mut self, name: &str, bytes: &[u8], source: &str) -> Option<ImportedFile> {
+        if self.is_dry_run() {
+            return Some(preview_file(name, bytes.len() as u64, source));
+        }
+        import_bytes(&self.app, name, bytes, source)
+            .map_err(|e| self.add_error(e))
+            .ok()
+    }
+
+    /// Copy an attachment file from disk; a dry run only describes it
+    pub fn store_attachment_file(&mut self, path: &Path) -> Option<ImportedFile> {
+        if self.is_dry_run() {
+            let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            let size = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+            return Some(preview_file(&name, size, &path.to_string_lossy()));
+        }
+        import_file(&self.app, path)
+            .map_err(|e| self.add_error(e))
+            .ok()
+    }
+
+    pub fn add_error(&mut self, error: String) {
+        error!("❌ Import: {}", error);
+        self.summary.errors.push(error);
+    }
+
+    pub fn add_note(&mut self, note: ImportedNote, processed_bytes: u64) {
+        self.summary.note_count += 1;
+        self.summary.attachment_count += note.attachments.len() as u32;
+        for tag in note.tags.iter() {
+            if !self.summary.tags.contains(tag) {
+                self.summary.tags.push(tag.clone());
+            }
+        }
+
+        let _ = self.app.emit("import-progress", ImportProgress {
+            import_id: self.summary.import_id.clone(),
+            source: self.summary.source.clone(),
+            processed_bytes,
+            total_bytes: self.total_bytes,
+            note_count: self.summary.note_count,
+            current_title: Some(note.title.clone()),
+        });
+
+        if self.is_dry_run() {
+            return;
+        }
+        self.pending.push(note);
+        if self.pending.len() >= NOTE_BATCH_SIZE {
+            self.flush();
+        }
+    }
+
+    fn flush(&mut self) {
+        if self.pending.is_empty() {
+            return;
+        }
+        let batch = ImportNotesBatch {
+            import_id: self.summary.import_id.clone(),
+            notes: std::mem::take(&mut self.pending),
+        };
+        if let Err(e) = self.app.emit("import-notes", &batch) {
+            error!("Failed to emit import-notes event: {}", e);
+        }
+    }
+
+    /// Send the remaining notes and return the summary
+    pub fn finish(mut self) -> ImportSummary {
+        self.flush();
+        self.summary.cancelled = self.is_cancelled();
+        info!(
+            "📥 {} import {}: {} notes, {} attachments, {} errors",
+            self.summary.source,
+            if self.summary.dry_run { "preview" } else { "finished" },
+            self.summary.note_count,
+            self.summary.attachment_count,
+            self.summary.errors.len()
+        );
+        self.summary
+    }
+}
+
+fn preview_file(name: &str, size: u64, source: &str) -> ImportedFile {
+    ImportedFile {
+        name: name.to_string(),
+        path: String::new(),
+        source_path: source.to_string(),
+        size,
+        mime_type: mime_guess::from_path(name).first_or_octet_stream().to_string(),
+        hash: String::new(),
+        imported_at: now_millis(),
+    }
+}
+
+/// Blinko tags are written inline as `#tag`, so whitespace is not allowed
+pub fn normalize_tag(tag: &str) -> String {
+    tag.trim().trim_start_matches('#').split_whitespace().collect::<Vec<_>>().join("_")
+}
+
+/// Assemble the final Markdown with a heading and trailing tag line
+pub fn build_note_content(title: &str, body: &str, tags: &[String]) -> String {
+    let mut content = String::new();
+    if !title.trim().is_empty() {
+        content.push_str(&format!("# {}\n\n", title.trim()));
+    }
+    content.push_str(body.trim());
+    if !tags.is_empty() {
+        let line = tags.iter().map(|t| format!("#{}", t)).collect::<Vec<_>>().join(" ");
+        content.push_str(&format!("\n\n{}", line));
+    }
+    content
+}
+
+/// Make a file name safe to store on any platform
+pub fn sanitize_file_name(name: &str) -> String {
+    let cleaned: String = name.chars()
+        .map(|c| if c.is_control() || "/\\:*?\"<>|".contains(c) { '_' } else { c })
+        .collect();
+    let cleaned = cleaned.trim().to_string();
+    if cleaned.is_empty() { "attachment".to_string() } else { cleaned }
+}
+
+/// Decode `%20`-style escapes used in exported Markdown links
+pub fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len()
+            && bytes[i + 1].is_ascii_hexdigit() && bytes[i + 2].is_ascii_hexdigit()
+        {
+            if let Ok(byte) = u8::from_str_radix(&value[i + 1..i + 3], 16) {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+/// Rewrite the target of every `[text](target)` / `![alt](target)` link.
+/// `rewrite` returns the new target, or `None` to keep the original.
+pub fn rewrite_markdown_links(content: &str, mut rewrite: impl FnMut(&str) -> Option<String>) -> String {
+    let mut output = String::with_capacity(content.len());
+    let mut rest = content;
+
+    while let Some(start) = rest.find("](") {
+        let target_start = start + 2;
+        let Some(len) = rest[target_start..].find(')') else { break };
+        let target = &rest[target_start..target_start + len];
+        output.push_str(&rest[..target_start]);
+        match rewrite(target.trim_matches(|c| c == '<' || c == '>')) {
+            Some(new_target) => output.push_str(&new_target),
+            None => output.push_str(target),
+        }
+        output.push(')');
+        rest = &rest[target_start + len + 1..];
+    }
+
+    output.push_str(rest);
+    output
+}
+
+/// Links with a scheme or an anchor are left alone by the importers
+pub fn is_external_link(target: &str) -> bool {
+    target.starts_with('#') || target.contains("://") || target.starts_with("mailto:")
+}
@@ -0,0 +1,225 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::NaiveDateTime;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::Path;
+
+use crate::desktop::ImportedFile;
+use crate::importer::{build_note_content, normalize_tag, sanitize_file_name, ImportSession, ImportSummary, ImportedNote};
+
+#[derive(Debug, Default)]
+struct EnexResource {
+    data: String,
+    mime: String,
+    file_name: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct EnexNote {
+    title: String,
+    content: String,
+    created: Option<u64>,
+    updated: Option<u64>,
+    tags: Vec<String>,
+    resources: Vec<EnexResource>,
+}
+
+/// ENEX timestamps look like `20230115T103000Z`
+fn parse_enex_date(value: &str) -> Option<u64> {
+    NaiveDateTime::parse_from_str(value.trim(), "%Y%m%dT%H%M%SZ")
+        .ok()
+        .map(|dt| dt.and_utc().timestamp_millis() as u64)
+}
+
+fn attribute_value(tag: &str, name: &str) -> Option<String> {
+    let needle = format!("{}=\"", name);
+    let start = tag.find(&needle)? + needle.len();
+    let end = tag[start..].find('"')? + start;
+    Some(tag[start..end].to_string())
+}
+
+/// Swap `<en-media hash="...">` placeholders for regular image/link elements
+fn replace_en_media(enml: &str, files: &HashMap<String, ImportedFile>) -> String {
+    let mut output = String::with_capacity(enml.len());
+    let mut rest = enml;
+
+    while let Some(start) = rest.find("<en-media") {
+        output.push_str(&rest[..start]);
+        let Some(tag_end) = rest[start..].find('>').map(|i| start + i + 1) else {
+            rest = &rest[start..];
+            break;
+        };
+        let tag = &rest[start..tag_end];
+        let mut after = &rest[tag_end..];
+        if !tag.ends_with("/>") {
+            if let Some(close) = after.find("</en-media>") {
+                after = &after[close + "</en-media>".len()..];
+            }
+        }
+
+        if let Some(file) = attribute_value(tag, "hash").and_then(|hash| files.get(&hash)) {
+            if file.mime_type.starts_with("image/") {
+                output.push_str(&format!("<img src=\"{0}\" alt=\"{0}\">", file.name));
+            } else {
+                output.push_str(&format!("<a href=\"{0}\">{0}</a>", file.name));
+            }
+        }
+        rest = after;
+    }
+
+    output.push_str(rest);
+    output
+}
+
+fn convert_enex_note(session: &mut ImportSession, source: &str, note: EnexNote) -> ImportedNote {
+    let mut attachments = Vec::new();
+    let mut by_hash = HashMap::new();
+
+    for (index, resource) in note.resources.into_iter().enumerate() {
+        let cleaned: String = resource.data.chars().filter(|c| !c.is_whitespace()).collect();
+        let bytes = match STANDARD.decode(cleaned) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                session.add_error(format!("Invalid attachment data in \"{}\": {}", note.title, e));
+                continue;
+            }
+        };
+
+        let name = resource.file_name
+            .map(|n| sanitize_file_name(&n))
+            .unwrap_or_else(|| {
+                let ext = mime_guess::get_mime_extensions_str(&resource.mime)
+                    .and_then(|exts| exts.first())
+                    .unwrap_or(&"bin");
+                format!("attachment-{}.{}", index + 1, ext)
+            });
+
+        if let Some(file) = session.store_attachment_bytes(&name, &bytes, source) {
+            // ENML references resources by the MD5 of their content
+            by_hash.insert(format!("{:x}", md5::compute(&bytes)), file.clone());
+            attachments.push(file);
+        }
+    }
+
+    let body = html2md::parse_html(&replace_en_media(&note.content, &by_hash));
+    let tags: Vec<String> = note.tags.iter()
+        .map(|t| normalize_tag(t))
+        .filter(|t| !t.is_empty())
+        .collect();
+
+    ImportedNote {
+        content: build_note_content(&note.title, &body, &tags),
+        title: note.title,
+        tags,
+        created_at: note.created,
+        updated_at: note.updated.or(note.created),
+        attachments,
+    }
+}
+
+/// Stream an ENEX export note by note, so multi-gigabyte archives don't have to fit in memory
+pub fn import_enex_file(session: &mut ImportSession, path: &Path) -> Result<(), String> {
+    let file = File::open(path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let source = path.to_string_lossy().to_string();
+    let mut reader = Reader::from_reader(BufReader::new(file));
+    reader.config_mut().trim_text(true);
+
+    let mut buf = Vec::new();
+    let mut stack: Vec<String> = Vec::new();
+    let mut text = String::new();
+    let mut current: Option<EnexNote> = None;
+
+    loop {
+        if session.is_cancelled() {
+            info!("⏹️ ENEX import cancelled");
+            break;
+        }
+
+        let event = reader.read_event_into(&mut buf)
+            .map_err(|e| format!("Invalid ENEX file at byte {}: {}", reader.buffer_position(), e))?;
+
+        match event {
+            Event::Start(e) => {
+                let name = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                match name.as_str() {
+                    "note" => current = Some(EnexNote::default()),
+                    "resource" => {
+                        if let Some(ref mut note) = current {
+                            note.resources.push(EnexResource::default());
+                        }
+                    }
+                    _ => {}
+                }
+                stack.push(name);
+                text.clear();
+            }
+            Event::Text(e) => {
+                let value = e.unescape().map_err(|e| format!("Invalid ENEX text: {}", e))?;
+                text.push_str(&value);
+            }
+            Event::CData(e) => {
+                text.push_str(&String::from_utf8_lossy(&e.into_inner()));
+            }
+            Event::End(_) => {
+                let name = stack.pop().unwrap_or_default();
+                let parent = stack.last().map(String::as_str).unwrap_or_default();
+
+                if let Some(ref mut note) = current {
+                    let value = std::mem::take(&mut text);
+                    match (parent, name.as_str()) {
+                        ("note", "title") => note.title = value.trim().to_string(),
+                        ("note", "content") => note.content = value,
+                        ("note", "created") => note.created = parse_enex_date(&value),
+                        ("note", "updated") => note.updated = parse_enex_date(&value),
+                        ("note", "tag") => note.tags.push(value),
+                        ("resource", "data") => {
+                            if let Some(resource) = note.resources.last_mut() {
+                                resource.data = value;
+                            }
+                        }
+                        ("resource", "mime") => {
+                            if let Some(resource) = note.resources.last_mut() {
+                                resource.mime = value.trim().to_string();
+                            }
+                        }
+                        ("resource-attributes", "file-name") => {
+                            if let Some(resource) = note.resources.last_mut() {
+                                resource.file_name = Some(value.trim().to_string()).filter(|n| !n.is_empty());
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+
+                if name == "note" {
+                    if let Some(note) = current.take() {
+                        let imported = convert_enex_note(session, &source, note);
+                        session.add_note(imported, reader.buffer_position() as u64);
+                    }
+                }
+                text.clear();
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(())
+}
+
+/// Import an Evernote export, streaming converted notes to the webview
+pub fn run_enex_import(app: &tauri::AppHandle, path: &Path, dry_run: bool) -> Result<ImportSummary, String> {
+    let total_bytes = fs::metadata(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?
+        .len();
+    info!("📥 Importing ENEX archive {}", path.display());
+
+    let mut session = ImportSession::new(app, "enex", total_bytes, dry_run);
+    import_enex_file(&mut session, path)?;
+    Ok(session.finish())
+}
@@ -0,0 +1,11 @@
+pub mod common;
+pub mod enex;
+pub mod notion;
+pub mod obsidian;
+pub mod commands;
+
+pub use common::*;
+pub use enex::*;
+pub use notion::*;
+pub use obsidian::*;
+pub use commands::*;
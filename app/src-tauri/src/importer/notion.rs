@@ -0,0 +1,186 @@
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{Cursor, Read, Seek};
+use std::path::Path;
+
+use zip::ZipArchive;
+
+use crate::importer::{
+    build_note_content, is_external_link, normalize_tag, percent_decode, rewrite_markdown_links, sanitize_file_name,
+    ImportSession, ImportSummary, ImportedNote,
+};
+
+/// Notion appends a 32 character hex id to every exported page and folder
+fn strip_notion_id(stem: &str) -> String {
+    match stem.rsplit_once(' ') {
+        Some((title, id)) if id.len() == 32 && id.chars().all(|c| c.is_ascii_hexdigit()) => title.to_string(),
+        _ => stem.to_string(),
+    }
+}
+
+/// Resolve a link relative to the page that contains it, zip paths always use `/`
+fn resolve_zip_path(page: &str, target: &str) -> String {
+    let mut parts: Vec<&str> = page.rsplit_once('/').map(|(dir, _)| dir.split('/').collect()).unwrap_or_default();
+    for part in target.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            part => parts.push(part),
+        }
+    }
+    parts.join("/")
+}
+
+fn read_entry<R: Read + Seek>(archive: &mut ZipArchive<R>, name: &str) -> Result<Vec<u8>, String> {
+    let mut entry = archive.by_name(name)
+        .map_err(|e| format!("Failed to read {} from archive: {}", name, e))?;
+    let mut bytes = Vec::with_capacity(entry.size() as usize);
+    entry.read_to_end(&mut bytes)
+        .map_err(|e| format!("Failed to read {} from archive: {}", name, e))?;
+    Ok(bytes)
+}
+
+fn is_page(name: &str) -> bool {
+    let lower = name.to_lowercase();
+    lower.ends_with(".md") || lower.ends_with(".html")
+}
+
+/// Pull the `# Title` heading and the `Tags:` property line out of an exported page
+fn split_notion_page(markdown: &str, fallback_title: String) -> (String, Vec<String>, String) {
+    let mut lines: Vec<&str> = markdown.trim_start().lines().collect();
+    let mut title = fallback_title;
+    if let Some(heading) = lines.first().and_then(|l| l.strip_prefix("# ")) {
+        title = heading.trim().to_string();
+        lines.remove(0);
+    }
+
+    // Page properties are rendered as `Key: value` lines right below the title
+    let mut tags = Vec::new();
+    let first = lines.iter().position(|l| !l.trim().is_empty()).unwrap_or(lines.len());
+    let mut i = first;
+    while i < lines.len() && !lines[i].trim().is_empty() {
+        let line = lines[i];
+        if let Some(value) = line.strip_prefix("Tags: ").or_else(|| line.strip_prefix("Tags:")) {
+            tags.extend(value.split(',').map(normalize_tag).filter(|t| !t.is_empty()));
+            lines.remove(i);
+            continue;
+        }
+        if !line.contains(": ") {
+            break;
+        }
+        i += 1;
+    }
+
+    (title, tags, lines.join("\n"))
+}
+
+fn convert_notion_page<R: Read + Seek>(
+    session: &mut ImportSession,
+    archive: &mut ZipArchive<R>,
+    names: &HashSet<String>,
+    referenced: &mut HashSet<String>,
+    page: &str,
+    source: &str,
+) -> Result<ImportedNote, String> {
+    let raw = String::from_utf8_lossy(&read_entry(archive, page)?).to_string();
+    let markdown = if page.to_lowercase().ends_with(".html") { html2md::parse_html(&raw) } else { raw };
+
+    let stem = Path::new(page).file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let (title, tags, body) = split_notion_page(&markdown, strip_notion_id(&stem));
+
+    let mut attachments = Vec::new();
+    let body = rewrite_markdown_links(&body, |target| {
+        if is_external_link(target) {
+            return None;
+        }
+        let resolved = resolve_zip_path(page, &percent_decode(target));
+        if !names.contains(&resolved) {
+            session.add_unresolved_link(format!("{} → {}", title, target));
+            return None;
+        }
+        // Links between pages have no Blinko equivalent and are kept as they are
+        if is_page(&resolved) {
+            return None;
+        }
+
+        referenced.insert(resolved.clone());
+        let name = sanitize_file_name(resolved.rsplit('/').next().unwrap_or(&resolved));
+        let bytes = read_entry(archive, &resolved).map_err(|e| session.add_error(e)).ok()?;
+        let file = session.store_attachment_bytes(&name, &bytes, source)?;
+        attachments.push(file);
+        Some(name)
+    });
+
+    Ok(ImportedNote {
+        content: build_note_content(&title, &body, &tags),
+        title,
+        tags,
+        created_at: None,
+        updated_at: None,
+        attachments,
+    })
+}
+
+fn import_notion_archive<R: Read + Seek>(
+    session: &mut ImportSession,
+    archive: &mut ZipArchive<R>,
+    source: &str,
+) -> Result<(), String> {
+    let mut entries = Vec::new();
+    for index in 0..archive.len() {
+        let entry = archive.by_index(index)
+            .map_err(|e| format!("Failed to read archive entry: {}", e))?;
+        if !entry.is_dir() {
+            entries.push((entry.name().to_string(), entry.compressed_size()));
+        }
+    }
+    let names: HashSet<String> = entries.iter().map(|(name, _)| name.clone()).collect();
+    let mut referenced = HashSet::new();
+    let mut processed_bytes = 0u64;
+
+    for (name, size) in entries.iter() {
+        if session.is_cancelled() {
+            info!("⏹️ Notion import cancelled");
+            return Ok(());
+        }
+        processed_bytes += size;
+
+        if name.to_lowercase().ends_with(".zip") {
+            // Large workspaces are exported as several nested part archives
+            let bytes = read_entry(archive, name)?;
+            let mut nested = ZipArchive::new(Cursor::new(bytes))
+                .map_err(|e| format!("Failed to open nested archive {}: {}", name, e))?;
+            import_notion_archive(session, &mut nested, source)?;
+        } else if is_page(name) {
+            match convert_notion_page(session, archive, &names, &mut referenced, name, source) {
+                Ok(note) => session.add_note(note, processed_bytes),
+                Err(e) => session.add_error(e),
+            }
+        }
+    }
+
+    for (name, _) in entries.iter() {
+        if !is_page(name) && !name.to_lowercase().ends_with(".zip") && !referenced.contains(name) {
+            session.add_skipped(name.clone());
+        }
+    }
+    Ok(())
+}
+
+/// Import a Notion "Markdown & CSV" or "HTML" workspace export
+pub fn run_notion_import(app: &tauri::AppHandle, path: &Path, dry_run: bool) -> Result<ImportSummary, String> {
+    let total_bytes = fs::metadata(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?
+        .len();
+    let file = File::open(path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mut archive = ZipArchive::new(file)
+        .map_err(|e| format!("Not a valid Notion export: {}", e))?;
+    info!("📥 Importing Notion export {}", path.display());
+
+    let mut session = ImportSession::new(app, "notion", total_bytes, dry_run);
+    import_notion_archive(&mut session, &mut archive, &path.to_string_lossy())?;
+    Ok(session.finish())
+}
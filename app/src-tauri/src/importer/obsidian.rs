@@ -0,0 +1,255 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use crate::desktop::ImportedFile;
+use crate::importer::{
+    build_note_content, is_external_link, normalize_tag, percent_decode, rewrite_markdown_links, ImportSession,
+    ImportSummary, ImportedNote,
+};
+
+/// Files in a vault, looked up the way Obsidian resolves links: by lowercase file name
+struct VaultIndex {
+    root: PathBuf,
+    by_name: HashMap<String, Vec<PathBuf>>,
+}
+
+impl VaultIndex {
+    fn build(root: &Path) -> Result<(Self, Vec<PathBuf>), String> {
+        let mut index = VaultIndex { root: root.to_path_buf(), by_name: HashMap::new() };
+        let mut files = Vec::new();
+        let mut dirs = vec![root.to_path_buf()];
+
+        while let Some(dir) = dirs.pop() {
+            let entries = fs::read_dir(&dir)
+                .map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+            for entry in entries.flatten() {
+                let path = entry.path();
+                let name = entry.file_name().to_string_lossy().to_string();
+                // Skip .obsidian settings, .trash and other hidden folders
+                if name.starts_with('.') {
+                    continue;
+                }
+                if path.is_dir() {
+                    dirs.push(path);
+                } else {
+                    index.by_name.entry(name.to_lowercase()).or_default().push(path.clone());
+                    files.push(path);
+                }
+            }
+        }
+
+        files.sort();
+        Ok((index, files))
+    }
+
+    /// `target` may be a bare name, a vault-relative path, or a path relative to the note
+    fn resolve(&self, note_dir: &Path, target: &str) -> Option<PathBuf> {
+        let has_extension = Path::new(target).extension().is_some();
+        let candidates: Vec<String> = if has_extension {
+            vec![target.to_string()]
+        } else {
+            vec![format!("{}.md", target), target.to_string()]
+        };
+
+        for candidate in candidates.iter() {
+            for base in [note_dir, self.root.as_path()] {
+                let path = base.join(candidate);
+                if path.is_file() {
+                    return Some(path);
+                }
+            }
+            let file_name = candidate.rsplit('/').next().unwrap_or(candidate).to_lowercase();
+            if let Some(paths) = self.by_name.get(&file_name) {
+                return paths.first().cloned();
+            }
+        }
+        None
+    }
+}
+
+fn is_markdown(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("md"))
+}
+
+/// Split YAML front matter into its tags and the remaining body.
+/// Handles both `tags: [a, b]` and block lists.
+fn split_front_matter(text: &str) -> (Vec<String>, String) {
+    let normalized = text.replace("\r\n", "\n");
+    let Some(rest) = normalized.strip_prefix("---\n") else {
+        return (Vec::new(), normalized);
+    };
+    let Some(end) = rest.find("\n---") else {
+        return (Vec::new(), normalized);
+    };
+
+    let mut tags = Vec::new();
+    let mut in_tags = false;
+    for line in rest[..end].lines() {
+        if in_tags {
+            if let Some(item) = line.trim_start().strip_prefix("- ") {
+                tags.push(normalize_tag(item.trim_matches('"')));
+                continue;
+            }
+            in_tags = false;
+        }
+        let Some((key, value)) = line.split_once(':') else { continue };
+        if matches!(key.trim(), "tags" | "tag") {
+            let value = value.trim();
+            if value.is_empty() {
+                in_tags = true;
+            } else {
+                tags.extend(
+                    value.trim_matches(|c| c == '[' || c == ']')
+                        .split(',')
+                        .map(|t| normalize_tag(t.trim().trim_matches('"'))),
+                );
+            }
+        }
+    }
+
+    tags.retain(|t| !t.is_empty());
+    let body = rest[end + 4..].trim_start_matches('\n').to_string();
+    (tags, body)
+}
+
+fn attachment_markdown(file: &ImportedFile) -> String {
+    if file.mime_type.starts_with("image/") {
+        format!("![{0}]({0})", file.name)
+    } else {
+        format!("[{0}]({0})", file.name)
+    }
+}
+
+/// Replace `[[target|alias]]` and `![[embed]]` links.
+/// `rewrite(is_embed, target, alias)` returns the replacement text or `None` to keep the link.
+fn rewrite_wikilinks(content: &str, mut rewrite: impl FnMut(bool, &str, Option<&str>) -> Option<String>) -> String {
+    let mut output = String::with_capacity(content.len());
+    let mut rest = content;
+
+    while let Some(start) = rest.find("[[") {
+        let Some(len) = rest[start + 2..].find("]]") else { break };
+        let is_embed = start > 0 && rest.as_bytes()[start - 1] == b'!';
+        let inner = &rest[start + 2..start + 2 + len];
+        let (link, alias) = match inner.split_once('|') {
+            Some((link, alias)) => (link, Some(alias)),
+            None => (inner, None),
+        };
+        // Heading and block references point inside the target note
+        let target = link.split('#').next().unwrap_or(link).trim();
+
+        let prefix_end = if is_embed { start - 1 } else { start };
+        let original = &rest[prefix_end..start + 4 + len];
+        output.push_str(&rest[..prefix_end]);
+        match rewrite(is_embed, target, alias) {
+            Some(replacement) => output.push_str(&replacement),
+            None => output.push_str(original),
+        }
+        rest = &rest[start + 4 + len..];
+    }
+
+    output.push_str(rest);
+    output
+}
+
+fn file_time_millis(time: std::io::Result<std::time::SystemTime>) -> Option<u64> {
+    time.ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as u64)
+}
+
+fn convert_obsidian_note(
+    session: &mut ImportSession,
+    index: &VaultIndex,
+    used: &mut HashSet<PathBuf>,
+    path: &Path,
+) -> Result<ImportedNote, String> {
+    let text = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let metadata = fs::metadata(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let title = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let note_dir = path.parent().unwrap_or(&index.root);
+    let (tags, body) = split_front_matter(&text);
+
+    let mut attachments = Vec::new();
+    let body = rewrite_wikilinks(&body, |is_embed, target, alias| {
+        if target.is_empty() {
+            return None;
+        }
+        let Some(resolved) = index.resolve(note_dir, target) else {
+            session.add_unresolved_link(format!("{} → {}", title, target));
+            return None;
+        };
+        if is_markdown(&resolved) {
+            // Embedded notes become plain links, Blinko can't transclude
+            return if is_embed {
+                Some(format!("[[{}{}]]", target, alias.map(|a| format!("|{}", a)).unwrap_or_default()))
+            } else {
+                None
+            };
+        }
+        used.insert(resolved.clone());
+        let file = session.store_attachment_file(&resolved)?;
+        let markdown = attachment_markdown(&file);
+        attachments.push(file);
+        Some(markdown)
+    });
+
+    let body = rewrite_markdown_links(&body, |target| {
+        // Skip external links and the embeds converted above
+        if is_external_link(target) || attachments.iter().any(|f: &ImportedFile| f.name == target) {
+            return None;
+        }
+        let decoded = percent_decode(target);
+        let resolved = index.resolve(note_dir, &decoded).filter(|p| !is_markdown(p))?;
+        used.insert(resolved.clone());
+        let file = session.store_attachment_file(&resolved)?;
+        let name = file.name.clone();
+        attachments.push(file);
+        Some(name)
+    });
+
+    Ok(ImportedNote {
+        content: build_note_content(&title, &body, &tags),
+        title,
+        tags,
+        created_at: file_time_millis(metadata.created()),
+        updated_at: file_time_millis(metadata.modified()),
+        attachments,
+    })
+}
+
+/// Import every Markdown note of an Obsidian vault with its embedded attachments
+pub fn run_obsidian_import(app: &tauri::AppHandle, vault: &Path, dry_run: bool) -> Result<ImportSummary, String> {
+    if !vault.is_dir() {
+        return Err(format!("Not a folder: {}", vault.display()));
+    }
+    let (index, files) = VaultIndex::build(vault)?;
+    let notes: Vec<&PathBuf> = files.iter().filter(|p| is_markdown(p)).collect();
+    let total_bytes = notes.iter().filter_map(|p| fs::metadata(p).ok()).map(|m| m.len()).sum();
+    info!("📥 Importing Obsidian vault {} ({} notes)", vault.display(), notes.len());
+
+    let mut session = ImportSession::new(app, "obsidian", total_bytes, dry_run);
+    let mut used = HashSet::new();
+    let mut processed_bytes = 0u64;
+    for path in notes {
+        if session.is_cancelled() {
+            info!("⏹️ Obsidian import cancelled");
+            break;
+        }
+        processed_bytes += fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        match convert_obsidian_note(&mut session, &index, &mut used, path) {
+            Ok(note) => session.add_note(note, processed_bytes),
+            Err(e) => session.add_error(e),
+        }
+    }
+
+    // Files no note embeds are reported so the user knows they were left behind
+    for path in files.iter().filter(|p| !is_markdown(p) && !used.contains(*p)) {
+        let relative = path.strip_prefix(vault).unwrap_or(path);
+        session.add_skipped(relative.to_string_lossy().to_string());
+    }
+    Ok(session.finish())
+}
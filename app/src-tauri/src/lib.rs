@@ -1,16 +1,40 @@
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
+#[macro_use]
+extern crate tracing;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
 mod desktop;
 #[cfg(all(target_os = "windows", any(feature = "whisper-cuda", feature = "whisper-cpu")))]
 mod voice;
+#[cfg(all(not(any(target_os = "android", target_os = "ios")), feature = "local-llm"))]
+mod llm;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+mod importer;
+#[cfg(any(target_os = "android", target_os = "ios"))]
+mod mobile_voice;
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 use desktop::*;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+use importer::*;
+#[cfg(all(not(any(target_os = "android", target_os = "ios")), feature = "local-llm"))]
+use llm::*;
 #[cfg(all(target_os = "windows", any(feature = "whisper-cuda", feature = "whisper-cpu")))]
 use voice::*;
-use tauri::Manager;
+#[cfg(any(target_os = "android", target_os = "ios"))]
+use mobile_voice::*;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    let mut builder = tauri::Builder::default()
+    let mut builder = tauri::Builder::default();
+
+    // Single instance must be registered first so a second launch hands over before anything else starts
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    {
+        builder = builder.plugin(tauri_plugin_single_instance::init(|app, args, cwd| {
+            handle_second_instance(app, args, cwd);
+        }));
+    }
+
+    builder = builder
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_upload::init())
         .plugin(tauri_plugin_http::init())
@@ -23,36 +47,20 @@ pub fn run() {
     #[cfg(not(any(target_os = "android", target_os = "ios")))]
     {
         builder = builder
-            .plugin(tauri_plugin_single_instance::init(|app, args, cwd| {
-                // Called when a second instance tries to start
-                println!("Second instance detected with args: {:?} and cwd: {:?}", args, cwd);
-
-                // Show and focus the existing window
-                if let Some(window) = app.get_webview_window("main") {
-                    // Show window if it's hidden
-                    if let Err(e) = window.show() {
-                        eprintln!("Failed to show window: {}", e);
-                    }
-
-                    // Unminimize if minimized
-                    if let Err(e) = window.unminimize() {
-                        eprintln!("Failed to unminimize window: {}", e);
-                    }
-
-                    // Bring to front and focus
-                    if let Err(e) = window.set_focus() {
-                        eprintln!("Failed to focus window: {}", e);
-                    }
-
-                    println!("Focused existing Blinko window");
-                }
-            }))
             .plugin(tauri_plugin_updater::Builder::new().build())
+            .plugin(tauri_plugin_notification::init())
+            .plugin(tauri_plugin_deep_link::init())
             .plugin(
                 tauri_plugin_global_shortcut::Builder::new()
                     .with_handler(create_global_shortcut_handler())
                     .build()
-            );
+            )
+            .manage(ShortcutRegistry::default());
+    }
+
+    #[cfg(all(target_os = "windows", any(feature = "whisper-cuda", feature = "whisper-cpu")))]
+    {
+        builder = builder.manage(VoiceState::default());
     }
 
     #[cfg(not(any(target_os = "android", target_os = "ios")))]
@@ -77,6 +85,310 @@ pub fn run() {
                 show_quicktool,
                 set_desktop_theme,
                 set_desktop_colors,
+                get_clipboard_watcher_config,
+                save_clipboard_watcher_config,
+                get_clipboard_history,
+                pin_clipboard_item,
+                get_ocr_config,
+                save_ocr_config,
+                recognize_text_in_image,
+                start_screen_recording,
+                stop_screen_recording,
+                is_screen_recording,
+                pick_color,
+                confirm_color_pick,
+                cancel_color_pick,
+                list_reminders,
+                create_reminder,
+                update_reminder,
+                delete_reminder,
+                start_focus_timer,
+                pause_focus_timer,
+                resume_focus_timer,
+                stop_focus_timer,
+                get_focus_timer_status,
+                set_badge_count,
+                take_pending_deep_links,
+                get_folder_watch_config,
+                add_watched_folder,
+                update_watched_folder,
+                remove_watched_folder,
+                set_folder_watch_paused,
+                get_markdown_sync_config,
+                save_markdown_sync_config,
+                sync_notes_to_folder,
+                link_markdown_note,
+                search_notes,
+                index_notes,
+                remove_notes_from_index,
+                clear_search_index,
+                #[cfg(feature = "semantic-search")]
+                embed_notes,
+                #[cfg(feature = "semantic-search")]
+                remove_note_embeddings,
+                #[cfg(feature = "semantic-search")]
+                semantic_search,
+                #[cfg(feature = "semantic-search")]
+                build_quickai_context,
+                #[cfg(all(not(any(target_os = "android", target_os = "ios")), feature = "local-llm"))]
+                get_llm_config,
+                #[cfg(all(not(any(target_os = "android", target_os = "ios")), feature = "local-llm"))]
+                save_llm_config_cmd,
+                #[cfg(all(not(any(target_os = "android", target_os = "ios")), feature = "local-llm"))]
+                list_llm_models,
+                #[cfg(all(not(any(target_os = "android", target_os = "ios")), feature = "local-llm"))]
+                download_llm_model,
+                #[cfg(all(not(any(target_os = "android", target_os = "ios")), feature = "local-llm"))]
+                cancel_llm_download,
+                #[cfg(all(not(any(target_os = "android", target_os = "ios")), feature = "local-llm"))]
+                delete_llm_model,
+                #[cfg(all(not(any(target_os = "android", target_os = "ios")), feature = "local-llm"))]
+                ask_local_llm,
+                #[cfg(all(not(any(target_os = "android", target_os = "ios")), feature = "local-llm"))]
+                cancel_local_llm,
+                #[cfg(all(not(any(target_os = "android", target_os = "ios")), feature = "local-llm"))]
+                unload_local_llm,
+                configure_offline_sync,
+                cache_notes,
+                get_cached_notes,
+                queue_note_change,
+                get_sync_status,
+                get_pending_operations,
+                replay_offline_queue,
+                resolve_offline_conflict,
+                retry_failed_operations,
+                get_connectivity_status,
+                check_connectivity_now,
+                enqueue_download,
+                list_downloads,
+                pause_download,
+                resume_download,
+                cancel_download,
+                clear_finished_downloads,
+                enqueue_upload,
+                list_uploads,
+                pause_upload,
+                resume_upload,
+                cancel_upload,
+                clear_finished_uploads,
+                store_token,
+                get_token,
+                delete_token,
+                list_accounts,
+                add_account,
+                update_account_token,
+                switch_account,
+                remove_account,
+                get_network_config,
+                save_network_config,
+                detect_system_proxy,
+                get_network_fetch_options,
+                get_local_api_config,
+                set_local_api_enabled,
+                rotate_local_api_token,
+                list_webhooks,
+                add_webhook,
+                update_webhook,
+                remove_webhook,
+                get_webhook_delivery_log,
+                clear_webhook_delivery_log,
+                test_webhook,
+                trigger_webhook_event,
+                get_mqtt_config,
+                save_mqtt_config,
+                get_mqtt_status,
+                mqtt_publish,
+                import_enex,
+                import_notion_zip,
+                import_obsidian_vault,
+                cancel_import,
+                get_backup_config,
+                save_backup_config,
+                create_backup,
+                preview_backup,
+                restore_backup,
+                list_backups,
+                get_encryption_status,
+                set_encryption_passphrase,
+                change_encryption_passphrase,
+                disable_encryption,
+                unlock_encryption,
+                lock_encryption,
+                get_image_processing_config,
+                save_image_processing_config,
+                process_image,
+                import_attachment,
+                get_thumbnail,
+                clear_thumbnail_cache,
+                get_thumbnail_config,
+                save_thumbnail_config,
+                get_recent_logs,
+                get_log_level,
+                set_log_level,
+                get_log_directory,
+                get_last_crash_report,
+                list_crash_reports,
+                dismiss_crash_report,
+                get_update_config,
+                set_update_channel,
+                check_for_updates_now,
+                download_update,
+                install_update_on_quit,
+                install_update_now,
+                get_portable_info,
+                get_data_directory_info,
+                set_data_directory,
+                get_autostart_status,
+                enable_autostart,
+                disable_autostart,
+                get_autostart_details,
+                get_idle_seconds,
+                get_idle_config,
+                save_idle_config,
+                get_dnd_status,
+                get_system_theme,
+                set_window_effect,
+                get_window_effects,
+                get_titlebar_config,
+                save_titlebar_config,
+                start_dragging,
+                start_resize_dragging,
+                minimize,
+                toggle_maximize,
+                is_window_maximized,
+                close_to_tray,
+                get_zoom,
+                set_zoom,
+                step_zoom,
+                print_to_pdf,
+                toggle_palette_window,
+                hide_palette_window,
+                update_palette_items,
+                search_palette,
+                select_palette_item,
+                register_shortcut_route,
+                unregister_shortcut_route,
+                get_shortcut_routes,
+                get_mouse_gesture_config,
+                save_mouse_gesture_config,
+                set_trigger_route,
+                get_hot_corner_config,
+                save_hot_corner_config,
+                get_restore_session,
+                set_restore_session,
+                set_session_draft,
+                take_session_draft,
+                save_quicknote_draft,
+                load_quicknote_draft,
+                clear_quicknote_draft,
+                list_templates,
+                save_template,
+                delete_template,
+                render_template,
+                get_daily_journal_config,
+                save_daily_journal_config,
+                open_daily_note,
+                get_snippet_config,
+                save_snippet_config,
+                evaluate_expression,
+                list_plugins,
+                enable_plugin,
+                reload_plugins,
+                get_plugins_directory,
+                apply_capture_plugins,
+                get_scripts_config,
+                save_scripts_config,
+                list_scripts,
+                run_script,
+                get_scripts_directory,
+                get_calendar_export_config,
+                save_calendar_export_config,
+                set_calendar_notes,
+                export_calendar,
+                get_caldav_config,
+                save_caldav_account,
+                remove_caldav_account,
+                set_caldav_todos,
+                link_caldav_todo,
+                sync_caldav_now,
+                get_s3_config,
+                save_s3_config,
+                test_s3_connection,
+                get_note_history_config,
+                save_note_history_config,
+                get_note_history,
+                restore_note_version,
+                list_note_versions,
+                diff_note_versions,
+                resolve_conflict,
+                get_sync_scheduler_config,
+                save_sync_scheduler_config,
+                get_sync_scheduler_status,
+                pause_sync,
+                resume_sync,
+                provide_refreshed_token,
+                get_api_queue,
+                clear_api_queue,
+                get_server_health,
+                export_site,
+                export_note,
+                export_tag,
+                speak_text,
+                stop_speaking,
+                list_tts_voices,
+                pause_speaking,
+                resume_speaking,
+                get_media_keys_config,
+                save_media_keys_config,
+                get_activity_config,
+                save_activity_config,
+                set_active_note,
+                get_activity_report,
+                clear_activity,
+                enter_focus_mode,
+                exit_focus_mode,
+                is_focus_mode,
+                set_capture_paused,
+                get_capture_paused,
+                get_privacy_guard_config,
+                save_privacy_guard_config,
+                get_privacy_guard_status,
+                get_capture_source,
+                capture_current_page,
+                clip_url,
+                get_feeds_config,
+                save_feeds_settings,
+                add_feed,
+                update_feed,
+                remove_feed,
+                refresh_feeds,
+                save_feed_items,
+                mark_feed_read,
+                get_mail_config,
+                save_mail_settings,
+                save_mail_account,
+                set_mail_account_enabled,
+                remove_mail_account,
+                check_mail_now,
+                register_share_target,
+                unregister_share_target,
+                is_share_target_registered,
+                get_control_socket_config,
+                set_control_socket_enabled,
+                rotate_control_socket_token,
+                get_control_actions,
+                register_context_menu,
+                unregister_context_menu,
+                is_context_menu_registered,
+                get_os_search_config,
+                set_os_search_enabled,
+                refresh_os_search_index,
+                get_resource_usage,
+                get_cache_stats,
+                clear_cache,
+                get_storage_settings,
+                save_storage_settings,
+                take_pending_quicknote_text,
                 // Voice recognition commands (Windows only with whisper features)
                 #[cfg(all(target_os = "windows", any(feature = "whisper-cuda", feature = "whisper-cpu")))]
                 get_voice_config,
@@ -91,30 +403,35 @@ pub fn run() {
                 #[cfg(all(target_os = "windows", any(feature = "whisper-cuda", feature = "whisper-cpu")))]
                 get_voice_status,
                 #[cfg(all(target_os = "windows", any(feature = "whisper-cuda", feature = "whisper-cpu")))]
-                is_cuda_available
+                is_cuda_available,
+                #[cfg(all(target_os = "windows", any(feature = "whisper-cuda", feature = "whisper-cpu")))]
+                record_audio_note,
+                #[cfg(all(target_os = "windows", any(feature = "whisper-cuda", feature = "whisper-cpu")))]
+                stop_audio_note
             ])
             .setup(|app| {
-                #[cfg(not(any(target_os = "android", target_os = "ios")))]
-                {
-                    use tauri_plugin_autostart::MacosLauncher;
-
-                    let _ = app.handle().plugin(tauri_plugin_autostart::init(
-                        MacosLauncher::LaunchAgent,
-                        Some(vec!["--autostart"]),
-                    ));
-                }
-
                 setup_app(app)?;
                 Ok(())
             })
-            .run(tauri::generate_context!())
-            .expect("error while running tauri application");
+            .build(tauri::generate_context!())
+            .expect("error while building tauri application")
+            .run(|app, event| match event {
+                tauri::RunEvent::ExitRequested { .. } => {
+                    save_session(app);
+                    flush_quicknote_draft(app);
+                }
+                tauri::RunEvent::Exit => install_pending_update_on_exit(app),
+                _ => {}
+            });
     }
 
     #[cfg(any(target_os = "android", target_os = "ios"))]
     {
         builder
-            .invoke_handler(tauri::generate_handler![])
+            .invoke_handler(tauri::generate_handler![
+                record_audio_note,
+                stop_audio_note
+            ])
             .setup(|_app| {
                 Ok(())
             })
@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::desktop::generate_id;
+use super::{
+    cancel_llm_generation, cancel_llm_model_download, delete_llm_model_file, download_llm_model_file,
+    generate_llm_answer, get_llm_model_path, list_llm_model_infos, load_llm_config, load_llm_model,
+    save_llm_config, unload_llm_model, LlmConfig, LlmModelInfo,
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LlmErrorEvent {
+    #[serde(rename = "requestId")]
+    pub request_id: String,
+    pub error: String,
+}
+
+#[tauri::command]
+pub fn get_llm_config(app: AppHandle) -> LlmConfig {
+    load_llm_config(&app)
+}
+
+#[tauri::command]
+pub async fn save_llm_config_cmd(app: AppHandle, config: LlmConfig) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        if config.enabled && config.active_model.is_none() {
+            return Err("Please download and select a model first".to_string());
+        }
+        save_llm_config(&app, &config)?;
+        if !config.enabled {
+            unload_llm_model();
+        }
+        Ok(())
+    })
+    .await
+    .map_err(|e| format!("Failed to save LLM settings: {}", e))?
+}
+
+#[tauri::command]
+pub fn list_llm_models(app: AppHandle) -> Result<Vec<LlmModelInfo>, String> {
+    list_llm_model_infos(&app)
+}
+
+/// Download a model, progress is reported through `llm-model-download-progress`
+#[tauri::command]
+pub async fn download_llm_model(app: AppHandle, model_id: String) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        download_llm_model_file(&app, &model_id).map(|path| path.to_string_lossy().to_string())
+    })
+    .await
+    .map_err(|e| format!("Download task failed: {}", e))?
+}
+
+#[tauri::command]
+pub fn cancel_llm_download() {
+    cancel_llm_model_download();
+}
+
+#[tauri::command]
+pub fn delete_llm_model(app: AppHandle, model_id: String) -> Result<(), String> {
+    let config = load_llm_config(&app);
+    if config.active_model.as_deref() == Some(model_id.as_str()) {
+        unload_llm_model();
+    }
+    delete_llm_model_file(&app, &model_id)
+}
+
+/// Start a local answer for the quickai window.
+/// Returns a request id right away, tokens arrive as `llm-token` events and the end as `llm-done`.
+#[tauri::command]
+pub fn ask_local_llm(app: AppHandle, prompt: String) -> Result<String, String> {
+    let config = load_llm_config(&app);
+    let model_id = config.active_model.clone()
+        .ok_or("No local model selected")?;
+    let model_path = get_llm_model_path(&app, &model_id)?;
+    let request_id = generate_id();
+
+    let id = request_id.clone();
+    std::thread::spawn(move || {
+        let result = load_llm_model(&model_path, config.gpu_layers)
+            .and_then(|model| generate_llm_answer(&app, &model, &config, &id, &prompt));
+
+        match result {
+            Ok(done) => {
+                info!("🧠 Local LLM answered with {} tokens", done.token_count);
+                let _ = app.emit("llm-done", done);
+            }
+            Err(e) => {
+                error!("❌ Local LLM failed: {}", e);
+                let _ = app.emit("llm-error", LlmErrorEvent { request_id: id, error: e });
+            }
+        }
+    });
+
+    Ok(request_id)
+}
+
+#[tauri::command]
+pub fn cancel_local_llm() {
+    cancel_llm_generation();
+}
+
+#[tauri::command]
+pub fn unload_local_llm() {
+    unload_llm_model();
+}
@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::desktop::{load_json_or_default, save_json};
+
+const LLM_CONFIG_FILE: &str = "llm_config.json";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LlmConfig {
+    /// Answer quick AI prompts locally instead of calling the cloud provider
+    pub enabled: bool,
+
+    /// Id of the downloaded model to use
+    #[serde(rename = "activeModel")]
+    pub active_model: Option<String>,
+
+    /// Context window in tokens
+    #[serde(rename = "contextSize")]
+    pub context_size: u32,
+
+    /// Maximum tokens generated per answer
+    #[serde(rename = "maxTokens")]
+    pub max_tokens: u32,
+
+    pub temperature: f32,
+
+    /// Layers offloaded to the GPU, 0 for CPU only
+    #[serde(rename = "gpuLayers")]
+    pub gpu_layers: u32,
+}
+
+impl Default for LlmConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            active_model: None,
+            context_size: 4096,
+            max_tokens: 512,
+            temperature: 0.7,
+            gpu_layers: 0,
+        }
+    }
+}
+
+/// Load local LLM config from file
+pub fn load_llm_config(app: &AppHandle) -> LlmConfig {
+    load_json_or_default(app, LLM_CONFIG_FILE)
+}
+
+/// Save local LLM config to file
+pub fn save_llm_config(app: &AppHandle, config: &LlmConfig) -> Result<(), String> {
+    save_json(app, LLM_CONFIG_FILE, config)
+}
@@ -0,0 +1,197 @@
+use serde::{Deserialize, Serialize};
+use std::num::NonZeroU32;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+use tauri::{AppHandle, Emitter};
+
+use llama_cpp_2::context::params::LlamaContextParams;
+use llama_cpp_2::llama_backend::LlamaBackend;
+use llama_cpp_2::llama_batch::LlamaBatch;
+use llama_cpp_2::model::params::LlamaModelParams;
+use llama_cpp_2::model::{AddBos, LlamaChatMessage, LlamaModel, Special};
+use llama_cpp_2::sampling::LlamaSampler;
+
+use super::LlmConfig;
+
+const PROMPT_BATCH_SIZE: usize = 512;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LlmTokenEvent {
+    #[serde(rename = "requestId")]
+    pub request_id: String,
+    pub token: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LlmDoneEvent {
+    #[serde(rename = "requestId")]
+    pub request_id: String,
+    pub text: String,
+    #[serde(rename = "tokenCount")]
+    pub token_count: u32,
+    pub cancelled: bool,
+}
+
+struct LoadedModel {
+    path: PathBuf,
+    gpu_layers: u32,
+    model: Arc<LlamaModel>,
+}
+
+static LLAMA_BACKEND: LazyLock<Result<LlamaBackend, String>> = LazyLock::new(|| {
+    LlamaBackend::init().map_err(|e| format!("Failed to initialize llama.cpp backend: {}", e))
+});
+static LOADED_MODEL: LazyLock<Mutex<Option<LoadedModel>>> = LazyLock::new(|| Mutex::new(None));
+static GENERATION_CANCELLED: AtomicBool = AtomicBool::new(false);
+
+fn backend() -> Result<&'static LlamaBackend, String> {
+    LLAMA_BACKEND.as_ref().map_err(|e| e.clone())
+}
+
+/// Load the model, reusing the one already in memory when path and settings match
+pub fn load_llm_model(path: &Path, gpu_layers: u32) -> Result<Arc<LlamaModel>, String> {
+    let mut loaded = LOADED_MODEL.lock().unwrap();
+    if let Some(ref current) = *loaded {
+        if current.path == path && current.gpu_layers == gpu_layers {
+            return Ok(current.model.clone());
+        }
+    }
+
+    // Free the previous model before loading another multi-GB file
+    *loaded = None;
+
+    info!("🧠 Loading local LLM: {}", path.display());
+    let params = LlamaModelParams::default().with_n_gpu_layers(gpu_layers);
+    let model = LlamaModel::load_from_file(backend()?, path, &params)
+        .map_err(|e| format!("Failed to load model {}: {}", path.display(), e))?;
+    let model = Arc::new(model);
+
+    *loaded = Some(LoadedModel {
+        path: path.to_path_buf(),
+        gpu_layers,
+        model: model.clone(),
+    });
+    info!("✅ Local LLM loaded");
+    Ok(model)
+}
+
+/// Drop the loaded model to release its memory
+pub fn unload_llm_model() {
+    *LOADED_MODEL.lock().unwrap() = None;
+}
+
+pub fn cancel_llm_generation() {
+    GENERATION_CANCELLED.store(true, Ordering::SeqCst);
+}
+
+/// Wrap the prompt with the model's own chat template, falling back to the raw prompt
+fn format_prompt(model: &LlamaModel, prompt: &str) -> String {
+    let formatted = model.chat_template(None)
+        .map_err(|e| e.to_string())
+        .and_then(|template| {
+            let message = LlamaChatMessage::new("user".to_string(), prompt.to_string())
+                .map_err(|e| e.to_string())?;
+            model.apply_chat_template(&template, &[message], true)
+                .map_err(|e| e.to_string())
+        });
+
+    match formatted {
+        Ok(text) => text,
+        Err(e) => {
+            warn!("⚠️ Model has no usable chat template ({}), sending raw prompt", e);
+            prompt.to_string()
+        }
+    }
+}
+
+/// Generate an answer, emitting each token as an `llm-token` event
+pub fn generate_llm_answer(
+    app: &AppHandle,
+    model: &LlamaModel,
+    config: &LlmConfig,
+    request_id: &str,
+    prompt: &str,
+) -> Result<LlmDoneEvent, String> {
+    GENERATION_CANCELLED.store(false, Ordering::SeqCst);
+
+    let context_size = config.context_size.max(512);
+    let context_params = LlamaContextParams::default()
+        .with_n_ctx(NonZeroU32::new(context_size));
+    let mut context = model.new_context(backend()?, context_params)
+        .map_err(|e| format!("Failed to create inference context: {}", e))?;
+
+    let tokens = model.str_to_token(&format_prompt(model, prompt), AddBos::Always)
+        .map_err(|e| format!("Failed to tokenize prompt: {}", e))?;
+    let max_prompt = (context_size - config.max_tokens.min(context_size / 2)) as usize;
+    // Keep the end of long prompts, the question is usually last
+    let tokens = &tokens[tokens.len().saturating_sub(max_prompt)..];
+
+    let mut batch = LlamaBatch::new(PROMPT_BATCH_SIZE.max(tokens.len()), 1);
+    let last_index = tokens.len().saturating_sub(1);
+    for (i, token) in tokens.iter().enumerate() {
+        batch.add(*token, i as i32, &[0], i == last_index)
+            .map_err(|e| format!("Failed to queue prompt tokens: {}", e))?;
+    }
+    context.decode(&mut batch)
+        .map_err(|e| format!("Failed to process prompt: {}", e))?;
+
+    let mut sampler = LlamaSampler::chain_simple([
+        LlamaSampler::temp(config.temperature),
+        LlamaSampler::dist(rand_seed()),
+    ]);
+
+    let mut position = batch.n_tokens();
+    let mut text = String::new();
+    let mut token_count = 0u32;
+    let mut cancelled = false;
+    let mut decoder = encoding_rs::UTF_8.new_decoder();
+
+    while token_count < config.max_tokens {
+        if GENERATION_CANCELLED.load(Ordering::SeqCst) {
+            cancelled = true;
+            break;
+        }
+
+        let token = sampler.sample(&context, batch.n_tokens() - 1);
+        sampler.accept(token);
+        if model.is_eog_token(token) {
+            break;
+        }
+
+        let bytes = model.token_to_bytes(token, Special::Tokenize)
+            .map_err(|e| format!("Failed to decode token: {}", e))?;
+        let mut piece = String::with_capacity(32);
+        let _ = decoder.decode_to_string(&bytes, &mut piece, false);
+        token_count += 1;
+
+        if !piece.is_empty() {
+            text.push_str(&piece);
+            let _ = app.emit("llm-token", LlmTokenEvent {
+                request_id: request_id.to_string(),
+                token: piece,
+            });
+        }
+
+        batch.clear();
+        batch.add(token, position, &[0], true)
+            .map_err(|e| format!("Failed to queue token: {}", e))?;
+        position += 1;
+        context.decode(&mut batch)
+            .map_err(|e| format!("Failed to generate: {}", e))?;
+    }
+
+    Ok(LlmDoneEvent {
+        request_id: request_id.to_string(),
+        text,
+        token_count,
+        cancelled,
+    })
+}
+
+fn rand_seed() -> u32 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(1234)
+}
@@ -0,0 +1,9 @@
+pub mod config;
+pub mod models;
+pub mod inference;
+pub mod commands;
+
+pub use config::*;
+pub use models::*;
+pub use inference::*;
+pub use commands::*;
@@ -0,0 +1,176 @@
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+use tauri::{AppHandle, Emitter};
+
+use crate::desktop::{ensure_disk_space, get_app_data_subdir, http_client_builder};
+
+const LLM_MODELS_DIR: &str = "models/llm";
+
+/// A GGUF model that can be downloaded from the model manager
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LlmModelInfo {
+    pub id: String,
+    pub name: String,
+    #[serde(rename = "fileName")]
+    pub file_name: String,
+    pub url: String,
+    /// Approximate download size in bytes
+    pub size: u64,
+    pub downloaded: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LlmDownloadProgress {
+    #[serde(rename = "modelId")]
+    pub model_id: String,
+    pub downloaded: u64,
+    pub total: u64,
+}
+
+static DOWNLOAD_CANCELLED: AtomicBool = AtomicBool::new(false);
+
+/// Small instruction-tuned models that run acceptably on a laptop CPU
+fn model_catalog() -> Vec<(&'static str, &'static str, &'static str, u64)> {
+    vec![
+        (
+            "qwen2.5-1.5b-instruct",
+            "Qwen2.5 1.5B Instruct (Q4_K_M)",
+            "https://huggingface.co/Qwen/Qwen2.5-1.5B-Instruct-GGUF/resolve/main/qwen2.5-1.5b-instruct-q4_k_m.gguf",
+            1_120_000_000,
+        ),
+        (
+            "llama-3.2-3b-instruct",
+            "Llama 3.2 3B Instruct (Q4_K_M)",
+            "https://huggingface.co/bartowski/Llama-3.2-3B-Instruct-GGUF/resolve/main/Llama-3.2-3B-Instruct-Q4_K_M.gguf",
+            2_020_000_000,
+        ),
+        (
+            "phi-3.5-mini-instruct",
+            "Phi 3.5 Mini Instruct (Q4_K_M)",
+            "https://huggingface.co/bartowski/Phi-3.5-mini-instruct-GGUF/resolve/main/Phi-3.5-mini-instruct-Q4_K_M.gguf",
+            2_390_000_000,
+        ),
+    ]
+}
+
+/// Get the directory where local LLM models are stored
+pub fn get_llm_models_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    get_app_data_subdir(app, LLM_MODELS_DIR)
+}
+
+/// List catalog models with their download state
+pub fn list_llm_model_infos(app: &AppHandle) -> Result<Vec<LlmModelInfo>, String> {
+    let dir = get_llm_models_dir(app)?;
+    Ok(model_catalog().into_iter().map(|(id, name, url, size)| {
+        let file_name = url.rsplit('/').next().unwrap_or(id).to_string();
+        LlmModelInfo {
+            id: id.to_string(),
+            name: name.to_string(),
+            downloaded: dir.join(&file_name).exists(),
+            file_name,
+            url: url.to_string(),
+            size,
+        }
+    }).collect())
+}
+
+fn find_model(app: &AppHandle, model_id: &str) -> Result<LlmModelInfo, String> {
+    list_llm_model_infos(app)?
+        .into_iter()
+        .find(|m| m.id == model_id)
+        .ok_or_else(|| format!("Unknown model: {}", model_id))
+}
+
+/// Resolve the file of a downloaded model
+pub fn get_llm_model_path(app: &AppHandle, model_id: &str) -> Result<PathBuf, String> {
+    let model = find_model(app, model_id)?;
+    let path = get_llm_models_dir(app)?.join(&model.file_name);
+    if !path.exists() {
+        return Err(format!("Model {} is not downloaded", model.name));
+    }
+    Ok(path)
+}
+
+/// Download a model into a .part file and move it in place once complete
+pub fn download_llm_model_file(app: &AppHandle, model_id: &str) -> Result<PathBuf, String> {
+    let model = find_model(app, model_id)?;
+    let target = get_llm_models_dir(app)?.join(&model.file_name);
+    if target.exists() {
+        return Ok(target);
+    }
+
+    DOWNLOAD_CANCELLED.store(false, Ordering::SeqCst);
+    info!("⬇️ Downloading model {} from {}", model.name, model.url);
+
+    let mut response = http_client_builder()
+        .timeout(None)
+        .build()
+        .and_then(|client| client.get(&model.url).send())
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| format!("Failed to download {}: {}", model.name, e))?;
+
+    let total = response.content_length().unwrap_or(model.size);
+    let partial = target.with_extension("gguf.part");
+    ensure_disk_space(&partial, total)?;
+    let mut file = File::create(&partial)
+        .map_err(|e| format!("Failed to create {}: {}", partial.display(), e))?;
+
+    let mut buffer = vec![0u8; 256 * 1024];
+    let mut downloaded = 0u64;
+    let mut last_emit = Instant::now();
+
+    loop {
+        if DOWNLOAD_CANCELLED.load(Ordering::SeqCst) {
+            drop(file);
+            let _ = fs::remove_file(&partial);
+            return Err("Download cancelled".to_string());
+        }
+
+        let read = response.read(&mut buffer)
+            .map_err(|e| format!("Download of {} interrupted: {}", model.name, e))?;
+        if read == 0 {
+            break;
+        }
+        file.write_all(&buffer[..read])
+            .map_err(|e| format!("Failed to write model file: {}", e))?;
+        downloaded += read as u64;
+
+        if last_emit.elapsed().as_millis() >= 250 {
+            last_emit = Instant::now();
+            let _ = app.emit("llm-model-download-progress", LlmDownloadProgress {
+                model_id: model.id.clone(),
+                downloaded,
+                total,
+            });
+        }
+    }
+
+    file.flush().map_err(|e| format!("Failed to write model file: {}", e))?;
+    drop(file);
+    fs::rename(&partial, &target)
+        .map_err(|e| format!("Failed to finalize model file: {}", e))?;
+
+    let _ = app.emit("llm-model-download-progress", LlmDownloadProgress {
+        model_id: model.id.clone(),
+        downloaded,
+        total: downloaded,
+    });
+    info!("✅ Model {} downloaded to {}", model.name, target.display());
+    Ok(target)
+}
+
+/// Ask a running download to stop
+pub fn cancel_llm_model_download() {
+    DOWNLOAD_CANCELLED.store(true, Ordering::SeqCst);
+}
+
+/// Delete a downloaded model file
+pub fn delete_llm_model_file(app: &AppHandle, model_id: &str) -> Result<(), String> {
+    let path = get_llm_model_path(app, model_id)?;
+    fs::remove_file(&path)
+        .map_err(|e| format!("Failed to delete {}: {}", path.display(), e))
+}
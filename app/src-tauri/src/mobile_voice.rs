@@ -0,0 +1,110 @@
+use tauri::{AppHandle, Emitter};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use tauri_plugin_blinko::{BlinkoExt, StartVoiceRecordingRequest, VoiceRecording, VoiceRecordingState};
+
+const DEFAULT_MAX_SECONDS: u32 = 300;
+const LEVEL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Same shape as the desktop audio note so the frontend handles both alike
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AudioNote {
+    /// The WAV file written by the native recorder, attach it like any picked file
+    pub file: VoiceRecording,
+    #[serde(rename = "durationSeconds")]
+    pub duration_seconds: f32,
+    /// Always `None` here, the server transcribes audio attachments once the note is saved
+    pub transcript: Option<String>,
+}
+
+/// Payload of `audio-note-state`, sent whenever the recording pauses, resumes or switches microphone
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AudioNoteState {
+    pub state: VoiceRecordingState,
+    pub route: Option<String>,
+    pub interruptions: u32,
+}
+
+static RECORDING_AUDIO_NOTE: AtomicBool = AtomicBool::new(false);
+static STOP_AUDIO_NOTE: AtomicBool = AtomicBool::new(false);
+
+fn record_audio_note_blocking(app: &AppHandle, max_seconds: u32) -> Result<AudioNote, String> {
+    let blinko = app.blinko();
+    STOP_AUDIO_NOTE.store(false, Ordering::SeqCst);
+    blinko.start_voice_recording(StartVoiceRecordingRequest { max_seconds: Some(max_seconds) })
+        .map_err(|e| format!("Failed to open microphone: {}", e))?;
+    let _ = app.emit("audio-note-started", max_seconds);
+
+    // The native recorder enforces the time limit and reports it as no longer recording.
+    // Interruptions keep it recording, so a call pauses the note instead of ending it.
+    let mut last_state: Option<AudioNoteState> = None;
+    while !STOP_AUDIO_NOTE.load(Ordering::SeqCst) {
+        std::thread::sleep(LEVEL_INTERVAL);
+        let status = match blinko.voice_recording_status() {
+            Ok(status) if status.recording => status,
+            _ => break,
+        };
+        let state = AudioNoteState { state: status.state, route: status.route, interruptions: status.interruptions };
+        if last_state.as_ref() != Some(&state) {
+            let _ = app.emit("audio-note-state", &state);
+            last_state = Some(state);
+        }
+        if status.state == VoiceRecordingState::Recording {
+            let _ = app.emit("audio-note-level", status.level);
+        }
+    }
+
+    let file = blinko.stop_voice_recording().map_err(|e| format!("Failed to finish recording: {}", e))?;
+    if file.duration_seconds <= 0.0 {
+        return Err("No audio was recorded".to_string());
+    }
+    if file.interruptions > 0 {
+        let _ = app.emit("audio-note-state", AudioNoteState {
+            state: VoiceRecordingState::Stopped,
+            route: None,
+            interruptions: file.interruptions,
+        });
+    }
+    Ok(AudioNote { duration_seconds: file.duration_seconds, file, transcript: None })
+}
+
+/// Record from the microphone until `stop_audio_note` or `max_seconds`. Mirrors the desktop
+/// command, including the `audio-note-*` events, so voice notes work the same on Android and iOS.
+#[tauri::command]
+pub async fn record_audio_note(app: AppHandle, max_seconds: Option<u32>, transcribe: Option<bool>) -> Result<AudioNote, String> {
+    let _ = transcribe;
+    if RECORDING_AUDIO_NOTE.swap(true, Ordering::SeqCst) {
+        return Err("An audio note is already being recorded".to_string());
+    }
+    let max_seconds = max_seconds.unwrap_or(DEFAULT_MAX_SECONDS).max(1);
+
+    let app_handle = app.clone();
+    let result = match tauri::async_runtime::spawn_blocking(move || record_audio_note_blocking(&app_handle, max_seconds)).await {
+        Ok(result) => result,
+        Err(e) => Err(format!("Audio note recording failed: {}", e)),
+    };
+    RECORDING_AUDIO_NOTE.store(false, Ordering::SeqCst);
+
+    match result {
+        Ok(note) => {
+            let _ = app.emit("audio-note-recorded", &note);
+            Ok(note)
+        }
+        Err(e) => {
+            let _ = app.emit("audio-note-failed", &e);
+            Err(e)
+        }
+    }
+}
+
+/// Finish the audio note being recorded
+#[tauri::command]
+pub fn stop_audio_note() -> Result<(), String> {
+    if !RECORDING_AUDIO_NOTE.load(Ordering::SeqCst) {
+        return Err("No audio note is being recorded".to_string());
+    }
+    STOP_AUDIO_NOTE.store(true, Ordering::SeqCst);
+    Ok(())
+}
@@ -0,0 +1,146 @@
+use tauri::{AppHandle, Emitter};
+use serde::{Deserialize, Serialize};
+use std::io::Cursor;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use super::{current_voice_processor, load_voice_config, AudioRecorder, WhisperTranscriber};
+use crate::desktop::{ensure_disk_space, get_attachments_dir, import_bytes, is_local_encryption_enabled, ImportedFile};
+
+const DEFAULT_MAX_SECONDS: u32 = 300;
+const LEVEL_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AudioNote {
+    /// The WAV file in the attachments area, attach it like any imported file
+    pub file: ImportedFile,
+    #[serde(rename = "durationSeconds")]
+    pub duration_seconds: f32,
+    /// Whisper transcription, `None` when not requested or no model is configured
+    pub transcript: Option<String>,
+}
+
+static RECORDING_AUDIO_NOTE: AtomicBool = AtomicBool::new(false);
+static STOP_AUDIO_NOTE: AtomicBool = AtomicBool::new(false);
+
+/// Encode interleaved float samples as 16-bit PCM WAV
+fn encode_wav(samples: &[f32], sample_rate: u32, channels: u16) -> Result<Vec<u8>, String> {
+    let spec = hound::WavSpec {
+        channels,
+        sample_rate,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut buffer = Cursor::new(Vec::new());
+    let mut writer = hound::WavWriter::new(&mut buffer, spec).map_err(|e| format!("Failed to encode audio: {}", e))?;
+    for sample in samples {
+        writer.write_sample((sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16)
+            .map_err(|e| format!("Failed to encode audio: {}", e))?;
+    }
+    writer.finalize().map_err(|e| format!("Failed to encode audio: {}", e))?;
+    Ok(buffer.into_inner())
+}
+
+/// Transcribe with the running voice processor's model, or load the configured one
+fn transcribe_audio_note(app: &AppHandle, audio: &[f32]) -> Result<String, String> {
+    let config = load_voice_config(app);
+    let language = Some(config.language.as_str()).filter(|language| *language != "auto");
+
+    let processor = current_voice_processor(app);
+    let text = match processor {
+        Some(processor) => processor.transcriber.transcribe(audio, language),
+        None => WhisperTranscriber::new(&config.model_path, config.gpu_acceleration)
+            .and_then(|transcriber| transcriber.transcribe(audio, language)),
+    };
+    text.map(|text| text.trim().to_string()).map_err(|e| format!("Transcription failed: {}", e))
+}
+
+fn record_audio_note_blocking(app: &AppHandle, max_seconds: u32, transcribe: bool) -> Result<AudioNote, String> {
+    // Recordings land in the attachments folder, which encryption doesn't cover
+    if is_local_encryption_enabled() {
+        return Err("Audio notes are unavailable while local encryption is on".to_string());
+    }
+
+    // A recorder of its own so the dictation hotkey keeps working meanwhile
+    let recorder = AudioRecorder::new().map_err(|e| format!("Failed to open microphone: {}", e))?;
+    // 16-bit samples for the longest recording allowed
+    let wav_bytes = recorder.sample_rate() as u64 * recorder.channels() as u64 * 2 * max_seconds as u64;
+    ensure_disk_space(&get_attachments_dir(app)?, wav_bytes)?;
+    STOP_AUDIO_NOTE.store(false, Ordering::SeqCst);
+    recorder.start_recording();
+    let _ = app.emit("audio-note-started", max_seconds);
+
+    let started = Instant::now();
+    let limit = Duration::from_secs(max_seconds as u64);
+    while started.elapsed() < limit && !STOP_AUDIO_NOTE.load(Ordering::SeqCst) {
+        std::thread::sleep(LEVEL_INTERVAL);
+        let _ = app.emit("audio-note-level", recorder.get_audio_level());
+    }
+
+    let samples = recorder.stop_recording_raw();
+    let duration_seconds = samples.len() as f32 / (recorder.sample_rate() as f32 * recorder.channels() as f32);
+    if samples.is_empty() {
+        return Err("No audio was recorded".to_string());
+    }
+
+    let wav = encode_wav(&samples, recorder.sample_rate(), recorder.channels())?;
+    let name = format!("audio-note-{}.wav", chrono::Local::now().format("%Y%m%d-%H%M%S"));
+    let file = import_bytes(app, &name, &wav, "audio-note")?;
+    info!("🎙️ Recorded audio note {} ({:.1}s)", file.name, duration_seconds);
+
+    let transcript = if transcribe {
+        match transcribe_audio_note(app, &recorder.resample_to_16k(&samples)) {
+            Ok(text) => Some(text),
+            Err(e) => {
+                warn!("⚠️ {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    Ok(AudioNote { file, duration_seconds, transcript })
+}
+
+/// Record from the microphone until `stop_audio_note` or `max_seconds`, store the WAV as an
+/// attachment and optionally transcribe it. Also emitted as `audio-note-recorded` so the
+/// frontend can create the note with the attachment.
+#[tauri::command]
+pub async fn record_audio_note(app: AppHandle, max_seconds: Option<u32>, transcribe: Option<bool>) -> Result<AudioNote, String> {
+    if RECORDING_AUDIO_NOTE.swap(true, Ordering::SeqCst) {
+        return Err("An audio note is already being recorded".to_string());
+    }
+    let max_seconds = max_seconds.unwrap_or(DEFAULT_MAX_SECONDS).max(1);
+    let transcribe = transcribe.unwrap_or(true);
+
+    let app_handle = app.clone();
+    let result = match tauri::async_runtime::spawn_blocking(move || record_audio_note_blocking(&app_handle, max_seconds, transcribe)).await {
+        Ok(result) => result,
+        Err(e) => Err(format!("Audio note recording failed: {}", e)),
+    };
+    RECORDING_AUDIO_NOTE.store(false, Ordering::SeqCst);
+
+    match result {
+        Ok(note) => {
+            if let Err(e) = app.emit("audio-note-recorded", &note) {
+                error!("Failed to emit audio-note-recorded event: {}", e);
+            }
+            Ok(note)
+        }
+        Err(e) => {
+            let _ = app.emit("audio-note-failed", &e);
+            Err(e)
+        }
+    }
+}
+
+/// Finish the audio note being recorded
+#[tauri::command]
+pub fn stop_audio_note() -> Result<(), String> {
+    if !RECORDING_AUDIO_NOTE.load(Ordering::SeqCst) {
+        return Err("No audio note is being recorded".to_string());
+    }
+    STOP_AUDIO_NOTE.store(true, Ordering::SeqCst);
+    Ok(())
+}
@@ -1,11 +1,7 @@
-use tauri::AppHandle;
+use tauri::{AppHandle, State};
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
 
-use super::{
-    VoiceConfig, VoiceProcessor, VOICE_STATE,
-    validate_voice_config
-};
+use super::{VoiceConfig, VoiceState, start_voice_processor, validate_voice_config};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct VoiceStatus {
@@ -27,29 +23,24 @@ pub async fn get_voice_config(app: AppHandle) -> Result<VoiceConfig, String> {
 #[tauri::command]
 pub async fn save_voice_config_cmd(
     app: AppHandle,
+    state: State<'_, VoiceState>,
     config: VoiceConfig
 ) -> Result<(), String> {
-    println!("Received voice config to save: {:?}", config);
+    info!("Received voice config to save: {:?}", config);
 
     // Validate configuration
     if let Err(e) = validate_voice_config(&config) {
-        println!("Voice config validation failed: {}", e);
+        info!("Voice config validation failed: {}", e);
         return Err(e);
     }
 
     // Save to file
     super::save_voice_config(&app, &config)?;
-    println!("Voice config saved to file successfully");
+    info!("Voice config saved to file successfully");
 
-    // Update the global state
-    {
-        let mut state = VOICE_STATE.lock();
-        *state.config.lock() = config.clone();
-
-        // If processor exists, update its config
-        if let Some(ref processor) = state.processor {
-            processor.update_config(config);
-        }
+    // Update the running processor, without the state lock held
+    if let Some(processor) = state.processor() {
+        processor.update_config(config);
     }
 
     Ok(())
@@ -58,86 +49,49 @@ pub async fn save_voice_config_cmd(
 /// Initialize voice recognition system
 #[tauri::command]
 pub async fn initialize_voice_recognition(app: AppHandle) -> Result<String, String> {
-    // Stop existing voice recognition if running
-    {
-        let state = VOICE_STATE.lock();
-        if let Some(ref processor) = state.processor {
-            println!("🔄 Stopping existing voice recognition service...");
-            processor.stop();
-        }
-    }
-
     let config = super::load_voice_config(&app);
-    println!("🔧 Reinitializing voice recognition with updated config...");
+    debug!("🔧 Reinitializing voice recognition with updated config...");
 
     // Validate configuration first
     validate_voice_config(&config)?;
 
-    match VoiceProcessor::new(config.clone()) {
-        Ok(processor) => {
-            let mode_info = processor.transcriber.get_mode_info().to_string();
-
-            {
-                let mut state = VOICE_STATE.lock();
-                state.processor = Some(Arc::new(processor));
-                state.is_initialized = true;
-                *state.config.lock() = config.clone();
-            }
-
-            // Start the voice recognition service with new configuration
-            if let Some(ref processor) = VOICE_STATE.lock().processor {
-                if let Err(e) = processor.start() {
-                    eprintln!("❌ Failed to start voice recognition service: {}", e);
-                    return Err(format!("Failed to start voice recognition service: {}", e));
-                } else {
-                    println!("🚀 Voice recognition service restarted with updated hotkey: {}", config.hotkey);
-                }
-            }
-
-            Ok(format!("Voice recognition reinitialized successfully ({}) with hotkey: {}", mode_info, config.hotkey))
-        }
-        Err(e) => {
-            Err(format!("Failed to initialize voice recognition: {}", e))
-        }
-    }
+    // Loading the model takes seconds, keep it off the async runtime
+    let hotkey = config.hotkey.clone();
+    let processor = tauri::async_runtime::spawn_blocking(move || start_voice_processor(&app, config, true))
+        .await
+        .map_err(|e| format!("Voice recognition task failed: {}", e))??;
+
+    info!("🚀 Voice recognition service restarted with updated hotkey: {}", hotkey);
+    Ok(format!("Voice recognition reinitialized successfully ({}) with hotkey: {}", processor.transcriber.get_mode_info(), hotkey))
 }
 
 /// Start voice recognition service
 #[tauri::command]
-pub async fn start_voice_recognition() -> Result<(), String> {
-    let state = VOICE_STATE.lock();
-
-    if let Some(ref processor) = state.processor {
-        processor.start()
-            .map_err(|e| format!("Failed to start voice recognition: {}", e))?;
-        Ok(())
-    } else {
-        Err("Voice recognition not initialized. Call initialize_voice_recognition first.".to_string())
-    }
+pub async fn start_voice_recognition(state: State<'_, VoiceState>) -> Result<(), String> {
+    let processor = state.processor()
+        .ok_or("Voice recognition not initialized. Call initialize_voice_recognition first.")?;
+    processor.start()
+        .map_err(|e| format!("Failed to start voice recognition: {}", e))
 }
 
 /// Stop voice recognition service
 #[tauri::command]
-pub async fn stop_voice_recognition() -> Result<(), String> {
-    let state = VOICE_STATE.lock();
-
-    if let Some(ref processor) = state.processor {
-        processor.stop();
-        Ok(())
-    } else {
-        Err("Voice recognition not initialized.".to_string())
-    }
+pub async fn stop_voice_recognition(state: State<'_, VoiceState>) -> Result<(), String> {
+    let processor = state.processor().ok_or("Voice recognition not initialized.")?;
+    processor.stop();
+    Ok(())
 }
 
 /// Get voice recognition status
 #[tauri::command]
-pub async fn get_voice_status() -> Result<VoiceStatus, String> {
-    let state = VOICE_STATE.lock();
+pub async fn get_voice_status(state: State<'_, VoiceState>) -> Result<VoiceStatus, String> {
+    let processor = state.processor();
+    let is_initialized = processor.is_some();
 
-    let (is_running, mode_info, audio_level) = if let Some(ref processor) = state.processor {
+    let (is_running, mode_info, audio_level) = if let Some(processor) = processor {
         (
             processor.is_running(),
-            Some(processor.transcriber.get_mode_info().to_string()),
+            Some(processor.transcriber.get_mode_info()),
             processor.get_audio_level()
         )
     } else {
@@ -145,7 +99,7 @@ pub async fn get_voice_status() -> Result<VoiceStatus, String> {
     };
 
     Ok(VoiceStatus {
-        is_initialized: state.is_initialized,
+        is_initialized,
         is_running,
         mode_info,
         audio_level,
@@ -1,7 +1,9 @@
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
-use tauri::{AppHandle, Manager};
+use tauri::AppHandle;
+
+use crate::desktop::get_app_data_dir;
 
 const VOICE_CONFIG_FILE: &str = "voice_config.json";
 
@@ -49,6 +51,19 @@ fn detect_system_language() -> String {
     }
 }
 
+/// Media key that starts and stops dictation besides the hotkey. Headset buttons only
+/// report a press, so a press starts recording and the next one ends it.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum MediaKeyTrigger {
+    #[default]
+    Off,
+    /// Play/pause, the single button on most Bluetooth headsets
+    PlayPause,
+    Next,
+    Previous,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct VoiceConfig {
     /// Whether voice recognition is enabled
@@ -86,12 +101,20 @@ pub struct VoiceConfig {
     /// Auto-detect GPU capabilities
     #[serde(rename = "autoGpuDetection")]
     pub auto_gpu_detection: bool,
+
+    /// Headset or media key used as an alternative dictation trigger
+    #[serde(rename = "mediaKeyTrigger", default)]
+    pub media_key_trigger: MediaKeyTrigger,
+
+    /// Run a warm-up transcription after launch, so the first dictation doesn't pay for GPU setup
+    #[serde(rename = "preloadOnStartup", default)]
+    pub preload_on_startup: bool,
 }
 
 impl Default for VoiceConfig {
     fn default() -> Self {
         let system_language = detect_system_language();
-        println!("🌍 Detected system language for voice recognition: {}", system_language);
+        info!("🌍 Detected system language for voice recognition: {}", system_language);
 
         Self {
             enabled: false,
@@ -104,6 +127,8 @@ impl Default for VoiceConfig {
             max_duration: 30.0, // 30 seconds maximum
             sample_rate: 16000, // 16kHz for Whisper
             auto_gpu_detection: true,
+            media_key_trigger: MediaKeyTrigger::Off,
+            preload_on_startup: false,
         }
     }
 }
@@ -132,16 +157,7 @@ impl VoiceConfig {
 
 /// Get voice config file path
 fn get_voice_config_path(app: &AppHandle) -> Result<PathBuf, String> {
-    let app_data_dir = app.path().app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
-
-    // Ensure directory exists
-    if !app_data_dir.exists() {
-        fs::create_dir_all(&app_data_dir)
-            .map_err(|e| format!("Failed to create app data directory: {}", e))?;
-    }
-
-    Ok(app_data_dir.join(VOICE_CONFIG_FILE))
+    Ok(get_app_data_dir(app)?.join(VOICE_CONFIG_FILE))
 }
 
 /// Load voice config from file
@@ -153,24 +169,24 @@ pub fn load_voice_config(app: &AppHandle) -> VoiceConfig {
                     Ok(content) => {
                         match serde_json::from_str::<VoiceConfig>(&content) {
                             Ok(config) => {
-                                println!("📁 Loaded existing voice config from: {}", path.display());
+                                info!("📁 Loaded existing voice config from: {}", path.display());
                                 return config;
                             }
                             Err(e) => {
-                                eprintln!("Failed to parse voice config: {}", e);
+                                error!("Failed to parse voice config: {}", e);
                             }
                         }
                     }
                     Err(e) => {
-                        eprintln!("Failed to read voice config file: {}", e);
+                        error!("Failed to read voice config file: {}", e);
                     }
                 }
             } else {
-                println!("🆕 Voice config file does not exist, creating with system language defaults");
+                info!("🆕 Voice config file does not exist, creating with system language defaults");
             }
         }
         Err(e) => {
-            eprintln!("Failed to get voice config path: {}", e);
+            error!("Failed to get voice config path: {}", e);
         }
     }
 
@@ -179,7 +195,7 @@ pub fn load_voice_config(app: &AppHandle) -> VoiceConfig {
 
     // Save the default config so it persists for next time
     if let Err(e) = save_voice_config(app, &default_config) {
-        eprintln!("Failed to save default voice config: {}", e);
+        error!("Failed to save default voice config: {}", e);
     }
 
     default_config
@@ -195,7 +211,7 @@ pub fn save_voice_config(app: &AppHandle, config: &VoiceConfig) -> Result<(), St
     fs::write(&path, content)
         .map_err(|e| format!("Failed to write voice config to file: {}", e))?;
 
-    println!("Saved voice config to: {}", path.display());
+    info!("Saved voice config to: {}", path.display());
     Ok(())
 }
 
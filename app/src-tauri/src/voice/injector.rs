@@ -0,0 +1,38 @@
+use std::error::Error;
+use tauri::AppHandle;
+
+use crate::desktop::{copy_text_for_manual_paste, inject_text, is_focused_password_field, is_injection_guarded, send_notification, InjectionStrategy};
+
+/// Delivers finished transcriptions, so the pipeline can run without typing into real windows
+pub trait TextInjector: Send + Sync {
+    fn inject(&self, text: &str) -> Result<(), Box<dyn Error>>;
+}
+
+/// Types into the focused window, or hands the text over through the clipboard where typing is unsafe
+pub struct ActiveWindowInjector {
+    app: AppHandle,
+}
+
+impl ActiveWindowInjector {
+    pub fn new(app: AppHandle) -> Self {
+        Self { app }
+    }
+}
+
+impl TextInjector for ActiveWindowInjector {
+    fn inject(&self, text: &str) -> Result<(), Box<dyn Error>> {
+        if is_injection_guarded() {
+            info!("🛡️ Screen is being shared, not typing the transcription");
+            return Ok(());
+        }
+        // Never type dictation into a password box, hand it over through the clipboard instead
+        if is_focused_password_field() == Some(true) {
+            copy_text_for_manual_paste(text)?;
+            warn!("🔒 Focused field is a password field, transcription copied to the clipboard");
+            send_notification(&self.app, "Dictation not typed", "The focused field is a password field. The text was copied to the clipboard instead.");
+            return Ok(());
+        }
+        inject_text(text, 0, InjectionStrategy::Type)?;
+        Ok(())
+    }
+}
@@ -1,41 +1,107 @@
 pub mod config;
 pub mod recorder;
 pub mod transcriber;
+pub mod injector;
 pub mod processor;
 pub mod commands;
+pub mod audio_note;
 
 pub use config::*;
 pub use recorder::*;
 pub use transcriber::*;
+pub use injector::*;
 pub use processor::*;
 pub use commands::*;
+pub use audio_note::*;
 
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use parking_lot::Mutex;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
 
-// Voice recognition state
-pub struct VoiceRecognitionState {
-    pub config: Arc<Mutex<VoiceConfig>>,
-    pub processor: Option<Arc<VoiceProcessor>>,
-    pub is_initialized: bool,
+/// Wait after launch before warming up, so it doesn't compete with startup work
+const WARM_UP_DELAY: Duration = Duration::from_secs(5);
+/// One second of silence at 16kHz
+const WARM_UP_SAMPLES: usize = 16000;
+
+/// Voice recognition state, managed by the app so it is dropped together with it
+#[derive(Default)]
+pub struct VoiceState {
+    processor: Mutex<Option<Arc<VoiceProcessor>>>,
+    // Set while a Whisper model is loading, so overlapping initialize calls don't load it twice
+    loading: AtomicBool,
 }
 
-impl VoiceRecognitionState {
-    pub fn new() -> Self {
-        Self {
-            config: Arc::new(Mutex::new(VoiceConfig::default())),
-            processor: None,
-            is_initialized: false,
-        }
+impl VoiceState {
+    /// The current processor, cloned so callers never hold the state lock while using it
+    pub fn processor(&self) -> Option<Arc<VoiceProcessor>> {
+        self.processor.lock().clone()
     }
 }
 
-impl Default for VoiceRecognitionState {
-    fn default() -> Self {
-        Self::new()
+/// The current processor, for callers that only have the app handle
+pub fn current_voice_processor(app: &AppHandle) -> Option<Arc<VoiceProcessor>> {
+    app.try_state::<VoiceState>()?.processor()
+}
+
+/// Start the hotkey listener with a new processor, replacing the previous one.
+/// With `load_model` this blocks for the whole model load, so call it off the UI thread.
+pub fn start_voice_processor(app: &AppHandle, config: VoiceConfig, load_model: bool) -> Result<Arc<VoiceProcessor>, String> {
+    let state = app.try_state::<VoiceState>().ok_or("Voice state is not managed")?;
+    if state.loading.swap(true, Ordering::SeqCst) {
+        return Err("Voice model is already loading".to_string());
     }
+    let result = load_and_start_processor(app, &state, config, load_model);
+    state.loading.store(false, Ordering::SeqCst);
+    result
 }
 
-// Global voice state
-pub static VOICE_STATE: std::sync::LazyLock<Arc<Mutex<VoiceRecognitionState>>> =
-    std::sync::LazyLock::new(|| Arc::new(Mutex::new(VoiceRecognitionState::new())));
\ No newline at end of file
+fn load_and_start_processor(app: &AppHandle, state: &VoiceState, config: VoiceConfig, load_model: bool) -> Result<Arc<VoiceProcessor>, String> {
+    if let Some(previous) = state.processor() {
+        info!("🔄 Stopping existing voice recognition service...");
+        previous.stop();
+    }
+
+    let processor = VoiceProcessor::new(app.clone(), config, load_model)
+        .map(Arc::new)
+        .map_err(|e| format!("Failed to initialize voice recognition: {}", e))?;
+    processor.start()
+        .map_err(|e| format!("Failed to start voice recognition service: {}", e))?;
+
+    let previous = state.processor.lock().replace(processor.clone());
+    // Freeing the old model can take a moment, do it without the lock held
+    drop(previous);
+    Ok(processor)
+}
+
+#[derive(Debug, Serialize, Clone)]
+struct VoiceModelReady {
+    #[serde(rename = "modeInfo")]
+    mode_info: String,
+    #[serde(rename = "warmUpMillis")]
+    warm_up_millis: u64,
+}
+
+/// Run one transcription of silence shortly after launch, so the first dictation doesn't pay
+/// for GPU and buffer setup, then send `voice-model-ready`. Loads the model first if needed.
+/// Blocks for the delay and the warm-up.
+pub fn warm_up_voice_model(app: &AppHandle, processor: &VoiceProcessor) {
+    std::thread::sleep(WARM_UP_DELAY);
+
+    let started = Instant::now();
+    let warm_up = processor.transcriber.preload()
+        .and_then(|_| processor.transcriber.transcribe(&[0.0; WARM_UP_SAMPLES], Some("en")));
+    match warm_up {
+        Ok(_) => {
+            let ready = VoiceModelReady {
+                mode_info: processor.transcriber.get_mode_info(),
+                warm_up_millis: started.elapsed().as_millis() as u64,
+            };
+            info!("🔥 Whisper model ready ({}, {}ms)", ready.mode_info, ready.warm_up_millis);
+            let _ = app.emit("voice-model-ready", ready);
+        }
+        Err(e) => error!("❌ Failed to warm up Whisper model: {}", e),
+    }
+}
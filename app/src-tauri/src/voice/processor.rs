@@ -1,87 +1,130 @@
 use std::sync::Arc;
 use std::thread;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use parking_lot::Mutex;
 use crossbeam_channel::{unbounded, Receiver, Sender};
-use enigo::{Enigo, Keyboard, Settings};
-use rdev::{listen, Event, EventType, Key};
+use rdev::{Event, EventType, Key};
+use tauri::{AppHandle, Emitter};
 
-use super::{AudioRecorder, WhisperTranscriber, VoiceConfig};
+use super::{ActiveWindowInjector, AudioRecorder, Recorder, TextInjector, Transcriber, VoiceConfig, WhisperTranscriber};
+use crate::desktop::{add_input_listener, dispatch_webhook_event, ensure_input_hook, get_foreground_app_info, publish_mqtt_event, run_plugin_hook, run_transcription_scripts, PluginHook};
+
+/// Hotkey presses shorter than this are taken as accidental and discarded
+const MIN_HOLD: Duration = Duration::from_millis(500);
+/// How often a running recording is checked against the maximum duration
+const MAX_DURATION_CHECK_INTERVAL: Duration = Duration::from_millis(200);
 
 pub struct VoiceProcessor {
-    recorder: Arc<AudioRecorder>,
-    pub transcriber: Arc<WhisperTranscriber>,
+    recorder: Arc<dyn Recorder>,
+    pub transcriber: Arc<dyn Transcriber>,
     config: Arc<Mutex<VoiceConfig>>,
-    tx: Sender<Vec<f32>>,
+    dictation: Arc<Mutex<Dictation>>,
     is_running: Arc<Mutex<bool>>,
 }
 
 impl VoiceProcessor {
-    pub fn new(config: VoiceConfig) -> Result<Self, Box<dyn std::error::Error>> {
+    /// With `load_model` false the Whisper model is loaded on the first transcription instead
+    pub fn new(app: AppHandle, config: VoiceConfig, load_model: bool) -> Result<Self, Box<dyn std::error::Error>> {
         // Initialize audio recorder with error handling
-        let recorder = match AudioRecorder::new() {
+        let recorder: Arc<dyn Recorder> = match AudioRecorder::new() {
             Ok(recorder) => {
                 Arc::new(recorder)
             }
             Err(e) => {
                 let error_msg = format!("Failed to initialize audio recorder: {}", e);
-                eprintln!("❌ {}", error_msg);
+                error!("❌ {}", error_msg);
                 return Err(error_msg.into());
             }
         };
 
         // Initialize transcriber with error handling
-        let transcriber = match WhisperTranscriber::new(&config.model_path, config.gpu_acceleration) {
+        let transcriber = if load_model {
+            WhisperTranscriber::new(&config.model_path, config.gpu_acceleration)
+        } else {
+            Ok(WhisperTranscriber::new_unloaded(&config.model_path, config.gpu_acceleration))
+        };
+        let transcriber: Arc<dyn Transcriber> = match transcriber {
             Ok(transcriber) => {
                 Arc::new(transcriber)
             }
             Err(e) => {
                 let error_msg = format!("Failed to initialize Whisper transcriber: {}", e);
-                eprintln!("❌ {}", error_msg);
+                error!("❌ {}", error_msg);
                 return Err(error_msg.into());
             }
         };
 
+        let injector = Arc::new(ActiveWindowInjector::new(app.clone()));
+        Ok(Self::with_components(Some(app), config, recorder, transcriber, injector))
+    }
+
+    /// Build a processor from its parts, e.g. prepared audio and a fake transcriber.
+    /// Without an app handle, plugins, events and scripts are skipped for transcriptions.
+    pub fn with_components(
+        app: Option<AppHandle>,
+        config: VoiceConfig,
+        recorder: Arc<dyn Recorder>,
+        transcriber: Arc<dyn Transcriber>,
+        injector: Arc<dyn TextInjector>
+    ) -> Self {
         // Create communication channel
         let (tx, rx): (Sender<Vec<f32>>, Receiver<Vec<f32>>) = unbounded();
 
         let config_arc = Arc::new(Mutex::new(config));
         let is_running = Arc::new(Mutex::new(false));
 
-        // Start transcription processing thread with error handling
+        // Start transcription processing thread
         let transcriber_clone = transcriber.clone();
         let config_clone = config_arc.clone();
         thread::spawn(move || {
-            Self::transcription_loop(rx, transcriber_clone, config_clone);
+            Self::transcription_loop(app, rx, transcriber_clone, injector, config_clone);
+        });
+
+        let dictation = Arc::new(Mutex::new(Dictation {
+            recorder: recorder.clone(),
+            tx,
+            config: config_arc.clone(),
+            state: DictationState::Idle,
+        }));
+
+        // Ends recordings at the maximum duration, until the processor and its listener are gone
+        let weak_dictation = Arc::downgrade(&dictation);
+        thread::spawn(move || {
+            while let Some(dictation) = weak_dictation.upgrade() {
+                dictation.lock().handle(DictationEvent::Tick, Instant::now());
+                drop(dictation);
+                thread::sleep(MAX_DURATION_CHECK_INTERVAL);
+            }
         });
 
-        println!("✅ Voice processor initialized successfully");
-        println!("🎵 Using transcriber mode: {}", transcriber.get_mode_info());
+        info!("✅ Voice processor initialized successfully");
+        info!("🎵 Using transcriber mode: {}", transcriber.get_mode_info());
 
-        Ok(VoiceProcessor {
+        VoiceProcessor {
             recorder,
             transcriber,
             config: config_arc,
-            tx,
+            dictation,
             is_running,
-        })
+        }
     }
 
     /// Start the voice recognition service
     pub fn start(&self) -> Result<(), Box<dyn std::error::Error>> {
         *self.is_running.lock() = true;
 
-        // Start global keyboard event monitoring thread
-        let recorder = self.recorder.clone();
-        let tx = self.tx.clone();
-        let is_running = self.is_running.clone();
-        let config_arc = self.config.clone();
-
-        thread::spawn(move || {
-            Self::global_keyboard_event_loop(recorder, tx, is_running, config_arc);
-        });
+        // The listener replaces the one of a previous processor, dropping its state with it
+        let target_key = Self::parse_hotkey(&self.config.lock().hotkey).unwrap_or(Key::F2);
+        let listener = HotkeyListener {
+            dictation: self.dictation.clone(),
+            config: self.config.clone(),
+            is_running: self.is_running.clone(),
+            target_key,
+        };
+        add_input_listener("voice", move |event| listener.handle(event));
+        ensure_input_hook();
 
-        println!("🚀 Voice recognition service started successfully");
+        info!("🚀 Voice recognition service started successfully");
         Ok(())
     }
 
@@ -105,124 +148,22 @@ impl VoiceProcessor {
         *self.is_running.lock()
     }
 
-    /// Get current audio level (for UI feedback)
-    pub fn get_audio_level(&self) -> f32 {
-        self.recorder.get_audio_level()
+    /// Start or stop a recording without the hotkey (e.g. from the command line).
+    /// Returns true when a recording was started.
+    pub fn toggle_recording(&self) -> bool {
+        let mut dictation = self.dictation.lock();
+        dictation.handle(DictationEvent::Toggle, Instant::now());
+        dictation.is_recording()
     }
 
-    /// Global keyboard event monitoring loop using rdev
-    fn global_keyboard_event_loop(
-        recorder: Arc<AudioRecorder>,
-        tx: Sender<Vec<f32>>,
-        is_running: Arc<Mutex<bool>>,
-        config: Arc<Mutex<VoiceConfig>>
-    ) {
-        use std::sync::LazyLock;
-
-        // Use static variables to avoid closure capture issues
-        static GLOBAL_RECORDER: LazyLock<Mutex<Option<Arc<AudioRecorder>>>> = LazyLock::new(|| Mutex::new(None));
-        static GLOBAL_TX: LazyLock<Mutex<Option<Sender<Vec<f32>>>>> = LazyLock::new(|| Mutex::new(None));
-        static GLOBAL_CONFIG: LazyLock<Mutex<Option<Arc<Mutex<VoiceConfig>>>>> = LazyLock::new(|| Mutex::new(None));
-        static GLOBAL_IS_RUNNING: LazyLock<Mutex<Option<Arc<Mutex<bool>>>>> = LazyLock::new(|| Mutex::new(None));
-        static TARGET_KEY: LazyLock<Mutex<Key>> = LazyLock::new(|| Mutex::new(Key::F2));
-        static RECORDING_START_TIME: LazyLock<Mutex<Option<Instant>>> = LazyLock::new(|| Mutex::new(None));
-
-        // Set global values
-        {
-            let config_snapshot = config.lock().clone();
-            let target_key = Self::parse_hotkey(&config_snapshot.hotkey).unwrap_or(Key::F2);
-
-            *GLOBAL_RECORDER.lock() = Some(recorder);
-            *GLOBAL_TX.lock() = Some(tx);
-            *GLOBAL_CONFIG.lock() = Some(config);
-            *GLOBAL_IS_RUNNING.lock() = Some(is_running);
-            *TARGET_KEY.lock() = target_key;
-        }
-
-        // Start listening for global keyboard events
-        if let Err(e) = listen(|event| {
-            // Get global values
-            let recorder = if let Some(ref recorder) = *GLOBAL_RECORDER.lock() {
-                recorder.clone()
-            } else {
-                return;
-            };
-
-            let tx = if let Some(ref tx) = *GLOBAL_TX.lock() {
-                tx.clone()
-            } else {
-                return;
-            };
-
-            let config = if let Some(ref config) = *GLOBAL_CONFIG.lock() {
-                config.clone()
-            } else {
-                return;
-            };
-
-            let is_running = if let Some(ref is_running) = *GLOBAL_IS_RUNNING.lock() {
-                is_running.clone()
-            } else {
-                return;
-            };
-
-            let target_key = *TARGET_KEY.lock();
-
-            // Check if we should still be running
-            if !*is_running.lock() {
-                return;
-            }
-
-            let config_snapshot = config.lock().clone();
-
-            // Check if voice recognition is enabled
-            if !config_snapshot.enabled {
-                return;
-            }
+    /// Whether a dictation is being recorded right now
+    pub fn is_recording(&self) -> bool {
+        self.recorder.is_recording()
+    }
 
-            // Simple key press/release detection
-            let Event { event_type, .. } = event;
-            match event_type {
-                EventType::KeyPress(key) => {
-                    if key == target_key {
-                        // Start recording immediately when target key is pressed
-                        if !recorder.is_recording() {
-                            *RECORDING_START_TIME.lock() = Some(Instant::now());
-                            recorder.start_recording();
-                        }
-                    }
-                }
-                EventType::KeyRelease(key) => {
-                    if key == target_key {
-                        // Stop recording when target key is released
-                        if recorder.is_recording() {
-                            // Check if recording duration is at least 500ms
-                            if let Some(start_time) = *RECORDING_START_TIME.lock() {
-                                let recording_duration = start_time.elapsed();
-                                if recording_duration.as_millis() >= 500 {
-                                    let audio_data = recorder.stop_recording();
-                                    if !audio_data.is_empty() &&
-                                       audio_data.len() as f32 / 16000.0 >= config_snapshot.min_duration {
-                                        if let Err(e) = tx.send(audio_data) {
-                                            eprintln!("Failed to send audio data for processing: {}", e);
-                                        }
-                                    }
-                                } else {
-                                    recorder.stop_recording(); // Discard the recording
-                                }
-                            } else {
-                                recorder.stop_recording(); // Fallback if start time not recorded
-                            }
-                            // Clear the recording start time
-                            *RECORDING_START_TIME.lock() = None;
-                        }
-                    }
-                }
-                _ => {}
-            }
-        }) {
-            eprintln!("❌ Failed to start global keyboard listener: {:?}", e);
-        }
+    /// Get current audio level (for UI feedback)
+    pub fn get_audio_level(&self) -> f32 {
+        self.recorder.get_audio_level()
     }
 
     /// Parse hotkey string to rdev Key
@@ -266,8 +207,10 @@ impl VoiceProcessor {
 
     /// Transcription processing loop
     fn transcription_loop(
+        app: Option<AppHandle>,
         rx: Receiver<Vec<f32>>,
-        transcriber: Arc<WhisperTranscriber>,
+        transcriber: Arc<dyn Transcriber>,
+        injector: Arc<dyn TextInjector>,
         config: Arc<Mutex<VoiceConfig>>
     ) {
         while let Ok(audio_data) = rx.recv() {
@@ -286,25 +229,389 @@ impl VoiceProcessor {
             match transcriber.transcribe(&audio_data, language) {
                 Ok(text) => {
                     if !text.trim().is_empty() {
-                        println!("📝 {}", text.trim());
+                        // Only the length, dictated text would end up in plaintext log files
+                        info!("📝 Transcribed {} characters", text.trim().chars().count());
+
+                        // Let enabled plugins rewrite the transcription first
+                        let text = match app {
+                            Some(ref app) => run_plugin_hook(app, PluginHook::OnTranscription, text.trim()),
+                            None => text.trim().to_string(),
+                        };
 
                         // Send text to active window
-                        if let Err(e) = Self::send_text_to_active_window(&text.trim()) {
-                            eprintln!("❌ Failed to send text: {}", e);
+                        if let Err(e) = injector.inject(&text) {
+                            error!("❌ Failed to send text: {}", e);
+                        }
+
+                        if let Some(ref app) = app {
+                            Self::publish_transcription(app, &text);
+                            run_transcription_scripts(app, &text);
                         }
                     }
                 }
                 Err(e) => {
-                    eprintln!("❌ Transcription failed: {}", e);
+                    error!("❌ Transcription failed: {}", e);
                 }
             }
         }
     }
 
-    /// Send transcribed text to the active window
-    fn send_text_to_active_window(text: &str) -> Result<(), Box<dyn std::error::Error>> {
-        let mut enigo = Enigo::new(&Settings::default())?;
-        enigo.text(text)?;
-        Ok(())
+    /// Let the frontend and automations know about a finished transcription
+    fn publish_transcription(app: &AppHandle, text: &str) {
+        // The app dictated into, so a note made from the dictation knows its source
+        let source = get_foreground_app_info();
+        let _ = app.emit("voice-transcription-completed", text);
+        let _ = app.emit("dictation-captured", serde_json::json!({ "text": text, "source": source }));
+        dispatch_webhook_event(app, "transcription.completed", serde_json::json!({ "text": text, "source": source }));
+        publish_mqtt_event("dictation", serde_json::json!({ "text": text, "source": source }));
+    }
+}
+
+impl Drop for VoiceProcessor {
+    fn drop(&mut self) {
+        // Leaves the hotkey listener inert until a new processor replaces it
+        *self.is_running.lock() = false;
+    }
+}
+
+/// What started the current recording
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DictationMode {
+    /// Records while the hotkey is held down
+    Hold,
+    /// Started and stopped by separate presses, e.g. a headset button or the command line
+    Toggle,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DictationState {
+    Idle,
+    Recording { started: Instant, mode: DictationMode },
+    /// The maximum duration ended a recording while the hotkey is still down,
+    /// so key repeats don't start another one before it is released
+    HeldAfterCutoff,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DictationEvent {
+    HotkeyPressed,
+    HotkeyReleased,
+    Toggle,
+    /// Periodic check against the maximum duration
+    Tick,
+}
+
+/// Push-to-talk and toggle state machine, fed by the hotkey, media keys and the command line.
+/// Finished recordings go to the transcription loop.
+struct Dictation {
+    recorder: Arc<dyn Recorder>,
+    tx: Sender<Vec<f32>>,
+    config: Arc<Mutex<VoiceConfig>>,
+    state: DictationState,
+}
+
+impl Dictation {
+    fn handle(&mut self, event: DictationEvent, now: Instant) {
+        match (event, self.state) {
+            (DictationEvent::HotkeyPressed, DictationState::Idle) => self.start(DictationMode::Hold, now),
+            (DictationEvent::HotkeyReleased, DictationState::Recording { started, mode: DictationMode::Hold }) => {
+                if now.duration_since(started) >= MIN_HOLD {
+                    self.finish();
+                } else {
+                    self.discard();
+                }
+            }
+            (DictationEvent::HotkeyReleased, DictationState::HeldAfterCutoff) => self.state = DictationState::Idle,
+            (DictationEvent::Toggle, DictationState::Recording { .. }) => self.finish(),
+            (DictationEvent::Toggle, _) => self.start(DictationMode::Toggle, now),
+            (DictationEvent::Tick, DictationState::Recording { started, mode }) => {
+                let max_duration = Duration::from_secs_f32(self.config.lock().max_duration.max(0.0));
+                if now.duration_since(started) >= max_duration {
+                    info!("⏱️ Dictation reached the maximum duration of {:.0}s", max_duration.as_secs_f32());
+                    self.finish();
+                    if mode == DictationMode::Hold {
+                        self.state = DictationState::HeldAfterCutoff;
+                    }
+                }
+            }
+            // Key repeats while held, and the hotkey during a toggled recording
+            _ => {}
+        }
     }
-}
\ No newline at end of file
+
+    fn is_recording(&self) -> bool {
+        matches!(self.state, DictationState::Recording { .. })
+    }
+
+    fn start(&mut self, mode: DictationMode, now: Instant) {
+        self.recorder.start_recording();
+        self.state = DictationState::Recording { started: now, mode };
+    }
+
+    /// Stop recording and queue the audio for transcription if it is long enough
+    fn finish(&mut self) {
+        self.state = DictationState::Idle;
+        let audio_data = self.recorder.stop_recording();
+        let min_duration = self.config.lock().min_duration;
+        if !audio_data.is_empty() && audio_data.len() as f32 / 16000.0 >= min_duration {
+            if let Err(e) = self.tx.send(audio_data) {
+                error!("Failed to send audio data for processing: {}", e);
+            }
+        }
+    }
+
+    fn discard(&mut self) {
+        self.state = DictationState::Idle;
+        self.recorder.stop_recording();
+    }
+}
+
+/// Turns hotkey events into dictation events, owned by the input listener closure
+struct HotkeyListener {
+    dictation: Arc<Mutex<Dictation>>,
+    config: Arc<Mutex<VoiceConfig>>,
+    is_running: Arc<Mutex<bool>>,
+    target_key: Key,
+}
+
+impl HotkeyListener {
+    fn handle(&self, event: Event) {
+        // Check if we should still be running
+        if !*self.is_running.lock() {
+            return;
+        }
+
+        // Check if voice recognition is enabled
+        if !self.config.lock().enabled {
+            return;
+        }
+
+        let dictation_event = match event.event_type {
+            EventType::KeyPress(key) if key == self.target_key => DictationEvent::HotkeyPressed,
+            EventType::KeyRelease(key) if key == self.target_key => DictationEvent::HotkeyReleased,
+            _ => return,
+        };
+        self.dictation.lock().handle(dictation_event, Instant::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    /// Hands out the same number of samples for every recording
+    struct FakeRecorder {
+        samples: usize,
+        recording: AtomicBool,
+        starts: AtomicUsize,
+    }
+
+    impl FakeRecorder {
+        fn new(samples: usize) -> Arc<Self> {
+            Arc::new(Self { samples, recording: AtomicBool::new(false), starts: AtomicUsize::new(0) })
+        }
+    }
+
+    impl Recorder for FakeRecorder {
+        fn start_recording(&self) {
+            self.starts.fetch_add(1, Ordering::SeqCst);
+            self.recording.store(true, Ordering::SeqCst);
+        }
+
+        fn stop_recording(&self) -> Vec<f32> {
+            if self.recording.swap(false, Ordering::SeqCst) {
+                vec![0.1; self.samples]
+            } else {
+                Vec::new()
+            }
+        }
+
+        fn is_recording(&self) -> bool {
+            self.recording.load(Ordering::SeqCst)
+        }
+
+        fn get_audio_level(&self) -> f32 {
+            0.0
+        }
+    }
+
+    struct FakeTranscriber {
+        text: String,
+    }
+
+    impl Transcriber for FakeTranscriber {
+        fn transcribe(&self, _audio_data: &[f32], _language: Option<&str>) -> Result<String, Box<dyn Error>> {
+            Ok(self.text.clone())
+        }
+
+        fn get_mode_info(&self) -> String {
+            "Fake".to_string()
+        }
+    }
+
+    struct FakeInjector {
+        tx: Sender<String>,
+    }
+
+    impl TextInjector for FakeInjector {
+        fn inject(&self, text: &str) -> Result<(), Box<dyn Error>> {
+            self.tx.send(text.to_string()).map_err(|e| e.to_string())?;
+            Ok(())
+        }
+    }
+
+    fn test_config() -> VoiceConfig {
+        VoiceConfig {
+            enabled: true,
+            min_duration: 0.1,
+            max_duration: 2.0,
+            ..VoiceConfig::default()
+        }
+    }
+
+    fn dictation(samples: usize) -> (Dictation, Arc<FakeRecorder>, Receiver<Vec<f32>>) {
+        let recorder = FakeRecorder::new(samples);
+        let (tx, rx) = unbounded();
+        let dictation = Dictation {
+            recorder: recorder.clone(),
+            tx,
+            config: Arc::new(Mutex::new(test_config())),
+            state: DictationState::Idle,
+        };
+        (dictation, recorder, rx)
+    }
+
+    fn millis(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn hold_records_until_hotkey_release() {
+        let (mut dictation, recorder, rx) = dictation(16000);
+        let start = Instant::now();
+
+        dictation.handle(DictationEvent::HotkeyPressed, start);
+        assert!(recorder.is_recording());
+        // Key repeats while held don't restart the recording
+        dictation.handle(DictationEvent::HotkeyPressed, start + millis(100));
+        assert_eq!(recorder.starts.load(Ordering::SeqCst), 1);
+
+        dictation.handle(DictationEvent::HotkeyReleased, start + millis(800));
+        assert!(!recorder.is_recording());
+        assert_eq!(rx.try_recv().map(|audio| audio.len()), Ok(16000));
+    }
+
+    #[test]
+    fn hold_shorter_than_minimum_is_discarded() {
+        let (mut dictation, recorder, rx) = dictation(16000);
+        let start = Instant::now();
+
+        dictation.handle(DictationEvent::HotkeyPressed, start);
+        dictation.handle(DictationEvent::HotkeyReleased, start + MIN_HOLD - millis(1));
+        assert!(!recorder.is_recording());
+        assert!(rx.try_recv().is_err());
+
+        dictation.handle(DictationEvent::HotkeyPressed, start + millis(1000));
+        dictation.handle(DictationEvent::HotkeyReleased, start + millis(1000) + MIN_HOLD);
+        assert!(rx.try_recv().is_ok());
+    }
+
+    #[test]
+    fn toggle_starts_and_stops_without_minimum_hold() {
+        let (mut dictation, recorder, rx) = dictation(16000);
+        let start = Instant::now();
+
+        dictation.handle(DictationEvent::Toggle, start);
+        assert!(dictation.is_recording());
+        // Releasing the hotkey doesn't end a toggled recording
+        dictation.handle(DictationEvent::HotkeyPressed, start + millis(50));
+        dictation.handle(DictationEvent::HotkeyReleased, start + millis(900));
+        assert!(recorder.is_recording());
+
+        dictation.handle(DictationEvent::Toggle, start + millis(1000));
+        assert!(!dictation.is_recording());
+        assert!(!recorder.is_recording());
+        assert!(rx.try_recv().is_ok());
+    }
+
+    #[test]
+    fn audio_below_min_duration_is_dropped() {
+        // 50ms of audio against a 100ms minimum
+        let (mut dictation, _recorder, rx) = dictation(800);
+        let start = Instant::now();
+
+        dictation.handle(DictationEvent::Toggle, start);
+        dictation.handle(DictationEvent::Toggle, start + millis(1000));
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn max_duration_ends_held_recording() {
+        let (mut dictation, recorder, rx) = dictation(16000);
+        let start = Instant::now();
+
+        dictation.handle(DictationEvent::HotkeyPressed, start);
+        dictation.handle(DictationEvent::Tick, start + millis(1900));
+        assert!(recorder.is_recording());
+
+        dictation.handle(DictationEvent::Tick, start + millis(2000));
+        assert!(!recorder.is_recording());
+        assert!(rx.try_recv().is_ok());
+
+        // Still held: key repeats and the release don't record again
+        dictation.handle(DictationEvent::HotkeyPressed, start + millis(2100));
+        assert!(!recorder.is_recording());
+        dictation.handle(DictationEvent::HotkeyReleased, start + millis(2500));
+        assert!(rx.try_recv().is_err());
+
+        dictation.handle(DictationEvent::HotkeyPressed, start + millis(3000));
+        assert!(recorder.is_recording());
+    }
+
+    #[test]
+    fn max_duration_ends_toggled_recording() {
+        let (mut dictation, recorder, rx) = dictation(16000);
+        let start = Instant::now();
+
+        dictation.handle(DictationEvent::Toggle, start);
+        dictation.handle(DictationEvent::Tick, start + millis(2500));
+        assert!(!recorder.is_recording());
+        assert!(rx.try_recv().is_ok());
+
+        // The next toggle starts a new recording instead of stopping the old one
+        dictation.handle(DictationEvent::Toggle, start + millis(3000));
+        assert!(recorder.is_recording());
+    }
+
+    fn processor_with_transcription(text: &str) -> (VoiceProcessor, Receiver<String>) {
+        let (tx, rx) = unbounded();
+        let processor = VoiceProcessor::with_components(
+            None,
+            test_config(),
+            FakeRecorder::new(16000),
+            Arc::new(FakeTranscriber { text: text.to_string() }),
+            Arc::new(FakeInjector { tx }),
+        );
+        (processor, rx)
+    }
+
+    #[test]
+    fn transcription_is_trimmed_and_injected() {
+        let (processor, injected) = processor_with_transcription("  Hello world \n");
+
+        assert!(processor.toggle_recording());
+        assert!(!processor.toggle_recording());
+        assert_eq!(injected.recv_timeout(Duration::from_secs(5)).as_deref(), Ok("Hello world"));
+    }
+
+    #[test]
+    fn blank_transcription_is_not_injected() {
+        let (processor, injected) = processor_with_transcription(" \n ");
+
+        assert!(processor.toggle_recording());
+        assert!(!processor.toggle_recording());
+        assert!(injected.recv_timeout(millis(500)).is_err());
+    }
+}
@@ -18,13 +18,13 @@ impl AudioRecorder {
         let device = host.default_input_device()
             .ok_or("No voice input device found!")?;
 
-        println!("Using audio device: {}", device.name()?);
+        info!("Using audio device: {}", device.name()?);
 
         let config = device.default_input_config()?;
         let sample_rate = config.sample_rate().0;
         let channels = config.channels();
 
-        println!("Audio config: {} Hz, {} channels", sample_rate, channels);
+        info!("Audio config: {} Hz, {} channels", sample_rate, channels);
 
         let is_recording = Arc::new(Mutex::new(false));
         let audio_data = Arc::new(Mutex::new(Vec::new()));
@@ -45,7 +45,7 @@ impl AudioRecorder {
                 }
             },
             |err| {
-                eprintln!("Audio stream error: {}", err);
+                error!("Audio stream error: {}", err);
             },
             None,
         )?;
@@ -64,16 +64,29 @@ impl AudioRecorder {
     pub fn start_recording(&self) {
         *self.is_recording.lock() = true;
         self.audio_data.lock().clear();
-        println!("🎤 Recording started...");
+        info!("🎤 Recording started...");
     }
 
     pub fn stop_recording(&self) -> Vec<f32> {
-        *self.is_recording.lock() = false;
-        let data = self.audio_data.lock().clone();
-        println!("⏹️  Recording stopped, recorded {:.2} seconds of audio",
+        let data = self.stop_recording_raw();
+        info!("⏹️  Recording stopped, recorded {:.2} seconds of audio",
                 data.len() as f32 / self.sample_rate as f32);
 
-        // Resample to 16kHz for Whisper (simple downsampling)
+        let resampled = self.resample_to_16k(&data);
+        info!("After resampling: {:.2} seconds of audio (16kHz)",
+                resampled.len() as f32 / 16000.0);
+        resampled
+    }
+
+    /// Stop and return the samples as captured, interleaved at the device rate
+    pub fn stop_recording_raw(&self) -> Vec<f32> {
+        *self.is_recording.lock() = false;
+        self.audio_data.lock().clone()
+    }
+
+    /// Convert captured samples to 16kHz mono for Whisper
+    pub fn resample_to_16k(&self, data: &[f32]) -> Vec<f32> {
+        // Simple downsampling
         let target_sample_rate = 16000.0;
         let resample_ratio = self.sample_rate as f32 / target_sample_rate;
         let new_length = (data.len() as f32 / resample_ratio) as usize;
@@ -91,12 +104,17 @@ impl AudioRecorder {
                 }
             }
         }
-
-        println!("After resampling: {:.2} seconds of audio (16kHz)",
-                resampled.len() as f32 / target_sample_rate);
         resampled
     }
 
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    pub fn channels(&self) -> u16 {
+        self.channels
+    }
+
     pub fn is_recording(&self) -> bool {
         *self.is_recording.lock()
     }
@@ -118,4 +136,31 @@ impl AudioRecorder {
 
         rms.sqrt()
     }
+}
+
+/// Audio capture behind the voice processor, so the pipeline can run on prepared samples instead of a microphone
+pub trait Recorder: Send + Sync {
+    fn start_recording(&self);
+    /// Stop and return the captured audio as 16kHz mono
+    fn stop_recording(&self) -> Vec<f32>;
+    fn is_recording(&self) -> bool;
+    fn get_audio_level(&self) -> f32;
+}
+
+impl Recorder for AudioRecorder {
+    fn start_recording(&self) {
+        AudioRecorder::start_recording(self)
+    }
+
+    fn stop_recording(&self) -> Vec<f32> {
+        AudioRecorder::stop_recording(self)
+    }
+
+    fn is_recording(&self) -> bool {
+        AudioRecorder::is_recording(self)
+    }
+
+    fn get_audio_level(&self) -> f32 {
+        AudioRecorder::get_audio_level(self)
+    }
 }
\ No newline at end of file
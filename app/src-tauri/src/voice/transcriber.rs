@@ -1,21 +1,65 @@
 use std::error::Error;
+use parking_lot::Mutex;
 use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
 
 pub struct WhisperTranscriber {
-    context: WhisperContext,
-    mode_info: String,
+    /// None while unloaded, reloaded on the next transcription
+    context: Mutex<Option<WhisperContext>>,
+    model_path: String,
+    use_gpu: bool,
+    mode_info: Mutex<String>,
 }
 
 impl WhisperTranscriber {
     /// Create a new WhisperTranscriber with automatic GPU/CPU fallback
     pub fn new(model_path: &str, use_gpu: bool) -> Result<Self, Box<dyn Error>> {
         let (context, mode_info) = create_whisper_context_with_auto_fallback(model_path, use_gpu)?;
-        Ok(Self { context, mode_info })
+        Ok(Self {
+            context: Mutex::new(Some(context)),
+            model_path: model_path.to_string(),
+            use_gpu,
+            mode_info: Mutex::new(mode_info),
+        })
+    }
+
+    /// Create a transcriber that loads the model on first use, so startup stays fast
+    pub fn new_unloaded(model_path: &str, use_gpu: bool) -> Self {
+        Self {
+            context: Mutex::new(None),
+            model_path: model_path.to_string(),
+            use_gpu,
+            mode_info: Mutex::new("Model not loaded".to_string()),
+        }
     }
 
     /// Get the current mode info (GPU/CPU)
-    pub fn get_mode_info(&self) -> &str {
-        &self.mode_info
+    pub fn get_mode_info(&self) -> String {
+        self.mode_info.lock().clone()
+    }
+
+    /// Load the model now if it isn't loaded yet; returns false if it already was
+    pub fn preload(&self) -> Result<bool, Box<dyn Error>> {
+        let mut context = self.context.lock();
+        if context.is_some() {
+            return Ok(false);
+        }
+        *context = Some(self.load_context()?);
+        Ok(true)
+    }
+
+    fn load_context(&self) -> Result<WhisperContext, Box<dyn Error>> {
+        let (context, mode_info) = create_whisper_context_with_auto_fallback(&self.model_path, self.use_gpu)?;
+        *self.mode_info.lock() = mode_info;
+        Ok(context)
+    }
+
+    pub fn is_loaded(&self) -> bool {
+        self.context.lock().is_some()
+    }
+
+    /// Release the model memory; returns false if it was not loaded
+    pub fn unload(&self) -> bool {
+        self.context.lock().take().is_some()
     }
 
     /// Transcribe audio data to text
@@ -29,8 +73,15 @@ impl WhisperTranscriber {
             return Ok(String::new());
         }
 
+        // Load the model if it was deferred at startup or unloaded while idle
+        let mut context = self.context.lock();
+        if context.is_none() {
+            info!("🔄 Loading Whisper model for transcription");
+            *context = Some(self.load_context()?);
+        }
+
         // Create state
-        let mut state = self.context.create_state()?;
+        let mut state = context.as_ref().ok_or("Whisper model not loaded")?.create_state()?;
 
         // Create parameters
         let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
@@ -63,6 +114,50 @@ impl WhisperTranscriber {
     }
 }
 
+/// Speech to text behind the voice processor, so the pipeline can run without a Whisper model
+pub trait Transcriber: Send + Sync {
+    fn transcribe(&self, audio_data: &[f32], language: Option<&str>) -> Result<String, Box<dyn Error>>;
+    fn get_mode_info(&self) -> String;
+    /// Load the model ahead of the first transcription; returns false if there was nothing to load
+    fn preload(&self) -> Result<bool, Box<dyn Error>> {
+        Ok(false)
+    }
+    /// Release the model memory; returns false if nothing was unloaded
+    fn unload(&self) -> bool {
+        false
+    }
+    /// Approximate memory held by the loaded model
+    fn memory_bytes(&self) -> u64 {
+        0
+    }
+}
+
+impl Transcriber for WhisperTranscriber {
+    fn transcribe(&self, audio_data: &[f32], language: Option<&str>) -> Result<String, Box<dyn Error>> {
+        WhisperTranscriber::transcribe(self, audio_data, language)
+    }
+
+    fn get_mode_info(&self) -> String {
+        WhisperTranscriber::get_mode_info(self)
+    }
+
+    fn preload(&self) -> Result<bool, Box<dyn Error>> {
+        WhisperTranscriber::preload(self)
+    }
+
+    fn unload(&self) -> bool {
+        WhisperTranscriber::unload(self)
+    }
+
+    fn memory_bytes(&self) -> u64 {
+        // ggml models are read into memory whole, so the file size is a close estimate
+        if !self.is_loaded() {
+            return 0;
+        }
+        std::fs::metadata(&self.model_path).map(|metadata| metadata.len()).unwrap_or(0)
+    }
+}
+
 /// Detect CUDA support (Windows specific)
 fn detect_cuda_support() -> (bool, String) {
     #[cfg(target_os = "windows")]
@@ -152,11 +247,11 @@ fn create_whisper_context_with_auto_fallback(
     prefer_gpu: bool,
 ) -> Result<(WhisperContext, String), Box<dyn Error>> {
     let (has_gpu, gpu_info) = detect_gpu_capabilities();
-    println!("🔍 {}", gpu_info);
+    debug!("🔍 {}", gpu_info);
 
     // Try GPU first if preferred and available
     if prefer_gpu && has_gpu {
-        println!("🚀 GPU support detected, attempting to enable GPU acceleration...");
+        info!("🚀 GPU support detected, attempting to enable GPU acceleration...");
 
         // Check which GPU features are compiled in
         let mut ctx_params = WhisperContextParameters::default();
@@ -164,34 +259,34 @@ fn create_whisper_context_with_auto_fallback(
 
         match WhisperContext::new_with_params(model_path, ctx_params) {
             Ok(ctx) => {
-                println!("✅ GPU mode enabled successfully (CUDA acceleration)");
+                info!("✅ GPU mode enabled successfully (CUDA acceleration)");
                 return Ok((ctx, "GPU (CUDA)".to_string()));
             }
             Err(e) => {
-                println!("⚠️ GPU mode failed: {}", e);
-                println!("💡 Possible reasons:");
-                println!("   - Incompatible CUDA runtime version");
-                println!("   - Insufficient GPU memory");
-                println!("   - Model file incompatible with GPU version");
-                println!("🔄 Auto-fallback to CPU mode");
+                warn!("⚠️ GPU mode failed: {}", e);
+                info!("💡 Possible reasons:");
+                info!("   - Incompatible CUDA runtime version");
+                info!("   - Insufficient GPU memory");
+                info!("   - Model file incompatible with GPU version");
+                info!("🔄 Auto-fallback to CPU mode");
             }
         }
         if prefer_gpu && has_gpu {
-            println!("⚡ GPU hardware detected, but CUDA feature not enabled");
-            println!("💡 To enable GPU acceleration on Windows:");
-            println!("   Add 'cuda' feature to build");
-            println!("🔄 Using CPU mode");
+            info!("⚡ GPU hardware detected, but CUDA feature not enabled");
+            info!("💡 To enable GPU acceleration on Windows:");
+            info!("   Add 'cuda' feature to build");
+            info!("🔄 Using CPU mode");
         }
     } else if prefer_gpu && !has_gpu {
-        println!("🔧 GPU acceleration requested but no GPU support detected, using CPU mode");
+        debug!("🔧 GPU acceleration requested but no GPU support detected, using CPU mode");
     } else {
-        println!("🔧 CPU mode selected");
+        debug!("🔧 CPU mode selected");
     }
 
     // Fallback to CPU mode
-    println!("🔧 Initializing CPU mode...");
+    debug!("🔧 Initializing CPU mode...");
     let ctx_params = WhisperContextParameters::default();
     let ctx = WhisperContext::new_with_params(model_path, ctx_params)?;
-    println!("✅ CPU mode enabled successfully");
+    info!("✅ CPU mode enabled successfully");
     Ok((ctx, "CPU".to_string()))
 }
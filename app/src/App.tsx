@@ -25,7 +25,11 @@ import { listen } from "@tauri-apps/api/event";
 import QuickNotePage from "./pages/quicknote";
 import QuickAIPage from "./pages/quickai";
 import QuickToolPage from "./pages/quicktool";
+import ColorPickerPage from "./pages/colorpicker";
+import PalettePage from "./pages/palette";
 import { useQuicknoteHotkey } from "./hooks/useQuicknoteHotkey";
+import { usePaletteItems } from "./hooks/usePaletteItems";
+import { useDeepLinks } from "./hooks/useDeepLinks";
 
 const HomePage = lazy(() => import('./pages/index'));
 const SignInPage = lazy(() => import('./pages/signin'));
@@ -123,6 +127,8 @@ const getWindowType = () => {
   if (path.startsWith('/quicktool')) return 'quicktool';
   if (path.startsWith('/quicknote')) return 'quicknote';
   if (path.startsWith('/quickai')) return 'quickai';
+  if (path.startsWith('/colorpicker')) return 'colorpicker';
+  if (path.startsWith('/palette')) return 'palette';
   return 'main';
 };
 
@@ -134,6 +140,8 @@ function AppRoutes() {
   if (windowType === 'main' && isDesktop()) {
     useQuickaiHotkey();
     useQuicknoteHotkey(true);
+    usePaletteItems();
+    useDeepLinks();
   }
 
   // Listen for navigation commands from Tauri (only for current window type)
@@ -225,6 +233,20 @@ function AppRoutes() {
         </Suspense>
       );
 
+    case 'colorpicker':
+      return (
+        <Routes>
+          <Route path="*" element={<ColorPickerPage />} />
+        </Routes>
+      );
+
+    case 'palette':
+      return (
+        <Routes>
+          <Route path="*" element={<PalettePage />} />
+        </Routes>
+      );
+
     default: // main window
       return (
         <Suspense fallback={<LoadingPage />}>
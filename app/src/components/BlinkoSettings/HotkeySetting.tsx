@@ -9,7 +9,6 @@ import { useTranslation } from 'react-i18next';
 import { Item, ItemWithTooltip } from './Item';
 import { useEffect, useState, useRef } from 'react';
 import { invoke } from '@tauri-apps/api/core';
-import { enable, isEnabled, disable } from '@tauri-apps/plugin-autostart';
 import { isDesktop, isInTauri, isWindows } from '@/lib/tauriHelper';
 import { CollapsibleCard } from '../Common/CollapsibleCard';
 import { ToastPlugin } from '@/store/module/Toast/Toast';
@@ -44,6 +43,7 @@ export const HotkeySetting = observer(() => {
   const [recordedAIKeys, setRecordedAIKeys] = useState<string[]>([]);
   const [registeredShortcuts, setRegisteredShortcuts] = useState<Record<string, string>>({});
   const [autoStartEnabled, setAutoStartEnabled] = useState(false);
+  const [autoStartHidden, setAutoStartHidden] = useState(true);
   const recordingRef = useRef<HTMLInputElement>(null);
   const recordingAIRef = useRef<HTMLInputElement>(null);
 
@@ -107,8 +107,9 @@ export const HotkeySetting = observer(() => {
   const getAutoStartStatus = async () => {
     if (!isTauriDesktop) return;
     try {
-      const enabled = await isEnabled();
-      setAutoStartEnabled(enabled);
+      const status = await invoke<{ enabled: boolean; hidden: boolean }>('get_autostart_status');
+      setAutoStartEnabled(status.enabled);
+      setAutoStartHidden(status.hidden);
     } catch (error) {
       console.error('Failed to get autostart status:', error);
     }
@@ -370,9 +371,9 @@ export const HotkeySetting = observer(() => {
 
     try {
       if (enabled) {
-        await enable();
+        await invoke('enable_autostart', { hidden: autoStartHidden });
       } else {
-        await disable();
+        await invoke('disable_autostart');
       }
       setAutoStartEnabled(enabled);
     } catch (error) {
@@ -383,6 +384,21 @@ export const HotkeySetting = observer(() => {
     }
   };
 
+  // Start in the tray or with the main window shown, re-registering the login item if enabled
+  const toggleAutoStartHidden = async (hidden: boolean) => {
+    if (!isTauriDesktop) return;
+
+    setAutoStartHidden(hidden);
+    if (!autoStartEnabled) return;
+    try {
+      await invoke('enable_autostart', { hidden });
+    } catch (error) {
+      console.error('Failed to update autostart:', error);
+      toast.error((error instanceof Error ? error.message : String(error)));
+      await getAutoStartStatus();
+    }
+  };
+
   // Reset to default shortcut
   const resetQuickNoteToDefault = async () => {
     await saveConfig({ quickNote: DEFAULT_HOTKEY_CONFIG.quickNote });
@@ -435,6 +451,23 @@ export const HotkeySetting = observer(() => {
             }
           />
 
+          {autoStartEnabled && (
+            <Item
+              leftContent={
+                <ItemWithTooltip
+                  content="Start hidden"
+                  toolTipContent="Start in the system tray without showing the main window"
+                />
+              }
+              rightContent={
+                <Switch
+                  isSelected={autoStartHidden}
+                  onValueChange={toggleAutoStartHidden}
+                />
+              }
+            />
+          )}
+
           {/* Hotkey enable switch */}
           <Item
             leftContent={
@@ -69,6 +69,8 @@ export const CommonLayout = observer(({ children, header }: { children?: React.R
     location.pathname == '/quicknote' ||
     location.pathname == '/quickai' ||
     location.pathname == '/quicktool' ||
+    location.pathname == '/colorpicker' ||
+    location.pathname == '/palette' ||
     location.pathname == '/signup' ||
     location.pathname == '/api-doc' ||
     location.pathname.includes('/share') ||
@@ -0,0 +1,54 @@
+import { useEffect } from 'react';
+import { useNavigate } from 'react-router-dom';
+import { invoke } from '@tauri-apps/api/core';
+import { isInTauri } from '@/lib/tauriHelper';
+
+interface OpenNoteLink {
+  action: 'open-note';
+  noteId: string;
+}
+
+/**
+ * Opens notes from blinko:// links in the main window.
+ * Links that arrived before the page was loaded are waiting in Rust and taken once listening.
+ */
+export const useDeepLinks = () => {
+  const navigate = useNavigate();
+
+  useEffect(() => {
+    if (!isInTauri()) return;
+
+    let isMounted = true;
+    let unlisten: (() => void) | null = null;
+
+    const openNote = (link: OpenNoteLink) => {
+      navigate(`/detail?id=${encodeURIComponent(link.noteId)}`);
+    };
+
+    const setupListener = async () => {
+      const { listen } = await import('@tauri-apps/api/event');
+      const unlistenOpenNote = await listen<OpenNoteLink>('deep-link-open-note', (event) => {
+        openNote(event.payload);
+      });
+      if (!isMounted) {
+        unlistenOpenNote();
+        return;
+      }
+      unlisten = unlistenOpenNote;
+
+      // Marks the frontend ready, later links arrive as events
+      try {
+        const pending = await invoke<OpenNoteLink[]>('take_pending_deep_links');
+        pending.filter(link => link.action === 'open-note').forEach(openNote);
+      } catch (error) {
+        console.error('Failed to take pending deep links:', error);
+      }
+    };
+    setupListener();
+
+    return () => {
+      isMounted = false;
+      unlisten?.();
+    };
+  }, [navigate]);
+};
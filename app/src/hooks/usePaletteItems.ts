@@ -0,0 +1,116 @@
+import { useEffect } from 'react';
+import { useNavigate } from 'react-router-dom';
+import { useTranslation } from 'react-i18next';
+import { autorun } from 'mobx';
+import { invoke } from '@tauri-apps/api/core';
+import { isInTauri, isDesktop } from '@/lib/tauriHelper';
+import { RootStore } from '@/store';
+import { BlinkoStore } from '@/store/blinkoStore';
+
+type PaletteItemKind = 'note' | 'command' | 'action';
+
+interface PaletteItem {
+  id: string;
+  kind: PaletteItemKind;
+  title: string;
+  subtitle?: string | null;
+  keywords?: string[];
+  shortcut?: string | null;
+}
+
+// Most recent notes offered in the palette
+const MAX_PALETTE_NOTES = 200;
+
+const noteTitle = (content: string) => {
+  const line = content.split('\n').map(line => line.replace(/^#+/, '').trim()).find(line => line.length > 0);
+  return (line || '…').slice(0, 80);
+};
+
+/**
+ * Feeds the command palette window from the main window and runs what is picked there.
+ * Actions navigate the main window, commands run in the background, notes open their detail page.
+ */
+export const usePaletteItems = () => {
+  const navigate = useNavigate();
+  const { t } = useTranslation();
+  const blinko = RootStore.Get(BlinkoStore);
+
+  useEffect(() => {
+    if (!isInTauri() || !isDesktop()) return;
+
+    const actions: PaletteItem[] = [
+      { id: 'action:/', kind: 'action', title: t('blinko'), keywords: ['home'] },
+      { id: 'action:/?path=notes', kind: 'action', title: t('notes') },
+      { id: 'action:/ai', kind: 'action', title: t('ai'), keywords: ['chat'] },
+      { id: 'action:/resources', kind: 'action', title: t('resources'), keywords: ['files', 'attachments'] },
+      { id: 'action:/review', kind: 'action', title: t('daily-review'), keywords: ['review'] },
+      { id: 'action:/analytics', kind: 'action', title: t('analytics'), keywords: ['stats'] },
+      { id: 'action:/settings', kind: 'action', title: t('settings'), keywords: ['preferences'] },
+    ];
+    const commands: PaletteItem[] = [
+      { id: 'command:toggle_quicknote_window', kind: 'command', title: t('palette.new-note'), keywords: ['create', 'quick note'] },
+      { id: 'command:toggle_quickai_window', kind: 'command', title: t('quick-ai'), keywords: ['ask'] },
+      { id: 'command:open_daily_note', kind: 'command', title: t('palette.daily-note'), keywords: ['journal', 'today'] },
+    ];
+
+    invoke('update_palette_items', { kind: 'action', items: actions }).catch(error => {
+      console.error('Failed to update palette actions:', error);
+    });
+    invoke('update_palette_items', { kind: 'command', items: commands }).catch(error => {
+      console.error('Failed to update palette commands:', error);
+    });
+
+    // Re-push notes whenever the loaded list changes
+    const disposeNotes = autorun(() => {
+      const notes: PaletteItem[] = (blinko.blinkoList.value ?? [])
+        .concat(blinko.noteOnlyList.value ?? [])
+        .slice(0, MAX_PALETTE_NOTES)
+        .map(note => ({
+          id: `note:${note.id}`,
+          kind: 'note' as const,
+          title: noteTitle(note.content ?? ''),
+          subtitle: note.tags?.map(tag => tag.tag?.name).filter(Boolean).join(', ') || null,
+          keywords: [(note.content ?? '').slice(0, 200)],
+        }));
+      invoke('update_palette_items', { kind: 'note', items: notes }).catch(error => {
+        console.error('Failed to update palette notes:', error);
+      });
+    });
+
+    let unlisten: (() => void) | null = null;
+    let isMounted = true;
+    const setupListener = async () => {
+      const { listen } = await import('@tauri-apps/api/event');
+      const unlistenSelection = await listen<PaletteItem>('palette-item-selected', async (event) => {
+        const [, target] = event.payload.id.split(/:(.*)/s);
+        try {
+          switch (event.payload.kind) {
+            case 'action':
+              navigate(target);
+              break;
+            case 'command':
+              await invoke(target);
+              break;
+            case 'note':
+              navigate(`/detail?id=${target}`);
+              break;
+          }
+        } catch (error) {
+          console.error('Failed to run palette item:', error);
+        }
+      });
+      if (isMounted) {
+        unlisten = unlistenSelection;
+      } else {
+        unlistenSelection();
+      }
+    };
+    setupListener();
+
+    return () => {
+      isMounted = false;
+      disposeNotes();
+      unlisten?.();
+    };
+  }, [navigate, t]);
+};
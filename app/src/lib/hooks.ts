@@ -2,7 +2,7 @@ import { useEffect, useState, useRef } from "react";
 import { helper } from "./helper";
 import { BlinkoStore } from "@/store/blinkoStore";
 import { RootStore } from "@/store";
-import { isAndroid, isInTauri } from '@/lib/tauriHelper';
+import { isAndroid, isIOS, isInTauri } from '@/lib/tauriHelper';
 import { ShowEditBlinkoModel } from '@/components/BlinkoRightClickMenu';
 import { eventBus } from '@/lib/event';
 
@@ -183,7 +183,8 @@ let isInitialized = false;
 
 // Singleton function to initialize Android shortcuts listener
 const initializeAndroidShortcuts = () => {
-  if (isInitialized || !isAndroid() || !isInTauri()) {
+  // iOS quick actions are delivered through the same storage key as Android shortcuts
+  if (isInitialized || !(isAndroid() || isIOS()) || !isInTauri()) {
     return;
   }
 
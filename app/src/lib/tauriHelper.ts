@@ -29,6 +29,18 @@ export function isAndroid() {
     }
 }
 
+/**
+ * isIOS
+ * @returns wether the platform is ios
+ */
+export function isIOS() {
+    try {
+        return platform() === 'ios';
+    } catch (error) {
+        return false
+    }
+}
+
 export function isDesktop() {
     try {
        return platform() === 'macos' || platform() === 'windows' || platform() === 'linux';
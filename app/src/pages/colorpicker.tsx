@@ -0,0 +1,62 @@
+import { useEffect } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { isInTauri } from "@/lib/tauriHelper";
+
+// Full screen overlay shown by the color picker hotkey: click samples the pixel under the cursor, Escape cancels
+const ColorPickerPage = () => {
+  const confirmPick = async () => {
+    try {
+      await invoke('confirm_color_pick');
+    } catch (error) {
+      console.error("❌ Failed to pick color:", error);
+    }
+  };
+
+  const cancelPick = async () => {
+    try {
+      await invoke('cancel_color_pick');
+    } catch (error) {
+      console.error("❌ Failed to cancel color pick:", error);
+    }
+  };
+
+  useEffect(() => {
+    if (!isInTauri()) return;
+
+    document.title = "Color Picker";
+    document.documentElement.style.background = 'transparent';
+    document.body.style.background = 'transparent';
+    document.body.style.overflow = 'hidden';
+    document.body.style.margin = '0';
+
+    const handleKeyDown = (event: KeyboardEvent) => {
+      if (event.key === 'Escape') {
+        cancelPick();
+      }
+    };
+    window.addEventListener('keydown', handleKeyDown);
+
+    return () => {
+      window.removeEventListener('keydown', handleKeyDown);
+      document.documentElement.style.background = '';
+      document.body.style.background = '';
+      document.body.style.overflow = '';
+      document.body.style.margin = '';
+    };
+  }, []);
+
+  return (
+    <div
+      className="fixed inset-0 cursor-crosshair select-none"
+      // Nearly transparent, fully transparent windows let clicks through on some platforms
+      style={{ background: 'rgba(0, 0, 0, 0.01)' }}
+      onClick={confirmPick}
+      onContextMenu={(event) => {
+        event.preventDefault();
+        cancelPick();
+      }}
+    />
+  );
+};
+
+export default ColorPickerPage;
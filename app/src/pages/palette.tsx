@@ -0,0 +1,165 @@
+import { useEffect, useRef, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { Icon } from "@/components/Common/Iconify/icons";
+import { isInTauri } from "@/lib/tauriHelper";
+import { cn } from "@/lib/utils";
+
+interface PaletteItem {
+  id: string;
+  kind: 'note' | 'command' | 'action';
+  title: string;
+  subtitle?: string | null;
+  shortcut?: string | null;
+}
+
+interface PaletteMatch {
+  item: PaletteItem;
+  score: number;
+  matchedIndices: number[];
+}
+
+const KIND_ICONS: Record<PaletteItem['kind'], string> = {
+  note: 'tabler:note',
+  command: 'tabler:bolt',
+  action: 'tabler:arrow-right',
+};
+
+const HighlightedTitle = ({ title, indices }: { title: string; indices: number[] }) => {
+  const marked = new Set(indices);
+  return (
+    <span>
+      {Array.from(title).map((char, index) => (
+        marked.has(index) ? <b key={index} className="text-primary">{char}</b> : <span key={index}>{char}</span>
+      ))}
+    </span>
+  );
+};
+
+const PalettePage = () => {
+  const { t } = useTranslation();
+  const [query, setQuery] = useState("");
+  const [matches, setMatches] = useState<PaletteMatch[]>([]);
+  const [selected, setSelected] = useState(0);
+  // Bumped when the window is shown, items may have changed since the last search
+  const [openCount, setOpenCount] = useState(0);
+  const inputRef = useRef<HTMLInputElement>(null);
+
+  useEffect(() => {
+    if (!isInTauri()) return;
+    let cancelled = false;
+    invoke<PaletteMatch[]>('search_palette', { query }).then(result => {
+      if (!cancelled) {
+        setMatches(result);
+        setSelected(0);
+      }
+    }).catch(error => console.error("❌ Palette search failed:", error));
+    return () => {
+      cancelled = true;
+    };
+  }, [query, openCount]);
+
+  useEffect(() => {
+    if (!isInTauri()) return;
+    document.title = "Command Palette";
+    document.body.style.overflow = 'hidden';
+
+    let unlisten: (() => void) | null = null;
+    let isMounted = true;
+    listen('palette-opened', () => {
+      setQuery("");
+      setOpenCount(count => count + 1);
+      inputRef.current?.focus();
+    }).then(fn => {
+      if (isMounted) {
+        unlisten = fn;
+      } else {
+        fn();
+      }
+    });
+
+    return () => {
+      isMounted = false;
+      unlisten?.();
+      document.body.style.overflow = '';
+    };
+  }, []);
+
+  const choose = async (match?: PaletteMatch) => {
+    if (!match) return;
+    try {
+      await invoke('select_palette_item', { id: match.item.id });
+    } catch (error) {
+      console.error("❌ Failed to select palette item:", error);
+    }
+  };
+
+  const handleKeyDown = (event: React.KeyboardEvent) => {
+    switch (event.key) {
+      case 'ArrowDown':
+        event.preventDefault();
+        setSelected(index => Math.min(index + 1, matches.length - 1));
+        break;
+      case 'ArrowUp':
+        event.preventDefault();
+        setSelected(index => Math.max(index - 1, 0));
+        break;
+      case 'Enter':
+        event.preventDefault();
+        choose(matches[selected]);
+        break;
+      case 'Escape':
+        event.preventDefault();
+        invoke('hide_palette_window').catch(() => {});
+        break;
+    }
+  };
+
+  return (
+    <div className="w-full h-screen flex flex-col bg-background rounded-lg overflow-hidden" onKeyDown={handleKeyDown}>
+      <div className="flex items-center gap-2 px-3 py-2 border-b border-border">
+        <Icon icon="tabler:search" className="text-default-500" />
+        <input
+          ref={inputRef}
+          autoFocus
+          value={query}
+          onChange={event => setQuery(event.target.value)}
+          placeholder={t('palette.search-placeholder')}
+          className="flex-1 bg-transparent outline-none text-sm"
+        />
+      </div>
+      <div className="flex-1 overflow-y-auto py-1">
+        {matches.length === 0 && (
+          <div className="px-3 py-4 text-sm text-default-500 text-center">{t('no-data')}</div>
+        )}
+        {matches.map((match, index) => (
+          <div
+            key={match.item.id}
+            onMouseEnter={() => setSelected(index)}
+            onClick={() => choose(match)}
+            className={cn(
+              "flex items-center gap-2 px-3 py-2 cursor-pointer text-sm",
+              index === selected && "bg-hover"
+            )}
+          >
+            <Icon icon={KIND_ICONS[match.item.kind]} className="text-default-500 shrink-0" />
+            <div className="flex-1 min-w-0">
+              <div className="truncate">
+                <HighlightedTitle title={match.item.title} indices={match.matchedIndices} />
+              </div>
+              {match.item.subtitle && (
+                <div className="truncate text-xs text-default-500">{match.item.subtitle}</div>
+              )}
+            </div>
+            {match.item.shortcut && (
+              <kbd className="text-xs text-default-500">{match.item.shortcut}</kbd>
+            )}
+          </div>
+        ))}
+      </div>
+    </div>
+  );
+};
+
+export default PalettePage;
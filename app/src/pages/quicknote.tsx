@@ -5,6 +5,8 @@ import { BlinkoStore } from "@/store/blinkoStore";
 import { useEffect, useRef } from "react";
 import { isInTauri } from "@/lib/tauriHelper";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { eventBus } from "@/lib/event";
 import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
 import { useTranslation } from "react-i18next";
 
@@ -130,6 +132,48 @@ const QuickNotePage = observer(() => {
     };
   }, []);
 
+  // Text sent from other apps, e.g. a share or the control socket, waits in Rust until the editor is up
+  useEffect(() => {
+    if (!isInTauri()) return;
+
+    let isMounted = true;
+    let unlisten: (() => void) | null = null;
+
+    const waitForEditor = async () => {
+      for (let attempt = 0; attempt < 50; attempt++) {
+        if (document.querySelector('#quicknote-editor [contenteditable="true"], #quicknote-editor textarea')) {
+          return true;
+        }
+        await new Promise(resolve => setTimeout(resolve, 100));
+      }
+      return false;
+    };
+
+    const insertPendingText = async () => {
+      if (!(await waitForEditor()) || !isMounted) return;
+      try {
+        const texts = await invoke<string[]>('take_pending_quicknote_text');
+        texts.forEach(text => eventBus.emit('editor:insert', text));
+      } catch (error) {
+        console.error('Failed to take pending quicknote text:', error);
+      }
+    };
+
+    listen('quicknote-text-pending', insertPendingText).then(fn => {
+      if (isMounted) {
+        unlisten = fn;
+      } else {
+        fn();
+      }
+    });
+    insertPendingText();
+
+    return () => {
+      isMounted = false;
+      unlisten?.();
+    };
+  }, []);
+
   const handleSend = async () => {
     // Call toggle method to close window after sending note - Tauri only
     if (isInTauri()) {
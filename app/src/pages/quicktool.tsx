@@ -18,6 +18,15 @@ interface TextSelectionEvent {
   modifier: string;
 }
 
+interface PickedColor {
+  hex: string;
+  r: number;
+  g: number;
+  b: number;
+  x: number;
+  y: number;
+}
+
 const QuickToolPage = observer(() => {
   console.log("🚀 QuickToolPage component instantiated");
 
@@ -213,6 +222,34 @@ const QuickToolPage = observer(() => {
   }, []);
 
 
+  // Copy colors sampled by the screen color picker
+  useEffect(() => {
+    if (!isInTauri()) return;
+
+    let isMounted = true;
+    let unlisten: (() => void) | null = null;
+
+    listen<PickedColor>('color-picked', async (event) => {
+      try {
+        await navigator.clipboard.writeText(event.payload.hex);
+        console.log("🎨 Picked color copied:", event.payload.hex);
+      } catch (error) {
+        console.error("❌ Failed to copy picked color:", error);
+      }
+    }).then((fn) => {
+      if (isMounted) {
+        unlisten = fn;
+      } else {
+        fn();
+      }
+    });
+
+    return () => {
+      isMounted = false;
+      unlisten?.();
+    };
+  }, []);
+
   // For now, just show the toolbar when the window is opened
   // The selected text will be handled differently or we can show a placeholder
   console.log("🔍 QuickToolPage render check - isInTauri:", isInTauri());
@@ -1,8 +1,12 @@
 package com.plugin.blinko
 
+import android.Manifest
 import android.app.Activity
+import app.tauri.PermissionState
 import app.tauri.annotation.Command
 import app.tauri.annotation.InvokeArg
+import app.tauri.annotation.Permission
+import app.tauri.annotation.PermissionCallback
 import app.tauri.annotation.TauriPlugin
 import app.tauri.plugin.JSObject
 import app.tauri.plugin.Plugin
@@ -13,10 +17,20 @@ class SetColorArgs {
   lateinit var hex: String
 }
 
+@InvokeArg
+class StartVoiceRecordingArgs {
+  var maxSeconds: Int? = null
+}
 
-@TauriPlugin
+
+@TauriPlugin(
+    permissions = [
+        Permission(strings = [Manifest.permission.RECORD_AUDIO], alias = "microphone")
+    ]
+)
 class BlinkoPlugin(private val activity: Activity): Plugin(activity) {
     private val implementation = Blinko()
+    private val voiceRecorder = VoiceRecorder()
 
     @Command
     fun setcolor(invoke: Invoke) {
@@ -30,4 +44,65 @@ class BlinkoPlugin(private val activity: Activity): Plugin(activity) {
         implementation.openAppSettings(activity)
         invoke.resolve()
     }
+
+    @Command
+    fun startVoiceRecording(invoke: Invoke) {
+        if (getPermissionState("microphone") != PermissionState.GRANTED) {
+            requestPermissionForAlias("microphone", invoke, "microphonePermissionCallback")
+            return
+        }
+        beginVoiceRecording(invoke)
+    }
+
+    @PermissionCallback
+    private fun microphonePermissionCallback(invoke: Invoke) {
+        if (getPermissionState("microphone") == PermissionState.GRANTED) {
+            beginVoiceRecording(invoke)
+        } else {
+            invoke.reject("Microphone permission was denied")
+        }
+    }
+
+    private fun beginVoiceRecording(invoke: Invoke) {
+        val args = invoke.parseArgs(StartVoiceRecordingArgs::class.java)
+        try {
+            voiceRecorder.start(activity, args.maxSeconds)
+            invoke.resolve()
+        } catch (e: Exception) {
+            invoke.reject(e.message ?: "Failed to start recording")
+        }
+    }
+
+    @Command
+    fun stopVoiceRecording(invoke: Invoke) {
+        try {
+            val (file, duration) = voiceRecorder.stop()
+            val result = JSObject()
+            result.put("path", file.absolutePath)
+            result.put("name", file.name)
+            result.put("size", file.length())
+            result.put("mimeType", "audio/wav")
+            result.put("durationSeconds", duration.toDouble())
+            result.put("interruptions", voiceRecorder.interruptions)
+            invoke.resolve(result)
+        } catch (e: Exception) {
+            invoke.reject(e.message ?: "Failed to stop recording")
+        }
+    }
+
+    @Command
+    fun voiceRecordingStatus(invoke: Invoke) {
+        val result = JSObject()
+        result.put("recording", voiceRecorder.isRecording)
+        result.put("level", voiceRecorder.currentLevel.toDouble())
+        result.put("elapsedSeconds", voiceRecorder.elapsedSeconds.toDouble())
+        result.put("state", when {
+            voiceRecorder.isInterrupted -> "interrupted"
+            voiceRecorder.isRecording -> "recording"
+            else -> "stopped"
+        })
+        result.put("route", voiceRecorder.route)
+        result.put("interruptions", voiceRecorder.interruptions)
+        invoke.resolve(result)
+    }
 }
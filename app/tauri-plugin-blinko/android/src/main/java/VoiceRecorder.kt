@@ -0,0 +1,307 @@
+package com.plugin.blinko
+
+import android.annotation.SuppressLint
+import android.content.Context
+import android.content.Intent
+import android.media.AudioAttributes
+import android.media.AudioDeviceInfo
+import android.media.AudioFocusRequest
+import android.media.AudioFormat
+import android.media.AudioManager
+import android.media.AudioRecord
+import android.media.MediaRecorder
+import android.os.Build
+import android.os.Handler
+import android.os.Looper
+import android.os.SystemClock
+import android.util.Log
+import androidx.core.content.ContextCompat
+import java.io.File
+import java.io.RandomAccessFile
+import java.text.SimpleDateFormat
+import java.util.Date
+import java.util.Locale
+import kotlin.math.abs
+import kotlin.math.max
+
+/**
+ * Records 16 kHz mono 16-bit PCM straight into a WAV file,
+ * the format the desktop recorder produces and the server transcribes.
+ *
+ * Calls and other apps taking audio focus pause the recording instead of filling it with
+ * silence, a foreground service keeps it going in the background, and a connected
+ * Bluetooth headset microphone is preferred over the built-in one.
+ */
+class VoiceRecorder {
+    companion object {
+        const val SAMPLE_RATE = 16000
+        private const val WAV_HEADER_SIZE = 44
+    }
+
+    private var audioRecord: AudioRecord? = null
+    private var audioManager: AudioManager? = null
+    private var focusRequest: AudioFocusRequest? = null
+    private var appContext: Context? = null
+    private var thread: Thread? = null
+    private var file: File? = null
+    private var startedAt = 0L
+    private var maxMillis = 0L
+    private var usingBluetooth = false
+
+    @Volatile private var running = false
+    @Volatile private var focusLost = false
+    @Volatile private var interrupted = false
+    @Volatile private var level = 0f
+    @Volatile private var bytesWritten = 0L
+    @Volatile var interruptions = 0
+        private set
+    @Volatile var route: String? = null
+        private set
+
+    private val focusListener = AudioManager.OnAudioFocusChangeListener { change ->
+        focusLost = when (change) {
+            AudioManager.AUDIOFOCUS_LOSS, AudioManager.AUDIOFOCUS_LOSS_TRANSIENT -> true
+            AudioManager.AUDIOFOCUS_GAIN -> false
+            else -> focusLost
+        }
+    }
+
+    val isRecording: Boolean
+        get() = running
+
+    val isInterrupted: Boolean
+        get() = running && interrupted
+
+    val currentLevel: Float
+        get() = if (running && !interrupted) level else 0f
+
+    /** Seconds of audio in the file, time spent interrupted does not count */
+    val elapsedSeconds: Float
+        get() = bytesWritten / 2f / SAMPLE_RATE
+
+    /** Caller must hold the RECORD_AUDIO permission */
+    @SuppressLint("MissingPermission")
+    fun start(context: Context, maxSeconds: Int?) {
+        if (running) throw IllegalStateException("A voice note is already being recorded")
+
+        val bufferSize = max(
+            AudioRecord.getMinBufferSize(SAMPLE_RATE, AudioFormat.CHANNEL_IN_MONO, AudioFormat.ENCODING_PCM_16BIT),
+            SAMPLE_RATE / 5 * 2
+        )
+        val record = AudioRecord(
+            MediaRecorder.AudioSource.VOICE_RECOGNITION,
+            SAMPLE_RATE,
+            AudioFormat.CHANNEL_IN_MONO,
+            AudioFormat.ENCODING_PCM_16BIT,
+            bufferSize
+        )
+        if (record.state != AudioRecord.STATE_INITIALIZED) {
+            record.release()
+            throw IllegalStateException("Microphone is not available")
+        }
+
+        val stamp = SimpleDateFormat("yyyyMMdd-HHmmss", Locale.US).format(Date())
+        val output = File(context.cacheDir, "audio-note-$stamp.wav")
+        val writer = RandomAccessFile(output, "rw")
+        writer.setLength(0)
+        writer.write(ByteArray(WAV_HEADER_SIZE))
+
+        val manager = context.getSystemService(Context.AUDIO_SERVICE) as AudioManager
+        appContext = context.applicationContext
+        audioManager = manager
+        audioRecord = record
+        file = output
+        bytesWritten = 0
+        level = 0f
+        interruptions = 0
+        focusLost = false
+        interrupted = false
+        maxMillis = (maxSeconds ?: 0) * 1000L
+
+        requestFocus(manager)
+        routeToBluetoothHeadset(manager, record)
+        watchRouting(record)
+        ContextCompat.startForegroundService(context, Intent(context, VoiceRecordingService::class.java))
+
+        startedAt = SystemClock.elapsedRealtime()
+        running = true
+        record.startRecording()
+
+        thread = Thread {
+            val buffer = ShortArray(bufferSize / 2)
+            val bytes = ByteArray(buffer.size * 2)
+            try {
+                while (running) {
+                    val read = record.read(buffer, 0, buffer.size)
+                    if (read <= 0) continue
+
+                    // During a call the microphone delivers silence, keep it out of the note
+                    val nowInterrupted = focusLost || isInCall(manager)
+                    if (nowInterrupted != interrupted) {
+                        interrupted = nowInterrupted
+                        if (nowInterrupted) {
+                            interruptions += 1
+                            Log.i("Blinko", "Voice recording interrupted")
+                        } else {
+                            Log.i("Blinko", "Voice recording resumed")
+                        }
+                    }
+                    if (interrupted) continue
+
+                    var peak = 0
+                    for (i in 0 until read) {
+                        val sample = buffer[i].toInt()
+                        peak = max(peak, abs(sample))
+                        bytes[i * 2] = (sample and 0xff).toByte()
+                        bytes[i * 2 + 1] = (sample shr 8 and 0xff).toByte()
+                    }
+                    writer.write(bytes, 0, read * 2)
+                    bytesWritten += read * 2
+                    level = peak / Short.MAX_VALUE.toFloat()
+                    if (maxMillis > 0 && bytesWritten / 2 * 1000L / SAMPLE_RATE >= maxMillis) {
+                        running = false
+                    }
+                }
+            } catch (e: Exception) {
+                Log.e("Blinko", "Voice recording failed: ${e.message}")
+                running = false
+            } finally {
+                writeWavHeader(writer, bytesWritten)
+                writer.close()
+            }
+        }.apply { start() }
+    }
+
+    /** Finish the recording (or collect one that hit its time limit) and return the WAV file */
+    fun stop(): Pair<File, Float> {
+        val output = file ?: throw IllegalStateException("No voice note is being recorded")
+        running = false
+        thread?.join()
+        audioRecord?.let {
+            it.stop()
+            it.release()
+        }
+        audioManager?.let { manager ->
+            releaseFocus(manager)
+            releaseBluetoothHeadset(manager)
+        }
+        appContext?.let { it.stopService(Intent(it, VoiceRecordingService::class.java)) }
+        audioRecord = null
+        audioManager = null
+        appContext = null
+        thread = null
+        file = null
+        interrupted = false
+        return Pair(output, bytesWritten / 2f / SAMPLE_RATE)
+    }
+
+    private fun isInCall(manager: AudioManager): Boolean {
+        return manager.mode == AudioManager.MODE_IN_CALL ||
+            (manager.mode == AudioManager.MODE_IN_COMMUNICATION && !usingBluetooth)
+    }
+
+    private fun requestFocus(manager: AudioManager) {
+        if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.O) {
+            val request = AudioFocusRequest.Builder(AudioManager.AUDIOFOCUS_GAIN_TRANSIENT)
+                .setAudioAttributes(
+                    AudioAttributes.Builder()
+                        .setUsage(AudioAttributes.USAGE_ASSISTANT)
+                        .setContentType(AudioAttributes.CONTENT_TYPE_SPEECH)
+                        .build()
+                )
+                .setOnAudioFocusChangeListener(focusListener)
+                .build()
+            focusRequest = request
+            manager.requestAudioFocus(request)
+        } else {
+            @Suppress("DEPRECATION")
+            manager.requestAudioFocus(focusListener, AudioManager.STREAM_VOICE_CALL, AudioManager.AUDIOFOCUS_GAIN_TRANSIENT)
+        }
+    }
+
+    private fun releaseFocus(manager: AudioManager) {
+        if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.O) {
+            focusRequest?.let { manager.abandonAudioFocusRequest(it) }
+            focusRequest = null
+        } else {
+            @Suppress("DEPRECATION")
+            manager.abandonAudioFocus(focusListener)
+        }
+    }
+
+    /** Record through a connected Bluetooth headset, which needs the SCO link up */
+    private fun routeToBluetoothHeadset(manager: AudioManager, record: AudioRecord) {
+        usingBluetooth = false
+        if (Build.VERSION.SDK_INT < Build.VERSION_CODES.M) return
+        val headset = manager.getDevices(AudioManager.GET_DEVICES_INPUTS)
+            .firstOrNull { it.type == AudioDeviceInfo.TYPE_BLUETOOTH_SCO } ?: return
+        try {
+            if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.S) {
+                val output = manager.availableCommunicationDevices.firstOrNull { it.type == AudioDeviceInfo.TYPE_BLUETOOTH_SCO }
+                if (output != null) manager.setCommunicationDevice(output)
+            } else {
+                @Suppress("DEPRECATION")
+                manager.startBluetoothSco()
+                @Suppress("DEPRECATION")
+                manager.isBluetoothScoOn = true
+            }
+            record.preferredDevice = headset
+            usingBluetooth = true
+            Log.i("Blinko", "Recording from Bluetooth headset ${headset.productName}")
+        } catch (e: Exception) {
+            Log.w("Blinko", "Bluetooth headset microphone unavailable: ${e.message}")
+        }
+    }
+
+    private fun releaseBluetoothHeadset(manager: AudioManager) {
+        if (!usingBluetooth) return
+        usingBluetooth = false
+        if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.S) {
+            manager.clearCommunicationDevice()
+        } else {
+            @Suppress("DEPRECATION")
+            manager.isBluetoothScoOn = false
+            @Suppress("DEPRECATION")
+            manager.stopBluetoothSco()
+        }
+    }
+
+    private fun describe(device: AudioDeviceInfo?): String? {
+        if (device == null) return null
+        return when (device.type) {
+            AudioDeviceInfo.TYPE_BUILTIN_MIC -> "Built-in microphone"
+            AudioDeviceInfo.TYPE_BLUETOOTH_SCO -> "Bluetooth: ${device.productName}"
+            AudioDeviceInfo.TYPE_WIRED_HEADSET -> "Wired headset"
+            AudioDeviceInfo.TYPE_USB_DEVICE, AudioDeviceInfo.TYPE_USB_HEADSET -> "USB: ${device.productName}"
+            else -> device.productName?.toString()
+        }
+    }
+
+    private fun watchRouting(record: AudioRecord) {
+        route = null
+        if (Build.VERSION.SDK_INT < Build.VERSION_CODES.N) return
+        record.addOnRoutingChangedListener({ routing ->
+            route = describe(routing.routedDevice)
+            Log.i("Blinko", "Voice recording input changed to $route")
+        }, Handler(Looper.getMainLooper()))
+    }
+
+    private fun writeWavHeader(writer: RandomAccessFile, dataSize: Long) {
+        val header = java.nio.ByteBuffer.allocate(WAV_HEADER_SIZE).order(java.nio.ByteOrder.LITTLE_ENDIAN)
+        header.put("RIFF".toByteArray())
+        header.putInt((dataSize + WAV_HEADER_SIZE - 8).toInt())
+        header.put("WAVE".toByteArray())
+        header.put("fmt ".toByteArray())
+        header.putInt(16)
+        header.putShort(1) // PCM
+        header.putShort(1) // mono
+        header.putInt(SAMPLE_RATE)
+        header.putInt(SAMPLE_RATE * 2)
+        header.putShort(2)
+        header.putShort(16)
+        header.put("data".toByteArray())
+        header.putInt(dataSize.toInt())
+        writer.seek(0)
+        writer.write(header.array())
+    }
+}
@@ -0,0 +1,76 @@
+package com.plugin.blinko
+
+import android.app.Notification
+import android.app.NotificationChannel
+import android.app.NotificationManager
+import android.app.Service
+import android.content.Intent
+import android.content.pm.ServiceInfo
+import android.os.Build
+import android.os.IBinder
+
+/**
+ * Foreground service that keeps microphone access while a voice note
+ * is recorded and the app goes to the background
+ */
+class VoiceRecordingService : Service() {
+    companion object {
+        private const val CHANNEL_ID = "blinko_voice_recording"
+        private const val NOTIFICATION_ID = 4426
+    }
+
+    override fun onBind(intent: Intent?): IBinder? = null
+
+    override fun onStartCommand(intent: Intent?, flags: Int, startId: Int): Int {
+        val notification = buildNotification()
+        if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.R) {
+            startForeground(NOTIFICATION_ID, notification, ServiceInfo.FOREGROUND_SERVICE_TYPE_MICROPHONE)
+        } else {
+            startForeground(NOTIFICATION_ID, notification)
+        }
+        return START_NOT_STICKY
+    }
+
+    override fun onDestroy() {
+        if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.N) {
+            stopForeground(STOP_FOREGROUND_REMOVE)
+        } else {
+            @Suppress("DEPRECATION")
+            stopForeground(true)
+        }
+        super.onDestroy()
+    }
+
+    private fun buildNotification(): Notification {
+        val launchIntent = packageManager.getLaunchIntentForPackage(packageName)
+        val contentIntent = launchIntent?.let {
+            android.app.PendingIntent.getActivity(
+                this, 0, it,
+                android.app.PendingIntent.FLAG_UPDATE_CURRENT or android.app.PendingIntent.FLAG_IMMUTABLE
+            )
+        }
+
+        if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.O) {
+            val manager = getSystemService(NotificationManager::class.java)
+            if (manager.getNotificationChannel(CHANNEL_ID) == null) {
+                manager.createNotificationChannel(
+                    NotificationChannel(CHANNEL_ID, "Voice notes", NotificationManager.IMPORTANCE_LOW)
+                )
+            }
+            return Notification.Builder(this, CHANNEL_ID)
+                .setContentTitle("Recording voice note")
+                .setSmallIcon(android.R.drawable.ic_btn_speak_now)
+                .setContentIntent(contentIntent)
+                .setOngoing(true)
+                .build()
+        }
+
+        @Suppress("DEPRECATION")
+        return Notification.Builder(this)
+            .setContentTitle("Recording voice note")
+            .setSmallIcon(android.R.drawable.ic_btn_speak_now)
+            .setContentIntent(contentIntent)
+            .setOngoing(true)
+            .build()
+    }
+}
@@ -1,4 +1,4 @@
-const COMMANDS: &[&str] = &["setcolor"];
+const COMMANDS: &[&str] = &["setcolor", "start_voice_recording", "stop_voice_recording", "voice_recording_status"];
 
 fn main() {
   tauri_plugin::Builder::new(COMMANDS)
@@ -11,4 +11,34 @@ export async function setStatusBarColor(hexColor: string): Promise<null> {
 
 export async function openAppSettings(): Promise<void> {
   await invoke('plugin:blinko|open_app_settings')
-}
\ No newline at end of file
+}
+
+export interface VoiceRecording {
+  path: string
+  name: string
+  size: number
+  mimeType: string
+  durationSeconds: number
+}
+
+export interface VoiceRecordingStatus {
+  recording: boolean
+  level: number
+  elapsedSeconds: number
+}
+
+export async function startVoiceRecording(maxSeconds?: number): Promise<void> {
+  await invoke('plugin:blinko|start_voice_recording', {
+    payload: {
+      maxSeconds,
+    },
+  })
+}
+
+export async function stopVoiceRecording(): Promise<VoiceRecording> {
+  return await invoke<VoiceRecording>('plugin:blinko|stop_voice_recording')
+}
+
+export async function getVoiceRecordingStatus(): Promise<VoiceRecordingStatus> {
+  return await invoke<VoiceRecordingStatus>('plugin:blinko|voice_recording_status')
+}
@@ -0,0 +1,148 @@
+import AVFoundation
+import Tauri
+import UIKit
+import WebKit
+
+class SetColorArgs: Decodable {
+  let hex: String
+}
+
+class StartVoiceRecordingArgs: Decodable {
+  let maxSeconds: Int?
+}
+
+/// Home screen quick actions, same ids as the Android launcher shortcuts
+enum QuickAction: String, CaseIterable {
+  case quickNote = "quick_note"
+  case voiceRecording = "voice_recording"
+
+  var title: String {
+    switch self {
+    case .quickNote: return "New Note"
+    case .voiceRecording: return "Voice Note"
+    }
+  }
+
+  var icon: UIApplicationShortcutIcon {
+    switch self {
+    case .quickNote: return UIApplicationShortcutIcon(systemImageName: "square.and.pencil")
+    case .voiceRecording: return UIApplicationShortcutIcon(systemImageName: "mic")
+    }
+  }
+}
+
+class BlinkoPlugin: Plugin {
+  private static weak var current: BlinkoPlugin?
+  private var webView: WKWebView?
+  private let voiceRecorder = VoiceRecorder()
+
+  @objc public override func load(webview: WKWebView) {
+    self.webView = webview
+    BlinkoPlugin.current = self
+    DispatchQueue.main.async {
+      UIApplication.shared.shortcutItems = QuickAction.allCases.map {
+        UIApplicationShortcutItem(type: $0.rawValue, localizedTitle: $0.title, localizedSubtitle: nil, icon: $0.icon, userInfo: nil)
+      }
+      BlinkoPlugin.installQuickActionHandler()
+    }
+  }
+
+  /// The app delegate is owned by the runtime, so the quick action callback is added to it here.
+  /// iOS calls it after launch as well, which covers cold starts from a quick action.
+  private static func installQuickActionHandler() {
+    guard let delegate = UIApplication.shared.delegate else { return }
+    let selector = #selector(UIApplicationDelegate.application(_:performActionFor:completionHandler:))
+    let handler: @convention(block) (AnyObject, UIApplication, UIApplicationShortcutItem, @escaping @convention(block) (Bool) -> Void) -> Void = {
+      _, _, item, completion in
+      guard let action = QuickAction(rawValue: item.type) else {
+        completion(false)
+        return
+      }
+      BlinkoPlugin.current?.inject(action: action)
+      completion(true)
+    }
+    class_addMethod(type(of: delegate), selector, imp_implementationWithBlock(handler), "v@:@@@?")
+  }
+
+  /// Hand the action to the frontend through the key it already polls for Android shortcuts,
+  /// after a short delay so the webview has loaded on a cold start
+  private func inject(action: QuickAction) {
+    DispatchQueue.main.asyncAfter(deadline: .now() + 1.5) { [weak self] in
+      self?.webView?.evaluateJavaScript(
+        "window.localStorage.setItem('android_shortcut_action', '\(action.rawValue)');",
+        completionHandler: nil)
+    }
+  }
+
+  @objc public func setcolor(_ invoke: Invoke) throws {
+    // The status bar follows the webview content on iOS, nothing to apply natively
+    _ = try invoke.parseArgs(SetColorArgs.self)
+    invoke.resolve()
+  }
+
+  @objc public func openAppSettings(_ invoke: Invoke) {
+    DispatchQueue.main.async {
+      if let url = URL(string: UIApplication.openSettingsURLString) {
+        UIApplication.shared.open(url)
+      }
+    }
+    invoke.resolve()
+  }
+
+  @objc public func startVoiceRecording(_ invoke: Invoke) throws {
+    let args = try invoke.parseArgs(StartVoiceRecordingArgs.self)
+    AVAudioSession.sharedInstance().requestRecordPermission { granted in
+      DispatchQueue.main.async {
+        guard granted else {
+          invoke.reject("Microphone permission was denied")
+          return
+        }
+        do {
+          try self.voiceRecorder.start(maxSeconds: args.maxSeconds)
+          invoke.resolve()
+        } catch {
+          invoke.reject(error.localizedDescription)
+        }
+      }
+    }
+  }
+
+  @objc public func stopVoiceRecording(_ invoke: Invoke) {
+    DispatchQueue.main.async {
+      do {
+        let (url, duration) = try self.voiceRecorder.stop()
+        let size = (try? FileManager.default.attributesOfItem(atPath: url.path)[.size] as? NSNumber)?.intValue ?? 0
+        invoke.resolve([
+          "path": url.path,
+          "name": url.lastPathComponent,
+          "size": size,
+          "mimeType": "audio/wav",
+          "durationSeconds": duration,
+          "interruptions": self.voiceRecorder.interruptions,
+        ])
+      } catch {
+        invoke.reject(error.localizedDescription)
+      }
+    }
+  }
+
+  @objc public func voiceRecordingStatus(_ invoke: Invoke) {
+    let recording = voiceRecorder.isRecording
+    var status: [String: Any] = [
+      "recording": recording,
+      "level": recording && !voiceRecorder.interrupted ? voiceRecorder.level : 0,
+      "elapsedSeconds": voiceRecorder.elapsedSeconds,
+      "state": !recording ? "stopped" : voiceRecorder.interrupted ? "interrupted" : "recording",
+      "interruptions": voiceRecorder.interruptions,
+    ]
+    if let route = voiceRecorder.route {
+      status["route"] = route
+    }
+    invoke.resolve(status)
+  }
+}
+
+@_cdecl("init_plugin_blinko")
+func initPlugin() -> Plugin {
+  return BlinkoPlugin()
+}
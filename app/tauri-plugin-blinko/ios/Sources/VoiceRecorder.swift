@@ -0,0 +1,264 @@
+import AVFoundation
+
+enum VoiceRecorderError: LocalizedError {
+  case alreadyRecording
+  case notRecording
+  case unsupportedInput
+
+  var errorDescription: String? {
+    switch self {
+    case .alreadyRecording: return "A voice note is already being recorded"
+    case .notRecording: return "No voice note is being recorded"
+    case .unsupportedInput: return "Microphone is not available"
+    }
+  }
+}
+
+/// Records 16 kHz mono 16-bit PCM WAV through AVAudioEngine, the format the
+/// desktop recorder produces and the server transcribes.
+///
+/// Calls and Siri interrupt the session, the recording then pauses and picks up
+/// again when the system ends the interruption. Route changes (a Bluetooth headset
+/// connecting or going away) rebuild the input tap for the new microphone format.
+/// Recording continues in the background through the `audio` background mode.
+class VoiceRecorder {
+  static let sampleRate = 16000.0
+
+  private let engine = AVAudioEngine()
+  private let targetFormat = AVAudioFormat(commonFormat: .pcmFormatInt16, sampleRate: VoiceRecorder.sampleRate, channels: 1, interleaved: true)!
+  private var file: AVAudioFile?
+  private var converter: AVAudioConverter?
+  private var maxSeconds: Double = 0
+  private var framesWritten: AVAudioFramePosition = 0
+  private var observers: [NSObjectProtocol] = []
+  private let lock = NSLock()
+  private(set) var level: Float = 0
+  private(set) var interrupted = false
+  private(set) var interruptions = 0
+  private(set) var route: String?
+
+  var isRecording: Bool {
+    lock.lock()
+    defer { lock.unlock() }
+    return file != nil && (engine.isRunning || interrupted)
+  }
+
+  /// Seconds of audio in the file, time spent interrupted does not count
+  var elapsedSeconds: Double {
+    lock.lock()
+    defer { lock.unlock() }
+    return Double(framesWritten) / VoiceRecorder.sampleRate
+  }
+
+  func start(maxSeconds: Int?) throws {
+    guard file == nil else { throw VoiceRecorderError.alreadyRecording }
+
+    let session = AVAudioSession.sharedInstance()
+    try session.setCategory(.playAndRecord, mode: .measurement, options: [.defaultToSpeaker, .allowBluetooth])
+    try session.setActive(true)
+    preferBluetoothHeadset(session)
+
+    let formatter = DateFormatter()
+    formatter.dateFormat = "yyyyMMdd-HHmmss"
+    let url = FileManager.default.temporaryDirectory.appendingPathComponent("audio-note-\(formatter.string(from: Date())).wav")
+    let settings: [String: Any] = [
+      AVFormatIDKey: kAudioFormatLinearPCM,
+      AVSampleRateKey: VoiceRecorder.sampleRate,
+      AVNumberOfChannelsKey: 1,
+      AVLinearPCMBitDepthKey: 16,
+      AVLinearPCMIsFloatKey: false,
+      AVLinearPCMIsBigEndianKey: false,
+    ]
+    let output = try AVAudioFile(forWriting: url, settings: settings, commonFormat: .pcmFormatInt16, interleaved: true)
+
+    lock.lock()
+    self.file = output
+    self.framesWritten = 0
+    self.level = 0
+    self.interrupted = false
+    self.interruptions = 0
+    self.maxSeconds = Double(maxSeconds ?? 0)
+    lock.unlock()
+
+    do {
+      try startEngine()
+    } catch {
+      lock.lock()
+      self.file = nil
+      lock.unlock()
+      throw error
+    }
+    observeSession()
+  }
+
+  /// Tap the current input in its own format, converting to 16 kHz mono as buffers arrive
+  private func startEngine() throws {
+    let input = engine.inputNode
+    input.removeTap(onBus: 0)
+    let inputFormat = input.outputFormat(forBus: 0)
+    guard inputFormat.sampleRate > 0, let converter = AVAudioConverter(from: inputFormat, to: targetFormat) else {
+      throw VoiceRecorderError.unsupportedInput
+    }
+    lock.lock()
+    self.converter = converter
+    lock.unlock()
+
+    input.installTap(onBus: 0, bufferSize: 4096, format: inputFormat) { [weak self] buffer, _ in
+      self?.append(buffer)
+    }
+    engine.prepare()
+    try engine.start()
+    route = AVAudioSession.sharedInstance().currentRoute.inputs.first?.portName
+  }
+
+  private func preferBluetoothHeadset(_ session: AVAudioSession) {
+    guard let headset = session.availableInputs?.first(where: { $0.portType == .bluetoothHFP }) else { return }
+    do {
+      try session.setPreferredInput(headset)
+    } catch {
+      NSLog("Blinko: Bluetooth headset microphone unavailable: \(error.localizedDescription)")
+    }
+  }
+
+  private func observeSession() {
+    let center = NotificationCenter.default
+    let session = AVAudioSession.sharedInstance()
+    observers = [
+      center.addObserver(forName: AVAudioSession.interruptionNotification, object: session, queue: .main) { [weak self] note in
+        self?.handleInterruption(note)
+      },
+      center.addObserver(forName: AVAudioSession.routeChangeNotification, object: session, queue: .main) { [weak self] note in
+        self?.handleRouteChange(note)
+      },
+      center.addObserver(forName: AVAudioSession.mediaServicesWereResetNotification, object: session, queue: .main) { [weak self] _ in
+        self?.resume()
+      },
+    ]
+  }
+
+  private func handleInterruption(_ note: Notification) {
+    guard let raw = note.userInfo?[AVAudioSessionInterruptionTypeKey] as? UInt,
+      let type = AVAudioSession.InterruptionType(rawValue: raw)
+    else { return }
+
+    switch type {
+    case .began:
+      // The system has already stopped the engine, remember why the file stopped growing
+      lock.lock()
+      if file != nil && !interrupted {
+        interrupted = true
+        interruptions += 1
+        level = 0
+      }
+      lock.unlock()
+      NSLog("Blinko: voice recording interrupted")
+    case .ended:
+      resume()
+    @unknown default:
+      break
+    }
+  }
+
+  private func handleRouteChange(_ note: Notification) {
+    guard let raw = note.userInfo?[AVAudioSessionRouteChangeReasonKey] as? UInt,
+      let reason = AVAudioSession.RouteChangeReason(rawValue: raw)
+    else { return }
+
+    switch reason {
+    case .newDeviceAvailable, .oldDeviceUnavailable, .override, .routeConfigurationChange:
+      guard file != nil, !interrupted else { return }
+      // A different microphone comes with a different format, the tap has to be rebuilt
+      engine.stop()
+      if reason == .newDeviceAvailable {
+        preferBluetoothHeadset(AVAudioSession.sharedInstance())
+      }
+      do {
+        try startEngine()
+        NSLog("Blinko: voice recording input changed to \(route ?? "unknown")")
+      } catch {
+        lock.lock()
+        interrupted = true
+        interruptions += 1
+        lock.unlock()
+        NSLog("Blinko: voice recording lost its input: \(error.localizedDescription)")
+      }
+    default:
+      break
+    }
+  }
+
+  private func resume() {
+    guard file != nil else { return }
+    do {
+      try AVAudioSession.sharedInstance().setActive(true)
+      try startEngine()
+      lock.lock()
+      interrupted = false
+      lock.unlock()
+      NSLog("Blinko: voice recording resumed")
+    } catch {
+      NSLog("Blinko: failed to resume voice recording: \(error.localizedDescription)")
+    }
+  }
+
+  private func append(_ buffer: AVAudioPCMBuffer) {
+    lock.lock()
+    defer { lock.unlock() }
+    guard let file = file, let converter = converter, !interrupted else { return }
+
+    let capacity = AVAudioFrameCount(Double(buffer.frameLength) * targetFormat.sampleRate / buffer.format.sampleRate) + 1
+    guard let converted = AVAudioPCMBuffer(pcmFormat: targetFormat, frameCapacity: capacity) else { return }
+    var consumed = false
+    converter.convert(to: converted, error: nil) { _, status in
+      if consumed {
+        status.pointee = .noDataNow
+        return nil
+      }
+      consumed = true
+      status.pointee = .haveData
+      return buffer
+    }
+
+    if let samples = converted.int16ChannelData?[0] {
+      var peak: Int16 = 0
+      for i in 0..<Int(converted.frameLength) {
+        peak = max(peak, samples[i] == Int16.min ? Int16.max : abs(samples[i]))
+      }
+      level = Float(peak) / Float(Int16.max)
+    }
+    do {
+      try file.write(from: converted)
+      framesWritten += AVAudioFramePosition(converted.frameLength)
+    } catch {
+      NSLog("Blinko: failed to write voice note: \(error.localizedDescription)")
+    }
+
+    if maxSeconds > 0 && Double(framesWritten) / VoiceRecorder.sampleRate >= maxSeconds {
+      DispatchQueue.main.async { [weak self] in
+        self?.engine.inputNode.removeTap(onBus: 0)
+        self?.engine.stop()
+      }
+    }
+  }
+
+  /// Finish the recording (or collect one that hit its time limit) and return the WAV file
+  func stop() throws -> (URL, Double) {
+    observers.forEach { NotificationCenter.default.removeObserver($0) }
+    observers = []
+    engine.inputNode.removeTap(onBus: 0)
+    engine.stop()
+
+    lock.lock()
+    defer { lock.unlock() }
+    guard let file = file else { throw VoiceRecorderError.notRecording }
+    let url = file.url
+    let duration = Double(framesWritten) / VoiceRecorder.sampleRate
+    // Releasing the file finalizes the WAV header
+    self.file = nil
+    self.converter = nil
+    self.level = 0
+    self.interrupted = false
+    try? AVAudioSession.sharedInstance().setActive(false, options: .notifyOthersOnDeactivation)
+    return (url, duration)
+  }
+}
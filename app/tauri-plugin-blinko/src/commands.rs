@@ -17,4 +17,26 @@ pub(crate) async fn open_app_settings<R: Runtime>(
     app: AppHandle<R>,
 ) -> Result<()> {
     app.blinko().open_app_settings()
+}
+
+#[command]
+pub(crate) async fn start_voice_recording<R: Runtime>(
+    app: AppHandle<R>,
+    payload: StartVoiceRecordingRequest,
+) -> Result<()> {
+    app.blinko().start_voice_recording(payload)
+}
+
+#[command]
+pub(crate) async fn stop_voice_recording<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<VoiceRecording> {
+    app.blinko().stop_voice_recording()
+}
+
+#[command]
+pub(crate) async fn voice_recording_status<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<VoiceRecordingStatus> {
+    app.blinko().voice_recording_status()
 }
\ No newline at end of file
@@ -23,4 +23,17 @@ impl<R: Runtime> Blinko<R> {
     // Different platforms would need different implementations
     Ok(())
   }
+
+  // Desktop builds record through the app's own voice module
+  pub fn start_voice_recording(&self, _payload: StartVoiceRecordingRequest) -> crate::Result<()> {
+    Err(crate::Error::Unsupported("voice recording"))
+  }
+
+  pub fn stop_voice_recording(&self) -> crate::Result<VoiceRecording> {
+    Err(crate::Error::Unsupported("voice recording"))
+  }
+
+  pub fn voice_recording_status(&self) -> crate::Result<VoiceRecordingStatus> {
+    Err(crate::Error::Unsupported("voice recording"))
+  }
 }
@@ -37,7 +37,10 @@ pub fn init<R: Runtime>() -> TauriPlugin<R> {
   Builder::new("blinko")
     .invoke_handler(tauri::generate_handler![
       commands::setcolor,
-      commands::open_app_settings
+      commands::open_app_settings,
+      commands::start_voice_recording,
+      commands::stop_voice_recording,
+      commands::voice_recording_status
     ])
     .setup(|app, api| {
       #[cfg(mobile)]
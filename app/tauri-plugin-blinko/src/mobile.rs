@@ -38,4 +38,25 @@ impl<R: Runtime> Blinko<R> {
       .run_mobile_plugin("openAppSettings", ())
       .map_err(Into::into)
   }
+
+  pub fn start_voice_recording(&self, payload: StartVoiceRecordingRequest) -> crate::Result<()> {
+    self
+      .0
+      .run_mobile_plugin("startVoiceRecording", payload)
+      .map_err(Into::into)
+  }
+
+  pub fn stop_voice_recording(&self) -> crate::Result<VoiceRecording> {
+    self
+      .0
+      .run_mobile_plugin("stopVoiceRecording", ())
+      .map_err(Into::into)
+  }
+
+  pub fn voice_recording_status(&self) -> crate::Result<VoiceRecordingStatus> {
+    self
+      .0
+      .run_mobile_plugin("voiceRecordingStatus", ())
+      .map_err(Into::into)
+  }
 }
@@ -4,4 +4,50 @@ use serde::{Deserialize, Serialize};
 #[serde(rename_all = "camelCase")]
 pub struct SetColorRequest {
   pub hex: String,
-}
\ No newline at end of file
+}
+#[derive(Debug, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct StartVoiceRecordingRequest {
+  /// Recording stops by itself after this many seconds
+  pub max_seconds: Option<u32>,
+}
+
+/// 16 kHz mono WAV written by the native recorder
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VoiceRecording {
+  pub path: String,
+  pub name: String,
+  pub size: u64,
+  pub mime_type: String,
+  pub duration_seconds: f32,
+  /// Calls or other audio sessions that paused the recording
+  #[serde(default)]
+  pub interruptions: u32,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum VoiceRecordingState {
+  Recording,
+  /// Paused by a call or another app, resumes by itself when it ends
+  Interrupted,
+  #[default]
+  Stopped,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VoiceRecordingStatus {
+  pub recording: bool,
+  /// Input level from 0.0 to 1.0
+  pub level: f32,
+  /// Seconds of audio recorded so far, interruptions excluded
+  pub elapsed_seconds: f32,
+  #[serde(default)]
+  pub state: VoiceRecordingState,
+  /// Microphone in use, e.g. a Bluetooth headset
+  pub route: Option<String>,
+  #[serde(default)]
+  pub interruptions: u32,
+}